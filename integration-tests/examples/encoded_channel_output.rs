@@ -5,7 +5,7 @@ use integration_tests::{examples::download_file, paths::integration_tests_root};
 use smelter::{config::read_config, logger, state::ApiState};
 use smelter_core::{codecs::*, protocols::*, *};
 use smelter_render::{
-    InputId, OutputId, Resolution,
+    InputId, OutputColorSpace, OutputId, Resolution,
     error::ErrorStack,
     scene::{Component, InputStreamComponent},
 };
@@ -47,6 +47,7 @@ fn main() {
                     height: 720,
                 },
                 pixel_format: OutputPixelFormat::YUV420P,
+                color_space: OutputColorSpace::default(),
                 raw_options: vec![],
             })),
             audio: Some(AudioEncoderOptions::Opus(OpusEncoderOptions {
@@ -63,6 +64,7 @@ fn main() {
                 input_id: input_id.clone(),
             }),
             end_condition: PipelineOutputEndCondition::Never,
+            framerate: None,
         }),
         audio: Some(RegisterOutputAudioOptions {
             initial: AudioMixerConfig {
@@ -72,6 +74,7 @@ fn main() {
                 }],
             },
             mixing_strategy: AudioMixingStrategy::SumClip,
+            loudness_normalization: None,
             channels: AudioChannels::Stereo,
             end_condition: PipelineOutputEndCondition::Never,
         }),
@@ -89,7 +92,9 @@ fn main() {
         queue_options: QueueInputOptions {
             required: true,
             offset: Some(Duration::ZERO),
+            audio_delay: AudioDelay::None,
         },
+        failover: Default::default(),
     };
 
     Pipeline::register_input(&state.pipeline().unwrap(), input_id.clone(), input_options).unwrap();
@@ -18,7 +18,7 @@ use smelter_core::{
     *,
 };
 use smelter_render::{
-    Frame, FrameData, InputId, OutputId, Resolution,
+    ColorInfo, Frame, FrameData, InputId, OutputColorSpace, OutputId, Resolution,
     error::ErrorStack,
     scene::{Component, InputStreamComponent},
 };
@@ -66,6 +66,7 @@ fn main() {
                     height: 720,
                 },
                 pixel_format: OutputPixelFormat::YUV420P,
+                color_space: OutputColorSpace::default(),
                 raw_options: vec![],
             })),
             audio: None,
@@ -76,6 +77,7 @@ fn main() {
                 input_id: input_id.clone(),
             }),
             end_condition: PipelineOutputEndCondition::Never,
+            framerate: None,
         }),
         audio: None, // TODO: add audio example
     };
@@ -91,6 +93,7 @@ fn main() {
         QueueInputOptions {
             required: true,
             offset: Some(Duration::ZERO),
+            audio_delay: AudioDelay::None,
         },
     )
     .unwrap();
@@ -127,6 +130,7 @@ fn generate_frames(device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<Frame> {
             data: FrameData::Rgba8UnormWgpuTexture(texture_a.clone()),
             resolution,
             pts: Duration::from_millis(i * 20),
+            color: ColorInfo::default(),
         })
     }
 
@@ -135,6 +139,7 @@ fn generate_frames(device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<Frame> {
             data: FrameData::Rgba8UnormWgpuTexture(texture_b.clone()),
             resolution,
             pts: Duration::from_millis(i * 20),
+            color: ColorInfo::default(),
         })
     }
 
@@ -143,6 +148,7 @@ fn generate_frames(device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<Frame> {
             data: FrameData::Rgba8UnormWgpuTexture(texture_c.clone()),
             resolution,
             pts: Duration::from_millis(i * 20),
+            color: ColorInfo::default(),
         })
     }
 
@@ -151,6 +157,7 @@ fn generate_frames(device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<Frame> {
             data: FrameData::Rgba8UnormWgpuTexture(texture_a.clone()),
             resolution,
             pts: Duration::from_millis(i * 20),
+            color: ColorInfo::default(),
         })
     }
 
@@ -159,6 +166,7 @@ fn generate_frames(device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<Frame> {
             data: FrameData::Rgba8UnormWgpuTexture(texture_b.clone()),
             resolution,
             pts: Duration::from_millis(i * 20),
+            color: ColorInfo::default(),
         })
     }
 
@@ -167,6 +175,7 @@ fn generate_frames(device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<Frame> {
             data: FrameData::Rgba8UnormWgpuTexture(texture_c.clone()),
             resolution,
             pts: Duration::from_millis(i * 20),
+            color: ColorInfo::default(),
         })
     }
 
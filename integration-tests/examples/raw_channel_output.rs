@@ -76,6 +76,7 @@ fn main() {
                 input_id: input_id.clone(),
             }),
             end_condition: PipelineOutputEndCondition::Never,
+            framerate: None,
         }),
         audio: Some(RegisterOutputAudioOptions {
             initial: AudioMixerConfig {
@@ -85,6 +86,7 @@ fn main() {
                 }],
             },
             mixing_strategy: AudioMixingStrategy::SumClip,
+            loudness_normalization: None,
             channels: AudioChannels::Stereo,
             end_condition: PipelineOutputEndCondition::Never,
         }),
@@ -102,7 +104,9 @@ fn main() {
         queue_options: QueueInputOptions {
             required: true,
             offset: Some(Duration::ZERO),
+            audio_delay: AudioDelay::None,
         },
+        failover: Default::default(),
     };
 
     Pipeline::register_input(&pipeline, input_id.clone(), input_options).unwrap();
@@ -23,7 +23,7 @@ mod main_module {
     };
     use smelter_core::{codecs::*, protocols::*, *};
     use smelter_render::{
-        Framerate, InputId, OutputId, Resolution,
+        Framerate, InputId, OutputColorSpace, OutputId, Resolution,
         error::ErrorStack,
         scene::{Component, InputStreamComponent},
     };
@@ -73,7 +73,9 @@ mod main_module {
             queue_options: QueueInputOptions {
                 required: false,
                 offset: None,
+                audio_delay: AudioDelay::None,
             },
+            failover: Default::default(),
         }
     }
 
@@ -106,6 +108,7 @@ mod main_module {
                     preset: FfmpegH264EncoderPreset::Ultrafast,
                     resolution: VIDEO_RESOLUTION,
                     pixel_format: OutputPixelFormat::YUV420P,
+                    color_space: OutputColorSpace::default(),
                     raw_options: vec![
                         ("tune".into(), "zerolatency".into()),
                         ("thread_type".into(), "slice".into()),
@@ -117,6 +120,7 @@ mod main_module {
             video: Some(RegisterOutputVideoOptions {
                 initial: Component::InputStream(InputStreamComponent { id: None, input_id }),
                 end_condition: PipelineOutputEndCondition::Never,
+                framerate: None,
             }),
             audio: None, // TODO: add audio example
         }
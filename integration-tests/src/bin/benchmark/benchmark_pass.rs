@@ -16,7 +16,8 @@ use smelter_core::{
     *,
 };
 use smelter_render::{
-    Frame, InputId, OutputId, RendererId, RendererSpec, RenderingMode, YuvPlanes, scene::Component,
+    ColorInfo, Frame, InputId, OutputColorSpace, OutputId, RendererId, RendererSpec, RenderingMode,
+    YuvPlanes, scene::Component,
 };
 use tracing::debug;
 
@@ -189,6 +190,7 @@ impl SingleBenchmarkPass {
                 video: Some(RegisterOutputVideoOptions {
                     initial: root,
                     end_condition: PipelineOutputEndCondition::Never,
+                    framerate: None,
                 }),
                 audio: None,
                 output_options: EncodedDataOutputOptions {
@@ -201,6 +203,7 @@ impl SingleBenchmarkPass {
                             height: self.output_resolution.height,
                         },
                         pixel_format: OutputPixelFormat::YUV420P,
+                        color_space: OutputColorSpace::default(),
                         raw_options: vec![("threads".into(), "0".into())],
                     })),
                 },
@@ -223,6 +226,7 @@ impl SingleBenchmarkPass {
                 video: Some(RegisterOutputVideoOptions {
                     initial: root,
                     end_condition: PipelineOutputEndCondition::Never,
+                    framerate: None,
                 }),
                 audio: None,
                 output_options: EncodedDataOutputOptions {
@@ -233,6 +237,7 @@ impl SingleBenchmarkPass {
                             height: self.output_resolution.height,
                         },
                         bitrate: None,
+                        color_space: OutputColorSpace::default(),
                     })),
                 },
             },
@@ -253,6 +258,7 @@ impl SingleBenchmarkPass {
                 video: Some(RegisterOutputVideoOptions {
                     initial: root,
                     end_condition: PipelineOutputEndCondition::Never,
+                    framerate: None,
                 }),
                 audio: None,
                 output_options: RawDataOutputOptions {
@@ -290,7 +296,9 @@ impl SingleBenchmarkPass {
                 queue_options: QueueInputOptions {
                     offset: None,
                     required: true,
+                    audio_delay: AudioDelay::None,
                 },
+                failover: Default::default(),
             },
         )
     }
@@ -311,6 +319,7 @@ impl SingleBenchmarkPass {
             QueueInputOptions {
                 offset: None,
                 required: true,
+                audio_delay: AudioDelay::None,
             },
         )?;
 
@@ -413,6 +422,7 @@ fn raw_data_sender(senders: Vec<Sender<PipelineEvent<Frame>>>, input: RawInputFi
                     height: input.resolution.height,
                 },
                 pts: Duration::from_secs_f64(counter as f64 / input.framerate),
+                color: ColorInfo::default(),
             };
             counter += 1;
 
@@ -4,8 +4,8 @@ use smelter_render::{
     InputId, OutputId, RendererId, RendererSpec,
     image::{ImageSource, ImageSpec, ImageType},
     scene::{
-        Component, ImageComponent, InputStreamComponent, RGBAColor, RescalerComponent,
-        ShaderComponent, Size, TilesComponent, ViewComponent,
+        Component, ImageComponent, ImagePlayback, InputStreamComponent, RGBAColor,
+        RescalerComponent, ShaderComponent, Size, TilesComponent, ViewComponent,
     },
     shader::ShaderSpec,
 };
@@ -74,6 +74,7 @@ pub fn static_image(_ctx: &SceneContext, _output_id: &OutputId) -> Component {
             image_id: renderer_id.clone(),
             width: None,
             height: None,
+            playback: ImagePlayback::default(),
         })
         .into(),
         ..Default::default()
@@ -87,6 +88,7 @@ pub fn image_with_shader(_ctx: &SceneContext, _output_id: &OutputId) -> Componen
             image_id: example_image().0,
             width: None,
             height: None,
+            playback: ImagePlayback::default(),
         })],
         id: None,
         shader_id: example_shader().0,
@@ -1,8 +1,8 @@
 use anyhow::Result;
 use integration_tests::paths::integration_tests_root;
 use smelter_core::{
-    DEFAULT_BUFFER_DURATION, PipelineOptions, PipelineWgpuOptions, PipelineWhipWhepServerOptions,
-    graphics_context::GraphicsContext,
+    DEFAULT_BUFFER_DURATION, DEFAULT_MEDIA_ALARM_THRESHOLD, PipelineOptions, PipelineWgpuOptions,
+    PipelineWhipWhepServerOptions, graphics_context::GraphicsContext,
 };
 use std::{
     fs::{self, File},
@@ -36,6 +36,7 @@ pub fn benchmark_pipeline_options(
         load_system_fonts: false,
         mixing_sample_rate: 48_000,
         stream_fallback_timeout: Duration::from_millis(500),
+        media_alarm_threshold: DEFAULT_MEDIA_ALARM_THRESHOLD,
         tokio_rt: None,
         whip_whep_stun_servers: Vec::new().into(),
         rendering_mode,
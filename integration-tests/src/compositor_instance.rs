@@ -34,22 +34,31 @@ impl Drop for CompositorInstance {
 impl CompositorInstance {
     /// api port and  start_whip_whep  are overwritten in config
     pub fn start(config: Option<Config>) -> Self {
+        let config = instance_config(config);
+        let runtime = Arc::new(Runtime::new().unwrap());
+        let state = ApiState::new(config, runtime).unwrap();
+        Self::start_with_state(state)
+    }
+
+    /// Starts the server with a state created by the test, e.g. to register raw data
+    /// inputs and outputs directly in its pipeline. Config should be created with
+    /// [`instance_config`].
+    pub fn start_with_state(state: Arc<ApiState>) -> Self {
         init_compositor_prerequisites();
-        let api_port = get_free_port();
-        let mut config = config.unwrap_or(read_config());
-        config.api_port = api_port;
-        config.whip_whep_enable = false;
+        let api_port = state.config.api_port;
+        let runtime = state.runtime.clone();
 
-        info!("Starting Smelter Integration Test with config:\n{config:#?}",);
+        info!(
+            "Starting Smelter Integration Test with config:\n{:#?}",
+            state.config
+        );
 
         let (should_close_sender, should_close_receiver) = crossbeam_channel::bounded(1);
-        let runtime = Arc::new(Runtime::new().unwrap());
-        let state = ApiState::new(config, runtime.clone()).unwrap();
 
         thread::Builder::new()
             .name("HTTP server startup thread".to_string())
             .spawn(move || {
-                run_api(state, runtime.clone(), should_close_receiver).unwrap();
+                run_api(state, runtime, should_close_receiver).unwrap();
             })
             .unwrap();
 
@@ -104,6 +113,15 @@ impl CompositorInstance {
     }
 }
 
+/// Config with a free api port and WHIP/WHEP server disabled.
+pub fn instance_config(config: Option<Config>) -> Config {
+    init_compositor_prerequisites();
+    let mut config = config.unwrap_or(read_config());
+    config.api_port = get_free_port();
+    config.whip_whep_enable = false;
+    config
+}
+
 fn get_free_port() -> u16 {
     static LAST_PORT: OnceLock<AtomicU16> = OnceLock::new();
     let port = LAST_PORT.get_or_init(|| AtomicU16::new(10_000 + (rand::random::<u16>() % 10_000)));
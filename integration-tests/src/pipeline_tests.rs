@@ -3,6 +3,8 @@ mod offline_processing;
 mod push_input_before_start;
 mod required_inputs;
 mod schedule_update;
+mod scheduled_batch;
+mod scheduled_register;
 mod unregistering;
 mod video_audio;
 
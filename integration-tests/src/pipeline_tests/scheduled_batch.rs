@@ -0,0 +1,253 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{Result, anyhow};
+use bytes::Bytes;
+use serde_json::json;
+use smelter::{
+    routes::{RegisterInput, RegisterOutput},
+    state::{ApiRegistry, ApiState, RegisteredOutput, pipeline_options_from_config},
+};
+use smelter_core::{
+    AudioDelay, Pipeline, PipelineEvent, PipelineOptions, PipelineOutputEndCondition,
+    PipelineWgpuOptions, QueueInputOptions, RegisterOutputVideoOptions,
+    RegisterRawDataOutputOptions, graphics_context::GraphicsContext, protocols::*,
+};
+use smelter_render::{
+    ColorInfo, Frame, FrameData, Framerate, InputId, OutputId, Resolution, YuvPlanes,
+    scene::{Component, InputStreamComponent},
+};
+use tokio::runtime::Runtime;
+
+use crate::{CompositorInstance, instance_config, read_rgba_texture};
+
+const SCHEDULE_TIME: Duration = Duration::from_millis(1000);
+const FRAME_INTERVAL: Duration = Duration::from_millis(40);
+const RESOLUTION: Resolution = Resolution {
+    width: 64,
+    height: 64,
+};
+
+/// Schedules a batch that registers an input, updates the scene of an output and
+/// unregisters the input that was rendered on it. All of them have to be applied
+/// starting from a frame with exactly the scheduled PTS.
+///
+/// Show white `input_1` for 1 second.
+/// Show red background after that.
+#[test]
+pub fn scheduled_batch() -> Result<()> {
+    let mut config = instance_config(None);
+    config.ahead_of_time_processing = true;
+    config.never_drop_output_frames = true;
+    config.output_framerate = Framerate { num: 25, den: 1 };
+    let runtime = Arc::new(Runtime::new()?);
+    let ctx = GraphicsContext::new(Default::default())?;
+    let (device, queue) = (ctx.device.clone(), ctx.queue.clone());
+    let pipeline = Pipeline::new(PipelineOptions {
+        wgpu_options: PipelineWgpuOptions::Context(ctx),
+        ..pipeline_options_from_config(&config, &runtime, &None)
+    })?;
+    let pipeline = Arc::new(Mutex::new(pipeline));
+    let input_id = InputId("input_1".into());
+    let output_id = OutputId("output_1".into());
+
+    let input_sender = Pipeline::register_raw_data_input(
+        &pipeline,
+        input_id.clone(),
+        RawDataInputOptions {
+            video: true,
+            audio: false,
+            buffer_duration: None,
+        },
+        QueueInputOptions {
+            required: true,
+            offset: Some(Duration::ZERO),
+            audio_delay: AudioDelay::None,
+        },
+    )?;
+    let output = Pipeline::register_raw_data_output(
+        &pipeline,
+        output_id.clone(),
+        raw_output_options(&input_id),
+    )?;
+
+    // Raw data inputs and outputs can't be registered with the API, so the registry
+    // only needs requests that pass the batch validation.
+    let mut registry = ApiRegistry::default();
+    registry.inputs.insert(
+        input_id,
+        serde_json::from_value::<RegisterInput>(json!({
+            "type": "rtp_stream",
+            "port": 0,
+            "video": {
+                "decoder": "ffmpeg_h264"
+            },
+        }))?,
+    );
+    registry.outputs.insert(
+        output_id,
+        RegisteredOutput::new(serde_json::from_value::<RegisterOutput>(json!({
+            "type": "rtp_stream",
+            "port": 0,
+            "video": {
+                "resolution": {
+                    "width": RESOLUTION.width,
+                    "height": RESOLUTION.height,
+                },
+                "encoder": {
+                    "type": "ffmpeg_h264",
+                },
+                "initial": {
+                    "root": {
+                        "type": "input_stream",
+                        "input_id": "input_1",
+                    }
+                }
+            },
+        }))?),
+    );
+
+    let instance = CompositorInstance::start_with_state(Arc::new(ApiState {
+        pipeline: Mutex::new(Some(pipeline.clone())),
+        registry: Arc::new(Mutex::new(registry)),
+        previews: Default::default(),
+        config,
+        chromium_context: None,
+        runtime,
+    }));
+
+    instance.send_request(
+        "batch",
+        json!({
+            "operations": [
+                {
+                    "type": "register_input",
+                    "input_id": "input_2",
+                    "input": {
+                        "type": "rtp_stream",
+                        "port": instance.get_port(),
+                        "transport_protocol": "udp",
+                        "video": {
+                            "decoder": "ffmpeg_h264"
+                        },
+                    },
+                },
+                {
+                    "type": "update_output",
+                    "output_id": "output_1",
+                    "video": {
+                        "root": {
+                            "type": "view",
+                            "background_color": "#FF0000FF",
+                            "children": [
+                                {
+                                    "type": "input_stream",
+                                    "input_id": "input_2",
+                                },
+                            ],
+                        }
+                    },
+                },
+                {
+                    "type": "unregister_input",
+                    "input_id": "input_1",
+                },
+            ],
+            "schedule_time_ms": SCHEDULE_TIME.as_millis() as u64,
+        }),
+    )?;
+    assert!(
+        !pipeline
+            .lock()
+            .unwrap()
+            .inputs()
+            .any(|(id, _)| id.0.as_ref() == "input_2"),
+        "Input registered before the scheduled time."
+    );
+
+    let input_sender = input_sender.video.unwrap();
+    for index in 0..100 {
+        input_sender.send(PipelineEvent::Data(white_frame(FRAME_INTERVAL * index)))?;
+    }
+
+    instance.send_request("start", json!({}))?;
+
+    let output = output.video.unwrap();
+    let mut start_pts = None;
+    loop {
+        let PipelineEvent::Data(frame) = output.recv_timeout(Duration::from_secs(10))? else {
+            return Err(anyhow!("Unexpected EOS on output_1."));
+        };
+        let pts = frame.pts - *start_pts.get_or_insert(frame.pts);
+        let [red, green, _, _] = center_pixel(&device, &queue, &frame);
+        let expected = match pts < SCHEDULE_TIME {
+            true => "white",
+            false => "red",
+        };
+        let is_valid = match pts < SCHEDULE_TIME {
+            true => red > 200 && green > 200,
+            false => red > 200 && green < 50,
+        };
+        if !is_valid {
+            return Err(anyhow!(
+                "Expected {expected} frame at pts {pts:?}, got red {red}, green {green}."
+            ));
+        }
+        if pts >= SCHEDULE_TIME + FRAME_INTERVAL * 5 {
+            break;
+        }
+    }
+
+    let pipeline = pipeline.lock().unwrap();
+    let inputs: Vec<_> = pipeline.inputs().map(|(id, _)| id.0.to_string()).collect();
+    assert_eq!(inputs, vec!["input_2".to_string()]);
+
+    Ok(())
+}
+
+fn raw_output_options(input_id: &InputId) -> RegisterRawDataOutputOptions {
+    RegisterRawDataOutputOptions {
+        output_options: RawDataOutputOptions {
+            video: Some(RawDataOutputVideoOptions {
+                resolution: RESOLUTION,
+            }),
+            audio: None,
+        },
+        video: Some(RegisterOutputVideoOptions {
+            initial: Component::InputStream(InputStreamComponent {
+                id: None,
+                input_id: input_id.clone(),
+            }),
+            end_condition: PipelineOutputEndCondition::Never,
+            framerate: None,
+        }),
+        audio: None,
+    }
+}
+
+fn white_frame(pts: Duration) -> Frame {
+    let (width, height) = (RESOLUTION.width, RESOLUTION.height);
+    Frame {
+        data: FrameData::PlanarYuv420(YuvPlanes {
+            y_plane: Bytes::from(vec![255; width * height]),
+            u_plane: Bytes::from(vec![128; width * height / 4]),
+            v_plane: Bytes::from(vec![128; width * height / 4]),
+        }),
+        resolution: RESOLUTION,
+        pts,
+        color: ColorInfo::default(),
+    }
+}
+
+/// RGBA value of the pixel in the middle of the frame.
+fn center_pixel(device: &wgpu::Device, queue: &wgpu::Queue, frame: &Frame) -> [u8; 4] {
+    let FrameData::Rgba8UnormWgpuTexture(texture) = &frame.data else {
+        panic!("Raw data output should produce wgpu textures.");
+    };
+    let data = read_rgba_texture(device, queue, texture);
+    let (width, height) = (RESOLUTION.width, RESOLUTION.height);
+    let index = (height / 2 * width + width / 2) * 4;
+    data[index..index + 4].try_into().unwrap()
+}
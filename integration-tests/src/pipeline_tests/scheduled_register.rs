@@ -0,0 +1,162 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{Result, anyhow};
+use bytes::Bytes;
+use smelter::{config::read_config, state::pipeline_options_from_config};
+use smelter_core::{
+    AudioDelay, Pipeline, PipelineEvent, PipelineOptions, PipelineOutputEndCondition,
+    PipelineWgpuOptions, QueueInputOptions, RegisterOutputVideoOptions,
+    RegisterRawDataOutputOptions, graphics_context::GraphicsContext, protocols::*,
+};
+use smelter_render::{
+    ColorInfo, Frame, FrameData, Framerate, InputId, OutputId, Resolution, YuvPlanes,
+    scene::{Component, InputStreamComponent},
+};
+use tokio::runtime::Runtime;
+
+use crate::read_rgba_texture;
+
+const SCHEDULE_TIME: Duration = Duration::from_millis(1000);
+const FRAME_INTERVAL: Duration = Duration::from_millis(40);
+const RESOLUTION: Resolution = Resolution {
+    width: 64,
+    height: 64,
+};
+
+/// Registers an input and an output for the same PTS. Both of them are initialized
+/// before the pipeline starts, and have to be used starting from a frame with exactly
+/// that PTS.
+#[test]
+pub fn scheduled_register() -> Result<()> {
+    let mut config = read_config();
+    config.ahead_of_time_processing = true;
+    config.never_drop_output_frames = true;
+    config.output_framerate = Framerate { num: 25, den: 1 };
+    let ctx = GraphicsContext::new(Default::default())?;
+    let (device, queue) = (ctx.device.clone(), ctx.queue.clone());
+    let pipeline = Pipeline::new(PipelineOptions {
+        wgpu_options: PipelineWgpuOptions::Context(ctx),
+        ..pipeline_options_from_config(&config, &Arc::new(Runtime::new()?), &None)
+    })?;
+    let pipeline = Arc::new(Mutex::new(pipeline));
+    let input_id = InputId("input_1".into());
+
+    let output_1 = Pipeline::register_raw_data_output(
+        &pipeline,
+        OutputId("output_1".into()),
+        raw_output_options(&input_id),
+    )?;
+
+    let (prepared_input, input_sender) = Pipeline::prepare_raw_data_input(
+        &pipeline,
+        input_id.clone(),
+        RawDataInputOptions {
+            video: true,
+            audio: false,
+            buffer_duration: None,
+        },
+        QueueInputOptions {
+            required: true,
+            offset: Some(Duration::ZERO),
+            audio_delay: AudioDelay::None,
+        },
+    )?;
+    let (prepared_output, output_2) = Pipeline::prepare_raw_data_output(
+        &pipeline,
+        OutputId("output_2".into()),
+        raw_output_options(&input_id),
+    )?;
+    Pipeline::schedule_event(&pipeline, SCHEDULE_TIME, move |pipeline| {
+        pipeline.register_prepared_input(prepared_input).unwrap();
+        pipeline.register_prepared_output(prepared_output).unwrap();
+    });
+
+    let input_sender = input_sender.video.unwrap();
+    for index in 0..100 {
+        input_sender.send(PipelineEvent::Data(white_frame(FRAME_INTERVAL * index)))?;
+    }
+
+    Pipeline::start(&pipeline);
+
+    let output_1 = output_1.video.unwrap();
+    let output_2 = output_2.video.unwrap();
+    let mut start_pts = None;
+    loop {
+        let PipelineEvent::Data(frame) = output_1.recv_timeout(Duration::from_secs(10))? else {
+            return Err(anyhow!("Unexpected EOS on output_1."));
+        };
+        let pts = frame.pts - *start_pts.get_or_insert(frame.pts);
+        let is_input_rendered = center_pixel(&device, &queue, &frame) > 200;
+        match pts < SCHEDULE_TIME {
+            true if is_input_rendered => {
+                return Err(anyhow!(
+                    "Input rendered before it was registered, pts {pts:?}."
+                ));
+            }
+            false if !is_input_rendered => {
+                return Err(anyhow!(
+                    "Input not rendered after it was registered, pts {pts:?}."
+                ));
+            }
+            _ => (),
+        }
+        if pts >= SCHEDULE_TIME + FRAME_INTERVAL * 5 {
+            break;
+        }
+    }
+
+    let PipelineEvent::Data(frame) = output_2.recv_timeout(Duration::from_secs(10))? else {
+        return Err(anyhow!("Unexpected EOS on output_2."));
+    };
+    assert_eq!(frame.pts - start_pts.unwrap(), SCHEDULE_TIME);
+    assert!(center_pixel(&device, &queue, &frame) > 200);
+
+    Ok(())
+}
+
+fn raw_output_options(input_id: &InputId) -> RegisterRawDataOutputOptions {
+    RegisterRawDataOutputOptions {
+        output_options: RawDataOutputOptions {
+            video: Some(RawDataOutputVideoOptions {
+                resolution: RESOLUTION,
+            }),
+            audio: None,
+        },
+        video: Some(RegisterOutputVideoOptions {
+            initial: Component::InputStream(InputStreamComponent {
+                id: None,
+                input_id: input_id.clone(),
+            }),
+            end_condition: PipelineOutputEndCondition::Never,
+            framerate: None,
+        }),
+        audio: None,
+    }
+}
+
+fn white_frame(pts: Duration) -> Frame {
+    let (width, height) = (RESOLUTION.width, RESOLUTION.height);
+    Frame {
+        data: FrameData::PlanarYuv420(YuvPlanes {
+            y_plane: Bytes::from(vec![255; width * height]),
+            u_plane: Bytes::from(vec![128; width * height / 4]),
+            v_plane: Bytes::from(vec![128; width * height / 4]),
+        }),
+        resolution: RESOLUTION,
+        pts,
+        color: ColorInfo::default(),
+    }
+}
+
+/// Red channel of the pixel in the middle of the frame.
+fn center_pixel(device: &wgpu::Device, queue: &wgpu::Queue, frame: &Frame) -> u8 {
+    let FrameData::Rgba8UnormWgpuTexture(texture) = &frame.data else {
+        panic!("Raw data output should produce wgpu textures.");
+    };
+    let data = read_rgba_texture(device, queue, texture);
+    let (width, height) = (RESOLUTION.width, RESOLUTION.height);
+    data[(height / 2 * width + width / 2) * 4]
+}
@@ -15,14 +15,21 @@ mod snapshot;
 mod test_case;
 mod utils;
 
+mod fragment_tests;
 mod image_tests;
+mod mask_tests;
+mod opacity_tests;
+mod renderer_snapshot_tests;
 mod rescaler_tests;
 mod shader_tests;
 mod simple_tests;
+mod slate_tests;
 mod text_tests;
+mod ticker_tests;
 mod tiles_tests;
 mod tiles_transitions_tests;
 mod transition_tests;
+mod video_clip_tests;
 mod view_tests;
 mod yuv_tests;
 
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use smelter_render::{
+    FrameSet, OutputFrameFormat, OutputId, Renderer, RendererId, RendererSpec, Resolution,
+    SnapshotSource,
+    error::UpdateFragmentError,
+    scene::{
+        AlignItems, BlendMode, BorderRadius, Component, Flex, FragmentComponent, JustifyContent,
+        Overflow, Position, RGBAColor, ViewChildrenDirection, ViewComponent,
+    },
+};
+
+use super::utils::create_renderer;
+
+const RESOLUTION: Resolution = Resolution {
+    width: 8,
+    height: 2,
+};
+
+const RED: RGBAColor = RGBAColor(255, 0, 0, 255);
+const BLUE: RGBAColor = RGBAColor(0, 0, 255, 255);
+
+fn fragment_id() -> RendererId {
+    RendererId("fragment".into())
+}
+
+fn view(background_color: RGBAColor, children: Vec<Component>) -> Component {
+    Component::View(ViewComponent {
+        id: None,
+        children,
+        direction: ViewChildrenDirection::Row,
+        position: Position::Static {
+            width: None,
+            height: None,
+        },
+        transition: None,
+        animation: None,
+        overflow: Overflow::Hidden,
+        background_color,
+        border_radius: BorderRadius::ZERO,
+        border_width: 0.0,
+        border_color: RGBAColor(0, 0, 0, 0),
+        box_shadow: vec![],
+        padding: Default::default(),
+        gap: 0.0,
+        justify_content: JustifyContent::Start,
+        align_items: AlignItems::Stretch,
+        wrap: false,
+        flex: Flex::default(),
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+    })
+}
+
+fn fragment(fragment_id: RendererId) -> Component {
+    Component::Fragment(FragmentComponent { fragment_id })
+}
+
+/// Registers a fragment and outputs that render it.
+fn renderer_with_fragment(outputs: &[OutputId]) -> Renderer {
+    let mut renderer = create_renderer();
+    renderer
+        .register_renderer(fragment_id(), RendererSpec::Fragment(view(RED, vec![])))
+        .unwrap();
+    for output_id in outputs {
+        renderer
+            .update_scene(
+                output_id.clone(),
+                RESOLUTION,
+                OutputFrameFormat::RgbaWgpuTexture,
+                fragment(fragment_id()),
+            )
+            .unwrap();
+    }
+    renderer
+}
+
+fn assert_output_color(renderer: &Renderer, output_id: &OutputId, color: RGBAColor) {
+    renderer.render(FrameSet::new(Duration::ZERO)).unwrap();
+    let snapshot = renderer
+        .snapshot(&SnapshotSource::Output(output_id.clone()))
+        .unwrap();
+    let RGBAColor(r, g, b, a) = color;
+    let mismatched = snapshot.data.chunks(4).any(|pixel| {
+        pixel
+            .iter()
+            .zip([r, g, b, a])
+            .any(|(p, c)| p.abs_diff(c) > 2)
+    });
+    assert!(
+        !mismatched,
+        "output: {output_id}, actual: {:?}, expected: {color:?}",
+        snapshot.data
+    );
+}
+
+/// Updating a fragment re-renders all outputs that use it.
+#[test]
+fn fragment_update() {
+    let outputs = [OutputId("output_1".into()), OutputId("output_2".into())];
+    let renderer = renderer_with_fragment(&outputs);
+    for output_id in &outputs {
+        assert_output_color(&renderer, output_id, RED);
+    }
+
+    renderer
+        .update_fragment(fragment_id(), view(BLUE, vec![]))
+        .unwrap();
+    for output_id in &outputs {
+        assert_output_color(&renderer, output_id, BLUE);
+    }
+}
+
+/// If the new fragment content is invalid, neither the fragment nor any of the
+/// outputs is updated.
+#[test]
+fn failed_fragment_update() {
+    let outputs = [OutputId("output_1".into()), OutputId("output_2".into())];
+    let renderer = renderer_with_fragment(&outputs);
+
+    let result = renderer.update_fragment(
+        fragment_id(),
+        view(BLUE, vec![fragment(RendererId("missing".into()))]),
+    );
+    assert!(matches!(result, Err(UpdateFragmentError::UpdateScene(_))));
+    for output_id in &outputs {
+        assert_output_color(&renderer, output_id, RED);
+    }
+
+    // Registered content was restored, so valid updates still work.
+    renderer
+        .update_fragment(fragment_id(), view(BLUE, vec![]))
+        .unwrap();
+    for output_id in &outputs {
+        assert_output_color(&renderer, output_id, BLUE);
+    }
+}
+
+/// A fragment can not include itself, directly or through other fragments.
+#[test]
+fn recursive_fragment_update() {
+    let output_id = OutputId("output_1".into());
+    let renderer = renderer_with_fragment(std::slice::from_ref(&output_id));
+
+    let result = renderer.update_fragment(fragment_id(), view(BLUE, vec![fragment(fragment_id())]));
+    assert!(result.is_err());
+    assert_output_color(&renderer, &output_id, RED);
+}
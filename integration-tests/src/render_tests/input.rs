@@ -1,10 +1,11 @@
-use smelter_render::{FrameData, Resolution, YuvPlanes, scene::RGBColor};
+use smelter_render::{ColorInfo, FrameData, Resolution, YuvPlanes, scene::RGBColor};
 
 #[derive(Debug, Clone)]
 pub(super) struct TestInput {
     pub name: String,
     pub resolution: Resolution,
     pub data: FrameData,
+    pub color: ColorInfo,
 }
 
 impl TestInput {
@@ -106,6 +107,7 @@ impl TestInput {
             name: format!("input_{index}"),
             resolution,
             data,
+            color: ColorInfo::default(),
         }
     }
 }
@@ -0,0 +1,35 @@
+use crate::paths::render_snapshots_dir_path;
+
+use super::{TestRunner, input::TestInput, test_case::TestCase, test_steps_from_scene};
+
+#[test]
+fn mask_tests() {
+    let mut runner = TestRunner::new(render_snapshots_dir_path().join("mask"));
+    let default = TestCase {
+        inputs: vec![TestInput::new(1)],
+        ..Default::default()
+    };
+
+    runner.add(TestCase {
+        name: "mask/alpha",
+        steps: test_steps_from_scene(include_str!("./mask/alpha.scene.json")),
+        ..default.clone()
+    });
+    runner.add(TestCase {
+        name: "mask/alpha_invert",
+        steps: test_steps_from_scene(include_str!("./mask/alpha_invert.scene.json")),
+        ..default.clone()
+    });
+    runner.add(TestCase {
+        name: "mask/luminance",
+        steps: test_steps_from_scene(include_str!("./mask/luminance.scene.json")),
+        ..default.clone()
+    });
+    runner.add(TestCase {
+        name: "mask/luminance_invert",
+        steps: test_steps_from_scene(include_str!("./mask/luminance_invert.scene.json")),
+        ..default.clone()
+    });
+
+    runner.run()
+}
@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use smelter_render::{
+    FrameSet, OutputFrameFormat, OutputId, Renderer, Resolution, SnapshotSource,
+    scene::{
+        AbsolutePosition, BlendMode, Component, HorizontalPosition, Position, RGBAColor,
+        VerticalPosition, ViewComponent,
+    },
+};
+
+use super::utils::create_renderer;
+
+const RESOLUTION: Resolution = Resolution {
+    width: 8,
+    height: 2,
+};
+
+const BLACK: RGBAColor = RGBAColor(0, 0, 0, 255);
+const RED: RGBAColor = RGBAColor(255, 0, 0, 255);
+const BLUE: RGBAColor = RGBAColor(0, 0, 255, 255);
+
+fn absolute_view(left: f32, width: f32, background_color: RGBAColor) -> Component {
+    Component::View(ViewComponent {
+        position: Position::Absolute(AbsolutePosition {
+            width: Some(width),
+            height: Some(RESOLUTION.height as f32),
+            position_horizontal: HorizontalPosition::LeftOffset(left),
+            position_vertical: VerticalPosition::TopOffset(0.0),
+            rotation_degrees: 0.0,
+        }),
+        background_color,
+        ..Default::default()
+    })
+}
+
+/// Black background with a semi-transparent view that contains red (x in 0..6)
+/// and blue (x in 2..8) views. Blue view covers the red one.
+fn overlapping_children_scene(opacity: f32, blend_mode: BlendMode) -> Component {
+    Component::View(ViewComponent {
+        background_color: BLACK,
+        children: vec![Component::View(ViewComponent {
+            children: vec![absolute_view(0.0, 6.0, RED), absolute_view(2.0, 6.0, BLUE)],
+            opacity,
+            blend_mode,
+            ..Default::default()
+        })],
+        ..Default::default()
+    })
+}
+
+/// Renders the scene and returns the middle row of pixels.
+fn render(renderer: &mut Renderer, scene: Component) -> Vec<[u8; 4]> {
+    let output_id = OutputId("output_1".into());
+    renderer
+        .update_scene(
+            output_id.clone(),
+            RESOLUTION,
+            OutputFrameFormat::RgbaWgpuTexture,
+            scene,
+        )
+        .unwrap();
+    renderer.render(FrameSet::new(Duration::ZERO)).unwrap();
+    let snapshot = renderer
+        .snapshot(&SnapshotSource::Output(output_id))
+        .unwrap();
+    snapshot
+        .data
+        .chunks(4)
+        .take(RESOLUTION.width)
+        .map(|pixel| [pixel[0], pixel[1], pixel[2], pixel[3]])
+        .collect()
+}
+
+/// Opacity is applied to the view as a whole, so the red view is not visible
+/// through the blue view that covers it.
+#[test]
+fn opacity_overlapping_children() {
+    let mut renderer = create_renderer();
+    let pixels = render(
+        &mut renderer,
+        overlapping_children_scene(0.5, BlendMode::Normal),
+    );
+
+    for (x, [r, g, b, _]) in pixels.iter().copied().enumerate() {
+        match x {
+            0..2 => assert!(r > 50 && r < 255 && b < 5, "x: {x}, pixels: {pixels:?}"),
+            _ => assert!(r < 5 && b > 50 && b < 255, "x: {x}, pixels: {pixels:?}"),
+        }
+        assert!(g < 5, "x: {x}, pixels: {pixels:?}");
+    }
+    // Overlapping area looks the same as the part covered only by the blue view.
+    assert_eq!(pixels[4], pixels[7], "pixels: {pixels:?}");
+}
+
+/// Fully opaque view is rendered the same way with and without opacity groups.
+#[test]
+fn opaque_overlapping_children() {
+    let mut renderer = create_renderer();
+    let pixels = render(
+        &mut renderer,
+        overlapping_children_scene(1.0, BlendMode::Normal),
+    );
+
+    assert_eq!(pixels[0], [255, 0, 0, 255], "pixels: {pixels:?}");
+    assert_eq!(pixels[4], [0, 0, 255, 255], "pixels: {pixels:?}");
+}
+
+/// Blend mode of a semi-transparent view is applied once to the entire view.
+#[test]
+fn opacity_with_blend_mode() {
+    let mut renderer = create_renderer();
+    let normal = render(
+        &mut renderer,
+        overlapping_children_scene(0.5, BlendMode::Normal),
+    );
+    // Screen blending with a black backdrop does not change the source color.
+    let screen = render(
+        &mut renderer,
+        overlapping_children_scene(0.5, BlendMode::Screen),
+    );
+
+    for (normal, screen) in normal.iter().zip(screen.iter()) {
+        for (normal, screen) in normal.iter().zip(screen.iter()) {
+            assert!(normal.abs_diff(*screen) <= 2, "{normal:?}, {screen:?}");
+        }
+    }
+}
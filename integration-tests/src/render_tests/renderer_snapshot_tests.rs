@@ -0,0 +1,140 @@
+use std::{sync::Arc, time::Duration};
+
+use smelter_render::{
+    Frame, FrameSet, InputId, OutputFrameFormat, OutputId, OutputSceneUpdate, Renderer, Resolution,
+    SnapshotSource,
+    error::SnapshotError,
+    scene::{
+        AlignItems, BlendMode, BorderRadius, Component, Flex, JustifyContent, Overflow, Position,
+        RGBAColor, ViewChildrenDirection, ViewComponent,
+    },
+};
+
+use super::{input::TestInput, test_case::OUTPUT_ID, utils::create_renderer};
+
+const RESOLUTION: Resolution = Resolution {
+    width: 8,
+    height: 2,
+};
+
+fn output_id() -> OutputId {
+    OutputId(OUTPUT_ID.into())
+}
+
+fn view(background_color: RGBAColor) -> Component {
+    Component::View(ViewComponent {
+        id: None,
+        children: vec![],
+        direction: ViewChildrenDirection::Row,
+        position: Position::Static {
+            width: None,
+            height: None,
+        },
+        transition: None,
+        animation: None,
+        overflow: Overflow::Hidden,
+        background_color,
+        border_radius: BorderRadius::ZERO,
+        border_width: 0.0,
+        border_color: RGBAColor(0, 0, 0, 0),
+        box_shadow: vec![],
+        padding: Default::default(),
+        gap: 0.0,
+        justify_content: JustifyContent::Start,
+        align_items: AlignItems::Stretch,
+        wrap: false,
+        flex: Flex::default(),
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+    })
+}
+
+fn render(renderer: &Renderer, inputs: &[TestInput]) {
+    let mut frame_set = FrameSet::new(Duration::ZERO);
+    for input in inputs {
+        let frame = Frame {
+            data: input.data.clone(),
+            resolution: input.resolution,
+            pts: Duration::ZERO,
+            color: input.color,
+        };
+        frame_set
+            .frames
+            .insert(InputId(Arc::from(input.name.clone())), frame);
+    }
+    renderer.render(frame_set).unwrap();
+}
+
+/// Snapshot of an output is the frame rendered by the scene, before it is converted
+/// to the output format.
+#[test]
+fn output_snapshot() {
+    let renderer = create_renderer();
+    renderer
+        .update_scenes(vec![OutputSceneUpdate {
+            output_id: output_id(),
+            resolution: RESOLUTION,
+            output_format: OutputFrameFormat::PlanarYuv420Bytes(Default::default()),
+            scene_root: view(RGBAColor(50, 100, 150, 255)),
+        }])
+        .unwrap();
+
+    let source = SnapshotSource::Output(output_id());
+    assert!(matches!(
+        renderer.snapshot(&source),
+        Err(SnapshotError::NoFrame)
+    ));
+
+    render(&renderer, &[]);
+    let snapshot = renderer.snapshot(&source).unwrap();
+    assert_eq!(snapshot.resolution, RESOLUTION);
+    let expected = [50, 100, 150, 255].repeat(RESOLUTION.width * RESOLUTION.height);
+    let mismatched = snapshot
+        .data
+        .iter()
+        .zip(&expected)
+        .any(|(actual, expected)| u8::abs_diff(*actual, *expected) > 2);
+    assert!(
+        !mismatched,
+        "actual: {:?}, expected: {expected:?}",
+        snapshot.data
+    );
+}
+
+#[test]
+fn input_snapshot() {
+    let renderer = create_renderer();
+    let input = TestInput::new_with_resolution(0, RESOLUTION);
+    renderer.register_input(InputId(input.name.clone().into()));
+    renderer
+        .update_scenes(vec![OutputSceneUpdate {
+            output_id: output_id(),
+            resolution: RESOLUTION,
+            output_format: OutputFrameFormat::RgbaWgpuTexture,
+            scene_root: view(RGBAColor(0, 0, 0, 255)),
+        }])
+        .unwrap();
+    render(&renderer, &[input.clone()]);
+
+    let snapshot = renderer
+        .snapshot(&SnapshotSource::Input(InputId(input.name.into())))
+        .unwrap();
+    assert_eq!(snapshot.resolution, RESOLUTION);
+    assert_eq!(
+        snapshot.data.len(),
+        RESOLUTION.width * RESOLUTION.height * 4
+    );
+}
+
+#[test]
+fn snapshot_not_found() {
+    let renderer = create_renderer();
+    assert!(matches!(
+        renderer.snapshot(&SnapshotSource::Output(output_id())),
+        Err(SnapshotError::OutputNotFound(_))
+    ));
+    assert!(matches!(
+        renderer.snapshot(&SnapshotSource::Input(InputId("input_1".into()))),
+        Err(SnapshotError::InputNotFound(_))
+    ));
+}
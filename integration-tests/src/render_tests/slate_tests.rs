@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use smelter_render::{
+    ColorInfo, Frame, FrameData, FrameSet, InputId, OutputFrameFormat, OutputId, Renderer,
+    RendererId, RendererSpec, Resolution, SnapshotSource, YuvPlanes,
+    error::SetInputSlateError,
+    image::{ImageSource, ImageSpec, ImageType},
+    scene::{Component, InputStreamComponent, RGBAColor},
+};
+
+use super::utils::create_renderer;
+
+const RESOLUTION: Resolution = Resolution {
+    width: 8,
+    height: 2,
+};
+
+const WHITE: RGBAColor = RGBAColor(255, 255, 255, 255);
+const BLUE: RGBAColor = RGBAColor(0, 0, 255, 255);
+
+const SLATE_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="8" height="2"><rect width="8" height="2" fill="#0000ff"/></svg>"##;
+
+fn input_id() -> InputId {
+    InputId("input_1".into())
+}
+
+fn slate_id() -> RendererId {
+    RendererId("slate".into())
+}
+
+fn output_id() -> OutputId {
+    OutputId("output_1".into())
+}
+
+/// White frame in limited range YUV.
+fn white_frame(pts: Duration) -> Frame {
+    let pixels = RESOLUTION.width * RESOLUTION.height;
+    Frame {
+        data: FrameData::PlanarYuv420(YuvPlanes {
+            y_plane: Bytes::from(vec![235; pixels]),
+            u_plane: Bytes::from(vec![128; pixels / 4]),
+            v_plane: Bytes::from(vec![128; pixels / 4]),
+        }),
+        resolution: RESOLUTION,
+        pts,
+        color: ColorInfo::default(),
+    }
+}
+
+/// Registers an input with a blue slate and an output that renders this input.
+fn renderer_with_slate() -> Renderer {
+    let mut renderer = create_renderer();
+    renderer
+        .register_renderer(
+            slate_id(),
+            RendererSpec::Image(ImageSpec {
+                src: ImageSource::Bytes {
+                    bytes: Bytes::from_static(SLATE_SVG.as_bytes()),
+                },
+                image_type: ImageType::Svg,
+            }),
+        )
+        .unwrap();
+    renderer.register_input(input_id());
+    renderer
+        .set_input_slate(&input_id(), Some(&slate_id()))
+        .unwrap();
+    renderer
+        .update_scene(
+            output_id(),
+            RESOLUTION,
+            OutputFrameFormat::RgbaWgpuTexture,
+            Component::InputStream(InputStreamComponent {
+                id: None,
+                input_id: input_id(),
+            }),
+        )
+        .unwrap();
+    renderer
+}
+
+fn assert_color(renderer: &Renderer, frame_set: FrameSet<InputId>, color: RGBAColor) {
+    let pts = frame_set.pts;
+    renderer.render(frame_set).unwrap();
+    let snapshot = renderer
+        .snapshot(&SnapshotSource::Output(output_id()))
+        .unwrap();
+    let RGBAColor(r, g, b, a) = color;
+    let mismatched = snapshot.data.chunks(4).any(|pixel| {
+        pixel
+            .iter()
+            .zip([r, g, b, a])
+            .any(|(p, c)| p.abs_diff(c) > 2)
+    });
+    assert!(
+        !mismatched,
+        "pts: {pts:?}, actual: {:?}, expected: {color:?}",
+        snapshot.data
+    );
+}
+
+fn frame_set(pts: Duration, frame: Option<Frame>) -> FrameSet<InputId> {
+    let mut frame_set = FrameSet::new(pts);
+    if let Some(frame) = frame {
+        frame_set.frames.insert(input_id(), frame);
+    }
+    frame_set
+}
+
+/// Slate is rendered only while the input does not deliver frames.
+#[test]
+fn slate_switching() {
+    let renderer = renderer_with_slate();
+    let secs = Duration::from_secs;
+
+    assert_color(
+        &renderer,
+        frame_set(secs(0), Some(white_frame(secs(0)))),
+        WHITE,
+    );
+    assert_color(&renderer, frame_set(secs(1), None), BLUE);
+    assert_color(
+        &renderer,
+        frame_set(secs(2), Some(white_frame(secs(2)))),
+        WHITE,
+    );
+    // Frames older than the stream fallback timeout are treated as missing.
+    assert_color(
+        &renderer,
+        frame_set(secs(6), Some(white_frame(secs(2)))),
+        BLUE,
+    );
+}
+
+#[test]
+fn slate_not_registered() {
+    let renderer = create_renderer();
+    renderer.register_input(input_id());
+
+    let result = renderer.set_input_slate(&input_id(), Some(&RendererId("missing".into())));
+    assert!(matches!(result, Err(SetInputSlateError::ImageNotFound(_))));
+}
@@ -50,7 +50,7 @@ impl Default for TestCase {
                 width: 640,
                 height: 360,
             },
-            output_format: OutputFrameFormat::PlanarYuv420Bytes,
+            output_format: OutputFrameFormat::PlanarYuv420Bytes(Default::default()),
         }
     }
 }
@@ -183,6 +183,7 @@ impl TestCase {
                 data: input.data.clone(),
                 resolution: input.resolution,
                 pts,
+                color: input.color,
             };
             frame_set.frames.insert(input_id, frame);
         }
@@ -74,6 +74,21 @@ fn text_tests() {
         steps: test_steps_from_scene(include_str!("./text/wrap_word.scene.json")),
         ..Default::default()
     });
+    runner.add(TestCase {
+        name: "text/outline",
+        steps: test_steps_from_scene(include_str!("./text/outline.scene.json")),
+        ..Default::default()
+    });
+    runner.add(TestCase {
+        name: "text/shadow",
+        steps: test_steps_from_scene(include_str!("./text/shadow.scene.json")),
+        ..Default::default()
+    });
+    runner.add(TestCase {
+        name: "text/spans",
+        steps: test_steps_from_scene(include_str!("./text/spans.scene.json")),
+        ..Default::default()
+    });
     runner.add(TestCase {
         // Test if removing text from scene works
         name: "text/remove_text_in_view",
@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use crate::paths::render_snapshots_dir_path;
+
+use super::{
+    TestRunner,
+    test_case::{Step, TestCase},
+};
+
+#[test]
+fn ticker_tests() {
+    let mut runner = TestRunner::new(render_snapshots_dir_path().join("ticker"));
+
+    // Items enter from the right edge, after 1.5s the track moved by 300px.
+    runner.add(TestCase {
+        name: "ticker/scroll_left_mid_scroll",
+        steps: vec![
+            Step::UpdateSceneJson(include_str!("./ticker/scroll_left.scene.json")),
+            Step::RenderWithSnapshot(Duration::from_millis(1500)),
+        ],
+        ..Default::default()
+    });
+
+    runner.run()
+}
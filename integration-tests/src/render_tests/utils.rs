@@ -22,6 +22,10 @@ pub(super) fn frame_to_rgba(frame: &Frame) -> Vec<u8> {
         FrameData::Rgba8UnormWgpuTexture(texture) => read_rgba_texture(texture).to_vec(),
         FrameData::Nv12WgpuTexture(_) => panic!("unsupported"),
         FrameData::Nv12(_) => panic!("unsupported"),
+        FrameData::PlanarYuv420P10(planes) => {
+            yuv_frame_to_rgba(frame, &yuv_planes_10bit_to_8bit(planes))
+        }
+        FrameData::P010(_) => panic!("unsupported"),
     }
 }
 
@@ -61,6 +65,21 @@ pub(super) fn yuv_frame_to_rgba(frame: &Frame, planes: &YuvPlanes) -> Vec<u8> {
     rgba_data
 }
 
+/// Converts 10-bit samples (stored in 16-bit little-endian values) to 8-bit ones.
+fn yuv_planes_10bit_to_8bit(planes: &YuvPlanes) -> YuvPlanes {
+    let convert = |plane: &bytes::Bytes| -> bytes::Bytes {
+        plane
+            .chunks_exact(2)
+            .map(|sample| (u16::from_le_bytes([sample[0], sample[1]]) >> 2) as u8)
+            .collect()
+    };
+    YuvPlanes {
+        y_plane: convert(&planes.y_plane),
+        u_plane: convert(&planes.u_plane),
+        v_plane: convert(&planes.v_plane),
+    }
+}
+
 fn get_graphics_ctx() -> GraphicsContext {
     static CTX: OnceLock<GraphicsContext> = OnceLock::new();
     CTX.get_or_init(|| {
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use smelter_render::{
+    ColorInfo, Frame, FrameData, FrameSet, OutputFrameFormat, OutputId, Renderer, RendererId,
+    RendererSpec, Resolution, SnapshotSource, YuvPlanes,
+    error::RegisterRendererError,
+    scene::{Component, ImagePlayback, RGBAColor, VideoClipComponent},
+    video_clip::{MAX_VIDEO_CLIP_SIZE, VideoClipSpec},
+};
+
+use super::utils::create_renderer;
+
+const RESOLUTION: Resolution = Resolution {
+    width: 8,
+    height: 2,
+};
+
+const WHITE: RGBAColor = RGBAColor(255, 255, 255, 255);
+const BLACK: RGBAColor = RGBAColor(0, 0, 0, 255);
+
+fn clip_id() -> RendererId {
+    RendererId("clip".into())
+}
+
+fn output_id() -> OutputId {
+    OutputId("output_1".into())
+}
+
+/// Solid color frame, `y` is a limited range luma value.
+fn frame(resolution: Resolution, y: u8, pts: Duration) -> Frame {
+    let pixels = resolution.width * resolution.height;
+    Frame {
+        data: FrameData::PlanarYuv420(YuvPlanes {
+            y_plane: Bytes::from(vec![y; pixels]),
+            u_plane: Bytes::from(vec![128; pixels / 4]),
+            v_plane: Bytes::from(vec![128; pixels / 4]),
+        }),
+        resolution,
+        pts,
+        color: ColorInfo::default(),
+    }
+}
+
+/// Registers 1 second clip, white for the first half and black for the second one.
+fn renderer_with_clip(playback: ImagePlayback) -> Renderer {
+    let mut renderer = create_renderer();
+    let spec = VideoClipSpec {
+        frames: vec![
+            frame(RESOLUTION, 235, Duration::ZERO),
+            frame(RESOLUTION, 16, Duration::from_millis(500)),
+        ],
+        duration: Duration::from_secs(1),
+    };
+    renderer
+        .register_renderer(clip_id(), RendererSpec::VideoClip(spec))
+        .unwrap();
+    renderer
+        .update_scene(
+            output_id(),
+            RESOLUTION,
+            OutputFrameFormat::RgbaWgpuTexture,
+            Component::VideoClip(VideoClipComponent {
+                id: None,
+                clip_id: clip_id(),
+                width: None,
+                height: None,
+                playback,
+            }),
+        )
+        .unwrap();
+    renderer
+}
+
+fn assert_color_at(renderer: &Renderer, pts: Duration, color: RGBAColor) {
+    renderer.render(FrameSet::new(pts)).unwrap();
+    let snapshot = renderer
+        .snapshot(&SnapshotSource::Output(output_id()))
+        .unwrap();
+    let RGBAColor(r, g, b, a) = color;
+    let mismatched = snapshot.data.chunks(4).any(|pixel| {
+        pixel
+            .iter()
+            .zip([r, g, b, a])
+            .any(|(p, c)| p.abs_diff(c) > 2)
+    });
+    assert!(
+        !mismatched,
+        "pts: {pts:?}, actual: {:?}, expected: {color:?}",
+        snapshot.data
+    );
+}
+
+#[test]
+fn video_clip_loop() {
+    let renderer = renderer_with_clip(ImagePlayback {
+        restart_on_mount: false,
+        ..Default::default()
+    });
+    assert_color_at(&renderer, Duration::ZERO, WHITE);
+    assert_color_at(&renderer, Duration::from_millis(600), BLACK);
+    assert_color_at(&renderer, Duration::from_millis(1100), WHITE);
+    assert_color_at(&renderer, Duration::from_millis(1600), BLACK);
+}
+
+/// After the last loop the final frame is displayed.
+#[test]
+fn video_clip_loop_count() {
+    let renderer = renderer_with_clip(ImagePlayback {
+        loop_count: Some(1),
+        restart_on_mount: false,
+        start_at: Duration::from_millis(250),
+    });
+    assert_color_at(&renderer, Duration::ZERO, WHITE);
+    assert_color_at(&renderer, Duration::from_millis(300), BLACK);
+    assert_color_at(&renderer, Duration::from_millis(1100), BLACK);
+}
+
+/// Clips are rejected based on the size of decoded frames, before anything
+/// is uploaded to the GPU.
+#[test]
+fn video_clip_too_large() {
+    let renderer = create_renderer();
+    let resolution = Resolution {
+        width: 4096,
+        height: 4096,
+    };
+    let frame_size = resolution.width * resolution.height * 4;
+    let frames = (0..=MAX_VIDEO_CLIP_SIZE / frame_size)
+        .map(|i| Frame {
+            data: FrameData::PlanarYuv420(YuvPlanes {
+                y_plane: Bytes::new(),
+                u_plane: Bytes::new(),
+                v_plane: Bytes::new(),
+            }),
+            resolution,
+            pts: Duration::from_millis(i as u64 * 40),
+            color: ColorInfo::default(),
+        })
+        .collect();
+    let spec = VideoClipSpec {
+        frames,
+        duration: Duration::from_secs(10),
+    };
+
+    let result = renderer.register_renderer(clip_id(), RendererSpec::VideoClip(spec));
+    assert!(matches!(result, Err(RegisterRendererError::VideoClip(..))));
+}
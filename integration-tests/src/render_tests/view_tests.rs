@@ -252,6 +252,38 @@ fn view_tests() {
         )),
         ..default.clone()
     });
+    runner.add(TestCase {
+        name: "view/flex_gap_justify_content",
+        steps: test_steps_from_scene(include_str!("./view/flex_gap_justify_content.scene.json")),
+        ..default.clone()
+    });
+    runner.add(TestCase {
+        name: "view/flex_align_items",
+        steps: test_steps_from_scene(include_str!("./view/flex_align_items.scene.json")),
+        ..default.clone()
+    });
+    runner.add(TestCase {
+        name: "view/flex_wrap",
+        steps: test_steps_from_scene(include_str!("./view/flex_wrap.scene.json")),
+        ..default.clone()
+    });
+    runner.add(TestCase {
+        name: "view/flex_grow",
+        steps: test_steps_from_scene(include_str!("./view/flex_grow.scene.json")),
+        ..default.clone()
+    });
+    runner.add(TestCase {
+        name: "view/flex_shrink",
+        steps: test_steps_from_scene(include_str!("./view/flex_shrink.scene.json")),
+        ..default.clone()
+    });
+    runner.add(TestCase {
+        name: "view/opacity_overlapping_children",
+        steps: test_steps_from_scene(include_str!(
+            "./view/opacity_overlapping_children.scene.json"
+        )),
+        ..default.clone()
+    });
 
     runner.run()
 }
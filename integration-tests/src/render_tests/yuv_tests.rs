@@ -2,15 +2,17 @@ use core::panic;
 use std::{sync::Arc, time::Duration};
 
 use smelter_render::{
-    OutputFrameFormat, RendererId, RendererSpec, Resolution,
+    ColorInfo, ColorMatrix, ColorPrimaries, ColorRange, ColorTransfer, FrameData, FrameSet,
+    InputId, NvPlanes, OutputColorSpace, OutputFrameFormat, OutputId, RendererId, RendererSpec,
+    Resolution, YuvPlanes,
     scene::{
-        BorderRadius, Component, Overflow, Position, RGBAColor, ShaderComponent, Size,
-        ViewChildrenDirection, ViewComponent,
+        AlignItems, BlendMode, BorderRadius, Component, Flex, InputStreamComponent, JustifyContent,
+        Overflow, Position, RGBAColor, ShaderComponent, Size, ViewChildrenDirection, ViewComponent,
     },
     shader::ShaderSpec,
 };
 
-use super::{Step, test_case::TestCase};
+use super::{Step, input::TestInput, test_case::TestCase, utils::create_renderer};
 
 fn run_case(test_case: TestCase, expected: &[u8]) {
     let snapshots = test_case.generate_snapshots();
@@ -97,6 +99,7 @@ fn yuv_test_uniform_color() {
                     height: None,
                 },
                 transition: None,
+                animation: None,
                 overflow: Overflow::Hidden,
                 background_color: RGBAColor(50, 0, 0, 255),
                 border_radius: BorderRadius::ZERO,
@@ -104,6 +107,13 @@ fn yuv_test_uniform_color() {
                 border_color: RGBAColor(0, 0, 0, 0),
                 box_shadow: vec![],
                 padding: Default::default(),
+                gap: 0.0,
+                justify_content: JustifyContent::Start,
+                align_items: AlignItems::Stretch,
+                wrap: false,
+                flex: Flex::default(),
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
             })),
             Step::RenderWithSnapshot(Duration::ZERO),
         ],
@@ -113,7 +123,13 @@ fn yuv_test_uniform_color() {
         output_format: OutputFrameFormat::RgbaWgpuTexture,
         ..yuv_case.clone()
     };
+    let yuv10_case = TestCase {
+        output_format: OutputFrameFormat::PlanarYuv420P10Bytes(Default::default()),
+        ..yuv_case.clone()
+    };
 
+    // 10-bit output is compared after dropping the 2 least significant bits
+    run_case(yuv10_case, &uniform_rgba(49, 0, 0, 16));
     #[rustfmt::skip]
     run_case(
         yuv_case,
@@ -130,3 +146,236 @@ fn yuv_test_uniform_color() {
         ],
     );
 }
+
+fn uniform_rgba(r: u8, g: u8, b: u8, pixels: usize) -> Vec<u8> {
+    [r, g, b, 255].repeat(pixels)
+}
+
+/// Input with a single color in P010 format (10-bit samples in the most significant
+/// bits of 16-bit little-endian values).
+fn uniform_p010_input(resolution: Resolution, yuv: (u16, u16, u16), color: ColorInfo) -> TestInput {
+    let (y, u, v) = yuv;
+    let pixels = resolution.width * resolution.height;
+    let y_plane = (y << 6).to_le_bytes().repeat(pixels);
+    let uv_plane = [(u << 6).to_le_bytes(), (v << 6).to_le_bytes()]
+        .concat()
+        .repeat(pixels / 4);
+    TestInput {
+        name: "input_1".into(),
+        resolution,
+        data: FrameData::P010(NvPlanes {
+            y_plane: y_plane.into(),
+            uv_planes: uv_plane.into(),
+        }),
+        color,
+    }
+}
+
+fn p010_case(yuv: (u16, u16, u16), color: ColorInfo) -> TestCase {
+    let resolution = Resolution {
+        width: 8,
+        height: 2,
+    };
+    TestCase {
+        inputs: vec![uniform_p010_input(resolution, yuv, color)],
+        resolution,
+        output_format: OutputFrameFormat::RgbaWgpuTexture,
+        steps: vec![
+            Step::UpdateScene(Component::InputStream(InputStreamComponent {
+                id: None,
+                input_id: InputId("input_1".into()),
+            })),
+            Step::RenderWithSnapshot(Duration::ZERO),
+        ],
+        ..Default::default()
+    }
+}
+
+/// Test how 10-bit SDR and HDR inputs are converted. HDR inputs are tone-mapped to SDR,
+/// highlights above 75% of the SDR reference white (203 nits) are compressed.
+#[test]
+fn yuv10_input_transfer() {
+    let hdr = |transfer| ColorInfo {
+        matrix: ColorMatrix::Bt2020,
+        primaries: ColorPrimaries::Bt2020,
+        transfer,
+        ..Default::default()
+    };
+    // Limited range gray with Y' = 0.5 and Y' = 0.75
+    let mid_gray = (502, 512, 512);
+    let light_gray = (721, 512, 512);
+
+    run_case(
+        p010_case(mid_gray, ColorInfo::default()),
+        &uniform_rgba(128, 128, 128, 16),
+    );
+    run_case(
+        p010_case(light_gray, ColorInfo::default()),
+        &uniform_rgba(191, 191, 191, 16),
+    );
+
+    // PQ: ~92 nits is below the knee, ~983 nits is compressed close to the SDR white
+    run_case(
+        p010_case(mid_gray, hdr(ColorTransfer::Pq)),
+        &uniform_rgba(184, 184, 184, 16),
+    );
+    run_case(
+        p010_case(light_gray, hdr(ColorTransfer::Pq)),
+        &uniform_rgba(255, 255, 255, 16),
+    );
+
+    // HLG with 1000 nits nominal peak
+    run_case(
+        p010_case(mid_gray, hdr(ColorTransfer::Hlg)),
+        &uniform_rgba(143, 143, 143, 16),
+    );
+    run_case(
+        p010_case(light_gray, hdr(ColorTransfer::Hlg)),
+        &uniform_rgba(241, 241, 241, 16),
+    );
+}
+
+fn uniform_view(background_color: RGBAColor) -> Component {
+    Component::View(ViewComponent {
+        id: None,
+        children: vec![],
+        direction: ViewChildrenDirection::Row,
+        position: Position::Static {
+            width: None,
+            height: None,
+        },
+        transition: None,
+        animation: None,
+        overflow: Overflow::Hidden,
+        background_color,
+        border_radius: BorderRadius::ZERO,
+        border_width: 0.0,
+        border_color: RGBAColor(0, 0, 0, 0),
+        box_shadow: vec![],
+        padding: Default::default(),
+        gap: 0.0,
+        justify_content: JustifyContent::Start,
+        align_items: AlignItems::Stretch,
+        wrap: false,
+        flex: Flex::default(),
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+    })
+}
+
+/// Renders a single color and returns the first Y, U and V sample of the output frame.
+fn render_yuv_samples(color: RGBAColor, color_space: OutputColorSpace) -> [u8; 3] {
+    let output_id = OutputId("output_1".into());
+    let mut renderer = create_renderer();
+    renderer
+        .update_scene(
+            output_id.clone(),
+            Resolution {
+                width: 8,
+                height: 2,
+            },
+            OutputFrameFormat::PlanarYuv420Bytes(color_space),
+            uniform_view(color),
+        )
+        .unwrap();
+    let mut outputs = renderer.render(FrameSet::new(Duration::ZERO)).unwrap();
+    let frame = outputs.frames.remove(&output_id).unwrap();
+    assert_eq!(frame.color, color_space.color_info());
+    let FrameData::PlanarYuv420(planes) = frame.data else {
+        panic!("Unexpected output format {:?}", frame.data);
+    };
+    [planes.y_plane[0], planes.u_plane[0], planes.v_plane[0]]
+}
+
+/// Input with a single color in the planar YUV 4:2:0 format.
+fn uniform_yuv420_input(resolution: Resolution, yuv: [u8; 3], color: ColorInfo) -> TestInput {
+    let [y, u, v] = yuv;
+    let pixels = resolution.width * resolution.height;
+    TestInput {
+        name: "input_1".into(),
+        resolution,
+        data: FrameData::PlanarYuv420(YuvPlanes {
+            y_plane: vec![y; pixels].into(),
+            u_plane: vec![u; pixels / 4].into(),
+            v_plane: vec![v; pixels / 4].into(),
+        }),
+        color,
+    }
+}
+
+fn yuv420_case(yuv: [u8; 3], color: ColorInfo) -> TestCase {
+    let resolution = Resolution {
+        width: 8,
+        height: 2,
+    };
+    TestCase {
+        inputs: vec![uniform_yuv420_input(resolution, yuv, color)],
+        resolution,
+        output_format: OutputFrameFormat::RgbaWgpuTexture,
+        steps: vec![
+            Step::UpdateScene(Component::InputStream(InputStreamComponent {
+                id: None,
+                input_id: InputId("input_1".into()),
+            })),
+            Step::RenderWithSnapshot(Duration::ZERO),
+        ],
+        ..Default::default()
+    }
+}
+
+/// YUV samples of RGB(200, 100, 50) for different matrices and ranges. The same samples
+/// are used to test conversion of outputs and inputs.
+const ORANGE_SAMPLES: [(ColorMatrix, ColorRange, [u8; 3]); 4] = [
+    (ColorMatrix::Bt709, ColorRange::Limited, [117, 96, 174]),
+    (ColorMatrix::Bt601, ColorRange::Limited, [123, 91, 175]),
+    (ColorMatrix::Bt709, ColorRange::Full, [118, 91, 180]),
+    (ColorMatrix::Bt601, ColorRange::Full, [124, 86, 182]),
+];
+
+#[test]
+fn yuv_output_color_space() {
+    for (matrix, range, expected) in ORANGE_SAMPLES {
+        let samples = render_yuv_samples(
+            RGBAColor(200, 100, 50, 255),
+            OutputColorSpace { matrix, range },
+        );
+        let mismatched = samples
+            .iter()
+            .zip(expected)
+            .any(|(actual, expected)| u8::abs_diff(*actual, expected) > 1);
+        assert!(
+            !mismatched,
+            "matrix: {matrix:?}, range: {range:?}, actual: {samples:?}, expected: {expected:?}"
+        );
+    }
+}
+
+#[test]
+fn yuv_input_color_space() {
+    for (matrix, range, samples) in ORANGE_SAMPLES {
+        let color = ColorInfo {
+            matrix,
+            range,
+            ..Default::default()
+        };
+        run_case(yuv420_case(samples, color), &uniform_rgba(200, 100, 50, 16));
+    }
+
+    // Full range `yuvj420p` ignores the range from the color info.
+    let resolution = Resolution {
+        width: 8,
+        height: 2,
+    };
+    let mut input = uniform_yuv420_input(resolution, [118, 91, 180], ColorInfo::default());
+    let FrameData::PlanarYuv420(planes) = input.data else {
+        unreachable!()
+    };
+    input.data = FrameData::PlanarYuvJ420(planes);
+    run_case(
+        TestCase {
+            inputs: vec![input],
+            ..yuv420_case([0; 3], ColorInfo::default())
+        },
+        &uniform_rgba(200, 100, 50, 16),
+    );
+}
@@ -10,7 +10,7 @@ use ffmpeg_next::{
     frame,
     media::Type,
 };
-use smelter_render::{Frame, FrameData, Resolution, YuvPlanes};
+use smelter_render::{ColorInfo, Frame, FrameData, Resolution, YuvPlanes};
 use webrtc::rtp::{self, codecs::h264::H264Packet, packetizer::Depacketizer};
 
 pub struct VideoDecoder {
@@ -93,6 +93,7 @@ impl VideoDecoder {
                 data,
                 resolution,
                 pts: Duration::from_micros(pts as u64),
+                color: ColorInfo::default(),
             });
         }
 
@@ -28,6 +28,17 @@ pub enum AudioMixingStrategy {
     SumScale,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Copy)]
+#[serde(deny_unknown_fields)]
+pub struct LoudnessNormalization {
+    /// Target loudness in LUFS measured as defined in EBU R128, e.g. `-23` for broadcast
+    /// or `-14` for streaming platforms. Value has to be in `[-70, 0]` range.
+    pub target_lufs: f64,
+    /// (**default=`-1.0`**) Maximum true peak of the normalized audio in dBTP.
+    /// Value has to be in `[-20, 0]` range.
+    pub true_peak_limit_dbtp: Option<f64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum AudioChannels {
@@ -75,6 +86,28 @@ impl From<AudioMixingStrategy> for core::AudioMixingStrategy {
     }
 }
 
+impl TryFrom<LoudnessNormalization> for core::LoudnessNormalizationOptions {
+    type Error = TypeError;
+
+    fn try_from(value: LoudnessNormalization) -> Result<Self, Self::Error> {
+        if !(-70.0..=0.0).contains(&value.target_lufs) {
+            return Err(TypeError::new(
+                "Loudness normalization target has to be in [-70, 0] range.",
+            ));
+        }
+        let true_peak_limit_dbtp = value.true_peak_limit_dbtp.unwrap_or(-1.0);
+        if !(-20.0..=0.0).contains(&true_peak_limit_dbtp) {
+            return Err(TypeError::new(
+                "Loudness normalization true peak limit has to be in [-20, 0] range.",
+            ));
+        }
+        Ok(Self {
+            target_lufs: value.target_lufs,
+            true_peak_limit_dbtp,
+        })
+    }
+}
+
 impl From<AudioChannels> for smelter_core::AudioChannels {
     fn from(value: AudioChannels) -> Self {
         match value {
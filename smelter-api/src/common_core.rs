@@ -1,6 +1,8 @@
+mod audio_delay;
 mod framerate;
 mod protocol;
 
+pub use audio_delay::*;
 pub use framerate::*;
 pub use protocol::*;
 
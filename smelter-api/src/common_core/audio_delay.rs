@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use crate::common_core::prelude as core;
+use crate::*;
+
+/// Largest accepted shift of input audio in both directions.
+pub const MAX_AUDIO_DELAY_MS: f64 = 10_000.0;
+
+/// Value of the `audio_delay_ms` field. Positive values delay audio, negative values
+/// play it earlier.
+#[derive(Debug, Clone, Copy)]
+pub struct AudioDelayMs(pub f64);
+
+impl TryFrom<AudioDelayMs> for core::AudioDelay {
+    type Error = TypeError;
+
+    fn try_from(AudioDelayMs(delay_ms): AudioDelayMs) -> Result<Self, Self::Error> {
+        if !(-MAX_AUDIO_DELAY_MS..=MAX_AUDIO_DELAY_MS).contains(&delay_ms) {
+            return Err(TypeError::new(format!(
+                "\"audio_delay_ms\" has to be in the [-{MAX_AUDIO_DELAY_MS}, {MAX_AUDIO_DELAY_MS}] range."
+            )));
+        }
+        let duration = Duration::from_secs_f64(delay_ms.abs() / 1000.0);
+        let delay = match delay_ms {
+            delay_ms if delay_ms > 0.0 => Self::Delay(duration),
+            delay_ms if delay_ms < 0.0 => Self::Advance(duration),
+            _ => Self::None,
+        };
+        Ok(delay)
+    }
+}
+
+impl AudioDelayMs {
+    /// Converts optional `audio_delay_ms` field of an input registration request.
+    pub(crate) fn from_option(delay_ms: Option<f64>) -> Result<core::AudioDelay, TypeError> {
+        delay_ms
+            .map(|delay_ms| AudioDelayMs(delay_ms).try_into())
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_delay_ms() {
+        let delay = |ms| core::AudioDelay::try_from(AudioDelayMs(ms));
+        assert_eq!(
+            delay(150.0),
+            Ok(core::AudioDelay::Delay(Duration::from_millis(150)))
+        );
+        assert_eq!(
+            delay(-40.0),
+            Ok(core::AudioDelay::Advance(Duration::from_millis(40)))
+        );
+        assert_eq!(delay(0.0), Ok(core::AudioDelay::None));
+        assert_eq!(
+            delay(MAX_AUDIO_DELAY_MS),
+            Ok(core::AudioDelay::Delay(Duration::from_secs(10)))
+        );
+        for invalid in [f64::NAN, f64::INFINITY, -1e300, MAX_AUDIO_DELAY_MS + 1.0] {
+            assert!(delay(invalid).is_err(), "audio_delay_ms={invalid}");
+        }
+        assert_eq!(AudioDelayMs::from_option(None), Ok(core::AudioDelay::None));
+    }
+}
@@ -14,6 +14,8 @@ impl TryFrom<Framerate> for smelter_render::Framerate {
     type Error = TypeError;
 
     fn try_from(framerate: Framerate) -> Result<Self, Self::Error> {
+        const ZERO_ERROR_MESSAGE: &str =
+            "Framerate numerator and denominator have to be larger than 0.";
         const ERROR_MESSAGE: &str = "Framerate needs to be an unsigned integer or a string in the \"NUM/DEN\" format, where NUM and DEN are both unsigned integers.";
         match framerate {
             Framerate::String(text) => {
@@ -26,9 +28,37 @@ impl TryFrom<Framerate> for smelter_render::Framerate {
                 let den = den_str
                     .parse::<u32>()
                     .or(Err(TypeError::new(ERROR_MESSAGE)))?;
+                if num == 0 || den == 0 {
+                    return Err(TypeError::new(ZERO_ERROR_MESSAGE));
+                }
                 Ok(smelter_render::Framerate { num, den })
             }
+            Framerate::U32(0) => Err(TypeError::new(ZERO_ERROR_MESSAGE)),
             Framerate::U32(num) => Ok(smelter_render::Framerate { num, den: 1 }),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn framerate(framerate: Framerate) -> Result<(u32, u32), TypeError> {
+        let framerate: smelter_render::Framerate = framerate.try_into()?;
+        Ok((framerate.num, framerate.den))
+    }
+
+    #[test]
+    fn test_framerate() {
+        assert_eq!(framerate(Framerate::U32(30)).unwrap(), (30, 1));
+        assert_eq!(
+            framerate(Framerate::String("30000/1001".into())).unwrap(),
+            (30000, 1001)
+        );
+
+        assert!(framerate(Framerate::U32(0)).is_err());
+        assert!(framerate(Framerate::String("0/1".into())).is_err());
+        assert!(framerate(Framerate::String("30/0".into())).is_err());
+        assert!(framerate(Framerate::String("30".into())).is_err());
+    }
+}
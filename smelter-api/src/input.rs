@@ -1,9 +1,15 @@
 mod decklink;
 mod decklink_into;
+mod failover;
+mod failover_into;
 mod hls;
 mod hls_into;
 mod mp4;
 mod mp4_into;
+mod playlist;
+mod playlist_into;
+mod replay;
+mod replay_into;
 mod rtmp;
 mod rtmp_into;
 mod rtp;
@@ -16,8 +22,11 @@ mod whip;
 mod whip_into;
 
 pub use decklink::*;
+pub use failover::*;
 pub use hls::*;
 pub use mp4::*;
+pub use playlist::*;
+pub use replay::*;
 pub use rtmp::*;
 pub use rtp::*;
 pub use v4l2::*;
@@ -1,6 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::InputFailover;
+
 /// Capture streams from devices connected to Blackmagic DeckLink card.
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -34,4 +36,12 @@ pub struct DeckLink {
     /// (**default=`false`**) If input is required and frames are not processed
     /// on time, then Smelter will delay producing output frames.
     pub required: Option<bool>,
+    /// Shift in milliseconds applied only to audio of this input to fix audio/video
+    /// synchronization. Positive values delay audio, negative values play it earlier.
+    /// Has to be in the `[-10000, 10000]` range. It can be changed later with the
+    /// `/api/input/:id/audio_delay` request.
+    pub audio_delay_ms: Option<f64>,
+    /// Backup inputs and slate image used when this input stops delivering data.
+    /// It can be changed later with the `/api/input/:id/failover` request.
+    pub failover: Option<InputFailover>,
 }
@@ -30,7 +30,9 @@ impl TryFrom<DeckLink> for core::RegisterInputOptions {
             queue_options: core::QueueInputOptions {
                 required: value.required.unwrap_or(false),
                 offset: None,
+                audio_delay: AudioDelayMs::from_option(value.audio_delay_ms)?,
             },
+            failover: value.failover.map(Into::into).unwrap_or_default(),
         })
     }
 
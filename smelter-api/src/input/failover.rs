@@ -0,0 +1,17 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{InputId, RendererId};
+
+/// Failover configuration of an input.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InputFailover {
+    /// Inputs used in place of this input when it stops delivering data for longer than
+    /// `SMELTER_STREAM_FALLBACK_TIMEOUT_MS`. The first available input from the list is
+    /// used. Audio and video are switched independently.
+    pub backup_inputs: Option<Vec<InputId>>,
+    /// Id of a registered image that is rendered when neither the input nor any of its
+    /// backups deliver frames.
+    pub slate_image_id: Option<RendererId>,
+}
@@ -0,0 +1,16 @@
+use crate::common_core::prelude as core;
+use crate::*;
+
+impl From<InputFailover> for core::InputFailoverOptions {
+    fn from(value: InputFailover) -> Self {
+        Self {
+            backup_inputs: value
+                .backup_inputs
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            slate_image_id: value.slate_image_id.map(Into::into),
+        }
+    }
+}
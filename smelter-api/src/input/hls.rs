@@ -3,6 +3,8 @@ use std::{collections::HashMap, sync::Arc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::InputFailover;
+
 /// Parameters for an input stream from HLS source.
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -16,6 +18,14 @@ pub struct HlsInput {
     /// not defined then the stream will be synchronized based on the delivery time of the initial
     /// frames.
     pub offset_ms: Option<f64>,
+    /// Shift in milliseconds applied only to audio of this input to fix audio/video
+    /// synchronization. Positive values delay audio, negative values play it earlier.
+    /// Has to be in the `[-10000, 10000]` range. It can be changed later with the
+    /// `/api/input/:id/audio_delay` request.
+    pub audio_delay_ms: Option<f64>,
+    /// Backup inputs and slate image used when this input stops delivering data.
+    /// It can be changed later with the `/api/input/:id/failover` request.
+    pub failover: Option<InputFailover>,
     /// Assigns which decoder should be used for media encoded with a specific codec.
     pub decoder_map: Option<HashMap<InputHlsCodec, HlsVideoDecoderOptions>>,
 }
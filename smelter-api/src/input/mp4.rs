@@ -4,6 +4,8 @@ use std::{collections::HashMap, path::Path, sync::Arc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::InputFailover;
+
 /// Input stream from MP4 file.
 /// Exactly one of `url` and `path` has to be defined.
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
@@ -22,6 +24,14 @@ pub struct Mp4Input {
     /// Offset in milliseconds relative to the pipeline start (start request). If offset is
     /// not defined then stream is synchronized based on the first frames delivery time.
     pub offset_ms: Option<f64>,
+    /// Shift in milliseconds applied only to audio of this input to fix audio/video
+    /// synchronization. Positive values delay audio, negative values play it earlier.
+    /// Has to be in the `[-10000, 10000]` range. It can be changed later with the
+    /// `/api/input/:id/audio_delay` request.
+    pub audio_delay_ms: Option<f64>,
+    /// Backup inputs and slate image used when this input stops delivering data.
+    /// It can be changed later with the `/api/input/:id/failover` request.
+    pub failover: Option<InputFailover>,
     /// Assigns which decoder should be used for media encoded with a specific codec.
     pub decoder_map: Option<HashMap<InputMp4Codec, Mp4VideoDecoderOptions>>,
 }
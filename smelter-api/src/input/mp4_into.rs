@@ -12,6 +12,8 @@ impl TryFrom<Mp4Input> for core::RegisterInputOptions {
             path,
             required,
             offset_ms,
+            audio_delay_ms,
+            failover,
             should_loop,
             decoder_map,
         } = value;
@@ -21,12 +23,14 @@ impl TryFrom<Mp4Input> for core::RegisterInputOptions {
         let queue_options = smelter_core::QueueInputOptions {
             required: required.unwrap_or(false),
             offset: offset_ms.map(|offset_ms| Duration::from_secs_f64(offset_ms / 1000.0)),
+            audio_delay: AudioDelayMs::from_option(audio_delay_ms)?,
         };
 
         let buffer = match &queue_options {
             core::QueueInputOptions {
                 required: false,
                 offset: None,
+                ..
             } => core::InputBufferOptions::Const(None),
             _ => core::InputBufferOptions::None,
         };
@@ -58,6 +62,7 @@ impl TryFrom<Mp4Input> for core::RegisterInputOptions {
                 buffer,
             }),
             queue_options,
+            failover: failover.map(Into::into).unwrap_or_default(),
         })
     }
 }
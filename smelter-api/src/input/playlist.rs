@@ -0,0 +1,47 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{InputFailover, InputMp4Codec, Mp4VideoDecoderOptions};
+
+/// Input that plays MP4 files one after another. Timestamps of consecutive items are
+/// continuous, so there are no gaps between them. Items can be added or removed while
+/// the input is playing.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PlaylistInput {
+    /// Items played in order. When the playlist runs out of items, the input waits
+    /// for new ones.
+    pub items: Vec<PlaylistItem>,
+    /// (**default=`false`**) If input is required and frames are not processed
+    /// on time, then Smelter will delay producing output frames.
+    pub required: Option<bool>,
+    /// Offset in milliseconds relative to the pipeline start (start request). If offset is
+    /// not defined then stream is synchronized based on the first frames delivery time.
+    pub offset_ms: Option<f64>,
+    /// Shift in milliseconds applied only to audio of this input to fix audio/video
+    /// synchronization. Positive values delay audio, negative values play it earlier.
+    /// Has to be in the `[-10000, 10000]` range. It can be changed later with the
+    /// `/api/input/:id/audio_delay` request.
+    pub audio_delay_ms: Option<f64>,
+    /// Backup inputs and slate image used when this input stops delivering data.
+    /// It can be changed later with the `/api/input/:id/failover` request.
+    pub failover: Option<InputFailover>,
+    /// Assigns which decoder should be used for media encoded with a specific codec.
+    pub decoder_map: Option<HashMap<InputMp4Codec, Mp4VideoDecoderOptions>>,
+}
+
+/// MP4 file played as a part of a playlist.
+/// Exactly one of `url` and `path` has to be defined.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PlaylistItem {
+    /// Identifies the item in playlist events and updates. It has to be unique
+    /// among queued items.
+    pub item_id: Arc<str>,
+    /// URL of the MP4 file.
+    pub url: Option<Arc<str>>,
+    /// Path to the MP4 file.
+    pub path: Option<Arc<Path>>,
+}
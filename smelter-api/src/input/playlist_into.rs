@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use crate::common_core::prelude as core;
+use crate::*;
+
+impl TryFrom<PlaylistInput> for core::RegisterInputOptions {
+    type Error = TypeError;
+
+    fn try_from(value: PlaylistInput) -> Result<Self, Self::Error> {
+        let PlaylistInput {
+            items,
+            required,
+            offset_ms,
+            audio_delay_ms,
+            failover,
+            decoder_map,
+        } = value;
+
+        let queue_options = smelter_core::QueueInputOptions {
+            required: required.unwrap_or(false),
+            offset: offset_ms.map(|offset_ms| Duration::from_secs_f64(offset_ms / 1000.0)),
+            audio_delay: AudioDelayMs::from_option(audio_delay_ms)?,
+        };
+
+        let buffer = match &queue_options {
+            core::QueueInputOptions {
+                required: false,
+                offset: None,
+                ..
+            } => core::InputBufferOptions::Const(None),
+            _ => core::InputBufferOptions::None,
+        };
+
+        let h264 = decoder_map
+            .as_ref()
+            .and_then(|decoders| decoders.get(&InputMp4Codec::H264))
+            .map(|decoder| match decoder {
+                Mp4VideoDecoderOptions::FfmpegH264 => core::VideoDecoderOptions::FfmpegH264,
+                Mp4VideoDecoderOptions::VulkanH264 => core::VideoDecoderOptions::VulkanH264,
+            });
+
+        Ok(core::RegisterInputOptions {
+            input_options: core::ProtocolInputOptions::Playlist(core::PlaylistInputOptions {
+                items: items
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                video_decoders: core::Mp4InputVideoDecoders { h264 },
+                buffer,
+            }),
+            queue_options,
+            failover: failover.map(Into::into).unwrap_or_default(),
+        })
+    }
+}
+
+impl TryFrom<PlaylistItem> for core::PlaylistItem {
+    type Error = TypeError;
+
+    fn try_from(value: PlaylistItem) -> Result<Self, Self::Error> {
+        const BAD_URL_PATH_SPEC: &str =
+            "Exactly one of `url` or `path` has to be specified for a playlist item.";
+
+        let source = match (value.url, value.path) {
+            (Some(_), Some(_)) | (None, None) => {
+                return Err(TypeError::new(BAD_URL_PATH_SPEC));
+            }
+            (Some(url), None) => core::Mp4InputSource::Url(url),
+            (None, Some(path)) => core::Mp4InputSource::File(path),
+        };
+
+        Ok(core::PlaylistItem {
+            item_id: value.item_id,
+            source,
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{InputFailover, InputId};
+
+/// Input that replays a time range recorded in the replay buffer of another input.
+/// Replay buffer has to be enabled on the source input with the
+/// `/api/input/:id/replay_buffer` request. The input ends after the range is played.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayInput {
+    /// Id of the input with a replay buffer enabled.
+    pub source_input_id: InputId,
+    /// Start of the replayed range in milliseconds, relative to the pipeline start.
+    pub start_ms: f64,
+    /// End of the replayed range in milliseconds, relative to the pipeline start.
+    pub end_ms: f64,
+    /// (**default=`1.0`**) Playback speed. Values below `1.0` produce slow motion.
+    /// Slowed down audio is played at a lower pitch. Has to be in the `[0.1, 16]` range.
+    pub speed: Option<f64>,
+    /// (**default=`false`**) If input is required and frames are not processed
+    /// on time, then Smelter will delay producing output frames.
+    pub required: Option<bool>,
+    /// Offset in milliseconds relative to the pipeline start (start request). If offset is
+    /// not defined then the replay starts right after the input is registered.
+    pub offset_ms: Option<f64>,
+    /// Shift in milliseconds applied only to audio of this input to fix audio/video
+    /// synchronization. Positive values delay audio, negative values play it earlier.
+    /// Has to be in the `[-10000, 10000]` range. It can be changed later with the
+    /// `/api/input/:id/audio_delay` request.
+    pub audio_delay_ms: Option<f64>,
+    /// Backup inputs and slate image used when this input stops delivering data.
+    /// It can be changed later with the `/api/input/:id/failover` request.
+    pub failover: Option<InputFailover>,
+}
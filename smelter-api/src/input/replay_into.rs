@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use crate::common_core::prelude as core;
+use crate::*;
+
+const MIN_REPLAY_SPEED: f64 = 0.1;
+const MAX_REPLAY_SPEED: f64 = 16.0;
+
+impl TryFrom<ReplayInput> for core::RegisterInputOptions {
+    type Error = TypeError;
+
+    fn try_from(value: ReplayInput) -> Result<Self, Self::Error> {
+        let ReplayInput {
+            source_input_id,
+            start_ms,
+            end_ms,
+            speed,
+            required,
+            offset_ms,
+            audio_delay_ms,
+            failover,
+        } = value;
+
+        let (Ok(start), Ok(end)) = (
+            Duration::try_from_secs_f64(start_ms / 1000.0),
+            Duration::try_from_secs_f64(end_ms / 1000.0),
+        ) else {
+            return Err(TypeError::new(
+                "\"start_ms\" and \"end_ms\" have to be non-negative finite numbers.",
+            ));
+        };
+        if end <= start {
+            return Err(TypeError::new(
+                "\"start_ms\" has to be smaller than \"end_ms\".",
+            ));
+        }
+        let speed = speed.unwrap_or(1.0);
+        if !(MIN_REPLAY_SPEED..=MAX_REPLAY_SPEED).contains(&speed) {
+            return Err(TypeError::new(format!(
+                "\"speed\" has to be in the [{MIN_REPLAY_SPEED}, {MAX_REPLAY_SPEED}] range."
+            )));
+        }
+
+        let queue_options = smelter_core::QueueInputOptions {
+            required: required.unwrap_or(false),
+            offset: offset_ms.map(|offset_ms| Duration::from_secs_f64(offset_ms / 1000.0)),
+            audio_delay: AudioDelayMs::from_option(audio_delay_ms)?,
+        };
+
+        Ok(core::RegisterInputOptions {
+            input_options: core::ProtocolInputOptions::Replay(core::ReplayInputOptions {
+                source_input_id: source_input_id.into(),
+                start,
+                end,
+                speed,
+            }),
+            queue_options,
+            failover: failover.map(Into::into).unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay_options(
+        start_ms: f64,
+        end_ms: f64,
+        speed: Option<f64>,
+    ) -> Result<core::ReplayInputOptions, TypeError> {
+        let input = ReplayInput {
+            source_input_id: serde_json::from_str("\"input_1\"").unwrap(),
+            start_ms,
+            end_ms,
+            speed,
+            required: None,
+            offset_ms: None,
+            audio_delay_ms: None,
+            failover: None,
+        };
+        let options: core::RegisterInputOptions = input.try_into()?;
+        match options.input_options {
+            core::ProtocolInputOptions::Replay(replay) => Ok(replay),
+            _ => unreachable!(),
+        }
+    }
+
+    fn replay_range(start_ms: f64, end_ms: f64) -> Result<(Duration, Duration), TypeError> {
+        let replay = replay_options(start_ms, end_ms, None)?;
+        Ok((replay.start, replay.end))
+    }
+
+    #[test]
+    fn test_replay_range() {
+        assert_eq!(
+            replay_range(1000.0, 2500.0).unwrap(),
+            (Duration::from_secs(1), Duration::from_millis(2500))
+        );
+        for (start_ms, end_ms) in [
+            (-1.0, 1000.0),
+            (1000.0, 1000.0),
+            (2000.0, 1000.0),
+            (0.0, 1e300),
+            (0.0, f64::INFINITY),
+        ] {
+            assert!(
+                replay_range(start_ms, end_ms).is_err(),
+                "start_ms={start_ms}, end_ms={end_ms}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_replay_speed() {
+        let speed = |speed| replay_options(0.0, 1000.0, speed).map(|replay| replay.speed);
+        assert_eq!(speed(None).unwrap(), 1.0);
+        assert_eq!(speed(Some(0.1)).unwrap(), 0.1);
+        assert_eq!(speed(Some(16.0)).unwrap(), 16.0);
+        for value in [0.0, -1.0, 1e-12, 0.09, 16.5, f64::NAN, f64::INFINITY] {
+            assert!(speed(Some(value)).is_err(), "speed={value}");
+        }
+    }
+}
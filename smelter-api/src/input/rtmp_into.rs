@@ -10,18 +10,22 @@ impl TryFrom<RtmpInput> for core::RegisterInputOptions {
             url,
             required,
             offset_ms,
+            audio_delay_ms,
+            failover,
             decoder_map,
         } = value;
 
         let queue_options = smelter_core::QueueInputOptions {
             required: required.unwrap_or(false),
             offset: offset_ms.map(|offset_ms| Duration::from_secs_f64(offset_ms / 1000.0)),
+            audio_delay: AudioDelayMs::from_option(audio_delay_ms)?,
         };
 
         let buffer = match &queue_options {
             core::QueueInputOptions {
                 required: false,
                 offset: None,
+                ..
             } => core::InputBufferOptions::Const(None),
             _ => core::InputBufferOptions::None,
         };
@@ -46,6 +50,7 @@ impl TryFrom<RtmpInput> for core::RegisterInputOptions {
         Ok(core::RegisterInputOptions {
             input_options: core::ProtocolInputOptions::RtmpServer(input_options),
             queue_options,
+            failover: failover.map(Into::into).unwrap_or_default(),
         })
     }
 }
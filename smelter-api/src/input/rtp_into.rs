@@ -17,12 +17,15 @@ impl TryFrom<RtpInput> for core::RegisterInputOptions {
             audio,
             required,
             offset_ms,
+            audio_delay_ms,
+            failover,
             transport_protocol,
         } = value;
 
         let queue_options = smelter_core::QueueInputOptions {
             required: required.unwrap_or(false),
             offset: offset_ms.map(|offset_ms| Duration::from_secs_f64(offset_ms / 1000.0)),
+            audio_delay: AudioDelayMs::from_option(audio_delay_ms)?,
         };
 
         let transport_protocol = transport_protocol.unwrap_or(TransportProtocol::Udp).into();
@@ -31,6 +34,7 @@ impl TryFrom<RtpInput> for core::RegisterInputOptions {
             core::QueueInputOptions {
                 required: false,
                 offset: None,
+                ..
             } => core::InputBufferOptions::Const(None),
             _ => core::InputBufferOptions::None,
         };
@@ -39,6 +43,7 @@ impl TryFrom<RtpInput> for core::RegisterInputOptions {
                 core::QueueInputOptions {
                     required: false,
                     offset: None,
+                    ..
                 } => core::RtpJitterBufferOptions {
                     mode: core::RtpJitterBufferMode::QueueBased,
                     buffer: input_buffer,
@@ -83,6 +88,7 @@ impl TryFrom<RtpInput> for core::RegisterInputOptions {
         Ok(core::RegisterInputOptions {
             input_options,
             queue_options,
+            failover: failover.map(Into::into).unwrap_or_default(),
         })
     }
 }
@@ -9,6 +9,7 @@ impl TryFrom<V4l2Input> for core::RegisterInputOptions {
         let queue_options = smelter_core::QueueInputOptions {
             required: value.required.unwrap_or(false),
             offset: None,
+            audio_delay: core::AudioDelay::None,
         };
 
         Ok(core::RegisterInputOptions {
@@ -22,6 +23,7 @@ impl TryFrom<V4l2Input> for core::RegisterInputOptions {
                     .transpose()?,
             }),
             queue_options,
+            failover: Default::default(),
         })
     }
 
@@ -13,17 +13,21 @@ impl TryFrom<WhepInput> for core::RegisterInputOptions {
             video,
             required,
             offset_ms,
+            audio_delay_ms,
+            failover,
         } = value;
 
         let queue_options = smelter_core::QueueInputOptions {
             required: required.unwrap_or(false),
             offset: offset_ms.map(|offset_ms| Duration::from_secs_f64(offset_ms / 1000.0)),
+            audio_delay: AudioDelayMs::from_option(audio_delay_ms)?,
         };
 
         let jitter_buffer = match &queue_options {
             core::QueueInputOptions {
                 required: false,
                 offset: None,
+                ..
             } => core::RtpJitterBufferOptions {
                 mode: core::RtpJitterBufferMode::QueueBased,
                 buffer: core::InputBufferOptions::LatencyOptimized,
@@ -53,6 +57,7 @@ impl TryFrom<WhepInput> for core::RegisterInputOptions {
         Ok(core::RegisterInputOptions {
             input_options,
             queue_options,
+            failover: failover.map(Into::into).unwrap_or_default(),
         })
     }
 }
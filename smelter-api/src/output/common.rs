@@ -9,6 +9,29 @@ pub enum PixelFormat {
     Yuv420p,
     Yuv422p,
     Yuv444p,
+    /// 10-bit YUV 4:2:0. Video is composed in 8-bit SDR, so it does not preserve more
+    /// precision of 10-bit inputs than `yuv420p`, and HDR inputs are tone-mapped.
+    Yuv420p10,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorSpace {
+    /// ITU-R BT.601, used by SD content.
+    Bt601,
+    /// ITU-R BT.709, used by HD content.
+    Bt709,
+    /// ITU-R BT.2020 with SDR transfer.
+    Bt2020,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorRange {
+    /// Limited (TV) range, e.g. luma values in 16-235 for 8-bit.
+    Limited,
+    /// Full (PC) range.
+    Full,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Copy)]
@@ -80,10 +80,40 @@ impl From<PixelFormat> for core::OutputPixelFormat {
             PixelFormat::Yuv420p => core::OutputPixelFormat::YUV420P,
             PixelFormat::Yuv422p => core::OutputPixelFormat::YUV422P,
             PixelFormat::Yuv444p => core::OutputPixelFormat::YUV444P,
+            PixelFormat::Yuv420p10 => core::OutputPixelFormat::YUV420P10,
         }
     }
 }
 
+impl From<ColorSpace> for smelter_render::ColorMatrix {
+    fn from(value: ColorSpace) -> Self {
+        match value {
+            ColorSpace::Bt601 => smelter_render::ColorMatrix::Bt601,
+            ColorSpace::Bt709 => smelter_render::ColorMatrix::Bt709,
+            ColorSpace::Bt2020 => smelter_render::ColorMatrix::Bt2020,
+        }
+    }
+}
+
+impl From<ColorRange> for smelter_render::ColorRange {
+    fn from(value: ColorRange) -> Self {
+        match value {
+            ColorRange::Limited => smelter_render::ColorRange::Limited,
+            ColorRange::Full => smelter_render::ColorRange::Full,
+        }
+    }
+}
+
+pub(super) fn output_color_space(
+    color_space: Option<ColorSpace>,
+    color_range: Option<ColorRange>,
+) -> smelter_render::OutputColorSpace {
+    smelter_render::OutputColorSpace {
+        matrix: color_space.unwrap_or(ColorSpace::Bt709).into(),
+        range: color_range.unwrap_or(ColorRange::Limited).into(),
+    }
+}
+
 impl TryFrom<VideoEncoderBitrate> for core::VideoEncoderBitrate {
     type Error = TypeError;
 
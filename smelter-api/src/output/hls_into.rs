@@ -1,3 +1,4 @@
+use super::common_into::output_color_space;
 use crate::common_core::prelude as core;
 use crate::*;
 
@@ -21,6 +22,7 @@ impl TryFrom<HlsOutput> for core::RegisterOutputOptions {
         let (video_encoder_options, output_video_options) = match video {
             Some(OutputHlsVideoOptions {
                 resolution,
+                framerate,
                 send_eos_when,
                 encoder,
                 initial,
@@ -29,6 +31,9 @@ impl TryFrom<HlsOutput> for core::RegisterOutputOptions {
                 let output_options = core::RegisterOutputVideoOptions {
                     initial: initial.try_into()?,
                     end_condition: send_eos_when.unwrap_or_default().try_into()?,
+                    framerate: framerate
+                        .map(smelter_render::Framerate::try_from)
+                        .transpose()?,
                 };
 
                 (Some(encoder_options), Some(output_options))
@@ -39,6 +44,7 @@ impl TryFrom<HlsOutput> for core::RegisterOutputOptions {
         let (audio_encoder_options, output_audio_options) = match audio {
             Some(OutputHlsAudioOptions {
                 mixing_strategy,
+                loudness_normalization,
                 send_eos_when,
                 encoder,
                 channels,
@@ -52,6 +58,9 @@ impl TryFrom<HlsOutput> for core::RegisterOutputOptions {
                     mixing_strategy: mixing_strategy
                         .unwrap_or(AudioMixingStrategy::SumClip)
                         .into(),
+                    loudness_normalization: loudness_normalization
+                        .map(TryInto::try_into)
+                        .transpose()?,
                     channels: channels.into(),
                 };
 
@@ -84,24 +93,30 @@ impl HlsVideoEncoderOptions {
                 preset,
                 bitrate,
                 pixel_format,
+                color_space,
+                color_range,
                 ffmpeg_options,
             } => core::VideoEncoderOptions::FfmpegH264(core::FfmpegH264EncoderOptions {
                 preset: preset.unwrap_or(H264EncoderPreset::Fast).into(),
                 resolution: resolution.into(),
                 bitrate: bitrate.map(|b| b.try_into()).transpose()?,
                 pixel_format: pixel_format.unwrap_or(PixelFormat::Yuv420p).into(),
+                color_space: output_color_space(*color_space, *color_range),
                 raw_options: ffmpeg_options
                     .clone()
                     .unwrap_or_default()
                     .into_iter()
                     .collect(),
             }),
-            HlsVideoEncoderOptions::VulkanH264 { bitrate } => {
-                core::VideoEncoderOptions::VulkanH264(core::VulkanH264EncoderOptions {
-                    resolution: resolution.into(),
-                    bitrate: bitrate.map(|bitrate| bitrate.try_into()).transpose()?,
-                })
-            }
+            HlsVideoEncoderOptions::VulkanH264 {
+                bitrate,
+                color_space,
+                color_range,
+            } => core::VideoEncoderOptions::VulkanH264(core::VulkanH264EncoderOptions {
+                resolution: resolution.into(),
+                bitrate: bitrate.map(|bitrate| bitrate.try_into()).transpose()?,
+                color_space: output_color_space(*color_space, *color_range),
+            }),
         };
         Ok(encoder_options)
     }
@@ -22,6 +22,10 @@ pub struct WhepOutput {
 pub struct OutputWhepVideoOptions {
     /// Output resolution in pixels.
     pub resolution: Resolution,
+    /// Output framerate. Must be either an unsigned integer, or a string in the "NUM/DEN"
+    /// format, where NUM and DEN are both unsigned integers. Defaults to the framerate
+    /// configured for the whole Smelter instance.
+    pub framerate: Option<Framerate>,
     /// Condition for termination of the output stream based on the input streams states. If output includes both audio and video streams, then EOS needs to be sent for every type.
     pub send_eos_when: Option<OutputEndCondition>,
     /// Video encoder options.
@@ -44,6 +48,12 @@ pub enum WhepVideoEncoderOptions {
         /// (**default=`"yuv420p"`**) Encoder pixel format.
         pixel_format: Option<PixelFormat>,
 
+        /// (**default=`"bt709"`**) Color space of the encoded video.
+        color_space: Option<ColorSpace>,
+
+        /// (**default=`"limited"`**) Color range of the encoded video.
+        color_range: Option<ColorRange>,
+
         /// Raw FFmpeg encoder options. Visit [docs](https://ffmpeg.org/ffmpeg-codecs.html) to learn more.
         ffmpeg_options: Option<HashMap<Arc<str>, Arc<str>>>,
     },
@@ -65,6 +75,12 @@ pub enum WhepVideoEncoderOptions {
         /// (**default=`"yuv420p"`**) Encoder pixel format.
         pixel_format: Option<PixelFormat>,
 
+        /// (**default=`"bt709"`**) Color space of the encoded video.
+        color_space: Option<ColorSpace>,
+
+        /// (**default=`"limited"`**) Color range of the encoded video.
+        color_range: Option<ColorRange>,
+
         /// Raw FFmpeg encoder options. Visit [docs](https://ffmpeg.org/ffmpeg-codecs.html) to learn more.
         ffmpeg_options: Option<HashMap<Arc<str>, Arc<str>>>,
     },
@@ -73,6 +89,12 @@ pub enum WhepVideoEncoderOptions {
         /// Encoding bitrate. If not provided, bitrate is calculated based on resolution and framerate.
         /// For example at 1080p 30 FPS the average bitrate is 5000 kbit/s and max bitrate is 6250 kbit/s.
         bitrate: Option<VideoEncoderBitrate>,
+
+        /// (**default=`"bt709"`**) Color space of the encoded video.
+        color_space: Option<ColorSpace>,
+
+        /// (**default=`"limited"`**) Color range of the encoded video.
+        color_range: Option<ColorRange>,
     },
 }
 
@@ -81,6 +103,8 @@ pub enum WhepVideoEncoderOptions {
 pub struct OutputWhepAudioOptions {
     /// (**default="sum_clip"**) Specifies how audio should be mixed.
     pub mixing_strategy: Option<AudioMixingStrategy>,
+    /// Normalizes loudness of the mixed audio to the target. Applied after the mixing strategy.
+    pub loudness_normalization: Option<LoudnessNormalization>,
     /// Condition for termination of output stream based on the input streams states.
     pub send_eos_when: Option<OutputEndCondition>,
     /// Audio encoder options.
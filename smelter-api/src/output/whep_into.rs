@@ -1,3 +1,4 @@
+use super::common_into::output_color_space;
 use crate::common_core::prelude as core;
 use crate::*;
 
@@ -20,6 +21,7 @@ impl TryFrom<WhepOutput> for core::RegisterOutputOptions {
         let (video_encoder_options, output_video_options) = match video {
             Some(OutputWhepVideoOptions {
                 resolution,
+                framerate,
                 send_eos_when,
                 encoder,
                 initial,
@@ -28,6 +30,9 @@ impl TryFrom<WhepOutput> for core::RegisterOutputOptions {
                 let output_options = core::RegisterOutputVideoOptions {
                     initial: initial.try_into()?,
                     end_condition: send_eos_when.unwrap_or_default().try_into()?,
+                    framerate: framerate
+                        .map(smelter_render::Framerate::try_from)
+                        .transpose()?,
                 };
                 (Some(encoder_options), Some(output_options))
             }
@@ -37,6 +42,7 @@ impl TryFrom<WhepOutput> for core::RegisterOutputOptions {
         let (audio_encoder_options, output_audio_options) = match audio {
             Some(OutputWhepAudioOptions {
                 mixing_strategy,
+                loudness_normalization,
                 send_eos_when,
                 encoder,
                 channels,
@@ -50,6 +56,9 @@ impl TryFrom<WhepOutput> for core::RegisterOutputOptions {
                     mixing_strategy: mixing_strategy
                         .unwrap_or(AudioMixingStrategy::SumClip)
                         .into(),
+                    loudness_normalization: loudness_normalization
+                        .map(TryInto::try_into)
+                        .transpose()?,
                     channels: channels.into(),
                 };
 
@@ -80,24 +89,30 @@ impl WhepVideoEncoderOptions {
                 preset,
                 bitrate,
                 pixel_format,
+                color_space,
+                color_range,
                 ffmpeg_options,
             } => core::VideoEncoderOptions::FfmpegH264(core::FfmpegH264EncoderOptions {
                 preset: preset.unwrap_or(H264EncoderPreset::Fast).into(),
                 bitrate: bitrate.map(|b| b.try_into()).transpose()?,
                 resolution: resolution.into(),
                 pixel_format: pixel_format.unwrap_or(PixelFormat::Yuv420p).into(),
+                color_space: output_color_space(*color_space, *color_range),
                 raw_options: ffmpeg_options
                     .clone()
                     .unwrap_or_default()
                     .into_iter()
                     .collect(),
             }),
-            WhepVideoEncoderOptions::VulkanH264 { bitrate } => {
-                core::VideoEncoderOptions::VulkanH264(core::VulkanH264EncoderOptions {
-                    resolution: resolution.into(),
-                    bitrate: bitrate.map(|bitrate| bitrate.try_into()).transpose()?,
-                })
-            }
+            WhepVideoEncoderOptions::VulkanH264 {
+                bitrate,
+                color_space,
+                color_range,
+            } => core::VideoEncoderOptions::VulkanH264(core::VulkanH264EncoderOptions {
+                resolution: resolution.into(),
+                bitrate: bitrate.map(|bitrate| bitrate.try_into()).transpose()?,
+                color_space: output_color_space(*color_space, *color_range),
+            }),
             WhepVideoEncoderOptions::FfmpegVp8 {
                 bitrate,
                 ffmpeg_options,
@@ -113,11 +128,14 @@ impl WhepVideoEncoderOptions {
             WhepVideoEncoderOptions::FfmpegVp9 {
                 bitrate,
                 pixel_format,
+                color_space,
+                color_range,
                 ffmpeg_options,
             } => core::VideoEncoderOptions::FfmpegVp9(core::FfmpegVp9EncoderOptions {
                 resolution: resolution.into(),
                 bitrate: bitrate.map(|b| b.try_into()).transpose()?,
                 pixel_format: pixel_format.unwrap_or(PixelFormat::Yuv420p).into(),
+                color_space: output_color_space(*color_space, *color_range),
                 raw_options: ffmpeg_options
                     .clone()
                     .unwrap_or_default()
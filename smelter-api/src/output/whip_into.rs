@@ -1,3 +1,4 @@
+use super::common_into::output_color_space;
 use crate::common_core::prelude as core;
 use crate::*;
 
@@ -24,6 +25,10 @@ impl TryFrom<WhipOutput> for core::RegisterOutputOptions {
                 let output_options = core::RegisterOutputVideoOptions {
                     initial: options.initial.try_into()?,
                     end_condition: options.send_eos_when.unwrap_or_default().try_into()?,
+                    framerate: options
+                        .framerate
+                        .map(smelter_render::Framerate::try_from)
+                        .transpose()?,
                 };
 
                 let encoder_preferences = match options.encoder_preferences.as_deref() {
@@ -48,6 +53,7 @@ impl TryFrom<WhipOutput> for core::RegisterOutputOptions {
         let (output_audio_options, audio_whip_options) = match audio {
             Some(OutputWhipAudioOptions {
                 mixing_strategy,
+                loudness_normalization,
                 send_eos_when,
                 channels,
                 encoder_preferences,
@@ -60,6 +66,9 @@ impl TryFrom<WhipOutput> for core::RegisterOutputOptions {
                     mixing_strategy: mixing_strategy
                         .unwrap_or(AudioMixingStrategy::SumClip)
                         .into(),
+                    loudness_normalization: loudness_normalization
+                        .map(TryInto::try_into)
+                        .transpose()?,
                     channels: channels.into(),
                 };
 
@@ -106,24 +115,30 @@ impl WhipVideoEncoderOptions {
                 preset,
                 bitrate,
                 pixel_format,
+                color_space,
+                color_range,
                 ffmpeg_options,
             } => core::WhipVideoEncoderOptions::FfmpegH264(core::FfmpegH264EncoderOptions {
                 preset: preset.unwrap_or(H264EncoderPreset::Fast).into(),
                 resolution: resolution.into(),
                 bitrate: bitrate.map(|b| b.try_into()).transpose()?,
                 pixel_format: pixel_format.unwrap_or(PixelFormat::Yuv420p).into(),
+                color_space: output_color_space(*color_space, *color_range),
                 raw_options: ffmpeg_options
                     .clone()
                     .unwrap_or_default()
                     .into_iter()
                     .collect(),
             }),
-            WhipVideoEncoderOptions::VulkanH264 { bitrate } => {
-                core::WhipVideoEncoderOptions::VulkanH264(core::VulkanH264EncoderOptions {
-                    resolution: resolution.into(),
-                    bitrate: bitrate.map(|b| b.try_into()).transpose()?,
-                })
-            }
+            WhipVideoEncoderOptions::VulkanH264 {
+                bitrate,
+                color_space,
+                color_range,
+            } => core::WhipVideoEncoderOptions::VulkanH264(core::VulkanH264EncoderOptions {
+                resolution: resolution.into(),
+                bitrate: bitrate.map(|b| b.try_into()).transpose()?,
+                color_space: output_color_space(*color_space, *color_range),
+            }),
             WhipVideoEncoderOptions::FfmpegVp8 {
                 bitrate,
                 ffmpeg_options,
@@ -139,11 +154,14 @@ impl WhipVideoEncoderOptions {
             WhipVideoEncoderOptions::FfmpegVp9 {
                 bitrate,
                 pixel_format,
+                color_space,
+                color_range,
                 ffmpeg_options,
             } => core::WhipVideoEncoderOptions::FfmpegVp9(core::FfmpegVp9EncoderOptions {
                 resolution: resolution.into(),
                 bitrate: bitrate.map(|b| b.try_into()).transpose()?,
                 pixel_format: pixel_format.unwrap_or(PixelFormat::Yuv420p).into(),
+                color_space: output_color_space(*color_space, *color_range),
                 raw_options: ffmpeg_options
                     .clone()
                     .unwrap_or_default()
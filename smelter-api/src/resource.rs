@@ -1,7 +1,15 @@
+mod fragment;
 mod image;
 mod shader;
 mod web_renderer;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod video_clip;
+
+pub use fragment::*;
 pub use image::*;
 pub use shader::*;
 pub use web_renderer::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use video_clip::*;
@@ -0,0 +1,19 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FragmentSpec {
+    /// Component tree that can be used in output scenes with a `fragment` component.
+    pub root: Component,
+}
+
+impl TryFrom<FragmentSpec> for smelter_render::RendererSpec {
+    type Error = TypeError;
+
+    fn try_from(spec: FragmentSpec) -> Result<Self, Self::Error> {
+        Ok(Self::Fragment(spec.root.try_into()?))
+    }
+}
@@ -24,6 +24,10 @@ pub enum ImageSpec {
         url: Option<String>,
         path: Option<String>,
     },
+    Webp {
+        url: Option<String>,
+        path: Option<String>,
+    },
     Auto {
         url: Option<String>,
         path: Option<String>,
@@ -67,6 +71,10 @@ impl TryFrom<ImageSpec> for smelter_render::RendererSpec {
                 src: from_url_or_path(url, path)?,
                 image_type: image::ImageType::Gif,
             },
+            ImageSpec::Webp { url, path } => image::ImageSpec {
+                src: from_url_or_path(url, path)?,
+                image_type: image::ImageType::Webp,
+            },
             ImageSpec::Auto { url, path } => image::ImageSpec {
                 src: from_url_or_path(url, path)?,
                 image_type: image::ImageType::Auto,
@@ -0,0 +1,37 @@
+use std::{path::Path, sync::Arc};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::common_core::prelude as core;
+use crate::*;
+
+/// Short video clip loaded from an MP4 file. The entire clip is decoded
+/// when registered and kept in memory, so only short clips are supported.
+/// Exactly one of `url` and `path` has to be defined.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VideoClipSpec {
+    /// URL of the MP4 file.
+    pub url: Option<Arc<str>>,
+    /// Path to the MP4 file.
+    pub path: Option<Arc<Path>>,
+}
+
+impl TryFrom<VideoClipSpec> for core::VideoClipOptions {
+    type Error = TypeError;
+
+    fn try_from(value: VideoClipSpec) -> Result<Self, Self::Error> {
+        const BAD_URL_PATH_SPEC: &str = "Exactly one of `url` or `path` has to be specified in a register request for a video clip.";
+
+        let source = match (value.url, value.path) {
+            (Some(_), Some(_)) | (None, None) => {
+                return Err(TypeError::new(BAD_URL_PATH_SPEC));
+            }
+            (Some(url), None) => core::Mp4InputSource::Url(url),
+            (None, Some(path)) => core::Mp4InputSource::File(path),
+        };
+
+        Ok(Self { source })
+    }
+}
@@ -8,11 +8,13 @@ mod common;
 mod common_into;
 mod component;
 mod component_into;
+mod patch;
 mod transition;
 
 pub use color::*;
 pub use common::*;
 pub use component::*;
+pub use patch::*;
 pub use transition::*;
 
 use crate::*;
@@ -13,9 +13,13 @@ pub enum Component {
     WebView(WebView),
     Shader(Shader),
     Image(Image),
+    VideoClip(VideoClip),
     Text(Text),
+    Mask(Mask),
     Tiles(Tiles),
     Rescaler(Rescaler),
+    Ticker(Ticker),
+    Fragment(Fragment),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
@@ -50,6 +54,29 @@ pub struct View {
 
     /// Direction defines how static children are positioned inside a View component.
     pub direction: Option<ViewDirection>,
+    /// (**default=`0.0`**) Space in pixels between static children. If `wrap` is enabled,
+    /// it also defines space between rows (or columns).
+    pub gap: Option<f32>,
+    /// (**default=`"start"`**) Distribution of static children along the `direction`.
+    pub justify_content: Option<JustifyContent>,
+    /// (**default=`"stretch"`**) Alignment of static children in the axis perpendicular
+    /// to the `direction`. Unless children are stretched, children without an explicit
+    /// size are sized to their content.
+    pub align_items: Option<AlignItems>,
+    /// (**default=`false`**) Move static children that do not fit into the next row (or
+    /// column if `direction` is `"column"`).
+    pub wrap: Option<bool>,
+
+    /// Share of the free space in the parent `View` that is added to the size of
+    /// this component. By default, components without an explicit size grow with
+    /// factor `1` and components with an explicit size do not grow.
+    /// Other children of a `View`, e.g. `Image` or `Tiles`, always use the default.
+    pub flex_grow: Option<f32>,
+    /// (**default=`0.0`**) Share of the missing space in the parent `View` that is
+    /// subtracted from the size of this component, weighted by its size. Components
+    /// do not shrink below the size of their content.
+    /// Other children of a `View`, e.g. `Image` or `Tiles`, always use the default.
+    pub flex_shrink: Option<f32>,
 
     /// Distance in pixels between this component's top edge and its parent's top edge (including a border).
     /// If this field is defined, then the component will ignore a layout defined by its parent.
@@ -74,6 +101,10 @@ pub struct View {
     /// effect if the previous scene already contained a `View` component with the same id.
     pub transition: Option<Transition>,
 
+    /// Keyframe animation of the component's size, position and opacity. Animated values
+    /// override values defined directly on the component.
+    pub animation: Option<Animation>,
+
     /// (**default=`"hidden"`**) Controls what happens to content that is too big to fit into an area.
     pub overflow: Option<Overflow>,
 
@@ -112,6 +143,12 @@ pub struct View {
 
     /// (**default=`0.0`**) Padding on left side in pixels.
     pub padding_left: Option<f32>,
+
+    /// (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+    pub opacity: Option<f32>,
+    /// (**default=`"normal"`**) Defines how the component is composed with the content
+    /// rendered below it.
+    pub blend_mode: Option<BlendMode>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
@@ -152,6 +189,39 @@ pub enum ViewDirection {
     Column,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JustifyContent {
+    /// Children are packed at the beginning of the row (or column).
+    Start,
+    /// Children are packed at the end of the row (or column).
+    End,
+    /// Children are packed in the middle of the row (or column).
+    Center,
+    /// Free space is distributed between children, first and last child are placed
+    /// at the edges.
+    SpaceBetween,
+    /// Free space is distributed around children, space at the edges is half of
+    /// the space between children.
+    SpaceAround,
+    /// Free space is distributed evenly between children and edges.
+    SpaceEvenly,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlignItems {
+    /// Children are placed at the top of the row (or at the left edge of the column).
+    Start,
+    /// Children are placed at the bottom of the row (or at the right edge of the column).
+    End,
+    /// Children are placed in the middle of the row (or column).
+    Center,
+    /// Children without an explicit size fill the entire row (or column), other
+    /// children are placed at the start.
+    Stretch,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Rescaler {
@@ -167,6 +237,17 @@ pub struct Rescaler {
     /// (**default=`"center"`**) Vertical alignment.
     pub vertical_align: Option<VerticalAlign>,
 
+    /// Share of the free space in the parent `View` that is added to the size of
+    /// this component. By default, components without an explicit size grow with
+    /// factor `1` and components with an explicit size do not grow.
+    /// Other children of a `View`, e.g. `Image` or `Tiles`, always use the default.
+    pub flex_grow: Option<f32>,
+    /// (**default=`0.0`**) Share of the missing space in the parent `View` that is
+    /// subtracted from the size of this component, weighted by its size. Components
+    /// do not shrink below the size of their content.
+    /// Other children of a `View`, e.g. `Image` or `Tiles`, always use the default.
+    pub flex_shrink: Option<f32>,
+
     /// Width of a component in pixels (without a border). Exact behavior might be different
     /// based on the parent component:
     /// - If the parent component is a layout, check sections "Absolute positioning" and "Static
@@ -203,6 +284,10 @@ pub struct Rescaler {
     /// effect if the previous scene already contained a `Rescaler` component with the same id.
     pub transition: Option<Transition>,
 
+    /// Keyframe animation of the component's size, position and opacity. Animated values
+    /// override values defined directly on the component.
+    pub animation: Option<Animation>,
+
     /// (**default=`0.0`**) Radius of a rounded corner.
     pub border_radius: Option<f32>,
 
@@ -214,6 +299,12 @@ pub struct Rescaler {
 
     /// List of box shadows.
     pub box_shadow: Option<Vec<BoxShadow>>,
+
+    /// (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+    pub opacity: Option<f32>,
+    /// (**default=`"normal"`**) Defines how the component is composed with the content
+    /// rendered below it.
+    pub blend_mode: Option<BlendMode>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
@@ -227,6 +318,21 @@ pub enum RescaleMode {
     Fill,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BlendMode {
+    /// Component is drawn over the content below it.
+    Normal,
+    /// Colors are multiplied with the content below. Result is always darker.
+    Multiply,
+    /// Inverted colors are multiplied with the inverted content below. Result is always lighter.
+    Screen,
+    /// Colors are added to the content below.
+    Add,
+    /// Multiply for dark parts of the content below and screen for bright ones.
+    Overlay,
+}
+
 /// WebView component renders a website using Chromium.
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -261,6 +367,48 @@ pub struct Image {
     /// Height of the image in pixels.
     /// If `width` is not explicitly provided, the image will automatically adjust its width to maintain its original aspect ratio relative to the height.
     pub height: Option<f32>,
+
+    /// Number of times an animated image (GIF, WebP, APNG) is played. After the last loop, the final
+    /// frame stays visible. If not provided, the animation loops forever.
+    pub loop_count: Option<u32>,
+
+    /// (**default=`true`**) If `true`, an animated image starts from the first frame when the component
+    /// is added to the scene. If `false`, the animation is synchronized with the output timeline, so it
+    /// looks like it was playing all along.
+    pub restart_on_mount: Option<bool>,
+
+    /// (**default=`0.0`**) Offset in milliseconds from which an animated image starts playing.
+    pub start_at_ms: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VideoClip {
+    /// Id of a component.
+    pub id: Option<ComponentId>,
+
+    /// Id of a video clip. It identifies a clip registered using a [`register video clip`](../routes.md#register-video-clip) request.
+    pub clip_id: RendererId,
+
+    /// Width of the clip in pixels.
+    /// If `height` is not explicitly provided, the clip will automatically adjust its height to maintain its original aspect ratio relative to the width.
+    pub width: Option<f32>,
+
+    /// Height of the clip in pixels.
+    /// If `width` is not explicitly provided, the clip will automatically adjust its width to maintain its original aspect ratio relative to the height.
+    pub height: Option<f32>,
+
+    /// Number of times a clip is played. After the last loop, the final frame stays visible.
+    /// If not provided, the clip loops forever.
+    pub loop_count: Option<u32>,
+
+    /// (**default=`true`**) If `true`, a clip starts from the first frame when the component
+    /// is added to the scene. If `false`, the clip is synchronized with the output timeline, so it
+    /// looks like it was playing all along.
+    pub restart_on_mount: Option<bool>,
+
+    /// (**default=`0.0`**) Offset in milliseconds from which a clip starts playing.
+    pub start_at_ms: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
@@ -317,8 +465,13 @@ pub struct Text {
     /// Id of a component.
     pub id: Option<ComponentId>,
 
-    /// Text that will be rendered.
-    pub text: Arc<str>,
+    /// Text that will be rendered. Exactly one of `text` and `spans` has to be defined.
+    pub text: Option<Arc<str>>,
+
+    /// Text split into fragments with different styles. Properties that are not defined
+    /// on a span are inherited from the component. Exactly one of `text` and `spans` has to
+    /// be defined.
+    pub spans: Option<Vec<TextSpan>>,
 
     /// Width of a texture that text will be rendered on. If not provided, the resulting texture
     /// will be sized based on the defined text but limited to `max_width` value.
@@ -353,6 +506,51 @@ pub struct Text {
     pub wrap: Option<TextWrapMode>,
     /// (**default=`"normal"`**) Font weight. The selected font needs to support the specified weight.
     pub weight: Option<TextWeight>,
+    /// (**default=`0.0`**) Additional space between letters in pixels.
+    pub letter_spacing: Option<f32>,
+    /// Outline drawn around the text.
+    pub outline: Option<TextOutline>,
+    /// Shadow drawn below the text.
+    pub shadow: Option<TextShadow>,
+}
+
+/// Fragment of the text with its own styling. Color emoji are rendered if a font that
+/// supports them (e.g. "Noto Color Emoji") is installed or registered.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TextSpan {
+    /// Text that will be rendered.
+    pub text: Arc<str>,
+    /// Font color in `#RRGGBBAA` format. Defaults to the `color` of the Text component.
+    pub color: Option<RGBAColor>,
+    /// Font family. Defaults to the `font_family` of the Text component.
+    pub font_family: Option<Arc<str>>,
+    /// Font style. Defaults to the `style` of the Text component.
+    pub style: Option<TextStyle>,
+    /// Font weight. Defaults to the `weight` of the Text component.
+    pub weight: Option<TextWeight>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TextOutline {
+    /// Width of the outline in pixels. Has to be in the `[0, 500]` range.
+    pub width: f32,
+    /// (**default=`"#000000FF"`**) Outline color in `#RRGGBBAA` format.
+    pub color: Option<RGBAColor>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TextShadow {
+    /// (**default=`0.0`**) Horizontal offset of the shadow in pixels. Has to be in the `[-500, 500]` range.
+    pub offset_x: Option<f32>,
+    /// (**default=`0.0`**) Vertical offset of the shadow in pixels. Has to be in the `[-500, 500]` range.
+    pub offset_y: Option<f32>,
+    /// (**default=`0.0`**) Blur radius of the shadow in pixels. Has to be in the `[0, 500]` range.
+    pub blur_radius: Option<f32>,
+    /// (**default=`"#000000FF"`**) Shadow color in `#RRGGBBAA` format.
+    pub color: Option<RGBAColor>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
@@ -405,6 +603,41 @@ pub enum Interpolation {
     Spring,
 }
 
+/// Mask component renders its content only in areas where the mask is visible. It can be
+/// used to cut content into any shape, e.g. a circle, a logo or a text.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Mask {
+    /// Id of a component.
+    pub id: Option<ComponentId>,
+    /// Component that will be rendered. It is stretched to the size of the mask component.
+    pub content: Box<Component>,
+    /// Component that defines which parts of the content are visible. It is stretched
+    /// to the size of the mask component.
+    pub mask: Box<Component>,
+
+    /// Width of a component in pixels.
+    pub width: f32,
+    /// Height of a component in pixels.
+    pub height: f32,
+
+    /// (**default=`"alpha"`**) Defines which property of the mask is used to calculate
+    /// visibility of the content.
+    pub mode: Option<MaskMode>,
+    /// (**default=`false`**) Render content only where the mask is not visible.
+    pub invert: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MaskMode {
+    /// Content is visible where the mask is opaque.
+    Alpha,
+    /// Content is visible where the mask is bright. Transparent parts of the mask are treated
+    /// as black.
+    Luminance,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Tiles {
@@ -442,4 +675,78 @@ pub struct Tiles {
     /// Defines how this component will behave during a scene update. This will only have an
     /// effect if the previous scene already contained a `Tiles` component with the same id.
     pub transition: Option<Transition>,
+
+    /// (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+    pub opacity: Option<f32>,
+    /// (**default=`"normal"`**) Defines how the component is composed with the content
+    /// rendered below it.
+    pub blend_mode: Option<BlendMode>,
+}
+
+/// Ticker component scrolls its children one after another, e.g. to display news headlines.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Ticker {
+    /// Id of a component. Scroll position is preserved across scene updates only if
+    /// the previous scene already contained a `Ticker` component with the same id.
+    pub id: Option<ComponentId>,
+    /// List of items to scroll. Items appended in a scene update are added at the end of
+    /// the queue without restarting the scroll. Items removed from the beginning of the list
+    /// do not affect positions of remaining items only if those items have an `id`.
+    pub children: Option<Vec<Component>>,
+
+    /// Width of a component in pixels. Exact behavior might be different based on the parent
+    /// component:
+    /// - If the parent component is a layout, check sections "Absolute positioning" and "Static
+    ///   positioning" of that component.
+    /// - If the parent component is not a layout, then this field is required.
+    pub width: Option<f32>,
+    /// Height of a component in pixels. Exact behavior might be different based on the parent
+    /// component:
+    /// - If the parent component is a layout, check sections "Absolute positioning" and "Static
+    ///   positioning" of that component.
+    /// - If the parent component is not a layout, then this field is required.
+    pub height: Option<f32>,
+
+    /// (**default=`"left"`**) Direction in which items are moving.
+    pub direction: Option<TickerDirection>,
+    /// (**default=`100`**) Scroll speed in pixels per second.
+    pub speed: Option<f32>,
+    /// (**default=`0`**) Space between consecutive items in pixels.
+    pub gap: Option<f32>,
+    /// (**default=`true`**) Start scrolling items from the beginning after the last one
+    /// leaves the component area.
+    #[serde(rename = "loop")]
+    pub should_loop: Option<bool>,
+    /// (**default=`"#00000000"`**) Background color in a `"#RRGGBBAA"` format.
+    pub background_color: Option<RGBAColor>,
+
+    /// (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+    pub opacity: Option<f32>,
+    /// (**default=`"normal"`**) Defines how the component is composed with the content
+    /// rendered below it.
+    pub blend_mode: Option<BlendMode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TickerDirection {
+    /// Items enter on the right edge and move to the left.
+    Left,
+    /// Items enter on the left edge and move to the right.
+    Right,
+    /// Items enter on the bottom edge and move up.
+    Up,
+    /// Items enter on the top edge and move down.
+    Down,
+}
+
+/// Fragment component renders a component tree registered with a
+/// [`register fragment`](../routes.md#register-fragment) request. Updating the fragment
+/// updates all scenes that use it.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Fragment {
+    /// Id of a registered scene fragment.
+    pub fragment_id: RendererId,
 }
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use smelter_render::MAX_NODE_RESOLUTION;
 use smelter_render::scene;
@@ -16,10 +16,14 @@ impl TryFrom<Component> for scene::Component {
             Component::View(view) => Ok(Self::View(view.try_into()?)),
             Component::WebView(web) => Ok(Self::WebView(web.try_into()?)),
             Component::Shader(shader) => Ok(Self::Shader(shader.try_into()?)),
-            Component::Image(image) => Ok(Self::Image(image.into())),
+            Component::Image(image) => Ok(Self::Image(image.try_into()?)),
+            Component::VideoClip(clip) => Ok(Self::VideoClip(clip.try_into()?)),
             Component::Text(text) => Ok(Self::Text(text.try_into()?)),
+            Component::Mask(mask) => Ok(Self::Mask(mask.try_into()?)),
             Component::Tiles(tiles) => Ok(Self::Tiles(tiles.try_into()?)),
             Component::Rescaler(rescaler) => Ok(Self::Rescaler(rescaler.try_into()?)),
+            Component::Ticker(ticker) => Ok(Self::Ticker(ticker.try_into()?)),
+            Component::Fragment(fragment) => Ok(Self::Fragment(fragment.into())),
         }
     }
 }
@@ -78,6 +82,26 @@ impl TryFrom<View> for scene::ViewComponent {
             Some(ViewDirection::Column) => scene::ViewChildrenDirection::Column,
             None => scene::ViewChildrenDirection::Row,
         };
+        let justify_content = match view.justify_content {
+            Some(JustifyContent::Start) => scene::JustifyContent::Start,
+            Some(JustifyContent::End) => scene::JustifyContent::End,
+            Some(JustifyContent::Center) => scene::JustifyContent::Center,
+            Some(JustifyContent::SpaceBetween) => scene::JustifyContent::SpaceBetween,
+            Some(JustifyContent::SpaceAround) => scene::JustifyContent::SpaceAround,
+            Some(JustifyContent::SpaceEvenly) => scene::JustifyContent::SpaceEvenly,
+            None => scene::JustifyContent::Start,
+        };
+        let align_items = match view.align_items {
+            Some(AlignItems::Start) => scene::AlignItems::Start,
+            Some(AlignItems::End) => scene::AlignItems::End,
+            Some(AlignItems::Center) => scene::AlignItems::Center,
+            Some(AlignItems::Stretch) => scene::AlignItems::Stretch,
+            None => scene::AlignItems::Stretch,
+        };
+        let gap = view.gap.unwrap_or(0.0);
+        if gap < 0.0 {
+            return Err(TypeError::new("\"gap\" cannot be negative."));
+        }
         let overflow = match view.overflow {
             Some(Overflow::Visible) => scene::Overflow::Visible,
             Some(Overflow::Hidden) => scene::Overflow::Hidden,
@@ -127,6 +151,7 @@ impl TryFrom<View> for scene::ViewComponent {
                 .map(TryInto::try_into)
                 .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 0)))?,
             transition: view.transition.map(TryInto::try_into).transpose()?,
+            animation: view.animation.map(TryInto::try_into).transpose()?,
             border_radius: BorderRadius::new_with_radius(view.border_radius.unwrap_or(0.0)),
             border_width: view.border_width.unwrap_or(0.0),
             border_color: view
@@ -140,6 +165,16 @@ impl TryFrom<View> for scene::ViewComponent {
                 .map(TryInto::try_into)
                 .collect::<Result<_, _>>()?,
             padding,
+            gap,
+            justify_content,
+            align_items,
+            wrap: view.wrap.unwrap_or(false),
+            flex: flex(view.flex_grow, view.flex_shrink)?,
+            opacity: opacity(view.opacity)?,
+            blend_mode: view
+                .blend_mode
+                .map(Into::into)
+                .unwrap_or(scene::BlendMode::Normal),
         })
     }
 }
@@ -203,6 +238,7 @@ impl TryFrom<Rescaler> for scene::RescalerComponent {
                 .unwrap_or(VerticalAlign::Center)
                 .into(),
             transition: rescaler.transition.map(TryInto::try_into).transpose()?,
+            animation: rescaler.animation.map(TryInto::try_into).transpose()?,
             border_radius: BorderRadius::new_with_radius(rescaler.border_radius.unwrap_or(0.0)),
             border_width: rescaler.border_width.unwrap_or(0.0),
             border_color: rescaler
@@ -215,6 +251,12 @@ impl TryFrom<Rescaler> for scene::RescalerComponent {
                 .into_iter()
                 .map(TryInto::try_into)
                 .collect::<Result<_, _>>()?,
+            flex: flex(rescaler.flex_grow, rescaler.flex_shrink)?,
+            opacity: opacity(rescaler.opacity)?,
+            blend_mode: rescaler
+                .blend_mode
+                .map(Into::into)
+                .unwrap_or(scene::BlendMode::Normal),
         })
     }
 }
@@ -269,45 +311,77 @@ impl From<ShaderParam> for scene::ShaderParam {
     }
 }
 
-impl From<Image> for scene::ImageComponent {
-    fn from(image: Image) -> Self {
-        Self {
+impl TryFrom<Image> for scene::ImageComponent {
+    type Error = TypeError;
+
+    fn try_from(image: Image) -> Result<Self, Self::Error> {
+        Ok(Self {
             id: image.id.map(Into::into),
             image_id: image.image_id.into(),
             width: image.width,
             height: image.height,
-        }
+            playback: playback_options(
+                image.loop_count,
+                image.restart_on_mount,
+                image.start_at_ms,
+            )?,
+        })
+    }
+}
+
+impl TryFrom<VideoClip> for scene::VideoClipComponent {
+    type Error = TypeError;
+
+    fn try_from(clip: VideoClip) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: clip.id.map(Into::into),
+            clip_id: clip.clip_id.into(),
+            width: clip.width,
+            height: clip.height,
+            playback: playback_options(clip.loop_count, clip.restart_on_mount, clip.start_at_ms)?,
+        })
+    }
+}
+
+fn playback_options(
+    loop_count: Option<u32>,
+    restart_on_mount: Option<bool>,
+    start_at_ms: Option<f64>,
+) -> Result<scene::ImagePlayback, TypeError> {
+    if loop_count == Some(0) {
+        return Err(TypeError::new("\"loop_count\" has to be greater than 0."));
     }
+    let start_at = match start_at_ms {
+        Some(start_at_ms) => Duration::try_from_secs_f64(start_at_ms / 1000.0).map_err(|_| {
+            TypeError::new("\"start_at_ms\" has to be a non-negative finite number.")
+        })?,
+        None => Duration::ZERO,
+    };
+    Ok(scene::ImagePlayback {
+        loop_count,
+        restart_on_mount: restart_on_mount.unwrap_or(true),
+        start_at,
+    })
 }
 
 impl TryFrom<Text> for scene::TextComponent {
     type Error = TypeError;
 
     fn try_from(text: Text) -> Result<Self, Self::Error> {
-        let style = match text.style {
-            Some(TextStyle::Normal) => scene::TextStyle::Normal,
-            Some(TextStyle::Italic) => scene::TextStyle::Italic,
-            Some(TextStyle::Oblique) => scene::TextStyle::Oblique,
-            None => scene::TextStyle::Normal,
-        };
+        let style = text
+            .style
+            .map(Into::into)
+            .unwrap_or(scene::TextStyle::Normal);
         let wrap = match text.wrap {
             Some(TextWrapMode::None) => scene::TextWrap::None,
             Some(TextWrapMode::Word) => scene::TextWrap::Word,
             Some(TextWrapMode::Glyph) => scene::TextWrap::Glyph,
             None => scene::TextWrap::None,
         };
-        let weight = match text.weight {
-            Some(TextWeight::Thin) => scene::TextWeight::Thin,
-            Some(TextWeight::ExtraLight) => scene::TextWeight::ExtraLight,
-            Some(TextWeight::Light) => scene::TextWeight::Light,
-            Some(TextWeight::Normal) => scene::TextWeight::Normal,
-            Some(TextWeight::Medium) => scene::TextWeight::Medium,
-            Some(TextWeight::SemiBold) => scene::TextWeight::SemiBold,
-            Some(TextWeight::Bold) => scene::TextWeight::Bold,
-            Some(TextWeight::ExtraBold) => scene::TextWeight::ExtraBold,
-            Some(TextWeight::Black) => scene::TextWeight::Black,
-            None => scene::TextWeight::Normal,
-        };
+        let weight = text
+            .weight
+            .map(Into::into)
+            .unwrap_or(scene::TextWeight::Normal);
         let dimensions = match (text.width, text.height, text.max_width, text.max_height) {
             (Some(width), Some(height), _, _) => scene::TextDimensions::Fixed { width, height },
             (None, Some(_), _, _) => {
@@ -338,9 +412,35 @@ impl TryFrom<Text> for scene::TextComponent {
             ));
         }
 
+        let spans = match (text.text, text.spans) {
+            (Some(text), None) => vec![scene::TextSpan {
+                text,
+                color: None,
+                font_family: None,
+                style: None,
+                weight: None,
+            }],
+            (None, Some(spans)) => spans
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            (Some(_), Some(_)) | (None, None) => {
+                return Err(TypeError::new(
+                    "Exactly one of \"text\" and \"spans\" properties has to be defined on a Text component.",
+                ));
+            }
+        };
+
+        let letter_spacing = text.letter_spacing.unwrap_or(0.0);
+        if !letter_spacing.is_finite() {
+            return Err(TypeError::new(
+                "\"letter_spacing\" property has to be a finite number",
+            ));
+        }
+
         let text = Self {
             id: text.id.map(Into::into),
-            text: text.text,
+            spans,
             font_size: text.font_size,
             dimensions,
             line_height,
@@ -357,11 +457,108 @@ impl TryFrom<Text> for scene::TextComponent {
                 .background_color
                 .map(TryInto::try_into)
                 .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 0)))?,
+            letter_spacing,
+            outline: text.outline.map(TryInto::try_into).transpose()?,
+            shadow: text.shadow.map(TryInto::try_into).transpose()?,
         };
         Ok(text)
     }
 }
 
+impl TryFrom<TextSpan> for scene::TextSpan {
+    type Error = TypeError;
+
+    fn try_from(span: TextSpan) -> Result<Self, Self::Error> {
+        Ok(Self {
+            text: span.text,
+            color: span.color.map(TryInto::try_into).transpose()?,
+            font_family: span.font_family,
+            style: span.style.map(Into::into),
+            weight: span.weight.map(Into::into),
+        })
+    }
+}
+
+/// Outline and shadow are rendered in the padding around the text, so large values
+/// would require textures larger than the renderer supports.
+const MAX_TEXT_EFFECT_SIZE: f32 = 500.0;
+
+impl TryFrom<TextOutline> for scene::TextOutline {
+    type Error = TypeError;
+
+    fn try_from(outline: TextOutline) -> Result<Self, Self::Error> {
+        if !(0.0..=MAX_TEXT_EFFECT_SIZE).contains(&outline.width) {
+            return Err(TypeError::new(format!(
+                "\"width\" property of a text outline has to be a number in the [0, {MAX_TEXT_EFFECT_SIZE}] range"
+            )));
+        }
+        Ok(Self {
+            width: outline.width,
+            color: outline
+                .color
+                .map(TryInto::try_into)
+                .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 255)))?,
+        })
+    }
+}
+
+impl TryFrom<TextShadow> for scene::TextShadow {
+    type Error = TypeError;
+
+    fn try_from(shadow: TextShadow) -> Result<Self, Self::Error> {
+        let blur_radius = shadow.blur_radius.unwrap_or(0.0);
+        if !(0.0..=MAX_TEXT_EFFECT_SIZE).contains(&blur_radius) {
+            return Err(TypeError::new(format!(
+                "\"blur_radius\" property of a text shadow has to be a number in the [0, {MAX_TEXT_EFFECT_SIZE}] range"
+            )));
+        }
+        let offset = |name: &str, value: Option<f32>| {
+            let value = value.unwrap_or(0.0);
+            match (-MAX_TEXT_EFFECT_SIZE..=MAX_TEXT_EFFECT_SIZE).contains(&value) {
+                true => Ok(value),
+                false => Err(TypeError::new(format!(
+                    "\"{name}\" property of a text shadow has to be a number in the [-{MAX_TEXT_EFFECT_SIZE}, {MAX_TEXT_EFFECT_SIZE}] range"
+                ))),
+            }
+        };
+        Ok(Self {
+            offset_x: offset("offset_x", shadow.offset_x)?,
+            offset_y: offset("offset_y", shadow.offset_y)?,
+            blur_radius,
+            color: shadow
+                .color
+                .map(TryInto::try_into)
+                .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 255)))?,
+        })
+    }
+}
+
+impl From<TextStyle> for scene::TextStyle {
+    fn from(style: TextStyle) -> Self {
+        match style {
+            TextStyle::Normal => scene::TextStyle::Normal,
+            TextStyle::Italic => scene::TextStyle::Italic,
+            TextStyle::Oblique => scene::TextStyle::Oblique,
+        }
+    }
+}
+
+impl From<TextWeight> for scene::TextWeight {
+    fn from(weight: TextWeight) -> Self {
+        match weight {
+            TextWeight::Thin => scene::TextWeight::Thin,
+            TextWeight::ExtraLight => scene::TextWeight::ExtraLight,
+            TextWeight::Light => scene::TextWeight::Light,
+            TextWeight::Normal => scene::TextWeight::Normal,
+            TextWeight::Medium => scene::TextWeight::Medium,
+            TextWeight::SemiBold => scene::TextWeight::SemiBold,
+            TextWeight::Bold => scene::TextWeight::Bold,
+            TextWeight::ExtraBold => scene::TextWeight::ExtraBold,
+            TextWeight::Black => scene::TextWeight::Black,
+        }
+    }
+}
+
 impl TryFrom<WebView> for scene::WebViewComponent {
     type Error = TypeError;
 
@@ -379,6 +576,39 @@ impl TryFrom<WebView> for scene::WebViewComponent {
     }
 }
 
+impl TryFrom<Mask> for scene::MaskComponent {
+    type Error = TypeError;
+
+    fn try_from(mask: Mask) -> Result<Self, Self::Error> {
+        // Content and mask are rendered to textures of that size.
+        let is_valid_size = |value: f32, max: usize| value > 0.0 && value <= max as f32;
+        if !is_valid_size(mask.width, MAX_NODE_RESOLUTION.width)
+            || !is_valid_size(mask.height, MAX_NODE_RESOLUTION.height)
+        {
+            return Err(TypeError::new(format!(
+                "\"width\" and \"height\" of a \"Mask\" component have to be positive and can't exceed {}x{}.",
+                MAX_NODE_RESOLUTION.width, MAX_NODE_RESOLUTION.height
+            )));
+        }
+        let mode = match mask.mode {
+            Some(MaskMode::Alpha) => scene::MaskMode::Alpha,
+            Some(MaskMode::Luminance) => scene::MaskMode::Luminance,
+            None => scene::MaskMode::Alpha,
+        };
+        Ok(Self {
+            id: mask.id.map(Into::into),
+            content: Box::new((*mask.content).try_into()?),
+            mask: Box::new((*mask.mask).try_into()?),
+            mode,
+            invert: mask.invert.unwrap_or(false),
+            size: scene::Size {
+                width: mask.width,
+                height: mask.height,
+            },
+        })
+    }
+}
+
 impl TryFrom<Tiles> for scene::TilesComponent {
     type Error = TypeError;
 
@@ -410,11 +640,98 @@ impl TryFrom<Tiles> for scene::TilesComponent {
                 .into(),
             vertical_align: tiles.vertical_align.unwrap_or(VerticalAlign::Center).into(),
             transition: tiles.transition.map(TryInto::try_into).transpose()?,
+            opacity: opacity(tiles.opacity)?,
+            blend_mode: tiles
+                .blend_mode
+                .map(Into::into)
+                .unwrap_or(scene::BlendMode::Normal),
         };
         Ok(result)
     }
 }
 
+impl TryFrom<Ticker> for scene::TickerComponent {
+    type Error = TypeError;
+
+    fn try_from(ticker: Ticker) -> Result<Self, Self::Error> {
+        let direction = match ticker.direction {
+            Some(TickerDirection::Left) => scene::TickerDirection::Left,
+            Some(TickerDirection::Right) => scene::TickerDirection::Right,
+            Some(TickerDirection::Up) => scene::TickerDirection::Up,
+            Some(TickerDirection::Down) => scene::TickerDirection::Down,
+            None => scene::TickerDirection::Left,
+        };
+        let speed = ticker.speed.unwrap_or(100.0);
+        if speed < 0.0 {
+            return Err(TypeError::new(
+                "Ticker speed can't be negative, use \"direction\" field to change scroll direction.",
+            ));
+        }
+        if ticker.gap.is_some_and(|gap| gap < 0.0) {
+            return Err(TypeError::new("Ticker gap can't be negative."));
+        }
+        Ok(Self {
+            id: ticker.id.map(Into::into),
+            children: ticker
+                .children
+                .unwrap_or_default()
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<_>, _>>()?,
+            width: ticker.width,
+            height: ticker.height,
+            direction,
+            speed,
+            gap: ticker.gap.unwrap_or(0.0),
+            should_loop: ticker.should_loop.unwrap_or(true),
+            background_color: ticker
+                .background_color
+                .map(TryInto::try_into)
+                .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 0)))?,
+            opacity: opacity(ticker.opacity)?,
+            blend_mode: ticker
+                .blend_mode
+                .map(Into::into)
+                .unwrap_or(scene::BlendMode::Normal),
+        })
+    }
+}
+
+fn flex(grow: Option<f32>, shrink: Option<f32>) -> Result<scene::Flex, TypeError> {
+    if grow.is_some_and(|grow| grow < 0.0) {
+        return Err(TypeError::new("\"flex_grow\" cannot be negative."));
+    }
+    if shrink.is_some_and(|shrink| shrink < 0.0) {
+        return Err(TypeError::new("\"flex_shrink\" cannot be negative."));
+    }
+    Ok(scene::Flex {
+        grow,
+        shrink: shrink.unwrap_or(0.0),
+    })
+}
+
+fn opacity(opacity: Option<f32>) -> Result<f32, TypeError> {
+    match opacity {
+        Some(opacity) if !(0.0..=1.0).contains(&opacity) => Err(TypeError::new(
+            "\"opacity\" has to be a number between 0.0 and 1.0.",
+        )),
+        Some(opacity) => Ok(opacity),
+        None => Ok(1.0),
+    }
+}
+
+impl From<BlendMode> for scene::BlendMode {
+    fn from(mode: BlendMode) -> Self {
+        match mode {
+            BlendMode::Normal => scene::BlendMode::Normal,
+            BlendMode::Multiply => scene::BlendMode::Multiply,
+            BlendMode::Screen => scene::BlendMode::Screen,
+            BlendMode::Add => scene::BlendMode::Add,
+            BlendMode::Overlay => scene::BlendMode::Overlay,
+        }
+    }
+}
+
 impl TryFrom<BoxShadow> for scene::BoxShadow {
     type Error = TypeError;
 
@@ -430,3 +747,156 @@ impl TryFrom<BoxShadow> for scene::BoxShadow {
         })
     }
 }
+
+impl From<Fragment> for scene::FragmentComponent {
+    fn from(fragment: Fragment) -> Self {
+        Self {
+            fragment_id: fragment.fragment_id.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn text(value: serde_json::Value) -> Result<scene::TextComponent, TypeError> {
+        serde_json::from_value::<Text>(value).unwrap().try_into()
+    }
+
+    #[test]
+    fn test_playback_options() {
+        let result = playback_options(Some(2), Some(false), Some(1500.0)).unwrap();
+        assert_eq!(result.loop_count, Some(2));
+        assert!(!result.restart_on_mount);
+        assert_eq!(result.start_at, Duration::from_millis(1500));
+
+        let result = playback_options(None, None, None).unwrap();
+        assert_eq!(result, scene::ImagePlayback::default());
+
+        assert!(playback_options(Some(0), None, None).is_err());
+        for start_at_ms in [-1.0, f64::NAN, f64::INFINITY, 1e300] {
+            assert!(
+                playback_options(None, None, Some(start_at_ms)).is_err(),
+                "start_at_ms={start_at_ms}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_flex() {
+        // Existing scenes with overflowing children keep their layout unless
+        // shrinking is enabled explicitly.
+        assert_eq!(
+            flex(None, None).unwrap(),
+            scene::Flex {
+                grow: None,
+                shrink: 0.0
+            }
+        );
+        assert_eq!(
+            flex(Some(2.0), Some(1.0)).unwrap(),
+            scene::Flex {
+                grow: Some(2.0),
+                shrink: 1.0
+            }
+        );
+        assert!(flex(Some(-1.0), None).is_err());
+        assert!(flex(None, Some(-1.0)).is_err());
+    }
+
+    #[test]
+    fn test_mask_size() {
+        let mask = |width: f32, height: f32| {
+            scene::MaskComponent::try_from(
+                serde_json::from_value::<Mask>(json!({
+                    "content": { "type": "view" },
+                    "mask": { "type": "view" },
+                    "width": width,
+                    "height": height,
+                }))
+                .unwrap(),
+            )
+        };
+        assert!(mask(100.0, 100.0).is_ok());
+        assert!(mask(7682.0, 4320.0).is_ok());
+        assert!(mask(0.0, 100.0).is_err());
+        assert!(mask(100.0, -1.0).is_err());
+        assert!(mask(7683.0, 100.0).is_err());
+        assert!(mask(100.0, 4321.0).is_err());
+        assert!(mask(1e9, 1e9).is_err());
+    }
+
+    #[test]
+    fn test_text_spans() {
+        let result = text(json!({
+            "spans": [{ "text": "a", "color": "#FF0000FF" }, { "text": "b" }],
+            "font_size": 10,
+        }))
+        .unwrap();
+        assert_eq!(result.spans.len(), 2);
+        assert_eq!(
+            result.spans[0].color,
+            Some(scene::RGBAColor(255, 0, 0, 255))
+        );
+        assert_eq!(result.spans[1].color, None);
+
+        let result = text(json!({ "text": "ab", "font_size": 10 })).unwrap();
+        assert_eq!(result.spans.len(), 1);
+        assert_eq!(&*result.spans[0].text, "ab");
+
+        assert!(text(json!({ "font_size": 10 })).is_err());
+        assert!(text(json!({ "text": "a", "spans": [], "font_size": 10 })).is_err());
+    }
+
+    #[test]
+    fn test_text_outline_and_shadow() {
+        let result = text(json!({
+            "text": "a",
+            "font_size": 10,
+            "outline": { "width": 2 },
+            "shadow": { "offset_y": 4, "blur_radius": 3 },
+        }))
+        .unwrap();
+        let outline = result.outline.unwrap();
+        assert_eq!(outline.width, 2.0);
+        assert_eq!(outline.color, scene::RGBAColor(0, 0, 0, 255));
+        let shadow = result.shadow.unwrap();
+        assert_eq!(shadow.offset_x, 0.0);
+        assert_eq!(shadow.offset_y, 4.0);
+        assert_eq!(shadow.blur_radius, 3.0);
+
+        assert!(text(json!({ "text": "a", "font_size": 10, "outline": { "width": -1 } })).is_err());
+        assert!(
+            text(json!({ "text": "a", "font_size": 10, "shadow": { "blur_radius": -1 } })).is_err()
+        );
+        assert!(
+            text(json!({ "text": "a", "font_size": 10, "shadow": { "blur_radius": 1e6 } }))
+                .is_err()
+        );
+        assert!(
+            text(json!({ "text": "a", "font_size": 10, "shadow": { "offset_x": -1e6 } })).is_err()
+        );
+        assert!(
+            text(json!({ "text": "a", "font_size": 10, "shadow": { "offset_y": 1e6 } })).is_err()
+        );
+    }
+
+    #[test]
+    fn test_text_shadow_rejects_nan() {
+        let shadow = |offset_x, blur_radius| {
+            scene::TextShadow::try_from(TextShadow {
+                offset_x: Some(offset_x),
+                offset_y: None,
+                blur_radius: Some(blur_radius),
+                color: None,
+            })
+        };
+        assert!(shadow(0.0, 0.0).is_ok());
+        assert!(shadow(f32::NAN, 0.0).is_err());
+        assert!(shadow(0.0, f32::NAN).is_err());
+        assert!(shadow(f32::INFINITY, 0.0).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smelter_render::scene;
+
+use crate::*;
+
+/// Operation that modifies a part of the current scene. Components are identified
+/// by the `id` field.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ScenePatch {
+    /// Replace a component (together with its children) with a new one.
+    Replace {
+        /// Id of a component that should be replaced.
+        id: ComponentId,
+        /// New component.
+        component: Component,
+    },
+    /// Insert a new child component. Only `View`, `Tiles`, `Ticker`, `Shader` and `WebView`
+    /// components can be a parent.
+    Insert {
+        /// Id of a parent component.
+        parent_id: ComponentId,
+        /// Position of the new component in the list of children. If not specified,
+        /// the component is added after the existing children.
+        index: Option<usize>,
+        /// New component.
+        component: Component,
+    },
+    /// Remove a component (together with its children).
+    Remove {
+        /// Id of a component that should be removed.
+        id: ComponentId,
+    },
+}
+
+impl TryFrom<ScenePatch> for scene::ScenePatch {
+    type Error = TypeError;
+
+    fn try_from(patch: ScenePatch) -> Result<Self, Self::Error> {
+        let patch = match patch {
+            ScenePatch::Replace { id, component } => scene::ScenePatch::Replace {
+                id: id.into(),
+                component: component.try_into()?,
+            },
+            ScenePatch::Insert {
+                parent_id,
+                index,
+                component,
+            } => scene::ScenePatch::Insert {
+                parent_id: parent_id.into(),
+                index,
+                component: component.try_into()?,
+            },
+            ScenePatch::Remove { id } => scene::ScenePatch::Remove { id: id.into() },
+        };
+        Ok(patch)
+    }
+}
+
+/// Patches that are applied to API components, e.g. to keep a copy of the scene
+/// in the same state as the scene in the renderer.
+impl From<ScenePatch> for scene::ScenePatch<Component> {
+    fn from(patch: ScenePatch) -> Self {
+        match patch {
+            ScenePatch::Replace { id, component } => scene::ScenePatch::Replace {
+                id: id.into(),
+                component,
+            },
+            ScenePatch::Insert {
+                parent_id,
+                index,
+                component,
+            } => scene::ScenePatch::Insert {
+                parent_id: parent_id.into(),
+                index,
+                component,
+            },
+            ScenePatch::Remove { id } => scene::ScenePatch::Remove { id: id.into() },
+        }
+    }
+}
+
+impl scene::PatchableComponent for Component {
+    fn has_id(&self, id: &scene::ComponentId) -> bool {
+        let component_id = match self {
+            Component::InputStream(input) => &input.id,
+            Component::View(view) => &view.id,
+            Component::WebView(web) => &web.id,
+            Component::Shader(shader) => &shader.id,
+            Component::Image(image) => &image.id,
+            Component::VideoClip(clip) => &clip.id,
+            Component::Text(text) => &text.id,
+            Component::Mask(mask) => &mask.id,
+            Component::Tiles(tiles) => &tiles.id,
+            Component::Rescaler(rescaler) => &rescaler.id,
+            Component::Ticker(ticker) => &ticker.id,
+            Component::Fragment(_) => &None,
+        };
+        component_id
+            .as_ref()
+            .is_some_and(|own_id| scene::ComponentId::from(own_id.clone()) == *id)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Self> {
+        match self {
+            Component::Mask(mask) => vec![mask.content.as_mut(), mask.mask.as_mut()],
+            Component::Rescaler(rescaler) => vec![rescaler.child.as_mut()],
+            Component::View(View { children, .. })
+            | Component::WebView(WebView { children, .. })
+            | Component::Shader(Shader { children, .. })
+            | Component::Tiles(Tiles { children, .. })
+            | Component::Ticker(Ticker { children, .. }) => children.iter_mut().flatten().collect(),
+            Component::InputStream(_)
+            | Component::Image(_)
+            | Component::VideoClip(_)
+            | Component::Text(_)
+            | Component::Fragment(_) => vec![],
+        }
+    }
+
+    fn children_list_mut(&mut self) -> Option<&mut Vec<Self>> {
+        match self {
+            Component::View(View { children, .. })
+            | Component::WebView(WebView { children, .. })
+            | Component::Shader(Shader { children, .. })
+            | Component::Tiles(Tiles { children, .. })
+            | Component::Ticker(Ticker { children, .. }) => {
+                Some(children.get_or_insert_with(Vec::new))
+            }
+            Component::InputStream(_)
+            | Component::Image(_)
+            | Component::VideoClip(_)
+            | Component::Text(_)
+            | Component::Mask(_)
+            | Component::Rescaler(_)
+            | Component::Fragment(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use smelter_render::scene::{PatchableComponent, SceneError};
+
+    use super::*;
+
+    fn apply(root: &mut Component, patch: serde_json::Value) -> Result<(), SceneError> {
+        let patch: ScenePatch = serde_json::from_value(patch).unwrap();
+        scene::ScenePatch::from(patch).apply(root)
+    }
+
+    fn child_ids(component: &mut Component) -> Vec<String> {
+        component
+            .children_mut()
+            .into_iter()
+            .map(|child| match child {
+                Component::View(View { id: Some(id), .. }) => serde_json::to_value(id)
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+                _ => panic!("Expected view with id"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_api_scene_patch() {
+        let mut root: Component = serde_json::from_value(json!({
+            "type": "view",
+            "id": "root",
+            "children": [
+                { "type": "view", "id": "a" },
+                { "type": "view", "id": "b", "children": [{ "type": "view", "id": "c" }] },
+            ]
+        }))
+        .unwrap();
+
+        apply(
+            &mut root,
+            json!({
+                "op": "insert",
+                "parent_id": "a",
+                "component": { "type": "view", "id": "d" },
+            }),
+        )
+        .unwrap();
+        apply(&mut root, json!({ "op": "remove", "id": "c" })).unwrap();
+        apply(
+            &mut root,
+            json!({
+                "op": "replace",
+                "id": "b",
+                "component": { "type": "view", "id": "e" },
+            }),
+        )
+        .unwrap();
+        assert_eq!(child_ids(&mut root), vec!["a", "e"]);
+        assert_eq!(child_ids(root.children_mut().remove(0)), vec!["d"]);
+
+        assert!(matches!(
+            apply(
+                &mut root,
+                json!({
+                    "op": "insert",
+                    "parent_id": "root",
+                    "index": 3,
+                    "component": { "type": "view", "id": "f" },
+                }),
+            ),
+            Err(SceneError::PatchIndexOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            apply(&mut root, json!({ "op": "remove", "id": "root" })),
+            Err(SceneError::PatchRemoveRequiredComponent(_))
+        ));
+        assert!(matches!(
+            apply(&mut root, json!({ "op": "remove", "id": "missing" })),
+            Err(SceneError::PatchComponentNotFound(_))
+        ));
+    }
+}
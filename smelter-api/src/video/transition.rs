@@ -30,14 +30,84 @@ pub enum EasingFunction {
     CubicBezier { points: [f64; 4] },
 }
 
+/// Keyframe animation of component properties. Supported by `View` and `Rescaler`
+/// components, other components can be animated by wrapping them in one of those.
+/// Animation starts when a component with this animation is added to the scene. To keep the animation running across scene
+/// updates, the component needs to have an `id` and the same animation definition.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Animation {
+    /// List of keyframes sorted by `time_ms`. Values of a property are interpolated
+    /// between consecutive keyframes that define that property.
+    pub keyframes: Vec<Keyframe>,
+    /// (**default=`"none"`**) Controls what happens after the last keyframe.
+    pub repeat: Option<AnimationRepeat>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Keyframe {
+    /// Time in milliseconds since the start of the animation.
+    pub time_ms: f64,
+    /// (**default=`"linear"`**) Easing function used to interpolate from this keyframe
+    /// to the next one.
+    pub easing_function: Option<EasingFunction>,
+
+    /// Width of a component in pixels.
+    pub width: Option<f32>,
+    /// Height of a component in pixels.
+    pub height: Option<f32>,
+    /// Distance in pixels between the component's top edge and its parent's top edge.
+    /// Only applies to absolutely positioned components.
+    pub top: Option<f32>,
+    /// Distance in pixels between the component's bottom edge and its parent's bottom edge.
+    /// Only applies to absolutely positioned components.
+    pub bottom: Option<f32>,
+    /// Distance in pixels between the component's left edge and its parent's left edge.
+    /// Only applies to absolutely positioned components.
+    pub left: Option<f32>,
+    /// Distance in pixels between the component's right edge and its parent's right edge.
+    /// Only applies to absolutely positioned components.
+    pub right: Option<f32>,
+    /// Rotation of a component in degrees. Only applies to absolutely positioned components.
+    pub rotation: Option<f32>,
+    /// Opacity of a component in the range `[0, 1]`.
+    pub opacity: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimationRepeat {
+    /// Play the animation once and keep the values from the last keyframe.
+    None,
+    /// Restart the animation from the first keyframe after it finishes.
+    Loop,
+    /// Play the animation forwards and then backwards.
+    Alternate,
+}
+
 impl TryFrom<Transition> for scene::Transition {
     type Error = TypeError;
 
     fn try_from(transition: Transition) -> Result<Self, Self::Error> {
-        let interpolation_kind = match transition.easing_function.unwrap_or(EasingFunction::Linear)
-        {
-            EasingFunction::Linear => scene::InterpolationKind::Linear,
-            EasingFunction::Bounce => scene::InterpolationKind::Bounce,
+        Ok(Self {
+            duration: Duration::from_secs_f64(transition.duration_ms / 1000.0),
+            interpolation_kind: transition
+                .easing_function
+                .unwrap_or(EasingFunction::Linear)
+                .try_into()?,
+            should_interrupt: transition.should_interrupt.unwrap_or(false),
+        })
+    }
+}
+
+impl TryFrom<EasingFunction> for scene::InterpolationKind {
+    type Error = TypeError;
+
+    fn try_from(easing_function: EasingFunction) -> Result<Self, Self::Error> {
+        match easing_function {
+            EasingFunction::Linear => Ok(scene::InterpolationKind::Linear),
+            EasingFunction::Bounce => Ok(scene::InterpolationKind::Bounce),
             EasingFunction::CubicBezier { points } => {
                 if points[0] < 0.0 || points[0] > 1.0 {
                     return Err(TypeError::new(
@@ -50,19 +120,126 @@ impl TryFrom<Transition> for scene::Transition {
                     ));
                 }
 
-                scene::InterpolationKind::CubicBezier {
+                Ok(scene::InterpolationKind::CubicBezier {
                     x1: points[0],
                     y1: points[1],
                     x2: points[2],
                     y2: points[3],
-                }
+                })
             }
+        }
+    }
+}
+
+impl TryFrom<Animation> for scene::Animation {
+    type Error = TypeError;
+
+    fn try_from(animation: Animation) -> Result<Self, Self::Error> {
+        if animation.keyframes.is_empty() {
+            return Err(TypeError::new("Animation needs at least one keyframe."));
+        }
+        let keyframes = animation
+            .keyframes
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<scene::Keyframe>, _>>()?;
+        if keyframes.windows(2).any(|pair| pair[0].time > pair[1].time) {
+            return Err(TypeError::new(
+                "Keyframes have to be sorted by \"time_ms\".",
+            ));
+        }
+        if keyframes
+            .iter()
+            .filter_map(|keyframe| keyframe.properties.opacity)
+            .any(|opacity| !(0.0..=1.0).contains(&opacity))
+        {
+            return Err(TypeError::new(
+                "Keyframe \"opacity\" has to be in the range [0, 1].",
+            ));
+        }
+
+        let repeat = match animation.repeat.unwrap_or(AnimationRepeat::None) {
+            AnimationRepeat::None => scene::AnimationRepeat::None,
+            AnimationRepeat::Loop => scene::AnimationRepeat::Loop,
+            AnimationRepeat::Alternate => scene::AnimationRepeat::Alternate,
         };
+        Ok(Self { keyframes, repeat })
+    }
+}
+
+impl TryFrom<Keyframe> for scene::Keyframe {
+    type Error = TypeError;
 
+    fn try_from(keyframe: Keyframe) -> Result<Self, Self::Error> {
+        let time = Duration::try_from_secs_f64(keyframe.time_ms / 1000.0).map_err(|_| {
+            TypeError::new("Keyframe \"time_ms\" has to be a non-negative finite number.")
+        })?;
         Ok(Self {
-            duration: Duration::from_secs_f64(transition.duration_ms / 1000.0),
-            interpolation_kind,
-            should_interrupt: transition.should_interrupt.unwrap_or(false),
+            time,
+            interpolation_kind: keyframe
+                .easing_function
+                .unwrap_or(EasingFunction::Linear)
+                .try_into()?,
+            properties: scene::KeyframeProperties {
+                width: keyframe.width,
+                height: keyframe.height,
+                top: keyframe.top,
+                bottom: keyframe.bottom,
+                left: keyframe.left,
+                right: keyframe.right,
+                rotation: keyframe.rotation,
+                opacity: keyframe.opacity,
+            },
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframe(time_ms: f64, opacity: Option<f32>) -> Keyframe {
+        Keyframe {
+            time_ms,
+            easing_function: None,
+            width: None,
+            height: None,
+            top: None,
+            bottom: None,
+            left: None,
+            right: None,
+            rotation: None,
+            opacity,
+        }
+    }
+
+    fn animation(keyframes: Vec<Keyframe>) -> Result<scene::Animation, TypeError> {
+        Animation {
+            keyframes,
+            repeat: None,
+        }
+        .try_into()
+    }
+
+    #[test]
+    fn test_animation_keyframe_time() {
+        let result = animation(vec![keyframe(0.0, None), keyframe(1500.0, Some(0.5))]).unwrap();
+        assert_eq!(result.keyframes[1].time, Duration::from_millis(1500));
+        assert_eq!(result.repeat, scene::AnimationRepeat::None);
+
+        for time_ms in [-1.0, f64::NAN, f64::INFINITY, 1e300] {
+            assert!(
+                animation(vec![keyframe(time_ms, None)]).is_err(),
+                "time_ms={time_ms}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_animation_validation() {
+        assert!(animation(vec![]).is_err());
+        assert!(animation(vec![keyframe(1000.0, None), keyframe(500.0, None)]).is_err());
+        assert!(animation(vec![keyframe(0.0, Some(1.5))]).is_err());
+        assert!(animation(vec![keyframe(500.0, None), keyframe(500.0, None)]).is_ok());
+    }
+}
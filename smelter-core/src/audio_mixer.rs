@@ -1,11 +1,14 @@
 use std::{
     collections::HashMap,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
-use smelter_render::{OutputId, error::UpdateSceneError};
+use smelter_render::{InputId, OutputId, error::UpdateSceneError};
 use tracing::trace;
 
+mod level_meter;
+mod loudness_normalizer;
 mod mix;
 mod prepare_inputs;
 mod types;
@@ -13,18 +16,20 @@ mod types;
 pub use types::*;
 
 use crate::prelude::*;
-use crate::{audio_mixer::mix::SampleMixer, prelude::OutputAudioSamples};
+use crate::{audio_mixer::mix::SampleMixer, event::Event, prelude::OutputAudioSamples};
 
-use self::prepare_inputs::{expected_samples_count, prepare_input_samples};
+use self::{
+    level_meter::LevelMeter,
+    loudness_normalizer::LoudnessNormalizer,
+    prepare_inputs::{expected_samples_count, prepare_input_samples},
+};
 
 #[derive(Debug, Clone)]
 pub(super) struct AudioMixer(Arc<Mutex<InternalAudioMixer>>);
 
 impl AudioMixer {
-    pub fn new(mixing_sample_rate: u32) -> Self {
-        Self(Arc::new(Mutex::new(InternalAudioMixer::new(
-            mixing_sample_rate,
-        ))))
+    pub fn new(ctx: &Arc<PipelineCtx>) -> Self {
+        Self(Arc::new(Mutex::new(InternalAudioMixer::new(ctx.clone()))))
     }
 
     pub fn mix_samples(&self, samples_set: InputSamplesSet) -> OutputSamplesSet {
@@ -32,19 +37,19 @@ impl AudioMixer {
         self.0.lock().unwrap().mix_samples(samples_set)
     }
 
-    pub fn register_output(
-        &self,
-        output_id: OutputId,
-        audio: AudioMixerConfig,
-        mixing_strategy: AudioMixingStrategy,
-        channels: AudioChannels,
-    ) {
-        self.0.lock().unwrap().outputs.insert(
+    pub fn register_output(&self, output_id: OutputId, options: RegisterOutputAudioOptions) {
+        let mut mixer = self.0.lock().unwrap();
+        let sample_rate = mixer.mixing_sample_rate;
+        mixer.outputs.insert(
             output_id,
             AudioOutputInfo {
-                audio,
-                channels,
-                mixing_strategy,
+                audio: options.initial,
+                channels: options.channels,
+                mixing_strategy: options.mixing_strategy,
+                loudness_normalizer: options
+                    .loudness_normalization
+                    .map(|options| LoudnessNormalizer::new(options, sample_rate)),
+                level_meter: LevelMeter::new(sample_rate),
             },
         );
     }
@@ -53,12 +58,29 @@ impl AudioMixer {
         self.0.lock().unwrap().outputs.remove(output_id);
     }
 
-    pub fn update_output(
+    /// Removes level meter of the input. Meters are kept while the input is registered,
+    /// even if it does not deliver samples for some batches.
+    pub fn unregister_input(&self, input_id: &InputId) {
+        self.0.lock().unwrap().input_level_meters.remove(input_id);
+    }
+
+    /// Updates multiple outputs, so new configurations are used starting from the
+    /// same batch of samples. If any output is not registered, none of them is updated.
+    pub fn update_outputs(
         &self,
-        output_id: &OutputId,
-        audio: AudioMixerConfig,
+        updates: Vec<(OutputId, AudioMixerConfig)>,
     ) -> Result<(), UpdateSceneError> {
-        self.0.lock().unwrap().update_output(output_id, audio)
+        let mut guard = self.0.lock().unwrap();
+        if let Some((output_id, _)) = updates
+            .iter()
+            .find(|(output_id, _)| !guard.outputs.contains_key(output_id))
+        {
+            return Err(UpdateSceneError::OutputNotRegistered(output_id.clone()));
+        }
+        for (output_id, audio) in updates {
+            guard.update_output(&output_id, audio)?;
+        }
+        Ok(())
     }
 }
 
@@ -67,11 +89,16 @@ const VOL_UP_THRESHOLD: f64 = 0.7;
 const VOL_DOWN_INCREMENT: f64 = 0.02;
 const VOL_UP_INCREMENT: f64 = 0.01;
 
+/// How often audio levels are reported, measured in PTS of mixed samples.
+const LEVELS_REPORT_INTERVAL: Duration = Duration::from_millis(100);
+
 #[derive(Debug)]
 struct AudioOutputInfo {
     audio: AudioMixerConfig,
     mixing_strategy: AudioMixingStrategy,
     channels: AudioChannels,
+    loudness_normalizer: Option<LoudnessNormalizer>,
+    level_meter: LevelMeter,
 }
 
 #[derive(Debug)]
@@ -79,19 +106,26 @@ pub(super) struct InternalAudioMixer {
     outputs: HashMap<OutputId, AudioOutputInfo>,
     mixing_sample_rate: u32,
     sample_mixer: SampleMixer,
+
+    input_level_meters: HashMap<InputId, LevelMeter>,
+    levels_reported_pts: Duration,
+    ctx: Arc<PipelineCtx>,
 }
 
 impl InternalAudioMixer {
-    pub fn new(mixing_sample_rate: u32) -> Self {
+    pub fn new(ctx: Arc<PipelineCtx>) -> Self {
         Self {
             outputs: HashMap::new(),
-            mixing_sample_rate,
+            mixing_sample_rate: ctx.mixing_sample_rate,
             sample_mixer: SampleMixer::new(
                 VOL_DOWN_THRESHOLD,
                 VOL_UP_THRESHOLD,
                 VOL_DOWN_INCREMENT,
                 VOL_UP_INCREMENT,
             ),
+            input_level_meters: HashMap::new(),
+            levels_reported_pts: Duration::ZERO,
+            ctx,
         }
     }
 
@@ -111,6 +145,7 @@ impl InternalAudioMixer {
 
     pub fn mix_samples(&mut self, samples_set: InputSamplesSet) -> OutputSamplesSet {
         let start_pts = samples_set.start_pts;
+        let end_pts = samples_set.end_pts;
         let samples_count = expected_samples_count(
             samples_set.start_pts,
             samples_set.end_pts,
@@ -118,9 +153,16 @@ impl InternalAudioMixer {
         );
         let input_samples = prepare_input_samples(samples_set, self.mixing_sample_rate);
 
-        OutputSamplesSet(
+        for (input_id, samples) in input_samples.iter() {
+            self.input_level_meters
+                .entry(input_id.clone())
+                .or_insert_with(|| LevelMeter::new(self.mixing_sample_rate))
+                .process(samples);
+        }
+
+        let output_samples = OutputSamplesSet(
             self.outputs
-                .iter()
+                .iter_mut()
                 .map(|(output_id, output_info)| {
                     let samples =
                         self.sample_mixer
@@ -128,6 +170,44 @@ impl InternalAudioMixer {
                     (output_id.clone(), OutputAudioSamples { samples, start_pts })
                 })
                 .collect(),
-        )
+        );
+
+        if end_pts >= self.levels_reported_pts + LEVELS_REPORT_INTERVAL {
+            self.levels_reported_pts = end_pts;
+            self.report_levels();
+        }
+        output_samples
+    }
+
+    /// Sends levels measured since the previous report to the stats monitor
+    /// and emits them as events.
+    fn report_levels(&mut self) {
+        let inputs: HashMap<_, _> = self
+            .input_level_meters
+            .iter_mut()
+            .map(|(input_id, meter)| (input_id.clone(), meter.levels()))
+            .collect();
+        let outputs: HashMap<_, _> = self
+            .outputs
+            .iter_mut()
+            .map(|(output_id, info)| (output_id.clone(), info.level_meter.levels()))
+            .collect();
+
+        for (input_id, levels) in inputs.iter() {
+            self.ctx
+                .event_emitter
+                .emit(Event::AudioInputLevels(input_id.clone(), *levels));
+        }
+        for (output_id, levels) in outputs.iter() {
+            self.ctx
+                .event_emitter
+                .emit(Event::AudioOutputLevels(output_id.clone(), *levels));
+        }
+        self.ctx
+            .stats_sender
+            .send(StatsEvent::AudioLevels(AudioLevelsStatsEvent {
+                inputs,
+                outputs,
+            }));
     }
 }
@@ -0,0 +1,255 @@
+use std::{collections::VecDeque, f64::consts::PI, time::Duration};
+
+use crate::prelude::*;
+
+const SHORT_TERM_WINDOW: Duration = Duration::from_secs(3);
+
+/// Measures levels of a stereo signal. Peak and RMS are calculated from samples
+/// processed since the last [`LevelMeter::levels`] call, short-term loudness from
+/// the last 3 seconds of K-weighted samples (ITU-R BS.1770).
+#[derive(Debug)]
+pub(super) struct LevelMeter {
+    filters: [KWeightingFilter; 2],
+
+    peak: f64,
+    sum_squares: f64,
+    samples_count: usize,
+
+    /// Sum of squared K-weighted samples of both channels and sample count of each processed batch.
+    short_term_batches: VecDeque<(f64, usize)>,
+    short_term_samples_count: usize,
+    short_term_max_samples_count: usize,
+}
+
+impl LevelMeter {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            filters: [
+                KWeightingFilter::new(sample_rate),
+                KWeightingFilter::new(sample_rate),
+            ],
+            peak: 0.0,
+            sum_squares: 0.0,
+            samples_count: 0,
+            short_term_batches: VecDeque::new(),
+            short_term_samples_count: 0,
+            short_term_max_samples_count: (SHORT_TERM_WINDOW.as_secs_f64() * sample_rate as f64)
+                as usize,
+        }
+    }
+
+    pub fn process(&mut self, samples: &[(f64, f64)]) {
+        let mut weighted_sum_squares = 0.0;
+        for (l, r) in samples.iter().copied() {
+            self.peak = f64::max(self.peak, f64::max(l.abs(), r.abs()));
+            self.sum_squares += (l * l + r * r) / 2.0;
+
+            let l = self.filters[0].process(l);
+            let r = self.filters[1].process(r);
+            weighted_sum_squares += l * l + r * r;
+        }
+        self.samples_count += samples.len();
+
+        self.short_term_batches
+            .push_back((weighted_sum_squares, samples.len()));
+        self.short_term_samples_count += samples.len();
+        while self.short_term_samples_count > self.short_term_max_samples_count {
+            let Some((_, count)) = self.short_term_batches.pop_front() else {
+                break;
+            };
+            self.short_term_samples_count -= count;
+        }
+    }
+
+    /// Returns levels and resets peak and RMS measurement.
+    pub fn levels(&mut self) -> AudioLevels {
+        if self.samples_count == 0 {
+            return AudioLevels::silence();
+        }
+        let mean_square = self.sum_squares / self.samples_count as f64;
+        let short_term_sum: f64 = self.short_term_batches.iter().map(|(sum, _)| sum).sum();
+        let short_term_mean_square =
+            short_term_sum / usize::max(self.short_term_samples_count, 1) as f64;
+
+        let levels = AudioLevels {
+            peak_dbfs: clamp_level(20.0 * self.peak.log10()),
+            rms_dbfs: clamp_level(10.0 * mean_square.log10()),
+            short_term_lufs: clamp_level(loudness(short_term_mean_square)),
+        };
+
+        self.peak = 0.0;
+        self.sum_squares = 0.0;
+        self.samples_count = 0;
+        levels
+    }
+}
+
+/// Loudness (LUFS) of a signal with the sum of mean squares of K-weighted channels.
+pub(super) fn loudness(mean_square: f64) -> f64 {
+    -0.691 + 10.0 * mean_square.log10()
+}
+
+fn clamp_level(value: f64) -> f64 {
+    match value.is_nan() {
+        true => AudioLevels::MIN_LEVEL,
+        false => f64::max(value, AudioLevels::MIN_LEVEL),
+    }
+}
+
+/// K-weighting filter from ITU-R BS.1770, a high shelf followed by a high pass filter.
+/// Coefficients are calculated for the sample rate, so it is not limited to 48kHz.
+#[derive(Debug)]
+pub(super) struct KWeightingFilter {
+    shelf: Biquad,
+    high_pass: Biquad,
+}
+
+impl KWeightingFilter {
+    pub fn new(sample_rate: u32) -> Self {
+        let sample_rate = sample_rate as f64;
+
+        let f0 = 1681.974450955533;
+        let gain_db = 3.999843853973347;
+        let q = 0.7071752369554196;
+        let k = f64::tan(PI * f0 / sample_rate);
+        let vh = f64::powf(10.0, gain_db / 20.0);
+        let vb = f64::powf(vh, 0.4996667741545416);
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = Biquad::new(
+            [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        );
+
+        let f0 = 38.13547087602444;
+        let q = 0.5003270373238773;
+        let k = f64::tan(PI * f0 / sample_rate);
+        let a0 = 1.0 + k / q + k * k;
+        let high_pass = Biquad::new(
+            [1.0, -2.0, 1.0],
+            [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        );
+
+        Self { shelf, high_pass }
+    }
+
+    pub fn process(&mut self, sample: f64) -> f64 {
+        self.high_pass.process(self.shelf.process(sample))
+    }
+}
+
+/// Second order IIR filter (transposed direct form II) with normalized `a0`.
+#[derive(Debug)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    state: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Self {
+            b,
+            a,
+            state: [0.0; 2],
+        }
+    }
+
+    fn process(&mut self, sample: f64) -> f64 {
+        let output = self.b[0] * sample + self.state[0];
+        self.state[0] = self.b[1] * sample - self.a[0] * output + self.state[1];
+        self.state[1] = self.b[2] * sample - self.a[1] * output;
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 48_000;
+    const BATCH_SIZE: usize = 960;
+
+    fn sine(frequency: f64, amplitude: f64, sample_rate: u32, duration: Duration) -> Vec<f64> {
+        let sample_count = (duration.as_secs_f64() * sample_rate as f64) as usize;
+        (0..sample_count)
+            .map(|index| {
+                amplitude * f64::sin(2.0 * PI * frequency * index as f64 / sample_rate as f64)
+            })
+            .collect()
+    }
+
+    /// Gain (dB) of the K-weighting filter for a sine wave, measured after the filter settles.
+    fn k_weighting_gain(frequency: f64, sample_rate: u32) -> f64 {
+        let mut filter = KWeightingFilter::new(sample_rate);
+        let samples = sine(frequency, 1.0, sample_rate, Duration::from_secs(2));
+        let filtered: Vec<f64> = samples.iter().map(|s| filter.process(*s)).collect();
+        let settled = &filtered[filtered.len() / 2..];
+        let mean_square = settled.iter().map(|s| s * s).sum::<f64>() / settled.len() as f64;
+        10.0 * f64::log10(mean_square / 0.5)
+    }
+
+    fn process_sine(meter: &mut LevelMeter, amplitude: f64, duration: Duration) {
+        let samples: Vec<(f64, f64)> = sine(997.0, amplitude, SAMPLE_RATE, duration)
+            .into_iter()
+            .map(|value| (value, value))
+            .collect();
+        for batch in samples.chunks(BATCH_SIZE) {
+            meter.process(batch);
+        }
+    }
+
+    #[test]
+    fn test_k_weighting_filter() {
+        // Gain at 997 Hz compensates for the -0.691 offset in the loudness formula.
+        let gain = k_weighting_gain(997.0, SAMPLE_RATE);
+        assert!((gain - 0.691).abs() < 0.05, "gain at 997 Hz: {gain}");
+        let gain = k_weighting_gain(997.0, 44_100);
+        assert!(
+            (gain - 0.691).abs() < 0.05,
+            "gain at 997 Hz (44.1kHz): {gain}"
+        );
+
+        let gain = k_weighting_gain(20.0, SAMPLE_RATE);
+        assert!(gain < -10.0, "gain at 20 Hz: {gain}");
+        let gain = k_weighting_gain(10_000.0, SAMPLE_RATE);
+        assert!((gain - 4.0).abs() < 0.2, "gain at 10 kHz: {gain}");
+    }
+
+    #[test]
+    fn test_sine_levels() {
+        // -20 dBFS sine in both channels
+        let mut meter = LevelMeter::new(SAMPLE_RATE);
+        process_sine(&mut meter, 0.1, SHORT_TERM_WINDOW);
+
+        let levels = meter.levels();
+        assert!((levels.peak_dbfs + 20.0).abs() < 0.01, "{levels:?}");
+        assert!((levels.rms_dbfs + 23.01).abs() < 0.05, "{levels:?}");
+        assert!((levels.short_term_lufs + 20.0).abs() < 0.1, "{levels:?}");
+    }
+
+    #[test]
+    fn test_levels_reset() {
+        let mut meter = LevelMeter::new(SAMPLE_RATE);
+        assert_eq!(meter.levels().short_term_lufs, AudioLevels::MIN_LEVEL);
+
+        process_sine(&mut meter, 0.1, Duration::from_secs(1));
+        meter.levels();
+
+        // Peak and RMS only include samples since the previous call, short-term
+        // loudness still includes the sine from the last 3 seconds.
+        meter.process(&[(0.0, 0.0); BATCH_SIZE]);
+        let levels = meter.levels();
+        assert_eq!(levels.peak_dbfs, AudioLevels::MIN_LEVEL);
+        assert_eq!(levels.rms_dbfs, AudioLevels::MIN_LEVEL);
+        assert!(levels.short_term_lufs > -21.0, "{levels:?}");
+
+        // Sine is no longer in the short-term window.
+        process_sine(&mut meter, 0.0, SHORT_TERM_WINDOW);
+        let levels = meter.levels();
+        assert!(levels.short_term_lufs < -90.0, "{levels:?}");
+    }
+}
@@ -0,0 +1,353 @@
+use std::{collections::VecDeque, f64::consts::PI, time::Duration};
+
+use crate::{
+    audio_mixer::level_meter::{KWeightingFilter, loudness},
+    prelude::*,
+};
+
+/// Duration of the most recent audio used to measure loudness.
+const MEASUREMENT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Loudness is measured in 400ms blocks with 75% overlap (ITU-R BS.1770),
+/// so blocks are built from 100ms steps.
+const BLOCK_STEP: Duration = Duration::from_millis(100);
+const STEPS_PER_BLOCK: usize = 4;
+
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+/// Range of the applied gain. Upper bound prevents amplifying background noise
+/// when the program is quiet for a long time.
+const MIN_GAIN_DB: f64 = -40.0;
+const MAX_GAIN_DB: f64 = 20.0;
+
+/// How fast the gain follows changes of measured loudness.
+const GAIN_CHANGE_DB_PER_SEC: f64 = 3.0;
+
+/// How fast the limiter restores the gain after it was reduced to meet the true peak limit.
+const LIMITER_RELEASE_DB_PER_SEC: f64 = 6.0;
+
+const TRUE_PEAK_OVERSAMPLING: usize = 4;
+const TRUE_PEAK_TAPS: usize = 12;
+
+/// Output is delayed by the delay of the true peak meter, so the limiter knows the
+/// true peak around every sample before the sample is output.
+const LOOKAHEAD_SAMPLES: usize = TRUE_PEAK_TAPS / 2;
+
+/// Adjusts gain of a mixed stereo signal, so its loudness matches the target.
+/// Loudness is measured with gating from EBU R128 over a sliding window and the gain
+/// changes gradually. True peak limiter is applied after the gain, it reduces the gain
+/// immediately and restores it slowly. Output is delayed by [`LOOKAHEAD_SAMPLES`].
+#[derive(Debug)]
+pub(super) struct LoudnessNormalizer {
+    sample_rate: u32,
+    target_lufs: f64,
+    true_peak_limit: f64,
+
+    filters: [KWeightingFilter; 2],
+    true_peak_meters: [TruePeakMeter; 2],
+    /// Samples that were measured, but not output yet.
+    lookahead: VecDeque<(f64, f64)>,
+
+    step_sum_squares: f64,
+    step_samples_count: usize,
+    step_max_samples_count: usize,
+    /// Sum of mean squares of K-weighted channels for each step in the measurement window.
+    steps: VecDeque<f64>,
+
+    gain_db: f64,
+    limiter_gain_db: f64,
+}
+
+impl LoudnessNormalizer {
+    pub fn new(options: LoudnessNormalizationOptions, sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            target_lufs: options.target_lufs,
+            true_peak_limit: db_to_linear(options.true_peak_limit_dbtp),
+            filters: [
+                KWeightingFilter::new(sample_rate),
+                KWeightingFilter::new(sample_rate),
+            ],
+            true_peak_meters: [TruePeakMeter::new(), TruePeakMeter::new()],
+            lookahead: VecDeque::from(vec![(0.0, 0.0); LOOKAHEAD_SAMPLES]),
+            step_sum_squares: 0.0,
+            step_samples_count: 0,
+            step_max_samples_count: (BLOCK_STEP.as_secs_f64() * sample_rate as f64) as usize,
+            steps: VecDeque::new(),
+            gain_db: 0.0,
+            limiter_gain_db: 0.0,
+        }
+    }
+
+    pub fn process(&mut self, samples: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+        if samples.is_empty() {
+            return samples;
+        }
+        let batch_duration_secs = samples.len() as f64 / self.sample_rate as f64;
+
+        // Peaks returned by the meters after processing the batch describe samples
+        // delayed by `LOOKAHEAD_SAMPLES`, that is exactly the samples output below.
+        let mut true_peak: f64 = 0.0;
+        for (l, r) in samples.iter().copied() {
+            self.measure(l, r);
+            let l_peak = self.true_peak_meters[0].process(l);
+            let r_peak = self.true_peak_meters[1].process(r);
+            true_peak = f64::max(true_peak, f64::max(l_peak, r_peak));
+        }
+        let sample_count = samples.len();
+        self.lookahead.extend(samples);
+        let samples: Vec<(f64, f64)> = self.lookahead.drain(..sample_count).collect();
+
+        let old_gain_db = self.gain_db;
+        // Gain does not change during silence.
+        if let Some(measured_lufs) = self.gated_loudness() {
+            let desired_gain_db =
+                (self.target_lufs - measured_lufs).clamp(MIN_GAIN_DB, MAX_GAIN_DB);
+            let max_change_db = GAIN_CHANGE_DB_PER_SEC * batch_duration_secs;
+            self.gain_db += (desired_gain_db - self.gain_db).clamp(-max_change_db, max_change_db);
+        }
+
+        // Gain changes linearly within the batch, so the highest gain is at one of its ends.
+        let max_gain = db_to_linear(f64::max(old_gain_db, self.gain_db));
+        let limit_db = linear_to_db(self.true_peak_limit / (true_peak * max_gain));
+        let released_db = self.limiter_gain_db + LIMITER_RELEASE_DB_PER_SEC * batch_duration_secs;
+        self.limiter_gain_db = f64::min(f64::min(released_db, 0.0), limit_db);
+
+        let old_gain = db_to_linear(old_gain_db);
+        let new_gain = db_to_linear(self.gain_db);
+        let limiter_gain = db_to_linear(self.limiter_gain_db);
+        samples
+            .into_iter()
+            .enumerate()
+            .map(|(index, (l, r))| {
+                let gain = old_gain + (new_gain - old_gain) * index as f64 / sample_count as f64;
+                let gain = gain * limiter_gain;
+                ((l * gain).clamp(-1.0, 1.0), (r * gain).clamp(-1.0, 1.0))
+            })
+            .collect()
+    }
+
+    fn measure(&mut self, l: f64, r: f64) {
+        let l = self.filters[0].process(l);
+        let r = self.filters[1].process(r);
+        self.step_sum_squares += l * l + r * r;
+        self.step_samples_count += 1;
+
+        if self.step_samples_count >= self.step_max_samples_count {
+            self.steps
+                .push_back(self.step_sum_squares / self.step_samples_count as f64);
+            let max_steps = MEASUREMENT_WINDOW.as_millis() / BLOCK_STEP.as_millis();
+            if self.steps.len() > max_steps as usize {
+                self.steps.pop_front();
+            }
+            self.step_sum_squares = 0.0;
+            self.step_samples_count = 0;
+        }
+    }
+
+    /// Loudness of the measurement window with absolute and relative gating applied.
+    /// Returns `None` if all blocks are below the absolute gate.
+    fn gated_loudness(&mut self) -> Option<f64> {
+        let blocks: Vec<f64> = self
+            .steps
+            .make_contiguous()
+            .windows(STEPS_PER_BLOCK)
+            .map(|steps| steps.iter().sum::<f64>() / STEPS_PER_BLOCK as f64)
+            .filter(|mean_square| loudness(*mean_square) > ABSOLUTE_GATE_LUFS)
+            .collect();
+        if blocks.is_empty() {
+            return None;
+        }
+
+        let relative_gate = loudness(mean(&blocks)) + RELATIVE_GATE_LU;
+        let gated_blocks: Vec<f64> = blocks
+            .into_iter()
+            .filter(|mean_square| loudness(*mean_square) > relative_gate)
+            .collect();
+        Some(loudness(mean(&gated_blocks)))
+    }
+}
+
+/// Estimates true peak of a signal (ITU-R BS.1770) by interpolating values between
+/// samples with a windowed sinc filter. Returned peak is delayed by half of the filter length.
+#[derive(Debug)]
+struct TruePeakMeter {
+    /// Filter coefficients for each interpolated position between samples.
+    phases: Vec<[f64; TRUE_PEAK_TAPS]>,
+    history: VecDeque<f64>,
+}
+
+impl TruePeakMeter {
+    fn new() -> Self {
+        let half_length = (TRUE_PEAK_TAPS / 2) as f64;
+        let phases = (1..TRUE_PEAK_OVERSAMPLING)
+            .map(|phase| {
+                let offset = phase as f64 / TRUE_PEAK_OVERSAMPLING as f64;
+                std::array::from_fn(|tap| {
+                    // distance between the tap and the interpolated position
+                    let x = tap as f64 - (half_length - 1.0) - offset;
+                    let sinc = f64::sin(PI * x) / (PI * x);
+                    let window = 0.5 + 0.5 * f64::cos(PI * x / half_length);
+                    sinc * window
+                })
+            })
+            .collect();
+        Self {
+            phases,
+            history: VecDeque::from(vec![0.0; TRUE_PEAK_TAPS]),
+        }
+    }
+
+    fn process(&mut self, sample: f64) -> f64 {
+        self.history.pop_front();
+        self.history.push_back(sample);
+
+        let sample_peak = self.history[TRUE_PEAK_TAPS / 2 - 1].abs();
+        self.phases
+            .iter()
+            .map(|coefficients| {
+                let value: f64 = coefficients
+                    .iter()
+                    .zip(self.history.iter())
+                    .map(|(coefficient, sample)| coefficient * sample)
+                    .sum();
+                value.abs()
+            })
+            .fold(sample_peak, f64::max)
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn db_to_linear(db: f64) -> f64 {
+    f64::powf(10.0, db / 20.0)
+}
+
+fn linear_to_db(value: f64) -> f64 {
+    20.0 * value.log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 48_000;
+    const BATCH_SIZE: usize = 960;
+
+    fn normalize_sine(
+        options: LoudnessNormalizationOptions,
+        amplitude: f64,
+        duration: Duration,
+    ) -> Vec<(f64, f64)> {
+        let mut normalizer = LoudnessNormalizer::new(options, SAMPLE_RATE);
+        let sample_count = (duration.as_secs_f64() * SAMPLE_RATE as f64) as usize;
+        let samples: Vec<(f64, f64)> = (0..sample_count)
+            .map(|index| {
+                let value =
+                    amplitude * f64::sin(2.0 * PI * 997.0 * index as f64 / SAMPLE_RATE as f64);
+                (value, value)
+            })
+            .collect();
+        samples
+            .chunks(BATCH_SIZE)
+            .flat_map(|batch| normalizer.process(batch.to_vec()))
+            .collect()
+    }
+
+    fn measure_loudness(samples: &[(f64, f64)]) -> f64 {
+        let mut filters = [
+            KWeightingFilter::new(SAMPLE_RATE),
+            KWeightingFilter::new(SAMPLE_RATE),
+        ];
+        let sum_squares: f64 = samples
+            .iter()
+            .map(|(l, r)| {
+                let l = filters[0].process(*l);
+                let r = filters[1].process(*r);
+                l * l + r * r
+            })
+            .sum();
+        loudness(sum_squares / samples.len() as f64)
+    }
+
+    fn measure_true_peak(samples: &[(f64, f64)]) -> f64 {
+        let mut meters = [TruePeakMeter::new(), TruePeakMeter::new()];
+        samples
+            .iter()
+            .map(|(l, r)| f64::max(meters[0].process(*l), meters[1].process(*r)))
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_converges_to_target() {
+        let options = LoudnessNormalizationOptions {
+            target_lufs: -23.0,
+            true_peak_limit_dbtp: -1.0,
+        };
+        // about -20 LUFS
+        let output = normalize_sine(options, 0.1, Duration::from_secs(15));
+
+        let last_3_secs = &output[output.len() - 3 * SAMPLE_RATE as usize..];
+        let measured_lufs = measure_loudness(last_3_secs);
+        assert!(
+            (measured_lufs - options.target_lufs).abs() < 0.5,
+            "measured: {measured_lufs} LUFS"
+        );
+        assert!(linear_to_db(measure_true_peak(&output)) <= options.true_peak_limit_dbtp);
+    }
+
+    #[test]
+    fn test_true_peak_limit() {
+        // Sine at about -6 LUFS would need a gain above the limit to reach the target.
+        let options = LoudnessNormalizationOptions {
+            target_lufs: 0.0,
+            true_peak_limit_dbtp: -1.0,
+        };
+        let output = normalize_sine(options, 0.5, Duration::from_secs(6));
+
+        let limit = db_to_linear(options.true_peak_limit_dbtp);
+        let sample_peak = output
+            .iter()
+            .map(|(l, r)| f64::max(l.abs(), r.abs()))
+            .fold(0.0, f64::max);
+        assert!(sample_peak <= limit + 1e-9, "sample peak: {sample_peak}");
+
+        let true_peak_dbtp = linear_to_db(measure_true_peak(&output));
+        assert!(
+            true_peak_dbtp <= options.true_peak_limit_dbtp + 0.01,
+            "true peak: {true_peak_dbtp} dBTP"
+        );
+
+        let last_3_secs = &output[output.len() - 3 * SAMPLE_RATE as usize..];
+        assert!(measure_loudness(last_3_secs) < options.target_lufs);
+    }
+
+    /// Peak at the end of a batch is limited in the same batch, because the output
+    /// is delayed by the look-ahead.
+    #[test]
+    fn test_lookahead() {
+        let options = LoudnessNormalizationOptions {
+            target_lufs: -23.0,
+            true_peak_limit_dbtp: -1.0,
+        };
+        let mut normalizer = LoudnessNormalizer::new(options, SAMPLE_RATE);
+        let mut peak_batch = vec![(0.0, 0.0); BATCH_SIZE];
+        peak_batch[BATCH_SIZE - 1] = (1.0, -1.0);
+
+        let output: Vec<(f64, f64)> = [vec![(0.0, 0.0); BATCH_SIZE], peak_batch]
+            .into_iter()
+            .chain(std::iter::repeat_n(vec![(0.0, 0.0); BATCH_SIZE], 2))
+            .flat_map(|batch| normalizer.process(batch))
+            .collect();
+
+        let peak_index = 2 * BATCH_SIZE - 1 + LOOKAHEAD_SAMPLES;
+        let (l, r) = output[peak_index];
+        assert!(l > 0.0 && r < 0.0);
+        assert!(l <= db_to_linear(options.true_peak_limit_dbtp) + 1e-9);
+        assert!(-r <= db_to_linear(options.true_peak_limit_dbtp) + 1e-9);
+        assert_eq!(output.len(), 4 * BATCH_SIZE);
+    }
+}
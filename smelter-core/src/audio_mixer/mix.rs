@@ -44,7 +44,7 @@ impl SampleMixer {
     pub fn mix_samples(
         &mut self,
         input_samples: &HashMap<InputId, Vec<(f64, f64)>>,
-        output_info: &AudioOutputInfo,
+        output_info: &mut AudioOutputInfo,
         samples_count: usize,
     ) -> AudioSamples {
         let summed_samples = self.sum_samples(
@@ -57,6 +57,11 @@ impl SampleMixer {
             AudioMixingStrategy::SumClip => self.clip_samples(summed_samples),
             AudioMixingStrategy::SumScale => self.scale_samples(summed_samples),
         };
+        let mixed = match &mut output_info.loudness_normalizer {
+            Some(normalizer) => normalizer.process(mixed),
+            None => mixed,
+        };
+        output_info.level_meter.process(&mixed);
 
         match output_info.channels {
             AudioChannels::Mono => {
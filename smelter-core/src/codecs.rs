@@ -66,6 +66,9 @@ pub enum OutputPixelFormat {
     YUV420P,
     YUV422P,
     YUV444P,
+    /// 10-bit YUV 4:2:0 with SDR color, converted from the 8-bit composition. HDR
+    /// output is not supported.
+    YUV420P10,
 }
 
 pub(crate) trait AudioEncoderOptionsExt {
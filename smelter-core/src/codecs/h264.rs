@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use smelter_render::Resolution;
+use smelter_render::{OutputColorSpace, Resolution};
 
 use crate::codecs::{OutputPixelFormat, VideoEncoderBitrate};
 
@@ -24,6 +24,7 @@ pub struct FfmpegH264EncoderOptions {
     pub bitrate: Option<VideoEncoderBitrate>,
     pub resolution: Resolution,
     pub pixel_format: OutputPixelFormat,
+    pub color_space: OutputColorSpace,
     pub raw_options: Vec<(Arc<str>, Arc<str>)>,
 }
 
@@ -31,6 +32,7 @@ pub struct FfmpegH264EncoderOptions {
 pub struct VulkanH264EncoderOptions {
     pub resolution: Resolution,
     pub bitrate: Option<VideoEncoderBitrate>,
+    pub color_space: OutputColorSpace,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use smelter_render::Resolution;
+use smelter_render::{OutputColorSpace, Resolution};
 
 use crate::codecs::{OutputPixelFormat, VideoEncoderBitrate};
 
@@ -9,5 +9,6 @@ pub struct FfmpegVp9EncoderOptions {
     pub resolution: Resolution,
     pub bitrate: Option<VideoEncoderBitrate>,
     pub pixel_format: OutputPixelFormat,
+    pub color_space: OutputColorSpace,
     pub raw_options: Vec<(Arc<str>, Arc<str>)>,
 }
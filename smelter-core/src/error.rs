@@ -1,8 +1,11 @@
+use std::sync::Arc;
+
 use smelter_render::{
-    InputId, OutputId,
+    InputId, OutputId, RendererId,
     error::{
         InitRendererEngineError, RegisterError, RegisterRendererError, RequestKeyframeError,
-        UnregisterRendererError, UpdateSceneError, WgpuError,
+        SetInputSlateError, SnapshotError, UnregisterRendererError, UpdateFragmentError,
+        UpdateSceneError, WgpuError,
     },
 };
 
@@ -24,6 +27,9 @@ pub enum InitPipelineError {
 
     #[error("Failed to initialize WHIP WHEP server.")]
     WhipWhepServerInitError(#[source] std::io::Error),
+
+    #[error("Failed to start media monitor thread.")]
+    CreateMediaMonitorThread(#[source] std::io::Error),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -33,6 +39,9 @@ pub enum RegisterInputError {
 
     #[error("Input initialization error while registering input for stream \"{0}\".")]
     InputError(InputId, #[source] InputInitError),
+
+    #[error("Failed to configure failover while registering input for stream \"{0}\".")]
+    FailoverError(InputId, #[source] InputFailoverError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -71,6 +80,51 @@ pub enum UnregisterInputError {
     StillInUse(InputId),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum InputFailoverError {
+    #[error("Failed to configure failover. Input stream \"{0}\" does not exist.")]
+    NotFound(InputId),
+
+    #[error("Failed to configure failover. Backup input stream \"{0}\" does not exist.")]
+    BackupNotFound(InputId),
+
+    #[error("Failed to configure failover. Input stream \"{0}\" can't be its own backup.")]
+    BackupSameAsInput(InputId),
+
+    #[error("Failed to configure failover slate.")]
+    Slate(#[from] SetInputSlateError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PlaylistUpdateError {
+    #[error("Failed to update playlist. Input stream \"{0}\" does not exist.")]
+    NotFound(InputId),
+
+    #[error("Failed to update playlist. Input stream \"{0}\" is not a playlist.")]
+    NotAPlaylist(InputId),
+
+    #[error("Playlist item \"{0}\" is already queued.")]
+    DuplicateItem(Arc<str>),
+
+    #[error("Playlist item \"{0}\" is not queued. Items that already started can't be removed.")]
+    ItemNotQueued(Arc<str>),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayBufferError {
+    #[error("Failed to enable replay buffer. Input stream \"{0}\" does not exist.")]
+    NotFound(InputId),
+
+    #[error("Failed to enable replay buffer. Input stream \"{0}\" does not decode encoded media.")]
+    UnsupportedInput(InputId),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AudioDelayError {
+    #[error("Failed to set audio delay. Input stream \"{0}\" does not exist.")]
+    NotFound(InputId),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum UnregisterOutputError {
     #[error("Failed to unregister output stream. Stream \"{0}\" does not exist.")]
@@ -180,10 +234,28 @@ pub enum InputInitError {
     #[error("Invalid video decoder provided. Expected {expected:?} decoder")]
     InvalidVideoDecoderProvided { expected: VideoCodec },
 
+    #[error("Playlist item \"{0}\" is specified more than once.")]
+    DuplicatePlaylistItem(Arc<str>),
+
+    #[error("Input stream \"{0}\" does not exist or does not have a replay buffer enabled.")]
+    ReplayBufferNotFound(InputId),
+
+    #[error("Replay buffer of input stream \"{0}\" has no media in the requested range.")]
+    EmptyReplayRange(InputId),
+
     #[error("Internal Server Error")]
     InternalServerError,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum RegisterVideoClipError {
+    #[error("Failed to decode video clip \"{1}\".")]
+    Decode(#[source] VideoClipDecodeError, RendererId),
+
+    #[error(transparent)]
+    Renderer(#[from] RegisterRendererError),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum DecoderInitError {
     #[cfg(feature = "vk-video")]
@@ -244,6 +316,10 @@ impl From<&RegisterInputError> for PipelineErrorInfo {
             RegisterInputError::InputError(_, _) => {
                 PipelineErrorInfo::new(INPUT_ERROR, ErrorType::ServerError)
             }
+
+            RegisterInputError::FailoverError(_, _) => {
+                PipelineErrorInfo::new(INVALID_INPUT_FAILOVER, ErrorType::UserError)
+            }
         }
     }
 }
@@ -293,6 +369,64 @@ impl From<&UnregisterInputError> for PipelineErrorInfo {
     }
 }
 
+const INVALID_INPUT_FAILOVER: &str = "INVALID_INPUT_FAILOVER";
+
+impl From<&InputFailoverError> for PipelineErrorInfo {
+    fn from(err: &InputFailoverError) -> Self {
+        match err {
+            InputFailoverError::NotFound(_) => {
+                PipelineErrorInfo::new(INPUT_STREAM_NOT_FOUND, ErrorType::EntityNotFound)
+            }
+            InputFailoverError::BackupNotFound(_)
+            | InputFailoverError::BackupSameAsInput(_)
+            | InputFailoverError::Slate(_) => {
+                PipelineErrorInfo::new(INVALID_INPUT_FAILOVER, ErrorType::UserError)
+            }
+        }
+    }
+}
+
+const PLAYLIST_UPDATE_ERROR: &str = "PLAYLIST_UPDATE_ERROR";
+const UNSUPPORTED_REPLAY_BUFFER_INPUT: &str = "UNSUPPORTED_REPLAY_BUFFER_INPUT";
+
+impl From<&PlaylistUpdateError> for PipelineErrorInfo {
+    fn from(err: &PlaylistUpdateError) -> Self {
+        match err {
+            PlaylistUpdateError::NotFound(_) => {
+                PipelineErrorInfo::new(INPUT_STREAM_NOT_FOUND, ErrorType::EntityNotFound)
+            }
+            PlaylistUpdateError::NotAPlaylist(_)
+            | PlaylistUpdateError::DuplicateItem(_)
+            | PlaylistUpdateError::ItemNotQueued(_) => {
+                PipelineErrorInfo::new(PLAYLIST_UPDATE_ERROR, ErrorType::UserError)
+            }
+        }
+    }
+}
+
+impl From<&ReplayBufferError> for PipelineErrorInfo {
+    fn from(err: &ReplayBufferError) -> Self {
+        match err {
+            ReplayBufferError::NotFound(_) => {
+                PipelineErrorInfo::new(INPUT_STREAM_NOT_FOUND, ErrorType::EntityNotFound)
+            }
+            ReplayBufferError::UnsupportedInput(_) => {
+                PipelineErrorInfo::new(UNSUPPORTED_REPLAY_BUFFER_INPUT, ErrorType::UserError)
+            }
+        }
+    }
+}
+
+impl From<&AudioDelayError> for PipelineErrorInfo {
+    fn from(err: &AudioDelayError) -> Self {
+        match err {
+            AudioDelayError::NotFound(_) => {
+                PipelineErrorInfo::new(INPUT_STREAM_NOT_FOUND, ErrorType::EntityNotFound)
+            }
+        }
+    }
+}
+
 const OUTPUT_STREAM_STILL_IN_USE: &str = "OUTPUT_STREAM_STILL_IN_USE";
 const OUTPUT_STREAM_NOT_FOUND: &str = "OUTPUT_STREAM_NOT_FOUND";
 const NO_AUDIO_AND_VIDEO_SPECIFIED: &str = "NO_AUDIO_AND_VIDEO_SPECIFIED";
@@ -336,6 +470,17 @@ impl From<&UpdateSceneError> for PipelineErrorInfo {
     }
 }
 
+impl From<&UpdateFragmentError> for PipelineErrorInfo {
+    fn from(err: &UpdateFragmentError) -> Self {
+        match err {
+            UpdateFragmentError::FragmentRegistry(_) => {
+                PipelineErrorInfo::new(ENTITY_NOT_FOUND, ErrorType::EntityNotFound)
+            }
+            UpdateFragmentError::UpdateScene(err) => err.into(),
+        }
+    }
+}
+
 const REQUEST_KEYFRAME_ERROR: &str = "REQUEST_KEYFRAME_ERROR";
 
 impl From<&RequestKeyframeError> for PipelineErrorInfo {
@@ -347,6 +492,28 @@ impl From<&RequestKeyframeError> for PipelineErrorInfo {
     }
 }
 
+const SNAPSHOT_NOT_AVAILABLE: &str = "SNAPSHOT_NOT_AVAILABLE";
+const SNAPSHOT_DOWNLOAD_FAILED: &str = "SNAPSHOT_DOWNLOAD_FAILED";
+
+impl From<&SnapshotError> for PipelineErrorInfo {
+    fn from(err: &SnapshotError) -> Self {
+        match err {
+            SnapshotError::OutputNotFound(_) => {
+                PipelineErrorInfo::new(OUTPUT_STREAM_NOT_FOUND, ErrorType::EntityNotFound)
+            }
+            SnapshotError::InputNotFound(_) => {
+                PipelineErrorInfo::new(INPUT_STREAM_NOT_FOUND, ErrorType::EntityNotFound)
+            }
+            SnapshotError::NoFrame => {
+                PipelineErrorInfo::new(SNAPSHOT_NOT_AVAILABLE, ErrorType::EntityNotFound)
+            }
+            SnapshotError::DownloadFailed(_) | SnapshotError::DownloadCanceled => {
+                PipelineErrorInfo::new(SNAPSHOT_DOWNLOAD_FAILED, ErrorType::ServerError)
+            }
+        }
+    }
+}
+
 const WGPU_INIT_ERROR: &str = "WGPU_INIT_ERROR";
 const LAYOUT_INIT_ERROR: &str = "LAYOUT_INIT_ERROR";
 
@@ -367,6 +534,7 @@ const ENTITY_ALREADY_REGISTERED: &str = "ENTITY_ALREADY_REGISTERED";
 const INVALID_SHADER: &str = "INVALID_SHADER";
 const REGISTER_IMAGE_ERROR: &str = "REGISTER_IMAGE_ERROR";
 const REGISTER_WEB_RENDERER_ERROR: &str = "REGISTER_WEB_RENDERER_ERROR";
+const REGISTER_VIDEO_CLIP_ERROR: &str = "REGISTER_VIDEO_CLIP_ERROR";
 
 impl From<&RegisterRendererError> for PipelineErrorInfo {
     fn from(err: &RegisterRendererError) -> Self {
@@ -385,6 +553,20 @@ impl From<&RegisterRendererError> for PipelineErrorInfo {
             RegisterRendererError::Web(_, _) => {
                 PipelineErrorInfo::new(REGISTER_WEB_RENDERER_ERROR, ErrorType::ServerError)
             }
+            RegisterRendererError::VideoClip(_, _) => {
+                PipelineErrorInfo::new(REGISTER_VIDEO_CLIP_ERROR, ErrorType::UserError)
+            }
+        }
+    }
+}
+
+impl From<&RegisterVideoClipError> for PipelineErrorInfo {
+    fn from(err: &RegisterVideoClipError) -> Self {
+        match err {
+            RegisterVideoClipError::Decode(_, _) => {
+                PipelineErrorInfo::new(REGISTER_VIDEO_CLIP_ERROR, ErrorType::UserError)
+            }
+            RegisterVideoClipError::Renderer(err) => err.into(),
         }
     }
 }
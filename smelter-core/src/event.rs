@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use crossbeam_channel::Receiver;
 use smelter_render::{
@@ -6,6 +6,8 @@ use smelter_render::{
     event_handler::{self, Emitter, emit_event},
 };
 
+use crate::stats::AudioLevels;
+
 #[derive(Debug, Clone)]
 pub enum Event {
     AudioInputStreamDelivered(InputId),
@@ -14,9 +16,52 @@ pub enum Event {
     VideoInputStreamPlaying(InputId),
     AudioInputStreamEos(InputId),
     VideoInputStreamEos(InputId),
+    /// Source used in place of an input with failover configured has changed.
+    AudioInputFailover(InputId, FailoverSource),
+    VideoInputFailover(InputId, FailoverSource),
+    /// Playlist input started playing an item.
+    PlaylistItemStart(InputId, Arc<str>),
+    /// Playlist input finished playing an item.
+    PlaylistItemEnd(InputId, Arc<str>),
+    /// Playlist item could not be played. It is followed by `PlaylistItemEnd`.
+    PlaylistItemError(InputId, Arc<str>, String),
+    /// Alarm condition on the input lasted longer than the configured threshold.
+    InputMediaAlarm(InputId, MediaAlarm),
+    /// Alarm condition on the input is no longer present.
+    InputMediaAlarmEnd(InputId, MediaAlarm),
+    /// Alarm condition on the output lasted longer than the configured threshold.
+    OutputMediaAlarm(OutputId, MediaAlarm),
+    /// Alarm condition on the output is no longer present.
+    OutputMediaAlarmEnd(OutputId, MediaAlarm),
+    /// Levels of the input audio, emitted periodically.
+    AudioInputLevels(InputId, AudioLevels),
+    /// Levels of the output audio mix, emitted periodically.
+    AudioOutputLevels(OutputId, AudioLevels),
     OutputDone(OutputId),
 }
 
+/// Source of the data that is delivered in place of an input with failover configured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailoverSource {
+    /// Input itself is delivering data.
+    Primary,
+    /// Input stalled, data from the specified backup input is used instead.
+    Backup(InputId),
+    /// Input and all its backups stalled. Slate image is rendered if configured.
+    Missing,
+}
+
+/// Condition of input media used for monitoring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaAlarm {
+    /// Audio peak level is below -60 dBFS.
+    Silence,
+    /// Average luma of video frames is close to black.
+    Black,
+    /// Consecutive video frames are (almost) identical.
+    Frozen,
+}
+
 fn input_event(kind: &str, input_id: InputId) -> event_handler::Event {
     event_handler::Event {
         kind: kind.to_string(),
@@ -24,6 +69,52 @@ fn input_event(kind: &str, input_id: InputId) -> event_handler::Event {
     }
 }
 
+fn failover_event(kind: &str, input_id: InputId, source: FailoverSource) -> event_handler::Event {
+    let mut properties = vec![("input_id".to_string(), input_id.to_string())];
+    match source {
+        FailoverSource::Primary => properties.push(("source".to_string(), "primary".to_string())),
+        FailoverSource::Backup(backup_id) => {
+            properties.push(("source".to_string(), "backup".to_string()));
+            properties.push(("backup_input_id".to_string(), backup_id.to_string()));
+        }
+        FailoverSource::Missing => properties.push(("source".to_string(), "missing".to_string())),
+    }
+    event_handler::Event {
+        kind: kind.to_string(),
+        properties,
+    }
+}
+
+fn playlist_item_event(kind: &str, input_id: InputId, item_id: Arc<str>) -> event_handler::Event {
+    event_handler::Event {
+        kind: kind.to_string(),
+        properties: vec![
+            ("input_id".to_string(), input_id.to_string()),
+            ("item_id".to_string(), item_id.to_string()),
+        ],
+    }
+}
+
+fn audio_levels_event(
+    kind: &str,
+    id_key: &str,
+    id: String,
+    levels: AudioLevels,
+) -> event_handler::Event {
+    event_handler::Event {
+        kind: kind.to_string(),
+        properties: vec![
+            (id_key.to_string(), id),
+            ("peak_dbfs".to_string(), levels.peak_dbfs.to_string()),
+            ("rms_dbfs".to_string(), levels.rms_dbfs.to_string()),
+            (
+                "short_term_lufs".to_string(),
+                levels.short_term_lufs.to_string(),
+            ),
+        ],
+    }
+}
+
 fn output_event(kind: &str, output_id: OutputId) -> event_handler::Event {
     event_handler::Event {
         kind: kind.to_string(),
@@ -40,6 +131,49 @@ impl From<Event> for event_handler::Event {
             Event::VideoInputStreamPlaying(id) => input_event("VIDEO_INPUT_PLAYING", id),
             Event::AudioInputStreamEos(id) => input_event("AUDIO_INPUT_EOS", id),
             Event::VideoInputStreamEos(id) => input_event("VIDEO_INPUT_EOS", id),
+            Event::AudioInputFailover(id, source) => {
+                failover_event("AUDIO_INPUT_FAILOVER", id, source)
+            }
+            Event::VideoInputFailover(id, source) => {
+                failover_event("VIDEO_INPUT_FAILOVER", id, source)
+            }
+            Event::PlaylistItemStart(id, item_id) => {
+                playlist_item_event("PLAYLIST_ITEM_START", id, item_id)
+            }
+            Event::PlaylistItemEnd(id, item_id) => {
+                playlist_item_event("PLAYLIST_ITEM_END", id, item_id)
+            }
+            Event::PlaylistItemError(id, item_id, error) => {
+                let mut event = playlist_item_event("PLAYLIST_ITEM_ERROR", id, item_id);
+                event.properties.push(("error".to_string(), error));
+                event
+            }
+            Event::InputMediaAlarm(id, alarm) => match alarm {
+                MediaAlarm::Silence => input_event("INPUT_SILENCE", id),
+                MediaAlarm::Black => input_event("INPUT_BLACK", id),
+                MediaAlarm::Frozen => input_event("INPUT_FROZEN", id),
+            },
+            Event::InputMediaAlarmEnd(id, alarm) => match alarm {
+                MediaAlarm::Silence => input_event("INPUT_SILENCE_END", id),
+                MediaAlarm::Black => input_event("INPUT_BLACK_END", id),
+                MediaAlarm::Frozen => input_event("INPUT_FROZEN_END", id),
+            },
+            Event::OutputMediaAlarm(id, alarm) => match alarm {
+                MediaAlarm::Silence => output_event("OUTPUT_SILENCE", id),
+                MediaAlarm::Black => output_event("OUTPUT_BLACK", id),
+                MediaAlarm::Frozen => output_event("OUTPUT_FROZEN", id),
+            },
+            Event::OutputMediaAlarmEnd(id, alarm) => match alarm {
+                MediaAlarm::Silence => output_event("OUTPUT_SILENCE_END", id),
+                MediaAlarm::Black => output_event("OUTPUT_BLACK_END", id),
+                MediaAlarm::Frozen => output_event("OUTPUT_FROZEN_END", id),
+            },
+            Event::AudioInputLevels(id, levels) => {
+                audio_levels_event("AUDIO_INPUT_LEVELS", "input_id", id.to_string(), levels)
+            }
+            Event::AudioOutputLevels(id, levels) => {
+                audio_levels_event("AUDIO_OUTPUT_LEVELS", "output_id", id.to_string(), levels)
+            }
             Event::OutputDone(id) => output_event("OUTPUT_DONE", id),
         }
     }
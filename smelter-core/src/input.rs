@@ -6,6 +6,7 @@ use crate::prelude::*;
 pub struct RegisterInputOptions {
     pub input_options: ProtocolInputOptions,
     pub queue_options: QueueInputOptions,
+    pub failover: InputFailoverOptions,
 }
 
 #[derive(Debug, Clone)]
@@ -13,6 +14,8 @@ pub enum ProtocolInputOptions {
     Rtp(RtpInputOptions),
     RtmpServer(RtmpServerInputOptions),
     Mp4(Mp4InputOptions),
+    Playlist(PlaylistInputOptions),
+    Replay(ReplayInputOptions),
     Hls(HlsInputOptions),
     Whip(WhipInputOptions),
     Whep(WhepInputOptions),
@@ -28,6 +31,61 @@ pub struct QueueInputOptions {
     /// Relative offset this input stream should have to the clock that
     /// starts when pipeline is started.
     pub offset: Option<Duration>,
+    /// Shift applied only to audio of this input, used to fix audio/video synchronization.
+    pub audio_delay: AudioDelay,
+}
+
+/// Shift of audio timestamps of an input relative to its video.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AudioDelay {
+    #[default]
+    None,
+    /// Audio is played later than it was received.
+    Delay(Duration),
+    /// Audio is played earlier than it was received.
+    Advance(Duration),
+}
+
+impl AudioDelay {
+    /// Returns `None` if audio with this PTS would have to be played before PTS 0.
+    pub(crate) fn apply(&self, pts: Duration) -> Option<Duration> {
+        match self {
+            Self::None => Some(pts),
+            Self::Delay(delay) => Some(pts + *delay),
+            Self::Advance(advance) => pts.checked_sub(*advance),
+        }
+    }
+
+    /// Returns how much earlier audio is played after switching to `new`, `None`
+    /// if it is not played earlier.
+    pub(crate) fn decrease_to(&self, new: &AudioDelay) -> Option<Duration> {
+        let decrease = self.offset_nanos() - new.offset_nanos();
+        (decrease > 0).then(|| Duration::from_nanos(decrease as u64))
+    }
+
+    fn offset_nanos(&self) -> i128 {
+        match self {
+            Self::None => 0,
+            Self::Delay(delay) => delay.as_nanos() as i128,
+            Self::Advance(advance) => -(advance.as_nanos() as i128),
+        }
+    }
+}
+
+/// Sources used in place of an input when it stops delivering data for longer
+/// than `PipelineOptions::stream_fallback_timeout`.
+#[derive(Debug, Clone, Default)]
+pub struct InputFailoverOptions {
+    /// Inputs used in order of precedence. Audio and video are switched independently.
+    pub backup_inputs: Vec<InputId>,
+    /// Image rendered when neither the input nor any of its backups deliver frames.
+    pub slate_image_id: Option<smelter_render::RendererId>,
+}
+
+impl InputFailoverOptions {
+    pub fn is_enabled(&self) -> bool {
+        !self.backup_inputs.is_empty() || self.slate_image_id.is_some()
+    }
 }
 
 pub enum InputInitInfo {
@@ -54,6 +112,8 @@ pub enum InputProtocolKind {
     Rtp,
     Rtmp,
     Mp4,
+    Playlist,
+    Replay,
     Hls,
     Whip,
     Whep,
@@ -76,3 +136,30 @@ pub enum InputBufferOptions {
     /// It will never decrease even network conditions improve.
     Adaptive,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_delay_apply() {
+        let pts = Duration::from_millis(100);
+        assert_eq!(AudioDelay::None.apply(pts), Some(pts));
+        assert_eq!(
+            AudioDelay::Delay(Duration::from_millis(50)).apply(pts),
+            Some(Duration::from_millis(150))
+        );
+        assert_eq!(
+            AudioDelay::Advance(Duration::from_millis(40)).apply(pts),
+            Some(Duration::from_millis(60))
+        );
+        assert_eq!(
+            AudioDelay::Advance(Duration::from_millis(100)).apply(pts),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            AudioDelay::Advance(Duration::from_millis(101)).apply(pts),
+            None
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use smelter_render::scene::Component;
+use smelter_render::{Framerate, scene::Component};
 
 use crate::prelude::*;
 
@@ -37,16 +37,29 @@ pub enum ProtocolOutputOptions {
 pub struct RegisterOutputVideoOptions {
     pub initial: Component,
     pub end_condition: PipelineOutputEndCondition,
+    /// Defaults to `PipelineOptions::output_framerate`.
+    pub framerate: Option<Framerate>,
 }
 
 #[derive(Debug, Clone)]
 pub struct RegisterOutputAudioOptions {
     pub initial: AudioMixerConfig,
     pub mixing_strategy: AudioMixingStrategy,
+    /// Applied to mixed samples after the mixing strategy.
+    pub loudness_normalization: Option<LoudnessNormalizationOptions>,
     pub channels: AudioChannels,
     pub end_condition: PipelineOutputEndCondition,
 }
 
+/// New scene and audio configuration of a single output passed to
+/// `Pipeline::update_outputs`.
+#[derive(Debug, Clone)]
+pub struct OutputUpdate {
+    pub output_id: OutputId,
+    pub video: Option<Component>,
+    pub audio: Option<AudioMixerConfig>,
+}
+
 #[derive(Debug, Clone)]
 pub struct AudioMixerConfig {
     pub inputs: Vec<AudioMixerInputConfig>,
@@ -65,6 +78,14 @@ pub enum AudioMixingStrategy {
     SumScale,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct LoudnessNormalizationOptions {
+    /// Target loudness (LUFS) measured as defined in EBU R128.
+    pub target_lufs: f64,
+    /// Maximum true peak (dBTP) of the normalized audio.
+    pub true_peak_limit_dbtp: f64,
+}
+
 #[derive(Debug, Clone)]
 pub enum PipelineOutputEndCondition {
     AnyOf(Vec<InputId>),
@@ -1,6 +1,7 @@
 use std::{
+    collections::HashMap,
     path::Path,
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
@@ -9,8 +10,11 @@ use tokio::runtime::Runtime;
 use smelter_render::{Framerate, RenderingMode, WgpuFeatures, web_renderer::ChromiumContext};
 
 use crate::{
-    event::EventEmitter, graphics_context::GraphicsContext,
-    pipeline::webrtc::WhipWhepPipelineState, stats::StatsSender,
+    event::EventEmitter,
+    graphics_context::GraphicsContext,
+    pipeline::webrtc::WhipWhepPipelineState,
+    queue::{ReplayBuffer, VideoMonitorThread},
+    stats::StatsSender,
 };
 
 use crate::prelude::*;
@@ -37,9 +41,12 @@ mod webrtc;
 mod input;
 mod instance;
 mod output;
+mod replay;
 mod utils;
 
+pub use input::PreparedInput;
 pub use instance::Pipeline;
+pub use output::PreparedOutput;
 
 #[cfg(target_os = "linux")]
 pub use v4l2::{V4l2DeviceInfo, V4l2FormatInfo, V4l2ResolutionInfo, list_v4l2_devices};
@@ -48,6 +55,8 @@ pub use v4l2::{V4l2DeviceInfo, V4l2FormatInfo, V4l2ResolutionInfo, list_v4l2_dev
 pub struct PipelineOptions {
     pub stream_fallback_timeout: Duration,
     pub default_buffer_duration: Duration,
+    /// Silence, black and frozen video on inputs are reported if they last longer than this value.
+    pub media_alarm_threshold: Duration,
 
     pub load_system_fonts: bool,
     pub run_late_scheduled_events: bool,
@@ -88,6 +97,7 @@ pub enum PipelineWhipWhepServerOptions {
 }
 
 pub const DEFAULT_BUFFER_DURATION: Duration = Duration::from_millis(16 * 5); // about 5 frames at 60 fps
+pub const DEFAULT_MEDIA_ALARM_THRESHOLD: Duration = Duration::from_secs(5);
 
 #[derive(Clone)]
 pub(crate) struct PipelineCtx {
@@ -102,6 +112,12 @@ pub(crate) struct PipelineCtx {
     pub graphics_context: GraphicsContext,
     pub event_emitter: Arc<EventEmitter>,
     pub stats_sender: StatsSender,
+    /// Silence, black and frozen video are reported if they last longer than this value.
+    pub media_alarm_threshold: Duration,
+    /// Shared by black and frozen video detection of all inputs and outputs.
+    pub video_monitor_thread: VideoMonitorThread,
+    /// Replay buffers of inputs, used as a source by replay inputs.
+    pub replay_buffers: Arc<Mutex<HashMap<InputId, ReplayBuffer>>>,
     tokio_rt: Arc<Runtime>,
     whip_whep_state: Option<Arc<WhipWhepPipelineState>>,
 }
@@ -9,7 +9,7 @@ use decklink::{
     InputCallbackResult, PixelFormat, VideoInputFlags, VideoInputFormatChangedEvents,
     VideoInputFrame,
 };
-use smelter_render::{Frame, FrameData, Resolution, error::ErrorStack};
+use smelter_render::{ColorInfo, Frame, FrameData, Resolution, error::ErrorStack};
 use tracing::{Span, debug, info, trace, warn};
 
 use crate::pipeline::resampler::dynamic_resampler::{DynamicResampler, DynamicResamplerBatch};
@@ -147,10 +147,13 @@ impl ChannelCallbackAdapter {
         } else {
             data
         };
+        let resolution = Resolution { width, height };
         Frame {
             data: FrameData::InterleavedUyvy422(data),
-            resolution: Resolution { width, height },
+            resolution,
             pts,
+            // SD video modes use BT.601, HD and above use BT.709
+            color: ColorInfo::from_resolution(resolution),
         }
     }
 
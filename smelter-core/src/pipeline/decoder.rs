@@ -4,7 +4,7 @@ use std::time::Duration;
 use crossbeam_channel::Sender;
 use smelter_render::Frame;
 
-use crate::prelude::*;
+use crate::{prelude::*, queue::ReplayAudioDecoder};
 
 pub(super) mod decoder_thread_audio;
 pub(super) mod decoder_thread_video;
@@ -77,6 +77,8 @@ pub(crate) trait AudioDecoder: Sized {
     type Options: Send + 'static;
 
     fn new(ctx: &Arc<PipelineCtx>, options: Self::Options) -> Result<Self, DecoderInitError>;
+    /// Decoder recorded in the replay buffer together with the chunks.
+    fn replay_decoder(options: &Self::Options) -> ReplayAudioDecoder;
     fn decode(&mut self, chunk: EncodedInputChunk) -> Result<Vec<DecodedSamples>, DecodingError>;
     fn flush(&mut self) -> Vec<DecodedSamples>;
 }
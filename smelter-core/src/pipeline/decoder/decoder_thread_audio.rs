@@ -6,6 +6,7 @@ use tracing::warn;
 use crate::{
     pipeline::{
         decoder::{AudioDecoderStream, DecoderThreadHandle, EncodedInputEvent},
+        replay::ReplayRecorder,
         resampler::decoder_resampler::ResampledDecoderStream,
     },
     thread_utils::{InitializableThread, ThreadMetadata},
@@ -21,6 +22,8 @@ pub(crate) struct AudioDecoderThreadOptions<Decoder: AudioDecoder> {
     pub samples_sender: Sender<PipelineEvent<InputAudioSamples>>,
     pub input_buffer_size: usize,
     pub force_resampling: bool,
+    /// Records chunks in the replay buffer of the input.
+    pub replay_recorder: Option<ReplayRecorder>,
 }
 
 pub(crate) struct AudioDecoderThread<Decoder: AudioDecoder> {
@@ -45,6 +48,7 @@ where
             samples_sender,
             input_buffer_size: buffer_size,
             force_resampling,
+            replay_recorder,
         } = options;
 
         let (chunk_sender, chunk_receiver) = crossbeam_channel::bounded(buffer_size);
@@ -54,6 +58,12 @@ where
             PipelineEvent::Data(chunk) => PipelineEvent::Data(EncodedInputEvent::Chunk(chunk)),
             PipelineEvent::EOS => PipelineEvent::EOS,
         });
+        let replay_decoder = Decoder::replay_decoder(&decoder_options);
+        let chunk_stream = chunk_stream.inspect(move |event| {
+            if let Some(replay_recorder) = &replay_recorder {
+                replay_recorder.record_audio(event, &replay_decoder);
+            }
+        });
 
         let decoded_stream =
             AudioDecoderStream::<Decoder, _>::new(ctx, decoder_options, chunk_stream)?;
@@ -11,6 +11,7 @@ use crate::{
         BytestreamTransformStream, BytestreamTransformer, DecoderThreadHandle, EncodedInputEvent,
         VideoDecoderStream,
     },
+    pipeline::replay::ReplayRecorder,
     thread_utils::{InitializableThread, ThreadMetadata},
 };
 
@@ -21,6 +22,8 @@ pub(crate) struct VideoDecoderThreadOptions<Transformer: BytestreamTransformer>
     pub transformer: Option<Transformer>,
     pub frame_sender: Sender<PipelineEvent<Frame>>,
     pub input_buffer_size: usize,
+    /// Records transformed chunks in the replay buffer of the input.
+    pub replay_recorder: Option<ReplayRecorder>,
 }
 
 pub(crate) struct VideoDecoderThread<Decoder: VideoDecoder, Transformer: BytestreamTransformer> {
@@ -46,6 +49,7 @@ where
             transformer,
             frame_sender,
             input_buffer_size: buffer_size,
+            replay_recorder,
         } = options;
         let (chunk_sender, chunk_receiver) = crossbeam_channel::bounded(buffer_size);
 
@@ -58,6 +62,11 @@ where
                     PipelineEvent::EOS => PipelineEvent::EOS,
                 }
             });
+        let transformed_bytestream = transformed_bytestream.inspect(move |event| {
+            if let Some(replay_recorder) = &replay_recorder {
+                replay_recorder.record_video(event);
+            }
+        });
 
         let decoder_stream = VideoDecoderStream::<Decoder, _>::new(ctx, transformed_bytestream)?;
 
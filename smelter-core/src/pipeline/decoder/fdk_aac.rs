@@ -4,6 +4,7 @@ use tracing::{error, info};
 
 use crate::pipeline::decoder::AudioDecoder;
 use crate::prelude::*;
+use crate::queue::ReplayAudioDecoder;
 
 use super::DecodedSamples;
 
@@ -25,6 +26,10 @@ impl AudioDecoder for FdkAacDecoder {
         })
     }
 
+    fn replay_decoder(options: &Self::Options) -> ReplayAudioDecoder {
+        ReplayAudioDecoder::Aac(options.clone())
+    }
+
     fn decode(&mut self, chunk: EncodedInputChunk) -> Result<Vec<DecodedSamples>, DecodingError> {
         match &mut self.decoder {
             Some(decoder) => Ok(decoder.decode(chunk)?),
@@ -1,7 +1,13 @@
 use std::time::Duration;
 
-use ffmpeg_next::format::Pixel;
-use smelter_render::{Frame, FrameData, Resolution, YuvPlanes};
+use ffmpeg_next::{
+    color::{self, TransferCharacteristic},
+    format::Pixel,
+};
+use smelter_render::{
+    ColorInfo, ColorMatrix, ColorPrimaries, ColorRange, ColorTransfer, Frame, FrameData, NvPlanes,
+    Resolution, YuvPlanes,
+};
 use tracing::error;
 
 use crate::prelude::*;
@@ -33,46 +39,94 @@ pub(super) fn from_av_frame(
 
     let data = match decoded.format() {
         Pixel::YUV420P => FrameData::PlanarYuv420(YuvPlanes {
-            y_plane: copy_plane_from_av(decoded, 0),
-            u_plane: copy_plane_from_av(decoded, 1),
-            v_plane: copy_plane_from_av(decoded, 2),
+            y_plane: copy_plane_from_av(decoded, 0, 1),
+            u_plane: copy_plane_from_av(decoded, 1, 1),
+            v_plane: copy_plane_from_av(decoded, 2, 1),
         }),
         Pixel::YUV422P => FrameData::PlanarYuv422(YuvPlanes {
-            y_plane: copy_plane_from_av(decoded, 0),
-            u_plane: copy_plane_from_av(decoded, 1),
-            v_plane: copy_plane_from_av(decoded, 2),
+            y_plane: copy_plane_from_av(decoded, 0, 1),
+            u_plane: copy_plane_from_av(decoded, 1, 1),
+            v_plane: copy_plane_from_av(decoded, 2, 1),
         }),
         Pixel::YUV444P => FrameData::PlanarYuv444(YuvPlanes {
-            y_plane: copy_plane_from_av(decoded, 0),
-            u_plane: copy_plane_from_av(decoded, 1),
-            v_plane: copy_plane_from_av(decoded, 2),
+            y_plane: copy_plane_from_av(decoded, 0, 1),
+            u_plane: copy_plane_from_av(decoded, 1, 1),
+            v_plane: copy_plane_from_av(decoded, 2, 1),
         }),
         Pixel::YUVJ420P => FrameData::PlanarYuvJ420(YuvPlanes {
-            y_plane: copy_plane_from_av(decoded, 0),
-            u_plane: copy_plane_from_av(decoded, 1),
-            v_plane: copy_plane_from_av(decoded, 2),
+            y_plane: copy_plane_from_av(decoded, 0, 1),
+            u_plane: copy_plane_from_av(decoded, 1, 1),
+            v_plane: copy_plane_from_av(decoded, 2, 1),
+        }),
+        Pixel::YUV420P10LE => FrameData::PlanarYuv420P10(YuvPlanes {
+            y_plane: copy_plane_from_av(decoded, 0, 2),
+            u_plane: copy_plane_from_av(decoded, 1, 2),
+            v_plane: copy_plane_from_av(decoded, 2, 2),
+        }),
+        Pixel::P010LE => FrameData::P010(NvPlanes {
+            y_plane: copy_plane_from_av(decoded, 0, 2),
+            // interleaved U and V samples
+            uv_planes: copy_plane_from_av(decoded, 1, 4),
         }),
         fmt => return Err(DecoderFrameConversionError::UnsupportedPixelFormat(fmt)),
     };
+    let resolution = Resolution {
+        width: decoded.width().try_into().unwrap(),
+        height: decoded.height().try_into().unwrap(),
+    };
     Ok(Frame {
         data,
-        resolution: Resolution {
-            width: decoded.width().try_into().unwrap(),
-            height: decoded.height().try_into().unwrap(),
-        },
+        resolution,
         pts,
+        color: color_info_from_av(decoded, resolution),
     })
 }
 
-fn copy_plane_from_av(decoded: &ffmpeg_next::frame::Video, plane: usize) -> bytes::Bytes {
-    let mut output_buffer = bytes::BytesMut::with_capacity(
-        decoded.plane_width(plane) as usize * decoded.plane_height(plane) as usize,
-    );
+/// Unspecified values fall back to defaults based on the resolution (BT.601 for SD content).
+fn color_info_from_av(decoded: &ffmpeg_next::frame::Video, resolution: Resolution) -> ColorInfo {
+    let fallback = ColorInfo::from_resolution(resolution);
+    let matrix = match decoded.color_space() {
+        color::Space::BT470BG | color::Space::SMPTE170M => ColorMatrix::Bt601,
+        color::Space::BT709 => ColorMatrix::Bt709,
+        color::Space::BT2020NCL | color::Space::BT2020CL => ColorMatrix::Bt2020,
+        _ => fallback.matrix,
+    };
+    let primaries = match decoded.color_primaries() {
+        color::Primaries::BT470BG | color::Primaries::SMPTE170M => ColorPrimaries::Bt601,
+        color::Primaries::BT709 => ColorPrimaries::Bt709,
+        color::Primaries::BT2020 => ColorPrimaries::Bt2020,
+        _ => fallback.primaries,
+    };
+    let range = match decoded.color_range() {
+        color::Range::JPEG => ColorRange::Full,
+        _ => ColorRange::Limited,
+    };
+    let transfer = match decoded.color_transfer_characteristic() {
+        TransferCharacteristic::SMPTE2084 => ColorTransfer::Pq,
+        TransferCharacteristic::ARIB_STD_B67 => ColorTransfer::Hlg,
+        _ => ColorTransfer::Sdr,
+    };
+    ColorInfo {
+        matrix,
+        primaries,
+        range,
+        transfer,
+    }
+}
+
+fn copy_plane_from_av(
+    decoded: &ffmpeg_next::frame::Video,
+    plane: usize,
+    bytes_per_pixel: usize,
+) -> bytes::Bytes {
+    let row_size = decoded.plane_width(plane) as usize * bytes_per_pixel;
+    let mut output_buffer =
+        bytes::BytesMut::with_capacity(row_size * decoded.plane_height(plane) as usize);
 
     decoded
         .data(plane)
         .chunks(decoded.stride(plane))
-        .map(|chunk| &chunk[..decoded.plane_width(plane) as usize])
+        .map(|chunk| &chunk[..row_size])
         .for_each(|chunk| output_buffer.extend_from_slice(chunk));
 
     output_buffer.freeze()
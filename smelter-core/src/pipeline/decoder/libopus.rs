@@ -3,6 +3,7 @@ use tracing::{debug, info, trace};
 
 use crate::pipeline::decoder::AudioDecoder;
 use crate::prelude::*;
+use crate::queue::ReplayAudioDecoder;
 
 use super::DecodedSamples;
 
@@ -42,6 +43,10 @@ impl AudioDecoder for OpusDecoder {
         })
     }
 
+    fn replay_decoder(_options: &Self::Options) -> ReplayAudioDecoder {
+        ReplayAudioDecoder::Opus
+    }
+
     fn decode(
         &mut self,
         encoded_chunk: EncodedInputChunk,
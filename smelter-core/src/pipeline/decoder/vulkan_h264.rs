@@ -1,6 +1,8 @@
 use std::{sync::Arc, time::Duration};
 
-use smelter_render::{Frame, FrameData, Resolution};
+use smelter_render::{
+    ColorInfo, ColorMatrix, ColorPrimaries, ColorRange, ColorTransfer, Frame, FrameData, Resolution,
+};
 use tracing::{debug, info, trace, warn};
 use vk_video::{
     DecoderError, ReferenceManagementError, WgpuTexturesDecoder,
@@ -90,7 +92,11 @@ impl VideoDecoderInstance for VulkanH264Decoder {
 }
 
 fn from_vk_frame(frame: vk_video::Frame<wgpu::Texture>) -> Frame {
-    let vk_video::Frame { data, pts } = frame;
+    let vk_video::Frame {
+        data,
+        pts,
+        color_description,
+    } = frame;
     let resolution = Resolution {
         width: data.width() as usize,
         height: data.height() as usize,
@@ -100,5 +106,44 @@ fn from_vk_frame(frame: vk_video::Frame<wgpu::Texture>) -> Frame {
         data: FrameData::Nv12WgpuTexture(data.into()),
         pts: Duration::from_micros(pts.unwrap()),
         resolution,
+        color: color_info(color_description, resolution),
+    }
+}
+
+/// Maps ITU-T H.273 code points, unspecified values fall back to defaults for the resolution.
+fn color_info(
+    color_description: Option<vk_video::ColorDescription>,
+    resolution: Resolution,
+) -> ColorInfo {
+    let fallback = ColorInfo::from_resolution(resolution);
+    let Some(description) = color_description else {
+        return fallback;
+    };
+    let matrix = match description.matrix_coefficients {
+        1 => ColorMatrix::Bt709,
+        5 | 6 => ColorMatrix::Bt601,
+        9 | 10 => ColorMatrix::Bt2020,
+        _ => fallback.matrix,
+    };
+    let primaries = match description.colour_primaries {
+        1 => ColorPrimaries::Bt709,
+        5 | 6 => ColorPrimaries::Bt601,
+        9 => ColorPrimaries::Bt2020,
+        _ => fallback.primaries,
+    };
+    let range = match description.full_range {
+        true => ColorRange::Full,
+        false => ColorRange::Limited,
+    };
+    let transfer = match description.transfer_characteristics {
+        16 => ColorTransfer::Pq,
+        18 => ColorTransfer::Hlg,
+        _ => ColorTransfer::Sdr,
+    };
+    ColorInfo {
+        matrix,
+        primaries,
+        range,
+        transfer,
     }
 }
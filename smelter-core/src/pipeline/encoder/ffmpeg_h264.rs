@@ -2,11 +2,12 @@ use std::{iter, sync::Arc};
 
 use ffmpeg_next::codec::Id;
 use ffmpeg_next::{Rational, codec::Context};
-use smelter_render::{Frame, OutputFrameFormat};
+use smelter_render::Frame;
 use tracing::{error, info, trace, warn};
 
 use crate::pipeline::encoder::ffmpeg_utils::{
-    create_av_frame, encoded_chunk_from_av_packet, into_ffmpeg_pixel_format, read_extradata,
+    create_av_frame, encoded_chunk_from_av_packet, into_ffmpeg_pixel_format,
+    into_output_frame_format, read_extradata, set_encoder_color_space,
 };
 use crate::pipeline::encoder::utils::bitrate_from_resolution_framerate;
 use crate::pipeline::ffmpeg_utils::FfmpegOptions;
@@ -43,14 +44,7 @@ impl VideoEncoder for FfmpegH264Encoder {
         encoder.set_width(options.resolution.width as u32);
         encoder.set_height(options.resolution.height as u32);
         encoder.set_frame_rate(Some((framerate.num as i32, framerate.den as i32)));
-        encoder.set_colorspace(ffmpeg_next::color::Space::BT709);
-        encoder.set_color_range(ffmpeg_next::color::Range::MPEG);
-        unsafe {
-            let encoder = encoder.as_mut_ptr();
-            use ffmpeg_next::ffi;
-            (*encoder).color_primaries = ffi::AVColorPrimaries::AVCOL_PRI_BT709;
-            (*encoder).color_trc = ffi::AVColorTransferCharacteristic::AVCOL_TRC_BT709;
-        }
+        set_encoder_color_space(&mut encoder, options.color_space);
 
         let ffmpeg_options = initialize_ffmpeg_h264_options(ctx, &options, codec_name);
 
@@ -64,11 +58,7 @@ impl VideoEncoder for FfmpegH264Encoder {
             },
             VideoEncoderConfig {
                 resolution: options.resolution,
-                output_format: match options.pixel_format {
-                    OutputPixelFormat::YUV420P => OutputFrameFormat::PlanarYuv420Bytes,
-                    OutputPixelFormat::YUV422P => OutputFrameFormat::PlanarYuv422Bytes,
-                    OutputPixelFormat::YUV444P => OutputFrameFormat::PlanarYuv444Bytes,
-                },
+                output_format: into_output_frame_format(options.pixel_format, options.color_space),
                 extradata,
             },
         ))
@@ -2,7 +2,7 @@ use core::slice;
 use ffmpeg_next::{format::Pixel, frame};
 use std::time::Duration;
 
-use smelter_render::FrameData;
+use smelter_render::{ColorMatrix, ColorRange, FrameData, OutputColorSpace, OutputFrameFormat};
 
 use crate::prelude::*;
 
@@ -22,10 +22,11 @@ pub(super) fn create_av_frame(
     frame: Frame,
     time_base: i32,
 ) -> Result<frame::Video, FrameConversionError> {
-    let (data, pixel_format) = match frame.data {
-        FrameData::PlanarYuv420(data) => (data, Pixel::YUV420P),
-        FrameData::PlanarYuv422(data) => (data, Pixel::YUV422P),
-        FrameData::PlanarYuv444(data) => (data, Pixel::YUV444P),
+    let (data, pixel_format, bytes_per_sample) = match frame.data {
+        FrameData::PlanarYuv420(data) => (data, Pixel::YUV420P, 1),
+        FrameData::PlanarYuv422(data) => (data, Pixel::YUV422P, 1),
+        FrameData::PlanarYuv444(data) => (data, Pixel::YUV444P, 1),
+        FrameData::PlanarYuv420P10(data) => (data, Pixel::YUV420P10LE, 2),
         _ => {
             return Err(FrameConversionError(format!(
                 "Unsupported pixel format {:?}",
@@ -40,9 +41,12 @@ pub(super) fn create_av_frame(
         frame.resolution.height as u32,
     );
 
-    let expected_y_plane_size = (av_frame.plane_width(0) * av_frame.plane_height(0)) as usize;
-    let expected_u_plane_size = (av_frame.plane_width(1) * av_frame.plane_height(1)) as usize;
-    let expected_v_plane_size = (av_frame.plane_width(2) * av_frame.plane_height(2)) as usize;
+    let expected_y_plane_size =
+        (av_frame.plane_width(0) * av_frame.plane_height(0)) as usize * bytes_per_sample;
+    let expected_u_plane_size =
+        (av_frame.plane_width(1) * av_frame.plane_height(1)) as usize * bytes_per_sample;
+    let expected_v_plane_size =
+        (av_frame.plane_width(2) * av_frame.plane_height(2)) as usize * bytes_per_sample;
     if expected_y_plane_size != data.y_plane.len() {
         return Err(FrameConversionError(format!(
             "Y plane is a wrong size, expected: {} received: {}",
@@ -67,16 +71,21 @@ pub(super) fn create_av_frame(
 
     av_frame.set_pts(Some((frame.pts.as_secs_f64() * time_base as f64) as i64));
 
-    write_plane_to_av_frame(&mut av_frame, 0, &data.y_plane);
-    write_plane_to_av_frame(&mut av_frame, 1, &data.u_plane);
-    write_plane_to_av_frame(&mut av_frame, 2, &data.v_plane);
+    write_plane_to_av_frame(&mut av_frame, 0, &data.y_plane, bytes_per_sample);
+    write_plane_to_av_frame(&mut av_frame, 1, &data.u_plane, bytes_per_sample);
+    write_plane_to_av_frame(&mut av_frame, 2, &data.v_plane, bytes_per_sample);
 
     Ok(av_frame)
 }
 
-fn write_plane_to_av_frame(frame: &mut frame::Video, plane: usize, data: &[u8]) {
+fn write_plane_to_av_frame(
+    frame: &mut frame::Video,
+    plane: usize,
+    data: &[u8],
+    bytes_per_sample: usize,
+) {
     let stride = frame.stride(plane);
-    let width = frame.plane_width(plane) as usize;
+    let width = frame.plane_width(plane) as usize * bytes_per_sample;
 
     data.chunks(width)
         .zip(frame.data_mut(plane).chunks_mut(stride))
@@ -129,5 +138,55 @@ pub(super) fn into_ffmpeg_pixel_format(
         OutputPixelFormat::YUV420P => ffmpeg_next::format::Pixel::YUV420P,
         OutputPixelFormat::YUV422P => ffmpeg_next::format::Pixel::YUV422P,
         OutputPixelFormat::YUV444P => ffmpeg_next::format::Pixel::YUV444P,
+        OutputPixelFormat::YUV420P10 => ffmpeg_next::format::Pixel::YUV420P10LE,
+    }
+}
+
+pub(super) fn into_output_frame_format(
+    pixel_format: OutputPixelFormat,
+    color_space: OutputColorSpace,
+) -> OutputFrameFormat {
+    match pixel_format {
+        OutputPixelFormat::YUV420P => OutputFrameFormat::PlanarYuv420Bytes(color_space),
+        OutputPixelFormat::YUV422P => OutputFrameFormat::PlanarYuv422Bytes(color_space),
+        OutputPixelFormat::YUV444P => OutputFrameFormat::PlanarYuv444Bytes(color_space),
+        OutputPixelFormat::YUV420P10 => OutputFrameFormat::PlanarYuv420P10Bytes(color_space),
+    }
+}
+
+/// Sets color metadata that is signaled in the bitstream (e.g. H264 VUI).
+pub(super) fn set_encoder_color_space(
+    encoder: &mut ffmpeg_next::encoder::video::Video,
+    color_space: OutputColorSpace,
+) {
+    use ffmpeg_next::ffi::{AVColorPrimaries, AVColorTransferCharacteristic};
+
+    let (space, primaries, trc) = match color_space.matrix {
+        ColorMatrix::Bt601 => (
+            ffmpeg_next::color::Space::SMPTE170M,
+            AVColorPrimaries::AVCOL_PRI_SMPTE170M,
+            AVColorTransferCharacteristic::AVCOL_TRC_SMPTE170M,
+        ),
+        ColorMatrix::Bt709 => (
+            ffmpeg_next::color::Space::BT709,
+            AVColorPrimaries::AVCOL_PRI_BT709,
+            AVColorTransferCharacteristic::AVCOL_TRC_BT709,
+        ),
+        ColorMatrix::Bt2020 => (
+            ffmpeg_next::color::Space::BT2020NCL,
+            AVColorPrimaries::AVCOL_PRI_BT2020,
+            AVColorTransferCharacteristic::AVCOL_TRC_BT2020_10,
+        ),
+    };
+    let range = match color_space.range {
+        ColorRange::Limited => ffmpeg_next::color::Range::MPEG,
+        ColorRange::Full => ffmpeg_next::color::Range::JPEG,
+    };
+    encoder.set_colorspace(space);
+    encoder.set_color_range(range);
+    unsafe {
+        let encoder = encoder.as_mut_ptr();
+        (*encoder).color_primaries = primaries;
+        (*encoder).color_trc = trc;
     }
 }
@@ -5,12 +5,12 @@ use ffmpeg_next::{
     codec::{Context, Id},
     format::Pixel,
 };
-use smelter_render::{Frame, OutputFrameFormat};
+use smelter_render::{ColorMatrix, ColorRange, Frame, OutputColorSpace, OutputFrameFormat};
 use tracing::{error, info, trace, warn};
 
 use crate::pipeline::{
     encoder::{
-        ffmpeg_utils::{create_av_frame, encoded_chunk_from_av_packet},
+        ffmpeg_utils::{create_av_frame, encoded_chunk_from_av_packet, set_encoder_color_space},
         utils::bitrate_from_resolution_framerate,
     },
     ffmpeg_utils::FfmpegOptions,
@@ -39,6 +39,12 @@ impl VideoEncoder for FfmpegVp8Encoder {
 
         let framerate = ctx.output_framerate;
 
+        // VP8 does not signal color metadata in the bitstream, decoders assume BT.601.
+        let color_space = OutputColorSpace {
+            matrix: ColorMatrix::Bt601,
+            range: ColorRange::Limited,
+        };
+
         let codec = ffmpeg_next::codec::encoder::find(Id::VP8).ok_or(EncoderInitError::NoCodec)?;
 
         let mut encoder = Context::new().encoder().video()?;
@@ -49,14 +55,7 @@ impl VideoEncoder for FfmpegVp8Encoder {
         encoder.set_width(options.resolution.width as u32);
         encoder.set_height(options.resolution.height as u32);
         encoder.set_frame_rate(Some((framerate.num as i32, framerate.den as i32)));
-        encoder.set_colorspace(ffmpeg_next::color::Space::BT709);
-        encoder.set_color_range(ffmpeg_next::color::Range::MPEG);
-        unsafe {
-            let encoder = encoder.as_mut_ptr();
-            use ffmpeg_next::ffi;
-            (*encoder).color_primaries = ffi::AVColorPrimaries::AVCOL_PRI_BT709;
-            (*encoder).color_trc = ffi::AVColorTransferCharacteristic::AVCOL_TRC_BT709;
-        }
+        set_encoder_color_space(&mut encoder, color_space);
 
         let mut ffmpeg_options = FfmpegOptions::from(&[
             // TODO: This will be properly set in followup PR with gop size option in api
@@ -101,7 +100,7 @@ impl VideoEncoder for FfmpegVp8Encoder {
             },
             VideoEncoderConfig {
                 resolution: options.resolution,
-                output_format: OutputFrameFormat::PlanarYuv420Bytes,
+                output_format: OutputFrameFormat::PlanarYuv420Bytes(color_space),
                 extradata: None,
             },
         ))
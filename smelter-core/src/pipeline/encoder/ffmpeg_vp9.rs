@@ -4,13 +4,14 @@ use ffmpeg_next::{
     Rational,
     codec::{Context, Id},
 };
-use smelter_render::{Frame, OutputFrameFormat};
+use smelter_render::Frame;
 use tracing::{error, info, trace, warn};
 
 use crate::pipeline::{
     PipelineCtx,
     encoder::ffmpeg_utils::{
         create_av_frame, encoded_chunk_from_av_packet, into_ffmpeg_pixel_format,
+        into_output_frame_format, set_encoder_color_space,
     },
     ffmpeg_utils::FfmpegOptions,
 };
@@ -48,14 +49,7 @@ impl VideoEncoder for FfmpegVp9Encoder {
         encoder.set_width(options.resolution.width as u32);
         encoder.set_height(options.resolution.height as u32);
         encoder.set_frame_rate(Some((framerate.num as i32, framerate.den as i32)));
-        encoder.set_colorspace(ffmpeg_next::color::Space::BT709);
-        encoder.set_color_range(ffmpeg_next::color::Range::MPEG);
-        unsafe {
-            let encoder = encoder.as_mut_ptr();
-            use ffmpeg_next::ffi;
-            (*encoder).color_primaries = ffi::AVColorPrimaries::AVCOL_PRI_BT709;
-            (*encoder).color_trc = ffi::AVColorTransferCharacteristic::AVCOL_TRC_BT709;
-        }
+        set_encoder_color_space(&mut encoder, options.color_space);
 
         // configuration based on https://developers.google.com/media/vp9/live-encoding
         let mut ffmpeg_options = FfmpegOptions::from(&[
@@ -119,11 +113,7 @@ impl VideoEncoder for FfmpegVp9Encoder {
             },
             VideoEncoderConfig {
                 resolution: options.resolution,
-                output_format: match options.pixel_format {
-                    OutputPixelFormat::YUV420P => OutputFrameFormat::PlanarYuv420Bytes,
-                    OutputPixelFormat::YUV422P => OutputFrameFormat::PlanarYuv422Bytes,
-                    OutputPixelFormat::YUV444P => OutputFrameFormat::PlanarYuv444Bytes,
-                },
+                output_format: into_output_frame_format(options.pixel_format, options.color_space),
                 extradata: None,
             },
         ))
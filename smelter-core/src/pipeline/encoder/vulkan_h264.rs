@@ -1,9 +1,9 @@
 use std::{num::NonZero, ops::Deref, sync::Arc};
 
-use smelter_render::{FrameData, OutputFrameFormat};
+use smelter_render::{ColorMatrix, ColorRange, FrameData, OutputColorSpace, OutputFrameFormat};
 use tracing::{error, info};
 use vk_video::{
-    WgpuTexturesEncoder,
+    ColorDescription, WgpuTexturesEncoder,
     parameters::{RateControl, Rational, VideoParameters},
 };
 
@@ -56,7 +56,9 @@ impl VideoEncoder for VulkanH264Encoder {
             },
         };
 
-        let encoder_params = device.encoder_parameters_high_quality(video_params, rate_control)?;
+        let mut encoder_params =
+            device.encoder_parameters_high_quality(video_params, rate_control)?;
+        encoder_params.color_description = Some(color_description(options.color_space));
         let encoder = device.create_wgpu_textures_encoder(encoder_params)?;
 
         Ok((
@@ -84,6 +86,7 @@ impl VideoEncoder for VulkanH264Encoder {
                 vk_video::Frame {
                     data: texture.deref().clone(),
                     pts: None,
+                    color_description: None,
                 },
                 force_keyframe,
             )
@@ -128,3 +131,19 @@ fn transition_texture(ctx: &GraphicsContext, texture: &wgpu::Texture) {
 
     ctx.queue.submit([command_encoder.finish()]);
 }
+
+/// H.273 code points matching the signaling of FFmpeg encoders.
+fn color_description(color_space: OutputColorSpace) -> ColorDescription {
+    let (colour_primaries, transfer_characteristics, matrix_coefficients) = match color_space.matrix
+    {
+        ColorMatrix::Bt601 => (6, 6, 6),
+        ColorMatrix::Bt709 => (1, 1, 1),
+        ColorMatrix::Bt2020 => (9, 14, 9),
+    };
+    ColorDescription {
+        full_range: color_space.range == ColorRange::Full,
+        colour_primaries,
+        transfer_characteristics,
+        matrix_coefficients,
+    }
+}
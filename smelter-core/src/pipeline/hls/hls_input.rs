@@ -31,6 +31,7 @@ use crate::{
             fdk_aac, ffmpeg_h264, vulkan_h264,
         },
         input::Input,
+        replay::ReplayRecorder,
         utils::{H264AvcDecoderConfig, H264AvccToAnnexB, input_buffer::InputBuffer},
     },
     queue::QueueDataReceiver,
@@ -119,6 +120,7 @@ impl HlsInput {
                 samples_sender,
                 input_buffer_size: 2000,
                 force_resampling: false,
+                replay_recorder: Some(ReplayRecorder::new(&ctx, &input_ref)),
             },
         )?;
 
@@ -159,6 +161,7 @@ impl HlsInput {
             transformer: h264_config.map(H264AvccToAnnexB::new),
             frame_sender,
             input_buffer_size: 2000,
+            replay_recorder: Some(ReplayRecorder::new(&ctx, &input_ref)),
         };
 
         let vulkan_supported = ctx.graphics_context.has_vulkan_decoder_support();
@@ -3,7 +3,8 @@ use std::sync::{Arc, Mutex};
 use crate::{
     pipeline::{
         hls::HlsInput,
-        mp4::Mp4Input,
+        mp4::{Mp4Input, PlaylistInput},
+        replay::ReplayInput,
         rtmp::RtmpServerInput,
         rtp::RtpInput,
         webrtc::{WhepInput, WhipInput},
@@ -28,6 +29,8 @@ pub enum Input {
     Rtp(RtpInput),
     RtmpServer(RtmpServerInput),
     Mp4(Mp4Input),
+    Playlist(PlaylistInput),
+    Replay(ReplayInput),
     Whip(WhipInput),
     Whep(WhepInput),
     Hls(HlsInput),
@@ -44,6 +47,8 @@ impl Input {
             Input::Rtp(_input) => InputProtocolKind::Rtp,
             Input::RtmpServer(_input) => InputProtocolKind::Rtmp,
             Input::Mp4(_input) => InputProtocolKind::Mp4,
+            Input::Playlist(_input) => InputProtocolKind::Playlist,
+            Input::Replay(_input) => InputProtocolKind::Replay,
             Input::Whip(_input) => InputProtocolKind::Whip,
             Input::Whep(_input) => InputProtocolKind::Whep,
             Input::Hls(_input) => InputProtocolKind::Hls,
@@ -65,6 +70,8 @@ pub(super) fn new_external_input(
         ProtocolInputOptions::Rtp(opts) => RtpInput::new_input(ctx, input_ref, opts),
         ProtocolInputOptions::RtmpServer(opts) => RtmpServerInput::new_input(ctx, input_ref, opts),
         ProtocolInputOptions::Mp4(opts) => Mp4Input::new_input(ctx, input_ref, opts),
+        ProtocolInputOptions::Playlist(opts) => PlaylistInput::new_input(ctx, input_ref, opts),
+        ProtocolInputOptions::Replay(opts) => ReplayInput::new_input(ctx, input_ref, opts),
         ProtocolInputOptions::Hls(opts) => HlsInput::new_input(ctx, input_ref, opts),
         ProtocolInputOptions::Whip(opts) => WhipInput::new_input(ctx, input_ref, opts),
         ProtocolInputOptions::Whep(opts) => WhepInput::new_input(ctx, input_ref, opts),
@@ -77,6 +84,15 @@ pub(super) fn new_external_input(
     }
 }
 
+/// Input initialized with [`Pipeline::prepare_input`] that is not yet registered.
+pub struct PreparedInput {
+    pub(super) input_id: InputId,
+    pub(super) input: PipelineInput,
+    pub(super) receiver: QueueDataReceiver,
+    pub(super) queue_options: QueueInputOptions,
+    pub(super) failover: InputFailoverOptions,
+}
+
 /// This method doesn't take pipeline lock for the whole scope,
 /// because input registration can potentially take a relatively long time.
 pub(super) fn register_pipeline_input<BuildFn, NewInputResult>(
@@ -85,6 +101,26 @@ pub(super) fn register_pipeline_input<BuildFn, NewInputResult>(
     queue_options: QueueInputOptions,
     build_input: BuildFn,
 ) -> Result<NewInputResult, RegisterInputError>
+where
+    BuildFn: FnOnce(
+        Arc<PipelineCtx>,
+        Ref<InputId>,
+    ) -> Result<(Input, NewInputResult, QueueDataReceiver), InputInitError>,
+{
+    let (prepared, input_result) =
+        prepare_pipeline_input(pipeline, input_id, queue_options, build_input)?;
+    add_pipeline_input(&mut pipeline.lock().unwrap(), prepared)?;
+    Ok(input_result)
+}
+
+/// Initializes the input without adding it to the pipeline, the pipeline is locked
+/// only to check the id and to get the context.
+pub(super) fn prepare_pipeline_input<BuildFn, NewInputResult>(
+    pipeline: &Arc<Mutex<Pipeline>>,
+    input_id: InputId,
+    queue_options: QueueInputOptions,
+    build_input: BuildFn,
+) -> Result<(PreparedInput, NewInputResult), RegisterInputError>
 where
     BuildFn: FnOnce(
         Arc<PipelineCtx>,
@@ -105,20 +141,40 @@ where
         receiver.video.as_ref().map(|_| false),
     );
 
-    let pipeline_input = PipelineInput {
-        input,
-        audio_eos_received,
-        video_eos_received,
+    let prepared = PreparedInput {
+        input_id,
+        input: PipelineInput {
+            input,
+            audio_eos_received,
+            video_eos_received,
+        },
+        receiver,
+        queue_options,
+        failover: InputFailoverOptions::default(),
     };
+    Ok((prepared, input_result))
+}
 
-    let mut guard = pipeline.lock().unwrap();
-
-    if guard.inputs.contains_key(&input_id) {
+/// Adds a prepared input to the queue and the renderer, so it is used starting
+/// from the next frame.
+pub(super) fn add_pipeline_input(
+    pipeline: &mut Pipeline,
+    prepared: PreparedInput,
+) -> Result<(), RegisterInputError> {
+    let PreparedInput {
+        input_id,
+        input: pipeline_input,
+        receiver,
+        queue_options,
+        failover,
+    } = prepared;
+
+    if pipeline.inputs.contains_key(&input_id) {
         return Err(RegisterInputError::AlreadyRegistered(input_id));
     };
 
     if pipeline_input.audio_eos_received.is_some() {
-        for (_, output) in guard.outputs.iter_mut() {
+        for (_, output) in pipeline.outputs.iter_mut() {
             if let Some(ref mut cond) = output.audio_end_condition {
                 cond.on_input_registered(&input_id);
             }
@@ -126,18 +182,25 @@ where
     }
 
     if pipeline_input.video_eos_received.is_some() {
-        for (_, output) in guard.outputs.iter_mut() {
+        for (_, output) in pipeline.outputs.iter_mut() {
             if let Some(ref mut cond) = output.video_end_condition {
                 cond.on_input_registered(&input_id);
             }
         }
     }
 
-    guard.inputs.insert(input_id.clone(), pipeline_input);
-    guard.queue.add_input(&input_id, receiver, queue_options);
-    guard.renderer.register_input(input_id);
+    pipeline.inputs.insert(input_id.clone(), pipeline_input);
+    pipeline.queue.add_input(&input_id, receiver, queue_options);
+    pipeline.renderer.register_input(input_id.clone());
 
-    Ok(input_result)
+    if failover.is_enabled()
+        && let Err(err) = pipeline.set_input_failover(&input_id, failover)
+    {
+        // Registration has to fail as a whole, so the request can be retried.
+        let _ = pipeline.unregister_input(&input_id);
+        return Err(RegisterInputError::FailoverError(input_id, err));
+    }
+    Ok(())
 }
 
 impl PipelineInput {
@@ -11,12 +11,14 @@ use tokio::runtime::Runtime;
 use tracing::{error, info, trace, warn};
 
 use smelter_render::{
-    FrameSet, InputId, OutputId, RegistryType, Renderer, RendererId, RendererOptions, RendererSpec,
+    FrameSet, InputId, OutputId, OutputSceneUpdate, PreparedRenderer, RegistryType, Renderer,
+    RendererId, RendererOptions, RendererSpec, RgbaSnapshot, SnapshotSource,
     error::{
-        ErrorStack, RegisterRendererError, RequestKeyframeError, UnregisterRendererError,
-        UpdateSceneError,
+        ErrorStack, RegisterRendererError, RequestKeyframeError, SnapshotError,
+        UnregisterRendererError, UpdateFragmentError, UpdateSceneError,
     },
-    scene::Component,
+    scene::{Component, ComponentLayout, ScenePatch},
+    video_clip::VideoClipSpec,
 };
 
 use crate::{
@@ -24,12 +26,22 @@ use crate::{
     event::{Event, EventEmitter},
     pipeline::{
         channel::{EncodedDataOutput, RawDataInput, RawDataOutput},
-        input::{PipelineInput, new_external_input, register_pipeline_input},
-        output::{OutputSender, PipelineOutput, new_external_output, register_pipeline_output},
+        input::{
+            Input, PipelineInput, PreparedInput, add_pipeline_input, new_external_input,
+            prepare_pipeline_input, register_pipeline_input,
+        },
+        mp4::decode_video_clip,
+        output::{
+            OutputSender, PipelineOutput, PreparedOutput, add_pipeline_output, new_external_output,
+            prepare_pipeline_output, register_pipeline_output,
+        },
         webrtc::{WhipWhepPipelineState, WhipWhepServer, WhipWhepServerHandle},
     },
-    queue::{Queue, QueueAudioOutput, QueueOptions, QueueVideoOutput},
-    stats::StatsMonitor,
+    queue::{
+        AudioMonitor, Queue, QueueAudioOutput, QueueOptions, QueueVideoOutput, ReplayBuffer,
+        VideoMonitor, VideoMonitorThread,
+    },
+    stats::{MediaAlarmSource, StatsMonitor},
 };
 use crate::{
     graphics_context::{GraphicsContext, GraphicsContextOptions},
@@ -74,13 +86,36 @@ impl Pipeline {
         input_id: InputId,
         options: RegisterInputOptions,
     ) -> Result<InputInitInfo, RegisterInputError> {
+        let (prepared, init_info) = Self::prepare_input(pipeline, input_id, options)?;
+        pipeline.lock().unwrap().register_prepared_input(prepared)?;
+        Ok(init_info)
+    }
+
+    /// Initializes an input (e.g. its transport and decoders) without registering it.
+    /// Register it later with [`Pipeline::register_prepared_input`].
+    pub fn prepare_input(
+        pipeline: &Arc<Mutex<Self>>,
+        input_id: InputId,
+        options: RegisterInputOptions,
+    ) -> Result<(PreparedInput, InputInitInfo), RegisterInputError> {
         let input_options = options.input_options;
-        register_pipeline_input(
+        let (mut prepared, init_info) = prepare_pipeline_input(
             pipeline,
             input_id,
             options.queue_options,
             |ctx, input_id| new_external_input(ctx, input_id, input_options),
-        )
+        )?;
+        prepared.failover = options.failover;
+        Ok((prepared, init_info))
+    }
+
+    /// Adds an input to the queue, it is used starting from the next frame. Prepared
+    /// input is dropped if the registration fails.
+    pub fn register_prepared_input(
+        &mut self,
+        prepared: PreparedInput,
+    ) -> Result<(), RegisterInputError> {
+        add_pipeline_input(self, prepared)
     }
 
     pub fn register_raw_data_input(
@@ -94,6 +129,18 @@ impl Pipeline {
         })
     }
 
+    /// Same as [`Pipeline::prepare_input`], but for an input that receives raw media.
+    pub fn prepare_raw_data_input(
+        pipeline: &Arc<Mutex<Self>>,
+        input_id: InputId,
+        raw_input_options: RawDataInputOptions,
+        queue_options: QueueInputOptions,
+    ) -> Result<(PreparedInput, RawDataInputSender), RegisterInputError> {
+        prepare_pipeline_input(pipeline, input_id, queue_options, |ctx, input_id| {
+            RawDataInput::new_input(ctx, input_id, raw_input_options)
+        })
+    }
+
     pub fn unregister_input(&mut self, input_id: &InputId) -> Result<(), UnregisterInputError> {
         if !self.inputs.contains_key(input_id) {
             return Err(UnregisterInputError::NotFound(input_id.clone()));
@@ -102,6 +149,8 @@ impl Pipeline {
         self.inputs.remove(input_id);
         self.queue.remove_input(input_id);
         self.renderer.unregister_input(input_id);
+        self.audio_mixer.unregister_input(input_id);
+        self.ctx.replay_buffers.lock().unwrap().remove(input_id);
         for output in self.outputs.values_mut() {
             if let Some(ref mut cond) = output.audio_end_condition {
                 cond.on_input_unregistered(input_id);
@@ -113,12 +162,120 @@ impl Pipeline {
         Ok(())
     }
 
+    /// Adds items to or removes upcoming items from a playlist input.
+    pub fn update_playlist(
+        &self,
+        input_id: &InputId,
+        update: PlaylistUpdate,
+    ) -> Result<(), PlaylistUpdateError> {
+        let Some(input) = self.inputs.get(input_id) else {
+            return Err(PlaylistUpdateError::NotFound(input_id.clone()));
+        };
+        match &input.input {
+            Input::Playlist(playlist) => playlist.update(update),
+            _ => Err(PlaylistUpdateError::NotAPlaylist(input_id.clone())),
+        }
+    }
+
+    /// Starts recording encoded media of the input into a rolling buffer. Replay inputs
+    /// can be registered with this input as a source. Enabling the buffer again replaces
+    /// the previous one and discards media recorded so far. Inputs that receive raw media
+    /// and replay inputs are not supported.
+    pub fn enable_replay_buffer(
+        &self,
+        input_id: &InputId,
+        options: ReplayBufferOptions,
+    ) -> Result<(), ReplayBufferError> {
+        let Some(input) = self.inputs.get(input_id) else {
+            return Err(ReplayBufferError::NotFound(input_id.clone()));
+        };
+        match input.input {
+            Input::Rtp(_)
+            | Input::RtmpServer(_)
+            | Input::Mp4(_)
+            | Input::Playlist(_)
+            | Input::Whip(_)
+            | Input::Whep(_)
+            | Input::Hls(_) => {}
+            _ => return Err(ReplayBufferError::UnsupportedInput(input_id.clone())),
+        }
+        let replay_buffer = ReplayBuffer::new(options);
+        self.queue
+            .set_replay_buffer(input_id, replay_buffer.clone());
+        self.ctx
+            .replay_buffers
+            .lock()
+            .unwrap()
+            .insert(input_id.clone(), replay_buffer);
+        Ok(())
+    }
+
+    /// Shifts audio of the input relative to its video. New value applies to audio
+    /// that was not queued yet. Lowering the delay drops queued audio that would
+    /// overlap, so the change might cause a short gap.
+    pub fn set_input_audio_delay(
+        &self,
+        input_id: &InputId,
+        audio_delay: AudioDelay,
+    ) -> Result<(), AudioDelayError> {
+        if !self.inputs.contains_key(input_id) {
+            return Err(AudioDelayError::NotFound(input_id.clone()));
+        }
+        self.queue.set_audio_delay(input_id, audio_delay);
+        Ok(())
+    }
+
+    /// Configures sources that are used in place of an input when it stalls. Replaces
+    /// the previous configuration, passing default options disables failover.
+    pub fn set_input_failover(
+        &mut self,
+        input_id: &InputId,
+        options: InputFailoverOptions,
+    ) -> Result<(), InputFailoverError> {
+        if !self.inputs.contains_key(input_id) {
+            return Err(InputFailoverError::NotFound(input_id.clone()));
+        }
+        for backup_id in &options.backup_inputs {
+            if backup_id == input_id {
+                return Err(InputFailoverError::BackupSameAsInput(input_id.clone()));
+            }
+            if !self.inputs.contains_key(backup_id) {
+                return Err(InputFailoverError::BackupNotFound(backup_id.clone()));
+            }
+        }
+
+        self.renderer
+            .set_input_slate(input_id, options.slate_image_id.as_ref())?;
+        match options.is_enabled() {
+            true => self
+                .queue
+                .set_input_failover(input_id, options.backup_inputs),
+            false => self.queue.remove_input_failover(input_id),
+        }
+        Ok(())
+    }
+
     pub fn register_output(
         pipeline: &Arc<Mutex<Self>>,
         output_id: OutputId,
         register_options: RegisterOutputOptions,
     ) -> Result<Option<Port>, RegisterOutputError> {
-        register_pipeline_output(
+        let (prepared, port) = Self::prepare_output(pipeline, output_id, register_options)?;
+        pipeline
+            .lock()
+            .unwrap()
+            .register_prepared_output(prepared)?;
+        Ok(port)
+    }
+
+    /// Initializes an output (e.g. its encoders and transport) without registering it.
+    /// Register it later with [`Pipeline::register_prepared_output`].
+    pub fn prepare_output(
+        pipeline: &Arc<Mutex<Self>>,
+        output_id: OutputId,
+        register_options: RegisterOutputOptions,
+    ) -> Result<(PreparedOutput, Option<Port>), RegisterOutputError> {
+        prepare_pipeline_output(
             pipeline,
             output_id,
             register_options.video,
@@ -127,6 +284,15 @@ impl Pipeline {
         )
     }
 
+    /// Adds an output to the renderer and the queue, it receives media starting from
+    /// the next frame. Prepared output is dropped if the registration fails.
+    pub fn register_prepared_output(
+        &mut self,
+        prepared: PreparedOutput,
+    ) -> Result<(), RegisterOutputError> {
+        add_pipeline_output(self, prepared)
+    }
+
     pub fn register_encoded_data_output(
         pipeline: &Arc<Mutex<Self>>,
         output_id: OutputId,
@@ -162,6 +328,24 @@ impl Pipeline {
         )
     }
 
+    /// Same as [`Pipeline::prepare_output`], but for an output that produces raw media.
+    pub fn prepare_raw_data_output(
+        pipeline: &Arc<Mutex<Self>>,
+        output_id: OutputId,
+        register_options: RegisterRawDataOutputOptions,
+    ) -> Result<(PreparedOutput, RawDataOutputReceiver), RegisterOutputError> {
+        prepare_pipeline_output(
+            pipeline,
+            output_id,
+            register_options.video,
+            register_options.audio,
+            |_ctx, _output_ref| {
+                let (output, result) = RawDataOutput::new(register_options.output_options)?;
+                Ok((Box::new(output), result))
+            },
+        )
+    }
+
     pub fn unregister_output(&mut self, output_id: &OutputId) -> Result<(), UnregisterOutputError> {
         if !self.outputs.contains_key(output_id) {
             return Err(UnregisterOutputError::NotFound(output_id.clone()));
@@ -170,6 +354,7 @@ impl Pipeline {
         self.audio_mixer.unregister_output(output_id);
         self.outputs.remove(output_id);
         self.renderer.unregister_output(output_id);
+        self.queue.remove_output(output_id);
         Ok(())
     }
 
@@ -183,6 +368,71 @@ impl Pipeline {
         Ok(())
     }
 
+    /// Initializes a renderer without registering it. Register it later with
+    /// [`Pipeline::register_prepared_renderer`].
+    pub fn prepare_renderer(
+        pipeline: &Arc<Mutex<Self>>,
+        renderer_id: RendererId,
+        transformation_spec: RendererSpec,
+    ) -> Result<PreparedRenderer, RegisterRendererError> {
+        let renderer = pipeline.lock().unwrap().renderer.clone();
+        renderer.prepare_renderer(renderer_id, transformation_spec)
+    }
+
+    pub fn register_prepared_renderer(
+        &self,
+        prepared: PreparedRenderer,
+    ) -> Result<(), RegisterRendererError> {
+        self.renderer.register_prepared_renderer(prepared)
+    }
+
+    /// Downloads the last frame of an output or an input. The pipeline is locked only to
+    /// get the renderer, and the renderer only while the copy of the frame is scheduled,
+    /// so waiting for the GPU blocks neither of them.
+    pub fn snapshot(
+        pipeline: &Arc<Mutex<Self>>,
+        source: SnapshotSource,
+    ) -> Result<RgbaSnapshot, SnapshotError> {
+        let renderer = pipeline.lock().unwrap().renderer.clone();
+        renderer.snapshot(&source)
+    }
+
+    /// Decodes the whole clip before registering it in the renderer, so this call
+    /// can take a while. The pipeline is not locked while decoding.
+    pub fn register_video_clip(
+        pipeline: &Arc<Mutex<Self>>,
+        renderer_id: RendererId,
+        options: VideoClipOptions,
+    ) -> Result<(), RegisterVideoClipError> {
+        let prepared = Self::prepare_video_clip(pipeline, renderer_id, options)?;
+        let renderer = pipeline.lock().unwrap().renderer.clone();
+        renderer.register_prepared_renderer(prepared)?;
+        Ok(())
+    }
+
+    /// Decodes the whole clip without registering it. Register it later with
+    /// [`Pipeline::register_prepared_renderer`].
+    pub fn prepare_video_clip(
+        pipeline: &Arc<Mutex<Self>>,
+        renderer_id: RendererId,
+        options: VideoClipOptions,
+    ) -> Result<PreparedRenderer, RegisterVideoClipError> {
+        let (ctx, renderer) = {
+            let guard = pipeline.lock().unwrap();
+            (guard.ctx.clone(), guard.renderer.clone())
+        };
+        let clip = decode_video_clip(&ctx, options.source)
+            .map_err(|err| RegisterVideoClipError::Decode(err, renderer_id.clone()))?;
+        let prepared = renderer.prepare_renderer(
+            renderer_id,
+            RendererSpec::VideoClip(VideoClipSpec {
+                frames: clip.frames,
+                duration: clip.duration,
+            }),
+        )?;
+        Ok(prepared)
+    }
+
     pub fn unregister_renderer(
         &self,
         renderer_id: &RendererId,
@@ -192,22 +442,97 @@ impl Pipeline {
             .unregister_renderer(renderer_id, registry_type)
     }
 
+    pub fn is_renderer_registered(
+        &self,
+        renderer_id: &RendererId,
+        registry_type: RegistryType,
+    ) -> bool {
+        self.renderer
+            .is_renderer_registered(renderer_id, registry_type)
+    }
+
+    pub fn update_fragment(
+        &self,
+        fragment_id: RendererId,
+        root: Component,
+    ) -> Result<(), UpdateFragmentError> {
+        info!(?fragment_id, "Update fragment {:?}", root);
+        self.renderer.update_fragment(fragment_id, root)
+    }
+
     pub fn update_output(
         &mut self,
         output_id: OutputId,
         video: Option<Component>,
         audio: Option<AudioMixerConfig>,
     ) -> Result<(), UpdateSceneError> {
-        self.check_output_spec(&output_id, &video, &audio)?;
-        if let Some(video) = video {
-            self.update_scene_root(output_id.clone(), video)?;
+        self.update_outputs(vec![OutputUpdate {
+            output_id,
+            video,
+            audio,
+        }])
+    }
+
+    /// Updates multiple outputs, so all new scenes are rendered starting from the same
+    /// frame. All updates are validated first, if any of them is invalid, none of the
+    /// outputs is updated.
+    pub fn update_outputs(&mut self, updates: Vec<OutputUpdate>) -> Result<(), UpdateSceneError> {
+        let mut scene_updates = Vec::new();
+        let mut audio_updates = Vec::new();
+        for update in updates {
+            let OutputUpdate {
+                output_id,
+                video,
+                audio,
+            } = update;
+            self.check_output_spec(&output_id, &video, &audio)?;
+            if let Some(video) = video
+                && let Some(scene_update) = self.scene_update(output_id.clone(), video)?
+            {
+                scene_updates.push(scene_update);
+            }
+            if let Some(audio) = audio
+                && self.is_audio_update_allowed(&output_id)?
+            {
+                info!(?output_id, "Update audio mixer {:?}", audio);
+                audio_updates.push((output_id, audio));
+            }
         }
 
-        if let Some(audio) = audio {
-            self.update_audio(&output_id, audio)?;
+        self.renderer.update_scenes(scene_updates)?;
+        self.audio_mixer.update_outputs(audio_updates)
+    }
+
+    pub fn patch_output(
+        &mut self,
+        output_id: OutputId,
+        patches: Vec<ScenePatch>,
+    ) -> Result<(), UpdateSceneError> {
+        let output = self
+            .outputs
+            .get(&output_id)
+            .ok_or_else(|| UpdateSceneError::OutputNotRegistered(output_id.clone()))?;
+
+        if let Some(cond) = &output.video_end_condition
+            && cond.did_output_end()
+        {
+            // Ignore updates after EOS
+            warn!("Received output patch on a finished output");
+            return Ok(());
         }
 
-        Ok(())
+        let Some(video_output) = output.output.video() else {
+            return Err(UpdateSceneError::AudioVideoNotMatching(output_id));
+        };
+
+        info!(?output_id, "Patch scene {:?}", patches);
+
+        self.renderer.patch_scene(
+            output_id,
+            video_output.resolution,
+            video_output.frame_format,
+            patches,
+        )
     }
 
     pub fn request_keyframe(&self, output_id: OutputId) -> Result<(), RequestKeyframeError> {
@@ -248,11 +573,12 @@ impl Pipeline {
         Ok(())
     }
 
-    fn update_scene_root(
-        &mut self,
+    /// Returns `None` if the output already ended and the update should be ignored.
+    fn scene_update(
+        &self,
         output_id: OutputId,
         scene_root: Component,
-    ) -> Result<(), UpdateSceneError> {
+    ) -> Result<Option<OutputSceneUpdate>, UpdateSceneError> {
         let output = self
             .outputs
             .get(&output_id)
@@ -263,7 +589,7 @@ impl Pipeline {
         {
             // Ignore updates after EOS
             warn!("Received output update on a finished output");
-            return Ok(());
+            return Ok(None);
         }
 
         let Some(video_output) = output.output.video() else {
@@ -272,19 +598,15 @@ impl Pipeline {
 
         info!(?output_id, "Update scene {:?}", scene_root);
 
-        self.renderer.update_scene(
+        Ok(Some(OutputSceneUpdate {
             output_id,
-            video_output.resolution,
-            video_output.frame_format,
+            resolution: video_output.resolution,
+            output_format: video_output.frame_format,
             scene_root,
-        )
+        }))
     }
 
-    fn update_audio(
-        &mut self,
-        output_id: &OutputId,
-        audio: AudioMixerConfig,
-    ) -> Result<(), UpdateSceneError> {
+    fn is_audio_update_allowed(&self, output_id: &OutputId) -> Result<bool, UpdateSceneError> {
         let output = self
             .outputs
             .get(output_id)
@@ -295,11 +617,9 @@ impl Pipeline {
         {
             // Ignore updates after EOS
             warn!("Received output update on a finished output");
-            return Ok(());
+            return Ok(false);
         }
-
-        info!(?output_id, "Update audio mixer {:?}", audio);
-        self.audio_mixer.update_output(output_id, audio)
+        Ok(true)
     }
 
     pub fn start(pipeline: &Arc<Mutex<Self>>) {
@@ -331,6 +651,20 @@ impl Pipeline {
         pipeline: &Arc<Mutex<Self>>,
         pts: Duration,
         callback: F,
+    ) {
+        Self::schedule_unlocked_event(pipeline, pts, move |pipeline| {
+            let mut guard = pipeline.lock().unwrap();
+            callback(&mut guard)
+        });
+    }
+
+    /// Same as [`Pipeline::schedule_event`], but the pipeline is not locked when the
+    /// callback is called. Required for operations that manage the lock themselves,
+    /// e.g. input and output registration.
+    pub fn schedule_unlocked_event<F: FnOnce(&Arc<Mutex<Self>>) + Send + 'static>(
+        pipeline: &Arc<Mutex<Self>>,
+        pts: Duration,
+        callback: F,
     ) {
         let weak = Arc::downgrade(pipeline);
         let guard = pipeline.lock().unwrap();
@@ -341,12 +675,16 @@ impl Pipeline {
                     warn!("Unable to call scheduled callback. Pipeline already dropped.");
                     return;
                 };
-                let mut guard = pipeline.lock().unwrap();
-                callback(&mut guard)
+                callback(&pipeline)
             }),
         );
     }
 
+    /// Positions of components with an id in the current scene of an output.
+    pub fn component_layouts(&self, output_id: &OutputId) -> Option<Vec<ComponentLayout>> {
+        self.renderer.component_layouts(output_id)
+    }
+
     pub fn outputs(&self) -> impl Iterator<Item = (&OutputId, OutputInfo)> {
         self.outputs.iter().map(|(id, output)| {
             let protocol = output.output.kind();
@@ -366,13 +704,17 @@ fn run_renderer_thread(
     pipeline: Weak<Mutex<Pipeline>>,
     frames_receiver: Receiver<QueueVideoOutput>,
 ) {
-    let renderer = match pipeline.upgrade() {
-        Some(pipeline) => pipeline.lock().unwrap().renderer.clone(),
+    let (renderer, ctx) = match pipeline.upgrade() {
+        Some(pipeline) => {
+            let guard = pipeline.lock().unwrap();
+            (guard.renderer.clone(), guard.ctx.clone())
+        }
         None => {
             warn!("Pipeline stopped before render thread was started.");
             return;
         }
     };
+    let mut monitors: HashMap<OutputId, VideoMonitor> = HashMap::new();
 
     for mut input_frames in frames_receiver.iter() {
         let Some(pipeline) = pipeline.upgrade() else {
@@ -403,10 +745,12 @@ fn run_renderer_thread(
                     }
                 })
                 .collect();
+        monitors.retain(|output_id, _| output_frame_senders.contains_key(output_id));
 
+        let outputs = std::mem::take(&mut input_frames.outputs);
         let input_frames: FrameSet<InputId> = input_frames.into();
-        trace!(?input_frames, "Rendering frames");
-        let output_frames = renderer.render(input_frames);
+        trace!(?input_frames, ?outputs, "Rendering frames");
+        let output_frames = renderer.render_outputs(input_frames, &outputs);
         let Ok(output_frames) = output_frames else {
             error!(
                 "Error while rendering: {}",
@@ -415,11 +759,19 @@ fn run_renderer_thread(
             continue;
         };
 
+        let pts = output_frames.pts;
         for (output_id, frame) in output_frames.frames {
             let Some(frame_sender) = output_frame_senders.get(&output_id) else {
                 warn!(?output_id, "Received new frame from renderer after EOS.");
                 continue;
             };
+            monitors
+                .entry(output_id.clone())
+                .or_insert_with(|| {
+                    let source = MediaAlarmSource::Output(output_id.clone());
+                    VideoMonitor::new(source, ctx.media_alarm_threshold, &ctx)
+                })
+                .process(&frame, pts);
 
             if frame_sender.send(PipelineEvent::Data(frame)).is_err() {
                 warn!(?output_id, "Failed to send output frames. Channel closed.");
@@ -433,13 +785,17 @@ fn run_audio_mixer_thread(
     pipeline: Weak<Mutex<Pipeline>>,
     audio_receiver: Receiver<QueueAudioOutput>,
 ) {
-    let audio_mixer = match pipeline.upgrade() {
-        Some(pipeline) => pipeline.lock().unwrap().audio_mixer.clone(),
+    let (audio_mixer, ctx) = match pipeline.upgrade() {
+        Some(pipeline) => {
+            let guard = pipeline.lock().unwrap();
+            (guard.audio_mixer.clone(), guard.ctx.clone())
+        }
         None => {
             warn!("Pipeline stopped before mixer thread was started.");
             return;
         }
     };
+    let mut monitors: HashMap<OutputId, AudioMonitor> = HashMap::new();
 
     for mut samples in audio_receiver.iter() {
         let Some(pipeline) = pipeline.upgrade() else {
@@ -470,6 +826,7 @@ fn run_audio_mixer_thread(
                     }
                 })
                 .collect();
+        monitors.retain(|output_id, _| output_samples_senders.contains_key(output_id));
 
         let mixed_samples = audio_mixer.mix_samples(samples.into());
 
@@ -478,6 +835,13 @@ fn run_audio_mixer_thread(
                 warn!(?output_id, "Received new mixed samples after EOS.");
                 continue;
             };
+            monitors
+                .entry(output_id.clone())
+                .or_insert_with(|| {
+                    let source = MediaAlarmSource::Output(output_id.clone());
+                    AudioMonitor::new(source, ctx.media_alarm_threshold, &ctx)
+                })
+                .process_output(&batch);
 
             if samples_sender.send(PipelineEvent::Data(batch)).is_err() {
                 warn!(?output_id, "Failed to send mixed audio. Channel closed.");
@@ -528,6 +892,8 @@ fn create_pipeline(opts: PipelineOptions) -> Result<Pipeline, InitPipelineError>
     };
 
     let (stats_monitor, stats_sender) = StatsMonitor::new();
+    let video_monitor_thread =
+        VideoMonitorThread::spawn().map_err(InitPipelineError::CreateMediaMonitorThread)?;
 
     let ctx = Arc::new(PipelineCtx {
         queue_sync_point: Instant::now(),
@@ -540,6 +906,9 @@ fn create_pipeline(opts: PipelineOptions) -> Result<Pipeline, InitPipelineError>
         download_dir,
         event_emitter: Arc::new(EventEmitter::new()),
         stats_sender,
+        media_alarm_threshold: opts.media_alarm_threshold,
+        video_monitor_thread,
+        replay_buffers: Arc::new(Mutex::new(HashMap::new())),
         tokio_rt: tokio_rt.clone(),
         graphics_context,
         whip_whep_state: match opts.whip_whep_server {
@@ -561,7 +930,7 @@ fn create_pipeline(opts: PipelineOptions) -> Result<Pipeline, InitPipelineError>
         queue: Queue::new(queue_options, &ctx),
         renderer,
         stats_monitor,
-        audio_mixer: AudioMixer::new(opts.mixing_sample_rate),
+        audio_mixer: AudioMixer::new(&ctx),
         is_started: false,
         ctx,
         whip_whep_handle,
@@ -1,6 +1,10 @@
 mod mp4_input;
 mod mp4_output;
+mod playlist_input;
 mod reader;
+mod video_clip;
 
 pub use mp4_input::Mp4Input;
 pub use mp4_output::Mp4Output;
+pub use playlist_input::PlaylistInput;
+pub(super) use video_clip::decode_video_clip;
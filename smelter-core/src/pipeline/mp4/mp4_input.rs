@@ -9,7 +9,7 @@ use std::{
     time::Duration,
 };
 
-use crossbeam_channel::bounded;
+use crossbeam_channel::{Sender, bounded};
 use tracing::{Level, Span, debug, error, span, trace};
 
 use crate::{
@@ -22,6 +22,7 @@ use crate::{
         },
         input::Input,
         mp4::reader::{DecoderOptions, Mp4FileReader, Track},
+        replay::ReplayRecorder,
         utils::{H264AvccToAnnexB, input_buffer::InputBuffer},
     },
     queue::QueueDataReceiver,
@@ -63,52 +64,12 @@ impl Mp4Input {
             return Err(Mp4InputError::NoTrack.into());
         }
 
-        let vulkan_supported = ctx.graphics_context.has_vulkan_decoder_support();
-        let h264_decoder = options.video_decoders.h264.unwrap_or({
-            if vulkan_supported {
-                VideoDecoderOptions::VulkanH264
-            } else {
-                VideoDecoderOptions::FfmpegH264
-            }
-        });
+        let h264_decoder = h264_decoder(&ctx, &options.video_decoders);
 
         let (video_handle, video_receiver, video_track) = match video {
             Some(track) => {
                 let (sender, receiver) = crossbeam_channel::bounded(10);
-                let handle = match (track.decoder_options(), h264_decoder) {
-                    (DecoderOptions::H264(h264_config), VideoDecoderOptions::FfmpegH264) => {
-                        VideoDecoderThread::<ffmpeg_h264::FfmpegH264Decoder, _>::spawn(
-                            input_ref.clone(),
-                            VideoDecoderThreadOptions {
-                                ctx: ctx.clone(),
-                                transformer: Some(H264AvccToAnnexB::new(h264_config.clone())),
-                                frame_sender: sender,
-                                input_buffer_size: 5,
-                            },
-                        )?
-                    }
-                    (DecoderOptions::H264(h264_config), VideoDecoderOptions::VulkanH264) => {
-                        if !vulkan_supported {
-                            return Err(InputInitError::DecoderError(
-                                DecoderInitError::VulkanContextRequiredForVulkanDecoder,
-                            ));
-                        }
-                        VideoDecoderThread::<vulkan_h264::VulkanH264Decoder, _>::spawn(
-                            input_ref.clone(),
-                            VideoDecoderThreadOptions {
-                                ctx: ctx.clone(),
-                                transformer: Some(H264AvccToAnnexB::new(h264_config.clone())),
-                                frame_sender: sender,
-                                input_buffer_size: 5,
-                            },
-                        )?
-                    }
-                    _ => {
-                        return Err(
-                            Mp4InputError::Unknown("Non H264 decoder options returned.").into()
-                        );
-                    }
-                };
+                let handle = spawn_video_decoder(&ctx, &input_ref, &track, h264_decoder, sender)?;
                 (Some(handle), Some(receiver), Some(track))
             }
             None => (None, None, None),
@@ -117,27 +78,7 @@ impl Mp4Input {
         let (audio_handle, audio_receiver, audio_track) = match audio {
             Some(track) => {
                 let (sender, receiver) = crossbeam_channel::bounded(10);
-                let handle = match track.decoder_options() {
-                    DecoderOptions::Aac(data) => {
-                        AudioDecoderThread::<fdk_aac::FdkAacDecoder>::spawn(
-                            input_ref.clone(),
-                            AudioDecoderThreadOptions {
-                                ctx: ctx.clone(),
-                                decoder_options: FdkAacDecoderOptions {
-                                    asc: Some(data.clone()),
-                                },
-                                samples_sender: sender,
-                                input_buffer_size: 5,
-                                force_resampling: false,
-                            },
-                        )?
-                    }
-                    _ => {
-                        return Err(
-                            Mp4InputError::Unknown("Non AAC decoder options returned.").into()
-                        );
-                    }
-                };
+                let handle = spawn_audio_decoder(&ctx, &input_ref, &track, sender)?;
                 (Some(handle), Some(receiver), Some(track))
             }
             None => (None, None, None),
@@ -187,7 +128,7 @@ impl Mp4Input {
         ))
     }
 
-    fn download_remote_file(
+    pub(super) fn download_remote_file(
         ctx: &Arc<PipelineCtx>,
         url: &str,
     ) -> Result<Arc<SourceFile>, Mp4InputError> {
@@ -209,6 +150,92 @@ impl Mp4Input {
     }
 }
 
+/// Decoder used for H264 tracks if not specified explicitly.
+pub(super) fn h264_decoder(
+    ctx: &PipelineCtx,
+    video_decoders: &Mp4InputVideoDecoders,
+) -> VideoDecoderOptions {
+    video_decoders.h264.unwrap_or({
+        if ctx.graphics_context.has_vulkan_decoder_support() {
+            VideoDecoderOptions::VulkanH264
+        } else {
+            VideoDecoderOptions::FfmpegH264
+        }
+    })
+}
+
+pub(super) fn spawn_video_decoder(
+    ctx: &Arc<PipelineCtx>,
+    input_ref: &Ref<InputId>,
+    track: &Track<File>,
+    h264_decoder: VideoDecoderOptions,
+    frame_sender: Sender<PipelineEvent<Frame>>,
+) -> Result<DecoderThreadHandle, InputInitError> {
+    let vulkan_supported = ctx.graphics_context.has_vulkan_decoder_support();
+    let handle = match (track.decoder_options(), h264_decoder) {
+        (DecoderOptions::H264(h264_config), VideoDecoderOptions::FfmpegH264) => {
+            VideoDecoderThread::<ffmpeg_h264::FfmpegH264Decoder, _>::spawn(
+                input_ref.clone(),
+                VideoDecoderThreadOptions {
+                    ctx: ctx.clone(),
+                    transformer: Some(H264AvccToAnnexB::new(h264_config.clone())),
+                    frame_sender,
+                    input_buffer_size: 5,
+                    replay_recorder: Some(ReplayRecorder::new(&ctx, &input_ref)),
+                },
+            )?
+        }
+        (DecoderOptions::H264(h264_config), VideoDecoderOptions::VulkanH264) => {
+            if !vulkan_supported {
+                return Err(InputInitError::DecoderError(
+                    DecoderInitError::VulkanContextRequiredForVulkanDecoder,
+                ));
+            }
+            VideoDecoderThread::<vulkan_h264::VulkanH264Decoder, _>::spawn(
+                input_ref.clone(),
+                VideoDecoderThreadOptions {
+                    ctx: ctx.clone(),
+                    transformer: Some(H264AvccToAnnexB::new(h264_config.clone())),
+                    frame_sender,
+                    input_buffer_size: 5,
+                    replay_recorder: Some(ReplayRecorder::new(&ctx, &input_ref)),
+                },
+            )?
+        }
+        _ => {
+            return Err(Mp4InputError::Unknown("Non H264 decoder options returned.").into());
+        }
+    };
+    Ok(handle)
+}
+
+pub(super) fn spawn_audio_decoder(
+    ctx: &Arc<PipelineCtx>,
+    input_ref: &Ref<InputId>,
+    track: &Track<File>,
+    samples_sender: Sender<PipelineEvent<InputAudioSamples>>,
+) -> Result<DecoderThreadHandle, InputInitError> {
+    let handle = match track.decoder_options() {
+        DecoderOptions::Aac(data) => AudioDecoderThread::<fdk_aac::FdkAacDecoder>::spawn(
+            input_ref.clone(),
+            AudioDecoderThreadOptions {
+                ctx: ctx.clone(),
+                decoder_options: FdkAacDecoderOptions {
+                    asc: Some(data.clone()),
+                },
+                samples_sender,
+                input_buffer_size: 5,
+                force_resampling: false,
+                replay_recorder: Some(ReplayRecorder::new(&ctx, &input_ref)),
+            },
+        )?,
+        _ => {
+            return Err(Mp4InputError::Unknown("Non AAC decoder options returned.").into());
+        }
+    };
+    Ok(handle)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn start_thread_with_loop(
     ctx: Arc<PipelineCtx>,
@@ -449,9 +476,9 @@ impl Drop for Mp4Input {
     }
 }
 
-struct SourceFile {
-    path: Arc<Path>,
-    remove_on_drop: bool,
+pub(super) struct SourceFile {
+    pub path: Arc<Path>,
+    pub remove_on_drop: bool,
 }
 
 impl Drop for SourceFile {
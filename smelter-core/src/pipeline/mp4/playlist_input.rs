@@ -0,0 +1,512 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    fs::File,
+    sync::{
+        Arc, Condvar, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, Sender, bounded, unbounded};
+use smelter_render::error::ErrorStack;
+use tracing::{Level, debug, error, span, trace};
+
+use crate::{
+    event::Event,
+    pipeline::{
+        input::Input,
+        mp4::{
+            mp4_input::{
+                Mp4Input, SourceFile, h264_decoder, spawn_audio_decoder, spawn_video_decoder,
+            },
+            reader::{Mp4FileReader, Track},
+        },
+        utils::input_buffer::InputBuffer,
+    },
+    queue::QueueDataReceiver,
+};
+
+use crate::prelude::*;
+
+/// Input that plays MP4 files one after another. Timestamps of consecutive items
+/// are continuous, so there is no gap between them. New items can be added and
+/// upcoming items removed while the playlist is playing.
+///
+/// If the playlist runs out of items the input stalls until new items are added.
+/// The next item is downloaded while the current one is playing, and item events
+/// are emitted when the queue reaches the item PTS, not when the item is read.
+pub struct PlaylistInput {
+    state: Arc<PlaylistState>,
+}
+
+struct PlaylistState {
+    items: Mutex<VecDeque<PlaylistItem>>,
+    items_added: Condvar,
+    should_close: AtomicBool,
+}
+
+impl PlaylistInput {
+    pub fn new_input(
+        ctx: Arc<PipelineCtx>,
+        input_ref: Ref<InputId>,
+        options: PlaylistInputOptions,
+    ) -> Result<(Input, InputInitInfo, QueueDataReceiver), InputInitError> {
+        let state = Arc::new(PlaylistState {
+            items: Mutex::new(VecDeque::new()),
+            items_added: Condvar::new(),
+            should_close: AtomicBool::new(false),
+        });
+        let input = Self {
+            state: state.clone(),
+        };
+        let update = PlaylistUpdate {
+            append: options.items,
+            ..Default::default()
+        };
+        if let Err(PlaylistUpdateError::DuplicateItem(item_id)) = input.update(update) {
+            return Err(InputInitError::DuplicatePlaylistItem(item_id));
+        }
+
+        // Items might not have both tracks, but the input has to declare them upfront.
+        let (video_sender, video_receiver) = bounded(10);
+        let (audio_sender, audio_receiver) = bounded(10);
+
+        let reader = PlaylistReader {
+            buffer: InputBuffer::new(&ctx, options.buffer),
+            h264_decoder: h264_decoder(&ctx, &options.video_decoders),
+            events: spawn_event_scheduler(ctx.clone(), state.clone()),
+            ctx,
+            input_ref,
+            state,
+            video_sender,
+            audio_sender,
+        };
+        std::thread::Builder::new()
+            .name("playlist reader".to_string())
+            .spawn(move || reader.run())
+            .unwrap();
+
+        Ok((
+            Input::Playlist(input),
+            InputInitInfo::Other,
+            QueueDataReceiver {
+                video: Some(video_receiver),
+                audio: Some(audio_receiver),
+            },
+        ))
+    }
+
+    pub fn update(&self, update: PlaylistUpdate) -> Result<(), PlaylistUpdateError> {
+        let mut items = self.state.items.lock().unwrap();
+        let mut item_ids: HashSet<_> = items.iter().map(|item| item.item_id.clone()).collect();
+        for item_id in &update.remove {
+            if !item_ids.remove(item_id) {
+                return Err(PlaylistUpdateError::ItemNotQueued(item_id.clone()));
+            }
+        }
+        for item in &update.append {
+            if !item_ids.insert(item.item_id.clone()) {
+                return Err(PlaylistUpdateError::DuplicateItem(item.item_id.clone()));
+            }
+        }
+
+        items.retain(|item| !update.remove.contains(&item.item_id));
+        if !update.append.is_empty() {
+            items.extend(update.append);
+            self.state.items_added.notify_all();
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PlaylistInput {
+    fn drop(&mut self) {
+        let _items = self.state.items.lock().unwrap();
+        self.state.should_close.store(true, Ordering::Relaxed);
+        self.state.items_added.notify_all();
+    }
+}
+
+impl PlaylistState {
+    /// Blocks until there is an item to play. Returns `None` if the input was closed.
+    fn next_item(&self) -> Option<PlaylistItem> {
+        let mut items = self.items.lock().unwrap();
+        loop {
+            if self.should_close.load(Ordering::Relaxed) {
+                return None;
+            }
+            if let Some(item) = items.pop_front() {
+                return Some(item);
+            }
+            items = self.items_added.wait(items).unwrap();
+        }
+    }
+
+    /// Returns the next item if it has to be downloaded before it can be played.
+    fn next_url_item(&self) -> Option<(Arc<str>, Arc<str>)> {
+        let items = self.items.lock().unwrap();
+        match items.front() {
+            Some(PlaylistItem {
+                item_id,
+                source: Mp4InputSource::Url(url),
+            }) => Some((item_id.clone(), url.clone())),
+            _ => None,
+        }
+    }
+
+    /// Blocks until the deadline. Returns `false` if the input was closed earlier.
+    fn wait_until(&self, deadline: Instant) -> bool {
+        let mut items = self.items.lock().unwrap();
+        loop {
+            if self.should_close.load(Ordering::Relaxed) {
+                return false;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            items = self
+                .items_added
+                .wait_timeout(items, deadline - now)
+                .unwrap()
+                .0;
+        }
+    }
+}
+
+/// Download of the next URL item, started while the current item is playing.
+struct PrefetchedItem {
+    item_id: Arc<str>,
+    url: Arc<str>,
+    handle: JoinHandle<Result<Arc<SourceFile>, Mp4InputError>>,
+}
+
+impl PrefetchedItem {
+    fn spawn(ctx: &Arc<PipelineCtx>, item_id: Arc<str>, url: Arc<str>) -> Self {
+        let (ctx, download_url) = (ctx.clone(), url.clone());
+        let handle = std::thread::Builder::new()
+            .name("playlist prefetch".to_string())
+            .spawn(move || Mp4Input::download_remote_file(&ctx, &download_url))
+            .unwrap();
+        Self {
+            item_id,
+            url,
+            handle,
+        }
+    }
+
+    fn wait(self) -> Result<Arc<SourceFile>, Mp4InputError> {
+        self.handle.join().unwrap()
+    }
+}
+
+/// Emits events in the order they were sent, each one when the queue reaches its PTS.
+/// Pending events are dropped when the input is closed.
+fn spawn_event_scheduler(
+    ctx: Arc<PipelineCtx>,
+    state: Arc<PlaylistState>,
+) -> Sender<(Duration, Event)> {
+    let (sender, receiver) = unbounded::<(Duration, Event)>();
+    std::thread::Builder::new()
+        .name("playlist events".to_string())
+        .spawn(move || {
+            for (pts, event) in receiver {
+                if !state.wait_until(ctx.queue_sync_point + pts) {
+                    return;
+                }
+                ctx.event_emitter.emit(event);
+            }
+        })
+        .unwrap();
+    sender
+}
+
+struct PlaylistReader {
+    ctx: Arc<PipelineCtx>,
+    input_ref: Ref<InputId>,
+    state: Arc<PlaylistState>,
+    buffer: InputBuffer,
+    h264_decoder: VideoDecoderOptions,
+    events: Sender<(Duration, Event)>,
+    video_sender: Sender<PipelineEvent<Frame>>,
+    audio_sender: Sender<PipelineEvent<InputAudioSamples>>,
+}
+
+impl PlaylistReader {
+    fn run(self) {
+        let _span = span!(
+            Level::INFO,
+            "Playlist",
+            input_id = self.input_ref.to_string()
+        )
+        .entered();
+        let input_id = self.input_ref.id().clone();
+        let mut prefetched: Option<PrefetchedItem> = None;
+        let mut offset = self.ctx.queue_sync_point.elapsed();
+        while let Some(item) = self.state.next_item() {
+            // If the playlist was empty for some time, do not schedule the next item in the past.
+            offset = Duration::max(offset, self.ctx.queue_sync_point.elapsed());
+
+            debug!(item_id = %item.item_id, "Starting playlist item");
+            let source = match (prefetched.take(), &item.source) {
+                (Some(prefetched), Mp4InputSource::Url(url))
+                    if prefetched.item_id == item.item_id && prefetched.url == *url =>
+                {
+                    prefetched.wait()
+                }
+                (_, Mp4InputSource::Url(url)) => Mp4Input::download_remote_file(&self.ctx, url),
+                (_, Mp4InputSource::File(path)) => Ok(Arc::new(SourceFile {
+                    path: path.clone(),
+                    remove_on_drop: false,
+                })),
+            };
+            prefetched = self
+                .state
+                .next_url_item()
+                .map(|(item_id, url)| PrefetchedItem::spawn(&self.ctx, item_id, url));
+
+            let result = source
+                .map_err(InputInitError::from)
+                .and_then(|source| self.play_item(&item.item_id, &source, offset));
+            match result {
+                Ok(end_pts) => offset = end_pts,
+                Err(err) => {
+                    let err = ErrorStack::new(&err).into_string();
+                    error!(item_id = %item.item_id, "Failed to play playlist item: {err}");
+                    self.schedule_event(
+                        offset,
+                        Event::PlaylistItemError(input_id.clone(), item.item_id.clone(), err),
+                    );
+                }
+            }
+            self.schedule_event(
+                offset,
+                Event::PlaylistItemEnd(input_id.clone(), item.item_id.clone()),
+            );
+        }
+    }
+
+    /// Schedules the event for the moment the queue reaches `pts` of this input.
+    fn schedule_event(&self, pts: Duration, event: Event) {
+        if self.events.send((pts + self.buffer.size(), event)).is_err() {
+            debug!("Failed to schedule playlist event. Channel closed.");
+        }
+    }
+
+    /// Decodes the whole item, returns PTS where the next item should start.
+    fn play_item(
+        &self,
+        item_id: &Arc<str>,
+        source: &SourceFile,
+        offset: Duration,
+    ) -> Result<Duration, InputInitError> {
+        let video = Mp4FileReader::from_path(&source.path)?.find_h264_track();
+        let audio = Mp4FileReader::from_path(&source.path)?.find_aac_track();
+        if video.is_none() && audio.is_none() {
+            return Err(Mp4InputError::NoTrack.into());
+        }
+
+        // Every item has its own decoders, because codec parameters can differ between files.
+        let video = match video {
+            Some(track) => {
+                let (sender, receiver) = bounded(10);
+                let handle = spawn_video_decoder(
+                    &self.ctx,
+                    &self.input_ref,
+                    &track,
+                    self.h264_decoder,
+                    sender,
+                )?;
+                Some((track, handle.chunk_sender, receiver))
+            }
+            None => None,
+        };
+        let audio = match audio {
+            Some(track) => {
+                let (sender, receiver) = bounded(10);
+                let handle = spawn_audio_decoder(&self.ctx, &self.input_ref, &track, sender)?;
+                Some((track, handle.chunk_sender, receiver))
+            }
+            None => None,
+        };
+
+        self.schedule_event(
+            offset,
+            Event::PlaylistItemStart(self.input_ref.id().clone(), item_id.clone()),
+        );
+        let end_pts = AtomicU64::new(offset.as_nanos() as u64);
+        std::thread::scope(|scope| {
+            let end_pts = &end_pts;
+            if let Some((mut track, chunk_sender, frame_receiver)) = video {
+                scope.spawn(move || forward_until_eos(frame_receiver, &self.video_sender));
+                scope.spawn(move || self.send_chunks(&mut track, chunk_sender, offset, end_pts));
+            }
+            if let Some((mut track, chunk_sender, samples_receiver)) = audio {
+                scope.spawn(move || forward_until_eos(samples_receiver, &self.audio_sender));
+                scope.spawn(move || self.send_chunks(&mut track, chunk_sender, offset, end_pts));
+            }
+        });
+
+        Ok(Duration::from_nanos(end_pts.load(Ordering::Relaxed)))
+    }
+
+    fn send_chunks(
+        &self,
+        track: &mut Track<File>,
+        chunk_sender: Sender<PipelineEvent<EncodedInputChunk>>,
+        offset: Duration,
+        end_pts: &AtomicU64,
+    ) {
+        for (mut chunk, duration) in track.chunks() {
+            chunk.pts += offset;
+            chunk.dts = chunk.dts.map(|dts| dts + offset);
+            end_pts.fetch_max((chunk.pts + duration).as_nanos() as u64, Ordering::Relaxed);
+
+            // add buffer after recording last sample
+            self.buffer.recalculate_buffer(chunk.pts);
+            chunk.pts += self.buffer.size();
+
+            trace!(pts=?chunk.pts, "Playlist reader produced a chunk.");
+            if chunk_sender.send(PipelineEvent::Data(chunk)).is_err() {
+                debug!("Failed to send a chunk. Channel closed.");
+                break;
+            }
+            if self.state.should_close.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+        // Flush the decoder, EOS itself is not forwarded to the queue.
+        if chunk_sender.send(PipelineEvent::EOS).is_err() {
+            debug!("Failed to send EOS to the decoder. Channel closed.");
+        }
+    }
+}
+
+/// Forwards decoded data of a single item to the input channel. EOS ends only
+/// the item, so it is not forwarded.
+fn forward_until_eos<T>(receiver: Receiver<PipelineEvent<T>>, sender: &Sender<PipelineEvent<T>>) {
+    for event in receiver {
+        match event {
+            PipelineEvent::Data(data) => {
+                if sender.send(PipelineEvent::Data(data)).is_err() {
+                    debug!("Failed to send decoded data. Channel closed.");
+                    return;
+                }
+            }
+            PipelineEvent::EOS => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn item(item_id: &str, source: Mp4InputSource) -> PlaylistItem {
+        PlaylistItem {
+            item_id: item_id.into(),
+            source,
+        }
+    }
+
+    fn url_item(item_id: &str) -> PlaylistItem {
+        item(
+            item_id,
+            Mp4InputSource::Url("http://example.com/a.mp4".into()),
+        )
+    }
+
+    fn file_item(item_id: &str) -> PlaylistItem {
+        item(item_id, Mp4InputSource::File(Arc::from(Path::new("a.mp4"))))
+    }
+
+    fn playlist(items: Vec<PlaylistItem>) -> PlaylistInput {
+        let input = PlaylistInput {
+            state: Arc::new(PlaylistState {
+                items: Mutex::new(VecDeque::new()),
+                items_added: Condvar::new(),
+                should_close: AtomicBool::new(false),
+            }),
+        };
+        input
+            .update(PlaylistUpdate {
+                append: items,
+                ..Default::default()
+            })
+            .unwrap();
+        input
+    }
+
+    fn item_ids(input: &PlaylistInput) -> Vec<String> {
+        let items = input.state.items.lock().unwrap();
+        items.iter().map(|item| item.item_id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_playlist_update() {
+        let input = playlist(vec![file_item("a"), file_item("b")]);
+
+        input
+            .update(PlaylistUpdate {
+                remove: vec!["a".into()],
+                append: vec![file_item("a"), file_item("c")],
+            })
+            .unwrap();
+        assert_eq!(item_ids(&input), ["b", "a", "c"]);
+
+        let err = input
+            .update(PlaylistUpdate {
+                append: vec![file_item("b")],
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(matches!(err, PlaylistUpdateError::DuplicateItem(id) if &*id == "b"));
+
+        // Invalid update is not applied partially.
+        let err = input
+            .update(PlaylistUpdate {
+                remove: vec!["c".into(), "d".into()],
+                append: vec![file_item("e")],
+            })
+            .unwrap_err();
+        assert!(matches!(err, PlaylistUpdateError::ItemNotQueued(id) if &*id == "d"));
+        assert_eq!(item_ids(&input), ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_next_url_item() {
+        let input = playlist(vec![file_item("a"), url_item("b")]);
+        assert_eq!(input.state.next_url_item(), None);
+
+        input.state.next_item().unwrap();
+        let (item_id, _) = input.state.next_url_item().unwrap();
+        assert_eq!(&*item_id, "b");
+
+        input.state.next_item().unwrap();
+        assert_eq!(input.state.next_url_item(), None);
+    }
+
+    #[test]
+    fn test_wait_until() {
+        let input = playlist(vec![]);
+        let state = input.state.clone();
+
+        let start = Instant::now();
+        assert!(state.wait_until(start + Duration::from_millis(20)));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        // Closing the input interrupts waiting for pending events.
+        let waiting_state = state.clone();
+        let handle = std::thread::spawn(move || {
+            waiting_state.wait_until(Instant::now() + Duration::from_secs(60))
+        });
+        std::thread::sleep(Duration::from_millis(20));
+        drop(input);
+        assert!(!handle.join().unwrap());
+        assert_eq!(state.next_item().map(|item| item.item_id), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::{sync::Arc, time::Duration};
+
+use smelter_render::{
+    Frame,
+    video_clip::{MAX_VIDEO_CLIP_SIZE, video_clip_size},
+};
+use tracing::trace;
+
+use crate::pipeline::{
+    decoder::{
+        BytestreamTransformer, EncodedInputEvent, VideoDecoder, VideoDecoderInstance,
+        ffmpeg_h264::FfmpegH264Decoder,
+    },
+    mp4::{
+        mp4_input::{Mp4Input, SourceFile},
+        reader::{DecoderOptions, Mp4FileReader},
+    },
+    utils::H264AvccToAnnexB,
+};
+
+use crate::prelude::*;
+
+pub(crate) struct DecodedVideoClip {
+    pub frames: Vec<Frame>,
+    pub duration: Duration,
+}
+
+/// Decodes the entire H264 track of an MP4 file on the calling thread.
+///
+/// Decoding stops when decoded frames exceed MAX_VIDEO_CLIP_SIZE, the renderer will reject
+/// such clip anyway, so there is no point in keeping more frames in memory.
+pub(crate) fn decode_video_clip(
+    ctx: &Arc<PipelineCtx>,
+    source: Mp4InputSource,
+) -> Result<DecodedVideoClip, VideoClipDecodeError> {
+    let source = match source {
+        Mp4InputSource::Url(url) => Mp4Input::download_remote_file(ctx, &url)?,
+        Mp4InputSource::File(path) => Arc::new(SourceFile {
+            path,
+            remove_on_drop: false,
+        }),
+    };
+
+    let Some(mut track) = Mp4FileReader::from_path(&source.path)?.find_h264_track() else {
+        return Err(Mp4InputError::NoTrack.into());
+    };
+    let DecoderOptions::H264(h264_config) = track.decoder_options() else {
+        return Err(Mp4InputError::Unknown("Non H264 decoder options returned.").into());
+    };
+
+    let mut transformer = H264AvccToAnnexB::new(h264_config.clone());
+    let mut decoder = FfmpegH264Decoder::new(ctx, None)?;
+
+    let mut frames = Vec::new();
+    let mut end_pts = Duration::ZERO;
+    for (mut chunk, duration) in track.chunks() {
+        end_pts = Duration::max(end_pts, chunk.pts + duration);
+        chunk.data = transformer.transform(chunk.data);
+        trace!(pts=?chunk.pts, "Decoding video clip chunk.");
+        frames.extend(decoder.decode(EncodedInputEvent::Chunk(chunk)));
+        if video_clip_size(&frames) > MAX_VIDEO_CLIP_SIZE {
+            return Ok(DecodedVideoClip {
+                frames,
+                duration: end_pts,
+            });
+        }
+    }
+    frames.extend(decoder.flush());
+
+    // Renderer normalizes frame timestamps, so the duration needs to be
+    // relative to the first frame.
+    let start_pts = frames
+        .iter()
+        .map(|frame| frame.pts)
+        .min()
+        .unwrap_or(Duration::ZERO);
+
+    Ok(DecodedVideoClip {
+        frames,
+        duration: end_pts.saturating_sub(start_pts),
+    })
+}
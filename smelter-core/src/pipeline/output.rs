@@ -4,7 +4,7 @@ use std::{
 };
 
 use crossbeam_channel::Sender;
-use smelter_render::OutputFrameFormat;
+use smelter_render::{Framerate, OutputFrameFormat};
 use tracing::{info, warn};
 
 use crate::pipeline::{
@@ -80,6 +80,15 @@ pub(super) enum OutputSender<T> {
     FinishedSender,
 }
 
+/// Output initialized with [`Pipeline::prepare_output`] that is not yet registered.
+pub struct PreparedOutput {
+    output_id: OutputId,
+    output: Box<dyn Output>,
+    video: Option<RegisterOutputVideoOptions>,
+    audio: Option<RegisterOutputAudioOptions>,
+    framerate: Framerate,
+}
+
 pub(super) fn register_pipeline_output<BuildFn, NewOutputResult>(
     pipeline: &Arc<Mutex<Pipeline>>,
     output_id: OutputId,
@@ -87,6 +96,27 @@ pub(super) fn register_pipeline_output<BuildFn, NewOutputResult>(
     audio: Option<RegisterOutputAudioOptions>,
     build_output: BuildFn,
 ) -> Result<NewOutputResult, RegisterOutputError>
+where
+    BuildFn: FnOnce(
+        Arc<PipelineCtx>,
+        Ref<OutputId>,
+    ) -> Result<(Box<dyn Output>, NewOutputResult), OutputInitError>,
+{
+    let (prepared, output_result) =
+        prepare_pipeline_output(pipeline, output_id, video, audio, build_output)?;
+    add_pipeline_output(&mut pipeline.lock().unwrap(), prepared)?;
+    Ok(output_result)
+}
+
+/// Initializes the output (e.g. its encoders and transport) without adding it to
+/// the pipeline, the pipeline is locked only to check the id and to get the context.
+pub(super) fn prepare_pipeline_output<BuildFn, NewOutputResult>(
+    pipeline: &Arc<Mutex<Pipeline>>,
+    output_id: OutputId,
+    video: Option<RegisterOutputVideoOptions>,
+    audio: Option<RegisterOutputAudioOptions>,
+    build_output: BuildFn,
+) -> Result<(PreparedOutput, NewOutputResult), RegisterOutputError>
 where
     BuildFn: FnOnce(
         Arc<PipelineCtx>,
@@ -103,27 +133,64 @@ where
     }
 
     let pipeline_ctx = pipeline.lock().unwrap().ctx.clone();
-    let (output, output_result) = build_output(pipeline_ctx, Ref::new(&output_id))
+    let framerate = video
+        .as_ref()
+        .and_then(|video| video.framerate)
+        .unwrap_or(pipeline_ctx.output_framerate);
+    // Encoders and muxers of this output use the output specific framerate.
+    let output_ctx = Arc::new(PipelineCtx {
+        output_framerate: framerate,
+        ..(*pipeline_ctx).clone()
+    });
+    let (output, output_result) = build_output(output_ctx, Ref::new(&output_id))
         .map_err(|e| RegisterOutputError::OutputError(output_id.clone(), e))?;
 
-    let mut guard = pipeline.lock().unwrap();
+    let prepared = PreparedOutput {
+        output_id,
+        output,
+        video,
+        audio,
+        framerate,
+    };
+    Ok((prepared, output_result))
+}
+
+/// Adds a prepared output to the renderer, the audio mixer and the queue, so it
+/// receives media starting from the next frame.
+pub(super) fn add_pipeline_output(
+    pipeline: &mut Pipeline,
+    prepared: PreparedOutput,
+) -> Result<(), RegisterOutputError> {
+    let PreparedOutput {
+        output_id,
+        output,
+        video,
+        audio,
+        framerate,
+    } = prepared;
 
-    if guard.outputs.contains_key(&output_id) {
+    if pipeline.outputs.contains_key(&output_id) {
         return Err(RegisterOutputError::AlreadyRegistered(output_id));
     }
 
     let output = PipelineOutput {
         output,
         audio_end_condition: audio.as_ref().map(|audio| {
-            PipelineOutputEndConditionState::new_audio(audio.end_condition.clone(), &guard.inputs)
+            PipelineOutputEndConditionState::new_audio(
+                audio.end_condition.clone(),
+                &pipeline.inputs,
+            )
         }),
         video_end_condition: video.as_ref().map(|video| {
-            PipelineOutputEndConditionState::new_video(video.end_condition.clone(), &guard.inputs)
+            PipelineOutputEndConditionState::new_video(
+                video.end_condition.clone(),
+                &pipeline.inputs,
+            )
         }),
     };
 
-    if let (Some(video_opts), Some(video_output)) = (video.clone(), output.output.video()) {
-        let result = guard.renderer.update_scene(
+    if let (Some(video_opts), Some(video_output)) = (video, output.output.video()) {
+        let result = pipeline.renderer.update_scene(
             output_id.clone(),
             video_output.resolution,
             video_output.frame_format,
@@ -131,23 +198,21 @@ where
         );
 
         if let Err(err) = result {
-            guard.renderer.unregister_output(&output_id);
+            pipeline.renderer.unregister_output(&output_id);
             return Err(RegisterOutputError::SceneError(output_id.clone(), err));
         }
+        pipeline.queue.add_output(&output_id, framerate);
     };
 
-    if let Some(audio_opts) = audio.clone() {
-        guard.audio_mixer.register_output(
-            output_id.clone(),
-            audio_opts.initial,
-            audio_opts.mixing_strategy,
-            audio_opts.channels,
-        );
+    if let Some(audio_opts) = audio {
+        pipeline
+            .audio_mixer
+            .register_output(output_id.clone(), audio_opts);
     }
 
-    guard.outputs.insert(output_id.clone(), output);
+    pipeline.outputs.insert(output_id, output);
 
-    Ok(output_result)
+    Ok(())
 }
 
 impl Pipeline {
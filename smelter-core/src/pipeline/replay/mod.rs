@@ -0,0 +1,5 @@
+mod recorder;
+mod replay_input;
+
+pub(crate) use recorder::ReplayRecorder;
+pub use replay_input::ReplayInput;
@@ -0,0 +1,57 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    pipeline::decoder::EncodedInputEvent,
+    queue::{ReplayAudioDecoder, ReplayBuffer},
+};
+
+use crate::prelude::*;
+
+/// Records chunks passed to the decoders of an input in the replay buffer of that
+/// input. Buffer is looked up for every chunk, so recording starts as soon as
+/// the buffer is enabled, without restarting the decoders.
+#[derive(Clone)]
+pub(crate) struct ReplayRecorder {
+    replay_buffers: Arc<Mutex<HashMap<InputId, ReplayBuffer>>>,
+    input_id: InputId,
+}
+
+impl ReplayRecorder {
+    pub fn new(ctx: &PipelineCtx, input_ref: &Ref<InputId>) -> Self {
+        Self {
+            replay_buffers: ctx.replay_buffers.clone(),
+            input_id: input_ref.id().clone(),
+        }
+    }
+
+    pub fn record_video(&self, event: &PipelineEvent<EncodedInputEvent>) {
+        if let PipelineEvent::Data(EncodedInputEvent::Chunk(chunk)) = event
+            && let Some(replay_buffer) = self.replay_buffer()
+        {
+            replay_buffer.push_video_chunk(chunk);
+        }
+    }
+
+    pub fn record_audio(
+        &self,
+        event: &PipelineEvent<EncodedInputEvent>,
+        decoder: &ReplayAudioDecoder,
+    ) {
+        if let PipelineEvent::Data(EncodedInputEvent::Chunk(chunk)) = event
+            && let Some(replay_buffer) = self.replay_buffer()
+        {
+            replay_buffer.push_audio_chunk(chunk, decoder);
+        }
+    }
+
+    fn replay_buffer(&self) -> Option<ReplayBuffer> {
+        self.replay_buffers
+            .lock()
+            .unwrap()
+            .get(&self.input_id)
+            .cloned()
+    }
+}
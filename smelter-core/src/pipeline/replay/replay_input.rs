@@ -0,0 +1,356 @@
+use std::{
+    ops::Range,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use crossbeam_channel::{Receiver, bounded};
+use smelter_render::Frame;
+use tracing::{Level, debug, span};
+
+use crate::{
+    pipeline::{
+        decoder::{
+            DecoderThreadHandle,
+            decoder_thread_audio::{AudioDecoderThread, AudioDecoderThreadOptions},
+            decoder_thread_video::{VideoDecoderThread, VideoDecoderThreadOptions},
+            fdk_aac, ffmpeg_h264, ffmpeg_vp8, ffmpeg_vp9, libopus, vulkan_h264,
+        },
+        input::Input,
+        utils::H264AvccToAnnexB,
+    },
+    queue::{QueueDataReceiver, ReplayAudioDecoder, ReplayAudioTrack, ReplayVideoTrack},
+    thread_utils::InitializableThread,
+};
+
+use crate::prelude::*;
+
+/// Input that plays a range of media recorded in the replay buffer of another input.
+/// Encoded chunks are copied from the buffer when the input is registered, so the replay
+/// is not affected by media dropped from the buffer later. Chunks are decoded again
+/// and decoded media is retimed to start when the input is registered.
+pub struct ReplayInput {
+    should_close: Arc<AtomicBool>,
+}
+
+/// Maps public PTS of the replayed range to PTS of the replay.
+#[derive(Debug, Clone, Copy)]
+struct ReplayTiming {
+    offset: Duration,
+    first_pts: Duration,
+    speed: f64,
+}
+
+impl ReplayInput {
+    pub fn new_input(
+        ctx: Arc<PipelineCtx>,
+        input_ref: Ref<InputId>,
+        opts: ReplayInputOptions,
+    ) -> Result<(Input, InputInitInfo, QueueDataReceiver), InputInitError> {
+        let replay_buffer = ctx
+            .replay_buffers
+            .lock()
+            .unwrap()
+            .get(&opts.source_input_id)
+            .cloned()
+            .ok_or_else(|| InputInitError::ReplayBufferNotFound(opts.source_input_id.clone()))?;
+
+        let range = opts.start..opts.end;
+        let video = replay_buffer.video(&range);
+        let audio = replay_buffer.audio(&range);
+
+        let first_video_pts = video.as_ref().map(|track| track.chunks[0].pts);
+        let first_audio_pts = audio.as_ref().map(|track| track.chunks[0].pts);
+        let first_pts = match (first_video_pts, first_audio_pts) {
+            (Some(video_pts), Some(audio_pts)) => video_pts.min(audio_pts),
+            (Some(pts), None) | (None, Some(pts)) => pts,
+            (None, None) => return Err(InputInitError::EmptyReplayRange(opts.source_input_id)),
+        };
+
+        // Replay starts right away, buffer gives the queue time to receive first frames.
+        let timing = ReplayTiming {
+            offset: ctx.queue_sync_point.elapsed() + ctx.default_buffer_duration,
+            first_pts: first_pts.max(range.start),
+            speed: opts.speed,
+        };
+
+        let should_close = Arc::new(AtomicBool::new(false));
+        let frame_receiver = video
+            .map(|track| spawn_video_replay(&ctx, &input_ref, track, &range, timing, &should_close))
+            .transpose()?;
+        let samples_receiver = audio
+            .map(|track| spawn_audio_replay(&ctx, &input_ref, track, &range, timing, &should_close))
+            .transpose()?;
+
+        Ok((
+            Input::Replay(Self { should_close }),
+            InputInitInfo::Other,
+            QueueDataReceiver {
+                video: frame_receiver,
+                audio: samples_receiver,
+            },
+        ))
+    }
+}
+
+impl Drop for ReplayInput {
+    fn drop(&mut self) {
+        self.should_close.store(true, Ordering::Relaxed);
+    }
+}
+
+impl ReplayTiming {
+    fn pts(&self, pts: Duration) -> Duration {
+        let elapsed = pts.saturating_sub(self.first_pts).as_secs_f64() / self.speed;
+        let elapsed = Duration::try_from_secs_f64(elapsed).unwrap_or(Duration::MAX);
+        self.offset.saturating_add(elapsed)
+    }
+}
+
+fn spawn_video_replay(
+    ctx: &Arc<PipelineCtx>,
+    input_ref: &Ref<InputId>,
+    track: ReplayVideoTrack,
+    range: &Range<Duration>,
+    timing: ReplayTiming,
+    should_close: &Arc<AtomicBool>,
+) -> Result<Receiver<PipelineEvent<Frame>>, InputInitError> {
+    let (decoded_sender, decoded_receiver) = bounded(5);
+    let options = VideoDecoderThreadOptions {
+        ctx: ctx.clone(),
+        transformer: None::<H264AvccToAnnexB>,
+        frame_sender: decoded_sender,
+        input_buffer_size: 5,
+        replay_recorder: None,
+    };
+    let vulkan_supported = ctx.graphics_context.has_vulkan_decoder_support();
+    let handle = match track.codec {
+        VideoCodec::H264 if vulkan_supported => VideoDecoderThread::<
+            vulkan_h264::VulkanH264Decoder,
+            _,
+        >::spawn(input_ref.clone(), options)?,
+        VideoCodec::H264 => VideoDecoderThread::<ffmpeg_h264::FfmpegH264Decoder, _>::spawn(
+            input_ref.clone(),
+            options,
+        )?,
+        VideoCodec::Vp8 => VideoDecoderThread::<ffmpeg_vp8::FfmpegVp8Decoder, _>::spawn(
+            input_ref.clone(),
+            options,
+        )?,
+        VideoCodec::Vp9 => VideoDecoderThread::<ffmpeg_vp9::FfmpegVp9Decoder, _>::spawn(
+            input_ref.clone(),
+            options,
+        )?,
+    };
+    spawn_chunk_sender(input_ref, "video", track.chunks, handle, should_close);
+
+    let range = range.clone();
+    let frames = decoded_receiver
+        .into_iter()
+        .filter_map(move |event| match event {
+            PipelineEvent::Data(frame) if range.contains(&frame.pts) => {
+                Some(PipelineEvent::Data(Frame {
+                    pts: timing.pts(frame.pts),
+                    ..frame
+                }))
+            }
+            PipelineEvent::Data(_) => None,
+            PipelineEvent::EOS => Some(PipelineEvent::EOS),
+        });
+    Ok(spawn_forwarder(input_ref, "video", frames, should_close))
+}
+
+fn spawn_audio_replay(
+    ctx: &Arc<PipelineCtx>,
+    input_ref: &Ref<InputId>,
+    track: ReplayAudioTrack,
+    range: &Range<Duration>,
+    timing: ReplayTiming,
+    should_close: &Arc<AtomicBool>,
+) -> Result<Receiver<PipelineEvent<InputAudioSamples>>, InputInitError> {
+    let (decoded_sender, decoded_receiver) = bounded(5);
+    let handle = match track.decoder {
+        ReplayAudioDecoder::Aac(decoder_options) => {
+            AudioDecoderThread::<fdk_aac::FdkAacDecoder>::spawn(
+                input_ref.clone(),
+                AudioDecoderThreadOptions {
+                    ctx: ctx.clone(),
+                    decoder_options,
+                    samples_sender: decoded_sender,
+                    input_buffer_size: 5,
+                    force_resampling: false,
+                    replay_recorder: None,
+                },
+            )?
+        }
+        ReplayAudioDecoder::Opus => AudioDecoderThread::<libopus::OpusDecoder>::spawn(
+            input_ref.clone(),
+            AudioDecoderThreadOptions {
+                ctx: ctx.clone(),
+                decoder_options: (),
+                samples_sender: decoded_sender,
+                input_buffer_size: 5,
+                force_resampling: false,
+                replay_recorder: None,
+            },
+        )?,
+    };
+    spawn_chunk_sender(input_ref, "audio", track.chunks, handle, should_close);
+
+    let range = range.clone();
+    let samples = decoded_receiver
+        .into_iter()
+        .filter_map(move |event| match event {
+            PipelineEvent::Data(batch) if range.contains(&batch.start_pts) => {
+                // Slowed down audio is played at a lower pitch.
+                let samples = match timing.speed == 1.0 {
+                    true => batch.samples,
+                    false => Arc::new(stretch_samples(&batch.samples, timing.speed)),
+                };
+                Some(PipelineEvent::Data(InputAudioSamples {
+                    samples,
+                    start_pts: timing.pts(batch.start_pts),
+                    end_pts: timing.pts(batch.end_pts),
+                }))
+            }
+            PipelineEvent::Data(_) => None,
+            PipelineEvent::EOS => Some(PipelineEvent::EOS),
+        });
+    Ok(spawn_forwarder(input_ref, "audio", samples, should_close))
+}
+
+/// Resamples the batch with linear interpolation, so it is played `1 / speed` times longer.
+fn stretch_samples(samples: &[(f64, f64)], speed: f64) -> Vec<(f64, f64)> {
+    let Some(last) = samples.len().checked_sub(1) else {
+        return Vec::new();
+    };
+    let stretched_len = (samples.len() as f64 / speed).round() as usize;
+    (0..stretched_len)
+        .map(|index| {
+            let position = index as f64 * speed;
+            let before = (position.floor() as usize).min(last);
+            let after = (before + 1).min(last);
+            let ratio = position - before as f64;
+            let (l1, r1) = samples[before];
+            let (l2, r2) = samples[after];
+            (l1 + (l2 - l1) * ratio, r1 + (r2 - r1) * ratio)
+        })
+        .collect()
+}
+
+/// Sends recorded chunks to the decoder followed by EOS. Channel is bounded, so
+/// the thread sends chunks only as fast as the decoder consumes them.
+fn spawn_chunk_sender(
+    input_ref: &Ref<InputId>,
+    track: &'static str,
+    chunks: Vec<EncodedInputChunk>,
+    handle: DecoderThreadHandle,
+    should_close: &Arc<AtomicBool>,
+) {
+    let input_ref = input_ref.clone();
+    let should_close = should_close.clone();
+    std::thread::Builder::new()
+        .name(format!("replay {track} chunk sender"))
+        .spawn(move || {
+            let _span = span!(
+                Level::INFO,
+                "Replay chunk sender",
+                input_id = input_ref.to_string(),
+                track
+            )
+            .entered();
+            for chunk in chunks {
+                if should_close.load(Ordering::Relaxed) {
+                    return;
+                }
+                if handle
+                    .chunk_sender
+                    .send(PipelineEvent::Data(chunk))
+                    .is_err()
+                {
+                    debug!("Failed to send replayed chunk. Channel closed.");
+                    return;
+                }
+            }
+            if handle.chunk_sender.send(PipelineEvent::EOS).is_err() {
+                debug!("Failed to send EOS. Channel closed.");
+            }
+        })
+        .unwrap();
+}
+
+/// Sends retimed media from the decoder to the queue.
+fn spawn_forwarder<T: Send + 'static>(
+    input_ref: &Ref<InputId>,
+    track: &'static str,
+    events: impl Iterator<Item = PipelineEvent<T>> + Send + 'static,
+    should_close: &Arc<AtomicBool>,
+) -> Receiver<PipelineEvent<T>> {
+    let (sender, receiver) = bounded(10);
+    let input_ref = input_ref.clone();
+    let should_close = should_close.clone();
+    std::thread::Builder::new()
+        .name(format!("replay {track} forwarder"))
+        .spawn(move || {
+            let _span = span!(
+                Level::INFO,
+                "Replay forwarder",
+                input_id = input_ref.to_string(),
+                track
+            )
+            .entered();
+            for event in events {
+                if should_close.load(Ordering::Relaxed) {
+                    return;
+                }
+                if sender.send(event).is_err() {
+                    debug!("Failed to send replayed media. Channel closed.");
+                    return;
+                }
+            }
+        })
+        .unwrap();
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stretch_samples() {
+        let samples = [(0.0, 0.0), (1.0, -1.0), (0.0, 0.0), (-1.0, 1.0)];
+        assert_eq!(stretch_samples(&samples, 1.0), samples.to_vec());
+        assert_eq!(
+            stretch_samples(&samples, 0.5),
+            vec![
+                (0.0, 0.0),
+                (0.5, -0.5),
+                (1.0, -1.0),
+                (0.5, -0.5),
+                (0.0, 0.0),
+                (-0.5, 0.5),
+                (-1.0, 1.0),
+                (-1.0, 1.0),
+            ]
+        );
+        assert_eq!(stretch_samples(&samples, 0.25).len(), 16);
+        assert!(stretch_samples(&[], 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_replay_timing() {
+        let timing = ReplayTiming {
+            offset: Duration::from_secs(10),
+            first_pts: Duration::from_secs(2),
+            speed: 0.5,
+        };
+        assert_eq!(timing.pts(Duration::from_secs(2)), Duration::from_secs(10));
+        assert_eq!(timing.pts(Duration::from_secs(3)), Duration::from_secs(12));
+        // frames decoded from a keyframe before the range are dropped before retiming
+        assert_eq!(timing.pts(Duration::from_secs(1)), Duration::from_secs(10));
+    }
+}
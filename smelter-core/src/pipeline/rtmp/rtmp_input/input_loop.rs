@@ -14,6 +14,7 @@ use crate::{
             decoder_thread_video::{VideoDecoderThread, VideoDecoderThreadOptions},
             fdk_aac, ffmpeg_h264, vulkan_h264,
         },
+        replay::ReplayRecorder,
         rtmp::rtmp_input::{
             StreamState, Track, demux::run_demuxer_loop, ffmpeg_context::FfmpegInputContext,
             ffmpeg_utils::read_extra_data,
@@ -104,6 +105,7 @@ fn setup_audio_track(
             samples_sender: decoder_sender.clone(),
             input_buffer_size: 10,
             force_resampling: true,
+            replay_recorder: Some(ReplayRecorder::new(&ctx, &input_ref)),
         },
     );
 
@@ -156,6 +158,7 @@ fn setup_video_track(
         transformer: h264_config.map(H264AvccToAnnexB::new),
         frame_sender: decoder_sender.clone(),
         input_buffer_size: 10,
+        replay_recorder: Some(ReplayRecorder::new(&ctx, &input_ref)),
     };
 
     let vulkan_supported = ctx.graphics_context.has_vulkan_decoder_support();
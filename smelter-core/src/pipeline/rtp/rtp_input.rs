@@ -21,6 +21,7 @@ use crate::{
             ffmpeg_vp9::FfmpegVp9Decoder, libopus::OpusDecoder, vulkan_h264::VulkanH264Decoder,
         },
         input::Input,
+        replay::ReplayRecorder,
         rtp::{
             RtpJitterBuffer, RtpJitterBufferInitOptions,
             depayloader::DepayloaderOptions,
@@ -136,15 +137,30 @@ impl RtpInput {
         let handle = match options {
             VideoDecoderOptions::FfmpegH264 => RtpVideoThread::<FfmpegH264Decoder>::spawn(
                 input_ref.clone(),
-                (ctx.clone(), DepayloaderOptions::H264, sender),
+                (
+                    ctx.clone(),
+                    DepayloaderOptions::H264,
+                    sender,
+                    ReplayRecorder::new(ctx, input_ref),
+                ),
             )?,
             VideoDecoderOptions::FfmpegVp8 => RtpVideoThread::<FfmpegVp8Decoder>::spawn(
                 input_ref.clone(),
-                (ctx.clone(), DepayloaderOptions::Vp8, sender),
+                (
+                    ctx.clone(),
+                    DepayloaderOptions::Vp8,
+                    sender,
+                    ReplayRecorder::new(ctx, input_ref),
+                ),
             )?,
             VideoDecoderOptions::FfmpegVp9 => RtpVideoThread::<FfmpegVp9Decoder>::spawn(
                 input_ref.clone(),
-                (ctx.clone(), DepayloaderOptions::Vp9, sender),
+                (
+                    ctx.clone(),
+                    DepayloaderOptions::Vp9,
+                    sender,
+                    ReplayRecorder::new(ctx, input_ref),
+                ),
             )?,
             VideoDecoderOptions::VulkanH264 => {
                 if !ctx.graphics_context.has_vulkan_decoder_support() {
@@ -152,7 +168,12 @@ impl RtpInput {
                 }
                 RtpVideoThread::<VulkanH264Decoder>::spawn(
                     input_ref.clone(),
-                    (ctx.clone(), DepayloaderOptions::H264, sender),
+                    (
+                        ctx.clone(),
+                        DepayloaderOptions::H264,
+                        sender,
+                        ReplayRecorder::new(ctx, input_ref),
+                    ),
                 )?
             }
         };
@@ -185,6 +206,7 @@ impl RtpInput {
                     decoder_options: (),
                     depayloader_options: DepayloaderOptions::Opus,
                     decoded_samples_sender: sender,
+                    replay_recorder: ReplayRecorder::new(ctx, input_ref),
                 },
             )?,
             RtpAudioOptions::FdkAac {
@@ -199,6 +221,7 @@ impl RtpInput {
                     decoder_options: FdkAacDecoderOptions { asc: Some(raw_asc) },
                     depayloader_options: DepayloaderOptions::Aac(depayloader_mode, asc),
                     decoded_samples_sender: sender,
+                    replay_recorder: ReplayRecorder::new(ctx, input_ref),
                 },
             )?,
         };
@@ -6,6 +6,7 @@ use tracing::warn;
 use crate::{
     pipeline::{
         decoder::{AudioDecoder, AudioDecoderStream},
+        replay::ReplayRecorder,
         resampler::decoder_resampler::ResampledDecoderStream,
         rtp::{
             RtpInputEvent,
@@ -28,6 +29,7 @@ pub(super) struct RtpAudioThreadOptions<Decoder: AudioDecoder> {
     pub depayloader_options: DepayloaderOptions,
     pub decoded_samples_sender: Sender<PipelineEvent<InputAudioSamples>>,
     pub sample_rate: u32,
+    pub replay_recorder: ReplayRecorder,
 }
 
 pub(super) struct RtpAudioThread<Decoder: AudioDecoder + 'static> {
@@ -49,6 +51,7 @@ impl<Decoder: AudioDecoder + 'static> InitializableThread for RtpAudioThread<Dec
             depayloader_options,
             decoded_samples_sender,
             sample_rate,
+            replay_recorder,
         } = options;
 
         let mixing_sample_rate = ctx.mixing_sample_rate;
@@ -57,11 +60,12 @@ impl<Decoder: AudioDecoder + 'static> InitializableThread for RtpAudioThread<Dec
         let depayloader_stream =
             DepayloaderStream::new(depayloader_options, rtp_packet_receiver.into_iter());
 
-        let decoder_stream = AudioDecoderStream::<Decoder, _>::new(
-            ctx,
-            decoder_options,
-            depayloader_stream.flatten(),
-        )?;
+        let replay_decoder = Decoder::replay_decoder(&decoder_options);
+        let depayloader_stream = depayloader_stream
+            .flatten()
+            .inspect(move |event| replay_recorder.record_audio(event, &replay_decoder));
+        let decoder_stream =
+            AudioDecoderStream::<Decoder, _>::new(ctx, decoder_options, depayloader_stream)?;
 
         let resampled_stream =
             ResampledDecoderStream::new(mixing_sample_rate, decoder_stream.flatten(), false)
@@ -7,6 +7,7 @@ use tracing::warn;
 use crate::{
     pipeline::{
         decoder::{VideoDecoder, VideoDecoderStream},
+        replay::ReplayRecorder,
         rtp::{
             RtpInputEvent,
             depayloader::{DepayloaderOptions, DepayloaderStream},
@@ -32,19 +33,22 @@ impl<Decoder: VideoDecoder> InitializableThread for RtpVideoThread<Decoder> {
         Arc<PipelineCtx>,
         DepayloaderOptions,
         Sender<PipelineEvent<Frame>>,
+        ReplayRecorder,
     );
 
     type SpawnOutput = RtpVideoTrackThreadHandle;
     type SpawnError = DecoderInitError;
 
     fn init(options: Self::InitOptions) -> Result<(Self, Self::SpawnOutput), Self::SpawnError> {
-        let (ctx, depayloader_options, frame_sender) = options;
+        let (ctx, depayloader_options, frame_sender, replay_recorder) = options;
 
         let (rtp_packet_sender, rtp_packet_receiver) = crossbeam_channel::bounded(5);
         let depayloader_stream =
             DepayloaderStream::new(depayloader_options, rtp_packet_receiver.into_iter());
-        let decoder_stream =
-            VideoDecoderStream::<Decoder, _>::new(ctx, depayloader_stream.flatten())?;
+        let depayloader_stream = depayloader_stream
+            .flatten()
+            .inspect(move |event| replay_recorder.record_video(event));
+        let decoder_stream = VideoDecoderStream::<Decoder, _>::new(ctx, depayloader_stream)?;
 
         let state = Self {
             stream: Box::new(decoder_stream.flatten()),
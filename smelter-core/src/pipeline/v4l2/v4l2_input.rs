@@ -4,7 +4,7 @@ use std::{
     time::Duration,
 };
 
-use smelter_render::{Frame, FrameData, Framerate, InputId, NvPlanes, Resolution};
+use smelter_render::{ColorInfo, Frame, FrameData, Framerate, InputId, NvPlanes, Resolution};
 use tracing::{Level, debug, error, info, span, warn};
 
 use crate::{pipeline::input::Input, prelude::*, queue::QueueDataReceiver};
@@ -309,6 +309,7 @@ impl InputState<'_> {
                 pts: self.ctx.queue_sync_point.elapsed() + Duration::from_millis(20),
                 data,
                 resolution: self.config.resolution,
+                color: ColorInfo::from_resolution(self.config.resolution),
             };
 
             if self.sender.send(PipelineEvent::Data(frame)).is_err() {
@@ -10,6 +10,7 @@ use crate::{
             AudioDecoderStream, DynamicVideoDecoderStream, KeyframeRequestSender,
             VideoDecoderMapping, libopus::OpusDecoder,
         },
+        replay::ReplayRecorder,
         resampler::decoder_resampler::ResampledDecoderStream,
         rtp::{
             RtpInputEvent,
@@ -20,6 +21,7 @@ use crate::{
         },
         webrtc::AsyncReceiverIter,
     },
+    queue::ReplayAudioDecoder,
     thread_utils::{InitializableThread, ThreadMetadata},
 };
 
@@ -41,22 +43,30 @@ impl InitializableThread for VideoTrackThread {
         VideoPayloadTypeMapping,
         Sender<PipelineEvent<Frame>>,
         KeyframeRequestSender,
+        ReplayRecorder,
     );
 
     type SpawnOutput = VideoTrackThreadHandle;
     type SpawnError = DecoderInitError;
 
     fn init(options: Self::InitOptions) -> Result<(Self, Self::SpawnOutput), Self::SpawnError> {
-        let (ctx, decoder_mapping, payload_type_mapping, frame_sender, keyframe_request_sender) =
-            options;
+        let (
+            ctx,
+            decoder_mapping,
+            payload_type_mapping,
+            frame_sender,
+            keyframe_request_sender,
+            replay_recorder,
+        ) = options;
         let (rtp_packet_sender, rtp_packet_receiver) = tokio::sync::mpsc::channel(5000);
 
         let packet_stream = AsyncReceiverIter {
             receiver: rtp_packet_receiver,
         };
 
-        let depayloader_stream =
-            DynamicDepayloaderStream::new(payload_type_mapping, packet_stream).flatten();
+        let depayloader_stream = DynamicDepayloaderStream::new(payload_type_mapping, packet_stream)
+            .flatten()
+            .inspect(move |event| replay_recorder.record_video(event));
 
         let decoder_stream = DynamicVideoDecoderStream::new(
             ctx,
@@ -110,13 +120,17 @@ pub(super) struct AudioTrackThread {
 }
 
 impl InitializableThread for AudioTrackThread {
-    type InitOptions = (Arc<PipelineCtx>, Sender<PipelineEvent<InputAudioSamples>>);
+    type InitOptions = (
+        Arc<PipelineCtx>,
+        Sender<PipelineEvent<InputAudioSamples>>,
+        ReplayRecorder,
+    );
 
     type SpawnOutput = AudioTrackThreadHandle;
     type SpawnError = DecoderInitError;
 
     fn init(options: Self::InitOptions) -> Result<(Self, Self::SpawnOutput), Self::SpawnError> {
-        let (ctx, samples_sender) = options;
+        let (ctx, samples_sender, replay_recorder) = options;
 
         let (rtp_packet_sender, rtp_packet_receiver) = tokio::sync::mpsc::channel(5000);
         let output_sample_rate = ctx.mixing_sample_rate;
@@ -125,8 +139,9 @@ impl InitializableThread for AudioTrackThread {
             receiver: rtp_packet_receiver,
         };
 
-        let depayloader_stream =
-            DepayloaderStream::new(DepayloaderOptions::Opus, packet_stream).flatten();
+        let depayloader_stream = DepayloaderStream::new(DepayloaderOptions::Opus, packet_stream)
+            .flatten()
+            .inspect(move |event| replay_recorder.record_audio(event, &ReplayAudioDecoder::Opus));
 
         let decoded_stream =
             AudioDecoderStream::<OpusDecoder, _>::new(ctx, (), depayloader_stream)?.flatten();
@@ -6,6 +6,7 @@ use webrtc::rtp_transceiver::rtp_codec::RTPCodecType;
 use crate::{
     pipeline::{
         decoder::VideoDecoderMapping,
+        replay::ReplayRecorder,
         rtp::{RtpJitterBuffer, depayloader::VideoPayloadTypeMapping},
         webrtc::{
             input_rtp_reader::WebrtcRtpReader,
@@ -67,7 +68,11 @@ async fn process_audio_track(
 
     let handle = AudioTrackThread::spawn(
         "WHEP input audio",
-        (ctx.pipeline_ctx.clone(), samples_sender),
+        (
+            ctx.pipeline_ctx.clone(),
+            samples_sender,
+            ReplayRecorder::new(&ctx.pipeline_ctx, &input_ref),
+        ),
     )?;
 
     let stats_sender = ctx.pipeline_ctx.stats_sender.clone();
@@ -138,6 +143,7 @@ async fn process_video_track(
             payload_type_mapping,
             frame_sender,
             keyframe_request_sender,
+            ReplayRecorder::new(&ctx.pipeline_ctx, &input_ref),
         ),
     )?;
 
@@ -6,6 +6,7 @@ use crate::{
     codecs::VideoDecoderOptions,
     pipeline::{
         decoder::VideoDecoderMapping,
+        replay::ReplayRecorder,
         rtp::{RtpJitterBuffer, depayloader::VideoPayloadTypeMapping},
         webrtc::{
             error::WhipWhepServerError,
@@ -67,7 +68,11 @@ async fn process_audio_track(
 
     let handle = AudioTrackThread::spawn(
         format!("WHIP input audio, input_id: {input_ref}"),
-        (ctx.pipeline_ctx.clone(), samples_sender),
+        (
+            ctx.pipeline_ctx.clone(),
+            samples_sender,
+            ReplayRecorder::new(&ctx.pipeline_ctx, &input_ref),
+        ),
     )?;
 
     let stats_sender = ctx.pipeline_ctx.stats_sender.clone();
@@ -143,6 +148,7 @@ async fn process_video_track(
             payload_type_mapping,
             frame_sender,
             keyframe_request_sender,
+            ReplayRecorder::new(&ctx.pipeline_ctx, &input_ref),
         ),
     )?;
 
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use itertools::Itertools;
+use smelter_render::OutputColorSpace;
 use tracing::warn;
 use webrtc::rtp_transceiver::rtp_codec::RTCRtpCodecParameters;
 
@@ -55,6 +56,7 @@ pub(super) fn resolve_video_preferences(
                         resolution,
                         bitrate: None,
                         pixel_format: OutputPixelFormat::YUV420P,
+                        color_space: OutputColorSpace::default(),
                         raw_options: Vec::new(),
                     }),
                     VideoEncoderOptions::FfmpegVp8(FfmpegVp8EncoderOptions {
@@ -66,6 +68,7 @@ pub(super) fn resolve_video_preferences(
                         VideoEncoderOptions::VulkanH264(VulkanH264EncoderOptions {
                             resolution,
                             bitrate: None,
+                            color_space: OutputColorSpace::default(),
                         })
                     } else {
                         VideoEncoderOptions::FfmpegH264(FfmpegH264EncoderOptions {
@@ -73,6 +76,7 @@ pub(super) fn resolve_video_preferences(
                             resolution,
                             bitrate: None,
                             pixel_format: OutputPixelFormat::YUV420P,
+                            color_space: OutputColorSpace::default(),
                             raw_options: Vec::new(),
                         })
                     },
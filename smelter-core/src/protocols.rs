@@ -1,6 +1,8 @@
 mod channel;
 mod hls;
 mod mp4;
+mod playlist;
+mod replay;
 mod rtmp;
 mod rtp;
 mod v4l2;
@@ -9,6 +11,8 @@ mod webrtc;
 pub use channel::*;
 pub use hls::*;
 pub use mp4::*;
+pub use playlist::*;
+pub use replay::*;
 pub use rtmp::*;
 pub use rtp::*;
 pub use v4l2::*;
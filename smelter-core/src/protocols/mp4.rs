@@ -6,6 +6,7 @@ use std::{
 use crate::{
     InputBufferOptions,
     codecs::{AudioEncoderOptions, VideoDecoderOptions, VideoEncoderOptions},
+    error::DecoderInitError,
 };
 
 #[derive(Debug, Clone)]
@@ -30,6 +31,11 @@ pub enum Mp4InputSource {
     File(Arc<Path>),
 }
 
+#[derive(Debug, Clone)]
+pub struct VideoClipOptions {
+    pub source: Mp4InputSource,
+}
+
 #[derive(Debug, Clone)]
 pub struct Mp4InputVideoDecoders {
     pub h264: Option<VideoDecoderOptions>,
@@ -52,3 +58,12 @@ pub enum Mp4InputError {
     #[error("Unknown error: {0}")]
     Unknown(&'static str),
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum VideoClipDecodeError {
+    #[error(transparent)]
+    Mp4(#[from] Mp4InputError),
+
+    #[error(transparent)]
+    Decoder(#[from] DecoderInitError),
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::{
+    InputBufferOptions,
+    protocols::{Mp4InputSource, Mp4InputVideoDecoders},
+};
+
+#[derive(Debug, Clone)]
+pub struct PlaylistInputOptions {
+    /// Items played in order. Timestamps of consecutive items are continuous.
+    pub items: Vec<PlaylistItem>,
+    pub video_decoders: Mp4InputVideoDecoders,
+    pub buffer: InputBufferOptions,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlaylistItem {
+    /// Identifies the item in events and playlist updates.
+    pub item_id: Arc<str>,
+    pub source: Mp4InputSource,
+}
+
+/// Changes to the upcoming items of a playlist. Items are removed first, then the new
+/// ones are appended. Update is applied only if all changes are valid.
+#[derive(Debug, Clone, Default)]
+pub struct PlaylistUpdate {
+    /// Ids of items that did not start playing yet.
+    pub remove: Vec<Arc<str>>,
+    /// Items added at the end of the playlist.
+    pub append: Vec<PlaylistItem>,
+}
@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// Rolling buffer of encoded media kept for an input, used as a source for replay inputs.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayBufferOptions {
+    /// Duration of the most recent media that is kept.
+    pub duration: Duration,
+    /// Limit of memory used by buffered encoded chunks. When it is exceeded
+    /// the oldest media is dropped, even if it is within `duration`.
+    pub max_memory_bytes: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplayInputOptions {
+    /// Input with a replay buffer enabled.
+    pub source_input_id: InputId,
+    /// Start of the replayed range (PTS relative to the pipeline start).
+    pub start: Duration,
+    /// End of the replayed range (PTS relative to the pipeline start).
+    pub end: Duration,
+    /// Playback speed, values below 1.0 produce slow motion. Slowed down audio
+    /// is played at a lower pitch.
+    pub speed: f64,
+}
@@ -1,10 +1,13 @@
 mod audio_queue;
+mod failover;
+mod media_monitor;
 mod queue_thread;
+mod replay_buffer;
 mod utils;
 mod video_queue;
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::Debug,
     sync::{
         Arc, Mutex,
@@ -14,12 +17,17 @@ use std::{
 };
 
 use crossbeam_channel::{Receiver, Sender, bounded};
-use smelter_render::{Frame, FrameSet, Framerate, InputId};
+use smelter_render::{Frame, FrameSet, Framerate, InputId, OutputId};
 
 use crate::audio_mixer::InputSamplesSet;
 
 use crate::prelude::*;
 
+pub(crate) use media_monitor::{AudioMonitor, VideoMonitor, VideoMonitorThread};
+pub(crate) use replay_buffer::{
+    ReplayAudioDecoder, ReplayAudioTrack, ReplayBuffer, ReplayVideoTrack,
+};
+
 use self::{
     audio_queue::AudioQueue,
     queue_thread::{QueueStartEvent, QueueThread},
@@ -40,6 +48,8 @@ pub struct QueueOptions {
     pub ahead_of_time_processing: bool,
     pub run_late_scheduled_events: bool,
     pub never_drop_output_frames: bool,
+    pub stream_fallback_timeout: Duration,
+    pub media_alarm_threshold: Duration,
 }
 
 impl From<&PipelineOptions> for QueueOptions {
@@ -50,6 +60,8 @@ impl From<&PipelineOptions> for QueueOptions {
             ahead_of_time_processing: opt.ahead_of_time_processing,
             run_late_scheduled_events: opt.run_late_scheduled_events,
             never_drop_output_frames: opt.never_drop_output_frames,
+            stream_fallback_timeout: opt.stream_fallback_timeout,
+            media_alarm_threshold: opt.media_alarm_threshold,
         }
     }
 }
@@ -72,7 +84,11 @@ pub struct Queue {
     video_queue: Mutex<VideoQueue>,
     audio_queue: Mutex<AudioQueue>,
 
+    /// Framerate used when no outputs are registered.
     output_framerate: Framerate,
+    /// Framerates of registered outputs. Queue produces frame sets for the union
+    /// of timestamps needed by all of them.
+    output_framerates: Mutex<HashMap<OutputId, Framerate>>,
 
     /// Duration of queue output samples set.
     audio_chunk_duration: Duration,
@@ -102,6 +118,8 @@ pub(super) struct QueueVideoOutput {
     pub(super) required: bool,
     pub(super) pts: Duration,
     pub(super) frames: HashMap<InputId, PipelineEvent<Frame>>,
+    /// Outputs that should produce a frame for this batch.
+    pub(super) outputs: HashSet<OutputId>,
 }
 
 impl From<QueueVideoOutput> for FrameSet<InputId> {
@@ -168,15 +186,20 @@ impl Queue {
         let queue = Arc::new(Queue {
             video_queue: Mutex::new(VideoQueue::new(
                 sync_point,
-                ctx.event_emitter.clone(),
+                ctx,
                 opts.ahead_of_time_processing,
+                opts.stream_fallback_timeout,
+                opts.media_alarm_threshold,
             )),
             output_framerate: opts.output_framerate,
+            output_framerates: Mutex::new(HashMap::new()),
 
             audio_queue: Mutex::new(AudioQueue::new(
                 sync_point,
-                ctx.event_emitter.clone(),
+                ctx,
                 opts.ahead_of_time_processing,
+                opts.stream_fallback_timeout,
+                opts.media_alarm_threshold,
             )),
             audio_chunk_duration: DEFAULT_AUDIO_CHUNK_DURATION,
 
@@ -235,6 +258,61 @@ impl Queue {
         self.audio_queue.lock().unwrap().remove_input(input_id);
     }
 
+    /// Configures backup inputs that are used in place of `input_id` when it stalls.
+    /// Backups are checked in the order they are specified.
+    pub fn set_input_failover(&self, input_id: &InputId, backups: Vec<InputId>) {
+        self.video_queue
+            .lock()
+            .unwrap()
+            .set_input_failover(input_id, backups.clone());
+        self.audio_queue
+            .lock()
+            .unwrap()
+            .set_input_failover(input_id, backups);
+    }
+
+    pub fn remove_input_failover(&self, input_id: &InputId) {
+        self.video_queue
+            .lock()
+            .unwrap()
+            .remove_input_failover(input_id);
+        self.audio_queue
+            .lock()
+            .unwrap()
+            .remove_input_failover(input_id);
+    }
+
+    pub fn set_audio_delay(&self, input_id: &InputId, audio_delay: AudioDelay) {
+        self.audio_queue
+            .lock()
+            .unwrap()
+            .set_audio_delay(input_id, audio_delay);
+    }
+
+    /// Starts recording media of the input into the replay buffer. Replaces
+    /// a previously set buffer.
+    pub(crate) fn set_replay_buffer(&self, input_id: &InputId, replay_buffer: ReplayBuffer) {
+        self.video_queue
+            .lock()
+            .unwrap()
+            .set_replay_buffer(input_id, replay_buffer.clone());
+        self.audio_queue
+            .lock()
+            .unwrap()
+            .set_replay_buffer(input_id, replay_buffer);
+    }
+
+    pub fn add_output(&self, output_id: &OutputId, framerate: Framerate) {
+        self.output_framerates
+            .lock()
+            .unwrap()
+            .insert(output_id.clone(), framerate);
+    }
+
+    pub fn remove_output(&self, output_id: &OutputId) {
+        self.output_framerates.lock().unwrap().remove(output_id);
+    }
+
     pub(super) fn start(
         self: &Arc<Self>,
         video_sender: Sender<QueueVideoOutput>,
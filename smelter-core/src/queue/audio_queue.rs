@@ -5,8 +5,11 @@ use std::{
 };
 
 use crate::{
-    event::{Event, EventEmitter},
-    queue::{SharedState, utils::EmitEventOnce},
+    event::{Event, EventEmitter, FailoverSource},
+    queue::{
+        SharedState, failover::InputFailover, media_monitor::AudioMonitor,
+        replay_buffer::ReplayBuffer, utils::EmitEventOnce,
+    },
 };
 
 use crate::prelude::*;
@@ -19,21 +22,34 @@ use tracing::debug;
 pub struct AudioQueue {
     sync_point: Instant,
     inputs: HashMap<InputId, AudioQueueInput>,
+    failovers: HashMap<InputId, InputFailover>,
     event_emitter: Arc<EventEmitter>,
+    ctx: Arc<PipelineCtx>,
     ahead_of_time_processing: bool,
+    /// Input is considered stalled if it did not deliver any samples for longer
+    /// than this value.
+    stream_fallback_timeout: Duration,
+    /// Silence is reported if it lasts longer than this value.
+    media_alarm_threshold: Duration,
 }
 
 impl AudioQueue {
     pub fn new(
         sync_point: Instant,
-        event_emitter: Arc<EventEmitter>,
+        ctx: &Arc<PipelineCtx>,
         ahead_of_time_processing: bool,
+        stream_fallback_timeout: Duration,
+        media_alarm_threshold: Duration,
     ) -> Self {
         AudioQueue {
             inputs: HashMap::new(),
-            event_emitter,
+            failovers: HashMap::new(),
+            event_emitter: ctx.event_emitter.clone(),
+            ctx: ctx.clone(),
             sync_point,
             ahead_of_time_processing,
+            stream_fallback_timeout,
+            media_alarm_threshold,
         }
     }
 
@@ -52,10 +68,18 @@ impl AudioQueue {
                 required: opts.required,
 
                 eos_received: false,
+                last_samples_end_pts: None,
                 sync_point: self.sync_point,
                 shared_state,
 
                 offset_from_start: opts.offset,
+                audio_delay: opts.audio_delay,
+                replay_buffer: None,
+                monitor: AudioMonitor::new(
+                    MediaAlarmSource::Input(input_id.clone()),
+                    self.media_alarm_threshold,
+                    &self.ctx,
+                ),
 
                 emit_once_delivered_event: EmitEventOnce::new(
                     Event::AudioInputStreamDelivered(input_id.clone()),
@@ -75,6 +99,39 @@ impl AudioQueue {
 
     pub fn remove_input(&mut self, input_id: &InputId) {
         self.inputs.remove(input_id);
+        self.failovers.remove(input_id);
+    }
+
+    pub fn set_input_failover(&mut self, input_id: &InputId, backups: Vec<InputId>) {
+        // input without this track does not need a replacement
+        if !self.inputs.contains_key(input_id) {
+            return;
+        }
+        let failover = InputFailover::new(
+            input_id.clone(),
+            backups,
+            self.stream_fallback_timeout,
+            Event::AudioInputFailover,
+            &self.event_emitter,
+        );
+        self.failovers.insert(input_id.clone(), failover);
+    }
+
+    pub fn remove_input_failover(&mut self, input_id: &InputId) {
+        self.failovers.remove(input_id);
+    }
+
+    pub fn set_audio_delay(&mut self, input_id: &InputId, audio_delay: AudioDelay) {
+        if let Some(input) = self.inputs.get_mut(input_id) {
+            drop_overlapping_batches(&mut input.queue, &input.audio_delay, &audio_delay);
+            input.audio_delay = audio_delay;
+        }
+    }
+
+    pub fn set_replay_buffer(&mut self, input_id: &InputId, replay_buffer: ReplayBuffer) {
+        if let Some(input) = self.inputs.get_mut(input_id) {
+            input.replay_buffer = Some(replay_buffer);
+        }
     }
 
     pub(super) fn pop_samples_set(
@@ -84,16 +141,21 @@ impl AudioQueue {
     ) -> QueueAudioOutput {
         let (start_pts, end_pts) = range;
         let mut required = false;
-        let samples = self
+        let mut samples = self
             .inputs
             .iter_mut()
             .map(|(input_id, input)| {
                 let audio_event = input.pop_samples(range, queue_start_pts);
                 required = required || audio_event.required;
+                if let PipelineEvent::Data(batches) = &audio_event.event {
+                    input.monitor.process_input(batches, end_pts);
+                }
                 (input_id.clone(), audio_event.event)
             })
             .collect();
 
+        self.apply_failovers(&mut samples, end_pts);
+
         QueueAudioOutput {
             required,
             samples,
@@ -102,6 +164,47 @@ impl AudioQueue {
         }
     }
 
+    /// Replaces samples of stalled inputs with samples from their backups. If no
+    /// backup is available the input is silent. Input that did not deliver any samples
+    /// did not start yet, it is replaced only after the fallback timeout.
+    fn apply_failovers(
+        &mut self,
+        samples: &mut HashMap<InputId, PipelineEvent<Vec<InputAudioSamples>>>,
+        end_pts: Duration,
+    ) {
+        let inputs = &self.inputs;
+        let timeout = self.stream_fallback_timeout;
+        let last_samples_end_pts =
+            |id: &InputId| inputs.get(id).and_then(|input| input.last_samples_end_pts);
+        let is_available =
+            |id: &InputId| last_samples_end_pts(id).is_some_and(|pts| pts + timeout >= end_pts);
+        // Resolve all substitutions before modifying samples, so an input that is
+        // a backup and has failover configured itself is always checked as is.
+        let substitutions: Vec<_> = self
+            .failovers
+            .iter_mut()
+            // EOS has to be delivered to the rest of the pipeline as is
+            .filter(|(input_id, _)| !matches!(samples.get(*input_id), Some(PipelineEvent::EOS)))
+            .filter_map(|(input_id, failover)| {
+                let is_started = last_samples_end_pts(input_id).is_some();
+                match failover.select(end_pts, is_started, &is_available) {
+                    FailoverSource::Primary => None,
+                    FailoverSource::Backup(backup_id) => match samples.get(&backup_id) {
+                        Some(PipelineEvent::Data(backup_samples)) => {
+                            Some((input_id.clone(), backup_samples.clone()))
+                        }
+                        _ => Some((input_id.clone(), vec![])),
+                    },
+                    FailoverSource::Missing => Some((input_id.clone(), vec![])),
+                }
+            })
+            .collect();
+
+        for (input_id, input_samples) in substitutions {
+            samples.insert(input_id, PipelineEvent::Data(input_samples));
+        }
+    }
+
     pub(super) fn should_push_for_pts_range(
         &mut self,
         pts_range: (Duration, Duration),
@@ -157,8 +260,16 @@ struct AudioQueueInput {
     /// Offset of the stream relative to the start. If set to `None`
     /// offset will be resolved automatically on the stream start.
     offset_from_start: Option<Duration>,
+    /// Shift applied to PTS of received batches. Changing it affects only batches
+    /// that were not enqueued yet, queued batches that would overlap them are dropped.
+    audio_delay: AudioDelay,
+    /// Records enqueued media if replay buffer is enabled for this input.
+    replay_buffer: Option<ReplayBuffer>,
+    monitor: AudioMonitor,
 
     eos_received: bool,
+    /// End PTS of the most recent batch returned from the queue.
+    last_samples_end_pts: Option<Duration>,
 
     sync_point: Instant,
     shared_state: SharedState,
@@ -209,7 +320,8 @@ impl AudioQueueInput {
                 required: true,
             };
         }
-        if !popped_samples.is_empty() {
+        if let Some(batch) = popped_samples.last() {
+            self.last_samples_end_pts = Some(batch.end_pts);
             self.emit_once_playing_event.emit();
         }
         AudioEvent {
@@ -281,7 +393,7 @@ impl AudioQueueInput {
             match self.receiver.try_recv()? {
                 PipelineEvent::Data(batch) => {
                     let _ = self.shared_state.get_or_init_first_pts(batch.start_pts);
-                    self.queue.push_back(batch);
+                    self.push_samples(batch.start_pts, batch, queue_start_pts);
                 }
                 PipelineEvent::EOS => self.eos_received = true,
             };
@@ -293,10 +405,11 @@ impl AudioQueueInput {
             match self.receiver.try_recv()? {
                 // pts start from sync point
                 PipelineEvent::Data(mut batch) => {
+                    let input_pts = batch.start_pts;
                     let first_pts = self.shared_state.get_or_init_first_pts(batch.start_pts);
                     batch.start_pts = offset_pts + batch.start_pts - first_pts;
                     batch.end_pts = offset_pts + batch.end_pts - first_pts;
-                    self.queue.push_back(batch);
+                    self.push_samples(input_pts, batch, queue_start_pts);
                 }
                 PipelineEvent::EOS => self.eos_received = true,
             };
@@ -305,9 +418,111 @@ impl AudioQueueInput {
         Ok(())
     }
 
+    /// `input_pts` is the start PTS of the batch before the offset was applied.
+    fn push_samples(
+        &mut self,
+        input_pts: Duration,
+        mut batch: InputAudioSamples,
+        queue_start_pts: Option<Duration>,
+    ) {
+        // Advancing audio drops batches that would start before PTS 0, instead of
+        // squeezing them into a shorter time range.
+        let (Some(start_pts), Some(end_pts)) = (
+            self.audio_delay.apply(batch.start_pts),
+            self.audio_delay.apply(batch.end_pts),
+        ) else {
+            return;
+        };
+        batch.start_pts = start_pts;
+        batch.end_pts = end_pts;
+        let pts = queue_start_pts.and_then(|start| batch.start_pts.checked_sub(start));
+        if let (Some(replay_buffer), Some(pts)) = (&self.replay_buffer, pts) {
+            replay_buffer.set_audio_pts_mapping(input_pts, pts);
+        }
+        self.queue.push_back(batch);
+    }
+
     /// Offset value calculated in form of PTS(relative to sync point)
     fn offset_pts(&self, queue_start_pts: Duration) -> Option<Duration> {
         self.offset_from_start
             .map(|offset| queue_start_pts + offset)
     }
 }
+
+/// Batches received after the audio delay is lowered start earlier than the ones
+/// already queued with the previous delay. Queued batches that end after the point
+/// where new batches will start are dropped, so there is a short gap instead of an
+/// overlap.
+fn drop_overlapping_batches(
+    queue: &mut VecDeque<InputAudioSamples>,
+    old_delay: &AudioDelay,
+    new_delay: &AudioDelay,
+) {
+    let Some(decrease) = old_delay.decrease_to(new_delay) else {
+        return;
+    };
+    let Some(last_batch) = queue.back() else {
+        return;
+    };
+    let next_batch_start_pts = last_batch.end_pts.saturating_sub(decrease);
+    while queue
+        .back()
+        .is_some_and(|batch| batch.end_pts > next_batch_start_pts)
+    {
+        queue.pop_back();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(start_ms: u64, end_ms: u64) -> InputAudioSamples {
+        InputAudioSamples {
+            samples: Arc::new(Vec::new()),
+            start_pts: Duration::from_millis(start_ms),
+            end_pts: Duration::from_millis(end_ms),
+        }
+    }
+
+    fn queued_ranges(queue: &VecDeque<InputAudioSamples>) -> Vec<(u128, u128)> {
+        queue
+            .iter()
+            .map(|batch| (batch.start_pts.as_millis(), batch.end_pts.as_millis()))
+            .collect()
+    }
+
+    #[test]
+    fn test_drop_overlapping_batches() {
+        let queued = || -> VecDeque<InputAudioSamples> {
+            [
+                batch(100, 120),
+                batch(120, 140),
+                batch(140, 160),
+                batch(160, 180),
+            ]
+            .into()
+        };
+        let delay = |ms| AudioDelay::Delay(Duration::from_millis(ms));
+        let advance = |ms| AudioDelay::Advance(Duration::from_millis(ms));
+
+        // lowering the delay by 30ms, new batches start at 150ms
+        let mut queue = queued();
+        drop_overlapping_batches(&mut queue, &delay(100), &delay(70));
+        assert_eq!(queued_ranges(&queue), vec![(100, 120), (120, 140)]);
+
+        // delay lowered from delay to advance, new batches start at 100ms
+        let mut queue = queued();
+        drop_overlapping_batches(&mut queue, &delay(20), &advance(60));
+        assert_eq!(queued_ranges(&queue), vec![]);
+
+        // raising the delay leaves a gap, queued batches are kept
+        let mut queue = queued();
+        drop_overlapping_batches(&mut queue, &advance(20), &delay(20));
+        assert_eq!(queued_ranges(&queue).len(), 4);
+
+        let mut queue = queued();
+        drop_overlapping_batches(&mut queue, &delay(20), &delay(20));
+        assert_eq!(queued_ranges(&queue).len(), 4);
+    }
+}
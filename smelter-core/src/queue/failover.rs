@@ -0,0 +1,163 @@
+use std::{sync::Arc, time::Duration};
+
+use smelter_render::InputId;
+use tracing::debug;
+
+use crate::event::{Event, EventEmitter, FailoverSource};
+
+/// Tracks which source is used in place of an input that has failover configured.
+pub(super) struct InputFailover {
+    input_id: InputId,
+    /// Backup inputs in order of precedence.
+    backups: Vec<InputId>,
+    active: FailoverSource,
+    /// Input that did not deliver any data yet is replaced only after this time.
+    stream_fallback_timeout: Duration,
+    /// PTS of the first selection, i.e. of the first batch after the failover
+    /// was configured.
+    first_pts: Option<Duration>,
+
+    event: fn(InputId, FailoverSource) -> Event,
+    event_emitter: Arc<EventEmitter>,
+}
+
+impl InputFailover {
+    pub fn new(
+        input_id: InputId,
+        backups: Vec<InputId>,
+        stream_fallback_timeout: Duration,
+        event: fn(InputId, FailoverSource) -> Event,
+        event_emitter: &Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            input_id,
+            backups,
+            active: FailoverSource::Primary,
+            stream_fallback_timeout,
+            first_pts: None,
+            event,
+            event_emitter: event_emitter.clone(),
+        }
+    }
+
+    /// Selects the first available source. Primary input always takes precedence,
+    /// backups are checked in the order they were specified. Primary input that did
+    /// not start yet is kept until the fallback timeout passes, counting from the first
+    /// selection. Emits an event when the selected source changes.
+    pub fn select(
+        &mut self,
+        pts: Duration,
+        is_primary_started: bool,
+        is_available: impl Fn(&InputId) -> bool,
+    ) -> FailoverSource {
+        let first_pts = *self.first_pts.get_or_insert(pts);
+        let is_starting = !is_primary_started && first_pts + self.stream_fallback_timeout >= pts;
+        let source = if is_starting || is_available(&self.input_id) {
+            FailoverSource::Primary
+        } else {
+            self.backups
+                .iter()
+                .find(|backup_id| is_available(backup_id))
+                .map(|backup_id| FailoverSource::Backup(backup_id.clone()))
+                .unwrap_or(FailoverSource::Missing)
+        };
+
+        if source != self.active {
+            let event = (self.event)(self.input_id.clone(), source.clone());
+            debug!(?event, "Emitting event");
+            self.event_emitter.emit(event);
+            self.active = source.clone();
+        }
+        source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossbeam_channel::Receiver;
+
+    use super::*;
+
+    fn input_id(id: &str) -> InputId {
+        InputId(id.into())
+    }
+
+    fn emitted_sources(events: &Receiver<Event>) -> Vec<FailoverSource> {
+        events
+            .try_iter()
+            .map(|event| match event {
+                Event::VideoInputFailover(_, source) => source,
+                event => panic!("Unexpected event {event:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select() {
+        let event_emitter = Arc::new(EventEmitter::new());
+        let events = event_emitter.subscribe();
+        let mut failover = InputFailover::new(
+            input_id("input"),
+            vec![input_id("backup_1"), input_id("backup_2")],
+            Duration::from_millis(500),
+            Event::VideoInputFailover,
+            &event_emitter,
+        );
+        let mut select = |available: &[&str]| {
+            failover.select(Duration::ZERO, true, |id| {
+                available.iter().any(|available_id| **available_id == *id.0)
+            })
+        };
+
+        // Primary input takes precedence over available backups.
+        assert_eq!(
+            select(&["input", "backup_1", "backup_2"]),
+            FailoverSource::Primary
+        );
+        assert_eq!(emitted_sources(&events), vec![]);
+
+        // Backups are checked in order.
+        let backup_1 = FailoverSource::Backup(input_id("backup_1"));
+        let backup_2 = FailoverSource::Backup(input_id("backup_2"));
+        assert_eq!(select(&["backup_1", "backup_2"]), backup_1);
+        assert_eq!(select(&["backup_2"]), backup_2);
+        assert_eq!(emitted_sources(&events), vec![backup_1, backup_2]);
+
+        // Event is emitted only when the source changes.
+        assert_eq!(select(&[]), FailoverSource::Missing);
+        assert_eq!(select(&[]), FailoverSource::Missing);
+        assert_eq!(emitted_sources(&events), vec![FailoverSource::Missing]);
+
+        // Switches back as soon as the primary input is available again.
+        assert_eq!(select(&["input"]), FailoverSource::Primary);
+        assert_eq!(emitted_sources(&events), vec![FailoverSource::Primary]);
+    }
+
+    #[test]
+    fn test_select_before_input_start() {
+        let event_emitter = Arc::new(EventEmitter::new());
+        let events = event_emitter.subscribe();
+        let mut failover = InputFailover::new(
+            input_id("input"),
+            vec![input_id("backup")],
+            Duration::from_millis(500),
+            Event::VideoInputFailover,
+            &event_emitter,
+        );
+        let is_available = |id: &InputId| *id.0 == *"backup";
+        let mut select =
+            |pts_ms| failover.select(Duration::from_millis(pts_ms), false, is_available);
+
+        // Input registered at 1000ms that did not deliver anything yet is kept
+        // until the timeout passes.
+        assert_eq!(select(1000), FailoverSource::Primary);
+        assert_eq!(select(1500), FailoverSource::Primary);
+        assert_eq!(emitted_sources(&events), vec![]);
+
+        assert_eq!(select(1501), FailoverSource::Backup(input_id("backup")));
+        assert_eq!(
+            emitted_sources(&events),
+            vec![FailoverSource::Backup(input_id("backup"))]
+        );
+    }
+}
@@ -0,0 +1,645 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use crossbeam_channel::{Receiver, Sender, TryRecvError, bounded, unbounded};
+use smelter_render::{ColorRange, FrameData};
+use tracing::{debug, error};
+
+use crate::{
+    event::{Event, MediaAlarm},
+    graphics_context::GraphicsContext,
+    stats::MediaAlarmSource,
+};
+
+use crate::prelude::*;
+
+/// Samples with absolute value below this level (-60 dBFS) are considered silent.
+const SILENCE_THRESHOLD: f64 = 0.001;
+
+/// Frame is black if its average luma (8-bit) exceeds the black level of its range
+/// (16 for limited range, 0 for full range) by less than this value.
+const BLACK_LUMA_MARGIN: f64 = 16.0;
+
+/// Frame is the same as the previous one if average difference of luma is below this value.
+const FROZEN_LUMA_DIFF_THRESHOLD: f64 = 1.0;
+
+/// Only every n-th pixel in every n-th row is analyzed.
+const LUMA_SAMPLING_STEP: usize = 8;
+
+/// Frames are analyzed at most once per this interval. Alarm thresholds are
+/// measured in seconds, so analyzing every frame is not necessary.
+const VIDEO_ANALYSIS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Tracks whether an alarm condition lasted longer than the threshold.
+#[derive(Debug)]
+struct AlarmState {
+    threshold: Duration,
+    /// PTS of the first media where the condition was detected.
+    condition_start_pts: Option<Duration>,
+    is_active: bool,
+}
+
+impl AlarmState {
+    fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            condition_start_pts: None,
+            is_active: false,
+        }
+    }
+
+    /// Returns the new state of the alarm if it changed.
+    fn update(&mut self, condition: bool, pts: Duration) -> Option<bool> {
+        if !condition {
+            self.condition_start_pts = None;
+            return match self.is_active {
+                true => {
+                    self.is_active = false;
+                    Some(false)
+                }
+                false => None,
+            };
+        }
+        let start_pts = *self.condition_start_pts.get_or_insert(pts);
+        if !self.is_active && pts.saturating_sub(start_pts) >= self.threshold {
+            self.is_active = true;
+            return Some(true);
+        }
+        None
+    }
+}
+
+/// Reports changes of an alarm condition of a single input or output. Active alarm
+/// is cleared from stats when the monitor is dropped.
+struct AlarmMonitor {
+    source: MediaAlarmSource,
+    alarm: MediaAlarm,
+    state: AlarmState,
+    ctx: Arc<PipelineCtx>,
+}
+
+impl AlarmMonitor {
+    fn new(
+        source: &MediaAlarmSource,
+        alarm: MediaAlarm,
+        threshold: Duration,
+        ctx: &Arc<PipelineCtx>,
+    ) -> Self {
+        Self {
+            source: source.clone(),
+            alarm,
+            state: AlarmState::new(threshold),
+            ctx: ctx.clone(),
+        }
+    }
+
+    fn update(&mut self, condition: bool, pts: Duration) {
+        let Some(is_active) = self.state.update(condition, pts) else {
+            return;
+        };
+        debug!(source=?self.source, alarm=?self.alarm, is_active, "Media alarm changed");
+        let event = match (&self.source, is_active) {
+            (MediaAlarmSource::Input(id), true) => Event::InputMediaAlarm(id.clone(), self.alarm),
+            (MediaAlarmSource::Input(id), false) => {
+                Event::InputMediaAlarmEnd(id.clone(), self.alarm)
+            }
+            (MediaAlarmSource::Output(id), true) => Event::OutputMediaAlarm(id.clone(), self.alarm),
+            (MediaAlarmSource::Output(id), false) => {
+                Event::OutputMediaAlarmEnd(id.clone(), self.alarm)
+            }
+        };
+        self.ctx.event_emitter.emit(event);
+        self.send_stats();
+    }
+
+    fn send_stats(&self) {
+        self.ctx.stats_sender.send(StatsEvent::MediaAlarm {
+            source: self.source.clone(),
+            alarm: self.alarm,
+            active: self.state.is_active,
+        });
+    }
+}
+
+impl Drop for AlarmMonitor {
+    fn drop(&mut self) {
+        if self.state.is_active {
+            self.state.is_active = false;
+            self.send_stats();
+        }
+    }
+}
+
+/// Runs the analysis of all video monitors of a pipeline, so it never delays frame
+/// delivery. The thread stops when all senders are dropped.
+#[derive(Clone)]
+pub(crate) struct VideoMonitorThread {
+    sender: Sender<VideoMonitorEvent>,
+    next_monitor_id: Arc<AtomicU64>,
+}
+
+enum VideoMonitorEvent {
+    Register {
+        id: u64,
+        analyzer: Box<VideoAnalyzer>,
+        is_analyzing: Arc<AtomicBool>,
+    },
+    Frame {
+        id: u64,
+        frame: Frame,
+        pts: Duration,
+    },
+    Unregister {
+        id: u64,
+    },
+}
+
+impl VideoMonitorThread {
+    pub fn spawn() -> Result<Self, std::io::Error> {
+        let (sender, receiver) = unbounded();
+        thread::Builder::new()
+            .name("Video media monitor".to_string())
+            .spawn(move || Self::run(receiver))?;
+        Ok(Self {
+            sender,
+            next_monitor_id: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    fn run(receiver: Receiver<VideoMonitorEvent>) {
+        let mut analyzers = HashMap::new();
+        for event in receiver.iter() {
+            match event {
+                VideoMonitorEvent::Register {
+                    id,
+                    analyzer,
+                    is_analyzing,
+                } => {
+                    analyzers.insert(id, (analyzer, is_analyzing));
+                }
+                VideoMonitorEvent::Frame { id, frame, pts } => {
+                    if let Some((analyzer, is_analyzing)) = analyzers.get_mut(&id) {
+                        analyzer.process(&frame, pts);
+                        is_analyzing.store(false, Ordering::Release);
+                    }
+                }
+                // Active alarms are cleared when the analyzer is dropped.
+                VideoMonitorEvent::Unregister { id } => {
+                    analyzers.remove(&id);
+                }
+            }
+        }
+    }
+}
+
+/// Detects black and frozen video. Frames are analyzed when they are used, so an input
+/// that stopped delivering frames (its last frame is used over and over) is reported
+/// as frozen.
+///
+/// Analysis runs on the [`VideoMonitorThread`] of the pipeline. Frames are sampled at most
+/// once per `VIDEO_ANALYSIS_INTERVAL`, a sample is skipped if the previous one is still
+/// analyzed.
+pub(crate) struct VideoMonitor {
+    id: u64,
+    sender: Sender<VideoMonitorEvent>,
+    is_analyzing: Arc<AtomicBool>,
+    next_analysis_pts: Duration,
+}
+
+impl VideoMonitor {
+    pub fn new(source: MediaAlarmSource, threshold: Duration, ctx: &Arc<PipelineCtx>) -> Self {
+        let thread = &ctx.video_monitor_thread;
+        let id = thread.next_monitor_id.fetch_add(1, Ordering::Relaxed);
+        let is_analyzing = Arc::new(AtomicBool::new(false));
+        let event = VideoMonitorEvent::Register {
+            id,
+            analyzer: Box::new(VideoAnalyzer::new(source, threshold, ctx)),
+            is_analyzing: is_analyzing.clone(),
+        };
+        if thread.sender.send(event).is_err() {
+            error!("Video media monitor thread stopped.");
+        }
+        Self {
+            id,
+            sender: thread.sender.clone(),
+            is_analyzing,
+            next_analysis_pts: Duration::ZERO,
+        }
+    }
+
+    /// `pts` is the moment when the frame is used, the same frame can be used
+    /// for multiple consecutive PTS.
+    pub fn process(&mut self, frame: &Frame, pts: Duration) {
+        if pts < self.next_analysis_pts {
+            return;
+        }
+        self.next_analysis_pts = pts + VIDEO_ANALYSIS_INTERVAL;
+        if self.is_analyzing.swap(true, Ordering::Acquire) {
+            return;
+        }
+        let event = VideoMonitorEvent::Frame {
+            id: self.id,
+            frame: frame.clone(),
+            pts,
+        };
+        if self.sender.send(event).is_err() {
+            error!("Video media monitor thread stopped.");
+        }
+    }
+}
+
+impl Drop for VideoMonitor {
+    fn drop(&mut self) {
+        let _ = self
+            .sender
+            .send(VideoMonitorEvent::Unregister { id: self.id });
+    }
+}
+
+struct VideoAnalyzer {
+    black: AlarmMonitor,
+    frozen: AlarmMonitor,
+    previous_luma: Option<Vec<u8>>,
+    previous_frame_pts: Option<Duration>,
+    /// Luma of GPU textures is downloaded in the background and analyzed when
+    /// one of the next frames is processed.
+    pending_download: Option<LumaDownload>,
+    ctx: Arc<PipelineCtx>,
+}
+
+impl VideoAnalyzer {
+    fn new(source: MediaAlarmSource, threshold: Duration, ctx: &Arc<PipelineCtx>) -> Self {
+        Self {
+            black: AlarmMonitor::new(&source, MediaAlarm::Black, threshold, ctx),
+            frozen: AlarmMonitor::new(&source, MediaAlarm::Frozen, threshold, ctx),
+            previous_luma: None,
+            previous_frame_pts: None,
+            pending_download: None,
+            ctx: ctx.clone(),
+        }
+    }
+
+    fn process(&mut self, frame: &Frame, pts: Duration) {
+        if self.previous_frame_pts.replace(frame.pts) == Some(frame.pts) {
+            self.frozen.update(true, pts);
+            return;
+        }
+
+        let Some((luma, range)) = self.luma(frame) else {
+            return;
+        };
+        if luma.is_empty() {
+            return;
+        }
+        self.black.update(is_black(&luma, range), pts);
+        let is_frozen = self
+            .previous_luma
+            .as_ref()
+            .is_some_and(|previous_luma| is_same_image(previous_luma, &luma));
+        self.frozen.update(is_frozen, pts);
+        self.previous_luma = Some(luma);
+    }
+
+    /// Returns sampled luma and its range.
+    fn luma(&mut self, frame: &Frame) -> Option<(Vec<u8>, ColorRange)> {
+        let texture = match &frame.data {
+            FrameData::Rgba8UnormWgpuTexture(texture) | FrameData::Nv12WgpuTexture(texture) => {
+                texture
+            }
+            FrameData::PlanarYuvJ420(_) => return Some((sample_luma(frame)?, ColorRange::Full)),
+            _ => return Some((sample_luma(frame)?, frame.color.range)),
+        };
+
+        let device = &self.ctx.graphics_context.device;
+        let luma = match self.pending_download.take() {
+            Some(download) => match download.try_read(device) {
+                Ok(luma) => luma,
+                Err(download) => {
+                    self.pending_download = Some(download);
+                    return None;
+                }
+            },
+            None => None,
+        };
+        self.pending_download =
+            LumaDownload::start(&self.ctx.graphics_context, texture, frame.color.range);
+        luma
+    }
+}
+
+/// Detects silent audio. Input that stopped delivering samples is silent.
+pub(crate) struct AudioMonitor {
+    silence: AlarmMonitor,
+}
+
+impl AudioMonitor {
+    pub fn new(source: MediaAlarmSource, threshold: Duration, ctx: &Arc<PipelineCtx>) -> Self {
+        Self {
+            silence: AlarmMonitor::new(&source, MediaAlarm::Silence, threshold, ctx),
+        }
+    }
+
+    /// Samples of an input returned by the queue for a range ending at `end_pts`.
+    pub fn process_input(&mut self, batches: &[InputAudioSamples], end_pts: Duration) {
+        let is_silent = batches
+            .iter()
+            .all(|batch| batch.samples.iter().all(|sample| is_silent_sample(*sample)));
+        self.silence.update(is_silent, end_pts);
+    }
+
+    pub fn process_output(&mut self, batch: &OutputAudioSamples) {
+        let is_silent = match &batch.samples {
+            AudioSamples::Mono(samples) => samples
+                .iter()
+                .all(|sample| is_silent_sample((*sample, *sample))),
+            AudioSamples::Stereo(samples) => samples.iter().all(|sample| is_silent_sample(*sample)),
+        };
+        self.silence.update(is_silent, batch.start_pts);
+    }
+}
+
+fn is_silent_sample((l, r): (f64, f64)) -> bool {
+    l.abs() < SILENCE_THRESHOLD && r.abs() < SILENCE_THRESHOLD
+}
+
+fn is_black(luma: &[u8], range: ColorRange) -> bool {
+    let black_level = match range {
+        ColorRange::Limited => 16.0,
+        ColorRange::Full => 0.0,
+    };
+    let average_luma = luma.iter().map(|value| *value as f64).sum::<f64>() / luma.len() as f64;
+    average_luma < black_level + BLACK_LUMA_MARGIN
+}
+
+fn is_same_image(previous_luma: &[u8], luma: &[u8]) -> bool {
+    if previous_luma.len() != luma.len() {
+        return false;
+    }
+    let diff_sum: f64 = previous_luma
+        .iter()
+        .zip(luma.iter())
+        .map(|(previous, current)| previous.abs_diff(*current) as f64)
+        .sum();
+    diff_sum / (luma.len() as f64) < FROZEN_LUMA_DIFF_THRESHOLD
+}
+
+/// Returns 8-bit luma of a subset of pixels. Returns `None` if frame data is not in RAM.
+fn sample_luma(frame: &Frame) -> Option<Vec<u8>> {
+    let width = frame.resolution.width;
+    let height = frame.resolution.height;
+    let positions = (0..height).step_by(LUMA_SAMPLING_STEP).flat_map(|y| {
+        (0..width)
+            .step_by(LUMA_SAMPLING_STEP)
+            .map(move |x| y * width + x)
+    });
+
+    let luma = match &frame.data {
+        FrameData::PlanarYuv420(planes)
+        | FrameData::PlanarYuv422(planes)
+        | FrameData::PlanarYuv444(planes)
+        | FrameData::PlanarYuvJ420(planes) => positions
+            .filter_map(|index| planes.y_plane.get(index).copied())
+            .collect(),
+        FrameData::Nv12(planes) => positions
+            .filter_map(|index| planes.y_plane.get(index).copied())
+            .collect(),
+        // 10-bit value stored in the least significant bits of a little-endian u16
+        FrameData::PlanarYuv420P10(planes) => positions
+            .filter_map(|index| {
+                let low = *planes.y_plane.get(index * 2)?;
+                let high = *planes.y_plane.get(index * 2 + 1)?;
+                Some((u16::from_le_bytes([low, high]) >> 2) as u8)
+            })
+            .collect(),
+        // 10-bit value stored in the most significant bits of a little-endian u16
+        FrameData::P010(planes) => positions
+            .filter_map(|index| planes.y_plane.get(index * 2 + 1).copied())
+            .collect(),
+        FrameData::InterleavedUyvy422(data) => positions
+            .filter_map(|index| data.get(index * 2 + 1).copied())
+            .collect(),
+        FrameData::InterleavedYuyv422(data) => positions
+            .filter_map(|index| data.get(index * 2).copied())
+            .collect(),
+        FrameData::Rgba8UnormWgpuTexture(_) | FrameData::Nv12WgpuTexture(_) => return None,
+    };
+    Some(luma)
+}
+
+/// Copy of every n-th row of a texture. RGBA textures are copied as is, for NV12
+/// textures only the luma plane is copied.
+struct LumaDownload {
+    buffer: wgpu::Buffer,
+    format: wgpu::TextureFormat,
+    /// Range of the downloaded luma, luma computed from RGBA is always full range.
+    range: ColorRange,
+    width: usize,
+    padded_row_size: usize,
+    result: Receiver<Result<(), wgpu::BufferAsyncError>>,
+}
+
+impl LumaDownload {
+    /// Returns `None` if the texture can not be copied. `nv12_range` is the range
+    /// of the frame if the texture is NV12.
+    fn start(
+        ctx: &GraphicsContext,
+        texture: &wgpu::Texture,
+        nv12_range: ColorRange,
+    ) -> Option<Self> {
+        let (aspect, bytes_per_pixel, range) = match texture.format() {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => {
+                (wgpu::TextureAspect::All, 4, ColorRange::Full)
+            }
+            wgpu::TextureFormat::NV12 => (wgpu::TextureAspect::Plane0, 1, nv12_range),
+            _ => return None,
+        };
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return None;
+        }
+
+        let size = texture.size();
+        let padded_row_size =
+            (size.width * bytes_per_pixel).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let rows: Vec<u32> = (0..size.height).step_by(LUMA_SAMPLING_STEP).collect();
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("media monitor luma buffer"),
+            size: (padded_row_size as usize * rows.len()) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("media monitor luma encoder"),
+            });
+        for (index, y) in rows.iter().enumerate() {
+            encoder.copy_texture_to_buffer(
+                wgpu::TexelCopyTextureInfo {
+                    texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d { x: 0, y: *y, z: 0 },
+                    aspect,
+                },
+                wgpu::TexelCopyBufferInfo {
+                    buffer: &buffer,
+                    layout: wgpu::TexelCopyBufferLayout {
+                        offset: (index * padded_row_size as usize) as u64,
+                        bytes_per_row: Some(padded_row_size),
+                        rows_per_image: None,
+                    },
+                },
+                wgpu::Extent3d {
+                    width: size.width,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let (sender, result) = bounded(1);
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                if let Err(err) = sender.send(result) {
+                    error!("channel send error: {err}")
+                }
+            });
+
+        Some(Self {
+            buffer,
+            format: texture.format(),
+            range,
+            width: size.width as usize,
+            padded_row_size: padded_row_size as usize,
+            result,
+        })
+    }
+
+    /// Returns luma and its range if the download finished (`None` if it failed).
+    /// Returns the download back if it is still in progress.
+    fn try_read(self, device: &wgpu::Device) -> Result<Option<(Vec<u8>, ColorRange)>, Self> {
+        if let Err(err) = device.poll(wgpu::PollType::Poll) {
+            debug!("Device poll failed: {err}");
+        }
+        match self.result.try_recv() {
+            Ok(Ok(())) => {}
+            Err(TryRecvError::Empty) => return Err(self),
+            Ok(Err(_)) | Err(TryRecvError::Disconnected) => return Ok(None),
+        }
+
+        let luma = {
+            let range = self.buffer.slice(..).get_mapped_range();
+            range
+                .chunks(self.padded_row_size)
+                .flat_map(|row| {
+                    (0..self.width)
+                        .step_by(LUMA_SAMPLING_STEP)
+                        .map(move |x| match self.format {
+                            wgpu::TextureFormat::NV12 => row[x],
+                            _ => rgb_to_luma(row[x * 4], row[x * 4 + 1], row[x * 4 + 2]),
+                        })
+                })
+                .collect()
+        };
+        self.buffer.unmap();
+        Ok(Some((luma, self.range)))
+    }
+}
+
+/// Full range BT.709 luma of gamma encoded RGB.
+fn rgb_to_luma(r: u8, g: u8, b: u8) -> u8 {
+    (0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use smelter_render::{ColorInfo, Resolution, YuvPlanes};
+
+    use super::*;
+
+    fn yuv_frame(width: usize, height: usize, luma: impl Fn(usize, usize) -> u8) -> Frame {
+        let y_plane: Vec<u8> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| luma(x, y))
+            .collect();
+        Frame {
+            data: FrameData::PlanarYuv420(YuvPlanes {
+                y_plane: Bytes::from(y_plane),
+                u_plane: Bytes::from(vec![128; width * height / 4]),
+                v_plane: Bytes::from(vec![128; width * height / 4]),
+            }),
+            resolution: Resolution { width, height },
+            pts: Duration::ZERO,
+            color: ColorInfo::default(),
+        }
+    }
+
+    #[test]
+    fn test_alarm_state() {
+        let mut state = AlarmState::new(Duration::from_secs(5));
+        let secs = Duration::from_secs;
+
+        assert_eq!(state.update(true, secs(10)), None);
+        assert_eq!(state.update(true, secs(14)), None);
+        assert_eq!(state.update(true, secs(15)), Some(true));
+        assert_eq!(state.update(true, secs(20)), None);
+        assert_eq!(state.update(false, secs(21)), Some(false));
+        assert_eq!(state.update(false, secs(22)), None);
+
+        // Condition has to last for the whole threshold again.
+        assert_eq!(state.update(true, secs(23)), None);
+        assert_eq!(state.update(false, secs(25)), None);
+        assert_eq!(state.update(true, secs(26)), None);
+        assert_eq!(state.update(true, secs(30)), None);
+        assert_eq!(state.update(true, secs(31)), Some(true));
+    }
+
+    #[test]
+    fn test_sample_luma() {
+        let frame = yuv_frame(32, 16, |x, y| (x + y) as u8);
+        // every 8th pixel of rows 0 and 8
+        assert_eq!(sample_luma(&frame).unwrap(), [0, 8, 16, 24, 8, 16, 24, 32]);
+    }
+
+    #[test]
+    fn test_black_and_frozen_detection() {
+        let black = sample_luma(&yuv_frame(32, 32, |_, _| 16)).unwrap();
+        let gray = sample_luma(&yuv_frame(32, 32, |_, _| 128)).unwrap();
+        let noise = sample_luma(&yuv_frame(32, 32, |x, y| ((x * 7 + y * 13) % 256) as u8)).unwrap();
+
+        let dark = sample_luma(&yuv_frame(32, 32, |_, _| 24)).unwrap();
+
+        assert!(is_black(&black, ColorRange::Limited));
+        assert!(!is_black(&gray, ColorRange::Limited));
+        // 24 is close to black in limited range, but not in full range
+        assert!(is_black(&dark, ColorRange::Limited));
+        assert!(!is_black(&dark, ColorRange::Full));
+        assert!(is_same_image(&gray, &gray));
+        assert!(!is_same_image(&gray, &noise));
+        assert!(!is_same_image(&gray, &gray[1..]));
+    }
+
+    #[test]
+    fn test_silence_detection() {
+        assert!(is_silent_sample((0.0, -0.0005)));
+        assert!(!is_silent_sample((0.0, 0.01)));
+        assert!(!is_silent_sample((-0.5, 0.0)));
+    }
+
+    #[test]
+    fn test_rgb_to_luma() {
+        assert_eq!(rgb_to_luma(0, 0, 0), 0);
+        assert_eq!(rgb_to_luma(255, 255, 255), 255);
+        assert_eq!(rgb_to_luma(255, 0, 0), 54);
+    }
+}
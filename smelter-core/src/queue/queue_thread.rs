@@ -1,5 +1,5 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashSet},
     ops::Add,
     sync::{Arc, atomic::Ordering},
     thread::{self, JoinHandle},
@@ -7,9 +7,10 @@ use std::{
 };
 
 use crossbeam_channel::{Receiver, Sender, select, tick};
+use smelter_render::OutputId;
 use tracing::{debug, info, info_span, trace, warn};
 
-use super::{Queue, QueueAudioOutput, QueueVideoOutput, ScheduledEvent};
+use super::{Queue, QueueAudioOutput, QueueVideoOutput, ScheduledEvent, utils::next_frame_set};
 
 pub(super) struct QueueThread {
     queue: Arc<Queue>,
@@ -110,7 +111,7 @@ impl QueueThreadAfterStart {
             video_processor: VideoQueueProcessor {
                 queue: queue_thread.queue,
                 sender: start_event.video_sender,
-                sent_batches_counter: 0,
+                last_pts: None,
                 queue_start_pts: start_event.start_time_pts,
             },
             scheduled_event_receiver: queue_thread.scheduled_event_receiver,
@@ -136,14 +137,16 @@ impl QueueThreadAfterStart {
     fn on_handle_tick(&mut self) {
         while !self.queue.should_close.load(Ordering::Relaxed) {
             let audio_pts_range = self.audio_processor.next_buffer_pts_range();
-            let video_pts = self.video_processor.next_buffer_pts();
+            let (video_pts, video_outputs) = self.video_processor.next_buffer_pts();
             let event_pts = self
                 .scheduled_events
                 .first_key_value()
                 .map(|(pts, _)| *pts + self.queue_start_pts);
 
+            // Events are applied before media with the same PTS, so e.g. an input
+            // registered for some PTS is already used in a frame with that PTS.
             if let Some(true) = event_pts
-                .map(|event_pts: Duration| event_pts < video_pts && event_pts < audio_pts_range.0)
+                .map(|event_pts: Duration| event_pts <= video_pts && event_pts <= audio_pts_range.0)
             {
                 info!("Handle scheduled event for PTS={:?}", event_pts);
                 if let Some((_, callbacks)) = self.scheduled_events.pop_first() {
@@ -164,7 +167,7 @@ impl QueueThreadAfterStart {
                 trace!(pts=?video_pts, "Try to push video frames.");
                 if self
                     .video_processor
-                    .try_push_next_frame_set(video_pts)
+                    .try_push_next_frame_set(video_pts, video_outputs)
                     .is_none()
                 {
                     break;
@@ -175,7 +178,7 @@ impl QueueThreadAfterStart {
 
     fn on_enqueue_event(&mut self, scheduled_event: ScheduledEvent) {
         let audio_pts_range = self.audio_processor.next_buffer_pts_range();
-        let video_pts = self.video_processor.next_buffer_pts();
+        let (video_pts, _) = self.video_processor.next_buffer_pts();
         let event_pts = self
             .scheduled_events
             .first_key_value()
@@ -203,22 +206,29 @@ impl QueueThreadAfterStart {
 
 struct VideoQueueProcessor {
     queue: Arc<Queue>,
-    sent_batches_counter: u32,
+    /// PTS (relative to queue start) of the last pushed or dropped frame set.
+    last_pts: Option<Duration>,
     queue_start_pts: Duration,
     sender: Sender<QueueVideoOutput>,
 }
 
 impl VideoQueueProcessor {
-    fn next_buffer_pts(&self) -> Duration {
-        Duration::from_secs_f64(
-            self.sent_batches_counter as f64 * self.queue.output_framerate.den as f64
-                / self.queue.output_framerate.num as f64,
-        ) + self.queue_start_pts
+    /// Earliest timestamp needed by any of the registered outputs and outputs that
+    /// need a frame with that timestamp.
+    fn next_buffer_pts(&self) -> (Duration, HashSet<OutputId>) {
+        let framerates = self.queue.output_framerates.lock().unwrap();
+        let (next_pts, outputs) =
+            next_frame_set(&framerates, self.queue.output_framerate, self.last_pts);
+        (next_pts + self.queue_start_pts, outputs)
     }
 
     /// Some(()) - Successfully pushed new frame (or dropped it).
     /// None - Nothing to push.
-    fn try_push_next_frame_set(&mut self, next_buffer_pts: Duration) -> Option<()> {
+    fn try_push_next_frame_set(
+        &mut self,
+        next_buffer_pts: Duration,
+        outputs: HashSet<OutputId>,
+    ) -> Option<()> {
         let mut internal_queue = self.queue.video_queue.lock().unwrap();
 
         let should_push_next_frame =
@@ -228,7 +238,7 @@ impl VideoQueueProcessor {
         }
 
         let mut frames_batch =
-            internal_queue.get_frames_batch(next_buffer_pts, self.queue_start_pts);
+            internal_queue.get_frames_batch(next_buffer_pts, self.queue_start_pts, outputs);
         drop(internal_queue);
 
         frames_batch.required = frames_batch.required || self.queue.never_drop_output_frames;
@@ -253,7 +263,7 @@ impl VideoQueueProcessor {
                 warn!(?pts, "Dropping video frame on queue output.");
             }
         }
-        self.sent_batches_counter += 1
+        self.last_pts = Some(pts.saturating_sub(self.queue_start_pts));
     }
 }
 
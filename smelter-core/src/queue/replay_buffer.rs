@@ -0,0 +1,482 @@
+use std::{
+    collections::VecDeque,
+    ops::Range,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::prelude::*;
+
+/// Rolling buffer of encoded chunks of a single input. Chunks are recorded by the
+/// decoders of the input, so replay inputs can decode them again. Chunks keep PTS
+/// values of the input, they are converted to public PTS values (relative to the queue
+/// start) with the mapping reported by the queue when media is enqueued.
+///
+/// Buffer is shared between decoders, the queue, and replay inputs, which copy
+/// the requested range when they are registered.
+#[derive(Clone)]
+pub(crate) struct ReplayBuffer(Arc<Mutex<ReplayBufferState>>);
+
+/// Decoder that can decode recorded audio chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ReplayAudioDecoder {
+    Aac(FdkAacDecoderOptions),
+    Opus,
+}
+
+/// Recorded chunks of a video track that start with a keyframe. PTS values
+/// are public PTS values.
+#[derive(Debug)]
+pub(crate) struct ReplayVideoTrack {
+    pub codec: VideoCodec,
+    pub chunks: Vec<EncodedInputChunk>,
+}
+
+/// Recorded chunks of an audio track. PTS values are public PTS values.
+#[derive(Debug)]
+pub(crate) struct ReplayAudioTrack {
+    pub decoder: ReplayAudioDecoder,
+    pub chunks: Vec<EncodedInputChunk>,
+}
+
+struct ReplayBufferState {
+    max_duration: Duration,
+    max_memory_bytes: usize,
+
+    video: TrackBuffer<VideoCodec>,
+    audio: TrackBuffer<ReplayAudioDecoder>,
+}
+
+struct TrackBuffer<Decoder> {
+    decoder: Option<Decoder>,
+    chunks: VecDeque<RecordedChunk>,
+    memory_bytes: usize,
+    pts_mapping: Option<PtsMapping>,
+}
+
+struct RecordedChunk {
+    chunk: EncodedInputChunk,
+    /// Decoding can start from this chunk. All audio chunks are independent.
+    is_keyframe: bool,
+}
+
+/// Pair of PTS values of the same media, the first one as produced by the input
+/// and the second one relative to the queue start.
+#[derive(Debug, Clone, Copy)]
+struct PtsMapping {
+    input_pts: Duration,
+    pts: Duration,
+}
+
+impl ReplayBuffer {
+    pub fn new(options: ReplayBufferOptions) -> Self {
+        Self(Arc::new(Mutex::new(ReplayBufferState {
+            max_duration: options.duration,
+            max_memory_bytes: options.max_memory_bytes,
+            video: TrackBuffer::new(),
+            audio: TrackBuffer::new(),
+        })))
+    }
+
+    /// Records a video chunk before it is passed to the decoder. Chunks recorded
+    /// before a change of the codec are dropped.
+    pub fn push_video_chunk(&self, chunk: &EncodedInputChunk) {
+        let MediaKind::Video(codec) = chunk.kind else {
+            return;
+        };
+        let mut is_keyframe = is_keyframe(codec, &chunk.data);
+        let mut state = self.0.lock().unwrap();
+        let ReplayBufferState {
+            max_duration,
+            max_memory_bytes,
+            video,
+            audio,
+        } = &mut *state;
+        // RTP depayloaders split frames into multiple chunks with the same PTS, so only
+        // the first chunk of a frame is marked. VP8 and VP9 frame headers are only in
+        // the first chunk, H264 IDR slices can follow parameter sets in separate chunks.
+        if let Some(frame_start) = video.frame_start(&codec, chunk.pts) {
+            if codec == VideoCodec::H264 && is_keyframe {
+                frame_start.is_keyframe = true;
+            }
+            is_keyframe = false;
+        }
+        video.push(codec, chunk.clone(), is_keyframe);
+        video.drop_old_chunks(
+            *max_duration,
+            max_memory_bytes.saturating_sub(audio.memory_bytes),
+        );
+    }
+
+    /// Records an audio chunk before it is passed to the decoder. Works like
+    /// [`ReplayBuffer::push_video_chunk`].
+    pub fn push_audio_chunk(&self, chunk: &EncodedInputChunk, decoder: &ReplayAudioDecoder) {
+        let mut state = self.0.lock().unwrap();
+        let ReplayBufferState {
+            max_duration,
+            max_memory_bytes,
+            video,
+            audio,
+        } = &mut *state;
+        audio.push(decoder.clone(), chunk.clone(), true);
+        audio.drop_old_chunks(
+            *max_duration,
+            max_memory_bytes.saturating_sub(video.memory_bytes),
+        );
+    }
+
+    /// Called by the queue when a frame is enqueued. `input_pts` is PTS of the frame
+    /// produced by the decoder and `pts` is the PTS relative to the queue start.
+    pub(super) fn set_video_pts_mapping(&self, input_pts: Duration, pts: Duration) {
+        let mut state = self.0.lock().unwrap();
+        state
+            .video
+            .pts_mapping
+            .get_or_insert(PtsMapping { input_pts, pts });
+    }
+
+    /// Works like [`ReplayBuffer::set_video_pts_mapping`] for sample batches.
+    pub(super) fn set_audio_pts_mapping(&self, input_pts: Duration, pts: Duration) {
+        let mut state = self.0.lock().unwrap();
+        state
+            .audio
+            .pts_mapping
+            .get_or_insert(PtsMapping { input_pts, pts });
+    }
+
+    /// Returns copies of chunks needed to decode frames with PTS in the range. Chunks start
+    /// from the last keyframe before the range and end before the first keyframe after it,
+    /// so the decoded frames still need to be filtered. Chunk data is shared with the buffer.
+    pub fn video(&self, range: &Range<Duration>) -> Option<ReplayVideoTrack> {
+        let state = self.0.lock().unwrap();
+        let track = &state.video;
+        let codec = track.decoder?;
+        let chunks = track.mapped_chunks();
+
+        let first = chunks
+            .iter()
+            .rposition(|(chunk, is_keyframe)| *is_keyframe && chunk.pts <= range.start)
+            .or_else(|| chunks.iter().position(|(_, is_keyframe)| *is_keyframe))?;
+        let chunks: Vec<_> = chunks[first..]
+            .iter()
+            .enumerate()
+            .take_while(|(index, (chunk, is_keyframe))| {
+                *index == 0 || !(*is_keyframe && chunk.pts >= range.end)
+            })
+            .map(|(_, (chunk, _))| chunk.clone())
+            .collect();
+        match chunks.first() {
+            Some(first) if first.pts < range.end => Some(ReplayVideoTrack { codec, chunks }),
+            _ => None,
+        }
+    }
+
+    /// Returns copies of audio chunks with PTS in the range.
+    pub fn audio(&self, range: &Range<Duration>) -> Option<ReplayAudioTrack> {
+        let state = self.0.lock().unwrap();
+        let track = &state.audio;
+        let decoder = track.decoder.clone()?;
+        let chunks: Vec<_> = track
+            .mapped_chunks()
+            .into_iter()
+            .map(|(chunk, _)| chunk)
+            .filter(|chunk| range.contains(&chunk.pts))
+            .collect();
+        match chunks.is_empty() {
+            true => None,
+            false => Some(ReplayAudioTrack { decoder, chunks }),
+        }
+    }
+}
+
+impl<Decoder: PartialEq> TrackBuffer<Decoder> {
+    fn new() -> Self {
+        Self {
+            decoder: None,
+            chunks: VecDeque::new(),
+            memory_bytes: 0,
+            pts_mapping: None,
+        }
+    }
+
+    fn push(&mut self, decoder: Decoder, chunk: EncodedInputChunk, is_keyframe: bool) {
+        if self.decoder.as_ref() != Some(&decoder) {
+            self.chunks.clear();
+            self.memory_bytes = 0;
+            self.decoder = Some(decoder);
+        }
+        // Decoding can't start in the middle of a group of pictures.
+        if self.chunks.is_empty() && !is_keyframe {
+            return;
+        }
+        self.memory_bytes += chunk.data.len();
+        self.chunks.push_back(RecordedChunk { chunk, is_keyframe });
+    }
+
+    /// First recorded chunk of the frame with the given PTS if the last recorded chunks
+    /// belong to that frame.
+    fn frame_start(&mut self, decoder: &Decoder, pts: Duration) -> Option<&mut RecordedChunk> {
+        if self.decoder.as_ref() != Some(decoder) || self.chunks.back()?.chunk.pts != pts {
+            return None;
+        }
+        let index = self
+            .chunks
+            .iter()
+            .rposition(|recorded| recorded.chunk.pts != pts)
+            .map_or(0, |index| index + 1);
+        self.chunks.get_mut(index)
+    }
+
+    /// Drops the oldest chunks older than `max_duration` before the last chunk or until
+    /// the memory limit is met. Chunks are dropped up to the next keyframe, so the buffer
+    /// always starts with a keyframe. Chunks that are needed to decode media from the start
+    /// of `max_duration` are kept.
+    fn drop_old_chunks(&mut self, max_duration: Duration, max_memory_bytes: usize) {
+        let Some(last_pts) = self.chunks.back().map(|recorded| recorded.chunk.pts) else {
+            return;
+        };
+        let min_pts = last_pts.saturating_sub(max_duration);
+        loop {
+            let next_keyframe = self
+                .chunks
+                .iter()
+                .skip(1)
+                .position(|recorded| recorded.is_keyframe)
+                .map(|index| index + 1);
+            let should_drop = match next_keyframe {
+                Some(index) => {
+                    self.chunks[index].chunk.pts <= min_pts || self.memory_bytes > max_memory_bytes
+                }
+                None => false,
+            };
+            let Some(index) = next_keyframe.filter(|_| should_drop) else {
+                break;
+            };
+            for recorded in self.chunks.drain(..index) {
+                self.memory_bytes -= recorded.chunk.data.len();
+            }
+        }
+        // Whole group of pictures does not fit in the memory limit.
+        if self.memory_bytes > max_memory_bytes {
+            self.chunks.clear();
+            self.memory_bytes = 0;
+        }
+    }
+
+    /// Chunks with PTS converted to values relative to the queue start. Chunks from before
+    /// the queue start or recorded before the mapping is known are skipped.
+    fn mapped_chunks(&self) -> Vec<(EncodedInputChunk, bool)> {
+        let Some(mapping) = self.pts_mapping else {
+            return Vec::new();
+        };
+        self.chunks
+            .iter()
+            .filter_map(|recorded| {
+                let pts = mapping.map(recorded.chunk.pts)?;
+                let dts = recorded.chunk.dts.and_then(|dts| mapping.map(dts));
+                let chunk = EncodedInputChunk {
+                    pts,
+                    dts,
+                    ..recorded.chunk.clone()
+                };
+                Some((chunk, recorded.is_keyframe))
+            })
+            .collect()
+    }
+}
+
+impl PtsMapping {
+    fn map(&self, input_pts: Duration) -> Option<Duration> {
+        (self.pts + input_pts).checked_sub(self.input_pts)
+    }
+}
+
+/// Checks if decoding can start from this chunk. Data of H264 chunks is expected to be
+/// in the Annex B format, SPS is treated as a start of a keyframe, because it is sent
+/// before IDR slices.
+fn is_keyframe(codec: VideoCodec, data: &[u8]) -> bool {
+    match codec {
+        VideoCodec::H264 => h264_nal_types(data)
+            .any(|nal_type| nal_type == H264_IDR_NAL_TYPE || nal_type == H264_SPS_NAL_TYPE),
+        // frame tag: bit 0 is 0 for key frames
+        VideoCodec::Vp8 => data.first().is_some_and(|byte| byte & 0b1 == 0),
+        VideoCodec::Vp9 => is_vp9_keyframe(data),
+    }
+}
+
+const H264_IDR_NAL_TYPE: u8 = 5;
+const H264_SPS_NAL_TYPE: u8 = 7;
+
+/// Types of NAL units that follow `00 00 01` start codes.
+fn h264_nal_types(data: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    data.windows(4)
+        .filter(|window| window[0..3] == [0, 0, 1])
+        .map(|window| window[3] & 0x1f)
+}
+
+/// Parses the beginning of the uncompressed header of a VP9 frame.
+fn is_vp9_keyframe(data: &[u8]) -> bool {
+    let Some(&byte) = data.first() else {
+        return false;
+    };
+    let bit = |index: u32| (byte >> (7 - index)) & 1;
+    // frame_marker
+    if bit(0) != 1 || bit(1) != 0 {
+        return false;
+    }
+    let profile = bit(2) | (bit(3) << 1);
+    // profile 3 has a reserved bit after the profile
+    let show_existing_frame_bit = if profile == 3 { 5 } else { 4 };
+    let show_existing_frame = bit(show_existing_frame_bit);
+    let frame_type = bit(show_existing_frame_bit + 1);
+    show_existing_frame == 0 && frame_type == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video_chunk(pts_ms: u64, keyframe: bool, len: usize) -> EncodedInputChunk {
+        let nal_type = if keyframe { H264_IDR_NAL_TYPE } else { 1 };
+        let mut data = vec![0, 0, 0, 1, nal_type];
+        data.resize(len.max(data.len()), 0xff);
+        EncodedInputChunk {
+            data: data.into(),
+            pts: Duration::from_millis(pts_ms),
+            dts: None,
+            kind: MediaKind::Video(VideoCodec::H264),
+        }
+    }
+
+    fn buffer(duration_ms: u64, max_memory_bytes: usize) -> ReplayBuffer {
+        ReplayBuffer::new(ReplayBufferOptions {
+            duration: Duration::from_millis(duration_ms),
+            max_memory_bytes,
+        })
+    }
+
+    fn chunk_pts(track: &ReplayVideoTrack) -> Vec<u64> {
+        track
+            .chunks
+            .iter()
+            .map(|chunk| chunk.pts.as_millis() as u64)
+            .collect()
+    }
+
+    #[test]
+    fn test_keyframe_detection() {
+        assert!(is_keyframe(
+            VideoCodec::H264,
+            &[0, 0, 0, 1, 0x67, 0, 0, 1, 0x65]
+        ));
+        assert!(is_keyframe(VideoCodec::H264, &[0, 0, 0, 1, 0x67, 0xff]));
+        assert!(!is_keyframe(VideoCodec::H264, &[0, 0, 0, 1, 0x68, 0xff]));
+        assert!(!is_keyframe(VideoCodec::H264, &[0, 0, 0, 1, 0x41, 0xff]));
+        assert!(is_keyframe(VideoCodec::Vp8, &[0x10, 0x02]));
+        assert!(!is_keyframe(VideoCodec::Vp8, &[0x11, 0x02]));
+        // frame_marker=2, profile=0, show_existing_frame=0, frame_type=0
+        assert!(is_keyframe(VideoCodec::Vp9, &[0b1000_0010]));
+        // frame_type=1
+        assert!(!is_keyframe(VideoCodec::Vp9, &[0b1000_0100]));
+    }
+
+    #[test]
+    fn test_replay_buffer_video_range() {
+        let buffer = buffer(10_000, usize::MAX);
+        // keyframe every 100ms, queue started when input PTS was 1s
+        for pts_ms in (1000..2000).step_by(20) {
+            buffer.push_video_chunk(&video_chunk(pts_ms, pts_ms % 100 == 0, 10));
+        }
+        assert!(buffer.video(&(Duration::ZERO..Duration::MAX)).is_none());
+        buffer.set_video_pts_mapping(Duration::from_millis(1500), Duration::from_millis(500));
+
+        let range = Duration::from_millis(250)..Duration::from_millis(330);
+        let track = buffer.video(&range).unwrap();
+        assert_eq!(track.codec, VideoCodec::H264);
+        assert_eq!(
+            chunk_pts(&track),
+            vec![200, 220, 240, 260, 280, 300, 320, 340, 360, 380]
+        );
+    }
+
+    #[test]
+    fn test_replay_buffer_split_frames() {
+        let buffer = buffer(10_000, usize::MAX);
+        let chunk = |pts_ms: u64, nal_type: u8| EncodedInputChunk {
+            data: vec![0, 0, 0, 1, nal_type, 0xff].into(),
+            pts: Duration::from_millis(pts_ms),
+            dts: None,
+            kind: MediaKind::Video(VideoCodec::H264),
+        };
+        // SPS, PPS and IDR slices of a keyframe are depayloaded as separate chunks
+        for pts_ms in [0, 100] {
+            buffer.push_video_chunk(&chunk(pts_ms, 0x67));
+            buffer.push_video_chunk(&chunk(pts_ms, 0x68));
+            buffer.push_video_chunk(&chunk(pts_ms, 0x65));
+            buffer.push_video_chunk(&chunk(pts_ms, 0x65));
+            buffer.push_video_chunk(&chunk(pts_ms + 50, 0x41));
+            buffer.push_video_chunk(&chunk(pts_ms + 50, 0x41));
+        }
+        buffer.set_video_pts_mapping(Duration::ZERO, Duration::ZERO);
+
+        let range = Duration::from_millis(120)..Duration::from_millis(200);
+        let track = buffer.video(&range).unwrap();
+        assert_eq!(chunk_pts(&track), vec![100, 100, 100, 100, 150, 150]);
+        assert_eq!(track.chunks[0].data[4], 0x67);
+
+        let range = Duration::from_millis(20)..Duration::from_millis(60);
+        let track = buffer.video(&range).unwrap();
+        assert_eq!(chunk_pts(&track), vec![0, 0, 0, 0, 50, 50]);
+    }
+
+    #[test]
+    fn test_replay_buffer_drops_whole_gops() {
+        let buffer = buffer(250, usize::MAX);
+        // first chunks before a keyframe can't be decoded
+        buffer.push_video_chunk(&video_chunk(0, false, 10));
+        for pts_ms in (20..1000).step_by(20) {
+            buffer.push_video_chunk(&video_chunk(pts_ms, pts_ms % 100 == 0, 10));
+        }
+        buffer.set_video_pts_mapping(Duration::ZERO, Duration::ZERO);
+        let track = buffer.video(&(Duration::ZERO..Duration::MAX)).unwrap();
+        // last chunk is at 980ms, so media from 730ms is needed
+        assert_eq!(chunk_pts(&track).first(), Some(&700));
+        assert_eq!(chunk_pts(&track).last(), Some(&980));
+    }
+
+    #[test]
+    fn test_replay_buffer_memory_limit() {
+        let buffer = buffer(60_000, 100);
+        for pts_ms in (0..1000).step_by(20) {
+            buffer.push_video_chunk(&video_chunk(pts_ms, pts_ms % 100 == 0, 10));
+        }
+        buffer.set_video_pts_mapping(Duration::ZERO, Duration::ZERO);
+        let track = buffer.video(&(Duration::ZERO..Duration::MAX)).unwrap();
+        // two groups of pictures fit in the limit
+        assert_eq!(
+            chunk_pts(&track),
+            (800..1000).step_by(20).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_replay_buffer_audio() {
+        let buffer = buffer(10_000, usize::MAX);
+        let decoder = ReplayAudioDecoder::Opus;
+        for pts_ms in (0..1000).step_by(20) {
+            let chunk = EncodedInputChunk {
+                data: vec![1, 2, 3].into(),
+                pts: Duration::from_millis(pts_ms),
+                dts: None,
+                kind: MediaKind::Audio(AudioCodec::Opus),
+            };
+            buffer.push_audio_chunk(&chunk, &decoder);
+        }
+        buffer.set_audio_pts_mapping(Duration::from_millis(100), Duration::ZERO);
+        let range = Duration::from_millis(100)..Duration::from_millis(160);
+        let track = buffer.audio(&range).unwrap();
+        assert_eq!(track.decoder, decoder);
+        let pts: Vec<_> = track.chunks.iter().map(|c| c.pts.as_millis()).collect();
+        assert_eq!(pts, vec![100, 120, 140]);
+    }
+}
@@ -1,5 +1,10 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
+use smelter_render::{Framerate, OutputId};
 use tracing::debug;
 
 use crate::event::{Event, EventEmitter};
@@ -28,3 +33,106 @@ impl EmitEventOnce {
         self.event.is_none()
     }
 }
+
+/// Returns PTS of the first frame (counted from queue start) for a specified framerate
+/// that is after `last_pts`. Values are rounded down to nanoseconds, so timestamps
+/// of outputs with the same or a compatible framerate match exactly.
+pub(super) fn next_frame_pts(framerate: Framerate, last_pts: Option<Duration>) -> Duration {
+    let frame_pts = |index: u128| {
+        let nanos = index * framerate.den as u128 * 1_000_000_000 / framerate.num as u128;
+        Duration::from_nanos(nanos as u64)
+    };
+    let Some(last_pts) = last_pts else {
+        return Duration::ZERO;
+    };
+    let mut index =
+        last_pts.as_nanos() * framerate.num as u128 / (framerate.den as u128 * 1_000_000_000);
+    while frame_pts(index) <= last_pts {
+        index += 1;
+    }
+    frame_pts(index)
+}
+
+/// Returns PTS of the next frame needed by any of the outputs and the outputs that need
+/// a frame with that PTS. When there are no outputs, `default_framerate` is used.
+pub(super) fn next_frame_set(
+    output_framerates: &HashMap<OutputId, Framerate>,
+    default_framerate: Framerate,
+    last_pts: Option<Duration>,
+) -> (Duration, HashSet<OutputId>) {
+    let next_pts = output_framerates
+        .values()
+        .map(|framerate| next_frame_pts(*framerate, last_pts))
+        .min()
+        .unwrap_or_else(|| next_frame_pts(default_framerate, last_pts));
+    let outputs = output_framerates
+        .iter()
+        .filter(|(_, framerate)| next_frame_pts(**framerate, last_pts) == next_pts)
+        .map(|(output_id, _)| output_id.clone())
+        .collect();
+    (next_pts, outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fps(num: u32, den: u32) -> Framerate {
+        Framerate { num, den }
+    }
+
+    #[test]
+    fn test_next_frame_pts() {
+        assert_eq!(next_frame_pts(fps(30, 1), None), Duration::ZERO);
+        assert_eq!(
+            next_frame_pts(fps(30, 1), Some(Duration::ZERO)),
+            Duration::from_nanos(33_333_333)
+        );
+        // frame after a timestamp that is not on the 30 fps grid
+        assert_eq!(
+            next_frame_pts(fps(30, 1), Some(Duration::from_millis(40))),
+            Duration::from_nanos(66_666_666)
+        );
+        assert_eq!(
+            next_frame_pts(fps(30_000, 1001), Some(Duration::ZERO)),
+            Duration::from_nanos(33_366_666)
+        );
+        // 60 fps grid contains all 30 fps timestamps
+        let mut last_pts = Some(Duration::ZERO);
+        for _ in 0..1000 {
+            let pts_30 = next_frame_pts(fps(30, 1), last_pts);
+            let pts_60 = next_frame_pts(fps(60, 1), Some(next_frame_pts(fps(60, 1), last_pts)));
+            assert_eq!(pts_30, pts_60);
+            last_pts = Some(pts_30);
+        }
+    }
+
+    #[test]
+    fn test_next_frame_set() {
+        let output_30 = OutputId("output_30".into());
+        let output_60 = OutputId("output_60".into());
+        let framerates = HashMap::from([
+            (output_30.clone(), fps(30, 1)),
+            (output_60.clone(), fps(60, 1)),
+        ]);
+
+        let (pts, outputs) = next_frame_set(&framerates, fps(25, 1), None);
+        assert_eq!(pts, Duration::ZERO);
+        assert_eq!(
+            outputs,
+            HashSet::from([output_30.clone(), output_60.clone()])
+        );
+
+        let (pts, outputs) = next_frame_set(&framerates, fps(25, 1), Some(pts));
+        assert_eq!(pts, Duration::from_nanos(16_666_666));
+        assert_eq!(outputs, HashSet::from([output_60.clone()]));
+
+        let (pts, outputs) = next_frame_set(&framerates, fps(25, 1), Some(pts));
+        assert_eq!(pts, Duration::from_nanos(33_333_333));
+        assert_eq!(outputs, HashSet::from([output_30, output_60]));
+
+        let (pts, outputs) = next_frame_set(&HashMap::new(), fps(25, 1), Some(pts));
+        assert_eq!(pts, Duration::from_millis(40));
+        assert!(outputs.is_empty());
+    }
+}
@@ -2,14 +2,17 @@ use crossbeam_channel::{Receiver, TryRecvError};
 use tracing::debug;
 
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     sync::Arc,
     time::{Duration, Instant},
 };
 
 use crate::{
-    event::{Event, EventEmitter},
-    queue::{QueueVideoOutput, SharedState, utils::EmitEventOnce},
+    event::{Event, EventEmitter, FailoverSource},
+    queue::{
+        QueueVideoOutput, SharedState, failover::InputFailover, media_monitor::VideoMonitor,
+        replay_buffer::ReplayBuffer, utils::EmitEventOnce,
+    },
 };
 
 use crate::prelude::*;
@@ -17,21 +20,33 @@ use crate::prelude::*;
 pub struct VideoQueue {
     sync_point: Instant,
     inputs: HashMap<InputId, VideoQueueInput>,
+    failovers: HashMap<InputId, InputFailover>,
     event_emitter: Arc<EventEmitter>,
+    ctx: Arc<PipelineCtx>,
     ahead_of_time_processing: bool,
+    /// Input is considered stalled if its latest frame is older than this value.
+    stream_fallback_timeout: Duration,
+    /// Black and frozen video is reported if it lasts longer than this value.
+    media_alarm_threshold: Duration,
 }
 
 impl VideoQueue {
     pub fn new(
         sync_point: Instant,
-        event_emitter: Arc<EventEmitter>,
+        ctx: &Arc<PipelineCtx>,
         ahead_of_time_processing: bool,
+        stream_fallback_timeout: Duration,
+        media_alarm_threshold: Duration,
     ) -> Self {
         VideoQueue {
             inputs: HashMap::new(),
-            event_emitter,
+            failovers: HashMap::new(),
+            event_emitter: ctx.event_emitter.clone(),
+            ctx: ctx.clone(),
             sync_point,
             ahead_of_time_processing,
+            stream_fallback_timeout,
+            media_alarm_threshold,
         }
     }
 
@@ -54,6 +69,12 @@ impl VideoQueue {
                 shared_state,
 
                 offset_from_start: opts.offset,
+                replay_buffer: None,
+                monitor: VideoMonitor::new(
+                    MediaAlarmSource::Input(input_id.clone()),
+                    self.media_alarm_threshold,
+                    &self.ctx,
+                ),
 
                 emit_once_delivered_event: EmitEventOnce::new(
                     Event::VideoInputStreamDelivered(input_id.clone()),
@@ -73,6 +94,32 @@ impl VideoQueue {
 
     pub fn remove_input(&mut self, input_id: &InputId) {
         self.inputs.remove(input_id);
+        self.failovers.remove(input_id);
+    }
+
+    pub fn set_input_failover(&mut self, input_id: &InputId, backups: Vec<InputId>) {
+        // input without this track does not need a replacement
+        if !self.inputs.contains_key(input_id) {
+            return;
+        }
+        let failover = InputFailover::new(
+            input_id.clone(),
+            backups,
+            self.stream_fallback_timeout,
+            Event::VideoInputFailover,
+            &self.event_emitter,
+        );
+        self.failovers.insert(input_id.clone(), failover);
+    }
+
+    pub fn remove_input_failover(&mut self, input_id: &InputId) {
+        self.failovers.remove(input_id);
+    }
+
+    pub fn set_replay_buffer(&mut self, input_id: &InputId, replay_buffer: ReplayBuffer) {
+        if let Some(input) = self.inputs.get_mut(input_id) {
+            input.replay_buffer = Some(replay_buffer);
+        }
     }
 
     /// Gets frames closest to buffer pts. It does not check whether input is ready
@@ -81,26 +128,34 @@ impl VideoQueue {
         &mut self,
         buffer_pts: Duration,
         queue_start_pts: Duration,
+        outputs: HashSet<OutputId>,
     ) -> QueueVideoOutput {
         let mut required = false;
-        let frames = self
+        let mut frames: HashMap<InputId, PipelineEvent<Frame>> = self
             .inputs
             .iter_mut()
-            .filter_map(
-                |(input_id, input)| match input.get_frame(buffer_pts, queue_start_pts) {
-                    Some(frame_event) => {
-                        required = required || frame_event.required;
-                        Some((input_id.clone(), frame_event.event))
-                    }
-                    None => None,
-                },
-            )
+            .filter_map(|(input_id, input)| {
+                let frame_event = input.get_frame(buffer_pts, queue_start_pts)?;
+                required = required || frame_event.required;
+                if let PipelineEvent::Data(frame) = &frame_event.event {
+                    input.monitor.process(frame, buffer_pts);
+                }
+                Some((input_id.clone(), frame_event.event))
+            })
             .collect();
 
+        apply_failovers(
+            &mut self.failovers,
+            &mut frames,
+            buffer_pts,
+            self.stream_fallback_timeout,
+        );
+
         QueueVideoOutput {
             frames,
             required,
             pts: buffer_pts,
+            outputs,
         }
     }
 
@@ -147,6 +202,46 @@ struct FrameEvent {
     event: PipelineEvent<Frame>,
 }
 
+/// Replaces frames of stalled inputs with frames from their backups. If no
+/// backup is available the frame is removed, so the renderer can show a slate.
+/// Input without any frame did not start yet, it is replaced only after the
+/// fallback timeout.
+fn apply_failovers(
+    failovers: &mut HashMap<InputId, InputFailover>,
+    frames: &mut HashMap<InputId, PipelineEvent<Frame>>,
+    buffer_pts: Duration,
+    stream_fallback_timeout: Duration,
+) {
+    let is_available = |id: &InputId| match frames.get(id) {
+        Some(PipelineEvent::Data(frame)) => frame.pts + stream_fallback_timeout >= buffer_pts,
+        _ => false,
+    };
+    // Resolve all substitutions before modifying frames, so an input that is
+    // a backup and has failover configured itself is always checked as is.
+    let substitutions: Vec<_> = failovers
+        .iter_mut()
+        // EOS has to be delivered to the rest of the pipeline as is
+        .filter(|(input_id, _)| !matches!(frames.get(*input_id), Some(PipelineEvent::EOS)))
+        .filter_map(|(input_id, failover)| {
+            let is_started = frames.contains_key(input_id);
+            match failover.select(buffer_pts, is_started, &is_available) {
+                FailoverSource::Primary => None,
+                FailoverSource::Backup(backup_id) => {
+                    Some((input_id.clone(), frames.get(&backup_id).cloned()))
+                }
+                FailoverSource::Missing => Some((input_id.clone(), None)),
+            }
+        })
+        .collect();
+
+    for (input_id, frame) in substitutions {
+        match frame {
+            Some(frame) => frames.insert(input_id, frame),
+            None => frames.remove(&input_id),
+        };
+    }
+}
+
 pub struct VideoQueueInput {
     /// Frames are PTS ordered where PTS=0 represents beginning of the stream.
     queue: VecDeque<Frame>,
@@ -159,6 +254,9 @@ pub struct VideoQueueInput {
     /// Offset of the stream relative to the start. If set to `None`
     /// offset will be resolved automatically on the stream start.
     offset_from_start: Option<Duration>,
+    /// Records enqueued media if replay buffer is enabled for this input.
+    replay_buffer: Option<ReplayBuffer>,
+    monitor: VideoMonitor,
 
     eos_received: bool,
 
@@ -323,7 +421,7 @@ impl VideoQueueInput {
             match self.receiver.try_recv()? {
                 PipelineEvent::Data(frame) => {
                     let _ = self.shared_state.get_or_init_first_pts(frame.pts);
-                    self.queue.push_back(frame);
+                    self.push_frame(frame.pts, frame, queue_start_pts);
                 }
                 PipelineEvent::EOS => self.eos_received = true,
             };
@@ -335,9 +433,10 @@ impl VideoQueueInput {
             match self.receiver.try_recv()? {
                 // pts start from sync point
                 PipelineEvent::Data(mut frame) => {
+                    let input_pts = frame.pts;
                     let first_pts = self.shared_state.get_or_init_first_pts(frame.pts);
                     frame.pts = offset_pts + frame.pts - first_pts;
-                    self.queue.push_back(frame);
+                    self.push_frame(input_pts, frame, queue_start_pts);
                 }
                 PipelineEvent::EOS => self.eos_received = true,
             };
@@ -345,9 +444,134 @@ impl VideoQueueInput {
         Ok(())
     }
 
+    /// `input_pts` is the PTS of the frame before the offset was applied.
+    fn push_frame(&mut self, input_pts: Duration, frame: Frame, queue_start_pts: Option<Duration>) {
+        let pts = queue_start_pts.and_then(|start| frame.pts.checked_sub(start));
+        if let (Some(replay_buffer), Some(pts)) = (&self.replay_buffer, pts) {
+            replay_buffer.set_video_pts_mapping(input_pts, pts);
+        }
+        self.queue.push_back(frame);
+    }
+
     /// Offset value calculated in form of PTS(relative to sync point)
     fn offset_pts(&self, queue_start_pts: Duration) -> Option<Duration> {
         self.offset_from_start
             .map(|offset| queue_start_pts + offset)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use smelter_render::{ColorInfo, FrameData, YuvPlanes};
+
+    use super::*;
+
+    fn input_id(id: &str) -> InputId {
+        InputId(id.into())
+    }
+
+    fn frame(pts_ms: u64) -> PipelineEvent<Frame> {
+        PipelineEvent::Data(Frame {
+            data: FrameData::PlanarYuv420(YuvPlanes {
+                y_plane: Bytes::new(),
+                u_plane: Bytes::new(),
+                v_plane: Bytes::new(),
+            }),
+            resolution: Resolution {
+                width: 0,
+                height: 0,
+            },
+            pts: Duration::from_millis(pts_ms),
+            color: ColorInfo::default(),
+        })
+    }
+
+    fn frame_pts_ms(frames: &HashMap<InputId, PipelineEvent<Frame>>, id: &str) -> Option<u128> {
+        match frames.get(&input_id(id))? {
+            PipelineEvent::Data(frame) => Some(frame.pts.as_millis()),
+            PipelineEvent::EOS => panic!("Unexpected EOS for {id}"),
+        }
+    }
+
+    #[test]
+    fn test_apply_failovers() {
+        let event_emitter = Arc::new(EventEmitter::new());
+        let mut failovers: HashMap<InputId, InputFailover> = [
+            ("stalled", "backup"),
+            ("delivering", "backup"),
+            ("missing", "missing_backup"),
+            ("ended", "backup"),
+        ]
+        .into_iter()
+        .map(|(id, backup_id)| {
+            let failover = InputFailover::new(
+                input_id(id),
+                vec![input_id(backup_id)],
+                Duration::from_millis(500),
+                Event::VideoInputFailover,
+                &event_emitter,
+            );
+            (input_id(id), failover)
+        })
+        .collect();
+        let mut frames: HashMap<InputId, PipelineEvent<Frame>> = [
+            (input_id("stalled"), frame(400)),
+            (input_id("delivering"), frame(600)),
+            (input_id("ended"), PipelineEvent::EOS),
+            (input_id("backup"), frame(1000)),
+        ]
+        .into_iter()
+        .collect();
+
+        apply_failovers(
+            &mut failovers,
+            &mut frames,
+            Duration::from_millis(1000),
+            Duration::from_millis(500),
+        );
+
+        // Latest frame is older than the timeout, so the backup frame is used.
+        assert_eq!(frame_pts_ms(&frames, "stalled"), Some(1000));
+        assert_eq!(frame_pts_ms(&frames, "delivering"), Some(600));
+        // Without any frame the renderer shows a slate.
+        assert_eq!(frame_pts_ms(&frames, "missing"), None);
+        assert!(matches!(
+            frames.get(&input_id("ended")),
+            Some(PipelineEvent::EOS)
+        ));
+    }
+
+    #[test]
+    fn test_apply_failovers_before_input_start() {
+        let event_emitter = Arc::new(EventEmitter::new());
+        let mut failovers: HashMap<InputId, InputFailover> = [(
+            input_id("not_started"),
+            InputFailover::new(
+                input_id("not_started"),
+                vec![input_id("backup")],
+                Duration::from_millis(500),
+                Event::VideoInputFailover,
+                &event_emitter,
+            ),
+        )]
+        .into_iter()
+        .collect();
+        let mut apply = |pts_ms| {
+            let mut frames: HashMap<InputId, PipelineEvent<Frame>> =
+                [(input_id("backup"), frame(pts_ms))].into_iter().collect();
+            apply_failovers(
+                &mut failovers,
+                &mut frames,
+                Duration::from_millis(pts_ms),
+                Duration::from_millis(500),
+            );
+            frame_pts_ms(&frames, "not_started")
+        };
+
+        // Input registered at 1000ms is not replaced before the timeout passes.
+        assert_eq!(apply(1000), None);
+        assert_eq!(apply(1500), None);
+        assert_eq!(apply(1600), Some(1600));
+    }
+}
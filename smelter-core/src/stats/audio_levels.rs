@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use smelter_render::{InputId, OutputId};
+
+/// Levels of an audio signal measured by the mixer. Silence is reported as
+/// [`AudioLevels::MIN_LEVEL`] instead of negative infinity.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AudioLevels {
+    /// Highest absolute sample value since the previous measurement (dBFS).
+    pub peak_dbfs: f64,
+    /// RMS of samples since the previous measurement (dBFS).
+    pub rms_dbfs: f64,
+    /// Loudness of the last 3 seconds as defined in EBU R128 (LUFS).
+    pub short_term_lufs: f64,
+}
+
+impl AudioLevels {
+    pub const MIN_LEVEL: f64 = -100.0;
+
+    pub fn silence() -> Self {
+        Self {
+            peak_dbfs: Self::MIN_LEVEL,
+            rms_dbfs: Self::MIN_LEVEL,
+            short_term_lufs: Self::MIN_LEVEL,
+        }
+    }
+}
+
+/// The most recent levels of all inputs (before volume is applied) and all
+/// output mixes (after mixing strategy is applied).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AudioLevelsReport {
+    pub inputs: HashMap<String, AudioLevels>,
+    pub outputs: HashMap<String, AudioLevels>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AudioLevelsStatsEvent {
+    pub inputs: HashMap<InputId, AudioLevels>,
+    pub outputs: HashMap<OutputId, AudioLevels>,
+}
@@ -29,6 +29,8 @@ impl InputStatsState {
             InputProtocolKind::Rtp => unimplemented!(),
             InputProtocolKind::Rtmp => unimplemented!(),
             InputProtocolKind::Mp4 => unimplemented!(),
+            InputProtocolKind::Playlist => unimplemented!(),
+            InputProtocolKind::Replay => unimplemented!(),
             InputProtocolKind::Hls => InputStatsState::Hls(HlsInputState::new()),
             InputProtocolKind::V4l2 => unimplemented!(),
             InputProtocolKind::DeckLink => unimplemented!(),
@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use smelter_render::{InputId, OutputId};
+
+use crate::event::MediaAlarm;
+
+/// Media alarms active on an input or an output.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct MediaAlarms {
+    pub silence: bool,
+    pub black: bool,
+    pub frozen: bool,
+}
+
+impl MediaAlarms {
+    pub(super) fn set(&mut self, alarm: MediaAlarm, active: bool) {
+        match alarm {
+            MediaAlarm::Silence => self.silence = active,
+            MediaAlarm::Black => self.black = active,
+            MediaAlarm::Frozen => self.frozen = active,
+        }
+    }
+
+    pub(super) fn is_any_active(&self) -> bool {
+        self.silence || self.black || self.frozen
+    }
+}
+
+/// Media alarms of all inputs and outputs. Only inputs and outputs with at least
+/// one active alarm are included in the report.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MediaAlarmsReport {
+    pub inputs: HashMap<String, MediaAlarms>,
+    pub outputs: HashMap<String, MediaAlarms>,
+}
+
+/// Input or output monitored for media alarms.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum MediaAlarmSource {
+    Input(InputId),
+    Output(OutputId),
+}
@@ -10,18 +10,27 @@ use tracing::warn;
 
 use crate::stats::{input_reports::InputStatsReport, state::StatsState};
 
+mod audio_levels;
 mod input_events;
 mod input_reports;
 mod input_state;
+mod media_alarms;
 mod state;
 mod utils;
 
+pub use audio_levels::{AudioLevels, AudioLevelsReport};
+pub use media_alarms::{MediaAlarms, MediaAlarmsReport};
+
+pub(crate) use audio_levels::AudioLevelsStatsEvent;
 pub(crate) use input_events::*;
+pub(crate) use media_alarms::MediaAlarmSource;
 pub(crate) use state::StatsEvent;
 
 #[derive(Debug, Serialize, Clone)]
 pub struct StatsReport {
     pub inputs: HashMap<String, InputStatsReport>,
+    pub audio_levels: AudioLevelsReport,
+    pub media_alarms: MediaAlarmsReport,
 }
 
 pub(crate) struct StatsMonitor(Arc<Mutex<StatsState>>);
@@ -55,6 +64,8 @@ impl StatsMonitor {
                 .iter_mut()
                 .map(|(input_ref, (_, input))| (input_ref.to_unique_string(), input.report()))
                 .collect(),
+            audio_levels: guard.audio_levels.clone(),
+            media_alarms: guard.media_alarms_report(),
         }
     }
 }
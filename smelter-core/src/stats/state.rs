@@ -3,12 +3,20 @@ use std::time::{Duration, Instant};
 
 use smelter_render::InputId;
 
-use crate::stats::input_state::InputStatsState;
+use crate::{
+    event::MediaAlarm,
+    stats::{
+        AudioLevelsReport, AudioLevelsStatsEvent, MediaAlarmSource, MediaAlarms, MediaAlarmsReport,
+        input_state::InputStatsState,
+    },
+};
 
 use crate::prelude::*;
 
 pub(crate) struct StatsState {
     pub inputs: HashMap<Ref<InputId>, (Instant, InputStatsState)>,
+    pub audio_levels: AudioLevelsReport,
+    pub media_alarms: HashMap<MediaAlarmSource, MediaAlarms>,
 }
 
 #[derive(Debug, Clone)]
@@ -21,6 +29,12 @@ pub(crate) enum StatsEvent {
         input_ref: Ref<InputId>,
         kind: InputProtocolKind,
     },
+    AudioLevels(AudioLevelsStatsEvent),
+    MediaAlarm {
+        source: MediaAlarmSource,
+        alarm: MediaAlarm,
+        active: bool,
+    },
 }
 
 impl IntoIterator for StatsEvent {
@@ -36,6 +50,8 @@ impl StatsState {
     pub fn new() -> Self {
         Self {
             inputs: HashMap::new(),
+            audio_levels: AudioLevelsReport::default(),
+            media_alarms: HashMap::new(),
         }
     }
 
@@ -57,10 +73,50 @@ impl StatsState {
                 self.inputs
                     .insert(input_ref, (now, InputStatsState::new(kind)));
             }
+            StatsEvent::AudioLevels(event) => {
+                self.audio_levels = AudioLevelsReport {
+                    inputs: event
+                        .inputs
+                        .into_iter()
+                        .map(|(input_id, levels)| (input_id.to_string(), levels))
+                        .collect(),
+                    outputs: event
+                        .outputs
+                        .into_iter()
+                        .map(|(output_id, levels)| (output_id.to_string(), levels))
+                        .collect(),
+                };
+            }
+            StatsEvent::MediaAlarm {
+                source,
+                alarm,
+                active,
+            } => {
+                let alarms = self.media_alarms.entry(source.clone()).or_default();
+                alarms.set(alarm, active);
+                if !alarms.is_any_active() {
+                    self.media_alarms.remove(&source);
+                }
+            }
         }
 
         // drop inputs that did not have an update for 5 minutes
         self.inputs
             .retain(|_, (updated_at, _)| *updated_at + Duration::from_secs(300) > now);
     }
+
+    pub fn media_alarms_report(&self) -> MediaAlarmsReport {
+        let mut report = MediaAlarmsReport::default();
+        for (source, alarms) in &self.media_alarms {
+            match source {
+                MediaAlarmSource::Input(input_id) => {
+                    report.inputs.insert(input_id.to_string(), *alarms)
+                }
+                MediaAlarmSource::Output(output_id) => {
+                    report.outputs.insert(output_id.to_string(), *alarms)
+                }
+            };
+        }
+        report
+    }
 }
@@ -2,7 +2,7 @@ use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use futures::future::join_all;
 use js_sys::Object;
-use smelter_render::{Frame, FrameData, FrameSet, InputId};
+use smelter_render::{ColorInfo, Frame, FrameData, FrameSet, InputId};
 use tracing::error;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
@@ -122,6 +122,7 @@ impl RendererInputs {
             resolution: texture.size().into(),
             data: FrameData::Rgba8UnormWgpuTexture(texture),
             pts,
+            color: ColorInfo::default(),
         })
     }
 
@@ -149,6 +150,7 @@ impl RendererInputs {
             data: FrameData::Rgba8UnormWgpuTexture(texture),
             resolution: size.into(),
             pts,
+            color: ColorInfo::default(),
         })
     }
 
@@ -179,6 +181,7 @@ impl RendererInputs {
             resolution: texture.size().into(),
             data: FrameData::Rgba8UnormWgpuTexture(texture),
             pts,
+            color: ColorInfo::default(),
         })
     }
 
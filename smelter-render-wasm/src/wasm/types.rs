@@ -79,7 +79,9 @@ impl From<FrameFormat> for smelter_render::OutputFrameFormat {
     fn from(value: FrameFormat) -> Self {
         match value {
             FrameFormat::RgbaBytes => smelter_render::OutputFrameFormat::RgbaWgpuTexture,
-            FrameFormat::YuvBytes => smelter_render::OutputFrameFormat::PlanarYuv420Bytes,
+            FrameFormat::YuvBytes => {
+                smelter_render::OutputFrameFormat::PlanarYuv420Bytes(Default::default())
+            }
         }
     }
 }
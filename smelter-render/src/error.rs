@@ -1,7 +1,8 @@
+use crate::transformations::video_clip::VideoClipError;
 use crate::transformations::web_renderer::CreateWebRendererError;
 use crate::wgpu::CreateWgpuCtxError;
 use crate::wgpu::common_pipeline::CreateShaderError;
-use crate::{OutputId, RendererId};
+use crate::{InputId, OutputId, RendererId};
 use crate::{registry, scene::SceneError, transformations::image::ImageError};
 
 pub use crate::registry::RegisterError;
@@ -27,6 +28,9 @@ pub enum RegisterRendererError {
     #[error("Failed to register image \"{1}\".")]
     Image(#[source] ImageError, RendererId),
 
+    #[error("Failed to register video clip \"{1}\".")]
+    VideoClip(#[source] VideoClipError, RendererId),
+
     #[error("Failed to register web renderer \"{1}\".")]
     Web(#[source] Box<CreateWebRendererError>, RendererId),
 }
@@ -37,6 +41,12 @@ pub enum UnregisterRendererError {
     RendererRegistry(#[from] registry::UnregisterError),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum SetInputSlateError {
+    #[error("Image \"{0}\" does not exist. Register it first before using it as a slate.")]
+    ImageNotFound(RendererId),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum RenderSceneError {
     #[error(transparent)]
@@ -65,6 +75,15 @@ pub enum UpdateSceneError {
     AudioVideoNotMatching(OutputId),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateFragmentError {
+    #[error(transparent)]
+    FragmentRegistry(#[from] registry::UpdateError),
+
+    #[error(transparent)]
+    UpdateScene(#[from] UpdateSceneError),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum RequestKeyframeError {
     #[error("Output \"{0}\" does not exist, register it first before requesting keyframe.")]
@@ -75,6 +94,24 @@ pub enum RequestKeyframeError {
     NoVideoOutput(OutputId),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("Output \"{0}\" does not exist or it does not produce video.")]
+    OutputNotFound(OutputId),
+
+    #[error("Input \"{0}\" does not exist.")]
+    InputNotFound(InputId),
+
+    #[error("No frame is available yet.")]
+    NoFrame,
+
+    #[error("Failed to download frame from GPU.")]
+    DownloadFailed(#[from] wgpu::BufferAsyncError),
+
+    #[error("Failed to download frame from GPU. Buffer mapping was canceled.")]
+    DownloadCanceled,
+}
+
 pub struct ErrorStack<'a>(Option<&'a dyn std::error::Error>);
 
 impl<'a> ErrorStack<'a> {
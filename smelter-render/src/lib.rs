@@ -12,9 +12,12 @@ mod types;
 pub use types::*;
 
 pub use registry::RegistryType;
+pub use state::OutputSceneUpdate;
+pub use state::PreparedRenderer;
 pub use state::Renderer;
 pub use state::RendererOptions;
 pub use state::RendererSpec;
+pub use state::{RgbaSnapshot, SnapshotSource};
 
 pub use wgpu::{WgpuFeatures, required_wgpu_features, set_required_wgpu_limits};
 
@@ -22,6 +25,12 @@ pub mod image {
     pub use crate::transformations::image::{ImageSource, ImageSpec, ImageType};
 }
 
+pub mod video_clip {
+    pub use crate::transformations::video_clip::{
+        MAX_VIDEO_CLIP_SIZE, VideoClipSpec, video_clip_size,
+    };
+}
+
 pub mod shader {
     pub use crate::transformations::shader::ShaderSpec;
 }
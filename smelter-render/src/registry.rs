@@ -20,11 +20,20 @@ pub struct UnregisterError {
     renderer_id: RendererId,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to update a {item_type}. The \"{renderer_id}\" {item_type} does not exist.")]
+pub struct UpdateError {
+    item_type: &'static str,
+    renderer_id: RendererId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RegistryType {
     Shader,
     WebRenderer,
     Image,
+    VideoClip,
+    Fragment,
 }
 
 impl RegistryType {
@@ -33,6 +42,8 @@ impl RegistryType {
             RegistryType::Shader => "shader",
             RegistryType::WebRenderer => "web renderer instance",
             RegistryType::Image => "image",
+            RegistryType::VideoClip => "video clip",
+            RegistryType::Fragment => "scene fragment",
         }
     }
 }
@@ -54,6 +65,10 @@ impl<T: Clone> RendererRegistry<T> {
         self.registry.get(key).cloned()
     }
 
+    pub(crate) fn contains(&self, key: &RendererId) -> bool {
+        self.registry.contains_key(key)
+    }
+
     pub(crate) fn register(&mut self, id: RendererId, renderer: T) -> Result<(), RegisterError> {
         if self.registry.contains_key(&id) {
             return Err(RegisterError::KeyTaken {
@@ -67,6 +82,17 @@ impl<T: Clone> RendererRegistry<T> {
         Ok(())
     }
 
+    /// Replaces already registered value and returns the previous one.
+    pub(crate) fn update(&mut self, id: &RendererId, renderer: T) -> Result<T, UpdateError> {
+        match self.registry.get_mut(id) {
+            Some(entry) => Ok(std::mem::replace(entry, renderer)),
+            None => Err(UpdateError {
+                item_type: self.registry_type.registry_item_name(),
+                renderer_id: id.clone(),
+            }),
+        }
+    }
+
     pub(crate) fn unregister(&mut self, id: &RendererId) -> Result<(), UnregisterError> {
         match self.registry.remove(id) {
             Some(_) => Ok(()),
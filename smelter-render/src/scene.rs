@@ -3,6 +3,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::scene::image_component::ImageRenderParams;
+use crate::scene::video_clip_component::VideoClipRenderParams;
 use crate::transformations::shader::Shader;
 use crate::transformations::shader::validation::error::ParametersValidationError;
 use crate::transformations::text_renderer::TextRenderParams;
@@ -12,30 +13,40 @@ use crate::{InputId, OutputId, RendererId, Resolution};
 use self::image_component::StatefulImageComponent;
 use self::input_stream_component::StatefulInputStreamComponent;
 use self::layout::StatefulLayoutComponent;
+use self::mask_component::StatefulMaskComponent;
 use self::scene_state::{BuildStateTreeCtx, IntermediateNode};
 use self::shader_component::StatefulShaderComponent;
 use self::text_component::StatefulTextComponent;
+use self::video_clip_component::StatefulVideoClipComponent;
 use self::web_view_component::StatefulWebViewComponent;
 
 pub(crate) use layout::LayoutNode;
+pub(crate) use mask_component::MaskComponentParams;
+pub use patch::{PatchableComponent, ScenePatch};
 pub(crate) use scene_state::{OutputNode, SceneState};
 pub(crate) use shader_component::ShaderComponentParams;
 
 pub use components::*;
 pub use types::*;
 
+mod animation;
 mod components;
+mod fragment_component;
 pub(super) mod image_component;
 mod input_stream_component;
 mod layout;
+mod mask_component;
+mod patch;
 mod rescaler_component;
 mod scene_state;
 mod shader_component;
 mod text_component;
+mod ticker_component;
 mod tiles_component;
 mod transition;
 mod types;
 mod validation;
+pub(super) mod video_clip_component;
 mod view_component;
 mod web_view_component;
 
@@ -46,16 +57,30 @@ pub struct OutputScene {
     pub resolution: Resolution,
 }
 
+/// Position of a component on the output frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentLayout {
+    pub component_id: ComponentId,
+    pub top: f32,
+    pub left: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
 #[derive(Debug, Clone)]
 pub enum Component {
     InputStream(InputStreamComponent),
     Shader(ShaderComponent),
     WebView(WebViewComponent),
     Image(ImageComponent),
+    VideoClip(VideoClipComponent),
     Text(TextComponent),
+    Mask(MaskComponent),
     View(ViewComponent),
     Tiles(TilesComponent),
     Rescaler(RescalerComponent),
+    Ticker(TickerComponent),
+    Fragment(FragmentComponent),
 }
 
 /// Stateful version of a `Component`. Represents the same element as
@@ -68,7 +93,9 @@ enum StatefulComponent {
     Shader(StatefulShaderComponent),
     WebView(StatefulWebViewComponent),
     Image(StatefulImageComponent),
+    VideoClip(StatefulVideoClipComponent),
     Text(StatefulTextComponent),
+    Mask(StatefulMaskComponent),
     Layout(Box<StatefulLayoutComponent>),
 }
 
@@ -94,7 +121,9 @@ pub(crate) enum NodeParams {
     Shader(ShaderComponentParams, Arc<Shader>),
     Web(Vec<ComponentId>, Arc<WebRenderer>),
     Image(ImageRenderParams),
+    VideoClip(VideoClipRenderParams),
     Text(TextRenderParams),
+    Mask(MaskComponentParams),
     Layout(LayoutNode),
 }
 
@@ -105,7 +134,9 @@ impl StatefulComponent {
             StatefulComponent::Shader(shader) => Some(shader.component.size.width),
             StatefulComponent::WebView(web) => Some(web.size().width),
             StatefulComponent::Image(image) => Some(image.width()),
+            StatefulComponent::VideoClip(clip) => Some(clip.width()),
             StatefulComponent::Text(text) => Some(text.width()),
+            StatefulComponent::Mask(mask) => Some(mask.component.size.width),
             StatefulComponent::Layout(layout) => match layout.position(pts) {
                 Position::Static { width, .. } => width,
                 Position::Absolute(position) => position.width,
@@ -119,7 +150,9 @@ impl StatefulComponent {
             StatefulComponent::Shader(shader) => Some(shader.component.size.height),
             StatefulComponent::WebView(web) => Some(web.size().height),
             StatefulComponent::Image(image) => Some(image.height()),
+            StatefulComponent::VideoClip(clip) => Some(clip.height()),
             StatefulComponent::Text(text) => Some(text.height()),
+            StatefulComponent::Mask(mask) => Some(mask.component.size.height),
             StatefulComponent::Layout(layout) => match layout.position(pts) {
                 Position::Static { height, .. } => height,
                 Position::Absolute(position) => position.height,
@@ -133,11 +166,14 @@ impl StatefulComponent {
             StatefulComponent::Shader(shader) => shader.intermediate_node(),
             StatefulComponent::WebView(web) => web.intermediate_node(),
             StatefulComponent::Image(image) => image.intermediate_node(),
+            StatefulComponent::VideoClip(clip) => clip.intermediate_node(),
             StatefulComponent::Text(text) => text.intermediate_node(),
+            StatefulComponent::Mask(mask) => mask.intermediate_node(),
             StatefulComponent::Layout(layout) => match layout.deref() {
                 StatefulLayoutComponent::View(view) => view.intermediate_node(),
                 StatefulLayoutComponent::Tiles(tiles) => tiles.intermediate_node(),
                 StatefulLayoutComponent::Rescaler(rescaler) => rescaler.intermediate_node(),
+                StatefulLayoutComponent::Ticker(ticker) => ticker.intermediate_node(),
             },
         }
     }
@@ -148,7 +184,9 @@ impl StatefulComponent {
             StatefulComponent::Shader(shader) => shader.children.iter_mut().collect(),
             StatefulComponent::WebView(web) => web.children.iter_mut().collect(),
             StatefulComponent::Image(_) => vec![],
+            StatefulComponent::VideoClip(_) => vec![],
             StatefulComponent::Text(_) => vec![],
+            StatefulComponent::Mask(mask) => mask.children.iter_mut().collect(),
             StatefulComponent::Layout(layout) => layout.children_mut(),
         }
     }
@@ -159,7 +197,9 @@ impl StatefulComponent {
             StatefulComponent::Shader(shader) => shader.component_id(),
             StatefulComponent::WebView(web) => web.component_id(),
             StatefulComponent::Image(image) => image.component_id(),
+            StatefulComponent::VideoClip(clip) => clip.component_id(),
             StatefulComponent::Text(text) => text.component_id(),
+            StatefulComponent::Mask(mask) => mask.component_id(),
             StatefulComponent::Layout(layout) => layout.component_id(),
         }
     }
@@ -177,10 +217,17 @@ impl Component {
             Component::Shader(shader) => shader.stateful_component(ctx),
             Component::WebView(web_view) => web_view.stateful_component(ctx),
             Component::Image(image) => image.stateful_component(ctx),
+            Component::VideoClip(clip) => clip.stateful_component(ctx),
             Component::Text(text) => text.stateful_component(ctx),
+            Component::Mask(mask) => mask.stateful_component(ctx),
             Component::View(view) => view.stateful_component(ctx),
             Component::Tiles(tiles) => tiles.stateful_component(ctx),
             Component::Rescaler(rescaler) => rescaler.stateful_component(ctx),
+            Component::Ticker(ticker) => ticker.stateful_component(ctx),
+            // Fragments are replaced with their content before the state tree is built.
+            Component::Fragment(fragment) => {
+                Err(SceneError::FragmentNotFound(fragment.fragment_id))
+            }
         }
     }
 }
@@ -200,6 +247,11 @@ pub enum SceneError {
     )]
     ImageNotFound(RendererId),
 
+    #[error(
+        "Video clip \"{0}\" does not exist. You have to register it first before using it in the scene definition."
+    )]
+    VideoClipNotFound(RendererId),
+
     #[error(
         "Shader \"{0}\" does not exist. You have to register it first before using it in the scene definition."
     )]
@@ -225,4 +277,37 @@ pub enum SceneError {
         "More than one component has an id \"{0}\". Component IDs in scene definition need to be unique."
     )]
     DuplicateComponentId(ComponentId),
+
+    #[error(
+        "Output \"{0}\" does not have a scene yet. Send a full scene update before patching it."
+    )]
+    PatchOutputSceneNotFound(OutputId),
+
+    #[error("Component with an id \"{0}\" does not exist in the current scene.")]
+    PatchComponentNotFound(ComponentId),
+
+    #[error(
+        "Component \"{0}\" does not support inserting children. Only \"View\", \"Tiles\", \"Ticker\", \"Shader\" and \"WebView\" components have a list of children."
+    )]
+    PatchParentWithoutChildrenList(ComponentId),
+
+    #[error("Index {index} is out of bounds, component \"{parent_id}\" has only {len} children.")]
+    PatchIndexOutOfBounds {
+        parent_id: ComponentId,
+        index: usize,
+        len: usize,
+    },
+
+    #[error(
+        "Component \"{0}\" can't be removed, because it is a root of the scene or a required child of its parent. Replace it instead."
+    )]
+    PatchRemoveRequiredComponent(ComponentId),
+
+    #[error(
+        "Scene fragment \"{0}\" does not exist. You have to register it first before using it in the scene definition."
+    )]
+    FragmentNotFound(RendererId),
+
+    #[error("Scene fragment \"{0}\" contains a reference to itself.")]
+    RecursiveFragment(RendererId),
 }
@@ -0,0 +1,231 @@
+use std::time::Duration;
+
+use super::{
+    AbsolutePosition, Animation, AnimationRepeat, HorizontalPosition, KeyframeProperties, Position,
+    VerticalPosition, types::interpolation::ContinuousValue,
+};
+
+#[derive(Debug, Clone)]
+pub(super) struct AnimationState {
+    animation: Animation,
+
+    /// PTS of a first frame of the animation.
+    start_pts: Duration,
+}
+
+impl AnimationState {
+    /// Keeps the start of the previous animation if its definition did not change,
+    /// otherwise the animation starts from the beginning.
+    pub fn new(
+        animation: Option<Animation>,
+        previous_animation: Option<&AnimationState>,
+        last_pts: Duration,
+    ) -> Option<Self> {
+        let animation = animation?;
+        let start_pts = match previous_animation {
+            Some(previous) if previous.animation == animation => previous.start_pts,
+            _ => last_pts,
+        };
+        Some(Self {
+            animation,
+            start_pts,
+        })
+    }
+
+    pub fn properties(&self, pts: Duration) -> KeyframeProperties {
+        let time = self.animation_time(pts);
+        KeyframeProperties {
+            width: self.property(time, |p| p.width),
+            height: self.property(time, |p| p.height),
+            top: self.property(time, |p| p.top),
+            bottom: self.property(time, |p| p.bottom),
+            left: self.property(time, |p| p.left),
+            right: self.property(time, |p| p.right),
+            rotation: self.property(time, |p| p.rotation),
+            opacity: self.property(time, |p| p.opacity),
+        }
+    }
+
+    /// Time in seconds relative to the first keyframe, with repeat mode applied.
+    fn animation_time(&self, pts: Duration) -> f64 {
+        let elapsed = pts.saturating_sub(self.start_pts).as_secs_f64();
+        let duration = self
+            .animation
+            .keyframes
+            .last()
+            .map(|keyframe| keyframe.time.as_secs_f64())
+            .unwrap_or(0.0);
+        if duration <= 0.0 {
+            return elapsed;
+        }
+        match self.animation.repeat {
+            AnimationRepeat::None => elapsed,
+            AnimationRepeat::Loop => elapsed % duration,
+            AnimationRepeat::Alternate => {
+                let cycle = (elapsed / duration) as u64;
+                match cycle % 2 {
+                    0 => elapsed % duration,
+                    _ => duration - (elapsed % duration),
+                }
+            }
+        }
+    }
+
+    /// Interpolates value between keyframes that define the property. Before the first
+    /// and after the last of those keyframes the value is constant.
+    fn property(&self, time: f64, get: impl Fn(&KeyframeProperties) -> Option<f32>) -> Option<f32> {
+        let mut previous = None;
+        for keyframe in self.animation.keyframes.iter() {
+            let Some(value) = get(&keyframe.properties) else {
+                continue;
+            };
+            let keyframe_time = keyframe.time.as_secs_f64();
+            if time > keyframe_time {
+                previous = Some((keyframe, value));
+                continue;
+            }
+            let Some((previous_keyframe, previous_value)) = previous else {
+                return Some(value);
+            };
+            let previous_time = previous_keyframe.time.as_secs_f64();
+            let progress = match keyframe_time - previous_time {
+                segment if segment > 0.0 => (time - previous_time) / segment,
+                _ => 1.0,
+            };
+            let state = previous_keyframe.interpolation_kind.state(progress);
+            return Some(ContinuousValue::interpolate(&previous_value, &value, state));
+        }
+        previous.map(|(_, value)| value)
+    }
+}
+
+impl Position {
+    pub(super) fn with_animated_properties(self, properties: &KeyframeProperties) -> Self {
+        match self {
+            Position::Static { width, height } => Self::Static {
+                width: properties.width.or(width),
+                height: properties.height.or(height),
+            },
+            Position::Absolute(AbsolutePosition {
+                width,
+                height,
+                position_horizontal,
+                position_vertical,
+                rotation_degrees,
+            }) => Self::Absolute(AbsolutePosition {
+                width: properties.width.or(width),
+                height: properties.height.or(height),
+                position_horizontal: match (properties.left, properties.right) {
+                    (Some(left), _) => HorizontalPosition::LeftOffset(left),
+                    (None, Some(right)) => HorizontalPosition::RightOffset(right),
+                    (None, None) => position_horizontal,
+                },
+                position_vertical: match (properties.top, properties.bottom) {
+                    (Some(top), _) => VerticalPosition::TopOffset(top),
+                    (None, Some(bottom)) => VerticalPosition::BottomOffset(bottom),
+                    (None, None) => position_vertical,
+                },
+                rotation_degrees: properties.rotation.unwrap_or(rotation_degrees),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::{InterpolationKind, Keyframe};
+
+    fn keyframe(time_ms: u64, opacity: Option<f32>, left: Option<f32>) -> Keyframe {
+        Keyframe {
+            time: Duration::from_millis(time_ms),
+            interpolation_kind: InterpolationKind::Linear,
+            properties: KeyframeProperties {
+                opacity,
+                left,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn state(keyframes: Vec<Keyframe>, repeat: AnimationRepeat) -> AnimationState {
+        AnimationState::new(
+            Some(Animation { keyframes, repeat }),
+            None,
+            Duration::from_secs(10),
+        )
+        .unwrap()
+    }
+
+    fn opacity_at(state: &AnimationState, ms: u64) -> f32 {
+        state
+            .properties(Duration::from_secs(10) + Duration::from_millis(ms))
+            .opacity
+            .unwrap()
+    }
+
+    #[test]
+    fn test_animation_interpolation() {
+        let state = state(
+            vec![
+                keyframe(0, Some(0.0), Some(10.0)),
+                keyframe(1000, None, Some(20.0)),
+                keyframe(2000, Some(1.0), None),
+            ],
+            AnimationRepeat::None,
+        );
+        assert_eq!(opacity_at(&state, 0), 0.0);
+        assert_eq!(opacity_at(&state, 500), 0.25);
+        assert_eq!(opacity_at(&state, 2000), 1.0);
+        assert_eq!(opacity_at(&state, 5000), 1.0);
+
+        let properties = state.properties(Duration::from_millis(10_500));
+        assert_eq!(properties.left, Some(15.0));
+        assert_eq!(properties.width, None);
+    }
+
+    #[test]
+    fn test_animation_repeat() {
+        let keyframes = vec![
+            keyframe(0, Some(0.0), None),
+            keyframe(1000, Some(1.0), None),
+        ];
+
+        let looped = state(keyframes.clone(), AnimationRepeat::Loop);
+        assert_eq!(opacity_at(&looped, 250), 0.25);
+        assert_eq!(opacity_at(&looped, 1250), 0.25);
+
+        let alternate = state(keyframes, AnimationRepeat::Alternate);
+        assert_eq!(opacity_at(&alternate, 250), 0.25);
+        assert_eq!(opacity_at(&alternate, 1250), 0.75);
+        assert_eq!(opacity_at(&alternate, 2250), 0.25);
+    }
+
+    #[test]
+    fn test_animation_restart() {
+        let animation = Animation {
+            keyframes: vec![
+                keyframe(0, Some(0.0), None),
+                keyframe(1000, Some(1.0), None),
+            ],
+            repeat: AnimationRepeat::None,
+        };
+        let previous = AnimationState::new(Some(animation.clone()), None, Duration::ZERO);
+
+        let same = AnimationState::new(
+            Some(animation.clone()),
+            previous.as_ref(),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert_eq!(same.start_pts, Duration::ZERO);
+
+        let changed = Animation {
+            repeat: AnimationRepeat::Loop,
+            ..animation
+        };
+        let changed =
+            AnimationState::new(Some(changed), previous.as_ref(), Duration::from_secs(5)).unwrap();
+        assert_eq!(changed.start_pts, Duration::from_secs(5));
+    }
+}
@@ -3,8 +3,8 @@ use std::{fmt::Display, sync::Arc, time::Duration};
 use crate::{InputId, RendererId};
 
 use super::{
-    AbsolutePosition, BorderRadius, BoxShadow, Component, HorizontalAlign, InterpolationKind,
-    RGBAColor, Size, VerticalAlign,
+    AbsolutePosition, BlendMode, BorderRadius, BoxShadow, Component, HorizontalAlign,
+    InterpolationKind, RGBAColor, Size, VerticalAlign,
 };
 
 mod interpolation;
@@ -51,6 +51,33 @@ pub struct ShaderParamStructField {
     pub value: ShaderParam,
 }
 
+/// Renders `content` only where `mask` is opaque (or bright in `MaskMode::Luminance`).
+/// Both children are stretched to the size of the component.
+#[derive(Debug, Clone)]
+pub struct MaskComponent {
+    pub id: Option<ComponentId>,
+    pub content: Box<Component>,
+    pub mask: Box<Component>,
+
+    pub mode: MaskMode,
+    /// Content is rendered where the mask is transparent (or dark) instead.
+    pub invert: bool,
+
+    pub size: Size,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskMode {
+    Alpha,
+    Luminance,
+}
+
+/// Reference to a component tree registered as a scene fragment.
+#[derive(Debug, Clone)]
+pub struct FragmentComponent {
+    pub fragment_id: RendererId,
+}
+
 #[derive(Debug, Clone)]
 pub struct WebViewComponent {
     pub id: Option<ComponentId>,
@@ -65,12 +92,69 @@ pub struct ImageComponent {
     pub image_id: RendererId,
     pub width: Option<f32>,
     pub height: Option<f32>,
+    pub playback: ImagePlayback,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoClipComponent {
+    pub id: Option<ComponentId>,
+    pub clip_id: RendererId,
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    pub playback: ImagePlayback,
+}
+
+/// Playback options of animated content e.g. animated images or video clips.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImagePlayback {
+    /// Number of times the animation is played. `None` means that the animation
+    /// loops forever. After the last loop the final frame is displayed.
+    pub loop_count: Option<u32>,
+    /// If true, the animation starts from the beginning when the component is
+    /// added to the scene. Otherwise, it is synchronized with the output timeline.
+    pub restart_on_mount: bool,
+    /// Offset into the animation from which the playback starts.
+    pub start_at: Duration,
+}
+
+impl Default for ImagePlayback {
+    fn default() -> Self {
+        Self {
+            loop_count: None,
+            restart_on_mount: true,
+            start_at: Duration::ZERO,
+        }
+    }
+}
+
+impl ImagePlayback {
+    /// Position inside an animation of length `duration` that should be displayed at `pts`.
+    /// Returns `None` if all loops were already played.
+    pub(crate) fn animation_pts(
+        &self,
+        start_pts: Duration,
+        pts: Duration,
+        duration: Duration,
+    ) -> Option<Duration> {
+        let elapsed = pts.saturating_sub(start_pts) + self.start_at;
+        let is_finished = self.loop_count.is_some_and(|loop_count| {
+            elapsed.as_nanos() >= duration.as_nanos() * loop_count as u128
+        });
+        match is_finished {
+            true => None,
+            false => Some(Duration::from_nanos(
+                (elapsed.as_nanos() % duration.as_nanos()) as u64,
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct TextComponent {
     pub id: Option<ComponentId>,
-    pub text: Arc<str>,
+    /// Text split into fragments with different styles. Fields that are not
+    /// defined on a span fall back to the values defined on the component.
+    pub spans: Vec<TextSpan>,
     /// in pixels
     pub font_size: f32,
     /// in pixels, default: same as font_size
@@ -85,6 +169,37 @@ pub struct TextComponent {
     pub wrap: TextWrap,
     pub background_color: RGBAColor,
     pub dimensions: TextDimensions,
+    /// in pixels, additional space between letters
+    pub letter_spacing: f32,
+    pub outline: Option<TextOutline>,
+    pub shadow: Option<TextShadow>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TextSpan {
+    pub text: Arc<str>,
+    pub color: Option<RGBAColor>,
+    pub font_family: Option<Arc<str>>,
+    pub style: Option<TextStyle>,
+    pub weight: Option<TextWeight>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TextOutline {
+    /// in pixels
+    pub width: f32,
+    pub color: RGBAColor,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TextShadow {
+    /// in pixels
+    pub offset_x: f32,
+    /// in pixels
+    pub offset_y: f32,
+    /// in pixels
+    pub blur_radius: f32,
+    pub color: RGBAColor,
 }
 
 #[derive(Debug, Clone)]
@@ -141,6 +256,7 @@ pub struct ViewComponent {
     pub direction: ViewChildrenDirection,
     pub position: Position,
     pub transition: Option<Transition>,
+    pub animation: Option<Animation>,
     pub overflow: Overflow,
 
     pub background_color: RGBAColor,
@@ -152,6 +268,19 @@ pub struct ViewComponent {
     pub box_shadow: Vec<BoxShadow>,
 
     pub padding: Padding,
+
+    /// Space between static children (and between lines if `wrap` is enabled).
+    pub gap: f32,
+    pub justify_content: JustifyContent,
+    pub align_items: AlignItems,
+    /// Move static children that do not fit into the next row (or column).
+    pub wrap: bool,
+
+    /// Resizing of this component if its parent is a `View`.
+    pub flex: Flex,
+
+    pub opacity: f32,
+    pub blend_mode: BlendMode,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -168,6 +297,54 @@ pub struct Transition {
     pub should_interrupt: bool,
 }
 
+/// Keyframe animation of component properties. Time is measured from the moment
+/// the component with this animation was added to the scene.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    /// Keyframes sorted by time.
+    pub keyframes: Vec<Keyframe>,
+    pub repeat: AnimationRepeat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    /// Time since the start of the animation.
+    pub time: Duration,
+    /// Interpolation used from this keyframe to the next keyframe that
+    /// defines the same property.
+    pub interpolation_kind: InterpolationKind,
+    pub properties: KeyframeProperties,
+}
+
+/// Property values that override values defined on the component. Properties
+/// that are not defined in any keyframe are not animated.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct KeyframeProperties {
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    /// Only applies to absolutely positioned components.
+    pub top: Option<f32>,
+    /// Only applies to absolutely positioned components.
+    pub bottom: Option<f32>,
+    /// Only applies to absolutely positioned components.
+    pub left: Option<f32>,
+    /// Only applies to absolutely positioned components.
+    pub right: Option<f32>,
+    /// Only applies to absolutely positioned components.
+    pub rotation: Option<f32>,
+    pub opacity: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationRepeat {
+    /// Play animation once and keep values from the last keyframe.
+    None,
+    /// Restart animation from the first keyframe after it finishes.
+    Loop,
+    /// Play animation forwards and backwards.
+    Alternate,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Position {
     Static {
@@ -183,6 +360,42 @@ pub enum ViewChildrenDirection {
     Column,
 }
 
+/// Distribution of static children along the direction of a `View`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JustifyContent {
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+/// Alignment of static children in the axis perpendicular to the direction of a `View`.
+/// Unless children are stretched, children without an explicit size are sized to
+/// their content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlignItems {
+    Start,
+    End,
+    Center,
+    /// Children without an explicit size fill the entire row (or column).
+    Stretch,
+}
+
+/// Defines how a static child of a `View` is resized along the view direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Flex {
+    /// Share of the free space that is added to the component size. If not defined,
+    /// components without an explicit size grow with factor 1 and components with
+    /// an explicit size do not grow.
+    pub grow: Option<f32>,
+    /// Share of the missing space that is subtracted from the component size, weighted
+    /// by its size. Components do not shrink by default and never shrink below the
+    /// size of their content.
+    pub shrink: f32,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct Padding {
     pub top: f32,
@@ -215,6 +428,7 @@ pub struct RescalerComponent {
 
     pub position: Position,
     pub transition: Option<Transition>,
+    pub animation: Option<Animation>,
 
     pub mode: RescaleMode,
     pub horizontal_align: HorizontalAlign,
@@ -225,6 +439,12 @@ pub struct RescalerComponent {
     pub border_color: RGBAColor,
 
     pub box_shadow: Vec<BoxShadow>,
+
+    /// Resizing of this component if its parent is a `View`.
+    pub flex: Flex,
+
+    pub opacity: f32,
+    pub blend_mode: BlendMode,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -248,9 +468,43 @@ pub struct TilesComponent {
     pub horizontal_align: HorizontalAlign,
     pub vertical_align: VerticalAlign,
 
+    pub opacity: f32,
+    pub blend_mode: BlendMode,
+
     pub transition: Option<Transition>,
 }
 
+#[derive(Debug, Clone)]
+pub struct TickerComponent {
+    pub id: Option<ComponentId>,
+    pub children: Vec<Component>,
+
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+
+    pub direction: TickerDirection,
+    /// Scroll speed in pixels per second.
+    pub speed: f32,
+    /// Space between consecutive items in pixels.
+    pub gap: f32,
+    /// Start scrolling items again from the beginning after the last one leaves
+    /// the component area.
+    pub should_loop: bool,
+    pub background_color: RGBAColor,
+
+    pub opacity: f32,
+    pub blend_mode: BlendMode,
+}
+
+/// Direction in which ticker items are moving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickerDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
 impl Default for ViewComponent {
     fn default() -> Self {
         Self {
@@ -262,6 +516,7 @@ impl Default for ViewComponent {
                 height: None,
             },
             transition: None,
+            animation: None,
             overflow: Overflow::Hidden,
             background_color: RGBAColor(0, 0, 0, 0),
             border_radius: BorderRadius::ZERO,
@@ -269,6 +524,13 @@ impl Default for ViewComponent {
             border_color: RGBAColor(0, 0, 0, 0),
             box_shadow: vec![],
             padding: Padding::ZERO,
+            gap: 0.0,
+            justify_content: JustifyContent::Start,
+            align_items: AlignItems::Stretch,
+            wrap: false,
+            flex: Flex::default(),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
 }
@@ -283,6 +545,7 @@ impl Default for RescalerComponent {
                 height: None,
             },
             transition: None,
+            animation: None,
             mode: RescaleMode::Fit,
             horizontal_align: HorizontalAlign::Center,
             vertical_align: VerticalAlign::Center,
@@ -290,6 +553,9 @@ impl Default for RescalerComponent {
             border_width: 0.0,
             border_color: RGBAColor(0, 0, 0, 0),
             box_shadow: vec![],
+            flex: Flex::default(),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
 }
@@ -308,6 +574,98 @@ impl Default for TilesComponent {
             horizontal_align: HorizontalAlign::Center,
             background_color: RGBAColor(0, 0, 0, 0),
             tile_aspect_ratio: (16, 9),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+        }
+    }
+}
+
+impl Default for TickerComponent {
+    fn default() -> Self {
+        Self {
+            id: None,
+            children: vec![],
+            width: None,
+            height: None,
+            direction: TickerDirection::Left,
+            speed: 100.0,
+            gap: 0.0,
+            should_loop: true,
+            background_color: RGBAColor(0, 0, 0, 0),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_animation_pts_loop_forever() {
+        let playback = ImagePlayback::default();
+        let start_pts = ms(1000);
+        let duration = ms(300);
+        assert_eq!(
+            playback.animation_pts(start_pts, ms(1000), duration),
+            Some(ms(0))
+        );
+        assert_eq!(
+            playback.animation_pts(start_pts, ms(1250), duration),
+            Some(ms(250))
+        );
+        assert_eq!(
+            playback.animation_pts(start_pts, ms(1700), duration),
+            Some(ms(100))
+        );
+        // Component mounted after the pts is shown from the first frame.
+        assert_eq!(
+            playback.animation_pts(start_pts, ms(500), duration),
+            Some(ms(0))
+        );
+    }
+
+    #[test]
+    fn test_animation_pts_loop_count() {
+        let playback = ImagePlayback {
+            loop_count: Some(2),
+            ..Default::default()
+        };
+        let duration = ms(300);
+        assert_eq!(
+            playback.animation_pts(ms(0), ms(450), duration),
+            Some(ms(150))
+        );
+        assert_eq!(
+            playback.animation_pts(ms(0), ms(599), duration),
+            Some(ms(299))
+        );
+        assert_eq!(playback.animation_pts(ms(0), ms(600), duration), None);
+        assert_eq!(playback.animation_pts(ms(0), ms(10_000), duration), None);
+    }
+
+    #[test]
+    fn test_animation_pts_start_at() {
+        let playback = ImagePlayback {
+            loop_count: Some(1),
+            start_at: ms(200),
+            ..Default::default()
+        };
+        let duration = ms(300);
+        assert_eq!(
+            playback.animation_pts(ms(0), ms(0), duration),
+            Some(ms(200))
+        );
+        assert_eq!(
+            playback.animation_pts(ms(0), ms(99), duration),
+            Some(ms(299))
+        );
+        // Offset counts towards the played loops.
+        assert_eq!(playback.animation_pts(ms(0), ms(100), duration), None);
+    }
+}
@@ -3,7 +3,7 @@ use crate::scene::{
     types::interpolation::{ContinuousValue, InterpolationState},
 };
 
-use super::{AbsolutePosition, Position};
+use super::{AbsolutePosition, Flex, Position};
 
 impl ContinuousValue for Position {
     fn interpolate(start: &Self, end: &Self, state: InterpolationState) -> Self {
@@ -88,3 +88,12 @@ impl ContinuousValue for BoxShadow {
         }
     }
 }
+
+impl ContinuousValue for Flex {
+    fn interpolate(start: &Self, end: &Self, state: InterpolationState) -> Self {
+        Self {
+            grow: ContinuousValue::interpolate(&start.grow, &end.grow, state),
+            shrink: ContinuousValue::interpolate(&start.shrink, &end.shrink, state),
+        }
+    }
+}
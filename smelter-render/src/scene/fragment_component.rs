@@ -0,0 +1,46 @@
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{RendererId, registry::RendererRegistry};
+
+use super::{Component, SceneError};
+
+impl Component {
+    /// Replace all `Fragment` components with component trees registered under
+    /// their ids. Returns ids of all fragments used in the scene.
+    pub(super) fn resolve_fragments(
+        &mut self,
+        fragments: &RendererRegistry<Arc<Component>>,
+    ) -> Result<HashSet<RendererId>, SceneError> {
+        let mut used_fragments = HashSet::new();
+        self.resolve_fragments_recursive(fragments, &mut vec![], &mut used_fragments)?;
+        Ok(used_fragments)
+    }
+
+    fn resolve_fragments_recursive(
+        &mut self,
+        fragments: &RendererRegistry<Arc<Component>>,
+        parent_fragments: &mut Vec<RendererId>,
+        used_fragments: &mut HashSet<RendererId>,
+    ) -> Result<(), SceneError> {
+        let Component::Fragment(fragment) = self else {
+            return self.children_mut().into_iter().try_for_each(|child| {
+                child.resolve_fragments_recursive(fragments, parent_fragments, used_fragments)
+            });
+        };
+
+        let fragment_id = fragment.fragment_id.clone();
+        if parent_fragments.contains(&fragment_id) {
+            return Err(SceneError::RecursiveFragment(fragment_id));
+        }
+        let root = fragments
+            .get(&fragment_id)
+            .ok_or_else(|| SceneError::FragmentNotFound(fragment_id.clone()))?;
+        *self = root.as_ref().clone();
+        used_fragments.insert(fragment_id.clone());
+
+        parent_fragments.push(fragment_id);
+        let result = self.resolve_fragments_recursive(fragments, parent_fragments, used_fragments);
+        parent_fragments.pop();
+        result
+    }
+}
@@ -3,7 +3,7 @@ use std::{sync::Arc, time::Duration};
 use crate::{Resolution, scene::Size, transformations::image::Image};
 
 use super::{
-    ComponentId, ImageComponent, IntermediateNode, SceneError, StatefulComponent,
+    ComponentId, ImageComponent, ImagePlayback, IntermediateNode, SceneError, StatefulComponent,
     scene_state::BuildStateTreeCtx,
 };
 
@@ -12,6 +12,7 @@ pub(crate) struct ImageRenderParams {
     pub(crate) image: Image,
     pub(crate) start_pts: Duration,
     pub(crate) resolution: Resolution,
+    pub(crate) playback: ImagePlayback,
 }
 
 #[derive(Debug, Clone)]
@@ -49,6 +50,7 @@ impl StatefulImageComponent {
             image: self.image,
             start_pts: self.start_pts,
             resolution: self.resolution,
+            playback: self.component.playback,
         }
     }
 }
@@ -109,14 +111,20 @@ impl ImageComponent {
             (_, _) => false,
         };
 
-        let component = match prev_state {
-            Some(state) if self == state.component && are_images_matching => state.clone(),
-            _ => StatefulImageComponent {
-                component: self,
-                image,
-                start_pts: ctx.last_render_pts,
-                resolution,
-            },
+        // Keep the animation running if only size of the component changed.
+        let start_pts = match prev_state {
+            Some(state) if are_images_matching && self.playback == state.component.playback => {
+                state.start_pts
+            }
+            _ if self.playback.restart_on_mount => ctx.last_render_pts,
+            _ => Duration::ZERO,
+        };
+
+        let component = StatefulImageComponent {
+            component: self,
+            image,
+            start_pts,
+            resolution,
         };
 
         Ok(StatefulComponent::Image(component))
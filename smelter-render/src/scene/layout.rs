@@ -6,8 +6,9 @@ use crate::{
 };
 
 use super::{
-    AbsolutePosition, BorderRadius, ComponentId, HorizontalPosition, Position, RGBAColor, Size,
-    StatefulComponent, VerticalPosition, rescaler_component::StatefulRescalerComponent,
+    AbsolutePosition, BlendMode, BorderRadius, ComponentId, Flex, HorizontalPosition, Position,
+    RGBAColor, Size, StatefulComponent, VerticalPosition,
+    rescaler_component::StatefulRescalerComponent, ticker_component::StatefulTickerComponent,
     tiles_component::StatefulTilesComponent, view_component::StatefulViewComponent,
 };
 
@@ -16,6 +17,7 @@ pub(super) enum StatefulLayoutComponent {
     View(StatefulViewComponent),
     Tiles(StatefulTilesComponent),
     Rescaler(StatefulRescalerComponent),
+    Ticker(StatefulTickerComponent),
 }
 
 #[derive(Debug)]
@@ -46,6 +48,7 @@ impl StatefulLayoutComponent {
             StatefulLayoutComponent::View(view) => view.layout(size, pts),
             StatefulLayoutComponent::Tiles(tiles) => tiles.layout(size, pts),
             StatefulLayoutComponent::Rescaler(rescaler) => rescaler.layout(size, pts),
+            StatefulLayoutComponent::Ticker(ticker) => ticker.layout(size, pts),
         }
     }
 
@@ -55,6 +58,30 @@ impl StatefulLayoutComponent {
             StatefulLayoutComponent::View(view) => view.position(pts),
             StatefulLayoutComponent::Tiles(tiles) => tiles.position(pts),
             StatefulLayoutComponent::Rescaler(rescaler) => rescaler.position(pts),
+            StatefulLayoutComponent::Ticker(ticker) => ticker.position(pts),
+        }
+    }
+
+    /// Resizing of a component if it is a static child of a `View`.
+    pub(super) fn flex(&self, pts: Duration) -> Flex {
+        match self {
+            StatefulLayoutComponent::View(view) => view.flex(pts),
+            StatefulLayoutComponent::Rescaler(rescaler) => rescaler.flex(pts),
+            StatefulLayoutComponent::Tiles(_) | StatefulLayoutComponent::Ticker(_) => {
+                Flex::default()
+            }
+        }
+    }
+
+    /// Size of the component content, used if the component does not define its size
+    /// and it is not stretched by its parent. Components that fit their content into
+    /// any size do not have a content size.
+    pub(super) fn content_size(&self, pts: Duration) -> Option<Size> {
+        match self {
+            StatefulLayoutComponent::View(view) => Some(view.content_size(pts)),
+            StatefulLayoutComponent::Tiles(_)
+            | StatefulLayoutComponent::Rescaler(_)
+            | StatefulLayoutComponent::Ticker(_) => None,
         }
     }
 
@@ -63,6 +90,7 @@ impl StatefulLayoutComponent {
             StatefulLayoutComponent::View(view) => view.component_id(),
             StatefulLayoutComponent::Tiles(tiles) => tiles.component_id(),
             StatefulLayoutComponent::Rescaler(rescaler) => rescaler.component_id(),
+            StatefulLayoutComponent::Ticker(ticker) => ticker.component_id(),
         }
     }
 
@@ -71,6 +99,7 @@ impl StatefulLayoutComponent {
             StatefulLayoutComponent::View(_) => "View",
             StatefulLayoutComponent::Tiles(_) => "Tiles",
             StatefulLayoutComponent::Rescaler(_) => "Rescaler",
+            StatefulLayoutComponent::Ticker(_) => "Ticker",
         }
     }
 
@@ -79,6 +108,7 @@ impl StatefulLayoutComponent {
             StatefulLayoutComponent::View(view) => view.children(),
             StatefulLayoutComponent::Tiles(tiles) => tiles.children(),
             StatefulLayoutComponent::Rescaler(rescaler) => rescaler.children(),
+            StatefulLayoutComponent::Ticker(ticker) => ticker.children(),
         }
     }
 
@@ -87,6 +117,7 @@ impl StatefulLayoutComponent {
             StatefulLayoutComponent::View(view) => view.children_mut(),
             StatefulLayoutComponent::Tiles(tiles) => tiles.children_mut(),
             StatefulLayoutComponent::Rescaler(rescaler) => rescaler.children_mut(),
+            StatefulLayoutComponent::Ticker(ticker) => ticker.children_mut(),
         }
     }
 
@@ -116,7 +147,9 @@ impl StatefulLayoutComponent {
                 }
                 StatefulComponent::Shader(_)
                 | StatefulComponent::Image(_)
+                | StatefulComponent::VideoClip(_)
                 | StatefulComponent::Text(_)
+                | StatefulComponent::Mask(_)
                 | StatefulComponent::WebView(_) => {
                     child_index_offset += 1; // no state
                 }
@@ -150,10 +183,18 @@ impl StatefulLayoutComponent {
                 index,
                 size: image.size(),
             },
+            StatefulComponent::VideoClip(clip) => LayoutContent::ChildNode {
+                index,
+                size: clip.size(),
+            },
             StatefulComponent::Text(text) => LayoutContent::ChildNode {
                 index,
                 size: text.size(),
             },
+            StatefulComponent::Mask(mask) => LayoutContent::ChildNode {
+                index,
+                size: mask.component.size,
+            },
         }
     }
 
@@ -205,6 +246,9 @@ impl StatefulLayoutComponent {
                     border_color: RGBAColor(0, 0, 0, 0),
                     border_radius: BorderRadius::ZERO,
                     box_shadow: vec![],
+                    opacity: 1.0,
+                    blend_mode: BlendMode::Normal,
+                    component_id: layout_component.component_id().cloned(),
                 }
             }
             _non_layout_components => {
@@ -231,6 +275,9 @@ impl StatefulLayoutComponent {
                     border_color: RGBAColor(0, 0, 0, 0),
                     border_radius: BorderRadius::ZERO,
                     box_shadow: vec![],
+                    opacity: 1.0,
+                    blend_mode: BlendMode::Normal,
+                    component_id: child.component_id().cloned(),
                 }
             }
         }
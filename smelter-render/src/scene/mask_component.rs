@@ -0,0 +1,59 @@
+use super::{
+    Component, ComponentId, IntermediateNode, MaskComponent, MaskMode, SceneError, Size,
+    StatefulComponent, scene_state::BuildStateTreeCtx,
+};
+
+#[derive(Debug, Clone)]
+pub(super) struct StatefulMaskComponent {
+    pub(super) component: MaskComponentParams,
+    /// Content and mask in that order.
+    pub(super) children: Vec<StatefulComponent>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MaskComponentParams {
+    pub(crate) id: Option<ComponentId>,
+    pub(crate) mode: MaskMode,
+    pub(crate) invert: bool,
+    pub(crate) size: Size,
+}
+
+impl StatefulMaskComponent {
+    pub(super) fn component_id(&self) -> Option<&ComponentId> {
+        self.component.id.as_ref()
+    }
+
+    pub(super) fn intermediate_node(&self) -> IntermediateNode {
+        let children = self
+            .children
+            .iter()
+            .map(StatefulComponent::intermediate_node)
+            .collect();
+
+        IntermediateNode::Mask {
+            mask: self.clone(),
+            children,
+        }
+    }
+}
+
+impl MaskComponent {
+    pub(super) fn stateful_component(
+        self,
+        ctx: &BuildStateTreeCtx,
+    ) -> Result<StatefulComponent, SceneError> {
+        let children = vec![
+            Component::stateful_component(*self.content, ctx)?,
+            Component::stateful_component(*self.mask, ctx)?,
+        ];
+        Ok(StatefulComponent::Mask(StatefulMaskComponent {
+            component: MaskComponentParams {
+                id: self.id,
+                mode: self.mode,
+                invert: self.invert,
+                size: self.size,
+            },
+            children,
+        }))
+    }
+}
@@ -0,0 +1,242 @@
+use super::{Component, ComponentId, SceneError};
+
+/// Operation that modifies a part of an existing scene identified by a component id.
+#[derive(Debug, Clone)]
+pub enum ScenePatch<C = Component> {
+    /// Replace component (together with its children) with a new one.
+    Replace { id: ComponentId, component: C },
+    /// Insert a new child component into a component with `parent_id`. If `index`
+    /// is not specified, the component is added after the existing children.
+    Insert {
+        parent_id: ComponentId,
+        index: Option<usize>,
+        component: C,
+    },
+    /// Remove component (together with its children).
+    Remove { id: ComponentId },
+}
+
+/// Component tree that can be modified with a [`ScenePatch`]. Implemented for scene
+/// components and for API types that represent them, so both are patched the same way.
+pub trait PatchableComponent: Sized {
+    fn has_id(&self, id: &ComponentId) -> bool;
+
+    /// All child components.
+    fn children_mut(&mut self) -> Vec<&mut Self>;
+
+    /// List of children for components that support any number of children.
+    fn children_list_mut(&mut self) -> Option<&mut Vec<Self>>;
+}
+
+impl<C: PatchableComponent> ScenePatch<C> {
+    pub fn apply(self, root: &mut C) -> Result<(), SceneError> {
+        match self {
+            ScenePatch::Replace { id, component } => {
+                let target = find_mut(root, &id).ok_or(SceneError::PatchComponentNotFound(id))?;
+                *target = component;
+            }
+            ScenePatch::Insert {
+                parent_id,
+                index,
+                component,
+            } => {
+                let parent = find_mut(root, &parent_id)
+                    .ok_or_else(|| SceneError::PatchComponentNotFound(parent_id.clone()))?;
+                let Some(children) = parent.children_list_mut() else {
+                    return Err(SceneError::PatchParentWithoutChildrenList(parent_id));
+                };
+                let index = index.unwrap_or(children.len());
+                if index > children.len() {
+                    return Err(SceneError::PatchIndexOutOfBounds {
+                        parent_id,
+                        index,
+                        len: children.len(),
+                    });
+                }
+                children.insert(index, component);
+            }
+            ScenePatch::Remove { id } => {
+                if root.has_id(&id) {
+                    return Err(SceneError::PatchRemoveRequiredComponent(id));
+                }
+                if !remove_descendant(root, &id)? {
+                    return Err(SceneError::PatchComponentNotFound(id));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn find_mut<'a, C: PatchableComponent>(node: &'a mut C, id: &ComponentId) -> Option<&'a mut C> {
+    if node.has_id(id) {
+        return Some(node);
+    }
+    node.children_mut()
+        .into_iter()
+        .find_map(|child| find_mut(child, id))
+}
+
+/// Returns `true` if component was found and removed.
+fn remove_descendant<C: PatchableComponent>(
+    node: &mut C,
+    id: &ComponentId,
+) -> Result<bool, SceneError> {
+    let index = node.children_mut().iter().position(|c| c.has_id(id));
+    if let Some(index) = index {
+        let Some(children) = node.children_list_mut() else {
+            return Err(SceneError::PatchRemoveRequiredComponent(id.clone()));
+        };
+        children.remove(index);
+        return Ok(true);
+    }
+
+    for child in node.children_mut() {
+        if remove_descendant(child, id)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+impl Component {
+    pub(super) fn apply_patch(&mut self, patch: ScenePatch) -> Result<(), SceneError> {
+        patch.apply(self)
+    }
+
+    pub(super) fn children_mut(&mut self) -> Vec<&mut Component> {
+        match self {
+            Component::Mask(mask) => vec![mask.content.as_mut(), mask.mask.as_mut()],
+            Component::Rescaler(rescaler) => vec![rescaler.child.as_mut()],
+            _ => PatchableComponent::children_list_mut(self)
+                .map(|children| children.iter_mut().collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl PatchableComponent for Component {
+    fn has_id(&self, id: &ComponentId) -> bool {
+        self.component_id() == Some(id)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Self> {
+        Component::children_mut(self)
+    }
+
+    fn children_list_mut(&mut self) -> Option<&mut Vec<Self>> {
+        match self {
+            Component::Shader(shader) => Some(&mut shader.children),
+            Component::WebView(web) => Some(&mut web.children),
+            Component::View(view) => Some(&mut view.children),
+            Component::Tiles(tiles) => Some(&mut tiles.children),
+            Component::Ticker(ticker) => Some(&mut ticker.children),
+            Component::InputStream(_)
+            | Component::Image(_)
+            | Component::VideoClip(_)
+            | Component::Text(_)
+            | Component::Mask(_)
+            | Component::Rescaler(_)
+            | Component::Fragment(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::scene::{RescalerComponent, ViewComponent};
+
+    use super::*;
+
+    fn view(id: &str, children: Vec<Component>) -> Component {
+        Component::View(ViewComponent {
+            id: Some(ComponentId(Arc::from(id))),
+            children,
+            ..Default::default()
+        })
+    }
+
+    fn id(id: &str) -> ComponentId {
+        ComponentId(Arc::from(id))
+    }
+
+    fn child_ids(component: &Component) -> Vec<&str> {
+        component
+            .children()
+            .into_iter()
+            .filter_map(|c| c.component_id().map(|id| id.0.as_ref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_scene_patch() {
+        let mut root = view("root", vec![view("a", vec![]), view("b", vec![])]);
+
+        root.apply_patch(ScenePatch::Insert {
+            parent_id: id("root"),
+            index: Some(1),
+            component: view("c", vec![]),
+        })
+        .unwrap();
+        assert_eq!(child_ids(&root), vec!["a", "c", "b"]);
+
+        root.apply_patch(ScenePatch::Remove { id: id("a") })
+            .unwrap();
+        assert_eq!(child_ids(&root), vec!["c", "b"]);
+
+        root.apply_patch(ScenePatch::Replace {
+            id: id("b"),
+            component: view("d", vec![view("e", vec![])]),
+        })
+        .unwrap();
+        assert_eq!(child_ids(&root), vec!["c", "d"]);
+
+        root.apply_patch(ScenePatch::Remove { id: id("e") })
+            .unwrap();
+        assert_eq!(child_ids(root.children()[1]), Vec::<&str>::new());
+
+        assert!(matches!(
+            root.apply_patch(ScenePatch::Remove { id: id("a") }),
+            Err(SceneError::PatchComponentNotFound(_))
+        ));
+        assert!(matches!(
+            root.apply_patch(ScenePatch::Remove { id: id("root") }),
+            Err(SceneError::PatchRemoveRequiredComponent(_))
+        ));
+        assert!(matches!(
+            root.apply_patch(ScenePatch::Insert {
+                parent_id: id("root"),
+                index: Some(3),
+                component: view("f", vec![]),
+            }),
+            Err(SceneError::PatchIndexOutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_scene_patch_required_child() {
+        let mut root = view(
+            "root",
+            vec![Component::Rescaler(RescalerComponent {
+                id: Some(id("rescaler")),
+                child: view("child", vec![]).into(),
+                ..Default::default()
+            })],
+        );
+
+        assert!(matches!(
+            root.apply_patch(ScenePatch::Remove { id: id("child") }),
+            Err(SceneError::PatchRemoveRequiredComponent(_))
+        ));
+        assert!(matches!(
+            root.apply_patch(ScenePatch::Insert {
+                parent_id: id("rescaler"),
+                index: None,
+                component: view("f", vec![]),
+            }),
+            Err(SceneError::PatchParentWithoutChildrenList(_))
+        ));
+    }
+}
@@ -3,8 +3,10 @@ use std::{ops::Deref, time::Duration};
 use crate::transformations::layout::NestedLayout;
 
 use super::{
-    BorderRadius, BoxShadow, Component, ComponentId, HorizontalAlign, IntermediateNode, Position,
-    RGBAColor, RescaleMode, SceneError, Size, StatefulComponent, VerticalAlign,
+    BlendMode, BorderRadius, BoxShadow, Component, ComponentId, Flex, HorizontalAlign,
+    IntermediateNode, Position, RGBAColor, RescaleMode, SceneError, Size, StatefulComponent,
+    VerticalAlign,
+    animation::AnimationState,
     components::RescalerComponent,
     layout::StatefulLayoutComponent,
     scene_state::BuildStateTreeCtx,
@@ -20,6 +22,7 @@ pub(super) struct StatefulRescalerComponent {
     start: Option<RescalerComponentParam>,
     end: RescalerComponentParam,
     transition: Option<TransitionState>,
+    animation: Option<AnimationState>,
     child: Box<StatefulComponent>,
 }
 
@@ -37,16 +40,31 @@ struct RescalerComponentParam {
     border_color: RGBAColor,
 
     box_shadow: Vec<BoxShadow>,
+
+    flex: Flex,
+
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl StatefulRescalerComponent {
     /// Generate state of the component for particular pts value.
     fn transition_snapshot(&self, pts: Duration) -> RescalerComponentParam {
-        let (Some(transition), Some(start)) = (&self.transition, &self.start) else {
-            return self.end.clone();
+        let rescaler = match (&self.transition, &self.start) {
+            (Some(transition), Some(start)) => {
+                ContinuousValue::interpolate(start, &self.end, transition.state(pts))
+            }
+            _ => self.end.clone(),
         };
-        let interpolation_progress = transition.state(pts);
-        ContinuousValue::interpolate(start, &self.end, interpolation_progress)
+        let Some(animation) = &self.animation else {
+            return rescaler;
+        };
+        let properties = animation.properties(pts);
+        RescalerComponentParam {
+            position: rescaler.position.with_animated_properties(&properties),
+            opacity: properties.opacity.unwrap_or(rescaler.opacity),
+            ..rescaler
+        }
     }
 
     pub(super) fn children(&self) -> Vec<&StatefulComponent> {
@@ -62,6 +80,10 @@ impl StatefulRescalerComponent {
         rescaler.position.with_border(rescaler.border_width)
     }
 
+    pub(super) fn flex(&self, pts: Duration) -> Flex {
+        self.transition_snapshot(pts).flex
+    }
+
     pub(super) fn component_id(&self) -> Option<&ComponentId> {
         self.end.id.as_ref()
     }
@@ -117,6 +139,9 @@ impl RescalerComponent {
             border_width: self.border_width,
             border_color: self.border_color,
             box_shadow: self.box_shadow,
+            flex: self.flex,
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
         };
 
         let props_changed = previous_state
@@ -134,10 +159,16 @@ impl RescalerComponent {
             interrupt_previous_transition,
             ctx.last_render_pts,
         );
+        let animation = AnimationState::new(
+            self.animation,
+            previous_state.and_then(|s| s.animation.as_ref()),
+            ctx.last_render_pts,
+        );
         let rescaler = StatefulRescalerComponent {
             start,
             end,
             transition,
+            animation,
             child: Box::new(Component::stateful_component(*self.child, ctx)?),
         };
         Ok(StatefulComponent::Layout(
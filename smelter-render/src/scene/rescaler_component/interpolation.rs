@@ -22,6 +22,9 @@ impl ContinuousValue for RescalerComponentParam {
             ),
             border_color: end.border_color,
             box_shadow: ContinuousValue::interpolate(&start.box_shadow, &end.box_shadow, state),
+            flex: ContinuousValue::interpolate(&start.flex, &end.flex, state),
+            opacity: ContinuousValue::interpolate(&start.opacity, &end.opacity, state),
+            blend_mode: end.blend_mode,
         }
     }
 }
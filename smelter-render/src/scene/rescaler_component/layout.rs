@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use crate::{
     scene::{
-        BorderRadius, HorizontalAlign, RGBAColor, RescaleMode, Size, StatefulComponent,
+        BlendMode, BorderRadius, HorizontalAlign, RGBAColor, RescaleMode, Size, StatefulComponent,
         VerticalAlign, layout::StatefulLayoutComponent,
     },
     transformations::layout::{LayoutContent, Mask, NestedLayout},
@@ -151,12 +151,18 @@ impl RescalerComponentParam {
                 border_color: RGBAColor(0, 0, 0, 0),
                 border_radius: BorderRadius::ZERO,
                 box_shadow: vec![],
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
+                component_id: child.component_id().cloned(),
             }],
             child_nodes_count,
             border_width: self.border_width,
             border_color: self.border_color,
             border_radius,
             box_shadow: self.box_shadow.clone(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            component_id: None,
         }
     }
 }
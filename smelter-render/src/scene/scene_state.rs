@@ -1,20 +1,26 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use tracing::error;
 
 use crate::{
-    InputId, OutputId, Resolution, state::renderers::Renderers,
+    InputId, OutputId, RendererId, Resolution, state::renderers::Renderers,
     transformations::text_renderer::TextRendererCtx,
 };
 
 use super::{
-    ComponentId, Node, NodeParams, OutputScene, Position, SceneError, Size, StatefulComponent,
+    Component, ComponentId, ComponentLayout, Node, NodeParams, OutputScene, Position, SceneError,
+    ScenePatch, Size, StatefulComponent,
     image_component::StatefulImageComponent,
     input_stream_component::StatefulInputStreamComponent,
     layout::{LayoutNode, SizedLayoutComponent, StatefulLayoutComponent},
+    mask_component::StatefulMaskComponent,
     shader_component::StatefulShaderComponent,
     text_component::StatefulTextComponent,
     validation::validate_scene_update,
+    video_clip_component::StatefulVideoClipComponent,
     web_view_component::StatefulWebViewComponent,
 };
 
@@ -26,7 +32,9 @@ pub(super) struct BuildStateTreeCtx<'a> {
     pub(super) input_resolutions: &'a HashMap<InputId, Resolution>,
 }
 
+#[derive(Clone)]
 pub(crate) struct SceneState {
+    /// Output scenes with fragments replaced by their content.
     output_scenes: HashMap<OutputId, OutputScene>,
     output_states: HashMap<OutputId, OutputSceneState>,
     last_pts: Duration,
@@ -38,6 +46,10 @@ pub(crate) struct SceneState {
 struct OutputSceneState {
     root: StatefulComponent,
     resolution: Resolution,
+    /// Scene root as it was defined in the update, it can reference fragments.
+    scene_root: Component,
+    /// Fragments used (directly or by other fragments) in the scene.
+    fragments: HashSet<RendererId>,
 }
 
 pub(crate) struct OutputNode {
@@ -78,7 +90,11 @@ impl SceneState {
         text_renderer_ctx: &TextRendererCtx,
     ) -> Result<OutputNode, SceneError> {
         let output_id = output_scene.output_id.clone();
-        validate_scene_update(&self.output_scenes, &output_scene)?;
+        let mut resolved_scene = output_scene.clone();
+        let fragments = resolved_scene
+            .scene_root
+            .resolve_fragments(&renderers.fragments)?;
+        validate_scene_update(&self.output_scenes, &resolved_scene)?;
 
         for (_, output) in self.output_states.iter_mut() {
             recalculate_layout(
@@ -106,8 +122,10 @@ impl SceneState {
         };
 
         let output_state_tree = OutputSceneState {
-            root: output_scene.scene_root.clone().stateful_component(&ctx)?,
+            root: resolved_scene.scene_root.clone().stateful_component(&ctx)?,
             resolution: output_scene.resolution,
+            scene_root: output_scene.scene_root,
+            fragments,
         };
 
         let output_node_tree = OutputNode {
@@ -119,11 +137,90 @@ impl SceneState {
             resolution: output_scene.resolution,
         };
 
-        self.output_scenes.insert(output_id.clone(), output_scene);
+        self.output_scenes.insert(output_id.clone(), resolved_scene);
         self.output_states.insert(output_id, output_state_tree);
 
         Ok(output_node_tree)
     }
+
+    /// Rebuild scenes of all outputs that use the fragment. Either all of those
+    /// outputs are updated or none of them.
+    pub(crate) fn update_fragment(
+        &mut self,
+        fragment_id: &RendererId,
+        renderers: &Renderers,
+        text_renderer_ctx: &TextRendererCtx,
+    ) -> Result<Vec<OutputNode>, SceneError> {
+        let output_scenes: Vec<OutputScene> = self
+            .output_states
+            .iter()
+            .filter(|(_, state)| state.fragments.contains(fragment_id))
+            .map(|(output_id, state)| OutputScene {
+                output_id: output_id.clone(),
+                scene_root: state.scene_root.clone(),
+                resolution: state.resolution,
+            })
+            .collect();
+
+        let previous_state = (self.output_scenes.clone(), self.output_states.clone());
+        let result = output_scenes
+            .into_iter()
+            .map(|output_scene| self.update_scene(output_scene, renderers, text_renderer_ctx))
+            .collect::<Result<Vec<_>, _>>();
+        if result.is_err() {
+            (self.output_scenes, self.output_states) = previous_state;
+        }
+        result
+    }
+
+    /// Applies patches to the last scene of an output. Result still needs to be
+    /// passed to `update_scene`.
+    pub(crate) fn patched_scene_root(
+        &self,
+        output_id: &OutputId,
+        patches: Vec<ScenePatch>,
+    ) -> Result<Component, SceneError> {
+        let mut scene_root = self
+            .output_states
+            .get(output_id)
+            .ok_or_else(|| SceneError::PatchOutputSceneNotFound(output_id.clone()))?
+            .scene_root
+            .clone();
+        for patch in patches {
+            scene_root.apply_patch(patch)?;
+        }
+        Ok(scene_root)
+    }
+
+    /// Positions of components with an id in the current scene of an output, calculated
+    /// for the last rendered frame. Only components placed by layout components (and
+    /// the root component) are included.
+    pub(crate) fn component_layouts(
+        &mut self,
+        output_id: &OutputId,
+    ) -> Option<Vec<ComponentLayout>> {
+        let output = self.output_states.get_mut(output_id)?;
+        let size: Size = output.resolution.into();
+        // Same as the layout recalculation on scene update, it only refreshes the state
+        // for the last rendered frame, so the tree does not need to be copied.
+        let root = &mut output.root;
+        update_input_sizes(root, &self.input_resolutions);
+
+        let root_layout = root.component_id().map(|component_id| ComponentLayout {
+            component_id: component_id.clone(),
+            top: 0.0,
+            left: 0.0,
+            width: size.width,
+            height: size.height,
+        });
+        let children_layouts = match root {
+            StatefulComponent::Layout(layout) => {
+                layout.layout(size, self.last_pts).component_layouts()
+            }
+            _ => vec![],
+        };
+        Some(root_layout.into_iter().chain(children_layouts).collect())
+    }
 }
 
 /// Intermediate representation of a node tree while it's being constructed.
@@ -138,7 +235,12 @@ pub(super) enum IntermediateNode {
         children: Vec<IntermediateNode>,
     },
     Image(StatefulImageComponent),
+    VideoClip(StatefulVideoClipComponent),
     Text(StatefulTextComponent),
+    Mask {
+        mask: StatefulMaskComponent,
+        children: Vec<IntermediateNode>,
+    },
     Layout {
         root: Box<StatefulLayoutComponent>,
         children: Vec<IntermediateNode>,
@@ -188,10 +290,23 @@ impl IntermediateNode {
                 params: NodeParams::Image(image.image_render_params()),
                 children: vec![],
             }),
+            IntermediateNode::VideoClip(clip) => Ok(Node {
+                params: NodeParams::VideoClip(clip.render_params()),
+                children: vec![],
+            }),
             IntermediateNode::Text(text) => Ok(Node {
                 params: NodeParams::Text(text.params),
                 children: vec![],
             }),
+            IntermediateNode::Mask { mask, children } => Ok(Node {
+                params: NodeParams::Mask(mask.component),
+                // Content and mask are stretched to the size of the component anyway,
+                // so layouts without explicit size can just fill it.
+                children: children
+                    .into_iter()
+                    .map(|node| node.build_tree(Some(size.into()), pts))
+                    .collect::<Result<_, _>>()?,
+            }),
         }
     }
 
@@ -204,7 +319,9 @@ impl IntermediateNode {
             } => Ok(shader.component.size),
             IntermediateNode::WebView { web, children: _ } => Ok(web.size()),
             IntermediateNode::Image(image) => Ok(image.size()),
+            IntermediateNode::VideoClip(clip) => Ok(clip.size()),
             IntermediateNode::Text(text) => Ok(text.size()),
+            IntermediateNode::Mask { mask, children: _ } => Ok(mask.component.size),
             IntermediateNode::Layout { root, children: _ } => {
                 let (width, height) = match root.position(pts) {
                     Position::Static { width, height } => (width, height),
@@ -263,6 +380,20 @@ fn recalculate_layout(
     }
 }
 
+fn update_input_sizes(
+    component: &mut StatefulComponent,
+    input_resolutions: &HashMap<InputId, Resolution>,
+) {
+    if let StatefulComponent::InputStream(input) = component
+        && let Some(resolution) = input_resolutions.get(&input.component.input_id)
+    {
+        input.size = (*resolution).into();
+    }
+    for child in component.children_mut() {
+        update_input_sizes(child, input_resolutions)
+    }
+}
+
 fn gather_components_with_id<'a>(
     component: &'a StatefulComponent,
     components: &mut HashMap<ComponentId, &'a StatefulComponent>,
@@ -294,11 +425,24 @@ fn gather_components_with_id<'a>(
                 components.insert(id.clone(), component);
             }
         }
+        StatefulComponent::VideoClip(clip) => {
+            if let Some(id) = clip.component_id() {
+                components.insert(id.clone(), component);
+            }
+        }
         StatefulComponent::Text(image) => {
             if let Some(id) = image.component_id() {
                 components.insert(id.clone(), component);
             }
         }
+        StatefulComponent::Mask(mask) => {
+            if let Some(id) = mask.component_id() {
+                components.insert(id.clone(), component);
+            }
+            for child in mask.children.iter() {
+                gather_components_with_id(child, components);
+            }
+        }
         StatefulComponent::Layout(layout) => {
             if let Some(id) = layout.component_id() {
                 components.insert(id.clone(), component);
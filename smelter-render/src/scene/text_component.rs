@@ -38,7 +38,7 @@ impl TextComponent {
         self,
         ctx: &BuildStateTreeCtx,
     ) -> Result<StatefulComponent, SceneError> {
-        let (buffer, resolution) = ctx
+        let (buffer, resolution, layout) = ctx
             .text_renderer_ctx
             .layout_text((&self).into(), self.dimensions);
         Ok(StatefulComponent::Text(StatefulTextComponent {
@@ -47,6 +47,7 @@ impl TextComponent {
                 buffer,
                 resolution,
                 background_color: self.background_color,
+                layout,
             },
         }))
     }
@@ -0,0 +1,378 @@
+use std::{ops::Deref, time::Duration};
+
+use crate::transformations::layout::NestedLayout;
+
+use super::{
+    BlendMode, Component, ComponentId, IntermediateNode, Position, RGBAColor, SceneError, Size,
+    StatefulComponent, TickerComponent, TickerDirection, layout::StatefulLayoutComponent,
+    scene_state::BuildStateTreeCtx,
+};
+
+mod layout;
+
+#[derive(Debug, Clone)]
+pub(super) struct StatefulTickerComponent {
+    component: TickerComponentParams,
+
+    /// Scroll offset at `start_pts`.
+    start_offset: f32,
+    start_pts: Duration,
+
+    /// Minimal position of each child on the track. Children that were already
+    /// present before a scene update keep their position, so the update does not
+    /// restart or move the scroll. New children are never placed closer than
+    /// the edge of the component, so they do not pop up in the middle of it.
+    min_track_positions: Vec<f32>,
+
+    /// Scroll offset and children positions from the last layout call. This field
+    /// is a source of the initial state after scene update.
+    last_layout: Option<TickerLayout>,
+
+    children: Vec<StatefulComponent>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TickerComponentParams {
+    id: Option<ComponentId>,
+
+    width: Option<f32>,
+    height: Option<f32>,
+
+    direction: TickerDirection,
+    speed: f32,
+    gap: f32,
+    should_loop: bool,
+    background_color: RGBAColor,
+
+    opacity: f32,
+    blend_mode: BlendMode,
+}
+
+#[derive(Debug, Clone)]
+struct TickerLayout {
+    offset: f32,
+    track_positions: Vec<f32>,
+}
+
+impl StatefulTickerComponent {
+    pub(super) fn component_id(&self) -> Option<&ComponentId> {
+        self.component.id.as_ref()
+    }
+
+    pub(super) fn position(&self, _pts: Duration) -> Position {
+        Position::Static {
+            width: self.component.width,
+            height: self.component.height,
+        }
+    }
+
+    pub(super) fn children(&self) -> Vec<&StatefulComponent> {
+        self.children.iter().collect()
+    }
+
+    pub(super) fn children_mut(&mut self) -> Vec<&mut StatefulComponent> {
+        self.children.iter_mut().collect()
+    }
+
+    pub(super) fn intermediate_node(&self) -> IntermediateNode {
+        let children = self
+            .children
+            .iter()
+            .flat_map(|component| {
+                let node = component.intermediate_node();
+                match node {
+                    IntermediateNode::Layout { root: _, children } => children,
+                    _ => vec![node],
+                }
+            })
+            .collect();
+
+        IntermediateNode::Layout {
+            root: StatefulLayoutComponent::Ticker(self.clone()).into(),
+            children,
+        }
+    }
+
+    pub(super) fn layout(&mut self, size: Size, pts: Duration) -> NestedLayout {
+        let (layout, ticker_layout) = self.layout_children(size, pts);
+        self.last_layout = Some(ticker_layout);
+        layout
+    }
+
+    /// Position on the track from the last layout of a child that is still present
+    /// after scene update. Children with an id are matched by id, children without
+    /// it are matched by index.
+    fn last_track_position(&self, index: usize, child: &StatefulComponent) -> Option<f32> {
+        let last_layout = self.last_layout.as_ref()?;
+        let prev_index = match child.component_id() {
+            Some(id) => self
+                .children
+                .iter()
+                .position(|prev_child| prev_child.component_id() == Some(id))?,
+            None => match self.children.get(index)?.component_id() {
+                Some(_) => return None,
+                None => index,
+            },
+        };
+        last_layout.track_positions.get(prev_index).copied()
+    }
+}
+
+impl TickerComponent {
+    pub(super) fn stateful_component(
+        self,
+        ctx: &BuildStateTreeCtx,
+    ) -> Result<StatefulComponent, SceneError> {
+        let previous_state = self
+            .id
+            .as_ref()
+            .and_then(|id| ctx.prev_state.get(id))
+            .and_then(|component| match component {
+                StatefulComponent::Layout(boxed_layout) => match boxed_layout.deref() {
+                    StatefulLayoutComponent::Ticker(ticker_state) => Some(ticker_state),
+                    _ => None,
+                },
+                _ => None,
+            });
+
+        let component = TickerComponentParams {
+            id: self.id,
+            width: self.width,
+            height: self.height,
+            direction: self.direction,
+            speed: self.speed,
+            gap: self.gap,
+            should_loop: self.should_loop,
+            background_color: self.background_color,
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+        };
+        let children = self
+            .children
+            .into_iter()
+            .map(|c| Component::stateful_component(c, ctx))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let start_offset = previous_state
+            .and_then(|state| state.last_layout.as_ref())
+            .map(|layout| layout.offset)
+            .unwrap_or(0.0);
+        let min_track_positions = min_track_positions(previous_state, &children, start_offset);
+
+        let ticker = StatefulTickerComponent {
+            component,
+            start_offset,
+            start_pts: ctx.last_render_pts,
+            min_track_positions,
+            last_layout: previous_state.and_then(|state| state.last_layout.clone()),
+            children,
+        };
+
+        Ok(StatefulComponent::Layout(
+            StatefulLayoutComponent::Ticker(ticker).into(),
+        ))
+    }
+}
+
+/// New children start at the current scroll offset, so they enter the component
+/// from its edge.
+fn min_track_positions(
+    previous_state: Option<&StatefulTickerComponent>,
+    children: &[StatefulComponent],
+    start_offset: f32,
+) -> Vec<f32> {
+    children
+        .iter()
+        .enumerate()
+        .map(|(index, child)| {
+            previous_state
+                .and_then(|state| state.last_track_position(index, child))
+                .unwrap_or(start_offset)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        InputId,
+        scene::{InputStreamComponent, input_stream_component::StatefulInputStreamComponent},
+    };
+
+    use super::*;
+
+    const SIZE: Size = Size {
+        width: 300.0,
+        height: 50.0,
+    };
+
+    fn secs(secs: f64) -> Duration {
+        Duration::from_secs_f64(secs)
+    }
+
+    /// Input stream 100px wide, so with 10px gap items are placed every 110px.
+    fn item(id: Option<&str>) -> StatefulComponent {
+        StatefulComponent::InputStream(StatefulInputStreamComponent {
+            component: InputStreamComponent {
+                id: id.map(|id| ComponentId(id.into())),
+                input_id: InputId("input_1".into()),
+            },
+            size: Size {
+                width: 100.0,
+                height: 50.0,
+            },
+        })
+    }
+
+    /// Ticker scrolling to the left with 100px/s created at pts 0.
+    fn ticker(children: Vec<StatefulComponent>, should_loop: bool) -> StatefulTickerComponent {
+        StatefulTickerComponent {
+            component: TickerComponentParams {
+                id: None,
+                width: None,
+                height: None,
+                direction: TickerDirection::Left,
+                speed: 100.0,
+                gap: 10.0,
+                should_loop,
+                background_color: RGBAColor(0, 0, 0, 0),
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
+            },
+            start_offset: 0.0,
+            start_pts: Duration::ZERO,
+            min_track_positions: vec![0.0; children.len()],
+            last_layout: None,
+            children,
+        }
+    }
+
+    /// Same state as the one created by a scene update that replaced children of
+    /// the `previous` ticker.
+    fn update(
+        previous: &StatefulTickerComponent,
+        children: Vec<StatefulComponent>,
+        pts: Duration,
+    ) -> StatefulTickerComponent {
+        let start_offset = previous.last_layout.as_ref().unwrap().offset;
+        let min_track_positions = min_track_positions(Some(previous), &children, start_offset);
+        StatefulTickerComponent {
+            start_offset,
+            start_pts: pts,
+            min_track_positions,
+            last_layout: previous.last_layout.clone(),
+            ..ticker(children, previous.component.should_loop)
+        }
+    }
+
+    fn layout(ticker: &mut StatefulTickerComponent, pts: Duration) -> TickerLayout {
+        ticker.layout(SIZE, pts);
+        ticker.last_layout.clone().unwrap()
+    }
+
+    fn assert_offset(ticker: &mut StatefulTickerComponent, pts: Duration, expected: f32) {
+        let offset = layout(ticker, pts).offset;
+        assert!(
+            (offset - expected).abs() < 0.01,
+            "pts: {pts:?}, offset: {offset}, expected: {expected}"
+        );
+    }
+
+    #[test]
+    fn test_scroll_offset_without_loop() {
+        let mut ticker = ticker(vec![item(None), item(None)], false);
+        assert_offset(&mut ticker, secs(0.0), 0.0);
+        assert_offset(&mut ticker, secs(2.0), 200.0);
+        assert_offset(&mut ticker, secs(5.2), 520.0);
+    }
+
+    #[test]
+    fn test_scroll_offset_loop() {
+        // Track ends at 210px, so the cycle is 210px + 300px of the component width.
+        let mut ticker = ticker(vec![item(None), item(None)], true);
+        assert_offset(&mut ticker, secs(0.0), 0.0);
+        assert_offset(&mut ticker, secs(5.0), 500.0);
+        assert_offset(&mut ticker, secs(5.2), 10.0);
+        assert_offset(&mut ticker, secs(10.3), 10.0);
+    }
+
+    #[test]
+    fn test_scroll_offset_wrap_around_after_update() {
+        let mut previous = ticker(vec![item(None), item(None)], true);
+        assert_offset(&mut previous, secs(4.0), 400.0);
+
+        // Offset wraps relative to the offset from before the update.
+        let mut updated = update(&previous, vec![item(None), item(None)], secs(4.0));
+        assert_offset(&mut updated, secs(4.0), 400.0);
+        assert_offset(&mut updated, secs(5.2), 10.0);
+
+        // Without children there is nothing to loop over.
+        let mut empty = ticker(vec![], true);
+        assert_offset(&mut empty, secs(5.2), 520.0);
+    }
+
+    #[test]
+    fn test_scroll_offset_precision() {
+        // After 10 hours the offset is 3600050px, f32 can only represent it with
+        // 0.25px precision.
+        let mut ticker = ticker(vec![item(None), item(None)], true);
+        assert_offset(&mut ticker, secs(36_000.5), 470.0);
+        assert_offset(&mut ticker, secs(36_000.51), 471.0);
+        assert_offset(&mut ticker, secs(36_000.52), 472.0);
+    }
+
+    fn ticker_with_layout(pts: Duration) -> StatefulTickerComponent {
+        let mut ticker = ticker(vec![item(Some("a")), item(Some("b"))], false);
+        assert_eq!(layout(&mut ticker, pts).track_positions, [0.0, 110.0]);
+        ticker
+    }
+
+    #[test]
+    fn test_track_positions_append() {
+        let previous = ticker_with_layout(secs(1.0));
+
+        // New item is placed after existing ones, but not before the current offset.
+        let children = vec![item(Some("a")), item(Some("b")), item(Some("c"))];
+        let mut updated = update(&previous, children, secs(1.0));
+        assert_eq!(updated.min_track_positions, [0.0, 110.0, 100.0]);
+        let updated_layout = layout(&mut updated, secs(2.0));
+        assert_eq!(updated_layout.offset, 200.0);
+        assert_eq!(updated_layout.track_positions, [0.0, 110.0, 220.0]);
+
+        // Item appended after the track already passed is placed at the current offset.
+        let previous = ticker_with_layout(secs(5.0));
+        let children = vec![item(Some("a")), item(Some("b")), item(Some("c"))];
+        let mut updated = update(&previous, children, secs(5.0));
+        let updated_layout = layout(&mut updated, secs(5.0));
+        assert_eq!(updated_layout.offset, 500.0);
+        assert_eq!(updated_layout.track_positions, [0.0, 110.0, 500.0]);
+    }
+
+    #[test]
+    fn test_track_positions_reorder_by_id() {
+        let previous = ticker_with_layout(secs(1.0));
+
+        let updated = update(&previous, vec![item(Some("b")), item(Some("a"))], secs(1.0));
+        assert_eq!(updated.min_track_positions, [110.0, 0.0]);
+
+        // Removed item does not move the remaining ones.
+        let updated = update(&previous, vec![item(Some("b"))], secs(1.0));
+        assert_eq!(updated.min_track_positions, [110.0]);
+    }
+
+    #[test]
+    fn test_track_positions_by_index() {
+        let mut previous = ticker(vec![item(None), item(None)], false);
+        layout(&mut previous, secs(1.0));
+
+        let children = vec![item(None), item(None), item(None)];
+        let updated = update(&previous, children, secs(1.0));
+        assert_eq!(updated.min_track_positions, [0.0, 110.0, 100.0]);
+
+        // Item without an id is not matched with an item that had an id at the same
+        // index, it is treated as a new one.
+        let previous = ticker_with_layout(secs(1.0));
+        let updated = update(&previous, vec![item(None), item(Some("a"))], secs(1.0));
+        assert_eq!(updated.min_track_positions, [100.0, 0.0]);
+    }
+}
@@ -0,0 +1,227 @@
+use std::time::Duration;
+
+use crate::{
+    scene::{
+        BlendMode, BorderRadius, RGBAColor, Size, StatefulComponent, TickerDirection,
+        layout::StatefulLayoutComponent,
+    },
+    transformations::layout::{LayoutContent, Mask, NestedLayout},
+};
+
+use super::{StatefulTickerComponent, TickerLayout};
+
+/// Size of a child along the scroll direction and perpendicular to it.
+#[derive(Debug, Clone, Copy)]
+struct ItemSize {
+    main: f32,
+    cross: f32,
+}
+
+impl StatefulTickerComponent {
+    pub(super) fn layout_children(
+        &mut self,
+        size: Size,
+        pts: Duration,
+    ) -> (NestedLayout, TickerLayout) {
+        let direction = self.component.direction;
+        let (track_length, cross_length) = match direction {
+            TickerDirection::Left | TickerDirection::Right => (size.width, size.height),
+            TickerDirection::Up | TickerDirection::Down => (size.height, size.width),
+        };
+
+        let item_sizes: Vec<_> = self
+            .children
+            .iter()
+            .map(|child| {
+                let (main, cross) = match direction {
+                    TickerDirection::Left | TickerDirection::Right => {
+                        (child.width(pts), child.height(pts))
+                    }
+                    TickerDirection::Up | TickerDirection::Down => {
+                        (child.height(pts), child.width(pts))
+                    }
+                };
+                ItemSize {
+                    main: main.unwrap_or(track_length),
+                    cross: cross.unwrap_or(cross_length),
+                }
+            })
+            .collect();
+        let track_positions = self.track_positions(&item_sizes);
+        let offset = self.scroll_offset(track_length, &track_positions, &item_sizes, pts);
+
+        let children = self
+            .children
+            .iter_mut()
+            .zip(track_positions.iter().zip(item_sizes.iter()))
+            .map(|(child, (track_position, item_size))| {
+                // distance between the edge where items are leaving the component
+                // and the front of the item
+                let distance_to_exit = track_length + track_position - offset;
+                let cross_offset = (cross_length - item_size.cross) / 2.0;
+                let (top, left, width, height) = match direction {
+                    TickerDirection::Left => (
+                        cross_offset,
+                        distance_to_exit,
+                        item_size.main,
+                        item_size.cross,
+                    ),
+                    TickerDirection::Right => (
+                        cross_offset,
+                        track_length - distance_to_exit - item_size.main,
+                        item_size.main,
+                        item_size.cross,
+                    ),
+                    TickerDirection::Up => (
+                        distance_to_exit,
+                        cross_offset,
+                        item_size.cross,
+                        item_size.main,
+                    ),
+                    TickerDirection::Down => (
+                        track_length - distance_to_exit - item_size.main,
+                        cross_offset,
+                        item_size.cross,
+                        item_size.main,
+                    ),
+                };
+                Self::layout_child(child, top, left, Size { width, height }, pts)
+            })
+            .collect::<Vec<_>>();
+
+        let layout = NestedLayout {
+            top: 0.0,
+            left: 0.0,
+            width: size.width,
+            height: size.height,
+            rotation_degrees: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            crop: None,
+            mask: Some(Mask {
+                radius: BorderRadius::ZERO,
+                top: 0.0,
+                left: 0.0,
+                width: size.width,
+                height: size.height,
+            }),
+            content: LayoutContent::Color(self.component.background_color),
+            child_nodes_count: children.iter().map(|l| l.child_nodes_count).sum(),
+            children,
+            border_width: 0.0,
+            border_color: RGBAColor(0, 0, 0, 0),
+            border_radius: BorderRadius::ZERO,
+            box_shadow: vec![],
+            opacity: self.component.opacity,
+            blend_mode: self.component.blend_mode,
+            component_id: None,
+        };
+        (
+            layout,
+            TickerLayout {
+                offset,
+                track_positions,
+            },
+        )
+    }
+
+    /// Position of the front of each child on the track. Children are placed
+    /// one after another, but not before their minimal position.
+    fn track_positions(&self, item_sizes: &[ItemSize]) -> Vec<f32> {
+        let mut next_position = f32::MIN;
+        self.min_track_positions
+            .iter()
+            .zip(item_sizes.iter())
+            .map(|(min_position, item_size)| {
+                let position = f32::max(*min_position, next_position);
+                next_position = position + item_size.main + self.component.gap;
+                position
+            })
+            .collect()
+    }
+
+    /// Distance that the track moved since the component was created. If looping
+    /// is enabled, the track goes back to the position where the first child is entering
+    /// the component after the last one leaves it.
+    fn scroll_offset(
+        &self,
+        track_length: f32,
+        track_positions: &[f32],
+        item_sizes: &[ItemSize],
+        pts: Duration,
+    ) -> f32 {
+        // f32 is not precise enough to represent the offset after a few hours of scrolling
+        let elapsed = pts.saturating_sub(self.start_pts).as_secs_f64();
+        let offset = self.start_offset as f64 + elapsed * self.component.speed as f64;
+
+        let (Some(first), Some(last), Some(last_size)) = (
+            track_positions.first(),
+            track_positions.last(),
+            item_sizes.last(),
+        ) else {
+            return offset as f32;
+        };
+        let (first, track_end) = (*first as f64, (*last + last_size.main) as f64);
+        let cycle_length = track_end - first + track_length as f64;
+        if !self.component.should_loop || offset < first || cycle_length <= 0.0 {
+            return offset as f32;
+        }
+        (first + (offset - first) % cycle_length) as f32
+    }
+
+    fn layout_child(
+        child: &mut StatefulComponent,
+        top: f32,
+        left: f32,
+        size: Size,
+        pts: Duration,
+    ) -> NestedLayout {
+        match child {
+            StatefulComponent::Layout(layout_component) => {
+                let children_layouts = layout_component.layout(size, pts);
+                NestedLayout {
+                    top,
+                    left,
+                    width: size.width,
+                    height: size.height,
+                    rotation_degrees: 0.0,
+                    scale_x: 1.0,
+                    scale_y: 1.0,
+                    crop: None,
+                    mask: None,
+                    content: LayoutContent::None,
+                    child_nodes_count: children_layouts.child_nodes_count,
+                    children: vec![children_layouts],
+                    border_width: 0.0,
+                    border_color: RGBAColor(0, 0, 0, 0),
+                    border_radius: BorderRadius::ZERO,
+                    box_shadow: vec![],
+                    opacity: 1.0,
+                    blend_mode: BlendMode::Normal,
+                    component_id: layout_component.component_id().cloned(),
+                }
+            }
+            _ => NestedLayout {
+                top,
+                left,
+                width: size.width,
+                height: size.height,
+                rotation_degrees: 0.0,
+                scale_x: 1.0,
+                scale_y: 1.0,
+                crop: None,
+                mask: None,
+                content: StatefulLayoutComponent::layout_content(child, 0),
+                child_nodes_count: 1,
+                children: vec![],
+                border_width: 0.0,
+                border_color: RGBAColor(0, 0, 0, 0),
+                border_radius: BorderRadius::ZERO,
+                box_shadow: vec![],
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
+                component_id: child.component_id().cloned(),
+            },
+        }
+    }
+}
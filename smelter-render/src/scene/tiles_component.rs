@@ -8,8 +8,8 @@ use self::{
 };
 
 use super::{
-    Component, ComponentId, HorizontalAlign, IntermediateNode, Position, RGBAColor, SceneError,
-    Size, StatefulComponent, TilesComponent, VerticalAlign,
+    BlendMode, Component, ComponentId, HorizontalAlign, IntermediateNode, Position, RGBAColor,
+    SceneError, Size, StatefulComponent, TilesComponent, VerticalAlign,
     layout::StatefulLayoutComponent,
     scene_state::BuildStateTreeCtx,
     transition::{TransitionOptions, TransitionState},
@@ -30,6 +30,9 @@ pub(super) struct StatefulTilesComponent {
     /// `start` value after scene update.
     last_layout: Option<(Vec<Option<Tile>>, Size)>,
 
+    /// Initial opacity for transition.
+    start_opacity: Option<f32>,
+
     transition: Option<TransitionState>,
 
     component: TilesComponentParams,
@@ -49,6 +52,9 @@ struct TilesComponentParams {
     padding: f32,
     horizontal_align: HorizontalAlign,
     vertical_align: VerticalAlign,
+
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl StatefulTilesComponent {
@@ -66,6 +72,14 @@ impl StatefulTilesComponent {
         ContinuousValue::interpolate(&start, &end, interpolation_progress)
     }
 
+    fn opacity(&self, pts: Duration) -> f32 {
+        let (Some(start), Some(transition)) = (self.start_opacity, &self.transition) else {
+            return self.component.opacity;
+        };
+        let interpolation_progress = transition.state(pts);
+        ContinuousValue::interpolate(&start, &self.component.opacity, interpolation_progress)
+    }
+
     pub(super) fn position(&self, _pts: Duration) -> Position {
         Position::Static {
             width: self.component.width,
@@ -110,7 +124,11 @@ impl StatefulTilesComponent {
             self.component.background_color,
         );
         self.last_layout = Some((tiles, size));
-        layout
+        NestedLayout {
+            opacity: self.opacity(pts),
+            blend_mode: self.component.blend_mode,
+            ..layout
+        }
     }
 }
 
@@ -142,6 +160,8 @@ impl TilesComponent {
             padding: self.padding,
             horizontal_align: self.horizontal_align,
             vertical_align: self.vertical_align,
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
         };
         let children = self
             .children
@@ -170,6 +190,7 @@ impl TilesComponent {
         let tiles = StatefulTilesComponent {
             start,
             last_layout: previous_state.and_then(|state| state.last_layout.clone()),
+            start_opacity: previous_state.map(|state| state.opacity(ctx.last_render_pts)),
             component,
             transition,
             children,
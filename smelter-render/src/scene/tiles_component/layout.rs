@@ -1,7 +1,10 @@
 use std::time::Duration;
 
 use crate::{
-    scene::{BorderRadius, RGBAColor, Size, StatefulComponent, layout::StatefulLayoutComponent},
+    scene::{
+        BlendMode, BorderRadius, RGBAColor, Size, StatefulComponent,
+        layout::StatefulLayoutComponent,
+    },
     transformations::layout::{LayoutContent, NestedLayout},
 };
 
@@ -37,6 +40,9 @@ pub(super) fn layout_tiles(
         border_color: RGBAColor(0, 0, 0, 0),
         border_radius: BorderRadius::ZERO,
         box_shadow: vec![],
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+        component_id: None,
     }
 }
 
@@ -77,6 +83,9 @@ fn layout_child(child: &mut StatefulComponent, tile: Option<Tile>, pts: Duration
                 border_color: RGBAColor(0, 0, 0, 0),
                 border_radius: BorderRadius::ZERO,
                 box_shadow: vec![],
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
+                component_id: layout_component.component_id().cloned(),
             }
         }
         _ => {
@@ -99,6 +108,9 @@ fn layout_child(child: &mut StatefulComponent, tile: Option<Tile>, pts: Duration
                 border_color: RGBAColor(0, 0, 0, 0),
                 border_radius: BorderRadius::ZERO,
                 box_shadow: vec![],
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
+                component_id: child.component_id().cloned(),
             }
         }
     }
@@ -106,7 +106,7 @@ impl TransitionState {
 }
 
 impl InterpolationKind {
-    fn state(&self, t: f64) -> InterpolationState {
+    pub(super) fn state(&self, t: f64) -> InterpolationState {
         match self {
             InterpolationKind::Linear => InterpolationState(t),
             InterpolationKind::Bounce => InterpolationState(bounce_easing(t)),
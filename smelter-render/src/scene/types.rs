@@ -1,5 +1,7 @@
 use std::ops::{Add, Div, Mul, Sub};
 
+use crate::ColorMatrix;
+
 mod convert;
 pub(crate) mod interpolation;
 
@@ -25,19 +27,23 @@ pub struct RGBColor(pub u8, pub u8, pub u8);
 impl RGBColor {
     pub const BLACK: Self = Self(0, 0, 0);
 
-    pub fn to_yuv(&self) -> (f32, f32, f32) {
+    /// Returns Y in range [0, 1] and U, V in range [-0.5, 0.5].
+    pub fn to_yuv(&self, matrix: ColorMatrix) -> (f32, f32, f32) {
         let r = self.0 as f32 / 255.0;
         let g = self.1 as f32 / 255.0;
         let b = self.2 as f32 / 255.0;
 
-        let y = r * 0.2126 + g * 0.7152 + b * 0.0722;
-        let u = r * -0.1146 + g * -0.3854 + b * 0.5;
-        let v = r * 0.5 + g * -0.4542 + b * -0.0458;
-        (
-            ((y * 0.858_823_54) + (16.0 / 255.0)).clamp(0.0, 1.0),
-            (((u + 0.5) * 0.878_431_4) + (16.0 / 255.0)).clamp(0.0, 1.0),
-            (((v + 0.5) * 0.878_431_4) + (16.0 / 255.0)).clamp(0.0, 1.0),
-        )
+        let (kr, kb) = match matrix {
+            ColorMatrix::Bt601 => (0.299, 0.114),
+            ColorMatrix::Bt709 => (0.2126, 0.0722),
+            ColorMatrix::Bt2020 => (0.2627, 0.0593),
+        };
+        let kg = 1.0 - kr - kb;
+
+        let y = kr * r + kg * g + kb * b;
+        let u = (b - y) / (2.0 * (1.0 - kb));
+        let v = (r - y) / (2.0 * (1.0 - kr));
+        (y, u, v)
     }
 }
 
@@ -166,3 +172,14 @@ pub struct BoxShadow {
     pub blur_radius: f32,
     pub color: RGBAColor,
 }
+
+/// Defines how colors of a component are combined with colors of components
+/// rendered below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    Normal,
+    Multiply,
+    Screen,
+    Add,
+    Overlay,
+}
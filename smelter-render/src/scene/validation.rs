@@ -5,29 +5,37 @@ use crate::{OutputId, RendererId};
 use super::{Component, ComponentId, OutputScene, SceneError};
 
 impl Component {
-    fn component_id(&self) -> Option<&ComponentId> {
+    pub(super) fn component_id(&self) -> Option<&ComponentId> {
         match self {
             Component::InputStream(input) => input.id.as_ref(),
             Component::Shader(shader) => shader.id.as_ref(),
             Component::WebView(web) => web.id.as_ref(),
             Component::Image(image) => image.id.as_ref(),
+            Component::VideoClip(clip) => clip.id.as_ref(),
             Component::Text(text) => text.id.as_ref(),
+            Component::Mask(mask) => mask.id.as_ref(),
             Component::View(view) => view.id.as_ref(),
             Component::Tiles(tiles) => tiles.id.as_ref(),
             Component::Rescaler(rescaler) => rescaler.id.as_ref(),
+            Component::Ticker(ticker) => ticker.id.as_ref(),
+            Component::Fragment(_fragment) => None,
         }
     }
 
-    fn children(&self) -> Vec<&Component> {
+    pub(super) fn children(&self) -> Vec<&Component> {
         match self {
             Component::InputStream(_input) => vec![],
             Component::Shader(shader) => shader.children.iter().collect(),
             Component::WebView(view) => view.children.iter().collect(),
             Component::Image(_image) => vec![],
+            Component::VideoClip(_clip) => vec![],
             Component::Text(_text) => vec![],
+            Component::Mask(mask) => vec![mask.content.as_ref(), mask.mask.as_ref()],
             Component::View(view) => view.children.iter().collect(),
             Component::Tiles(tiles) => tiles.children.iter().collect(),
             Component::Rescaler(rescaler) => vec![rescaler.child.as_ref()],
+            Component::Ticker(ticker) => ticker.children.iter().collect(),
+            Component::Fragment(_fragment) => vec![],
         }
     }
 }
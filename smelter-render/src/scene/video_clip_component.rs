@@ -0,0 +1,114 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{Resolution, scene::Size, transformations::video_clip::VideoClip};
+
+use super::{
+    ComponentId, ImagePlayback, IntermediateNode, SceneError, StatefulComponent,
+    VideoClipComponent, scene_state::BuildStateTreeCtx,
+};
+
+#[derive(Debug)]
+pub(crate) struct VideoClipRenderParams {
+    pub(crate) clip: Arc<VideoClip>,
+    pub(crate) start_pts: Duration,
+    pub(crate) resolution: Resolution,
+    pub(crate) playback: ImagePlayback,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct StatefulVideoClipComponent {
+    pub(super) component: VideoClipComponent,
+    pub(super) clip: Arc<VideoClip>,
+    pub(super) start_pts: Duration,
+    pub(super) resolution: Resolution,
+}
+
+impl StatefulVideoClipComponent {
+    pub(super) fn component_id(&self) -> Option<&ComponentId> {
+        self.component.id.as_ref()
+    }
+
+    pub(super) fn width(&self) -> f32 {
+        self.resolution.width as f32
+    }
+    pub(super) fn height(&self) -> f32 {
+        self.resolution.height as f32
+    }
+    pub(super) fn size(&self) -> Size {
+        Size {
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+
+    pub(super) fn intermediate_node(&self) -> IntermediateNode {
+        IntermediateNode::VideoClip(self.clone())
+    }
+
+    pub(super) fn render_params(self) -> VideoClipRenderParams {
+        VideoClipRenderParams {
+            clip: self.clip,
+            start_pts: self.start_pts,
+            resolution: self.resolution,
+            playback: self.component.playback,
+        }
+    }
+}
+
+impl VideoClipComponent {
+    pub(super) fn stateful_component(
+        self,
+        ctx: &BuildStateTreeCtx,
+    ) -> Result<StatefulComponent, SceneError> {
+        let clip = ctx
+            .renderers
+            .video_clips
+            .get(&self.clip_id)
+            .ok_or_else(|| SceneError::VideoClipNotFound(self.clip_id.clone()))?;
+
+        let original_aspect_ratio =
+            clip.resolution().width as f32 / clip.resolution().height as f32;
+
+        let resolution = match (self.width, self.height) {
+            (Some(width), Some(height)) => Resolution {
+                width: width.round() as usize,
+                height: height.round() as usize,
+            },
+            (Some(width), None) => Resolution {
+                width: width.round() as usize,
+                height: (width / original_aspect_ratio).round() as usize,
+            },
+            (None, Some(height)) => Resolution {
+                width: (height * original_aspect_ratio).round() as usize,
+                height: height.round() as usize,
+            },
+            (None, None) => clip.resolution(),
+        };
+
+        let prev_state = self
+            .id
+            .as_ref()
+            .and_then(|id| ctx.prev_state.get(id))
+            .and_then(|component| match component {
+                StatefulComponent::VideoClip(clip) => Some(clip),
+                _ => None,
+            });
+
+        let start_pts = match prev_state {
+            Some(state)
+                if Arc::ptr_eq(&state.clip, &clip) && self.playback == state.component.playback =>
+            {
+                state.start_pts
+            }
+            _ if self.playback.restart_on_mount => ctx.last_render_pts,
+            _ => Duration::ZERO,
+        };
+
+        Ok(StatefulComponent::VideoClip(StatefulVideoClipComponent {
+            component: self,
+            clip,
+            start_pts,
+            resolution,
+        }))
+    }
+}
@@ -3,8 +3,9 @@ use std::{ops::Deref, time::Duration};
 use crate::{scene::ViewChildrenDirection, transformations::layout::NestedLayout};
 
 use super::{
-    BorderRadius, BoxShadow, Component, ComponentId, IntermediateNode, Overflow, Padding, Position,
-    RGBAColor, SceneError, Size, StatefulComponent,
+    AlignItems, BlendMode, BorderRadius, BoxShadow, Component, ComponentId, Flex, IntermediateNode,
+    JustifyContent, Overflow, Padding, Position, RGBAColor, SceneError, Size, StatefulComponent,
+    animation::AnimationState,
     components::ViewComponent,
     layout::StatefulLayoutComponent,
     scene_state::BuildStateTreeCtx,
@@ -20,6 +21,7 @@ pub(super) struct StatefulViewComponent {
     start: Option<ViewComponentParam>,
     end: ViewComponentParam,
     transition: Option<TransitionState>,
+    animation: Option<AnimationState>,
     children: Vec<StatefulComponent>,
 }
 
@@ -39,15 +41,35 @@ struct ViewComponentParam {
     box_shadow: Vec<BoxShadow>,
 
     padding: Padding,
+
+    gap: f32,
+    justify_content: JustifyContent,
+    align_items: AlignItems,
+    wrap: bool,
+
+    flex: Flex,
+
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl StatefulViewComponent {
     fn view(&self, pts: Duration) -> ViewComponentParam {
-        let (Some(transition), Some(start)) = (&self.transition, &self.start) else {
-            return self.end.clone();
+        let view = match (&self.transition, &self.start) {
+            (Some(transition), Some(start)) => {
+                ContinuousValue::interpolate(start, &self.end, transition.state(pts))
+            }
+            _ => self.end.clone(),
         };
-        let interpolation_progress = transition.state(pts);
-        ContinuousValue::interpolate(start, &self.end, interpolation_progress)
+        let Some(animation) = &self.animation else {
+            return view;
+        };
+        let properties = animation.properties(pts);
+        ViewComponentParam {
+            position: view.position.with_animated_properties(&properties),
+            opacity: properties.opacity.unwrap_or(view.opacity),
+            ..view
+        }
     }
 
     pub(super) fn children(&self) -> Vec<&StatefulComponent> {
@@ -66,6 +88,10 @@ impl StatefulViewComponent {
             .with_padding(view.padding)
     }
 
+    pub(super) fn flex(&self, pts: Duration) -> Flex {
+        self.view(pts).flex
+    }
+
     pub(super) fn component_id(&self) -> Option<&ComponentId> {
         self.end.id.as_ref()
     }
@@ -93,6 +119,12 @@ impl StatefulViewComponent {
         let view = self.view(pts);
         view.layout(size, &mut self.children, pts)
     }
+
+    /// Size of static children including padding and border, used if the view size
+    /// is not defined.
+    pub(super) fn content_size(&self, pts: Duration) -> Size {
+        self.view(pts).content_size(&self.children, pts)
+    }
 }
 
 impl ViewComponent {
@@ -126,6 +158,13 @@ impl ViewComponent {
             border_color: self.border_color,
             box_shadow: self.box_shadow,
             padding: self.padding,
+            gap: self.gap,
+            justify_content: self.justify_content,
+            align_items: self.align_items,
+            wrap: self.wrap,
+            flex: self.flex,
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
         };
 
         let props_changed = previous_state
@@ -143,10 +182,16 @@ impl ViewComponent {
             interrupt_previous_transition,
             ctx.last_render_pts,
         );
+        let animation = AnimationState::new(
+            self.animation,
+            previous_state.and_then(|s| s.animation.as_ref()),
+            ctx.last_render_pts,
+        );
         let view = StatefulViewComponent {
             start,
             end,
             transition,
+            animation,
             children: self
                 .children
                 .into_iter()
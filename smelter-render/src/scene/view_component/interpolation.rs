@@ -23,6 +23,13 @@ impl ContinuousValue for ViewComponentParam {
             border_color: end.border_color,
             box_shadow: ContinuousValue::interpolate(&start.box_shadow, &end.box_shadow, state),
             padding: ContinuousValue::interpolate(&start.padding, &end.padding, state),
+            gap: ContinuousValue::interpolate(&start.gap, &end.gap, state),
+            justify_content: end.justify_content,
+            align_items: end.align_items,
+            wrap: end.wrap,
+            flex: ContinuousValue::interpolate(&start.flex, &end.flex, state),
+            opacity: ContinuousValue::interpolate(&start.opacity, &end.opacity, state),
+            blend_mode: end.blend_mode,
         }
     }
 }
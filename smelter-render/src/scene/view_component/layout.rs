@@ -1,30 +1,47 @@
-use std::time::Duration;
+use std::{ops::Range, time::Duration};
 
 use crate::{
     scene::{
-        BorderRadius, Overflow, Position, RGBAColor, Size, StatefulComponent,
-        ViewChildrenDirection, layout::StatefulLayoutComponent,
+        AlignItems, BlendMode, BorderRadius, Flex, JustifyContent, Overflow, Position, RGBAColor,
+        Size, StatefulComponent, ViewChildrenDirection, layout::StatefulLayoutComponent,
     },
     transformations::layout::{LayoutContent, Mask, NestedLayout},
 };
 
 use super::ViewComponentParam;
 
+/// Static child described in terms of the main axis (defined by the view direction)
+/// and the cross axis (perpendicular to it).
+#[derive(Debug, Clone, Copy)]
+struct FlexItem {
+    /// Size along the main axis before growing or shrinking.
+    basis: f32,
+    /// Size along the cross axis if it's defined explicitly.
+    cross_size: Option<f32>,
+    /// Size of the content along the main axis. Children never shrink below it.
+    min_main_size: f32,
+    /// Size of the content along the cross axis, used if the child is not stretched.
+    /// Components that fit their content into any size do not define it.
+    content_cross_size: Option<f32>,
+    grow: f32,
+    shrink: f32,
+}
+
+/// Static children placed in a single row (or column). If wrapping is disabled
+/// all static children belong to the same line.
 #[derive(Debug)]
-struct StaticChildLayoutOpts {
-    width: Option<f32>,
-    height: Option<f32>,
-    /// Offset inside parent component (position where next static child should start).
-    static_offset: f32,
-    /// Define size(width or height) of a static component if it's not
-    /// already defined explicitly.
-    /// For direction=row defines width of a static component
-    /// For direction=column defines height of a static component
-    static_child_size: f32,
-    parent_size: Size,
-    /// border width before rescaling, it is used to calculate top/left offset correctly
-    /// when `overflow: fit` is set
-    parent_border_width: f32,
+struct FlexLine {
+    items: Range<usize>,
+    /// Size of the line along the cross axis.
+    cross_size: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct StaticChildLayout {
+    top: f32,
+    left: f32,
+    width: f32,
+    height: f32,
 }
 
 impl ViewComponentParam {
@@ -39,7 +56,21 @@ impl ViewComponentParam {
             height: f32::max(size.height - 2.0 * self.border_width, 0.0),
         };
         let border_radius = self.border_radius.clip_to_size(size);
-        let static_child_size = self.static_child_size(content_size, children, pts);
+
+        let positions: Vec<_> = children
+            .iter()
+            .map(|child| match child {
+                StatefulComponent::Layout(layout) => layout.position(pts),
+                non_layout_component => Position::Static {
+                    width: non_layout_component.width(pts),
+                    height: non_layout_component.height(pts),
+                },
+            })
+            .collect();
+        let items = self.flex_items(children, &positions, pts);
+        let (available_main, available_cross) = self.available_size(content_size);
+        let lines = self.flex_lines(&items, available_main, available_cross);
+
         let (scale, crop, mask) = match self.overflow {
             Overflow::Visible => (1.0, None, None),
             Overflow::Hidden => (
@@ -54,7 +85,7 @@ impl ViewComponentParam {
                 }),
             ),
             Overflow::Fit => (
-                self.scale_factor_for_overflow_fit(content_size, children, pts),
+                self.scale_factor_for_overflow_fit(content_size, &items, &lines),
                 None,
                 Some(Mask {
                     radius: border_radius - self.border_width,
@@ -66,43 +97,23 @@ impl ViewComponentParam {
             ),
         };
 
-        // offset along x or y direction (depends on self.direction) where next
-        // child component should be placed
-        let mut static_offset = self.border_width / scale;
+        let mut static_layouts = self
+            .static_children_layouts(&items, &lines, available_main, self.border_width / scale)
+            .into_iter();
 
         let children: Vec<_> = children
             .iter_mut()
-            .map(|child| {
-                let position = match child {
-                    StatefulComponent::Layout(layout) => layout.position(pts),
-                    ref non_layout_component => Position::Static {
-                        width: non_layout_component.width(pts),
-                        height: non_layout_component.height(pts),
-                    },
-                };
-                match position {
-                    Position::Static { width, height } => {
-                        let (layout, updated_static_offset) = self.layout_static_child(
-                            child,
-                            StaticChildLayoutOpts {
-                                width,
-                                height,
-                                static_offset,
-                                static_child_size,
-                                parent_size: content_size,
-                                parent_border_width: self.border_width / scale,
-                            },
-                            pts,
-                        );
-
-                        static_offset = updated_static_offset;
-                        layout
-                    }
-                    Position::Absolute(position) => {
-                        StatefulLayoutComponent::layout_absolute_position_child(
-                            child, position, size, pts,
-                        )
-                    }
+            .zip(positions)
+            .map(|(child, position)| match position {
+                Position::Static { .. } => {
+                    // there is exactly one static layout for each static child
+                    let static_layout = static_layouts.next().unwrap_or_default();
+                    Self::layout_static_child(child, static_layout, pts)
+                }
+                Position::Absolute(position) => {
+                    StatefulLayoutComponent::layout_absolute_position_child(
+                        child, position, size, pts,
+                    )
                 }
             })
             .collect();
@@ -123,40 +134,24 @@ impl ViewComponentParam {
             border_color: self.border_color,
             border_radius,
             box_shadow: self.box_shadow.clone(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            component_id: None,
         }
     }
 
     fn layout_static_child(
-        &self,
         child: &mut StatefulComponent,
-        opts: StaticChildLayoutOpts,
+        static_layout: StaticChildLayout,
         pts: Duration,
-    ) -> (NestedLayout, f32) {
-        let mut static_offset = opts.static_offset;
-        let (top, left, width, height) = match self.direction {
-            ViewChildrenDirection::Row => {
-                let width = opts.width.unwrap_or(opts.static_child_size);
-                let height = opts
-                    .height
-                    .unwrap_or(opts.parent_size.height - self.padding.vertical());
-                let top = opts.parent_border_width + self.padding.top;
-                let left = static_offset + self.padding.left;
-                static_offset += width;
-                (top, left, width, height)
-            }
-            ViewChildrenDirection::Column => {
-                let height = opts.height.unwrap_or(opts.static_child_size);
-                let width = opts
-                    .width
-                    .unwrap_or(opts.parent_size.width - self.padding.horizontal());
-                let top = static_offset + self.padding.top;
-                let left = opts.parent_border_width + self.padding.left;
-                static_offset += height;
-                (top, left, width, height)
-            }
-        };
-
-        let layout = match child {
+    ) -> NestedLayout {
+        let StaticChildLayout {
+            top,
+            left,
+            width,
+            height,
+        } = static_layout;
+        match child {
             StatefulComponent::Layout(layout_component) => {
                 let children_layouts = layout_component.layout(Size { width, height }, pts);
                 NestedLayout {
@@ -176,6 +171,9 @@ impl ViewComponentParam {
                     border_color: RGBAColor(0, 0, 0, 0),
                     border_radius: BorderRadius::ZERO,
                     box_shadow: vec![],
+                    opacity: 1.0,
+                    blend_mode: BlendMode::Normal,
+                    component_id: layout_component.component_id().cloned(),
                 }
             }
             _ => NestedLayout {
@@ -195,91 +193,374 @@ impl ViewComponentParam {
                 border_color: RGBAColor(0, 0, 0, 0),
                 border_radius: BorderRadius::ZERO,
                 box_shadow: vec![],
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
+                component_id: child.component_id().cloned(),
             },
-        };
-        (layout, static_offset)
+        }
     }
 
-    /// Calculate a size of a static child component that does not have it explicitly defined.
-    /// Returned value represents width if the direction is `ViewChildrenDirection::Row` or
-    /// height if the direction is `ViewChildrenDirection::Column`.
+    fn flex_items(
+        &self,
+        children: &[StatefulComponent],
+        positions: &[Position],
+        pts: Duration,
+    ) -> Vec<FlexItem> {
+        children
+            .iter()
+            .zip(positions)
+            .filter_map(|(child, position)| match position {
+                Position::Static { width, height } => Some((child, *width, *height)),
+                Position::Absolute(_) => None,
+            })
+            .map(|(child, width, height)| {
+                let (main_size, cross_size) = match self.direction {
+                    ViewChildrenDirection::Row => (width, height),
+                    ViewChildrenDirection::Column => (height, width),
+                };
+                // only `View` and `Rescaler` define flex properties
+                let flex = match child {
+                    StatefulComponent::Layout(layout) => layout.flex(pts),
+                    _ => Flex::default(),
+                };
+                let default_grow = match main_size {
+                    Some(_) => 0.0,
+                    None => 1.0,
+                };
+                let basis = main_size.unwrap_or(0.0);
+                let content_size =
+                    child_content_size(child, pts).map(|size| match self.direction {
+                        ViewChildrenDirection::Row => (size.width, size.height),
+                        ViewChildrenDirection::Column => (size.height, size.width),
+                    });
+                FlexItem {
+                    basis,
+                    cross_size,
+                    min_main_size: content_size.map_or(0.0, |(main, _)| f32::min(main, basis)),
+                    content_cross_size: content_size.map(|(_, cross)| cross),
+                    grow: flex.grow.unwrap_or(default_grow),
+                    shrink: flex.shrink,
+                }
+            })
+            .collect()
+    }
+
+    /// Size available for static children along the main and the cross axis.
     ///
-    /// size represents dimensions of content (without a border).
-    fn static_child_size(&self, size: Size, children: &[StatefulComponent], pts: Duration) -> f32 {
-        let max_size = match self.direction {
-            super::ViewChildrenDirection::Row => size.width - self.padding.horizontal(),
-            super::ViewChildrenDirection::Column => size.height - self.padding.vertical(),
+    /// content_size represents dimensions of content (without a border).
+    fn available_size(&self, content_size: Size) -> (f32, f32) {
+        let width = content_size.width - self.padding.horizontal();
+        let height = content_size.height - self.padding.vertical();
+        match self.direction {
+            ViewChildrenDirection::Row => (width, height),
+            ViewChildrenDirection::Column => (height, width),
+        }
+    }
+
+    /// Split static children into lines. A new line is started when the next child does
+    /// not fit into the current one, every line contains at least one child.
+    fn flex_lines(
+        &self,
+        items: &[FlexItem],
+        available_main: f32,
+        available_cross: f32,
+    ) -> Vec<FlexLine> {
+        if !self.wrap {
+            return vec![FlexLine {
+                items: 0..items.len(),
+                cross_size: available_cross,
+            }];
+        }
+
+        let mut ranges = vec![];
+        let mut line_start = 0;
+        let mut line_size = 0.0;
+        for (index, item) in items.iter().enumerate() {
+            if index == line_start {
+                line_size = item.basis;
+            } else if line_size + self.gap + item.basis > available_main {
+                ranges.push(line_start..index);
+                line_start = index;
+                line_size = item.basis;
+            } else {
+                line_size += self.gap + item.basis;
+            }
+        }
+        if line_start < items.len() {
+            ranges.push(line_start..items.len());
+        }
+
+        // Lines without any explicitly sized child share the remaining space equally.
+        let explicit_sizes: Vec<_> = ranges
+            .iter()
+            .map(|range| {
+                items[range.clone()]
+                    .iter()
+                    .filter_map(|item| self.fixed_cross_size(item))
+                    .max_by(f32::total_cmp)
+            })
+            .collect();
+        let unknown_size_count = explicit_sizes.iter().filter(|s| s.is_none()).count();
+        let unknown_line_size = match unknown_size_count {
+            0 => 0.0,
+            count => f32::max(
+                0.0,
+                (available_cross
+                    - explicit_sizes.iter().flatten().sum::<f32>()
+                    - self.gaps_size(ranges.len()))
+                    / count as f32,
+            ),
         };
 
-        let children_with_unknown_size_count = Self::static_children_iter(children, pts)
-            .filter(|child| match self.direction {
-                ViewChildrenDirection::Row => child.width(pts).is_none(),
-                ViewChildrenDirection::Column => child.height(pts).is_none(),
+        ranges
+            .into_iter()
+            .zip(explicit_sizes)
+            .map(|(items, cross_size)| FlexLine {
+                items,
+                cross_size: cross_size.unwrap_or(unknown_line_size),
             })
-            .count();
-        let static_children_sum = self.sum_static_children_sizes(children, pts);
+            .collect()
+    }
+
+    fn static_children_layouts(
+        &self,
+        items: &[FlexItem],
+        lines: &[FlexLine],
+        available_main: f32,
+        parent_border_width: f32,
+    ) -> Vec<StaticChildLayout> {
+        let mut layouts = Vec::with_capacity(items.len());
+        // offset along the cross axis where the next line should be placed
+        let mut line_offset = 0.0;
+        for line in lines {
+            let line_items = &items[line.items.clone()];
+            let main_sizes = self.main_sizes(line_items, available_main);
+            let free_space =
+                available_main - main_sizes.iter().sum::<f32>() - self.gaps_size(line_items.len());
+            // offset along the main axis where the next child should be placed
+            let (mut main_offset, spacing) = self.justify(free_space, line_items.len());
 
-        if children_with_unknown_size_count == 0 {
-            return 0.0; // if there is no dynamically sized children then this value does not matter
+            for (item, main_size) in line_items.iter().zip(main_sizes) {
+                let (cross_size, cross_offset) = self.align(item, line.cross_size);
+                let cross_offset = line_offset + cross_offset;
+                let layout = match self.direction {
+                    ViewChildrenDirection::Row => StaticChildLayout {
+                        top: parent_border_width + self.padding.top + cross_offset,
+                        left: parent_border_width + self.padding.left + main_offset,
+                        width: main_size,
+                        height: cross_size,
+                    },
+                    ViewChildrenDirection::Column => StaticChildLayout {
+                        top: parent_border_width + self.padding.top + main_offset,
+                        left: parent_border_width + self.padding.left + cross_offset,
+                        width: cross_size,
+                        height: main_size,
+                    },
+                };
+                layouts.push(layout);
+                main_offset += main_size + self.gap + spacing;
+            }
+            line_offset += line.cross_size + self.gap;
         }
-        f32::max(
-            0.0,
-            (max_size - static_children_sum) / children_with_unknown_size_count as f32,
-        )
+        layouts
+    }
+
+    /// Sizes of children along the main axis after distributing free space (or the lack
+    /// of it) according to their grow and shrink factors.
+    fn main_sizes(&self, items: &[FlexItem], available_main: f32) -> Vec<f32> {
+        let mut sizes: Vec<f32> = items.iter().map(|item| item.basis).collect();
+        let free_space = available_main - sizes.iter().sum::<f32>() - self.gaps_size(items.len());
+        let grow_sum: f32 = items.iter().map(|item| item.grow).sum();
+
+        if free_space > 0.0 && grow_sum > 0.0 {
+            return items
+                .iter()
+                .map(|item| item.basis + free_space * item.grow / grow_sum)
+                .collect();
+        }
+        // With overflow "fit" children are scaled down instead.
+        if free_space >= 0.0 || self.overflow == Overflow::Fit {
+            return sizes;
+        }
+
+        // Children that would shrink below their minimal size are frozen at that size
+        // and the missing space is distributed again between the remaining children.
+        let mut frozen: Vec<bool> = items
+            .iter()
+            .map(|item| item.shrink <= 0.0 || item.basis <= item.min_main_size)
+            .collect();
+        loop {
+            for ((item, size), frozen) in items.iter().zip(&mut sizes).zip(&frozen) {
+                if !frozen {
+                    *size = item.basis;
+                }
+            }
+            let free_space =
+                available_main - sizes.iter().sum::<f32>() - self.gaps_size(items.len());
+            let shrink_sum: f32 = items
+                .iter()
+                .zip(&frozen)
+                .filter(|(_, frozen)| !**frozen)
+                .map(|(item, _)| item.shrink * item.basis)
+                .sum();
+            if free_space >= 0.0 || shrink_sum <= 0.0 {
+                return sizes;
+            }
+
+            let mut any_frozen = false;
+            for ((item, size), frozen) in items.iter().zip(&mut sizes).zip(&mut frozen) {
+                if *frozen {
+                    continue;
+                }
+                let shrunk_size = item.basis + free_space * item.shrink * item.basis / shrink_sum;
+                if shrunk_size < item.min_main_size {
+                    *size = item.min_main_size;
+                    *frozen = true;
+                    any_frozen = true;
+                } else {
+                    *size = shrunk_size;
+                }
+            }
+            if !any_frozen {
+                return sizes;
+            }
+        }
+    }
+
+    /// Offset of the first child and additional space between children along the main axis.
+    fn justify(&self, free_space: f32, items_count: usize) -> (f32, f32) {
+        let count = items_count as f32;
+        match self.justify_content {
+            JustifyContent::Start => (0.0, 0.0),
+            JustifyContent::End => (free_space, 0.0),
+            JustifyContent::Center => (free_space / 2.0, 0.0),
+            // distributing negative space would make children overlap
+            _ if free_space <= 0.0 || items_count == 0 => (0.0, 0.0),
+            JustifyContent::SpaceBetween if items_count == 1 => (0.0, 0.0),
+            JustifyContent::SpaceBetween => (0.0, free_space / (count - 1.0)),
+            JustifyContent::SpaceAround => (free_space / count / 2.0, free_space / count),
+            JustifyContent::SpaceEvenly => (free_space / (count + 1.0), free_space / (count + 1.0)),
+        }
+    }
+
+    /// Size and offset of a child along the cross axis within its line.
+    fn align(&self, item: &FlexItem, line_size: f32) -> (f32, f32) {
+        let size = self.fixed_cross_size(item).unwrap_or(line_size);
+        let offset = match self.align_items {
+            AlignItems::Start | AlignItems::Stretch => 0.0,
+            AlignItems::Center => (line_size - size) / 2.0,
+            AlignItems::End => line_size - size,
+        };
+        (size, offset)
+    }
+
+    /// Size of a child along the cross axis if it does not depend on the line size.
+    /// Children without an explicit size are sized to their content unless they are
+    /// stretched. Components that fit their content into any size always fill the line.
+    fn fixed_cross_size(&self, item: &FlexItem) -> Option<f32> {
+        match self.align_items {
+            AlignItems::Stretch => item.cross_size,
+            AlignItems::Start | AlignItems::Center | AlignItems::End => {
+                item.cross_size.or(item.content_cross_size)
+            }
+        }
+    }
+
+    /// Size of static children (before growing or shrinking) including padding and border.
+    pub(super) fn content_size(&self, children: &[StatefulComponent], pts: Duration) -> Size {
+        let sizes: Vec<_> = children
+            .iter()
+            .filter_map(|child| {
+                let (width, height) = match child {
+                    StatefulComponent::Layout(layout) => match layout.position(pts) {
+                        Position::Static { width, height } => (width, height),
+                        Position::Absolute(_) => return None,
+                    },
+                    non_layout_component => (
+                        non_layout_component.width(pts),
+                        non_layout_component.height(pts),
+                    ),
+                };
+                let content_size = child_content_size(child, pts).unwrap_or(Size {
+                    width: 0.0,
+                    height: 0.0,
+                });
+                let size = Size {
+                    width: width.unwrap_or(content_size.width),
+                    height: height.unwrap_or(content_size.height),
+                };
+                Some(match self.direction {
+                    ViewChildrenDirection::Row => (size.width, size.height),
+                    ViewChildrenDirection::Column => (size.height, size.width),
+                })
+            })
+            .collect();
+        let main = sizes.iter().map(|(main, _)| main).sum::<f32>() + self.gaps_size(sizes.len());
+        let cross = sizes.iter().map(|(_, cross)| *cross).fold(0.0, f32::max);
+        let (width, height) = match self.direction {
+            ViewChildrenDirection::Row => (main, cross),
+            ViewChildrenDirection::Column => (cross, main),
+        };
+        Size {
+            width: width + self.padding.horizontal() + 2.0 * self.border_width,
+            height: height + self.padding.vertical() + 2.0 * self.border_width,
+        }
+    }
+
+    fn gaps_size(&self, items_count: usize) -> f32 {
+        self.gap * items_count.saturating_sub(1) as f32
     }
 
     fn scale_factor_for_overflow_fit(
         &self,
         content_size: Size,
-        children: &[StatefulComponent],
-        pts: Duration,
+        items: &[FlexItem],
+        lines: &[FlexLine],
     ) -> f32 {
-        let sum_size = self
-            .sum_static_children_sizes(children, pts)
-            .max(0.000000001); // avoid division by 0
-        let (max_size, max_alternative_size) = match self.direction {
-            super::ViewChildrenDirection::Row => (content_size.width, content_size.height),
-            super::ViewChildrenDirection::Column => (content_size.height, content_size.width),
-        };
-        let max_alternative_size_for_child = Self::static_children_iter(children, pts)
-            .map(|child| match self.direction {
-                ViewChildrenDirection::Row => child.height(pts).unwrap_or(0.0),
-                ViewChildrenDirection::Column => child.width(pts).unwrap_or(0.0),
+        let sum_size = lines
+            .iter()
+            .map(|line| {
+                let line_items = &items[line.items.clone()];
+                line_items.iter().map(|item| item.basis).sum::<f32>()
+                    + self.gaps_size(line_items.len())
             })
-            .max_by(|a, b| f32::partial_cmp(a, b).unwrap()) // will panic if comparing NaN
+            .max_by(f32::total_cmp)
             .unwrap_or(0.0)
             .max(0.000000001); // avoid division by 0
+        let sum_alternative_size = (lines
+            .iter()
+            .map(|line| {
+                items[line.items.clone()]
+                    .iter()
+                    .map(|item| self.fixed_cross_size(item).unwrap_or(0.0))
+                    .max_by(f32::total_cmp)
+                    .unwrap_or(0.0)
+            })
+            .sum::<f32>()
+            + self.gaps_size(lines.len()))
+        .max(0.000000001); // avoid division by 0
+        let (max_size, max_alternative_size) = match self.direction {
+            ViewChildrenDirection::Row => (content_size.width, content_size.height),
+            ViewChildrenDirection::Column => (content_size.height, content_size.width),
+        };
 
         f32::min(
             1.0,
             f32::min(
                 max_size / sum_size,
-                max_alternative_size / max_alternative_size_for_child,
+                max_alternative_size / sum_alternative_size,
             ),
         )
     }
+}
 
-    fn sum_static_children_sizes(&self, children: &[StatefulComponent], pts: Duration) -> f32 {
-        let size_accessor = match self.direction {
-            super::ViewChildrenDirection::Row => StatefulComponent::width,
-            super::ViewChildrenDirection::Column => StatefulComponent::height,
-        };
-
-        Self::static_children_iter(children, pts)
-            .map(|component| size_accessor(component, pts).unwrap_or(0.0))
-            .sum()
-    }
-
-    fn static_children_iter(
-        children: &[StatefulComponent],
-        pts: Duration,
-    ) -> impl Iterator<Item = &StatefulComponent> {
-        children.iter().filter(move |child| match child {
-            StatefulComponent::Layout(layout) => match layout.position(pts) {
-                super::Position::Static { .. } => true,
-                super::Position::Absolute(_) => false,
-            },
-            _ => true,
-        })
+/// Size of the child content if it does not define its size explicitly.
+fn child_content_size(child: &StatefulComponent, pts: Duration) -> Option<Size> {
+    match child {
+        StatefulComponent::Layout(layout) => layout.content_size(pts),
+        non_layout_component => Some(Size {
+            width: non_layout_component.width(pts).unwrap_or(0.0),
+            height: non_layout_component.height(pts).unwrap_or(0.0),
+        }),
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -8,16 +9,20 @@ use crate::{
     FrameSet, InputId, OutputFrameFormat, OutputId, RegistryType, RendererId, RenderingMode,
     Resolution,
     error::{
-        InitRendererEngineError, RegisterRendererError, RenderSceneError, UnregisterRendererError,
-        UpdateSceneError,
+        InitRendererEngineError, RegisterRendererError, RenderSceneError, SetInputSlateError,
+        SnapshotError, UnregisterRendererError, UpdateFragmentError, UpdateSceneError,
     },
     image,
-    scene::{Component, OutputScene, SceneState},
+    scene::{
+        Component, ComponentLayout, ImagePlayback, OutputScene, ScenePatch, SceneState,
+        image_component::ImageRenderParams,
+    },
     shader,
     transformations::{
-        image::Image,
+        image::{Image, ImageNode},
         shader::Shader,
         text_renderer::TextRendererCtx,
+        video_clip::{self, VideoClip},
         web_renderer::{self, ChromiumContext, WebRenderer},
     },
     types::Framerate,
@@ -38,6 +43,9 @@ pub mod node;
 pub mod render_graph;
 mod render_loop;
 pub mod renderers;
+mod snapshot;
+
+pub use snapshot::{RgbaSnapshot, SnapshotSource};
 
 pub struct RendererOptions {
     pub chromium_context: Option<Arc<ChromiumContext>>,
@@ -88,6 +96,40 @@ pub enum RendererSpec {
     Shader(shader::ShaderSpec),
     WebRenderer(web_renderer::WebRendererSpec),
     Image(image::ImageSpec),
+    VideoClip(video_clip::VideoClipSpec),
+    /// Component tree that can be referenced from output scenes with
+    /// `Component::Fragment`.
+    Fragment(Component),
+}
+
+/// Renderer initialized with [`Renderer::prepare_renderer`] that is not yet
+/// registered.
+pub struct PreparedRenderer {
+    id: RendererId,
+    renderer: PreparedRendererKind,
+}
+
+enum PreparedRendererKind {
+    Shader(Arc<Shader>),
+    WebRenderer(Arc<WebRenderer>),
+    Image(Image),
+    VideoClip(Arc<VideoClip>),
+    Fragment(Arc<Component>),
+}
+
+impl PreparedRenderer {
+    pub fn id(&self) -> &RendererId {
+        &self.id
+    }
+}
+
+/// New scene of a single output passed to [`Renderer::update_scenes`].
+#[derive(Debug, Clone)]
+pub struct OutputSceneUpdate {
+    pub output_id: OutputId,
+    pub resolution: Resolution,
+    pub output_format: OutputFrameFormat,
+    pub scene_root: Component,
 }
 
 impl Renderer {
@@ -109,6 +151,41 @@ impl Renderer {
             .unregister_input(input_id);
     }
 
+    /// Sets an image that is rendered in place of an input when it does not deliver
+    /// frames. Passing `None` removes the slate.
+    pub fn set_input_slate(
+        &self,
+        input_id: &InputId,
+        image_id: Option<&RendererId>,
+    ) -> Result<(), SetInputSlateError> {
+        let mut guard = self.0.lock().unwrap();
+        let Some(image_id) = image_id else {
+            guard.render_graph.slates.remove(input_id);
+            return Ok(());
+        };
+        let image = guard
+            .renderers
+            .images
+            .get(image_id)
+            .ok_or_else(|| SetInputSlateError::ImageNotFound(image_id.clone()))?;
+        let resolution = image.resolution();
+        let slate = ImageNode::new(
+            &guard.wgpu_ctx,
+            ImageRenderParams {
+                image,
+                start_pts: Duration::ZERO,
+                resolution,
+                // keep animated slates in sync with the output timeline
+                playback: ImagePlayback {
+                    restart_on_mount: false,
+                    ..Default::default()
+                },
+            },
+        );
+        guard.render_graph.slates.insert(input_id.clone(), slate);
+        Ok(())
+    }
+
     pub fn unregister_output(&self, output_id: &OutputId) {
         self.0
             .lock()
@@ -123,28 +200,66 @@ impl Renderer {
         id: RendererId,
         spec: RendererSpec,
     ) -> Result<(), RegisterRendererError> {
+        let renderer = self.prepare_renderer(id, spec)?;
+        self.register_prepared_renderer(renderer)
+    }
+
+    /// Initializes a renderer without adding it to the registry. Initialization
+    /// (e.g. compiling a shader or starting a browser instance) can take a long time
+    /// and does not lock the renderer.
+    pub fn prepare_renderer(
+        &self,
+        id: RendererId,
+        spec: RendererSpec,
+    ) -> Result<PreparedRenderer, RegisterRendererError> {
         let ctx = self.0.lock().unwrap().register_ctx();
-        match spec {
+        let renderer = match spec {
             RendererSpec::Shader(spec) => {
                 let shader = Shader::new(&ctx.wgpu_ctx, spec)
                     .map_err(|err| RegisterRendererError::Shader(err.into(), id.clone()))?;
-
-                let mut guard = self.0.lock().unwrap();
-                Ok(guard.renderers.shaders.register(id, Arc::new(shader))?)
+                PreparedRendererKind::Shader(Arc::new(shader))
             }
             RendererSpec::WebRenderer(params) => {
                 let web = WebRenderer::new(&ctx, &id, params)
                     .map_err(|err| RegisterRendererError::Web(err.into(), id.clone()))?;
-
-                let mut guard = self.0.lock().unwrap();
-                Ok(guard.renderers.web_renderers.register(id, Arc::new(web))?)
+                PreparedRendererKind::WebRenderer(Arc::new(web))
             }
             RendererSpec::Image(spec) => {
                 let asset = Image::new(&ctx, spec)
                     .map_err(|err| RegisterRendererError::Image(err, id.clone()))?;
+                PreparedRendererKind::Image(asset)
+            }
+            RendererSpec::VideoClip(spec) => {
+                let clip = VideoClip::new(&ctx.wgpu_ctx, spec)
+                    .map_err(|err| RegisterRendererError::VideoClip(err, id.clone()))?;
+                PreparedRendererKind::VideoClip(Arc::new(clip))
+            }
+            RendererSpec::Fragment(root) => PreparedRendererKind::Fragment(Arc::new(root)),
+        };
+        Ok(PreparedRenderer { id, renderer })
+    }
 
-                let mut guard = self.0.lock().unwrap();
-                Ok(guard.renderers.images.register(id, asset)?)
+    /// Adds a renderer created with [`Renderer::prepare_renderer`] to the registry,
+    /// so it can be used in scenes.
+    pub fn register_prepared_renderer(
+        &self,
+        prepared: PreparedRenderer,
+    ) -> Result<(), RegisterRendererError> {
+        let PreparedRenderer { id, renderer } = prepared;
+        let mut guard = self.0.lock().unwrap();
+        match renderer {
+            PreparedRendererKind::Shader(shader) => {
+                Ok(guard.renderers.shaders.register(id, shader)?)
+            }
+            PreparedRendererKind::WebRenderer(web) => {
+                Ok(guard.renderers.web_renderers.register(id, web)?)
+            }
+            PreparedRendererKind::Image(asset) => Ok(guard.renderers.images.register(id, asset)?),
+            PreparedRendererKind::VideoClip(clip) => {
+                Ok(guard.renderers.video_clips.register(id, clip)?)
+            }
+            PreparedRendererKind::Fragment(root) => {
+                Ok(guard.renderers.fragments.register(id, root)?)
             }
         }
     }
@@ -159,17 +274,57 @@ impl Renderer {
             RegistryType::Shader => guard.renderers.shaders.unregister(renderer_id)?,
             RegistryType::WebRenderer => guard.renderers.web_renderers.unregister(renderer_id)?,
             RegistryType::Image => guard.renderers.images.unregister(renderer_id)?,
+            RegistryType::VideoClip => guard.renderers.video_clips.unregister(renderer_id)?,
+            RegistryType::Fragment => guard.renderers.fragments.unregister(renderer_id)?,
         }
         Ok(())
     }
 
+    pub fn is_renderer_registered(
+        &self,
+        renderer_id: &RendererId,
+        registry_type: RegistryType,
+    ) -> bool {
+        let guard = self.0.lock().unwrap();
+        match registry_type {
+            RegistryType::Shader => guard.renderers.shaders.contains(renderer_id),
+            RegistryType::WebRenderer => guard.renderers.web_renderers.contains(renderer_id),
+            RegistryType::Image => guard.renderers.images.contains(renderer_id),
+            RegistryType::VideoClip => guard.renderers.video_clips.contains(renderer_id),
+            RegistryType::Fragment => guard.renderers.fragments.contains(renderer_id),
+        }
+    }
+
+    /// Replaces content of a registered fragment and updates all outputs that use it,
+    /// so the change is visible on all of them starting from the same frame.
+    pub fn update_fragment(
+        &self,
+        fragment_id: RendererId,
+        root: Component,
+    ) -> Result<(), UpdateFragmentError> {
+        self.0.lock().unwrap().update_fragment(fragment_id, root)
+    }
+
     pub fn register_font(&self, font_source: fontdb::Source) {
         let ctx = self.0.lock().unwrap().text_renderer_ctx.clone();
         ctx.add_font(font_source);
     }
 
     pub fn render(&self, input: FrameSet<InputId>) -> Result<FrameSet<OutputId>, RenderSceneError> {
-        self.0.lock().unwrap().render(input)
+        self.0.lock().unwrap().render(input, |_| true)
+    }
+
+    /// Renders only the specified outputs, other outputs are skipped and do not produce
+    /// a frame for this set of inputs.
+    pub fn render_outputs(
+        &self,
+        input: FrameSet<InputId>,
+        outputs: &HashSet<OutputId>,
+    ) -> Result<FrameSet<OutputId>, RenderSceneError> {
+        self.0
+            .lock()
+            .unwrap()
+            .render(input, |output_id| outputs.contains(output_id))
     }
 
     pub fn update_scene(
@@ -185,6 +340,43 @@ impl Renderer {
             .update_scene(output_id, resolution, scene_root, output_format)
     }
 
+    /// Updates scenes of multiple outputs, so all of them are rendered starting from
+    /// the same frame. If any of the scenes is invalid, none of them is updated.
+    pub fn update_scenes(&self, updates: Vec<OutputSceneUpdate>) -> Result<(), UpdateSceneError> {
+        self.0.lock().unwrap().update_scenes(updates)
+    }
+
+    /// Applies patches to the current scene of an output. Patches are applied
+    /// atomically, if any of them fails the scene is not modified.
+    pub fn patch_scene(
+        &self,
+        output_id: OutputId,
+        resolution: Resolution,
+        output_format: OutputFrameFormat,
+        patches: Vec<ScenePatch>,
+    ) -> Result<(), UpdateSceneError> {
+        let mut guard = self.0.lock().unwrap();
+        let scene_root = guard.scene.patched_scene_root(&output_id, patches)?;
+        guard.update_scene(output_id, resolution, scene_root, output_format)
+    }
+
+    /// Positions of components with an id in the current scene of an output. Returns
+    /// `None` if the output does not have a scene.
+    pub fn component_layouts(&self, output_id: &OutputId) -> Option<Vec<ComponentLayout>> {
+        self.0.lock().unwrap().scene.component_layouts(output_id)
+    }
+
+    /// Downloads the last rendered frame of an output or the last frame of an input.
+    /// Renderer is locked only while the copy of the frame is scheduled, waiting
+    /// for the GPU does not block rendering.
+    pub fn snapshot(&self, source: &SnapshotSource) -> Result<RgbaSnapshot, SnapshotError> {
+        let pending = {
+            let guard = self.0.lock().unwrap();
+            snapshot::start_snapshot(&guard.wgpu_ctx, &guard.render_graph, source)?
+        };
+        pending.download()
+    }
+
     pub fn wgpu_ctx(&self) -> (Arc<wgpu::Device>, Arc<wgpu::Queue>) {
         let guard = self.0.lock().unwrap();
         (guard.wgpu_ctx.device.clone(), guard.wgpu_ctx.queue.clone())
@@ -197,10 +389,7 @@ impl InnerRenderer {
 
         Ok(Self {
             wgpu_ctx: wgpu_ctx.clone(),
-            text_renderer_ctx: Arc::new(TextRendererCtx::new(
-                &wgpu_ctx.device,
-                opts.load_system_fonts,
-            )),
+            text_renderer_ctx: Arc::new(TextRendererCtx::new(&wgpu_ctx, opts.load_system_fonts)),
             render_graph: RenderGraph::empty(),
             renderers: Renderers::new(wgpu_ctx)?,
             stream_fallback_timeout: opts.stream_fallback_timeout,
@@ -219,6 +408,7 @@ impl InnerRenderer {
     pub fn render(
         &mut self,
         inputs: FrameSet<InputId>,
+        should_render: impl Fn(&OutputId) -> bool,
     ) -> Result<FrameSet<OutputId>, RenderSceneError> {
         let ctx = &mut RenderCtx {
             wgpu_ctx: &self.wgpu_ctx,
@@ -241,9 +431,9 @@ impl InnerRenderer {
         trace!("Upload input textures");
         populate_inputs(ctx, &mut self.render_graph, inputs);
         trace!("Run render graph");
-        run_transforms(ctx, &mut self.render_graph, pts);
+        run_transforms(ctx, &mut self.render_graph, pts, &should_render);
         trace!("Download output textures");
-        let frames = read_outputs(ctx, &mut self.render_graph, pts);
+        let frames = read_outputs(ctx, &mut self.render_graph, pts, &should_render);
 
         scope.pop(&ctx.wgpu_ctx.device)?;
 
@@ -257,24 +447,100 @@ impl InnerRenderer {
         scene_root: Component,
         output_format: OutputFrameFormat,
     ) -> Result<(), UpdateSceneError> {
-        let output = OutputScene {
-            output_id: output_id.clone(),
-            scene_root,
+        self.update_scenes(vec![OutputSceneUpdate {
+            output_id,
             resolution,
-        };
-        let output_node =
-            self.scene
-                .update_scene(output, &self.renderers, &self.text_renderer_ctx)?;
-        self.render_graph.update(
-            &RenderCtx {
-                wgpu_ctx: &self.wgpu_ctx,
-                text_renderer_ctx: &self.text_renderer_ctx,
-                renderers: &self.renderers,
-                stream_fallback_timeout: self.stream_fallback_timeout,
-            },
-            output_node,
             output_format,
-        )?;
-        Ok(())
+            scene_root,
+        }])
+    }
+
+    /// Render trees of all outputs are created before any of them is replaced. Scene
+    /// state is restored if any of the updates fails.
+    pub fn update_scenes(
+        &mut self,
+        updates: Vec<OutputSceneUpdate>,
+    ) -> Result<(), UpdateSceneError> {
+        let previous_scene = self.scene.clone();
+        let ctx = RenderCtx {
+            wgpu_ctx: &self.wgpu_ctx,
+            text_renderer_ctx: &self.text_renderer_ctx,
+            renderers: &self.renderers,
+            stream_fallback_timeout: self.stream_fallback_timeout,
+        };
+        let output_trees = updates
+            .into_iter()
+            .map(|update| {
+                let output = OutputScene {
+                    output_id: update.output_id,
+                    scene_root: update.scene_root,
+                    resolution: update.resolution,
+                };
+                let output_node =
+                    self.scene
+                        .update_scene(output, ctx.renderers, ctx.text_renderer_ctx)?;
+                RenderGraph::create_output_tree(&ctx, output_node, update.output_format)
+            })
+            .collect::<Result<Vec<_>, UpdateSceneError>>();
+
+        match output_trees {
+            Ok(output_trees) => {
+                for (output_id, output_tree) in output_trees {
+                    self.render_graph.insert_output(output_id, output_tree);
+                }
+                Ok(())
+            }
+            Err(err) => {
+                self.scene = previous_scene;
+                Err(err)
+            }
+        }
+    }
+
+    /// Roots of all outputs that use the fragment are created before any of them is
+    /// replaced. Fragment and scene state are restored if any of the updates fails.
+    pub fn update_fragment(
+        &mut self,
+        fragment_id: RendererId,
+        root: Component,
+    ) -> Result<(), UpdateFragmentError> {
+        let previous_scene = self.scene.clone();
+        let previous_root = self
+            .renderers
+            .fragments
+            .update(&fragment_id, Arc::new(root))?;
+
+        let ctx = RenderCtx {
+            wgpu_ctx: &self.wgpu_ctx,
+            text_renderer_ctx: &self.text_renderer_ctx,
+            renderers: &self.renderers,
+            stream_fallback_timeout: self.stream_fallback_timeout,
+        };
+        let output_roots = self
+            .scene
+            .update_fragment(&fragment_id, ctx.renderers, ctx.text_renderer_ctx)
+            .map_err(UpdateSceneError::from)
+            .and_then(|output_nodes| {
+                output_nodes
+                    .into_iter()
+                    .map(|output_node| RenderGraph::create_root(&ctx, output_node))
+                    .collect::<Result<Vec<_>, _>>()
+            });
+
+        match output_roots {
+            Ok(output_roots) => {
+                for (output_id, root) in output_roots {
+                    self.render_graph.replace_root(&output_id, root);
+                }
+                Ok(())
+            }
+            Err(err) => {
+                self.scene = previous_scene;
+                self.renderers
+                    .fragments
+                    .update(&fragment_id, previous_root)?;
+                Err(err.into())
+            }
+        }
     }
 }
@@ -1,3 +1,4 @@
+use high_bit_depth_yuv::HighBitDepthYuvInput;
 use interleaved_uyvy422::InterleavedUyvy422Input;
 use nv12_texture::NV12Input;
 use planar_yuv::PlanarYuvInput;
@@ -15,6 +16,7 @@ use super::node_texture::NodeTexture;
 // CPU - connect as linear(default) view
 // WebGl - create temporary rgb texture, write to it convert from srg to rgb
 
+mod high_bit_depth_yuv;
 mod interleaved_uyvy422;
 mod interleaved_yuyv422;
 mod nv12_texture;
@@ -28,6 +30,8 @@ enum InputTextureState {
     InterleavedUyvy422(InterleavedUyvy422Input),
     InterleavedYuyv422(InterleavedYuyv422Input),
     Nv12(NV12Input),
+    /// 10-bit planar or semi-planar YUV, SDR or HDR
+    HighBitDepthYuv(HighBitDepthYuvInput),
     /// Depending on rendering mode
     /// - GPU - Rgba8UnormSrgb
     /// - CPU optimized - Rgba8Unorm (but data is in sRGB color space)
@@ -43,6 +47,7 @@ impl InputTextureState {
             InputTextureState::InterleavedYuyv422(input) => input.resolution(),
             InputTextureState::Rgba8Unorm(input) => input.resolution(),
             InputTextureState::Nv12(input) => input.resolution(),
+            InputTextureState::HighBitDepthYuv(input) => input.resolution(),
         }
     }
 }
@@ -63,11 +68,23 @@ impl InputTexture {
             FrameData::PlanarYuv420(planes) => {
                 match &mut self.0 {
                     Some(InputTextureState::PlanarYuv(input)) => {
-                        input.upload(ctx, planes, PlanarYuvVariant::YUV420, frame.resolution);
+                        input.upload(
+                            ctx,
+                            planes,
+                            PlanarYuvVariant::YUV420,
+                            frame.resolution,
+                            frame.color,
+                        );
                     }
                     state => {
                         let mut input = PlanarYuvInput::new(ctx, PlanarYuvVariant::YUV420);
-                        input.upload(ctx, planes, PlanarYuvVariant::YUV420, frame.resolution);
+                        input.upload(
+                            ctx,
+                            planes,
+                            PlanarYuvVariant::YUV420,
+                            frame.resolution,
+                            frame.color,
+                        );
                         *state = Some(InputTextureState::PlanarYuv(input));
                     }
                 };
@@ -75,11 +92,23 @@ impl InputTexture {
             FrameData::PlanarYuv422(planes) => {
                 match &mut self.0 {
                     Some(InputTextureState::PlanarYuv(input)) => {
-                        input.upload(ctx, planes, PlanarYuvVariant::YUV422, frame.resolution);
+                        input.upload(
+                            ctx,
+                            planes,
+                            PlanarYuvVariant::YUV422,
+                            frame.resolution,
+                            frame.color,
+                        );
                     }
                     state => {
                         let mut input = PlanarYuvInput::new(ctx, PlanarYuvVariant::YUV422);
-                        input.upload(ctx, planes, PlanarYuvVariant::YUV422, frame.resolution);
+                        input.upload(
+                            ctx,
+                            planes,
+                            PlanarYuvVariant::YUV422,
+                            frame.resolution,
+                            frame.color,
+                        );
                         *state = Some(InputTextureState::PlanarYuv(input));
                     }
                 };
@@ -87,11 +116,23 @@ impl InputTexture {
             FrameData::PlanarYuv444(planes) => {
                 match &mut self.0 {
                     Some(InputTextureState::PlanarYuv(input)) => {
-                        input.upload(ctx, planes, PlanarYuvVariant::YUV444, frame.resolution);
+                        input.upload(
+                            ctx,
+                            planes,
+                            PlanarYuvVariant::YUV444,
+                            frame.resolution,
+                            frame.color,
+                        );
                     }
                     state => {
                         let mut input = PlanarYuvInput::new(ctx, PlanarYuvVariant::YUV444);
-                        input.upload(ctx, planes, PlanarYuvVariant::YUV444, frame.resolution);
+                        input.upload(
+                            ctx,
+                            planes,
+                            PlanarYuvVariant::YUV444,
+                            frame.resolution,
+                            frame.color,
+                        );
                         *state = Some(InputTextureState::PlanarYuv(input));
                     }
                 };
@@ -99,45 +140,77 @@ impl InputTexture {
             FrameData::PlanarYuvJ420(planes) => {
                 match &mut self.0 {
                     Some(InputTextureState::PlanarYuv(input)) => {
-                        input.upload(ctx, planes, PlanarYuvVariant::YUVJ420, frame.resolution);
+                        input.upload(
+                            ctx,
+                            planes,
+                            PlanarYuvVariant::YUVJ420,
+                            frame.resolution,
+                            frame.color,
+                        );
                     }
                     state => {
                         let mut input = PlanarYuvInput::new(ctx, PlanarYuvVariant::YUVJ420);
-                        input.upload(ctx, planes, PlanarYuvVariant::YUVJ420, frame.resolution);
+                        input.upload(
+                            ctx,
+                            planes,
+                            PlanarYuvVariant::YUVJ420,
+                            frame.resolution,
+                            frame.color,
+                        );
                         *state = Some(InputTextureState::PlanarYuv(input));
                     }
                 };
             }
+            FrameData::PlanarYuv420P10(planes) => match &mut self.0 {
+                Some(InputTextureState::HighBitDepthYuv(input)) => {
+                    input.upload_planar(ctx, planes, frame.resolution, frame.color);
+                }
+                state => {
+                    let mut input = HighBitDepthYuvInput::new_planar(ctx);
+                    input.upload_planar(ctx, planes, frame.resolution, frame.color);
+                    *state = Some(InputTextureState::HighBitDepthYuv(input));
+                }
+            },
+            FrameData::P010(planes) => match &mut self.0 {
+                Some(InputTextureState::HighBitDepthYuv(input)) => {
+                    input.upload_semi_planar(ctx, planes, frame.resolution, frame.color);
+                }
+                state => {
+                    let mut input = HighBitDepthYuvInput::new_semi_planar(ctx);
+                    input.upload_semi_planar(ctx, planes, frame.resolution, frame.color);
+                    *state = Some(InputTextureState::HighBitDepthYuv(input));
+                }
+            },
             FrameData::Nv12(planes) => match &mut self.0 {
                 Some(InputTextureState::Nv12(input)) => {
-                    input.upload(ctx, planes, frame.resolution);
+                    input.upload(ctx, planes, frame.resolution, frame.color);
                 }
 
                 state => {
                     let mut input = NV12Input::new_uploadable(ctx, frame.resolution);
-                    input.upload(ctx, planes, frame.resolution);
+                    input.upload(ctx, planes, frame.resolution, frame.color);
                     *state = Some(InputTextureState::Nv12(input));
                 }
             },
             FrameData::InterleavedUyvy422(data) => {
                 match &mut self.0 {
                     Some(InputTextureState::InterleavedUyvy422(input)) => {
-                        input.upload(ctx, &data, frame.resolution);
+                        input.upload(ctx, &data, frame.resolution, frame.color);
                     }
                     state => {
                         let mut input = InterleavedUyvy422Input::new(ctx);
-                        input.upload(ctx, &data, frame.resolution);
+                        input.upload(ctx, &data, frame.resolution, frame.color);
                         *state = Some(InputTextureState::InterleavedUyvy422(input));
                     }
                 };
             }
             FrameData::InterleavedYuyv422(data) => match &mut self.0 {
                 Some(InputTextureState::InterleavedYuyv422(input)) => {
-                    input.upload(ctx, &data, frame.resolution);
+                    input.upload(ctx, &data, frame.resolution, frame.color);
                 }
                 state => {
                     let mut input = InterleavedYuyv422Input::new(ctx);
-                    input.upload(ctx, &data, frame.resolution);
+                    input.upload(ctx, &data, frame.resolution, frame.color);
                     *state = Some(InputTextureState::InterleavedYuyv422(input));
                 }
             },
@@ -156,11 +229,11 @@ impl InputTexture {
             FrameData::Nv12WgpuTexture(texture) => {
                 match &mut self.0 {
                     Some(InputTextureState::Nv12(input)) => {
-                        input.update(ctx, texture).unwrap();
+                        input.update(ctx, texture, frame.color).unwrap();
                     }
                     state => {
                         *state = Some(InputTextureState::Nv12(
-                            NV12Input::new_from_texture(ctx, texture).unwrap(),
+                            NV12Input::new_from_texture(ctx, texture, frame.color).unwrap(),
                         ));
                     }
                 };
@@ -178,6 +251,7 @@ impl InputTexture {
                     InputTextureState::InterleavedYuyv422(state) => state.convert(ctx, dst_state),
                     InputTextureState::Rgba8Unorm(state) => state.convert(ctx, dst_state),
                     InputTextureState::Nv12(state) => state.convert(ctx, dst_state),
+                    InputTextureState::HighBitDepthYuv(state) => state.convert(ctx, dst_state),
                 }
             }
             None => dest.clear(),
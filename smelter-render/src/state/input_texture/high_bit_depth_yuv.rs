@@ -0,0 +1,176 @@
+use tracing::error;
+
+use crate::{
+    ColorInfo, NvPlanes, RenderingMode, Resolution, YuvPlanes,
+    state::node_texture::NodeTextureState,
+    wgpu::{
+        WgpuCtx,
+        format::Yuv10Layout,
+        texture::{P010Texture, PlanarYuvTextures, PlanarYuvVariant, Rgba16FloatTexture},
+    },
+};
+
+use super::convert_linear_to_srgb::RgbToSrgbConverter;
+
+enum UploadTextures {
+    Planar(PlanarYuvTextures),
+    SemiPlanar(P010Texture),
+}
+
+impl UploadTextures {
+    fn layout(&self) -> Yuv10Layout {
+        match self {
+            UploadTextures::Planar(_) => Yuv10Layout::Planar,
+            UploadTextures::SemiPlanar(_) => Yuv10Layout::SemiPlanar,
+        }
+    }
+
+    fn resolution(&self) -> Resolution {
+        match self {
+            UploadTextures::Planar(textures) => textures.resolution,
+            UploadTextures::SemiPlanar(texture) => texture.resolution(),
+        }
+    }
+
+    fn new_bind_group(&self, ctx: &WgpuCtx) -> wgpu::BindGroup {
+        match self {
+            UploadTextures::Planar(textures) => textures.new_bind_group(ctx),
+            UploadTextures::SemiPlanar(texture) => texture.new_bind_group(ctx),
+        }
+    }
+}
+
+/// 10-bit YUV input (SDR or HDR). Frames are first converted to linear light in
+/// a float texture and then tone mapped into the 8-bit node texture.
+pub(super) struct HighBitDepthYuvInput {
+    upload_textures: UploadTextures,
+    yuv_bind_group: wgpu::BindGroup,
+    linear_texture: Rgba16FloatTexture,
+    linear_bind_group: wgpu::BindGroup,
+    color: ColorInfo,
+    color_space_converter: Option<RgbToSrgbConverter>,
+}
+
+impl HighBitDepthYuvInput {
+    pub fn new_planar(ctx: &WgpuCtx) -> Self {
+        let textures =
+            PlanarYuvTextures::new(ctx, Resolution::MIN_2X2, PlanarYuvVariant::YUV420P10);
+        Self::new(ctx, UploadTextures::Planar(textures))
+    }
+
+    pub fn new_semi_planar(ctx: &WgpuCtx) -> Self {
+        let texture = P010Texture::new(ctx, Resolution::MIN_2X2);
+        Self::new(ctx, UploadTextures::SemiPlanar(texture))
+    }
+
+    fn new(ctx: &WgpuCtx, upload_textures: UploadTextures) -> Self {
+        let yuv_bind_group = upload_textures.new_bind_group(ctx);
+        let linear_texture = Rgba16FloatTexture::new(ctx, upload_textures.resolution());
+        let linear_bind_group = linear_texture.new_bind_group(ctx);
+
+        Self {
+            upload_textures,
+            yuv_bind_group,
+            linear_texture,
+            linear_bind_group,
+            color: ColorInfo::default(),
+            color_space_converter: None,
+        }
+    }
+
+    pub fn is_semi_planar(&self) -> bool {
+        matches!(self.upload_textures, UploadTextures::SemiPlanar(_))
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.upload_textures.resolution()
+    }
+
+    pub fn upload_planar(
+        &mut self,
+        ctx: &WgpuCtx,
+        planes: YuvPlanes,
+        resolution: Resolution,
+        color: ColorInfo,
+    ) {
+        if resolution != self.resolution() || self.is_semi_planar() {
+            let textures = PlanarYuvTextures::new(ctx, resolution, PlanarYuvVariant::YUV420P10);
+            self.recreate(ctx, UploadTextures::Planar(textures));
+        }
+        if let UploadTextures::Planar(textures) = &mut self.upload_textures {
+            textures.upload(ctx, &planes);
+        }
+        self.color = color;
+    }
+
+    pub fn upload_semi_planar(
+        &mut self,
+        ctx: &WgpuCtx,
+        planes: NvPlanes,
+        resolution: Resolution,
+        color: ColorInfo,
+    ) {
+        if resolution != self.resolution() || !self.is_semi_planar() {
+            let texture = P010Texture::new(ctx, resolution);
+            self.recreate(ctx, UploadTextures::SemiPlanar(texture));
+        }
+        if let UploadTextures::SemiPlanar(texture) = &self.upload_textures {
+            texture.upload(ctx, &planes);
+        }
+        self.color = color;
+    }
+
+    pub fn convert(&mut self, ctx: &WgpuCtx, dest: &NodeTextureState) {
+        ctx.format.yuv10_to_rgba_float.convert(
+            ctx,
+            self.upload_textures.layout(),
+            self.color,
+            &self.yuv_bind_group,
+            self.linear_texture.view(),
+        );
+
+        match dest {
+            NodeTextureState::GpuOptimized { texture, .. } => {
+                // write to sRGB texture as if it was linear
+                ctx.format.rgba_float_to_rgba_linear.convert(
+                    ctx,
+                    self.color,
+                    &self.linear_bind_group,
+                    texture.linear_view(),
+                );
+            }
+            NodeTextureState::CpuOptimized { texture, .. } => {
+                ctx.format.rgba_float_to_rgba_linear.convert(
+                    ctx,
+                    self.color,
+                    &self.linear_bind_group,
+                    texture.view(),
+                );
+            }
+            NodeTextureState::WebGl { texture, .. } => {
+                let Some(color_space_converter) = &mut self.color_space_converter else {
+                    error!("Missing color space converter");
+                    return;
+                };
+                ctx.format.rgba_float_to_rgba_linear.convert(
+                    ctx,
+                    self.color,
+                    &self.linear_bind_group,
+                    color_space_converter.texture.view(),
+                );
+                color_space_converter.convert(ctx, texture.texture());
+            }
+        }
+    }
+
+    fn recreate(&mut self, ctx: &WgpuCtx, upload_textures: UploadTextures) {
+        let resolution = upload_textures.resolution();
+        self.yuv_bind_group = upload_textures.new_bind_group(ctx);
+        self.upload_textures = upload_textures;
+        self.linear_texture = Rgba16FloatTexture::new(ctx, resolution);
+        self.linear_bind_group = self.linear_texture.new_bind_group(ctx);
+        if ctx.mode == RenderingMode::WebGl {
+            self.color_space_converter = Some(RgbToSrgbConverter::new(ctx, resolution))
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use tracing::error;
 
 use crate::{
-    RenderingMode, Resolution,
+    ColorInfo, RenderingMode, Resolution,
     state::node_texture::NodeTextureState,
     wgpu::{WgpuCtx, texture::InterleavedYuyv422Texture},
 };
@@ -11,6 +11,7 @@ use super::convert_linear_to_srgb::RgbToSrgbConverter;
 pub(super) struct InterleavedYuyv422Input {
     upload_textures: InterleavedYuyv422Texture,
     yuv_bind_group: wgpu::BindGroup,
+    color: ColorInfo,
     color_space_converter: Option<RgbToSrgbConverter>,
 }
 
@@ -22,6 +23,7 @@ impl InterleavedYuyv422Input {
         Self {
             upload_textures,
             yuv_bind_group,
+            color: ColorInfo::default(),
             color_space_converter: None,
         }
     }
@@ -30,9 +32,10 @@ impl InterleavedYuyv422Input {
         self.upload_textures.resolution
     }
 
-    pub fn upload(&mut self, ctx: &WgpuCtx, data: &[u8], resolution: Resolution) {
+    pub fn upload(&mut self, ctx: &WgpuCtx, data: &[u8], resolution: Resolution, color: ColorInfo) {
         self.maybe_recreate(ctx, resolution);
         self.upload_textures.upload(ctx, data);
+        self.color = color;
     }
 
     pub fn convert(&mut self, ctx: &WgpuCtx, dest: &NodeTextureState) {
@@ -41,6 +44,7 @@ impl InterleavedYuyv422Input {
                 // write to sRGB texture as if it was linear
                 ctx.format.interleaved_yuyv_to_rgba_linear.convert(
                     ctx,
+                    self.color,
                     &self.yuv_bind_group,
                     texture.linear_view(),
                 );
@@ -48,6 +52,7 @@ impl InterleavedYuyv422Input {
             NodeTextureState::CpuOptimized { texture, .. } => {
                 ctx.format.interleaved_yuyv_to_rgba_linear.convert(
                     ctx,
+                    self.color,
                     &self.yuv_bind_group,
                     texture.view(),
                 );
@@ -59,6 +64,7 @@ impl InterleavedYuyv422Input {
                 };
                 ctx.format.interleaved_yuyv_to_rgba_linear.convert(
                     ctx,
+                    self.color,
                     &self.yuv_bind_group,
                     color_space_converter.texture.view(),
                 );
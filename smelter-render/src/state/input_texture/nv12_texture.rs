@@ -3,7 +3,7 @@ use std::sync::Arc;
 use tracing::error;
 
 use crate::{
-    NvPlanes, RenderingMode, Resolution,
+    ColorInfo, NvPlanes, RenderingMode, Resolution,
     state::node_texture::NodeTextureState,
     wgpu::{
         WgpuCtx,
@@ -17,12 +17,14 @@ pub(super) struct NV12Input {
     nv12_texture: NV12Texture,
     color_space_converter: Option<RgbToSrgbConverter>,
     bind_group: wgpu::BindGroup,
+    color: ColorInfo,
 }
 
 impl NV12Input {
     pub fn new_from_texture(
         ctx: &WgpuCtx,
         texture: Arc<wgpu::Texture>,
+        color: ColorInfo,
     ) -> Result<Self, NV12TextureViewCreateError> {
         let size = texture.size();
         let nv12_texture = NV12Texture::from_wgpu_texture(texture)?;
@@ -35,6 +37,7 @@ impl NV12Input {
             nv12_texture,
             color_space_converter,
             bind_group,
+            color,
         })
     }
 
@@ -49,6 +52,7 @@ impl NV12Input {
             nv12_texture,
             color_space_converter,
             bind_group,
+            color: ColorInfo::default(),
         }
     }
 
@@ -60,8 +64,10 @@ impl NV12Input {
         &mut self,
         ctx: &WgpuCtx,
         texture: Arc<wgpu::Texture>,
+        color: ColorInfo,
     ) -> Result<(), NV12TextureViewCreateError> {
         self.nv12_texture = NV12Texture::from_wgpu_texture(texture)?;
+        self.color = color;
         self.bind_group = self.nv12_texture.new_bind_group(ctx);
         match (ctx.mode, &self.color_space_converter) {
             (RenderingMode::WebGl, Some(converter))
@@ -77,9 +83,16 @@ impl NV12Input {
         Ok(())
     }
 
-    pub fn upload(&mut self, ctx: &WgpuCtx, planes: NvPlanes, resolution: Resolution) {
+    pub fn upload(
+        &mut self,
+        ctx: &WgpuCtx,
+        planes: NvPlanes,
+        resolution: Resolution,
+        color: ColorInfo,
+    ) {
         self.maybe_recreate_before_upload(ctx, resolution);
         self.nv12_texture.upload(ctx, &planes);
+        self.color = color;
     }
 
     fn maybe_recreate_before_upload(&mut self, ctx: &WgpuCtx, resolution: Resolution) {
@@ -100,14 +113,18 @@ impl NV12Input {
                 // write to sRGB texture as if it was linear
                 ctx.format.nv12_to_rgba_linear.convert(
                     ctx,
+                    self.color,
                     &self.bind_group,
                     texture.linear_view(),
                 );
             }
             NodeTextureState::CpuOptimized { texture, .. } => {
-                ctx.format
-                    .nv12_to_rgba_linear
-                    .convert(ctx, &self.bind_group, texture.view());
+                ctx.format.nv12_to_rgba_linear.convert(
+                    ctx,
+                    self.color,
+                    &self.bind_group,
+                    texture.view(),
+                );
             }
             NodeTextureState::WebGl { texture, .. } => {
                 let Some(color_space_converter) = &mut self.color_space_converter else {
@@ -117,6 +134,7 @@ impl NV12Input {
 
                 ctx.format.nv12_to_rgba_linear.convert(
                     ctx,
+                    self.color,
                     &self.bind_group,
                     color_space_converter.texture.view(),
                 );
@@ -1,7 +1,7 @@
 use tracing::error;
 
 use crate::{
-    RenderingMode, Resolution, YuvPlanes,
+    ColorInfo, RenderingMode, Resolution, YuvPlanes,
     state::node_texture::NodeTextureState,
     wgpu::{
         WgpuCtx,
@@ -14,6 +14,7 @@ use super::convert_linear_to_srgb::RgbToSrgbConverter;
 pub(super) struct PlanarYuvInput {
     upload_textures: PlanarYuvTextures,
     yuv_bind_group: wgpu::BindGroup,
+    color: ColorInfo,
     color_space_converter: Option<RgbToSrgbConverter>,
 }
 
@@ -26,6 +27,7 @@ impl PlanarYuvInput {
         Self {
             upload_textures,
             yuv_bind_group,
+            color: ColorInfo::default(),
             color_space_converter: None,
         }
     }
@@ -40,9 +42,11 @@ impl PlanarYuvInput {
         planes: YuvPlanes,
         variant: PlanarYuvVariant,
         resolution: Resolution,
+        color: ColorInfo,
     ) {
         self.maybe_recreate(ctx, resolution, variant);
         self.upload_textures.upload(ctx, &planes);
+        self.color = color;
     }
 
     pub fn convert(&mut self, ctx: &WgpuCtx, dest: &NodeTextureState) {
@@ -52,6 +56,7 @@ impl PlanarYuvInput {
                 ctx.format.planar_yuv_to_rgba_linear.convert(
                     ctx,
                     self.upload_textures.variant(),
+                    self.color,
                     &self.yuv_bind_group,
                     texture.linear_view(),
                 );
@@ -60,6 +65,7 @@ impl PlanarYuvInput {
                 ctx.format.planar_yuv_to_rgba_linear.convert(
                     ctx,
                     self.upload_textures.variant(),
+                    self.color,
                     &self.yuv_bind_group,
                     texture.view(),
                 );
@@ -72,6 +78,7 @@ impl PlanarYuvInput {
                 ctx.format.planar_yuv_to_rgba_linear.convert(
                     ctx,
                     self.upload_textures.variant(),
+                    self.color,
                     &self.yuv_bind_group,
                     color_space_converter.texture.view(),
                 );
@@ -4,12 +4,17 @@ use std::time::Duration;
 use std::vec;
 
 use crate::InputId;
-use crate::scene::{self, ComponentId, ShaderComponentParams, image_component::ImageRenderParams};
+use crate::scene::{
+    self, ComponentId, MaskComponentParams, ShaderComponentParams,
+    image_component::ImageRenderParams, video_clip_component::VideoClipRenderParams,
+};
 use crate::transformations::layout::LayoutNode;
+use crate::transformations::mask::MaskNode;
 use crate::transformations::shader::Shader;
 use crate::transformations::shader::node::ShaderNode;
 
 use crate::transformations::text_renderer::TextRenderParams;
+use crate::transformations::video_clip::VideoClipNode;
 use crate::transformations::web_renderer::WebRenderer;
 use crate::transformations::{
     image::ImageNode, text_renderer::TextRendererNode, web_renderer::WebRendererNode,
@@ -24,6 +29,8 @@ pub(super) enum InnerRenderNode {
     Web(WebRendererNode),
     Text(TextRendererNode),
     Image(ImageNode),
+    VideoClip(VideoClipNode),
+    Mask(MaskNode),
     Layout(LayoutNode),
     InputStreamRef(InputId),
 }
@@ -45,6 +52,8 @@ impl InnerRenderNode {
                 renderer.render(ctx, target);
             }
             InnerRenderNode::Image(node) => node.render(ctx, target, pts),
+            InnerRenderNode::VideoClip(node) => node.render(ctx, target, pts),
+            InnerRenderNode::Mask(node) => node.render(ctx.wgpu_ctx, sources, target),
             InnerRenderNode::InputStreamRef(_) => {
                 // Nothing to do, textures on input nodes should be populated
                 // at the start of render loop
@@ -79,7 +88,9 @@ impl RenderNode {
                 Self::new_web_renderer_node(ctx, children, children_ids, web_renderer)
             }
             scene::NodeParams::Image(image_params) => Self::new_image_node(ctx, image_params),
+            scene::NodeParams::VideoClip(clip_params) => Self::new_video_clip_node(clip_params),
             scene::NodeParams::Text(text_params) => Self::new_text_node(ctx, text_params),
+            scene::NodeParams::Mask(mask_params) => Self::new_mask_node(ctx, children, mask_params),
             scene::NodeParams::Layout(layout_provider) => {
                 Self::new_layout_node(ctx, children, layout_provider)
             }
@@ -153,6 +164,17 @@ impl RenderNode {
         }
     }
 
+    pub(super) fn new_video_clip_node(params: VideoClipRenderParams) -> Self {
+        let node = InnerRenderNode::VideoClip(VideoClipNode::new(params));
+        let output = NodeTexture::new();
+
+        Self {
+            renderer: node,
+            output,
+            children: vec![],
+        }
+    }
+
     pub(super) fn new_text_node(ctx: &RenderCtx, params: TextRenderParams) -> Self {
         let node = InnerRenderNode::Text(TextRendererNode::new(ctx, params));
         let output = NodeTexture::new();
@@ -164,6 +186,22 @@ impl RenderNode {
         }
     }
 
+    pub(super) fn new_mask_node(
+        ctx: &RenderCtx,
+        children: Vec<RenderNode>,
+        params: MaskComponentParams,
+    ) -> Self {
+        let mut output = NodeTexture::new();
+        output.ensure_size(ctx.wgpu_ctx, params.size.into());
+        let node = InnerRenderNode::Mask(MaskNode::new(ctx, params));
+
+        Self {
+            renderer: node,
+            output,
+            children,
+        }
+    }
+
     pub(super) fn new_layout_node(
         ctx: &RenderCtx,
         children: Vec<RenderNode>,
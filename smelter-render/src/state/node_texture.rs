@@ -60,7 +60,6 @@ pub enum NodeTextureState {
     GpuOptimized {
         texture: RgbaMultiViewTexture,
         linear_bind_group: wgpu::BindGroup,
-        #[allow(dead_code)]
         srgb_bind_group: wgpu::BindGroup,
     },
     CpuOptimized {
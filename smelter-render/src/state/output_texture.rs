@@ -6,7 +6,7 @@ use tracing::error;
 use wgpu::{Buffer, BufferAsyncError};
 
 use crate::{
-    OutputFrameFormat, Resolution,
+    OutputColorSpace, OutputFrameFormat, Resolution,
     wgpu::{
         WgpuCtx,
         texture::{
@@ -23,15 +23,23 @@ pub enum OutputTexture {
 impl OutputTexture {
     pub fn new(ctx: &WgpuCtx, resolution: Resolution, format: OutputFrameFormat) -> Self {
         match format {
-            OutputFrameFormat::PlanarYuv420Bytes => Self::PlanarYuvTextures(Box::new(
-                PlanarYuvOutput::new(ctx, resolution, PlanarYuvVariant::YUV420),
+            OutputFrameFormat::PlanarYuv420Bytes(color_space) => Self::PlanarYuvTextures(Box::new(
+                PlanarYuvOutput::new(ctx, resolution, PlanarYuvVariant::YUV420, color_space),
             )),
-            OutputFrameFormat::PlanarYuv422Bytes => Self::PlanarYuvTextures(Box::new(
-                PlanarYuvOutput::new(ctx, resolution, PlanarYuvVariant::YUV422),
+            OutputFrameFormat::PlanarYuv422Bytes(color_space) => Self::PlanarYuvTextures(Box::new(
+                PlanarYuvOutput::new(ctx, resolution, PlanarYuvVariant::YUV422, color_space),
             )),
-            OutputFrameFormat::PlanarYuv444Bytes => Self::PlanarYuvTextures(Box::new(
-                PlanarYuvOutput::new(ctx, resolution, PlanarYuvVariant::YUV444),
+            OutputFrameFormat::PlanarYuv444Bytes(color_space) => Self::PlanarYuvTextures(Box::new(
+                PlanarYuvOutput::new(ctx, resolution, PlanarYuvVariant::YUV444, color_space),
             )),
+            OutputFrameFormat::PlanarYuv420P10Bytes(color_space) => {
+                Self::PlanarYuvTextures(Box::new(PlanarYuvOutput::new(
+                    ctx,
+                    resolution,
+                    PlanarYuvVariant::YUV420P10,
+                    color_space,
+                )))
+            }
             OutputFrameFormat::RgbaWgpuTexture => Self::Rgba8UnormWgpuTexture { resolution },
         }
     }
@@ -41,10 +49,16 @@ pub struct PlanarYuvOutput {
     textures: PlanarYuvTextures,
     buffers: [wgpu::Buffer; 3],
     resolution: Resolution,
+    color_space: OutputColorSpace,
 }
 
 impl PlanarYuvOutput {
-    pub fn new(ctx: &WgpuCtx, resolution: Resolution, pixel_format: PlanarYuvVariant) -> Self {
+    pub fn new(
+        ctx: &WgpuCtx,
+        resolution: Resolution,
+        pixel_format: PlanarYuvVariant,
+        color_space: OutputColorSpace,
+    ) -> Self {
         let textures = PlanarYuvTextures::new(ctx, resolution, pixel_format);
         let buffers = textures.new_download_buffers(ctx);
 
@@ -52,6 +66,7 @@ impl PlanarYuvOutput {
             textures,
             buffers,
             resolution,
+            color_space,
         }
     }
 
@@ -63,6 +78,10 @@ impl PlanarYuvOutput {
         self.resolution
     }
 
+    pub fn color_space(&self) -> OutputColorSpace {
+        self.color_space
+    }
+
     pub fn start_download<'a>(
         &'a self,
         ctx: &WgpuCtx,
@@ -85,7 +104,9 @@ impl PlanarYuvOutput {
         size: wgpu::Extent3d,
         source: &'a Buffer,
     ) -> impl FnOnce() -> Result<bytes::Bytes, BufferAsyncError> + 'a {
-        let buffer = bytes::BytesMut::with_capacity((size.width * size.height) as usize);
+        let block_size = self.textures.variant().bytes_per_sample();
+        let row_size = block_size * size.width;
+        let buffer = bytes::BytesMut::with_capacity((row_size * size.height) as usize);
         let (s, r) = bounded(1);
         source
             .slice(..)
@@ -100,9 +121,9 @@ impl PlanarYuvOutput {
             let mut buffer = buffer.writer();
             {
                 let range = source.slice(..).get_mapped_range();
-                let chunks = range.chunks(pad_to_256(size.width) as usize);
+                let chunks = range.chunks(pad_to_256(row_size) as usize);
                 for chunk in chunks {
-                    buffer.write_all(&chunk[..size.width as usize]).unwrap();
+                    buffer.write_all(&chunk[..row_size as usize]).unwrap();
                 }
             };
             source.unmap();
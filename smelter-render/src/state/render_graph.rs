@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use crate::scene::{self, OutputNode};
+use crate::transformations::image::ImageNode;
 use crate::{InputId, OutputFrameFormat, OutputId};
 use crate::{error::UpdateSceneError, wgpu::WgpuErrorScope};
 
@@ -12,6 +13,8 @@ use super::{RenderCtx, node::RenderNode};
 pub(super) struct RenderGraph {
     pub(super) outputs: HashMap<OutputId, OutputRenderTree>,
     pub(super) inputs: HashMap<InputId, (NodeTexture, InputTexture)>,
+    /// Images rendered in place of inputs that do not deliver frames.
+    pub(super) slates: HashMap<InputId, ImageNode>,
 }
 
 pub(super) struct OutputRenderTree {
@@ -24,6 +27,7 @@ impl RenderGraph {
         Self {
             outputs: HashMap::new(),
             inputs: HashMap::new(),
+            slates: HashMap::new(),
         }
     }
 
@@ -34,18 +38,21 @@ impl RenderGraph {
 
     pub(super) fn unregister_input(&mut self, input_id: &InputId) {
         self.inputs.remove(input_id);
+        self.slates.remove(input_id);
     }
 
     pub(super) fn unregister_output(&mut self, output_id: &OutputId) {
         self.outputs.remove(output_id);
     }
 
-    pub(super) fn update(
-        &mut self,
+    /// Creates a render tree of an output. The tree is not used until it is passed
+    /// to `insert_output`, so trees of multiple outputs can be created before any
+    /// of them is replaced.
+    pub(super) fn create_output_tree(
         ctx: &RenderCtx,
         output: OutputNode,
         output_format: OutputFrameFormat,
-    ) -> Result<(), UpdateSceneError> {
+    ) -> Result<(OutputId, OutputRenderTree), UpdateSceneError> {
         // TODO: If we want nodes to be stateful we could try reusing nodes instead
         //       of recreating them on every scene update
         let scope = WgpuErrorScope::push(&ctx.wgpu_ctx.device);
@@ -57,9 +64,31 @@ impl RenderGraph {
 
         scope.pop(&ctx.wgpu_ctx.device)?;
 
-        self.outputs.insert(output.output_id, output_tree);
+        Ok((output.output_id, output_tree))
+    }
+
+    pub(super) fn insert_output(&mut self, output_id: OutputId, output_tree: OutputRenderTree) {
+        self.outputs.insert(output_id, output_tree);
+    }
 
-        Ok(())
+    /// Creates a new root node of an already registered output. Like with
+    /// `create_output_tree`, the root is not used until it is passed to `replace_root`.
+    pub(super) fn create_root(
+        ctx: &RenderCtx,
+        output: OutputNode,
+    ) -> Result<(OutputId, RenderNode), UpdateSceneError> {
+        let scope = WgpuErrorScope::push(&ctx.wgpu_ctx.device);
+        let root = Self::create_node(ctx, output.node)?;
+        scope.pop(&ctx.wgpu_ctx.device)?;
+
+        Ok((output.output_id, root))
+    }
+
+    /// Replace render tree of an already registered output, but keep its output texture.
+    pub(super) fn replace_root(&mut self, output_id: &OutputId, root: RenderNode) {
+        if let Some(output_tree) = self.outputs.get_mut(output_id) {
+            output_tree.root = root;
+        }
     }
 
     fn create_node(ctx: &RenderCtx, node: scene::Node) -> Result<RenderNode, UpdateSceneError> {
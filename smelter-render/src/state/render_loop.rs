@@ -3,7 +3,7 @@ use std::{collections::HashMap, sync::Arc, time::Duration};
 use tracing::{error, warn};
 
 use crate::{
-    Frame, FrameData, FrameSet, InputId, OutputId, RenderingMode, Resolution,
+    ColorInfo, Frame, FrameData, FrameSet, InputId, OutputId, RenderingMode, Resolution,
     scene::RGBColor,
     state::{RenderCtx, node::RenderNode, render_graph::RenderGraph},
     wgpu::texture::{
@@ -17,17 +17,20 @@ use super::{
 };
 
 pub(super) fn populate_inputs(
-    ctx: &RenderCtx,
+    ctx: &mut RenderCtx,
     scene: &mut RenderGraph,
     mut frame_set: FrameSet<InputId>,
 ) {
+    let mut missing_inputs = Vec::new();
     for (input_id, (_node_texture, input_textures)) in &mut scene.inputs {
         let Some(frame) = frame_set.frames.remove(input_id) else {
             input_textures.clear();
+            missing_inputs.push(input_id.clone());
             continue;
         };
         if Duration::saturating_sub(frame_set.pts, ctx.stream_fallback_timeout) > frame.pts {
             input_textures.clear();
+            missing_inputs.push(input_id.clone());
             continue;
         }
 
@@ -39,6 +42,16 @@ pub(super) fn populate_inputs(
     for (node_texture, input_textures) in scene.inputs.values_mut() {
         input_textures.convert_to_node_texture(ctx.wgpu_ctx, node_texture);
     }
+
+    for input_id in missing_inputs {
+        let (Some((node_texture, _)), Some(slate)) = (
+            scene.inputs.get_mut(&input_id),
+            scene.slates.get_mut(&input_id),
+        ) else {
+            continue;
+        };
+        slate.render(ctx, node_texture, frame_set.pts);
+    }
 }
 
 enum PartialOutputFrame<'a, F>
@@ -60,14 +73,20 @@ pub(super) fn read_outputs(
     ctx: &RenderCtx,
     scene: &mut RenderGraph,
     pts: Duration,
+    should_render: &impl Fn(&OutputId) -> bool,
 ) -> HashMap<OutputId, Frame> {
     let mut partial_textures = Vec::with_capacity(scene.outputs.len());
-    for (output_id, output) in &scene.outputs {
+    let outputs = scene
+        .outputs
+        .iter()
+        .filter(|(output_id, _)| should_render(output_id));
+    for (output_id, output) in outputs {
         match output.root.output_texture(&scene.inputs).state() {
             Some(node) => match &output.output_texture {
                 OutputTexture::PlanarYuvTextures(yuv_output) => {
                     ctx.wgpu_ctx.format.rgba_to_yuv.convert(
                         ctx.wgpu_ctx,
+                        yuv_output.color_space(),
                         node.output_texture_bind_group(),
                         yuv_output.yuv_textures(),
                     );
@@ -99,6 +118,7 @@ pub(super) fn read_outputs(
                         resolution: texture.size().into(),
                         data: FrameData::Rgba8UnormWgpuTexture(texture.into()),
                         pts,
+                        color: ColorInfo::default(),
                     };
                     partial_textures.push(PartialOutputFrame::CompleteFrame {
                         output_id: output_id.clone(),
@@ -109,9 +129,11 @@ pub(super) fn read_outputs(
             // fallback if root node in render graph is empty
             None => match &output.output_texture {
                 OutputTexture::PlanarYuvTextures(yuv_output) => {
-                    yuv_output
-                        .yuv_textures()
-                        .fill_with_color(ctx.wgpu_ctx, RGBColor::BLACK);
+                    yuv_output.yuv_textures().fill_with_color(
+                        ctx.wgpu_ctx,
+                        RGBColor::BLACK,
+                        yuv_output.color_space(),
+                    );
 
                     let pending_download = yuv_output.start_download(ctx.wgpu_ctx);
                     partial_textures.push(PartialOutputFrame::PendingYuvDownload {
@@ -138,6 +160,7 @@ pub(super) fn read_outputs(
                             data: FrameData::Rgba8UnormWgpuTexture(Arc::new(wgpu_texture)),
                             resolution: *resolution,
                             pts,
+                            color: ColorInfo::default(),
                         },
                     })
                 }
@@ -169,21 +192,24 @@ pub(super) fn read_outputs(
                     error!("Output_id {} not found", output_id);
                     continue;
                 };
-                let data = match &output.output_texture {
+                let (data, color) = match &output.output_texture {
                     OutputTexture::PlanarYuvTextures(planar_yuv_output) => {
-                        match planar_yuv_output.yuv_textures().variant() {
+                        let data = match planar_yuv_output.yuv_textures().variant() {
                             PlanarYuvVariant::YUV420 => FrameData::PlanarYuv420(yuv_planes),
                             PlanarYuvVariant::YUV422 => FrameData::PlanarYuv422(yuv_planes),
                             PlanarYuvVariant::YUV444 => FrameData::PlanarYuv444(yuv_planes),
                             PlanarYuvVariant::YUVJ420 => FrameData::PlanarYuvJ420(yuv_planes),
-                        }
+                            PlanarYuvVariant::YUV420P10 => FrameData::PlanarYuv420P10(yuv_planes),
+                        };
+                        (data, planar_yuv_output.color_space().color_info())
                     }
-                    _ => FrameData::PlanarYuv420(yuv_planes),
+                    _ => (FrameData::PlanarYuv420(yuv_planes), ColorInfo::default()),
                 };
                 let frame = Frame {
                     data,
                     resolution,
                     pts,
+                    color,
                 };
                 result.insert(output_id.clone(), frame);
             }
@@ -196,8 +222,17 @@ pub(super) fn read_outputs(
     result
 }
 
-pub(super) fn run_transforms(ctx: &mut RenderCtx, scene: &mut RenderGraph, pts: Duration) {
-    for output in scene.outputs.values_mut() {
+pub(super) fn run_transforms(
+    ctx: &mut RenderCtx,
+    scene: &mut RenderGraph,
+    pts: Duration,
+    should_render: &impl Fn(&OutputId) -> bool,
+) {
+    let outputs = scene
+        .outputs
+        .iter_mut()
+        .filter(|(output_id, _)| should_render(output_id));
+    for (_, output) in outputs {
         render_node(ctx, &scene.inputs, pts, &mut output.root);
     }
 }
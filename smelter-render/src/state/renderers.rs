@@ -3,8 +3,10 @@ use std::sync::Arc;
 use crate::{
     error::InitRendererEngineError,
     registry::{RegistryType, RendererRegistry},
+    scene::Component,
     transformations::{
-        image::Image, layout::LayoutRenderer, shader::Shader, web_renderer::WebRenderer,
+        image::Image, layout::LayoutRenderer, mask::MaskPipeline, shader::Shader,
+        video_clip::VideoClip, web_renderer::WebRenderer,
     },
 };
 
@@ -14,7 +16,10 @@ pub(crate) struct Renderers {
     pub(crate) shaders: RendererRegistry<Arc<Shader>>,
     pub(crate) web_renderers: RendererRegistry<Arc<WebRenderer>>,
     pub(crate) images: RendererRegistry<Image>,
+    pub(crate) video_clips: RendererRegistry<Arc<VideoClip>>,
+    pub(crate) fragments: RendererRegistry<Arc<Component>>,
     pub(crate) layout: LayoutRenderer,
+    pub(crate) mask: Arc<MaskPipeline>,
 }
 
 impl Renderers {
@@ -23,8 +28,11 @@ impl Renderers {
             shaders: RendererRegistry::new(RegistryType::Shader),
             web_renderers: RendererRegistry::new(RegistryType::WebRenderer),
             images: RendererRegistry::new(RegistryType::Image),
+            video_clips: RendererRegistry::new(RegistryType::VideoClip),
+            fragments: RendererRegistry::new(RegistryType::Fragment),
             layout: LayoutRenderer::new(&wgpu_ctx)
                 .map_err(InitRendererEngineError::LayoutTransformationsInitError)?,
+            mask: Arc::new(MaskPipeline::new(&wgpu_ctx)),
         })
     }
 }
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use crossbeam_channel::bounded;
+use tracing::{error, warn};
+
+use crate::{
+    InputId, OutputId, Resolution,
+    error::SnapshotError,
+    wgpu::{
+        WgpuCtx,
+        texture::{TextureExt, utils::pad_to_256},
+    },
+};
+
+use super::render_graph::RenderGraph;
+
+#[derive(Debug, Clone)]
+pub enum SnapshotSource {
+    /// Last frame rendered for an output.
+    Output(OutputId),
+    /// Last frame of an input, after it was uploaded to the GPU.
+    Input(InputId),
+}
+
+/// Frame in RGBA format, 4 bytes per pixel without any padding between rows.
+#[derive(Debug, Clone)]
+pub struct RgbaSnapshot {
+    pub resolution: Resolution,
+    pub data: bytes::Bytes,
+}
+
+/// Copy of a frame scheduled on the GPU that was not downloaded yet. Waiting for the copy
+/// does not need access to the render graph, so it can happen without locking the renderer.
+pub(super) struct PendingSnapshot {
+    ctx: Arc<WgpuCtx>,
+    buffer: wgpu::Buffer,
+    size: wgpu::Extent3d,
+}
+
+pub(super) fn start_snapshot(
+    ctx: &Arc<WgpuCtx>,
+    render_graph: &RenderGraph,
+    source: &SnapshotSource,
+) -> Result<PendingSnapshot, SnapshotError> {
+    let node_texture = match source {
+        SnapshotSource::Output(output_id) => render_graph
+            .outputs
+            .get(output_id)
+            .ok_or_else(|| SnapshotError::OutputNotFound(output_id.clone()))?
+            .root
+            .output_texture(&render_graph.inputs),
+        SnapshotSource::Input(input_id) => {
+            &render_graph
+                .inputs
+                .get(input_id)
+                .ok_or_else(|| SnapshotError::InputNotFound(input_id.clone()))?
+                .0
+        }
+    };
+    let Some(state) = node_texture.state() else {
+        return Err(SnapshotError::NoFrame);
+    };
+    let texture = state.texture();
+    let buffer = texture.new_download_buffer(ctx);
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("snapshot download encoder"),
+        });
+    texture.copy_to_buffer(&mut encoder, &buffer);
+    ctx.queue.submit(Some(encoder.finish()));
+
+    Ok(PendingSnapshot {
+        ctx: ctx.clone(),
+        buffer,
+        size: texture.size(),
+    })
+}
+
+impl PendingSnapshot {
+    /// Blocks until the copy is finished and returns the frame without row padding.
+    pub(super) fn download(self) -> Result<RgbaSnapshot, SnapshotError> {
+        let Self { ctx, buffer, size } = self;
+        let (s, r) = bounded(1);
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                if let Err(err) = s.send(result) {
+                    error!("channel send error: {err}")
+                }
+            });
+        while let Err(wgpu::PollError::Timeout) = ctx.device.poll(wgpu::MaintainBase::Wait) {
+            warn!("Device poll failed.")
+        }
+        // Sender is dropped without sending if the callback was never called.
+        r.recv().map_err(|_| SnapshotError::DownloadCanceled)??;
+
+        let row_size = (size.width * 4) as usize;
+        let mut data = bytes::BytesMut::with_capacity(row_size * size.height as usize);
+        {
+            let range = buffer.slice(..).get_mapped_range();
+            for chunk in range.chunks(pad_to_256(size.width * 4) as usize) {
+                data.extend_from_slice(&chunk[..row_size]);
+            }
+        }
+        buffer.unmap();
+
+        Ok(RgbaSnapshot {
+            resolution: size.into(),
+            data: data.freeze(),
+        })
+    }
+}
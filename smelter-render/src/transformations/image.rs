@@ -40,6 +40,7 @@ pub enum ImageType {
     Jpeg,
     Svg,
     Gif,
+    Webp,
     Auto,
 }
 
@@ -54,10 +55,8 @@ impl Image {
     pub fn new(ctx: &RegisterCtx, spec: ImageSpec) -> Result<Self, ImageError> {
         let file = Self::download_file(&spec.src)?;
         let renderer = match spec.image_type {
-            ImageType::Png => {
-                let asset = BitmapAsset::new(&ctx.wgpu_ctx, file, ImageFormat::Png)?;
-                Image::Bitmap(Arc::new(asset))
-            }
+            // PNG files might contain an animation (APNG)
+            ImageType::Png => Self::new_animated_or_bitmap(ctx, file, ImageFormat::Png)?,
             ImageType::Jpeg => {
                 let asset = BitmapAsset::new(&ctx.wgpu_ctx, file, ImageFormat::Jpeg)?;
                 Image::Bitmap(Arc::new(asset))
@@ -66,17 +65,8 @@ impl Image {
                 let asset = SvgAsset::new(&ctx.wgpu_ctx, file)?;
                 Image::Svg(Arc::new(asset))
             }
-            ImageType::Gif => {
-                let asset = AnimatedAsset::new(&ctx.wgpu_ctx, file.clone(), ImageFormat::Gif);
-                match asset {
-                    Ok(asset) => Image::Animated(Arc::new(asset)),
-                    Err(AnimatedError::SingleFrame) => {
-                        let asset = BitmapAsset::new(&ctx.wgpu_ctx, file, ImageFormat::Gif)?;
-                        Image::Bitmap(Arc::new(asset))
-                    }
-                    Err(err) => return Err(ImageError::from(err)),
-                }
-            }
+            ImageType::Gif => Self::new_animated_or_bitmap(ctx, file, ImageFormat::Gif)?,
+            ImageType::Webp => Self::new_animated_or_bitmap(ctx, file, ImageFormat::WebP)?,
             ImageType::Auto => {
                 let format = match image::guess_format(&file) {
                     Ok(format) => format,
@@ -90,18 +80,8 @@ impl Image {
                 };
 
                 match format {
-                    ImageFormat::Gif => {
-                        let asset =
-                            AnimatedAsset::new(&ctx.wgpu_ctx, file.clone(), ImageFormat::Gif);
-                        match asset {
-                            Ok(asset) => Image::Animated(Arc::new(asset)),
-                            Err(AnimatedError::SingleFrame) => {
-                                let asset =
-                                    BitmapAsset::new(&ctx.wgpu_ctx, file, ImageFormat::Gif)?;
-                                Image::Bitmap(Arc::new(asset))
-                            }
-                            Err(err) => return Err(ImageError::from(err)),
-                        }
+                    ImageFormat::Gif | ImageFormat::WebP | ImageFormat::Png => {
+                        Self::new_animated_or_bitmap(ctx, file, format)?
                     }
                     other_format => {
                         let asset = BitmapAsset::new(&ctx.wgpu_ctx, file, other_format)?;
@@ -113,6 +93,23 @@ impl Image {
         Ok(renderer)
     }
 
+    /// Formats that support animations can also contain a single frame. In that
+    /// case, the more efficient bitmap implementation is used.
+    fn new_animated_or_bitmap(
+        ctx: &RegisterCtx,
+        file: Bytes,
+        format: ImageFormat,
+    ) -> Result<Self, ImageError> {
+        match AnimatedAsset::new(&ctx.wgpu_ctx, file.clone(), format) {
+            Ok(asset) => Ok(Image::Animated(Arc::new(asset))),
+            Err(AnimatedError::SingleFrame) => {
+                let asset = BitmapAsset::new(&ctx.wgpu_ctx, file, format)?;
+                Ok(Image::Bitmap(Arc::new(asset)))
+            }
+            Err(err) => Err(ImageError::from(err)),
+        }
+    }
+
     pub fn resolution(&self) -> Resolution {
         match self {
             Image::Bitmap(asset) => asset.resolution(),
@@ -166,7 +163,7 @@ impl ImageNode {
             },
             Image::Animated(asset) => Self::Animated {
                 asset,
-                state: AnimatedNodeState::new(image.start_pts, image.resolution),
+                state: AnimatedNodeState::new(image.start_pts, image.resolution, image.playback),
             },
             Image::Svg(asset) => Self::Svg {
                 asset,
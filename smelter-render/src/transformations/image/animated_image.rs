@@ -1,9 +1,13 @@
 use std::time::Duration;
 
-use image::{AnimationDecoder, ImageFormat, codecs::gif::GifDecoder};
+use image::{
+    AnimationDecoder, ImageFormat,
+    codecs::{gif::GifDecoder, png::PngDecoder, webp::WebPDecoder},
+};
 
 use crate::{
     RenderingMode, Resolution,
+    scene::ImagePlayback,
     state::node_texture::NodeTextureState,
     wgpu::{
         WgpuCtx,
@@ -16,6 +20,7 @@ use super::AnimatedError;
 pub struct AnimatedNodeState {
     start_pts: Duration,
     resolution: Resolution,
+    playback: ImagePlayback,
 }
 
 #[derive(Debug)]
@@ -46,6 +51,14 @@ impl AnimatedAsset {
     ) -> Result<Self, AnimatedError> {
         let decoded_frames = match format {
             ImageFormat::Gif => GifDecoder::new(&data[..])?.into_frames(),
+            ImageFormat::WebP => WebPDecoder::new(&data[..])?.into_frames(),
+            ImageFormat::Png => {
+                let decoder = PngDecoder::new(&data[..])?;
+                if !decoder.is_apng() {
+                    return Err(AnimatedError::SingleFrame);
+                }
+                decoder.apng().into_frames()
+            }
             other => return Err(AnimatedError::UnsupportedImageFormat(other)),
         };
 
@@ -124,16 +137,22 @@ impl AnimatedAsset {
         state: &mut AnimatedNodeState,
         pts: Duration,
     ) {
-        let animation_pts = Duration::from_nanos(
-            ((pts.as_nanos() - state.start_pts.as_nanos()) % self.animation_duration.as_nanos())
-                as u64,
-        );
-
-        let closest_frame = self
-            .frames
-            .iter()
-            .min_by_key(|frame| u128::abs_diff(frame.pts().as_nanos(), animation_pts.as_nanos()))
-            .unwrap();
+        let animation_pts =
+            state
+                .playback
+                .animation_pts(state.start_pts, pts, self.animation_duration);
+
+        let closest_frame = match animation_pts {
+            Some(animation_pts) => self
+                .frames
+                .iter()
+                .min_by_key(|frame| {
+                    u128::abs_diff(frame.pts().as_nanos(), animation_pts.as_nanos())
+                })
+                .unwrap(),
+            // Hold the last frame after all loops were played.
+            None => self.frames.last().unwrap(),
+        };
         match &closest_frame {
             AnimationFrame::Srgb { bg, .. } => {
                 ctx.utils
@@ -154,10 +173,11 @@ impl AnimatedAsset {
 }
 
 impl AnimatedNodeState {
-    pub fn new(start_pts: Duration, resolution: Resolution) -> Self {
+    pub fn new(start_pts: Duration, resolution: Resolution, playback: ImagePlayback) -> Self {
         Self {
             start_pts,
             resolution,
+            playback,
         }
     }
     pub fn resolution(&self) -> Resolution {
@@ -2,10 +2,14 @@ use std::{sync::Arc, time::Duration};
 
 use crate::{
     Resolution,
-    scene::{BorderRadius, BoxShadow, RGBAColor, Size},
-    state::{RenderCtx, node_texture::NodeTexture},
+    scene::{BlendMode, BorderRadius, BoxShadow, ComponentId, RGBAColor, Size},
+    state::{
+        RenderCtx,
+        node_texture::{NodeTexture, NodeTextureState},
+    },
 };
 
+mod component_layouts;
 mod flatten;
 mod layout_renderer;
 mod params;
@@ -24,6 +28,10 @@ pub(crate) trait LayoutProvider: Send {
 pub(crate) struct LayoutNode {
     layout_provider: Box<dyn LayoutProvider>,
     shader: Arc<LayoutShader>,
+    /// Copy of the output texture used by layouts with non-normal blend mode.
+    backdrop: NodeTexture,
+    /// Textures of opacity groups that are rendered before the output.
+    layers: Vec<NodeTexture>,
 }
 
 /// When rendering we cut this fragment from texture and stretch it on
@@ -68,6 +76,21 @@ struct RenderLayout {
     border_radius: BorderRadius,
     masks: Vec<Mask>,
     content: RenderLayoutContent,
+    // Opacity multiplied by opacity of all parents (up to the opacity group)
+    opacity: f32,
+    blend_mode: BlendMode,
+    // Opacity groups this layout belongs to, starting from the outermost one.
+    opacity_groups: Vec<OpacityGroup>,
+}
+
+/// Layouts of a component with children and opacity lower than 1. They are rendered
+/// to a separate texture first, and then opacity and blend mode are applied to the
+/// entire group.
+#[derive(Debug, Clone)]
+struct OpacityGroup {
+    id: usize,
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 #[derive(Debug, Clone)]
@@ -134,6 +157,13 @@ pub struct NestedLayout {
     pub border_radius: BorderRadius,
     pub box_shadow: Vec<BoxShadow>,
 
+    /// Opacity of this layout and all of its children. Layout with children is rendered
+    /// to a separate texture first, so overlapping children are not visible through
+    /// each other.
+    pub opacity: f32,
+    /// Blend mode of this layout. Children with `BlendMode::Normal` inherit it.
+    pub blend_mode: BlendMode,
+
     pub(crate) children: Vec<NestedLayout>,
     /// Describes how many children of this component are nodes. This value also
     /// counts `layout` if its content is a `LayoutContent::ChildNode`.
@@ -143,6 +173,8 @@ pub struct NestedLayout {
     /// of its children then child_nodes_count will count all of those components even
     /// though only one of those children will be present in the layouts tree.
     pub(crate) child_nodes_count: usize,
+    /// Id of a component that is positioned by this layout.
+    pub(crate) component_id: Option<ComponentId>,
 }
 
 impl LayoutNode {
@@ -152,6 +184,8 @@ impl LayoutNode {
         Self {
             layout_provider,
             shader,
+            backdrop: NodeTexture::new(),
+            layers: vec![],
         }
     }
 
@@ -168,26 +202,93 @@ impl LayoutNode {
             .collect();
         let output_resolution = self.layout_provider.resolution(pts);
         let layouts = self.layout_provider.layouts(pts, &input_resolutions);
-        let layouts = layouts.flatten(&input_resolutions, output_resolution);
+        let mut layers = layouts.flatten(&input_resolutions, output_resolution);
+        let output_layouts = layers.pop().unwrap_or_default();
+
+        let needs_backdrop = layers
+            .iter()
+            .chain([&output_layouts])
+            .flatten()
+            .any(|layout| layout.blend_mode != BlendMode::Normal);
+        let backdrop = if needs_backdrop {
+            Some(self.backdrop.ensure_size(ctx.wgpu_ctx, output_resolution))
+        } else {
+            self.backdrop.clear();
+            None
+        };
+
+        self.layers.resize_with(layers.len(), NodeTexture::new);
+        for (index, layouts) in layers.into_iter().enumerate() {
+            // layouts can only use layers rendered before them
+            let (rendered_layers, remaining_layers) = self.layers.split_at_mut(index);
+            let textures = Self::layout_textures(&layouts, sources, rendered_layers);
+            Self::render_layouts(
+                ctx,
+                &self.shader,
+                layouts,
+                &textures,
+                &mut remaining_layers[0],
+                backdrop,
+                output_resolution,
+            );
+        }
+
+        let textures = Self::layout_textures(&output_layouts, sources, &self.layers);
+        Self::render_layouts(
+            ctx,
+            &self.shader,
+            output_layouts,
+            &textures,
+            target,
+            backdrop,
+            output_resolution,
+        );
+    }
 
-        let textures: Vec<Option<&NodeTexture>> = layouts
+    /// Textures used by layouts. Indexes after the input textures refer to layers.
+    fn layout_textures<'a>(
+        layouts: &[RenderLayout],
+        sources: &[&'a NodeTexture],
+        layers: &'a [NodeTexture],
+    ) -> Vec<Option<&'a NodeTexture>> {
+        layouts
             .iter()
             .map(|layout| match layout.content {
                 RenderLayoutContent::BoxShadow { .. } => None,
                 RenderLayoutContent::Color { .. } => None,
-                RenderLayoutContent::ChildNode { index, .. } => match sources.get(index) {
-                    Some(node_texture) => Some(*node_texture),
-                    None => {
+                RenderLayoutContent::ChildNode { index, .. } => {
+                    let texture = match index.checked_sub(sources.len()) {
+                        None => sources.get(index).copied(),
+                        Some(layer_index) => layers.get(layer_index),
+                    };
+                    if texture.is_none() {
                         error!("Invalid source index in layout");
-                        None
                     }
-                },
+                    texture
+                }
             })
-            .collect();
+            .collect()
+    }
 
-        let target = target.ensure_size(ctx.wgpu_ctx, output_resolution);
-        self.shader
-            .render(ctx.wgpu_ctx, output_resolution, layouts, &textures, target);
+    /// * `backdrop` - texture used by layouts with non-normal blend mode, see `LayoutShader::render`.
+    fn render_layouts(
+        ctx: &RenderCtx,
+        shader: &LayoutShader,
+        layouts: Vec<RenderLayout>,
+        textures: &[Option<&NodeTexture>],
+        target: &mut NodeTexture,
+        backdrop: Option<&NodeTextureState>,
+        resolution: Resolution,
+    ) {
+        let target = target.ensure_size(ctx.wgpu_ctx, resolution);
+        shader.render(
+            ctx.wgpu_ctx,
+            resolution,
+            layouts,
+            textures,
+            target,
+            backdrop,
+        );
     }
 }
 
@@ -213,6 +314,9 @@ impl NestedLayout {
             border_color: RGBAColor(0, 0, 0, 0),
             border_radius: BorderRadius::ZERO,
             box_shadow: vec![],
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            component_id: None,
         }
     }
 }
@@ -70,11 +70,17 @@ struct LayoutInfo {
     // 0 -> Texture, 1 -> Color, 2 -> BoxShadow
     layout_type: u32,
     index: u32,
-    masks_len: u32
+    masks_len: u32,
+    // 0 -> Normal, 1 -> Multiply, 2 -> Screen, 3 -> Add, 4 -> Overlay
+    blend_mode: u32,
+    opacity: f32,
 }
 
 
 @group(0) @binding(0) var texture: texture_2d<f32>;
+// Copy of the output texture before this layout is rendered. Only
+// valid if blend_mode is not Normal.
+@group(0) @binding(1) var backdrop_texture: texture_2d<f32>;
 
 @group(1) @binding(0) var<uniform> output_resolution: vec4<f32>;
 @group(1) @binding(1) var<uniform> texture_params: array<TextureParams, 100>;
@@ -250,8 +256,66 @@ fn roundedRectSDF(dist: vec2<f32>, size: vec2<f32>, radius: vec4<f32>, rotation:
     return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0, 0.0))) - r.x;
 }
 
+// Blend functions from https://www.w3.org/TR/compositing-1/#blending
+// cb - backdrop color, cs - source color (both not premultiplied)
+fn blend_channels(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+    switch layout_info.blend_mode {
+        // multiply
+        case 1u: {
+            return cb * cs;
+        }
+        // screen
+        case 2u: {
+            return cb + cs - cb * cs;
+        }
+        // overlay
+        case 4u: {
+            let multiply = 2.0 * cb * cs;
+            let screen = 1.0 - 2.0 * (1.0 - cb) * (1.0 - cs);
+            return select(screen, multiply, cb <= vec3(0.5));
+        }
+        default {
+            return cs;
+        }
+    }
+}
+
+// Composes premultiplied source color with the premultiplied backdrop color.
+fn blend(source: vec4<f32>, backdrop: vec4<f32>) -> vec4<f32> {
+    // add
+    if (layout_info.blend_mode == 3u) {
+        return min(source + backdrop, vec4(1.0));
+    }
+    if (source.a <= 0.0) {
+        return backdrop;
+    }
+    if (backdrop.a <= 0.0) {
+        return source;
+    }
+    let cs = source.rgb / source.a;
+    let cb = backdrop.rgb / backdrop.a;
+    let blended = blend_channels(cb, cs);
+
+    let color = source.rgb * (1.0 - backdrop.a)
+        + backdrop.rgb * (1.0 - source.a)
+        + source.a * backdrop.a * blended;
+    let alpha = source.a + backdrop.a * (1.0 - source.a);
+    return vec4(color, alpha);
+}
+
 @fragment
 fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let color = layout_color(input) * layout_info.opacity;
+    if (layout_info.blend_mode == 0u) {
+        return color;
+    }
+    // Pipeline for non-normal blend modes replaces the output value, so
+    // result needs to be composed with the backdrop here.
+    let backdrop_color = textureLoad(backdrop_texture, vec2<i32>(input.position.xy), 0);
+    return blend(color, backdrop_color);
+}
+
+fn layout_color(input: VertexOutput) -> vec4<f32> {
     let transparent = vec4<f32>(0.0, 0.0, 0.0, 0.0);
 
     var mask_alpha = 1.0;
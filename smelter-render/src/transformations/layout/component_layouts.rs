@@ -0,0 +1,66 @@
+use crate::scene::ComponentLayout;
+
+use super::NestedLayout;
+
+/// Maps coordinates of a layout to the coordinates of the output frame.
+#[derive(Debug, Clone, Copy)]
+struct Transform {
+    top: f32,
+    left: f32,
+    scale_x: f32,
+    scale_y: f32,
+}
+
+impl NestedLayout {
+    /// Positions of all components with an id that are placed by this layout tree. Values
+    /// are in the coordinates of the output frame and do not take into account rotation
+    /// or parts that are cut off by masks.
+    pub(crate) fn component_layouts(&self) -> Vec<ComponentLayout> {
+        let mut layouts = Vec::new();
+        let transform = Transform {
+            top: 0.0,
+            left: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+        };
+        self.collect_component_layouts(transform, &mut layouts);
+        layouts
+    }
+
+    fn collect_component_layouts(&self, parent: Transform, layouts: &mut Vec<ComponentLayout>) {
+        let top = parent.top + self.top * parent.scale_y;
+        let left = parent.left + self.left * parent.scale_x;
+        if let Some(component_id) = &self.component_id {
+            layouts.push(ComponentLayout {
+                component_id: component_id.clone(),
+                top,
+                left,
+                width: self.width * parent.scale_x,
+                height: self.height * parent.scale_y,
+            });
+        }
+
+        // Crop is applied before scaling, so it replaces the scale of this layout.
+        let transform = match &self.crop {
+            Some(crop) if crop.width > 0.0 && crop.height > 0.0 => {
+                let scale_x = parent.scale_x * self.width / crop.width;
+                let scale_y = parent.scale_y * self.height / crop.height;
+                Transform {
+                    top: top - crop.top * scale_y,
+                    left: left - crop.left * scale_x,
+                    scale_x,
+                    scale_y,
+                }
+            }
+            _ => Transform {
+                top,
+                left,
+                scale_x: parent.scale_x * self.scale_x,
+                scale_y: parent.scale_y * self.scale_y,
+            },
+        };
+        for child in self.children.iter() {
+            child.collect_component_layouts(transform, layouts);
+        }
+    }
+}
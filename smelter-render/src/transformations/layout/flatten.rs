@@ -1,30 +1,111 @@
 use std::{iter, mem};
 
-use crate::{Resolution, scene::RGBAColor};
+use crate::{
+    Resolution,
+    scene::{BlendMode, RGBAColor},
+};
 
 use super::{
-    BoxShadow, Crop, LayoutContent, Mask, NestedLayout, RenderLayout, RenderLayoutContent,
+    BorderRadius, BoxShadow, Crop, LayoutContent, Mask, NestedLayout, OpacityGroup, RenderLayout,
+    RenderLayoutContent,
 };
 
 impl NestedLayout {
+    /// Returns layouts grouped into layers. Each layer is rendered to a separate texture,
+    /// the last one is rendered to the output. Layers are referenced by the layouts of
+    /// the following layers as `RenderLayoutContent::ChildNode` with indexes starting
+    /// after the indexes of input textures.
     pub(super) fn flatten(
         self,
         input_resolutions: &[Option<Resolution>],
         resolution: Resolution,
-    ) -> Vec<RenderLayout> {
-        let (shadow, layouts) = self.inner_flatten(0, vec![]);
-        shadow
+    ) -> Vec<Vec<RenderLayout>> {
+        let (shadow, layouts) = self.inner_flatten(0, vec![], &mut 0);
+        let layouts = shadow
             .into_iter()
             .chain(layouts)
             .filter(|layout| Self::should_render(layout, input_resolutions, resolution))
             .map(NestedLayout::fix_final_render_layout)
-            .collect()
+            .collect();
+
+        let mut layers = vec![];
+        let layouts = Self::split_layers(layouts, &mut layers, input_resolutions.len(), resolution);
+        layers.push(layouts);
+        layers
+    }
+
+    /// Moves layouts of each opacity group to a separate layer (after layers of nested
+    /// groups) and replaces them with a single layout that renders that layer.
+    fn split_layers(
+        layouts: Vec<RenderLayout>,
+        layers: &mut Vec<Vec<RenderLayout>>,
+        inputs_count: usize,
+        resolution: Resolution,
+    ) -> Vec<RenderLayout> {
+        let mut result = vec![];
+        let mut layouts = layouts.into_iter().peekable();
+        while let Some(mut layout) = layouts.next() {
+            if layout.opacity_groups.is_empty() {
+                result.push(layout);
+                continue;
+            }
+            let group = layout.opacity_groups.remove(0);
+            let mut group_layouts = vec![layout];
+            while let Some(mut layout) = layouts.next_if(|layout| {
+                layout.opacity_groups.first().map(|group| group.id) == Some(group.id)
+            }) {
+                layout.opacity_groups.remove(0);
+                group_layouts.push(layout);
+            }
+
+            if group.opacity <= 0.0 {
+                continue;
+            }
+
+            let mut group_layouts =
+                Self::split_layers(group_layouts, layers, inputs_count, resolution);
+            // Nothing overlaps inside of the group, so it does not need a separate texture.
+            if group_layouts.len() == 1 && group_layouts[0].blend_mode == BlendMode::Normal {
+                let mut layout = group_layouts.remove(0);
+                layout.opacity *= group.opacity;
+                layout.blend_mode = group.blend_mode;
+                result.push(layout);
+                continue;
+            }
+
+            layers.push(group_layouts);
+            result.push(RenderLayout {
+                top: 0.0,
+                left: 0.0,
+                width: resolution.width as f32,
+                height: resolution.height as f32,
+                rotation_degrees: 0.0,
+                border_radius: BorderRadius::ZERO,
+                masks: vec![],
+                content: RenderLayoutContent::ChildNode {
+                    index: inputs_count + layers.len() - 1,
+                    border_color: RGBAColor(0, 0, 0, 0),
+                    border_width: 0.0,
+                    crop: Crop {
+                        top: 0.0,
+                        left: 0.0,
+                        width: resolution.width as f32,
+                        height: resolution.height as f32,
+                    },
+                },
+                opacity: group.opacity,
+                blend_mode: group.blend_mode,
+                opacity_groups: vec![],
+            });
+        }
+        result
     }
 
     fn inner_flatten(
         mut self,
         child_index_offset: usize,
         parent_masks: Vec<Mask>,
+        opacity_groups_count: &mut usize,
     ) -> (Vec<RenderLayout>, Vec<RenderLayout>) {
         let mut child_index_offset = child_index_offset;
         if let LayoutContent::ChildNode { index, size } = self.content {
@@ -58,27 +139,78 @@ impl NestedLayout {
                 .into_iter()
                 .map(|child| {
                     let child_nodes_count = child.child_nodes_count;
-                    let (shadows, layouts) =
-                        child.inner_flatten(child_index_offset, parent_masks.clone());
+                    let (shadows, layouts) = child.inner_flatten(
+                        child_index_offset,
+                        parent_masks.clone(),
+                        opacity_groups_count,
+                    );
                     child_index_offset += child_nodes_count;
                     (shadows, layouts)
                 })
                 .unzip();
-        let children_shadow = children_shadow
+        let children_shadow: Vec<_> = children_shadow
             .into_iter()
             .flatten()
             .map(|l| self.flatten_child(l))
             .collect();
-        let children_layouts = children_layouts
+        let children_layouts: Vec<_> = children_layouts
             .into_iter()
             .flatten()
             .map(|l| self.flatten_child(l))
             .collect();
+        let layouts = [vec![layout], children_shadow, children_layouts].concat();
+
+        // Layout with children is rendered as a separate layer, so opacity is applied
+        // once to the entire group and overlapping children are not visible through
+        // each other.
+        let layouts = if self.opacity < 1.0 && layouts.len() > 1 {
+            let group = OpacityGroup {
+                id: *opacity_groups_count,
+                opacity: self.opacity,
+                blend_mode: self.blend_mode,
+            };
+            *opacity_groups_count += 1;
+            layouts
+                .into_iter()
+                .enumerate()
+                .map(|(index, mut layout)| {
+                    // the layout of this component has its own opacity and blend mode
+                    if index == 0 {
+                        layout.opacity = 1.0;
+                        layout.blend_mode = BlendMode::Normal;
+                    }
+                    layout.opacity_groups.insert(0, group.clone());
+                    layout
+                })
+                .collect()
+        } else {
+            layouts
+                .into_iter()
+                .enumerate()
+                .map(|(index, layout)| match index {
+                    0 => layout,
+                    _ => self.apply_opacity_and_blend_mode(layout),
+                })
+                .collect()
+        };
 
-        (
-            box_shadow_layouts,
-            [vec![layout], children_shadow, children_layouts].concat(),
-        )
+        (box_shadow_layouts, layouts)
+    }
+
+    /// Applies opacity and blend mode of this layout to a flattened child. If the child
+    /// belongs to an opacity group, they are applied to the entire group instead.
+    fn apply_opacity_and_blend_mode(&self, mut child: RenderLayout) -> RenderLayout {
+        match child.opacity_groups.first_mut() {
+            Some(group) => {
+                group.opacity *= self.opacity;
+                group.blend_mode = self.child_blend_mode(group.blend_mode);
+            }
+            None => {
+                child.opacity *= self.opacity;
+                child.blend_mode = self.child_blend_mode(child.blend_mode);
+            }
+        }
+        child
     }
 
     // Final pass on each render layout, it applies following modifications:
@@ -125,6 +257,7 @@ impl NestedLayout {
     ) -> bool {
         if layout.width <= 0.0
             || layout.height <= 0.0
+            || layout.opacity <= 0.0
             || layout.top > resolution.height as f32
             || layout.left > resolution.width as f32
         {
@@ -208,6 +341,9 @@ impl NestedLayout {
                 // scaled
                 border_radius: child.border_radius * unified_scale,
                 masks: self.parent_parent_masks(&child.masks),
+                opacity: child.opacity,
+                blend_mode: child.blend_mode,
+                opacity_groups: child.opacity_groups,
             },
             Some(crop) => {
                 // Below values are only correct if `crop` is in the same coordinate
@@ -242,6 +378,9 @@ impl NestedLayout {
                             },
                             border_radius: child.border_radius * unified_scale,
                             masks: self.parent_parent_masks(&child.masks),
+                            opacity: child.opacity,
+                            blend_mode: child.blend_mode,
+                            opacity_groups: child.opacity_groups,
                         }
                     }
                     RenderLayoutContent::ChildNode {
@@ -282,6 +421,9 @@ impl NestedLayout {
                             },
                             border_radius: child.border_radius * unified_scale,
                             masks: self.parent_parent_masks(&child.masks),
+                            opacity: child.opacity,
+                            blend_mode: child.blend_mode,
+                            opacity_groups: child.opacity_groups,
                         }
                     }
                     RenderLayoutContent::BoxShadow { color, blur_radius } => {
@@ -297,6 +439,9 @@ impl NestedLayout {
                             },
                             border_radius: child.border_radius * unified_scale,
                             masks: self.parent_parent_masks(&child.masks),
+                            opacity: child.opacity,
+                            blend_mode: child.blend_mode,
+                            opacity_groups: child.opacity_groups,
                         }
                     }
                 }
@@ -340,6 +485,9 @@ impl NestedLayout {
             },
             border_radius: self.border_radius,
             masks: parent_masks.to_vec(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            opacity_groups: vec![],
         }
     }
 
@@ -357,6 +505,17 @@ impl NestedLayout {
                 blur_radius: box_shadow.blur_radius,
             },
             masks: parent_masks.to_vec(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            opacity_groups: vec![],
+        }
+    }
+
+    /// Children with a normal blend mode use blend mode of their parent.
+    fn child_blend_mode(&self, child_blend_mode: BlendMode) -> BlendMode {
+        match child_blend_mode {
+            BlendMode::Normal => self.blend_mode,
+            blend_mode => blend_mode,
         }
     }
 
@@ -388,3 +547,130 @@ impl NestedLayout {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESOLUTION: Resolution = Resolution {
+        width: 100,
+        height: 100,
+    };
+
+    fn layout(color: RGBAColor, opacity: f32, children: Vec<NestedLayout>) -> NestedLayout {
+        NestedLayout {
+            top: 0.0,
+            left: 0.0,
+            width: 100.0,
+            height: 100.0,
+            rotation_degrees: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            crop: None,
+            mask: None,
+            content: LayoutContent::Color(color),
+            border_width: 0.0,
+            border_color: RGBAColor(0, 0, 0, 0),
+            border_radius: BorderRadius::ZERO,
+            box_shadow: vec![],
+            opacity,
+            blend_mode: BlendMode::Normal,
+            children,
+            child_nodes_count: 0,
+            component_id: None,
+        }
+    }
+
+    fn color_layout(color: RGBAColor) -> NestedLayout {
+        layout(color, 1.0, vec![])
+    }
+
+    fn layer_index(layout: &RenderLayout) -> usize {
+        match layout.content {
+            RenderLayoutContent::ChildNode { index, .. } => index,
+            _ => panic!("Expected a layer, got {layout:?}"),
+        }
+    }
+
+    const RED: RGBAColor = RGBAColor(255, 0, 0, 255);
+    const BLUE: RGBAColor = RGBAColor(0, 0, 255, 255);
+    const TRANSPARENT: RGBAColor = RGBAColor(0, 0, 0, 0);
+
+    #[test]
+    fn test_opacity_group() {
+        let root = layout(
+            TRANSPARENT,
+            0.5,
+            vec![color_layout(RED), color_layout(BLUE)],
+        );
+        let layers = root.flatten(&[], RESOLUTION);
+
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].len(), 2);
+        assert!(layers[0].iter().all(|layout| layout.opacity == 1.0));
+
+        assert_eq!(layers[1].len(), 1);
+        assert_eq!(layer_index(&layers[1][0]), 0);
+        assert_eq!(layers[1][0].opacity, 0.5);
+        assert_eq!(layers[1][0].width, 100.0);
+    }
+
+    #[test]
+    fn test_opacity_single_child() {
+        // Only the child is rendered, so opacity can be applied directly.
+        let root = layout(TRANSPARENT, 0.5, vec![layout(RED, 0.5, vec![])]);
+        let layers = root.flatten(&[], RESOLUTION);
+
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].len(), 1);
+        assert_eq!(layers[0][0].opacity, 0.25);
+    }
+
+    #[test]
+    fn test_nested_opacity_groups() {
+        let inner = layout(
+            TRANSPARENT,
+            0.5,
+            vec![color_layout(RED), color_layout(BLUE)],
+        );
+        let mut root = layout(RED, 0.5, vec![inner]);
+        root.blend_mode = BlendMode::Screen;
+        let inputs = [Some(RESOLUTION)];
+        let layers = root.flatten(&inputs, RESOLUTION);
+
+        // Layers are indexed after inputs.
+        assert_eq!(layers.len(), 3);
+        assert_eq!(layers[0].len(), 2);
+        assert_eq!(layers[1].len(), 2);
+        assert_eq!(layer_index(&layers[1][1]), 1);
+        assert_eq!(layers[1][1].opacity, 0.5);
+        assert_eq!(layer_index(&layers[2][0]), 2);
+        assert_eq!(layers[2][0].opacity, 0.5);
+
+        // Blend mode is applied to the entire group.
+        assert!(
+            layers[..2]
+                .iter()
+                .flatten()
+                .all(|layout| layout.blend_mode == BlendMode::Normal)
+        );
+        assert_eq!(layers[2][0].blend_mode, BlendMode::Screen);
+    }
+
+    #[test]
+    fn test_transparent_opacity_group() {
+        let root = layout(
+            TRANSPARENT,
+            1.0,
+            vec![layout(
+                TRANSPARENT,
+                0.0,
+                vec![color_layout(RED), color_layout(BLUE)],
+            )],
+        );
+        let layers = root.flatten(&[], RESOLUTION);
+
+        assert_eq!(layers.len(), 1);
+        assert!(layers[0].is_empty());
+    }
+}
@@ -6,7 +6,7 @@ use wgpu::{
 
 use crate::{
     Resolution,
-    scene::RGBAColor,
+    scene::{BlendMode, RGBAColor},
     wgpu::{WgpuCtx, utils::convert_to_shader_color},
 };
 
@@ -23,14 +23,25 @@ pub struct LayoutInfo {
     pub layout_type: u32,
     pub index: u32,
     pub masks_len: u32,
+    pub blend_mode: BlendMode,
+    pub opacity: f32,
 }
 
 impl LayoutInfo {
-    pub fn to_bytes(&self) -> [u8; 16] {
-        let mut result = [0u8; 16];
+    pub fn to_bytes(&self) -> [u8; 20] {
+        let blend_mode: u32 = match self.blend_mode {
+            BlendMode::Normal => 0,
+            BlendMode::Multiply => 1,
+            BlendMode::Screen => 2,
+            BlendMode::Add => 3,
+            BlendMode::Overlay => 4,
+        };
+        let mut result = [0u8; 20];
         result[0..4].copy_from_slice(&self.layout_type.to_le_bytes());
         result[4..8].copy_from_slice(&self.index.to_le_bytes());
         result[8..12].copy_from_slice(&self.masks_len.to_le_bytes());
+        result[12..16].copy_from_slice(&blend_mode.to_le_bytes());
+        result[16..20].copy_from_slice(&self.opacity.to_le_bytes());
         result
     }
 }
@@ -207,6 +218,9 @@ impl ParamsBindGroups {
                 border_radius,
                 masks,
                 content,
+                opacity,
+                blend_mode,
+                opacity_groups: _,
             } = layout;
             let border_radius_bytes = borders_radius_to_bytes(*border_radius);
 
@@ -220,6 +234,8 @@ impl ParamsBindGroups {
                         layout_type: 1,
                         index: color_params.len() as u32,
                         masks_len: masks.len() as u32,
+                        blend_mode: *blend_mode,
+                        opacity: *opacity,
                     };
                     let mut color_params_bytes = [0u8; 80];
                     color_params_bytes[0..16].copy_from_slice(&border_radius_bytes);
@@ -244,6 +260,8 @@ impl ParamsBindGroups {
                         layout_type: 0,
                         index: texture_params.len() as u32,
                         masks_len: masks.len() as u32,
+                        blend_mode: *blend_mode,
+                        opacity: *opacity,
                     };
                     let mut texture_params_bytes = [0u8; 80];
                     texture_params_bytes[0..16].copy_from_slice(&border_radius_bytes);
@@ -267,6 +285,8 @@ impl ParamsBindGroups {
                         layout_type: 2,
                         index: box_shadow_params.len() as u32,
                         masks_len: masks.len() as u32,
+                        blend_mode: *blend_mode,
+                        opacity: *opacity,
                     };
                     let mut box_shadow_params_bytes = [0u8; 64];
                     box_shadow_params_bytes[0..16].copy_from_slice(&border_radius_bytes);
@@ -4,6 +4,7 @@ use tracing::error;
 
 use crate::{
     Resolution,
+    scene::BlendMode,
     state::node_texture::{NodeTexture, NodeTextureState},
     wgpu::{
         WgpuCtx, WgpuErrorScope,
@@ -18,6 +19,10 @@ const LABEL: Option<&str> = Some("layout node");
 #[derive(Debug)]
 pub struct LayoutShader {
     pipeline: wgpu::RenderPipeline,
+    /// Pipeline used for layouts with non-normal blend mode. Shader is blending
+    /// with a copy of the output texture, so the result replaces the current value.
+    blend_pipeline: wgpu::RenderPipeline,
+    textures_bind_group_layout: wgpu::BindGroupLayout,
     sampler: Sampler,
     params_bind_groups: ParamsBindGroups,
 }
@@ -42,6 +47,7 @@ impl LayoutShader {
     ) -> Result<Self, CreateShaderError> {
         let sampler = Sampler::new(&wgpu_ctx.device);
         let params_bind_groups = ParamsBindGroups::new(wgpu_ctx);
+        let textures_bind_group_layout = textures_bind_group_layout(&wgpu_ctx.device);
 
         let pipeline_layout =
             wgpu_ctx
@@ -49,14 +55,14 @@ impl LayoutShader {
                 .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                     label: LABEL,
                     bind_group_layouts: &[
-                        &wgpu_ctx.format.single_texture_layout,
+                        &textures_bind_group_layout,
                         &params_bind_groups.bind_group_1_layout,
                         &params_bind_groups.bind_group_2_layout,
                         &sampler.bind_group_layout,
                     ],
                     push_constant_ranges: &[wgpu::PushConstantRange {
                         stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                        range: 0..16,
+                        range: 0..20,
                     }],
                 });
 
@@ -67,14 +73,26 @@ impl LayoutShader {
             &shader_module,
             wgpu_ctx.default_view_format(),
         );
+        let blend_pipeline = common_pipeline::create_render_pipeline_with_blend(
+            "Layout node (blend mode)",
+            &wgpu_ctx.device,
+            &pipeline_layout,
+            &shader_module,
+            wgpu_ctx.default_view_format(),
+            Some(wgpu::BlendState::REPLACE),
+        );
 
         Ok(Self {
             pipeline,
+            blend_pipeline,
+            textures_bind_group_layout,
             sampler,
             params_bind_groups,
         })
     }
 
+    /// * `backdrop` - texture used to store a copy of the target for layouts with
+    ///   non-normal blend mode. It needs to have the same size as `target`.
     pub fn render(
         &self,
         wgpu_ctx: &Arc<WgpuCtx>,
@@ -82,11 +100,13 @@ impl LayoutShader {
         layouts: Vec<RenderLayout>,
         textures: &[Option<&NodeTexture>],
         target: &NodeTextureState,
+        backdrop: Option<&NodeTextureState>,
     ) {
         let layout_infos = self
             .params_bind_groups
             .update(wgpu_ctx, output_resolution, layouts);
-        let input_texture_bgs: Vec<wgpu::BindGroup> = self.input_textures_bg(wgpu_ctx, textures);
+        let input_texture_bgs: Vec<wgpu::BindGroup> =
+            self.input_textures_bg(wgpu_ctx, textures, backdrop);
 
         if layout_infos.len() != input_texture_bgs.len() {
             error!(
@@ -99,53 +119,86 @@ impl LayoutShader {
         let mut encoder = wgpu_ctx
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: LABEL });
+        let mut render_pass = Self::begin_render_pass(
+            &mut encoder,
+            target,
+            wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+        );
+
+        for (index, (texture_bg, layout_info)) in input_texture_bgs
+            .iter()
+            .zip(layout_infos.iter())
+            .take(100)
+            .enumerate()
         {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: LABEL,
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    view: target.view(),
-                    resolve_target: None,
-                })],
-                // TODO: depth stencil attachments
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            for (index, (texture_bg, layout_info)) in input_texture_bgs
-                .iter()
-                .zip(layout_infos.iter())
-                .take(100)
-                .enumerate()
-            {
-                render_pass.set_pipeline(&self.pipeline);
-
-                render_pass.set_push_constants(
-                    wgpu::ShaderStages::VERTEX_FRAGMENT,
-                    0,
-                    &layout_info.to_bytes(),
-                );
-
-                render_pass.set_bind_group(0, texture_bg, &[]);
-                render_pass.set_bind_group(1, &self.params_bind_groups.bind_group_1, &[]);
-                render_pass.set_bind_group(2, &self.params_bind_groups.bind_groups_2[index].0, &[]);
-                render_pass.set_bind_group(3, &self.sampler.bind_group, &[]);
-
-                wgpu_ctx.plane.draw(&mut render_pass);
+            let blend_backdrop = match layout_info.blend_mode {
+                BlendMode::Normal => None,
+                _ => backdrop,
+            };
+            match blend_backdrop {
+                Some(backdrop) => {
+                    // Texture can't be sampled while it's a render target, so everything
+                    // rendered so far needs to be copied.
+                    drop(render_pass);
+                    encoder.copy_texture_to_texture(
+                        target.texture().as_image_copy(),
+                        backdrop.texture().as_image_copy(),
+                        target.texture().size(),
+                    );
+                    render_pass = Self::begin_render_pass(&mut encoder, target, wgpu::LoadOp::Load);
+                    render_pass.set_pipeline(&self.blend_pipeline);
+                }
+                None => render_pass.set_pipeline(&self.pipeline),
             }
+
+            render_pass.set_push_constants(
+                wgpu::ShaderStages::VERTEX_FRAGMENT,
+                0,
+                &layout_info.to_bytes(),
+            );
+
+            render_pass.set_bind_group(0, texture_bg, &[]);
+            render_pass.set_bind_group(1, &self.params_bind_groups.bind_group_1, &[]);
+            render_pass.set_bind_group(2, &self.params_bind_groups.bind_groups_2[index].0, &[]);
+            render_pass.set_bind_group(3, &self.sampler.bind_group, &[]);
+
+            wgpu_ctx.plane.draw(&mut render_pass);
         }
+        drop(render_pass);
         wgpu_ctx.queue.submit(Some(encoder.finish()));
     }
 
+    fn begin_render_pass<'a>(
+        encoder: &'a mut wgpu::CommandEncoder,
+        target: &NodeTextureState,
+        load: wgpu::LoadOp<wgpu::Color>,
+    ) -> wgpu::RenderPass<'a> {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: LABEL,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+                view: target.view(),
+                resolve_target: None,
+            })],
+            // TODO: depth stencil attachments
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        })
+    }
+
     fn input_textures_bg(
         &self,
         wgpu_ctx: &Arc<WgpuCtx>,
         textures: &[Option<&NodeTexture>],
+        backdrop: Option<&NodeTextureState>,
     ) -> Vec<wgpu::BindGroup> {
+        let backdrop_view = backdrop
+            .map(|state| state.view())
+            .unwrap_or(wgpu_ctx.default_empty_view());
         textures
             .iter()
             .map(|texture| {
@@ -158,14 +211,38 @@ impl LayoutShader {
                 wgpu_ctx
                     .device
                     .create_bind_group(&wgpu::BindGroupDescriptor {
-                        layout: &wgpu_ctx.format.single_texture_layout,
+                        layout: &self.textures_bind_group_layout,
                         label: LABEL,
-                        entries: &[wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::TextureView(view),
-                        }],
+                        entries: &[
+                            wgpu::BindGroupEntry {
+                                binding: 0,
+                                resource: wgpu::BindingResource::TextureView(view),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 1,
+                                resource: wgpu::BindingResource::TextureView(backdrop_view),
+                            },
+                        ],
                     })
             })
             .collect()
     }
 }
+
+/// Layout for a texture of a rendered layout and a copy of the output texture.
+fn textures_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+        binding,
+        count: None,
+        visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+        ty: wgpu::BindingType::Texture {
+            multisampled: false,
+            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+            view_dimension: wgpu::TextureViewDimension::D2,
+        },
+    };
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Layout textures bind group layout"),
+        entries: &[texture_entry(0), texture_entry(1)],
+    })
+}
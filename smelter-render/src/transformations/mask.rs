@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use crate::{
+    scene::{MaskComponentParams, MaskMode},
+    state::{RenderCtx, node_texture::NodeTexture},
+    wgpu::{
+        WgpuCtx,
+        common_pipeline::{self, Sampler},
+    },
+};
+
+const LABEL: Option<&str> = Some("Mask");
+
+/// Renders content texture with a transparency defined by a mask texture. Both textures
+/// are stretched to the size of the output.
+#[derive(Debug)]
+pub(crate) struct MaskPipeline {
+    pipeline: wgpu::RenderPipeline,
+    sampler: Sampler,
+}
+
+impl MaskPipeline {
+    pub fn new(ctx: &WgpuCtx) -> Self {
+        let shader_module = ctx
+            .device
+            .create_shader_module(wgpu::include_wgsl!("./mask/apply_mask.wgsl"));
+        let sampler = Sampler::new(&ctx.device);
+
+        let pipeline_layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: LABEL,
+                bind_group_layouts: &[
+                    &ctx.format.single_texture_layout,
+                    &ctx.format.single_texture_layout,
+                    &sampler.bind_group_layout,
+                ],
+                push_constant_ranges: &[wgpu::PushConstantRange {
+                    stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    range: 0..8,
+                }],
+            });
+
+        let pipeline = common_pipeline::create_render_pipeline(
+            "Mask",
+            &ctx.device,
+            &pipeline_layout,
+            &shader_module,
+            ctx.default_view_format(),
+        );
+
+        Self { pipeline, sampler }
+    }
+}
+
+pub struct MaskNode {
+    pipeline: Arc<MaskPipeline>,
+    params: MaskComponentParams,
+}
+
+impl MaskNode {
+    pub(crate) fn new(ctx: &RenderCtx, params: MaskComponentParams) -> Self {
+        Self {
+            pipeline: ctx.renderers.mask.clone(),
+            params,
+        }
+    }
+
+    /// First source is the content, the second one is the mask.
+    pub fn render(&self, ctx: &WgpuCtx, sources: &[&NodeTexture], target: &mut NodeTexture) {
+        let target = target.ensure_size(ctx, self.params.size.into());
+        let texture_bg = |source: Option<&&NodeTexture>| {
+            let view = source
+                .and_then(|texture| texture.state())
+                .map(|state| state.view())
+                .unwrap_or_else(|| ctx.default_empty_view());
+            ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: LABEL,
+                layout: &ctx.format.single_texture_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(view),
+                }],
+            })
+        };
+        let content_bg = texture_bg(sources.first());
+        let mask_bg = texture_bg(sources.get(1));
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: LABEL });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: LABEL,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    view: target.view(),
+                    resolve_target: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(&self.pipeline.pipeline);
+            render_pass.set_push_constants(
+                wgpu::ShaderStages::VERTEX_FRAGMENT,
+                0,
+                &self.mask_info_bytes(),
+            );
+            render_pass.set_bind_group(0, &content_bg, &[]);
+            render_pass.set_bind_group(1, &mask_bg, &[]);
+            render_pass.set_bind_group(2, &self.pipeline.sampler.bind_group, &[]);
+
+            ctx.plane.draw(&mut render_pass);
+        }
+        ctx.queue.submit(Some(encoder.finish()));
+    }
+
+    fn mask_info_bytes(&self) -> [u8; 8] {
+        let mode: u32 = match self.params.mode {
+            MaskMode::Alpha => 0,
+            MaskMode::Luminance => 1,
+        };
+        let invert = self.params.invert as u32;
+
+        let mut result = [0u8; 8];
+        result[0..4].copy_from_slice(&mode.to_le_bytes());
+        result[4..8].copy_from_slice(&invert.to_le_bytes());
+        result
+    }
+}
@@ -0,0 +1,53 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+}
+
+struct MaskInfo {
+    // 0 - alpha, 1 - luminance
+    mode: u32,
+    // 1 if mask should be inverted
+    invert: u32,
+}
+
+@group(0) @binding(0) var content_texture: texture_2d<f32>;
+@group(1) @binding(0) var mask_texture: texture_2d<f32>;
+@group(2) @binding(0) var sampler_: sampler;
+
+var<push_constant> mask_info: MaskInfo;
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+
+    output.position = vec4(input.position, 1.0);
+    output.tex_coords = input.tex_coords;
+
+    return output;
+}
+
+fn mask_value(coords: vec2<f32>) -> f32 {
+    let mask_color = textureSample(mask_texture, sampler_, coords);
+
+    var value = mask_color.a;
+    if (mask_info.mode == 1u) {
+        // Colors are premultiplied, so transparent parts of a mask are also
+        // treated as black.
+        value = dot(mask_color.rgb, vec3(0.2126, 0.7152, 0.0722));
+    }
+    if (mask_info.invert == 1u) {
+        value = 1.0 - value;
+    }
+    return clamp(value, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    // Content is premultiplied, so all channels are scaled.
+    return textureSample(content_texture, sampler_, input.tex_coords) * mask_value(input.tex_coords);
+}
@@ -1,7 +1,9 @@
 pub mod image;
 pub mod layout;
+pub mod mask;
 pub mod shader;
 pub mod text_renderer;
+pub mod video_clip;
 
 #[cfg(feature = "web-renderer")]
 pub mod web_renderer;
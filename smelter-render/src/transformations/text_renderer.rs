@@ -7,7 +7,7 @@ use std::{
 use glyphon::{
     AttrsOwned, Buffer, Cache, Color, FontSystem, Metrics, Shaping, SwashCache, TextArea,
     TextAtlas, TextBounds,
-    cosmic_text::FontFeatures,
+    cosmic_text::{FontFeatures, LetterSpacing},
     fontdb::{Database, Source},
 };
 use tracing::warn;
@@ -17,7 +17,7 @@ use wgpu::{
 };
 
 use crate::{
-    Resolution,
+    MAX_NODE_RESOLUTION, Resolution,
     scene::{
         HorizontalAlign, RGBAColor, TextComponent, TextDimensions, TextStyle, TextWeight, TextWrap,
     },
@@ -25,11 +25,23 @@ use crate::{
     wgpu::{WgpuCtx, utils::convert_to_shader_color},
 };
 
+use self::effects::{TextEffects, TextEffectsPipeline, TextPadding};
+
+mod effects;
+
 #[derive(Debug, Clone)]
 pub(crate) struct TextRenderParams {
     pub(crate) buffer: TextBuffer,
     pub(crate) resolution: Resolution,
     pub(crate) background_color: RGBAColor,
+    pub(crate) layout: TextLayout,
+}
+
+/// Placement of the text buffer inside the texture and effects applied on it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TextLayout {
+    padding: TextPadding,
+    effects: Option<TextEffects>,
 }
 
 #[derive(Clone)]
@@ -54,6 +66,9 @@ pub(crate) struct TextRendererNode {
     buffer: TextBuffer,
     resolution: Resolution,
     background_color: wgpu::Color,
+    layout: TextLayout,
+    /// Text without effects, used only if outline or shadow is defined.
+    intermediate_texture: NodeTexture,
     was_rendered: bool,
 }
 
@@ -65,6 +80,8 @@ impl TextRendererNode {
             buffer: params.buffer,
             resolution: params.resolution,
             background_color,
+            layout: params.layout,
+            intermediate_texture: NodeTexture::new(),
             was_rendered: false,
         }
     }
@@ -115,8 +132,8 @@ impl TextRendererNode {
                 &viewport,
                 [TextArea {
                     buffer: &self.buffer.0,
-                    left: 0 as f32,
-                    top: 0 as f32,
+                    left: self.layout.padding.left,
+                    top: self.layout.padding.top,
                     scale: 1.0,
                     bounds: TextBounds {
                         left: 0,
@@ -139,8 +156,14 @@ impl TextRendererNode {
                     label: Some("Text renderer encoder"),
                 });
 
-        let target_state = target.ensure_size(renderer_ctx.wgpu_ctx, self.resolution);
-        let view = &target_state.view();
+        // With effects, text is rendered on a transparent background first and
+        // then composed with outline, shadow and background into the target.
+        let (text_target, clear_color) = match self.layout.effects {
+            Some(_) => (&mut self.intermediate_texture, wgpu::Color::TRANSPARENT),
+            None => (&mut *target, self.background_color),
+        };
+        let text_target_state = text_target.ensure_size(renderer_ctx.wgpu_ctx, self.resolution);
+        let view = &text_target_state.view();
         {
             let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
                 label: None,
@@ -148,7 +171,7 @@ impl TextRendererNode {
                     view,
                     resolve_target: None,
                     ops: Operations {
-                        load: LoadOp::Clear(self.background_color),
+                        load: LoadOp::Clear(clear_color),
                         store: wgpu::StoreOp::Store,
                     },
                 })],
@@ -161,42 +184,35 @@ impl TextRendererNode {
         }
 
         renderer_ctx.wgpu_ctx.queue.submit(Some(encoder.finish()));
+
+        if let (Some(effects), Some(text_state)) =
+            (self.layout.effects, self.intermediate_texture.state())
+        {
+            let target_state = target.ensure_size(renderer_ctx.wgpu_ctx, self.resolution);
+            renderer_ctx.text_renderer_ctx.effects_pipeline.render(
+                renderer_ctx.wgpu_ctx,
+                effects,
+                self.background_color,
+                text_state,
+                target_state,
+            );
+        }
         self.was_rendered = true;
     }
 }
 
 pub(crate) struct TextParams {
-    content: Arc<str>,
+    spans: Vec<(Arc<str>, AttrsOwned)>,
     attributes: AttrsOwned,
     font_size: f32,
     line_height: f32,
     align: glyphon::cosmic_text::Align,
     wrap: glyphon::cosmic_text::Wrap,
+    effects: Option<TextEffects>,
 }
 
 impl From<&TextComponent> for TextParams {
     fn from(text: &TextComponent) -> Self {
-        let RGBAColor(r, g, b, a) = text.color;
-        let color = glyphon::Color::rgba(r, g, b, a);
-
-        let family = glyphon::FamilyOwned::Name(text.font_family.clone().into());
-
-        let style = match text.style {
-            TextStyle::Normal => glyphon::Style::Normal,
-            TextStyle::Italic => glyphon::Style::Italic,
-            TextStyle::Oblique => glyphon::Style::Oblique,
-        };
-        let weight = match text.weight {
-            TextWeight::Thin => glyphon::Weight::THIN,
-            TextWeight::ExtraLight => glyphon::Weight::EXTRA_LIGHT,
-            TextWeight::Light => glyphon::Weight::LIGHT,
-            TextWeight::Normal => glyphon::Weight::NORMAL,
-            TextWeight::Medium => glyphon::Weight::MEDIUM,
-            TextWeight::SemiBold => glyphon::Weight::SEMIBOLD,
-            TextWeight::Bold => glyphon::Weight::BOLD,
-            TextWeight::ExtraBold => glyphon::Weight::EXTRA_BOLD,
-            TextWeight::Black => glyphon::Weight::BLACK,
-        };
         let wrap = match text.wrap {
             TextWrap::None => glyphon::cosmic_text::Wrap::None,
             TextWrap::Glyph => glyphon::cosmic_text::Wrap::Glyph,
@@ -208,37 +224,91 @@ impl From<&TextComponent> for TextParams {
             HorizontalAlign::Justified => glyphon::cosmic_text::Align::Justified,
             HorizontalAlign::Center => glyphon::cosmic_text::Align::Center,
         };
+        // cosmic-text expects letter spacing in EM units
+        let letter_spacing = match text.letter_spacing != 0.0 && text.font_size > 0.0 {
+            true => Some(LetterSpacing(text.letter_spacing / text.font_size)),
+            false => None,
+        };
+
+        let attributes =
+            |color: &RGBAColor, font_family: &Arc<str>, style: &TextStyle, weight: &TextWeight| {
+                let RGBAColor(r, g, b, a) = *color;
+                glyphon::AttrsOwned {
+                    color_opt: Some(glyphon::Color::rgba(r, g, b, a)),
+                    family_owned: glyphon::FamilyOwned::Name(font_family.clone().into()),
+                    stretch: Default::default(),
+                    style: text_style(style),
+                    weight: text_weight(weight),
+                    metadata: Default::default(),
+                    cache_key_flags: glyphon::cosmic_text::CacheKeyFlags::empty(),
+                    metrics_opt: None,
+                    font_features: FontFeatures::default(),
+                    letter_spacing_opt: letter_spacing,
+                }
+            };
+
+        let spans = text
+            .spans
+            .iter()
+            .map(|span| {
+                let attributes = attributes(
+                    span.color.as_ref().unwrap_or(&text.color),
+                    span.font_family.as_ref().unwrap_or(&text.font_family),
+                    span.style.as_ref().unwrap_or(&text.style),
+                    span.weight.as_ref().unwrap_or(&text.weight),
+                );
+                (span.text.clone(), attributes)
+            })
+            .collect();
+
+        let effects = match (text.outline, text.shadow) {
+            (None, None) => None,
+            (outline, shadow) => Some(TextEffects { outline, shadow }),
+        };
 
         Self {
-            attributes: glyphon::AttrsOwned {
-                color_opt: Some(color),
-                family_owned: family,
-                stretch: Default::default(),
-                style,
-                weight,
-                metadata: Default::default(),
-                cache_key_flags: glyphon::cosmic_text::CacheKeyFlags::empty(),
-                metrics_opt: None,
-                font_features: FontFeatures::default(),
-                letter_spacing_opt: None,
-            },
-            content: text.text.clone(),
+            spans,
+            attributes: attributes(&text.color, &text.font_family, &text.style, &text.weight),
             font_size: text.font_size,
             line_height: text.line_height,
             align,
             wrap,
+            effects,
         }
     }
 }
 
+fn text_style(style: &TextStyle) -> glyphon::Style {
+    match style {
+        TextStyle::Normal => glyphon::Style::Normal,
+        TextStyle::Italic => glyphon::Style::Italic,
+        TextStyle::Oblique => glyphon::Style::Oblique,
+    }
+}
+
+fn text_weight(weight: &TextWeight) -> glyphon::Weight {
+    match weight {
+        TextWeight::Thin => glyphon::Weight::THIN,
+        TextWeight::ExtraLight => glyphon::Weight::EXTRA_LIGHT,
+        TextWeight::Light => glyphon::Weight::LIGHT,
+        TextWeight::Normal => glyphon::Weight::NORMAL,
+        TextWeight::Medium => glyphon::Weight::MEDIUM,
+        TextWeight::SemiBold => glyphon::Weight::SEMIBOLD,
+        TextWeight::Bold => glyphon::Weight::BOLD,
+        TextWeight::ExtraBold => glyphon::Weight::EXTRA_BOLD,
+        TextWeight::Black => glyphon::Weight::BLACK,
+    }
+}
+
 pub struct TextRendererCtx {
     font_system: Mutex<FontSystem>,
     swash_cache: Mutex<SwashCache>,
     cache: Mutex<Cache>,
+    effects_pipeline: TextEffectsPipeline,
 }
 
 impl TextRendererCtx {
-    pub(crate) fn new(device: &wgpu::Device, load_system_fonts: bool) -> Self {
+    pub(crate) fn new(wgpu_ctx: &WgpuCtx, load_system_fonts: bool) -> Self {
         let mut font_system = if load_system_fonts {
             FontSystem::new()
         } else {
@@ -266,7 +336,8 @@ impl TextRendererCtx {
         Self {
             font_system: Mutex::new(font_system),
             swash_cache: Mutex::new(SwashCache::new()),
-            cache: Mutex::new(Cache::new(device)),
+            cache: Mutex::new(Cache::new(&wgpu_ctx.device)),
+            effects_pipeline: TextEffectsPipeline::new(wgpu_ctx),
         }
     }
 
@@ -281,21 +352,38 @@ impl TextRendererCtx {
         &self,
         text_params: TextParams,
         text_resolution: TextDimensions,
-    ) -> (TextBuffer, Resolution) {
+    ) -> (TextBuffer, Resolution, TextLayout) {
         let font_system = &mut self.font_system.lock().unwrap();
         let mut buffer = Buffer::new(
             font_system,
             Metrics::new(text_params.font_size, text_params.line_height),
         );
 
-        buffer.set_text(
+        buffer.set_rich_text(
             font_system,
-            &text_params.content,
+            text_params
+                .spans
+                .iter()
+                .map(|(text, attributes)| (text.as_ref(), attributes.as_attrs())),
             &text_params.attributes.as_attrs(),
             Shaping::Advanced,
+            None,
         );
         buffer.set_wrap(font_system, text_params.wrap);
 
+        // Outline and shadow are rendered outside of the text bounds,
+        // so the buffer is smaller than the texture.
+        let padding = text_params
+            .effects
+            .map(|effects| effects.padding())
+            .unwrap_or_default();
+        let buffer_size = |width: f32, height: f32| {
+            (
+                f32::max(width - padding.horizontal(), 0.0),
+                f32::max(height - padding.vertical(), 0.0),
+            )
+        };
+
         let texture_size = match text_resolution {
             TextDimensions::Fixed { width, height } => Resolution {
                 width: width as usize,
@@ -305,16 +393,23 @@ impl TextRendererCtx {
                 max_width,
                 max_height,
             } => {
+                let (max_width, max_height) = buffer_size(max_width, max_height);
                 buffer.set_size(font_system, Some(max_width), Some(max_height));
                 buffer.shape_until_scroll(font_system, false);
-                Self::get_text_resolution(
+                let text_size = Self::get_text_resolution(
                     buffer.lines.iter(),
                     text_params.line_height,
                     text_params.font_size,
-                )
+                );
+
+                Resolution {
+                    width: (text_size.width as f32 + padding.horizontal()) as usize,
+                    height: (text_size.height as f32 + padding.vertical()) as usize,
+                }
             }
             TextDimensions::FittedColumn { width, max_height } => {
-                buffer.set_size(font_system, Some(width), Some(max_height));
+                let (buffer_width, max_height) = buffer_size(width, max_height);
+                buffer.set_size(font_system, Some(buffer_width), Some(max_height));
                 buffer.shape_until_scroll(font_system, false);
                 let text_size = Self::get_text_resolution(
                     buffer.lines.iter(),
@@ -324,22 +419,33 @@ impl TextRendererCtx {
 
                 Resolution {
                     width: width as usize,
-                    height: text_size.height,
+                    height: (text_size.height as f32 + padding.vertical()) as usize,
                 }
             }
         };
+        // Padding of outline and shadow can make the texture larger than the layout size.
+        let texture_size = Resolution {
+            width: usize::min(texture_size.width, MAX_NODE_RESOLUTION.width),
+            height: usize::min(texture_size.height, MAX_NODE_RESOLUTION.height),
+        };
 
+        let (buffer_width, buffer_height) =
+            buffer_size(texture_size.width as f32, texture_size.height as f32);
         buffer.set_size(
             font_system,
-            Some(texture_size.width as f32),
-            Some(texture_size.height as f32 + text_params.line_height),
+            Some(buffer_width),
+            Some(buffer_height + text_params.line_height),
         );
         for line in &mut buffer.lines {
             line.set_align(Some(text_params.align));
         }
         buffer.shape_until_scroll(font_system, false);
 
-        (TextBuffer(buffer.into()), texture_size)
+        let layout = TextLayout {
+            padding,
+            effects: text_params.effects,
+        };
+        (TextBuffer(buffer.into()), texture_size, layout)
     }
 
     fn get_text_resolution<'a, I: Iterator<Item = &'a glyphon::BufferLine>>(
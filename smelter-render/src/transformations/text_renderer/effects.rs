@@ -0,0 +1,292 @@
+use crate::{
+    Resolution,
+    scene::{RGBAColor, TextOutline, TextShadow},
+    state::node_texture::NodeTextureState,
+    wgpu::{
+        WgpuCtx,
+        common_pipeline::{self, Sampler},
+        utils::convert_to_shader_color,
+    },
+};
+
+const LABEL: Option<&str> = Some("Text effects");
+
+/// Outlines wider than that are clamped, every additional pixel
+/// significantly increases the number of samples in the shader.
+const MAX_OUTLINE_WIDTH: f32 = 32.0;
+const MAX_SHADOW_BLUR_RADIUS: f32 = 64.0;
+/// Offset is added to the texture size, so it is clamped to keep the texture small.
+const MAX_SHADOW_OFFSET: f32 = 500.0;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TextEffects {
+    pub outline: Option<TextOutline>,
+    pub shadow: Option<TextShadow>,
+}
+
+/// Space around the text that is needed to fit outline and shadow.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct TextPadding {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl TextPadding {
+    pub fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+
+    pub fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+}
+
+impl TextEffects {
+    pub fn padding(&self) -> TextPadding {
+        let outline_width = self.outline_width();
+        let Some(shadow) = self.shadow else {
+            return TextPadding {
+                left: outline_width.ceil(),
+                top: outline_width.ceil(),
+                right: outline_width.ceil(),
+                bottom: outline_width.ceil(),
+            };
+        };
+        let blur_radius = self.shadow_blur_radius();
+        let (offset_x, offset_y) = Self::shadow_offset(shadow);
+        TextPadding {
+            left: f32::max(outline_width, blur_radius - offset_x).ceil(),
+            top: f32::max(outline_width, blur_radius - offset_y).ceil(),
+            right: f32::max(outline_width, blur_radius + offset_x).ceil(),
+            bottom: f32::max(outline_width, blur_radius + offset_y).ceil(),
+        }
+    }
+
+    fn outline_width(&self) -> f32 {
+        self.outline
+            .map(|outline| clamp(outline.width, 0.0, MAX_OUTLINE_WIDTH))
+            .unwrap_or(0.0)
+    }
+
+    fn shadow_blur_radius(&self) -> f32 {
+        self.shadow
+            .map(|shadow| clamp(shadow.blur_radius, 0.0, MAX_SHADOW_BLUR_RADIUS))
+            .unwrap_or(0.0)
+    }
+
+    fn shadow_offset(shadow: TextShadow) -> (f32, f32) {
+        (
+            clamp(shadow.offset_x, -MAX_SHADOW_OFFSET, MAX_SHADOW_OFFSET),
+            clamp(shadow.offset_y, -MAX_SHADOW_OFFSET, MAX_SHADOW_OFFSET),
+        )
+    }
+
+    fn to_bytes(self, ctx: &WgpuCtx, resolution: Resolution) -> [u8; 64] {
+        let transparent = RGBAColor(0, 0, 0, 0);
+        let outline_color = self
+            .outline
+            .map(|outline| outline.color)
+            .unwrap_or(transparent);
+        let shadow_color = self
+            .shadow
+            .map(|shadow| shadow.color)
+            .unwrap_or(transparent);
+        let (offset_x, offset_y) = self.shadow.map(Self::shadow_offset).unwrap_or((0.0, 0.0));
+
+        let values: [f32; 16] = {
+            let [or, og, ob, oa] = convert_to_shader_color(ctx, &outline_color);
+            let [sr, sg, sb, sa] = convert_to_shader_color(ctx, &shadow_color);
+            [
+                or as f32,
+                og as f32,
+                ob as f32,
+                oa as f32,
+                sr as f32,
+                sg as f32,
+                sb as f32,
+                sa as f32,
+                resolution.width as f32,
+                resolution.height as f32,
+                offset_x,
+                offset_y,
+                self.outline_width(),
+                self.shadow_blur_radius(),
+                0.0,
+                0.0,
+            ]
+        };
+
+        let mut result = [0u8; 64];
+        for (index, value) in values.iter().enumerate() {
+            result[index * 4..(index + 1) * 4].copy_from_slice(&value.to_le_bytes());
+        }
+        result
+    }
+}
+
+/// Composes text rendered to an intermediate texture with its outline and shadow.
+/// Both effects are calculated based on the alpha channel, so they also work for
+/// color glyphs e.g. emoji.
+#[derive(Debug)]
+pub(crate) struct TextEffectsPipeline {
+    pipeline: wgpu::RenderPipeline,
+    sampler: Sampler,
+}
+
+impl TextEffectsPipeline {
+    pub fn new(ctx: &WgpuCtx) -> Self {
+        let shader_module = ctx
+            .device
+            .create_shader_module(wgpu::include_wgsl!("./effects.wgsl"));
+        let sampler = Sampler::new(&ctx.device);
+
+        let pipeline_layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: LABEL,
+                bind_group_layouts: &[
+                    &ctx.format.single_texture_layout,
+                    &sampler.bind_group_layout,
+                ],
+                push_constant_ranges: &[wgpu::PushConstantRange {
+                    stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    range: 0..64,
+                }],
+            });
+
+        let pipeline = common_pipeline::create_render_pipeline(
+            "Text effects",
+            &ctx.device,
+            &pipeline_layout,
+            &shader_module,
+            ctx.default_view_format(),
+        );
+
+        Self { pipeline, sampler }
+    }
+
+    pub fn render(
+        &self,
+        ctx: &WgpuCtx,
+        effects: TextEffects,
+        background_color: wgpu::Color,
+        src: &NodeTextureState,
+        dst: &NodeTextureState,
+    ) {
+        let src_bg = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: LABEL,
+            layout: &ctx.format.single_texture_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(src.view()),
+            }],
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: LABEL });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: LABEL,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(background_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    view: dst.view(),
+                    resolve_target: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_push_constants(
+                wgpu::ShaderStages::VERTEX_FRAGMENT,
+                0,
+                &effects.to_bytes(ctx, src.resolution()),
+            );
+            render_pass.set_bind_group(0, &src_bg, &[]);
+            render_pass.set_bind_group(1, &self.sampler.bind_group, &[]);
+
+            ctx.plane.draw(&mut render_pass);
+        }
+        ctx.queue.submit(Some(encoder.finish()));
+    }
+}
+
+/// Same as [`f32::clamp`], but NaN is treated as `0.0`.
+fn clamp(value: f32, min: f32, max: f32) -> f32 {
+    match value.is_nan() {
+        true => 0.0,
+        false => value.clamp(min, max),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: RGBAColor = RGBAColor(0, 0, 0, 255);
+
+    fn padding(outline: Option<TextOutline>, shadow: Option<TextShadow>) -> [f32; 4] {
+        let TextPadding {
+            left,
+            top,
+            right,
+            bottom,
+        } = TextEffects { outline, shadow }.padding();
+        [left, top, right, bottom]
+    }
+
+    fn outline(width: f32) -> TextOutline {
+        TextOutline {
+            width,
+            color: BLACK,
+        }
+    }
+
+    #[test]
+    fn test_outline_padding() {
+        assert_eq!(padding(None, None), [0.0; 4]);
+        assert_eq!(padding(Some(outline(2.5)), None), [3.0; 4]);
+        assert_eq!(padding(Some(outline(100.0)), None), [MAX_OUTLINE_WIDTH; 4]);
+        assert_eq!(padding(Some(outline(-5.0)), None), [0.0; 4]);
+    }
+
+    #[test]
+    fn test_shadow_padding() {
+        let shadow = TextShadow {
+            offset_x: 4.0,
+            offset_y: -2.0,
+            blur_radius: 3.0,
+            color: BLACK,
+        };
+        assert_eq!(padding(None, Some(shadow)), [0.0, 5.0, 7.0, 1.0]);
+        // Outline is drawn around the text, so it needs space on every side.
+        assert_eq!(
+            padding(Some(outline(2.0)), Some(shadow)),
+            [2.0, 5.0, 7.0, 2.0]
+        );
+
+        let shadow = TextShadow {
+            blur_radius: 1000.0,
+            ..shadow
+        };
+        assert_eq!(padding(None, Some(shadow)), [60.0, 66.0, 68.0, 62.0]);
+
+        let shadow = TextShadow {
+            offset_x: 1e9,
+            offset_y: f32::NAN,
+            blur_radius: f32::NAN,
+            color: BLACK,
+        };
+        assert_eq!(
+            padding(None, Some(shadow)),
+            [0.0, 0.0, MAX_SHADOW_OFFSET, 0.0]
+        );
+    }
+}
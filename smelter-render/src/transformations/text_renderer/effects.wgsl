@@ -0,0 +1,112 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+}
+
+struct EffectsInfo {
+    // premultiplied
+    outline_color: vec4<f32>,
+    // premultiplied
+    shadow_color: vec4<f32>,
+    texture_size: vec2<f32>,
+    // in pixels
+    shadow_offset: vec2<f32>,
+    // in pixels
+    outline_width: f32,
+    // in pixels
+    shadow_blur_radius: f32,
+    _padding: vec2<f32>,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(1) @binding(0) var sampler_: sampler;
+
+var<push_constant> effects_info: EffectsInfo;
+
+const PI: f32 = 3.1415926535897932384626433832795;
+const MAX_BLUR_STEPS: f32 = 8.0;
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+
+    output.position = vec4(input.position, 1.0);
+    output.tex_coords = input.tex_coords;
+
+    return output;
+}
+
+fn alpha_at(coords: vec2<f32>) -> f32 {
+    // Sampler clamps to edge, but there is no text outside of the texture.
+    if (any(coords < vec2(0.0)) || any(coords > vec2(1.0))) {
+        return 0.0;
+    }
+    return textureSampleLevel(texture, sampler_, coords, 0.0).a;
+}
+
+// Maximum alpha in a circle with a provided radius.
+fn outline_alpha(coords: vec2<f32>) -> f32 {
+    let pixel_size = 1.0 / effects_info.texture_size;
+    let width = effects_info.outline_width;
+
+    var alpha = alpha_at(coords);
+    let rings = u32(ceil(width));
+    for (var ring = 1u; ring <= rings; ring++) {
+        let radius = min(f32(ring), width);
+        let samples = max(8u, u32(ceil(2.0 * PI * radius)));
+        for (var i = 0u; i < samples; i++) {
+            let angle = 2.0 * PI * f32(i) / f32(samples);
+            let offset = vec2(cos(angle), sin(angle)) * radius;
+            alpha = max(alpha, alpha_at(coords + offset * pixel_size));
+        }
+    }
+    return alpha;
+}
+
+// Gaussian blur of alpha channel shifted by the shadow offset.
+fn shadow_alpha(coords: vec2<f32>) -> f32 {
+    let pixel_size = 1.0 / effects_info.texture_size;
+    let center = coords - effects_info.shadow_offset * pixel_size;
+    let radius = effects_info.shadow_blur_radius;
+    if (radius <= 0.0) {
+        return alpha_at(center);
+    }
+
+    let steps = i32(min(ceil(radius), MAX_BLUR_STEPS));
+    let step_size = radius / f32(steps);
+    let sigma = radius / 2.0;
+
+    var sum = 0.0;
+    var weights_sum = 0.0;
+    for (var x = -steps; x <= steps; x++) {
+        for (var y = -steps; y <= steps; y++) {
+            let offset = vec2(f32(x), f32(y)) * step_size;
+            let weight = exp(-dot(offset, offset) / (2.0 * sigma * sigma));
+            sum += weight * alpha_at(center + offset * pixel_size);
+            weights_sum += weight;
+        }
+    }
+    return sum / weights_sum;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    // Text is rendered on a transparent background, so color is already premultiplied.
+    let text_color = textureSampleLevel(texture, sampler_, input.tex_coords, 0.0);
+
+    var color = vec4(0.0);
+    if (effects_info.shadow_color.a > 0.0) {
+        color = effects_info.shadow_color * shadow_alpha(input.tex_coords);
+    }
+    if (effects_info.outline_width > 0.0 && effects_info.outline_color.a > 0.0) {
+        let outline_color = effects_info.outline_color * outline_alpha(input.tex_coords);
+        color = outline_color + color * (1.0 - outline_color.a);
+    }
+
+    return text_color + color * (1.0 - text_color.a);
+}
@@ -0,0 +1,173 @@
+use std::{fmt, time::Duration};
+
+use crate::{
+    Frame, Resolution,
+    scene::video_clip_component::VideoClipRenderParams,
+    state::{
+        RenderCtx,
+        input_texture::InputTexture,
+        node_texture::{NodeTexture, NodeTextureState},
+    },
+    wgpu::WgpuCtx,
+};
+
+/// Every frame of a clip is kept in GPU memory as an RGBA texture, so only short
+/// clips are supported. Limit in bytes, e.g. ~4 seconds of 1080p30 video.
+pub const MAX_VIDEO_CLIP_SIZE: usize = 1024 * 1024 * 1024;
+
+/// GPU memory in bytes required to store decoded frames of a clip.
+pub fn video_clip_size(frames: &[Frame]) -> usize {
+    frames
+        .iter()
+        .map(|frame| frame.resolution.width * frame.resolution.height * 4)
+        .sum()
+}
+
+#[derive(Debug, Clone)]
+pub struct VideoClipSpec {
+    /// Decoded frames of a clip. Timestamps are relative to the start of a file.
+    pub frames: Vec<Frame>,
+    /// Duration of a clip, after that time playback starts from the first frame.
+    pub duration: Duration,
+}
+
+pub struct VideoClip {
+    frames: Vec<VideoClipFrame>,
+    duration: Duration,
+    resolution: Resolution,
+}
+
+struct VideoClipFrame {
+    pts: Duration,
+    texture: NodeTexture,
+}
+
+impl VideoClip {
+    pub fn new(ctx: &WgpuCtx, spec: VideoClipSpec) -> Result<Self, VideoClipError> {
+        let mut frames = spec.frames;
+        if video_clip_size(&frames) > MAX_VIDEO_CLIP_SIZE {
+            return Err(VideoClipError::TooLarge);
+        }
+        frames.sort_by_key(|frame| frame.pts);
+
+        let Some(first_frame) = frames.first() else {
+            return Err(VideoClipError::NoFrames);
+        };
+        let resolution = first_frame.resolution;
+        let start_pts = first_frame.pts;
+        if !frames.iter().all(|frame| frame.resolution == resolution) {
+            return Err(VideoClipError::UnsupportedVariableResolution);
+        }
+
+        // Single input texture is reused, each frame is converted into
+        // its own RGBA texture right after upload.
+        let mut input_texture = InputTexture::new();
+        let frames = frames
+            .into_iter()
+            .map(|frame| {
+                let pts = frame.pts.saturating_sub(start_pts);
+                let mut texture = NodeTexture::new();
+                input_texture.upload(ctx, frame);
+                ctx.queue.submit([]);
+                input_texture.convert_to_node_texture(ctx, &mut texture);
+                VideoClipFrame { pts, texture }
+            })
+            .collect();
+
+        Ok(Self {
+            frames,
+            duration: Duration::max(spec.duration, Duration::from_nanos(1)),
+            resolution,
+        })
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    fn frame(&self, animation_pts: Option<Duration>) -> &VideoClipFrame {
+        match animation_pts {
+            Some(animation_pts) => {
+                let next_frame_index = self
+                    .frames
+                    .partition_point(|frame| frame.pts <= animation_pts);
+                &self.frames[next_frame_index.saturating_sub(1)]
+            }
+            // Hold the last frame after all loops were played.
+            None => self.frames.last().unwrap(),
+        }
+    }
+}
+
+impl fmt::Debug for VideoClip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VideoClip")
+            .field("frames", &self.frames.len())
+            .field("duration", &self.duration)
+            .field("resolution", &self.resolution)
+            .finish()
+    }
+}
+
+pub struct VideoClipNode {
+    params: VideoClipRenderParams,
+}
+
+impl VideoClipNode {
+    pub fn new(params: VideoClipRenderParams) -> Self {
+        Self { params }
+    }
+
+    pub fn render(&mut self, ctx: &mut RenderCtx, target: &mut NodeTexture, pts: Duration) {
+        let clip = &self.params.clip;
+        let animation_pts =
+            self.params
+                .playback
+                .animation_pts(self.params.start_pts, pts, clip.duration);
+        let Some(frame) = clip.frame(animation_pts).texture.state() else {
+            target.clear();
+            return;
+        };
+
+        let target = target.ensure_size(ctx.wgpu_ctx, self.params.resolution);
+        let wgpu_ctx = ctx.wgpu_ctx;
+        match frame {
+            NodeTextureState::GpuOptimized {
+                srgb_bind_group, ..
+            }
+            | NodeTextureState::WebGl {
+                srgb_bind_group, ..
+            } => {
+                wgpu_ctx.utils.srgb_rgba_add_premult_alpha.render(
+                    wgpu_ctx,
+                    srgb_bind_group,
+                    target.view(),
+                );
+            }
+            NodeTextureState::CpuOptimized {
+                linear_bind_group, ..
+            } => {
+                wgpu_ctx.utils.linear_rgba_add_premult_alpha.render(
+                    wgpu_ctx,
+                    linear_bind_group,
+                    target.view(),
+                );
+            }
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VideoClipError {
+    #[error(
+        "Video clip requires over {} MiB of GPU memory. Only short clips are currently supported.",
+        MAX_VIDEO_CLIP_SIZE / (1024 * 1024)
+    )]
+    TooLarge,
+
+    #[error("Video clip does not contain any frames.")]
+    NoFrames,
+
+    #[error("Failed to read video clip, variable resolution is not supported.")]
+    UnsupportedVariableResolution,
+}
@@ -22,6 +22,9 @@ pub struct Frame {
     pub data: FrameData,
     pub resolution: Resolution,
     pub pts: Duration,
+    /// For input frames it describes how `data` should be converted to RGB. Output frames
+    /// are always SDR, RGBA textures use BT.709 primaries with sRGB transfer.
+    pub color: ColorInfo,
 }
 
 #[derive(Debug, Clone)]
@@ -29,12 +32,91 @@ pub enum FrameData {
     PlanarYuv420(YuvPlanes),
     PlanarYuv422(YuvPlanes),
     PlanarYuv444(YuvPlanes),
+    /// Full range YUV 4:2:0 (`yuvj420p`), range from the frame color info is ignored.
     PlanarYuvJ420(YuvPlanes),
+    /// 10-bit planar YUV 4:2:0 (`yuv420p10le`). Each sample is stored as a little-endian
+    /// 16-bit value with data in the 10 least significant bits.
+    PlanarYuv420P10(YuvPlanes),
     InterleavedUyvy422(bytes::Bytes),
     InterleavedYuyv422(bytes::Bytes),
     Rgba8UnormWgpuTexture(Arc<wgpu::Texture>),
     Nv12WgpuTexture(Arc<wgpu::Texture>),
     Nv12(NvPlanes),
+    /// 10-bit semi-planar YUV 4:2:0 (`p010le`). Each sample is stored as a little-endian
+    /// 16-bit value with data in the 10 most significant bits.
+    P010(NvPlanes),
+}
+
+/// Color metadata of the frame data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ColorInfo {
+    pub matrix: ColorMatrix,
+    pub primaries: ColorPrimaries,
+    pub range: ColorRange,
+    pub transfer: ColorTransfer,
+}
+
+impl ColorInfo {
+    /// Color info for streams that do not signal it. SD resolutions are assumed
+    /// to be BT.601 and everything else BT.709.
+    pub fn from_resolution(resolution: Resolution) -> Self {
+        if resolution.height < 720 {
+            Self {
+                matrix: ColorMatrix::Bt601,
+                primaries: ColorPrimaries::Bt601,
+                ..Default::default()
+            }
+        } else {
+            Self::default()
+        }
+    }
+}
+
+/// Matrix used to convert between RGB and YUV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorMatrix {
+    Bt601,
+    #[default]
+    Bt709,
+    /// BT.2020 non-constant luminance.
+    Bt2020,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorPrimaries {
+    /// SMPTE 170M / BT.470BG.
+    Bt601,
+    #[default]
+    Bt709,
+    Bt2020,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorRange {
+    /// Y in range [16, 235], U and V in range [16, 240] (for 8-bit).
+    #[default]
+    Limited,
+    /// Y, U and V use the entire range of values.
+    Full,
+}
+
+/// Transfer function of the frame data. Compositing is done in 8-bit SDR, so HDR input
+/// frames (PQ and HLG) are tone-mapped to SDR with BT.709 primaries when uploaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorTransfer {
+    /// BT.709 (or sRGB for RGBA textures).
+    #[default]
+    Sdr,
+    /// SMPTE ST 2084 (PQ).
+    Pq,
+    /// ARIB STD-B67 (HLG).
+    Hlg,
+}
+
+impl ColorTransfer {
+    pub fn is_hdr(&self) -> bool {
+        matches!(self, ColorTransfer::Pq | ColorTransfer::Hlg)
+    }
 }
 
 #[derive(Clone)]
@@ -184,8 +266,37 @@ impl From<wgpu::Extent3d> for Resolution {
 
 #[derive(Debug, Clone, Copy)]
 pub enum OutputFrameFormat {
-    PlanarYuv420Bytes,
-    PlanarYuv422Bytes,
-    PlanarYuv444Bytes,
+    PlanarYuv420Bytes(OutputColorSpace),
+    PlanarYuv422Bytes(OutputColorSpace),
+    PlanarYuv444Bytes(OutputColorSpace),
+    /// 10-bit planar YUV 4:2:0 (`yuv420p10le`). Frames are converted from the 8-bit
+    /// composition, so they have at most 8 bits of precision per color channel.
+    PlanarYuv420P10Bytes(OutputColorSpace),
     RgbaWgpuTexture,
 }
+
+/// Color space of YUV frames produced for an output. Output frames are always SDR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct OutputColorSpace {
+    pub matrix: ColorMatrix,
+    pub range: ColorRange,
+}
+
+impl OutputColorSpace {
+    /// Scene is rendered with BT.709 primaries. It is converted to BT.2020 primaries for
+    /// the BT.2020 matrix, BT.601 output keeps BT.709 primaries (difference is negligible)
+    /// but is signaled as SMPTE 170M, as expected by SD receivers.
+    pub fn color_info(&self) -> ColorInfo {
+        let primaries = match self.matrix {
+            ColorMatrix::Bt601 => ColorPrimaries::Bt601,
+            ColorMatrix::Bt709 => ColorPrimaries::Bt709,
+            ColorMatrix::Bt2020 => ColorPrimaries::Bt2020,
+        };
+        ColorInfo {
+            matrix: self.matrix,
+            primaries,
+            range: self.range,
+            transfer: ColorTransfer::Sdr,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use interleaved_uyvy_to_rgba::InterleavedUyvy422ToRgbaConverter;
 use interleaved_yuyv_to_rgba::InterleavedYuyv422ToRgbaConverter;
 use nv12_to_rgba::Nv12ToRgbaConverter;
+use rgba_float_to_rgba::RgbaFloatToRgbaConverter;
+use yuv10_to_rgba_float::Yuv10ToRgbaFloatConverter;
 
 use self::{planar_yuv_to_rgba::PlanarYuvToRgbaConverter, rgba_to_yuv::RgbaToYuvConverter};
 
@@ -9,11 +11,16 @@ use super::{
     texture::{NV12Texture, PlanarYuvTextures},
 };
 
+mod color_params;
 mod interleaved_uyvy_to_rgba;
 mod interleaved_yuyv_to_rgba;
 mod nv12_to_rgba;
 mod planar_yuv_to_rgba;
+mod rgba_float_to_rgba;
 mod rgba_to_yuv;
+mod yuv10_to_rgba_float;
+
+pub use yuv10_to_rgba_float::Yuv10Layout;
 
 #[derive(Debug)]
 pub struct TextureFormat {
@@ -22,10 +29,13 @@ pub struct TextureFormat {
     pub interleaved_yuyv_to_rgba_linear: InterleavedYuyv422ToRgbaConverter,
     pub nv12_to_rgba_linear: Nv12ToRgbaConverter,
     pub rgba_to_yuv: RgbaToYuvConverter,
+    pub yuv10_to_rgba_float: Yuv10ToRgbaFloatConverter,
+    pub rgba_float_to_rgba_linear: RgbaFloatToRgbaConverter,
 
     pub single_texture_layout: wgpu::BindGroupLayout,
     pub planar_yuv_layout: wgpu::BindGroupLayout,
     pub nv12_layout: wgpu::BindGroupLayout,
+    pub yuv_uint_layout: wgpu::BindGroupLayout,
 }
 
 impl TextureFormat {
@@ -33,6 +43,7 @@ impl TextureFormat {
         let single_texture_layout = create_single_texture_bgl(device);
         let planar_yuv_layout = PlanarYuvTextures::new_bind_group_layout(device);
         let nv12_layout = NV12Texture::new_bind_group_layout(device);
+        let yuv_uint_layout = create_yuv_uint_bgl(device);
 
         let planar_yuv_to_rgba_linear = PlanarYuvToRgbaConverter::new(
             device,
@@ -55,6 +66,13 @@ impl TextureFormat {
 
         let rgba_to_yuv = RgbaToYuvConverter::new(device, &single_texture_layout);
 
+        let yuv10_to_rgba_float = Yuv10ToRgbaFloatConverter::new(device, &yuv_uint_layout);
+        let rgba_float_to_rgba_linear = RgbaFloatToRgbaConverter::new(
+            device,
+            &single_texture_layout,
+            wgpu::TextureFormat::Rgba8Unorm,
+        );
+
         Self {
             planar_yuv_to_rgba_linear,
             interleaved_uyvy_to_rgba_linear,
@@ -62,10 +80,13 @@ impl TextureFormat {
             nv12_to_rgba_linear,
 
             rgba_to_yuv,
+            yuv10_to_rgba_float,
+            rgba_float_to_rgba_linear,
 
             single_texture_layout,
             planar_yuv_layout,
             nv12_layout,
+            yuv_uint_layout,
         }
     }
 }
@@ -85,3 +106,22 @@ fn create_single_texture_bgl(device: &wgpu::Device) -> wgpu::BindGroupLayout {
         }],
     })
 }
+
+/// Layout for 10-bit YUV textures. Integer textures can't be sampled, so
+/// shaders read them with `textureLoad`.
+fn create_yuv_uint_bgl(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    let entry = |binding| wgpu::BindGroupLayoutEntry {
+        binding,
+        count: None,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Texture {
+            multisampled: false,
+            sample_type: wgpu::TextureSampleType::Uint,
+            view_dimension: wgpu::TextureViewDimension::D2,
+        },
+    };
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("10-bit YUV textures bind group layout"),
+        entries: &[entry(0), entry(1), entry(2)],
+    })
+}
@@ -0,0 +1,39 @@
+use crate::{ColorInfo, ColorMatrix, ColorRange};
+
+/// Push constants shared by shaders that convert between YUV and RGB. Layout has to
+/// match `YuvColorParams` struct defined in those shaders.
+#[repr(C)]
+#[derive(Debug, bytemuck::Pod, bytemuck::Zeroable, Clone, Copy)]
+pub(super) struct YuvColorParams {
+    /// 0 - BT.601, 1 - BT.709, 2 - BT.2020
+    matrix: u32,
+    /// 0 - limited range, 1 - full range
+    full_range: u32,
+}
+
+impl YuvColorParams {
+    pub const SIZE: u32 = std::mem::size_of::<YuvColorParams>() as u32;
+
+    pub fn new(matrix: ColorMatrix, range: ColorRange) -> Self {
+        let matrix = match matrix {
+            ColorMatrix::Bt601 => 0,
+            ColorMatrix::Bt709 => 1,
+            ColorMatrix::Bt2020 => 2,
+        };
+        let full_range = match range {
+            ColorRange::Limited => 0,
+            ColorRange::Full => 1,
+        };
+        Self { matrix, full_range }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+impl From<ColorInfo> for YuvColorParams {
+    fn from(color: ColorInfo) -> Self {
+        Self::new(color.matrix, color.range)
+    }
+}
@@ -1,6 +1,9 @@
-use crate::wgpu::common_pipeline::{PRIMITIVE_STATE, Sampler, Vertex};
+use crate::{
+    ColorInfo,
+    wgpu::common_pipeline::{PRIMITIVE_STATE, Sampler, Vertex},
+};
 
-use super::WgpuCtx;
+use super::{WgpuCtx, color_params::YuvColorParams};
 
 #[derive(Debug)]
 pub struct InterleavedUyvy422ToRgbaConverter {
@@ -21,7 +24,10 @@ impl InterleavedUyvy422ToRgbaConverter {
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Interleaved UYVY 4:2:2 to RGBA color converter render pipeline layout"),
             bind_group_layouts: &[yuv_textures_bind_group_layout, &sampler.bind_group_layout],
-            push_constant_ranges: &[],
+            push_constant_ranges: &[wgpu::PushConstantRange {
+                stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                range: 0..YuvColorParams::SIZE,
+            }],
         });
 
         let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
@@ -60,7 +66,13 @@ impl InterleavedUyvy422ToRgbaConverter {
         Self { pipeline, sampler }
     }
 
-    pub fn convert(&self, ctx: &WgpuCtx, src_bg: &wgpu::BindGroup, dst_view: &wgpu::TextureView) {
+    pub fn convert(
+        &self,
+        ctx: &WgpuCtx,
+        color: ColorInfo,
+        src_bg: &wgpu::BindGroup,
+        dst_view: &wgpu::TextureView,
+    ) {
         let mut encoder = ctx
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -84,6 +96,11 @@ impl InterleavedUyvy422ToRgbaConverter {
             });
 
             render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_push_constants(
+                wgpu::ShaderStages::VERTEX_FRAGMENT,
+                0,
+                YuvColorParams::from(color).bytes(),
+            );
             render_pass.set_bind_group(0, src_bg, &[]);
             render_pass.set_bind_group(1, &self.sampler.bind_group, &[]);
 
@@ -1,11 +1,14 @@
 use wgpu::ShaderStages;
 
-use crate::wgpu::{
-    common_pipeline::{PRIMITIVE_STATE, Sampler, Vertex},
-    texture::PlanarYuvVariant,
+use crate::{
+    ColorInfo, ColorRange,
+    wgpu::{
+        common_pipeline::{PRIMITIVE_STATE, Sampler, Vertex},
+        texture::PlanarYuvVariant,
+    },
 };
 
-use super::WgpuCtx;
+use super::{WgpuCtx, color_params::YuvColorParams};
 
 #[derive(Debug)]
 pub struct PlanarYuvToRgbaConverter {
@@ -28,7 +31,7 @@ impl PlanarYuvToRgbaConverter {
             bind_group_layouts: &[yuv_textures_bind_group_layout, &sampler.bind_group_layout],
             push_constant_ranges: &[wgpu::PushConstantRange {
                 stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                range: 0..YUVToRGBAPushConstants::push_constant_size(),
+                range: 0..YuvColorParams::SIZE,
             }],
         });
 
@@ -72,9 +75,15 @@ impl PlanarYuvToRgbaConverter {
         &self,
         ctx: &WgpuCtx,
         yuv_variant: PlanarYuvVariant,
+        color: ColorInfo,
         src_bg: &wgpu::BindGroup,
         dst_view: &wgpu::TextureView,
     ) {
+        let range = match yuv_variant {
+            PlanarYuvVariant::YUVJ420 => ColorRange::Full,
+            _ => color.range,
+        };
+
         let mut encoder = ctx
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -103,7 +112,7 @@ impl PlanarYuvToRgbaConverter {
             render_pass.set_push_constants(
                 ShaderStages::VERTEX_FRAGMENT,
                 0,
-                YUVToRGBAPushConstants::new(yuv_variant).push_constant(),
+                YuvColorParams::new(color.matrix, range).bytes(),
             );
 
             ctx.plane.draw(&mut render_pass);
@@ -112,32 +121,3 @@ impl PlanarYuvToRgbaConverter {
         ctx.queue.submit(Some(encoder.finish()));
     }
 }
-
-#[repr(C)]
-#[derive(Debug, bytemuck::Pod, bytemuck::Zeroable, Clone, Copy)]
-struct YUVToRGBAPushConstants {
-    pixel_format: u32,
-}
-
-impl YUVToRGBAPushConstants {
-    fn new(variant: PlanarYuvVariant) -> Self {
-        match variant {
-            PlanarYuvVariant::YUV420 => Self { pixel_format: 0 },
-            PlanarYuvVariant::YUV422 => Self { pixel_format: 0 },
-            PlanarYuvVariant::YUV444 => Self { pixel_format: 0 },
-            PlanarYuvVariant::YUVJ420 => Self { pixel_format: 1 },
-        }
-    }
-
-    fn push_constant_size() -> u32 {
-        let size = std::mem::size_of::<YUVToRGBAPushConstants>() as u32;
-        match size % 4 {
-            0 => size,
-            rest => size + (4 - rest),
-        }
-    }
-
-    fn push_constant(&self) -> &[u8] {
-        bytemuck::bytes_of(self)
-    }
-}
@@ -24,35 +24,59 @@ fn vs_main(input: VertexInput) -> VertexOutput {
 
 @group(1) @binding(0) var sampler_: sampler;
 
-struct PushConstantParams {
-    // 0 - pixel format without J (limited colorspace range)
-    // 1 - pixel format with J (full colorspace)
-    pixel_format: u32,
+struct YuvColorParams {
+    // 0 - BT.601, 1 - BT.709, 2 - BT.2020
+    matrix: u32,
+    // 0 - limited range, 1 - full range
+    full_range: u32,
 }
 
-var<push_constant> params: PushConstantParams;
+var<push_constant> color: YuvColorParams;
 
-@fragment
-fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
-    var y = textureSample(y_texture, sampler_, input.tex_coords).x;
-    var u = textureSample(u_texture, sampler_, input.tex_coords).x;
-    var v = textureSample(v_texture, sampler_, input.tex_coords).x;
+// YUV conversion from: https://en.wikipedia.org/w/index.php?title=YCbCr&section=8#ITU-R_BT.709_conversion
+// generalized for BT.601 and BT.2020 coefficients.
+fn yuv_to_rgb(y_sample: f32, u_sample: f32, v_sample: f32) -> vec3<f32> {
+    var y = y_sample;
+    var u = u_sample;
+    var v = v_sample;
 
-    // YUV conversion from: https://en.wikipedia.org/w/index.php?title=YCbCr&section=8#ITU-R_BT.709_conversion
-    // YUV values footroom needs to be removed for non J formats
-    // UV planes are in range (0, 1), but equation expects (-0.5, 0.5)
-
-    if params.pixel_format == 0u {
+    // YUV values footroom needs to be removed for limited range
+    if color.full_range == 0u {
         // (235 - 16) / (255 - 0) = (219 / 255) ~= .858
-        y = clamp((y - (16.0/255.0)) / 0.85882352941, 0.0, 1.0);
+        y = (y - (16.0/255.0)) / 0.85882352941;
         // (240 - 16) / (255 - 0) = (224 / 255) ~= .878
-        u = clamp((u - (16.0/255.0)) / 0.87843137254, 0.0, 1.0);
-        v = clamp((v - (16.0/255.0)) / 0.87843137254, 0.0, 1.0);
+        u = (u - (16.0/255.0)) / 0.87843137254;
+        v = (v - (16.0/255.0)) / 0.87843137254;
     }
 
-    let r = y + 1.5748 * (v - 0.5);
-    let g = y - 0.1873 * (u - 0.5) - 0.4681 * (v - 0.5);
-    let b = y + 1.8556 * (u - 0.5);
+    // UV planes are in range (0, 1), but equation expects (-0.5, 0.5)
+    y = clamp(y, 0.0, 1.0);
+    u = clamp(u, 0.0, 1.0) - 0.5;
+    v = clamp(v, 0.0, 1.0) - 0.5;
+
+    var kr = 0.2126;
+    var kb = 0.0722;
+    if color.matrix == 0u {
+        kr = 0.299;
+        kb = 0.114;
+    } else if color.matrix == 2u {
+        kr = 0.2627;
+        kb = 0.0593;
+    }
+    let kg = 1.0 - kr - kb;
+
+    let r = y + 2.0 * (1.0 - kr) * v;
+    let g = y - (2.0 * kb * (1.0 - kb) / kg) * u - (2.0 * kr * (1.0 - kr) / kg) * v;
+    let b = y + 2.0 * (1.0 - kb) * u;
+
+    return clamp(vec3<f32>(r, g, b), vec3(0.0), vec3(1.0));
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    var y = textureSample(y_texture, sampler_, input.tex_coords).x;
+    var u = textureSample(u_texture, sampler_, input.tex_coords).x;
+    var v = textureSample(v_texture, sampler_, input.tex_coords).x;
 
-    return vec4<f32>(clamp(r, 0.0, 1.0), clamp(g, 0.0, 1.0), clamp(b, 0.0, 1.0), 1.0);
+    return vec4<f32>(yuv_to_rgb(y, u, v), 1.0);
 }
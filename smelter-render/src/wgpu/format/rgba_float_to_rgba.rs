@@ -0,0 +1,119 @@
+use wgpu::ShaderStages;
+
+use crate::{
+    ColorInfo, ColorPrimaries,
+    wgpu::common_pipeline::{PRIMITIVE_STATE, Sampler, Vertex},
+};
+
+use super::WgpuCtx;
+
+/// Converts linear light RGB from a `Rgba16Float` texture into BT.709 values that can be
+/// stored in 8-bit textures. HDR content is tone mapped to the SDR range.
+#[derive(Debug)]
+pub struct RgbaFloatToRgbaConverter {
+    pipeline: wgpu::RenderPipeline,
+    sampler: Sampler,
+}
+
+impl RgbaFloatToRgbaConverter {
+    pub fn new(
+        device: &wgpu::Device,
+        single_texture_bind_group_layout: &wgpu::BindGroupLayout,
+        dst_view_format: wgpu::TextureFormat,
+    ) -> Self {
+        let shader_module =
+            device.create_shader_module(wgpu::include_wgsl!("rgba_float_to_rgba.wgsl"));
+        let sampler = Sampler::new(device);
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("RGBA float to RGBA tone mapping render pipeline layout"),
+            bind_group_layouts: &[single_texture_bind_group_layout, &sampler.bind_group_layout],
+            push_constant_ranges: &[wgpu::PushConstantRange {
+                stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                range: 0..8,
+            }],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("RGBA float to RGBA tone mapping render pipeline"),
+            layout: Some(&pipeline_layout),
+            primitive: PRIMITIVE_STATE,
+
+            vertex: wgpu::VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                buffers: &[Vertex::LAYOUT],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+
+            fragment: Some(wgpu::FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: dst_view_format,
+                    write_mask: wgpu::ColorWrites::all(),
+                    blend: None,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            depth_stencil: None,
+            cache: None,
+        });
+
+        Self { pipeline, sampler }
+    }
+
+    pub fn convert(
+        &self,
+        ctx: &WgpuCtx,
+        color: ColorInfo,
+        src_bg: &wgpu::BindGroup,
+        dst_view: &wgpu::TextureView,
+    ) {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("RGBA float to RGBA tone mapping encoder"),
+            });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("RGBA float to RGBA tone mapping render pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    view: dst_view,
+                    resolve_target: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, src_bg, &[]);
+            render_pass.set_bind_group(1, &self.sampler.bind_group, &[]);
+            render_pass.set_push_constants(
+                ShaderStages::VERTEX_FRAGMENT,
+                0,
+                bytemuck::bytes_of(&[
+                    (color.primaries == ColorPrimaries::Bt2020) as u32,
+                    color.transfer.is_hdr() as u32,
+                ]),
+            );
+
+            ctx.plane.draw(&mut render_pass);
+        }
+
+        ctx.queue.submit(Some(encoder.finish()));
+    }
+}
@@ -0,0 +1,73 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+
+    output.position = vec4(input.position, 1.0);
+    output.tex_coords = input.tex_coords;
+
+    return output;
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(1) @binding(0) var sampler_: sampler;
+
+struct PushConstantParams {
+    // 1 if source uses BT.2020 primaries
+    bt2020_primaries: u32,
+    // 1 if source is HDR (PQ or HLG) and needs tone mapping
+    is_hdr: u32,
+}
+
+var<push_constant> params: PushConstantParams;
+
+// Luminance (relative to reference white) above which highlights are compressed.
+const KNEE: f32 = 0.75;
+// 1000 nits mastering peak relative to 203 nits reference white.
+const PEAK: f32 = 4.926;
+
+// Extended Reinhard applied only above the knee, so SDR range content is unchanged.
+fn tone_map(luminance: f32) -> f32 {
+    if luminance <= KNEE {
+        return luminance;
+    }
+    let x = (luminance - KNEE) / (1.0 - KNEE);
+    let peak = (PEAK - KNEE) / (1.0 - KNEE);
+    let mapped = x * (1.0 + x / (peak * peak)) / (1.0 + x);
+    return KNEE + mapped * (1.0 - KNEE);
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    var rgb = textureSample(texture, sampler_, input.tex_coords).rgb;
+
+    if params.bt2020_primaries == 1u {
+        // BT.2020 to BT.709 primaries (ITU-R BT.2087)
+        rgb = vec3<f32>(
+            1.6605 * rgb.r - 0.5876 * rgb.g - 0.0728 * rgb.b,
+            -0.1246 * rgb.r + 1.1329 * rgb.g - 0.0083 * rgb.b,
+            -0.0182 * rgb.r - 0.1006 * rgb.g + 1.1187 * rgb.b,
+        );
+        rgb = max(rgb, vec3(0.0));
+    }
+
+    if params.is_hdr == 1u {
+        let luminance = dot(rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+        if luminance > 0.0 {
+            rgb = rgb * (tone_map(luminance) / luminance);
+        }
+    }
+
+    // BT.1886 inverse EOTF, output is stored "as if linear" like other YUV to RGBA converters.
+    let encoded = pow(clamp(rgb, vec3(0.0), vec3(1.0)), vec3(1.0 / 2.4));
+    return vec4<f32>(encoded, 1.0);
+}
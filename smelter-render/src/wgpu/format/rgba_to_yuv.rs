@@ -1,13 +1,18 @@
-use crate::wgpu::{
-    common_pipeline::{PRIMITIVE_STATE, Sampler, Vertex},
-    texture::PlanarYuvTextures,
+use crate::{
+    OutputColorSpace,
+    wgpu::{
+        common_pipeline::{PRIMITIVE_STATE, Sampler, Vertex},
+        texture::{PlanarYuvTextures, PlanarYuvVariant},
+    },
 };
 
-use super::WgpuCtx;
+use super::{WgpuCtx, color_params::YuvColorParams};
 
 #[derive(Debug)]
 pub struct RgbaToYuvConverter {
     pipeline: wgpu::RenderPipeline,
+    /// Writes 10-bit samples into `R16Uint` planes.
+    pipeline_10bit: wgpu::RenderPipeline,
     sampler: Sampler,
 }
 
@@ -23,29 +28,58 @@ impl RgbaToYuvConverter {
             bind_group_layouts: &[single_texture_bind_group_layout, &sampler.bind_group_layout],
             push_constant_ranges: &[wgpu::PushConstantRange {
                 stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                range: 0..4,
+                range: 0..(4 + YuvColorParams::SIZE),
             }],
         });
 
         let shader_module = device.create_shader_module(wgpu::include_wgsl!("rgba_to_yuv.wgsl"));
 
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        let pipeline = Self::new_pipeline(
+            device,
+            &pipeline_layout,
+            &shader_module,
+            "fs_main",
+            wgpu::TextureFormat::R8Unorm,
+        );
+        let pipeline_10bit = Self::new_pipeline(
+            device,
+            &pipeline_layout,
+            &shader_module,
+            "fs_main_10bit",
+            wgpu::TextureFormat::R16Uint,
+        );
+
+        Self {
+            pipeline,
+            pipeline_10bit,
+            sampler,
+        }
+    }
+
+    fn new_pipeline(
+        device: &wgpu::Device,
+        pipeline_layout: &wgpu::PipelineLayout,
+        shader_module: &wgpu::ShaderModule,
+        fragment_entry_point: &str,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::RenderPipeline {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
             label: Some("RGBA to YUV color converter pipeline"),
-            layout: Some(&pipeline_layout),
+            layout: Some(pipeline_layout),
             primitive: PRIMITIVE_STATE,
 
             vertex: wgpu::VertexState {
-                module: &shader_module,
+                module: shader_module,
                 entry_point: Some("vs_main"),
                 buffers: &[Vertex::LAYOUT],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
             },
 
             fragment: Some(wgpu::FragmentState {
-                module: &shader_module,
-                entry_point: Some("fs_main"),
+                module: shader_module,
+                entry_point: Some(fragment_entry_point),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::R8Unorm,
+                    format,
                     write_mask: wgpu::ColorWrites::all(),
                     blend: None,
                 })],
@@ -59,18 +93,28 @@ impl RgbaToYuvConverter {
             },
             multiview: None,
             cache: None,
-        });
-
-        Self { pipeline, sampler }
+        })
     }
 
-    pub fn convert(&self, ctx: &WgpuCtx, src_bg: &wgpu::BindGroup, dst: &PlanarYuvTextures) {
+    pub fn convert(
+        &self,
+        ctx: &WgpuCtx,
+        color_space: OutputColorSpace,
+        src_bg: &wgpu::BindGroup,
+        dst: &PlanarYuvTextures,
+    ) {
         let mut encoder = ctx
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                 label: Some("RGBA to YUV color converter command encoder"),
             });
 
+        let (pipeline, y_clear, uv_clear) = match dst.variant() {
+            // Black in 10-bit limited range YUV is y = 64, u = 512, v = 512
+            PlanarYuvVariant::YUV420P10 => (&self.pipeline_10bit, 64.0, 512.0),
+            _ => (&self.pipeline, 0.0, 0.5),
+        };
+
         for plane in [0, 1, 2] {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("YUV to RGBA color converter render pass"),
@@ -81,16 +125,16 @@ impl RgbaToYuvConverter {
                         // and to 0.5, 0.5, 0.5 when drawing the u and v planes.
                         load: wgpu::LoadOp::Clear(if plane == 0 {
                             wgpu::Color {
-                                r: 0.0,
-                                g: 0.0,
-                                b: 0.0,
+                                r: y_clear,
+                                g: y_clear,
+                                b: y_clear,
                                 a: 1.0,
                             }
                         } else {
                             wgpu::Color {
-                                r: 0.5,
-                                g: 0.5,
-                                b: 0.5,
+                                r: uv_clear,
+                                g: uv_clear,
+                                b: uv_clear,
                                 a: 1.0,
                             }
                         }),
@@ -104,12 +148,17 @@ impl RgbaToYuvConverter {
                 occlusion_query_set: None,
             });
 
-            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_pipeline(pipeline);
             render_pass.set_push_constants(
                 wgpu::ShaderStages::VERTEX_FRAGMENT,
                 0,
                 &(plane as u32).to_le_bytes(),
             );
+            render_pass.set_push_constants(
+                wgpu::ShaderStages::VERTEX_FRAGMENT,
+                4,
+                YuvColorParams::new(color_space.matrix, color_space.range).bytes(),
+            );
             render_pass.set_bind_group(0, src_bg, &[]);
             render_pass.set_bind_group(1, &self.sampler.bind_group, &[]);
             ctx.plane.draw(&mut render_pass);
@@ -21,34 +21,89 @@ fn vs_main(input: VertexInput) -> VertexOutput {
 @group(0) @binding(0) var texture: texture_2d<f32>;
 @group(1) @binding(0) var sampler_: sampler;
 
-var<push_constant> plane_selector: u32;
+struct PushConstantParams {
+    // 0 - Y, 1 - U, 2 - V
+    plane_selector: u32,
+    // 0 - BT.601, 1 - BT.709, 2 - BT.2020
+    matrix: u32,
+    // 0 - limited range, 1 - full range
+    full_range: u32,
+}
+
+var<push_constant> params: PushConstantParams;
+
+// Returns Y in range (0, 1) or U/V in range (-0.5, 0.5).
+fn yuv_component(tex_coords: vec2<f32>) -> f32 {
+    var color = textureSample(texture, sampler_, tex_coords).rgb;
+
+    var kr = 0.2126;
+    var kb = 0.0722;
+    if params.matrix == 0u {
+        kr = 0.299;
+        kb = 0.114;
+    } else if params.matrix == 2u {
+        kr = 0.2627;
+        kb = 0.0593;
+        // Scene is rendered with BT.709 primaries, convert them to BT.2020 (ITU-R BT.2087)
+        let linear = pow(clamp(color, vec3(0.0), vec3(1.0)), vec3(2.4));
+        let bt2020 = vec3<f32>(
+            0.6274 * linear.r + 0.3293 * linear.g + 0.0433 * linear.b,
+            0.0691 * linear.r + 0.9195 * linear.g + 0.0114 * linear.b,
+            0.0164 * linear.r + 0.0880 * linear.g + 0.8956 * linear.b,
+        );
+        color = pow(clamp(bt2020, vec3(0.0), vec3(1.0)), vec3(1.0 / 2.4));
+    }
+    let kg = 1.0 - kr - kb;
+
+    // YUV conversion from: https://en.wikipedia.org/w/index.php?title=YCbCr&section=8#ITU-R_BT.709_conversion
+    // generalized for BT.601 and BT.2020 coefficients.
+    let y = kr * color.r + kg * color.g + kb * color.b;
+    if params.plane_selector == 0u {
+        return y;
+    } else if params.plane_selector == 1u {
+        return (color.b - y) / (2.0 * (1.0 - kb));
+    } else {
+        return (color.r - y) / (2.0 * (1.0 - kr));
+    }
+}
 
 @fragment
 fn fs_main(input: VertexOutput) -> @location(0) f32 {
-    let color = textureSample(texture, sampler_, input.tex_coords);
-    var component: f32;
+    let component = yuv_component(input.tex_coords);
+
+    if params.full_range == 1u {
+        if params.plane_selector == 0u {
+            return clamp(component, 0.0, 1.0);
+        }
+        return clamp(component + 0.5, 0.0, 1.0);
+    }
 
-    // YUV conversion from: https://en.wikipedia.org/w/index.php?title=YCbCr&section=8#ITU-R_BT.709_conversion
     // YUV values footroom needs to be added
     // UV planes are returned in range (-0.5, 0.5) and need to be moved to (0, 1)
-    if(plane_selector == 0u) {
-        // Y plane
-        let y = color.r * 0.2126 + color.g * 0.7152 + color.b * 0.0722;
+    if params.plane_selector == 0u {
         // (235 - 16) / (255 - 0) = (219 / 255) ~= .858
-        component = (y * 0.85882352941) + (16.0/255.0);
-    } else if(plane_selector == 1u) {
-        // U plane
-        let u = color.r * -0.1146 + color.g * -0.3854 + color.b * 0.5;
-        // (240 - 16) / (255 - 0) = (224 / 255) ~= .878
-        component = ((u + 0.5) * 0.87843137254) + (16.0/255.0);
-    } else if(plane_selector == 2u) {
-        // V plane
-        let v = color.r * 0.5 + color.g * -0.4542 + color.b * -0.0458;
-        // (240 - 16) / (255 - 0) = (224 / 255) ~= .878
-        component = ((v + 0.5) * 0.87843137254) + (16.0/255.0);
-    } else {
-        component = 0.0;
+        return clamp((component * 0.85882352941) + (16.0/255.0), 0.0, 1.0);
+    }
+    // (240 - 16) / (255 - 0) = (224 / 255) ~= .878
+    return clamp(((component + 0.5) * 0.87843137254) + (16.0/255.0), 0.0, 1.0);
+}
+
+// Writes 10-bit samples into R16Uint textures.
+@fragment
+fn fs_main_10bit(input: VertexOutput) -> @location(0) u32 {
+    let component = yuv_component(input.tex_coords);
+
+    if params.full_range == 1u {
+        if params.plane_selector == 0u {
+            return u32(round(clamp(component, 0.0, 1.0) * 1023.0));
+        }
+        return u32(round(clamp(component + 0.5, 0.0, 1.0) * 1023.0));
     }
 
-    return clamp(component, 0.0, 1.0);
+    if params.plane_selector == 0u {
+        // Y in range [64, 940]
+        return u32(round(clamp(component, 0.0, 1.0) * 876.0 + 64.0));
+    }
+    // U and V in range [64, 960]
+    return u32(round(clamp(component + 0.5, 0.0, 1.0) * 896.0 + 64.0));
 }
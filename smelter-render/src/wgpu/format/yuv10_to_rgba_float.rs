@@ -0,0 +1,170 @@
+use wgpu::ShaderStages;
+
+use crate::{
+    ColorInfo, ColorMatrix, ColorRange, ColorTransfer,
+    wgpu::common_pipeline::{PRIMITIVE_STATE, Vertex},
+};
+
+use super::WgpuCtx;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Yuv10Layout {
+    /// `yuv420p10le` - separate U and V planes, samples in the least significant bits.
+    Planar,
+    /// `p010le` - interleaved UV plane, samples in the most significant bits.
+    SemiPlanar,
+}
+
+/// Converts 10-bit YUV 4:2:0 into linear light RGB stored in a `Rgba16Float` texture.
+/// PQ and HLG sources are not clamped to the SDR range, gamut is not converted.
+#[derive(Debug)]
+pub struct Yuv10ToRgbaFloatConverter {
+    pipeline: wgpu::RenderPipeline,
+}
+
+impl Yuv10ToRgbaFloatConverter {
+    pub fn new(device: &wgpu::Device, yuv_uint_bind_group_layout: &wgpu::BindGroupLayout) -> Self {
+        let shader_module =
+            device.create_shader_module(wgpu::include_wgsl!("yuv10_to_rgba_float.wgsl"));
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("10-bit YUV to RGBA float color converter render pipeline layout"),
+            bind_group_layouts: &[yuv_uint_bind_group_layout],
+            push_constant_ranges: &[wgpu::PushConstantRange {
+                stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                range: 0..Yuv10ToRgbaFloatPushConstants::push_constant_size(),
+            }],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("10-bit YUV to RGBA float color converter render pipeline"),
+            layout: Some(&pipeline_layout),
+            primitive: PRIMITIVE_STATE,
+
+            vertex: wgpu::VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                buffers: &[Vertex::LAYOUT],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+
+            fragment: Some(wgpu::FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::Rgba16Float,
+                    write_mask: wgpu::ColorWrites::all(),
+                    blend: None,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            depth_stencil: None,
+            cache: None,
+        });
+
+        Self { pipeline }
+    }
+
+    pub fn convert(
+        &self,
+        ctx: &WgpuCtx,
+        layout: Yuv10Layout,
+        color: ColorInfo,
+        src_bg: &wgpu::BindGroup,
+        dst_view: &wgpu::TextureView,
+    ) {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("10-bit YUV to RGBA float color converter encoder"),
+            });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("10-bit YUV to RGBA float color converter render pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    view: dst_view,
+                    resolve_target: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, src_bg, &[]);
+            render_pass.set_push_constants(
+                ShaderStages::VERTEX_FRAGMENT,
+                0,
+                Yuv10ToRgbaFloatPushConstants::new(layout, color).push_constant(),
+            );
+
+            ctx.plane.draw(&mut render_pass);
+        }
+
+        ctx.queue.submit(Some(encoder.finish()));
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, bytemuck::Pod, bytemuck::Zeroable, Clone, Copy)]
+struct Yuv10ToRgbaFloatPushConstants {
+    semi_planar: u32,
+    sample_shift: u32,
+    transfer: u32,
+    matrix: u32,
+    full_range: u32,
+}
+
+impl Yuv10ToRgbaFloatPushConstants {
+    fn new(layout: Yuv10Layout, color: ColorInfo) -> Self {
+        let (semi_planar, sample_shift) = match layout {
+            Yuv10Layout::Planar => (0, 0),
+            Yuv10Layout::SemiPlanar => (1, 6),
+        };
+        let transfer = match color.transfer {
+            ColorTransfer::Sdr => 0,
+            ColorTransfer::Pq => 1,
+            ColorTransfer::Hlg => 2,
+        };
+        let matrix = match color.matrix {
+            ColorMatrix::Bt601 => 0,
+            ColorMatrix::Bt709 => 1,
+            ColorMatrix::Bt2020 => 2,
+        };
+        let full_range = match color.range {
+            ColorRange::Limited => 0,
+            ColorRange::Full => 1,
+        };
+        Self {
+            semi_planar,
+            sample_shift,
+            transfer,
+            matrix,
+            full_range,
+        }
+    }
+
+    fn push_constant_size() -> u32 {
+        let size = std::mem::size_of::<Yuv10ToRgbaFloatPushConstants>() as u32;
+        match size % 4 {
+            0 => size,
+            rest => size + (4 - rest),
+        }
+    }
+
+    fn push_constant(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
@@ -0,0 +1,150 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+
+    output.position = vec4(input.position, 1.0);
+    output.tex_coords = input.tex_coords;
+
+    return output;
+}
+
+@group(0) @binding(0) var y_texture: texture_2d<u32>;
+// For semi-planar layout both bindings contain the same interleaved UV texture.
+@group(0) @binding(1) var u_texture: texture_2d<u32>;
+@group(0) @binding(2) var v_texture: texture_2d<u32>;
+
+struct PushConstantParams {
+    // 0 - planar (yuv420p10le)
+    // 1 - semi-planar (P010)
+    semi_planar: u32,
+    // Samples are stored in 16-bit values, P010 keeps data in the most significant bits.
+    sample_shift: u32,
+    // 0 - SDR (BT.709)
+    // 1 - PQ
+    // 2 - HLG
+    transfer: u32,
+    // 0 - BT.601, 1 - BT.709, 2 - BT.2020
+    matrix: u32,
+    // 0 - limited range, 1 - full range
+    full_range: u32,
+}
+
+var<push_constant> params: PushConstantParams;
+
+// Luminance of the SDR reference white in nits (ITU-R BT.2408).
+const REFERENCE_WHITE: f32 = 203.0;
+// Nominal peak luminance of the HLG display used for the OOTF.
+const HLG_PEAK_LUMINANCE: f32 = 1000.0;
+
+fn texel_coords(texture: texture_2d<u32>, tex_coords: vec2<f32>) -> vec2<u32> {
+    let size = textureDimensions(texture);
+    let coords = vec2<u32>(tex_coords * vec2<f32>(size));
+    return min(coords, size - vec2<u32>(1u, 1u));
+}
+
+// SMPTE ST 2084 EOTF, returns luminance relative to the reference white.
+fn pq_to_linear(value: f32) -> f32 {
+    let m1 = 0.1593017578125;
+    let m2 = 78.84375;
+    let c1 = 0.8359375;
+    let c2 = 18.8515625;
+    let c3 = 18.6875;
+
+    let e = pow(value, 1.0 / m2);
+    let nits = 10000.0 * pow(max(e - c1, 0.0) / (c2 - c3 * e), 1.0 / m1);
+    return nits / REFERENCE_WHITE;
+}
+
+// ARIB STD-B67 inverse OETF, returns normalized scene light.
+fn hlg_to_scene_linear(value: f32) -> f32 {
+    let a = 0.17883277;
+    let b = 0.28466892;
+    let c = 0.55991073;
+
+    if value <= 0.5 {
+        return value * value / 3.0;
+    }
+    return (exp((value - c) / a) + b) / 12.0;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let y_coords = texel_coords(y_texture, input.tex_coords);
+    let uv_coords = texel_coords(u_texture, input.tex_coords);
+
+    let y_sample = textureLoad(y_texture, y_coords, 0).x >> params.sample_shift;
+    var u_sample: u32;
+    var v_sample: u32;
+    if params.semi_planar == 1u {
+        let uv = textureLoad(u_texture, uv_coords, 0);
+        u_sample = uv.x >> params.sample_shift;
+        v_sample = uv.y >> params.sample_shift;
+    } else {
+        u_sample = textureLoad(u_texture, uv_coords, 0).x >> params.sample_shift;
+        v_sample = textureLoad(v_texture, uv_coords, 0).x >> params.sample_shift;
+    }
+
+    var y: f32;
+    var u: f32;
+    var v: f32;
+    if params.full_range == 0u {
+        // Limited range: Y in [64, 940], U and V in [64, 960]
+        y = clamp((f32(y_sample) - 64.0) / 876.0, 0.0, 1.0);
+        u = clamp((f32(u_sample) - 512.0) / 896.0, -0.5, 0.5);
+        v = clamp((f32(v_sample) - 512.0) / 896.0, -0.5, 0.5);
+    } else {
+        y = clamp(f32(y_sample) / 1023.0, 0.0, 1.0);
+        u = clamp((f32(u_sample) - 512.0) / 1023.0, -0.5, 0.5);
+        v = clamp((f32(v_sample) - 512.0) / 1023.0, -0.5, 0.5);
+    }
+
+    var kr = 0.2126;
+    var kb = 0.0722;
+    if params.matrix == 0u {
+        kr = 0.299;
+        kb = 0.114;
+    } else if params.matrix == 2u {
+        kr = 0.2627;
+        kb = 0.0593;
+    }
+    let kg = 1.0 - kr - kb;
+
+    let rgb = clamp(
+        vec3<f32>(
+            y + 2.0 * (1.0 - kr) * v,
+            y - (2.0 * kb * (1.0 - kb) / kg) * u - (2.0 * kr * (1.0 - kr) / kg) * v,
+            y + 2.0 * (1.0 - kb) * u,
+        ),
+        vec3(0.0),
+        vec3(1.0),
+    );
+
+    if params.transfer == 0u {
+        // BT.1886 EOTF
+        return vec4<f32>(pow(rgb, vec3(2.4)), 1.0);
+    }
+
+    if params.transfer == 1u {
+        return vec4<f32>(pq_to_linear(rgb.r), pq_to_linear(rgb.g), pq_to_linear(rgb.b), 1.0);
+    }
+
+    let scene = vec3<f32>(
+        hlg_to_scene_linear(rgb.r),
+        hlg_to_scene_linear(rgb.g),
+        hlg_to_scene_linear(rgb.b),
+    );
+    // HLG OOTF with system gamma 1.2
+    let luminance = dot(scene, vec3<f32>(kr, kg, kb));
+    let display = HLG_PEAK_LUMINANCE * pow(luminance, 0.2) * scene;
+    return vec4<f32>(display / REFERENCE_WHITE, 1.0);
+}
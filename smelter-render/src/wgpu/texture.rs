@@ -1,7 +1,9 @@
 mod base;
 mod interleaved_yuv422;
 mod nv12;
+mod p010;
 mod planar_yuv;
+mod rgba_float;
 mod rgba_linear;
 mod rgba_multiview;
 mod rgba_srgb;
@@ -20,11 +22,13 @@ pub type BgraSrgbTexture = bgra_srgb::BgraSrgbTexture;
 pub type RgbaMultiViewTexture = rgba_multiview::RgbaMultiViewTexture;
 pub type RgbaLinearTexture = rgba_linear::RgbaLinearTexture;
 pub type RgbaSrgbTexture = rgba_srgb::RgbaSrgbTexture;
+pub type Rgba16FloatTexture = rgba_float::Rgba16FloatTexture;
 
 pub type PlanarYuvTextures = planar_yuv::PlanarYuvTextures;
 pub type InterleavedUyvy422Texture = interleaved_yuv422::InterleavedYuv422Texture;
 pub type InterleavedYuyv422Texture = interleaved_yuv422::InterleavedYuv422Texture;
 pub type NV12Texture = nv12::NV12Texture;
+pub type P010Texture = p010::P010Texture;
 
 pub type PlanarYuvVariant = planar_yuv::YuvVariant;
 
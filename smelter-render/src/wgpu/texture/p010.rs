@@ -0,0 +1,90 @@
+use crate::{
+    NvPlanes, Resolution,
+    wgpu::{
+        WgpuCtx,
+        texture::{TextureExt, base::new_texture},
+    },
+};
+
+/// 10-bit semi-planar YUV 4:2:0. Samples are uploaded as 16-bit integers, so the
+/// `R16Uint` (Y) and `Rg16Uint` (UV) textures can only be read with `textureLoad`.
+#[derive(Debug)]
+pub struct P010Texture {
+    y_texture: wgpu::Texture,
+    uv_texture: wgpu::Texture,
+    y_view: wgpu::TextureView,
+    uv_view: wgpu::TextureView,
+    resolution: Resolution,
+}
+
+impl P010Texture {
+    pub fn new(ctx: &WgpuCtx, resolution: Resolution) -> Self {
+        let usage = wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING;
+
+        let y_texture = new_texture(
+            &ctx.device,
+            Some("p010 y plane texture"),
+            wgpu::Extent3d {
+                width: resolution.width as u32,
+                height: resolution.height as u32,
+                ..Default::default()
+            },
+            wgpu::TextureFormat::R16Uint,
+            usage,
+            &[],
+        );
+        let uv_texture = new_texture(
+            &ctx.device,
+            Some("p010 uv plane texture"),
+            wgpu::Extent3d {
+                width: resolution.width as u32 / 2,
+                height: resolution.height as u32 / 2,
+                ..Default::default()
+            },
+            wgpu::TextureFormat::Rg16Uint,
+            usage,
+            &[],
+        );
+
+        Self {
+            y_view: y_texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            uv_view: uv_texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            y_texture,
+            uv_texture,
+            resolution,
+        }
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    pub fn upload(&self, ctx: &WgpuCtx, planes: &NvPlanes) {
+        self.y_texture.upload_data(&ctx.queue, &planes.y_plane, 2);
+        self.uv_texture
+            .upload_data(&ctx.queue, &planes.uv_planes, 4);
+    }
+
+    /// Uses the same layout as 10-bit planar YUV textures, UV plane is bound twice
+    /// in place of U and V planes.
+    pub fn new_bind_group(&self, ctx: &WgpuCtx) -> wgpu::BindGroup {
+        ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("p010 texture bind group"),
+            layout: &ctx.format.yuv_uint_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&self.y_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&self.uv_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&self.uv_view),
+                },
+            ],
+        })
+    }
+}
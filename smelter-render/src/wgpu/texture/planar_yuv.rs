@@ -3,7 +3,7 @@ use std::marker::PhantomData;
 use bytes::Bytes;
 use wgpu::Buffer;
 
-use crate::{Resolution, YuvPlanes, scene::RGBColor, wgpu::WgpuCtx};
+use crate::{ColorRange, OutputColorSpace, Resolution, YuvPlanes, scene::RGBColor, wgpu::WgpuCtx};
 
 use super::{
     TextureExt,
@@ -51,6 +51,24 @@ pub enum YuvVariant {
     YUV422,
     YUV444,
     YUVJ420,
+    /// 10-bit YUV 4:2:0, planes are stored in `R16Uint` textures.
+    YUV420P10,
+}
+
+impl YuvVariant {
+    fn plane_format(&self) -> wgpu::TextureFormat {
+        match self {
+            YuvVariant::YUV420P10 => wgpu::TextureFormat::R16Uint,
+            _ => wgpu::TextureFormat::R8Unorm,
+        }
+    }
+
+    pub fn bytes_per_sample(&self) -> u32 {
+        match self {
+            YuvVariant::YUV420P10 => 2,
+            _ => 1,
+        }
+    }
 }
 
 pub struct PlanarYuvTextures {
@@ -63,7 +81,7 @@ pub struct PlanarYuvTextures {
 impl PlanarYuvTextures {
     pub fn new(ctx: &WgpuCtx, resolution: Resolution, variant: YuvVariant) -> Self {
         let (u_width, u_height, v_width, v_height) = match variant {
-            YuvVariant::YUV420 | YuvVariant::YUVJ420 => (
+            YuvVariant::YUV420 | YuvVariant::YUVJ420 | YuvVariant::YUV420P10 => (
                 resolution.width / 2,
                 resolution.height / 2,
                 resolution.width / 2,
@@ -82,9 +100,10 @@ impl PlanarYuvTextures {
                 resolution.height,
             ),
         };
-        let y = Self::new_plane(ctx, resolution.width, resolution.height);
-        let u = Self::new_plane(ctx, u_width, u_height);
-        let v = Self::new_plane(ctx, v_width, v_height);
+        let format = variant.plane_format();
+        let y = Self::new_plane(ctx, resolution.width, resolution.height, format);
+        let u = Self::new_plane(ctx, u_width, u_height, format);
+        let v = Self::new_plane(ctx, v_width, v_height, format);
         Self {
             variant,
             planes_views: [
@@ -109,7 +128,12 @@ impl PlanarYuvTextures {
         self.variant
     }
 
-    fn new_plane(ctx: &WgpuCtx, width: usize, height: usize) -> wgpu::Texture {
+    fn new_plane(
+        ctx: &WgpuCtx,
+        width: usize,
+        height: usize,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::Texture {
         new_texture(
             &ctx.device,
             None,
@@ -119,12 +143,12 @@ impl PlanarYuvTextures {
                 depth_or_array_layers: 1,
             },
             // TODO(noituri): [WASM] Format unsupported on firefox
-            wgpu::TextureFormat::R8Unorm,
+            format,
             wgpu::TextureUsages::RENDER_ATTACHMENT
                 | wgpu::TextureUsages::COPY_DST
                 | wgpu::TextureUsages::COPY_SRC
                 | wgpu::TextureUsages::TEXTURE_BINDING,
-            &[wgpu::TextureFormat::R8Unorm],
+            &[format],
         )
     }
 
@@ -142,9 +166,13 @@ impl PlanarYuvTextures {
     }
 
     pub fn new_bind_group(&self, ctx: &WgpuCtx) -> wgpu::BindGroup {
+        let layout = match self.variant {
+            YuvVariant::YUV420P10 => &ctx.format.yuv_uint_layout,
+            _ => &ctx.format.planar_yuv_layout,
+        };
         ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Planar YUV all textures bind group"),
-            layout: &ctx.format.planar_yuv_layout,
+            layout,
             entries: &[
                 wgpu::BindGroupEntry {
                     binding: 0,
@@ -185,13 +213,34 @@ impl PlanarYuvTextures {
     }
 
     pub fn upload(&mut self, ctx: &WgpuCtx, planes: &YuvPlanes) {
-        self.planes_textures[0].upload_data(&ctx.queue, &planes.y_plane, 1);
-        self.planes_textures[1].upload_data(&ctx.queue, &planes.u_plane, 1);
-        self.planes_textures[2].upload_data(&ctx.queue, &planes.v_plane, 1);
+        let bytes_per_sample = self.variant.bytes_per_sample();
+        self.planes_textures[0].upload_data(&ctx.queue, &planes.y_plane, bytes_per_sample);
+        self.planes_textures[1].upload_data(&ctx.queue, &planes.u_plane, bytes_per_sample);
+        self.planes_textures[2].upload_data(&ctx.queue, &planes.v_plane, bytes_per_sample);
     }
 
-    pub fn fill_with_color(&self, ctx: &WgpuCtx, color: RGBColor) {
-        let (y, u, v) = color.to_yuv();
+    pub fn fill_with_color(&self, ctx: &WgpuCtx, color: RGBColor, color_space: OutputColorSpace) {
+        let (y, u, v) = color.to_yuv(color_space.matrix);
+        // UV planes are in range (-0.5, 0.5) and need to be moved to (0, 1)
+        let (y, u, v) = (y, u + 0.5, v + 0.5);
+        if self.variant == YuvVariant::YUV420P10 {
+            let values = match color_space.range {
+                // Y in range [64, 940], U and V in range [64, 960]
+                ColorRange::Limited => [y * 876.0 + 64.0, u * 896.0 + 64.0, v * 896.0 + 64.0],
+                ColorRange::Full => [y * 1023.0, u * 1023.0, v * 1023.0],
+            };
+            self.fill_uint_planes(ctx, values);
+            return;
+        }
+        let (y, u, v) = match color_space.range {
+            // (235 - 16) / 255 for Y and (240 - 16) / 255 for U and V
+            ColorRange::Limited => (
+                y * 0.858_823_54 + 16.0 / 255.0,
+                u * 0.878_431_4 + 16.0 / 255.0,
+                v * 0.878_431_4 + 16.0 / 255.0,
+            ),
+            ColorRange::Full => (y, u, v),
+        };
         ctx.utils
             .r8_fill_with_value
             .fill(ctx, self.plane_view(0), y);
@@ -202,4 +251,36 @@ impl PlanarYuvTextures {
             .r8_fill_with_value
             .fill(ctx, self.plane_view(2), v);
     }
+
+    /// Integer textures can't be filled with the `r8_fill_with_value` pipeline, so planes are
+    /// cleared with a render pass instead.
+    fn fill_uint_planes(&self, ctx: &WgpuCtx, values: [f32; 3]) {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Fill 10-bit planar YUV textures encoder"),
+            });
+        for (plane, value) in values.into_iter().enumerate() {
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Fill 10-bit planar YUV textures render pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: (value as f64).round(),
+                            g: 0.0,
+                            b: 0.0,
+                            a: 0.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    view: self.plane_view(plane),
+                    resolve_target: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+        }
+        ctx.queue.submit(Some(encoder.finish()));
+    }
 }
@@ -0,0 +1,59 @@
+use crate::{Resolution, wgpu::WgpuCtx};
+
+use super::base::new_texture;
+
+/// Texture that stores linear light values. Values are not clamped to the `[0, 1]`
+/// range, `1.0` represents the SDR reference white. It is only used to convert 10-bit
+/// input frames, composition is done in 8-bit textures.
+#[derive(Debug)]
+pub struct Rgba16FloatTexture {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+impl Rgba16FloatTexture {
+    pub fn new(ctx: &WgpuCtx, resolution: Resolution) -> Self {
+        let size = wgpu::Extent3d {
+            width: resolution.width as u32,
+            height: resolution.height as u32,
+            depth_or_array_layers: 1,
+        };
+        let usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::COPY_SRC
+            | wgpu::TextureUsages::TEXTURE_BINDING;
+
+        let texture = new_texture(
+            &ctx.device,
+            Some("rgba16float texture"),
+            size,
+            wgpu::TextureFormat::Rgba16Float,
+            usage,
+            &[wgpu::TextureFormat::Rgba16Float],
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self { texture, view }
+    }
+
+    pub fn new_bind_group(&self, ctx: &WgpuCtx) -> wgpu::BindGroup {
+        ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("rgba16float texture bind group"),
+            layout: &ctx.format.single_texture_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&self.view),
+            }],
+        })
+    }
+
+    pub fn size(&self) -> wgpu::Extent3d {
+        self.texture.size()
+    }
+
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+}
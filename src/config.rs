@@ -7,7 +7,7 @@ use std::{
 };
 
 use rand::Rng;
-use smelter_core::DEFAULT_BUFFER_DURATION;
+use smelter_core::{DEFAULT_BUFFER_DURATION, DEFAULT_MEDIA_ALARM_THRESHOLD};
 use smelter_render::{Framerate, RenderingMode, WgpuFeatures};
 use tracing::error;
 
@@ -22,6 +22,7 @@ pub struct Config {
     pub download_root: Arc<Path>,
     pub stream_fallback_timeout: Duration,
     pub default_buffer_duration: Duration,
+    pub media_alarm_threshold: Duration,
 
     pub ahead_of_time_processing: bool,
     pub run_late_scheduled_events: bool,
@@ -143,6 +144,23 @@ fn try_read_config() -> Result<Config, String> {
         Err(_) => DEFAULT_STREAM_FALLBACK_TIMEOUT,
     };
 
+    let media_alarm_threshold = match env::var("SMELTER_MEDIA_ALARM_THRESHOLD_MS") {
+        Ok(threshold_ms) => match threshold_ms
+            .parse::<f64>()
+            .ok()
+            .and_then(|threshold_ms| Duration::try_from_secs_f64(threshold_ms / 1000.0).ok())
+        {
+            Some(threshold) => threshold,
+            None => {
+                println!(
+                    "CONFIG ERROR: Invalid value provided for \"SMELTER_MEDIA_ALARM_THRESHOLD_MS\". Falling back to default value {DEFAULT_MEDIA_ALARM_THRESHOLD:?}."
+                );
+                DEFAULT_MEDIA_ALARM_THRESHOLD
+            }
+        },
+        Err(_) => DEFAULT_MEDIA_ALARM_THRESHOLD,
+    };
+
     let default_logger_level = "info,wgpu_hal=warn,wgpu_core=warn,webrtc_srtp::session=warn";
     let logger_level = match env::var("SMELTER_LOGGER_LEVEL") {
         Ok(level) => format!("{default_logger_level},{level}"),
@@ -288,6 +306,7 @@ fn try_read_config() -> Result<Config, String> {
         run_late_scheduled_events,
         never_drop_output_frames,
         stream_fallback_timeout,
+        media_alarm_threshold,
         web_renderer_enable,
         web_renderer_gpu_enable,
         download_root,
@@ -4,12 +4,13 @@ use axum::{http::StatusCode, response::IntoResponse};
 use serde::Serialize;
 use smelter_api::TypeError;
 use smelter_core::error::{
-    ErrorType, InitPipelineError, PipelineErrorInfo, RegisterInputError, RegisterOutputError,
-    UnregisterInputError, UnregisterOutputError,
+    AudioDelayError, ErrorType, InitPipelineError, InputFailoverError, PipelineErrorInfo,
+    PlaylistUpdateError, RegisterInputError, RegisterOutputError, RegisterVideoClipError,
+    ReplayBufferError, UnregisterInputError, UnregisterOutputError,
 };
 use smelter_render::error::{
-    ErrorStack, RegisterRendererError, RequestKeyframeError, UnregisterRendererError,
-    UpdateSceneError,
+    ErrorStack, RegisterRendererError, RequestKeyframeError, SnapshotError,
+    UnregisterRendererError, UpdateFragmentError, UpdateSceneError,
 };
 
 #[derive(Debug)]
@@ -71,12 +72,19 @@ macro_rules! impl_api_err {
 impl_api_err!(RegisterInputError);
 impl_api_err!(RegisterOutputError);
 impl_api_err!(RegisterRendererError);
+impl_api_err!(RegisterVideoClipError);
 impl_api_err!(RequestKeyframeError);
 impl_api_err!(UnregisterInputError);
 impl_api_err!(UnregisterOutputError);
 impl_api_err!(UnregisterRendererError);
 impl_api_err!(UpdateSceneError);
+impl_api_err!(UpdateFragmentError);
 impl_api_err!(InitPipelineError);
+impl_api_err!(SnapshotError);
+impl_api_err!(InputFailoverError);
+impl_api_err!(PlaylistUpdateError);
+impl_api_err!(ReplayBufferError);
+impl_api_err!(AudioDelayError);
 
 impl From<TypeError> for ApiError {
     fn from(err: TypeError) -> Self {
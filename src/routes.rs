@@ -19,37 +19,80 @@ use crate::{
 };
 
 use self::{
-    update_output::handle_keyframe_request, update_output::handle_output_update,
-    ws::handle_ws_upgrade,
+    update_fragment::handle_fragment_update, update_output::handle_keyframe_request,
+    update_output::handle_output_patch, update_output::handle_output_update, ws::handle_ws_upgrade,
 };
 use crate::middleware::body_logger_middleware;
 
+mod audio_delay;
+mod batch;
+mod input_failover;
+mod preview;
+mod query;
 mod register_request;
+mod replay_buffer;
+mod snapshot;
 mod status;
 mod unregister_request;
+mod update_fragment;
 mod update_output;
+mod update_playlist;
 mod ws;
 
-pub use register_request::{RegisterInput, RegisterOutput};
+pub use audio_delay::AudioDelayRequest;
+pub use batch::BatchRequest;
+pub use preview::OutputPreviews;
+pub use register_request::{RegisterInput, RegisterOutput, RegisterSchedule};
+pub use replay_buffer::ReplayBufferRequest;
 pub use unregister_request::{UnregisterInput, UnregisterOutput};
-pub use update_output::UpdateOutputRequest;
+pub use update_fragment::UpdateFragmentRequest;
+pub use update_output::{PatchOutputRequest, UpdateOutputRequest};
+pub use update_playlist::UpdatePlaylistRequest;
 
 pub fn routes(state: Arc<ApiState>) -> Router {
     let inputs = Router::new()
+        .route("/", get(query::handle_inputs))
         .route("/:id/register", post(register_request::handle_input))
-        .route("/:id/unregister", post(unregister_request::handle_input));
+        .route("/:id/unregister", post(unregister_request::handle_input))
+        .route("/:id/failover", post(input_failover::handle_input_failover))
+        .route(
+            "/:id/playlist",
+            post(update_playlist::handle_playlist_update),
+        )
+        .route("/:id/audio_delay", post(audio_delay::handle_audio_delay))
+        .route(
+            "/:id/replay_buffer",
+            post(replay_buffer::handle_replay_buffer),
+        )
+        .route("/:id/snapshot", get(snapshot::handle_input_snapshot));
 
     let outputs = Router::new()
+        .route("/", get(query::handle_outputs))
+        .route("/:id/scene", get(query::handle_output_scene))
+        .route("/:id/snapshot", get(snapshot::handle_output_snapshot))
+        .route("/:id/preview", get(preview::handle_mjpeg_preview))
+        .route("/:id/preview/ws", get(preview::handle_ws_preview))
         .route("/:id/register", post(register_request::handle_output))
         .route("/:id/unregister", post(unregister_request::handle_output))
         .route("/:id/update", post(handle_output_update))
+        .route("/:id/patch", post(handle_output_patch))
         .route("/:id/request_keyframe", post(handle_keyframe_request));
 
     let image = Router::new()
+        .route("/", get(query::handle_images))
         .route("/:id/register", post(register_request::handle_image))
         .route("/:id/unregister", post(unregister_request::handle_image));
 
+    let video_clip = Router::new()
+        .route("/", get(query::handle_video_clips))
+        .route("/:id/register", post(register_request::handle_video_clip))
+        .route(
+            "/:id/unregister",
+            post(unregister_request::handle_video_clip),
+        );
+
     let web = Router::new()
+        .route("/", get(query::handle_web_renderers))
         .route("/:id/register", post(register_request::handle_web_renderer))
         .route(
             "/:id/unregister",
@@ -57,9 +100,16 @@ pub fn routes(state: Arc<ApiState>) -> Router {
         );
 
     let shader = Router::new()
+        .route("/", get(query::handle_shaders))
         .route("/:id/register", post(register_request::handle_shader))
         .route("/:id/unregister", post(unregister_request::handle_shader));
 
+    let fragment = Router::new()
+        .route("/", get(query::handle_fragments))
+        .route("/:id/register", post(register_request::handle_fragment))
+        .route("/:id/update", post(handle_fragment_update))
+        .route("/:id/unregister", post(unregister_request::handle_fragment));
+
     let font = Router::new().route("/register", post(register_request::handle_font));
 
     async fn handle_start(State(state): State<Arc<ApiState>>) -> Result<Response, ApiError> {
@@ -78,12 +128,15 @@ pub fn routes(state: Arc<ApiState>) -> Router {
         .nest("/api/input", inputs)
         .nest("/api/output", outputs)
         .nest("/api/image", image)
+        .nest("/api/video-clip", video_clip)
         .nest("/api/web-renderer", web)
         .nest("/api/shader", shader)
+        .nest("/api/fragment", fragment)
         .nest("/api/font", font)
         // Start request
         .route("/api/start", post(handle_start))
         .route("/api/reset", post(handle_reset))
+        .route("/api/batch", post(batch::handle_batch))
         // WebSocket - events
         .route("/ws", get(ws_handler))
         .route("/status", get(status_handler))
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smelter_api::AudioDelayMs;
+use smelter_core::AudioDelay;
+
+use crate::{
+    error::ApiError,
+    state::{ApiState, Response},
+};
+
+use smelter_api::InputId;
+
+use super::Json;
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AudioDelayRequest {
+    /// Shift in milliseconds applied only to audio of the input. Positive values delay
+    /// audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range.
+    /// Replaces the previous value.
+    pub audio_delay_ms: f64,
+}
+
+pub(super) async fn handle_audio_delay(
+    State(api): State<Arc<ApiState>>,
+    Path(input_id): Path<InputId>,
+    Json(request): Json<AudioDelayRequest>,
+) -> Result<Response, ApiError> {
+    let input_id = smelter_render::InputId::from(input_id);
+    let audio_delay: AudioDelay = AudioDelayMs(request.audio_delay_ms).try_into()?;
+    api.pipeline()?
+        .lock()
+        .unwrap()
+        .set_input_audio_delay(&input_id, audio_delay)?;
+    api.registry
+        .lock()
+        .unwrap()
+        .update_input_audio_delay(&input_id, request.audio_delay_ms);
+    Ok(Response::Ok {})
+}
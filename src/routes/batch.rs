@@ -0,0 +1,886 @@
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+use axum::{extract::State, http::StatusCode};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smelter_core::{
+    OutputUpdate, Pipeline, PreparedInput, PreparedOutput, RegisterInputOptions,
+    RegisterOutputOptions, protocols::VideoClipOptions,
+};
+use smelter_render::{PreparedRenderer, RegistryType, RendererSpec};
+use tracing::{error, warn};
+
+use crate::{
+    error::ApiError,
+    routes::{RegisterInput, RegisterOutput},
+    state::{ApiRegistry, ApiState, RegisteredOutput, Response},
+};
+
+use smelter_api::{
+    AudioScene, FragmentSpec, ImageSpec, InputId, OutputId, RendererId, ShaderSpec, VideoClipSpec,
+    VideoScene, WebRendererSpec,
+};
+
+use super::{Json, register_request};
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BatchRequest {
+    /// All operations are validated before any of them is applied, if one of them is
+    /// invalid the whole batch is rejected. Registrations are applied first, then all
+    /// output updates are applied at once, so new scenes are rendered starting from the
+    /// same frame. If any registration or update fails, or any of the unregistered
+    /// entities no longer exists, entities registered by the batch are unregistered
+    /// and no output is updated. Unregistrations are applied last.
+    pub operations: Vec<BatchOperation>,
+    /// Time in milliseconds when the batch should be applied. Value `0` represents
+    /// time of the start request.
+    ///
+    /// All registered entities are initialized before the response is sent. The whole
+    /// batch is applied at once, starting from a frame with exactly that time. Errors
+    /// of a scheduled batch are only logged.
+    pub schedule_time_ms: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum BatchOperation {
+    RegisterInput {
+        input_id: InputId,
+        input: RegisterInput,
+    },
+    UnregisterInput {
+        input_id: InputId,
+    },
+    RegisterOutput {
+        output_id: OutputId,
+        output: Box<RegisterOutput>,
+    },
+    UnregisterOutput {
+        output_id: OutputId,
+    },
+    UpdateOutput {
+        output_id: OutputId,
+        video: Option<VideoScene>,
+        audio: Option<AudioScene>,
+    },
+    RegisterShader {
+        shader_id: RendererId,
+        shader: ShaderSpec,
+    },
+    UnregisterShader {
+        shader_id: RendererId,
+    },
+    RegisterWebRenderer {
+        instance_id: RendererId,
+        web_renderer: WebRendererSpec,
+    },
+    UnregisterWebRenderer {
+        instance_id: RendererId,
+    },
+    RegisterImage {
+        image_id: RendererId,
+        image: ImageSpec,
+    },
+    UnregisterImage {
+        image_id: RendererId,
+    },
+    RegisterVideoClip {
+        clip_id: RendererId,
+        video_clip: VideoClipSpec,
+    },
+    UnregisterVideoClip {
+        clip_id: RendererId,
+    },
+    RegisterFragment {
+        fragment_id: RendererId,
+        fragment: FragmentSpec,
+    },
+    UnregisterFragment {
+        fragment_id: RendererId,
+    },
+}
+
+/// Batch operation converted to pipeline types. Original requests are kept to
+/// update the registry.
+enum Operation {
+    RegisterInput {
+        input_id: smelter_render::InputId,
+        options: RegisterInputOptions,
+        request: RegisterInput,
+    },
+    RegisterOutput {
+        output_id: smelter_render::OutputId,
+        options: RegisterOutputOptions,
+        request: RegisterOutput,
+    },
+    RegisterRenderer {
+        renderer_id: smelter_render::RendererId,
+        spec: RendererSpec,
+        request: RendererRequest,
+    },
+    RegisterVideoClip {
+        renderer_id: smelter_render::RendererId,
+        options: VideoClipOptions,
+        request: VideoClipSpec,
+    },
+    /// Input initialized with [`prepare_registrations`].
+    RegisterPreparedInput {
+        input_id: smelter_render::InputId,
+        prepared: PreparedInput,
+        request: RegisterInput,
+    },
+    /// Output initialized with [`prepare_registrations`].
+    RegisterPreparedOutput {
+        output_id: smelter_render::OutputId,
+        prepared: PreparedOutput,
+        request: RegisterOutput,
+    },
+    /// Renderer or video clip initialized with [`prepare_registrations`].
+    RegisterPreparedRenderer {
+        prepared: PreparedRenderer,
+        request: RendererRequest,
+    },
+    UnregisterInput(smelter_render::InputId),
+    UnregisterOutput(smelter_render::OutputId),
+    UnregisterRenderer(smelter_render::RendererId, RegistryType),
+    UpdateOutput {
+        update: OutputUpdate,
+        request: (Option<VideoScene>, Option<AudioScene>),
+    },
+}
+
+enum RendererRequest {
+    Shader(ShaderSpec),
+    WebRenderer(WebRendererSpec),
+    Image(ImageSpec),
+    VideoClip(VideoClipSpec),
+    Fragment(FragmentSpec),
+}
+
+/// Registered entity that is unregistered if a later registration from the batch fails.
+enum Registered {
+    Input(smelter_render::InputId),
+    Output(smelter_render::OutputId),
+    Renderer(smelter_render::RendererId, RegistryType),
+}
+
+pub(super) async fn handle_batch(
+    State(api): State<Arc<ApiState>>,
+    Json(request): Json<BatchRequest>,
+) -> Result<Response, ApiError> {
+    let schedule_time = request
+        .schedule_time_ms
+        .map(register_request::schedule_time)
+        .transpose()?;
+    let operations = {
+        let registry = api.registry.lock().unwrap();
+        prepare_operations(&registry, request.operations)?
+    };
+    let pipeline = api.pipeline()?;
+    let registry = api.registry.clone();
+    let operations = {
+        let pipeline = pipeline.clone();
+        tokio::task::spawn_blocking(move || prepare_registrations(&pipeline, operations))
+            .await
+            // `unwrap()` panics only when the task panicked or `response.abort()` was called
+            .unwrap()?
+    };
+    match schedule_time {
+        Some(schedule_time) => {
+            Pipeline::schedule_event(&pipeline, schedule_time, move |pipeline| {
+                if let Err(err) = apply_operations(pipeline, &registry, operations) {
+                    error!(
+                        "Error while running scheduled batch for pts {}ms: {}",
+                        schedule_time.as_millis(),
+                        err.stack.join("\n")
+                    );
+                }
+            });
+        }
+        None => apply_operations(&mut pipeline.lock().unwrap(), &registry, operations)?,
+    }
+    Ok(Response::Ok {})
+}
+
+/// Converts operations to pipeline types and checks if they refer to entities that
+/// exist at the point of the batch where they are applied.
+fn prepare_operations(
+    registry: &ApiRegistry,
+    operations: Vec<BatchOperation>,
+) -> Result<Vec<Operation>, ApiError> {
+    let mut inputs = RegisteredIds::new(registry.inputs.keys().cloned());
+    let mut outputs = RegisteredIds::new(registry.outputs.keys().cloned());
+    let mut renderers = RegisteredIds::new(
+        [
+            RegistryType::Shader,
+            RegistryType::WebRenderer,
+            RegistryType::Image,
+            RegistryType::VideoClip,
+            RegistryType::Fragment,
+        ]
+        .into_iter()
+        .flat_map(|registry_type| {
+            registered_renderers(registry, registry_type)
+                .into_iter()
+                .map(move |id| (id, registry_type))
+        }),
+    );
+
+    operations
+        .into_iter()
+        .enumerate()
+        .map(|(index, operation)| {
+            let invalid = |message: &dyn Display| invalid_operation(index, message);
+            let operation = match operation {
+                BatchOperation::RegisterInput { input_id, input } => Operation::RegisterInput {
+                    input_id: input_id.into(),
+                    options: input.clone().try_into().map_err(|err| invalid(&err))?,
+                    request: input,
+                },
+                BatchOperation::RegisterOutput { output_id, output } => Operation::RegisterOutput {
+                    output_id: output_id.into(),
+                    options: (*output).clone().try_into().map_err(|err| invalid(&err))?,
+                    request: *output,
+                },
+                BatchOperation::UpdateOutput {
+                    output_id,
+                    video,
+                    audio,
+                } => Operation::UpdateOutput {
+                    update: OutputUpdate {
+                        output_id: output_id.into(),
+                        video: video
+                            .clone()
+                            .map(TryInto::try_into)
+                            .transpose()
+                            .map_err(|err| invalid(&err))?,
+                        audio: audio
+                            .clone()
+                            .map(TryInto::try_into)
+                            .transpose()
+                            .map_err(|err| invalid(&err))?,
+                    },
+                    request: (video, audio),
+                },
+                BatchOperation::RegisterShader { shader_id, shader } => {
+                    Operation::RegisterRenderer {
+                        renderer_id: shader_id.into(),
+                        spec: shader.clone().try_into().map_err(|err| invalid(&err))?,
+                        request: RendererRequest::Shader(shader),
+                    }
+                }
+                BatchOperation::RegisterWebRenderer {
+                    instance_id,
+                    web_renderer,
+                } => Operation::RegisterRenderer {
+                    renderer_id: instance_id.into(),
+                    spec: web_renderer
+                        .clone()
+                        .try_into()
+                        .map_err(|err| invalid(&err))?,
+                    request: RendererRequest::WebRenderer(web_renderer),
+                },
+                BatchOperation::RegisterImage { image_id, image } => Operation::RegisterRenderer {
+                    renderer_id: image_id.into(),
+                    spec: image.clone().try_into().map_err(|err| invalid(&err))?,
+                    request: RendererRequest::Image(image),
+                },
+                BatchOperation::RegisterVideoClip {
+                    clip_id,
+                    video_clip,
+                } => Operation::RegisterVideoClip {
+                    renderer_id: clip_id.into(),
+                    options: video_clip.clone().try_into().map_err(|err| invalid(&err))?,
+                    request: video_clip,
+                },
+                BatchOperation::RegisterFragment {
+                    fragment_id,
+                    fragment,
+                } => Operation::RegisterRenderer {
+                    renderer_id: fragment_id.into(),
+                    spec: fragment.clone().try_into().map_err(|err| invalid(&err))?,
+                    request: RendererRequest::Fragment(fragment),
+                },
+                BatchOperation::UnregisterInput { input_id } => {
+                    Operation::UnregisterInput(input_id.into())
+                }
+                BatchOperation::UnregisterOutput { output_id } => {
+                    Operation::UnregisterOutput(output_id.into())
+                }
+                BatchOperation::UnregisterShader { shader_id } => {
+                    Operation::UnregisterRenderer(shader_id.into(), RegistryType::Shader)
+                }
+                BatchOperation::UnregisterWebRenderer { instance_id } => {
+                    Operation::UnregisterRenderer(instance_id.into(), RegistryType::WebRenderer)
+                }
+                BatchOperation::UnregisterImage { image_id } => {
+                    Operation::UnregisterRenderer(image_id.into(), RegistryType::Image)
+                }
+                BatchOperation::UnregisterVideoClip { clip_id } => {
+                    Operation::UnregisterRenderer(clip_id.into(), RegistryType::VideoClip)
+                }
+                BatchOperation::UnregisterFragment { fragment_id } => {
+                    Operation::UnregisterRenderer(fragment_id.into(), RegistryType::Fragment)
+                }
+            };
+
+            let is_valid = match &operation {
+                Operation::RegisterInput { input_id, .. } => inputs.register(input_id.clone()),
+                Operation::UnregisterInput(input_id) => inputs.unregister(input_id.clone()),
+                Operation::RegisterOutput { output_id, .. } => outputs.register(output_id.clone()),
+                Operation::UnregisterOutput(output_id) => outputs.unregister(output_id.clone()),
+                Operation::UpdateOutput { update, .. } => outputs.exists(update.output_id.clone()),
+                Operation::RegisterRenderer {
+                    renderer_id,
+                    request,
+                    ..
+                } => renderers.register((renderer_id.clone(), request.registry_type())),
+                Operation::RegisterVideoClip { renderer_id, .. } => {
+                    renderers.register((renderer_id.clone(), RegistryType::VideoClip))
+                }
+                Operation::RegisterPreparedInput { input_id, .. } => {
+                    inputs.register(input_id.clone())
+                }
+                Operation::RegisterPreparedOutput { output_id, .. } => {
+                    outputs.register(output_id.clone())
+                }
+                Operation::RegisterPreparedRenderer { prepared, request } => {
+                    renderers.register((prepared.id().clone(), request.registry_type()))
+                }
+                Operation::UnregisterRenderer(renderer_id, registry_type) => {
+                    renderers.unregister((renderer_id.clone(), *registry_type))
+                }
+            };
+            match is_valid {
+                true => Ok(operation),
+                false => Err(invalid(&operation.conflict_message())),
+            }
+        })
+        .collect()
+}
+
+/// Initializes inputs, outputs and renderers, and decodes video clips, so applying the
+/// batch only adds them to the pipeline. Nothing is registered yet, so a failure does
+/// not require a revert.
+fn prepare_registrations(
+    pipeline: &Arc<Mutex<Pipeline>>,
+    operations: Vec<Operation>,
+) -> Result<Vec<Operation>, ApiError> {
+    operations
+        .into_iter()
+        .map(|operation| match operation {
+            Operation::RegisterInput {
+                input_id,
+                options,
+                request,
+            } => Ok(Operation::RegisterPreparedInput {
+                prepared: Pipeline::prepare_input(pipeline, input_id.clone(), options)?.0,
+                input_id,
+                request,
+            }),
+            Operation::RegisterOutput {
+                output_id,
+                options,
+                request,
+            } => Ok(Operation::RegisterPreparedOutput {
+                prepared: Pipeline::prepare_output(pipeline, output_id.clone(), options)?.0,
+                output_id,
+                request,
+            }),
+            Operation::RegisterRenderer {
+                renderer_id,
+                spec,
+                request,
+            } => Ok(Operation::RegisterPreparedRenderer {
+                prepared: Pipeline::prepare_renderer(pipeline, renderer_id, spec)?,
+                request,
+            }),
+            Operation::RegisterVideoClip {
+                renderer_id,
+                options,
+                request,
+            } => Ok(Operation::RegisterPreparedRenderer {
+                prepared: Pipeline::prepare_video_clip(pipeline, renderer_id, options)?,
+                request: RendererRequest::VideoClip(request),
+            }),
+            operation => Ok(operation),
+        })
+        .collect()
+}
+
+/// Registers new entities first, then updates all outputs with a single pipeline call.
+/// If any registration or update fails, or any of the unregistered entities does not
+/// exist in the pipeline, entities registered by the batch are unregistered.
+/// Unregistrations are applied last. Everything is applied with the pipeline locked,
+/// so the whole batch takes effect starting from the same frame.
+fn apply_operations(
+    pipeline: &mut Pipeline,
+    registry: &Arc<Mutex<ApiRegistry>>,
+    operations: Vec<Operation>,
+) -> Result<(), ApiError> {
+    let mut registrations = Vec::new();
+    let mut updates = Vec::new();
+    let mut unregistrations = Vec::new();
+    for (index, operation) in operations.into_iter().enumerate() {
+        match operation {
+            Operation::UpdateOutput { update, request } => updates.push((update, request)),
+            operation if operation.is_registration() => registrations.push(operation),
+            operation => unregistrations.push((index, operation)),
+        }
+    }
+
+    let mut registered = Vec::new();
+    for operation in registrations {
+        match register(pipeline, registry, operation) {
+            Ok(entity) => registered.push(entity),
+            Err(err) => {
+                revert_registrations(pipeline, registry, registered);
+                return Err(err);
+            }
+        }
+    }
+
+    let result = check_unregistrations(pipeline, &unregistrations)
+        .and_then(|()| update_outputs(pipeline, registry, updates));
+    if let Err(err) = result {
+        revert_registrations(pipeline, registry, registered);
+        return Err(err);
+    }
+    for (_, operation) in unregistrations {
+        apply_unregistration(pipeline, registry, operation);
+    }
+    Ok(())
+}
+
+/// Unregistrations are applied after the output updates, so they can't fail once the
+/// updates are applied. The API registry is validated before the batch is applied,
+/// but the pipeline state could have changed since then.
+fn check_unregistrations(
+    pipeline: &Pipeline,
+    unregistrations: &[(usize, Operation)],
+) -> Result<(), ApiError> {
+    for (index, operation) in unregistrations {
+        let exists = match operation {
+            Operation::UnregisterInput(input_id) => pipeline.inputs().any(|(id, _)| id == input_id),
+            Operation::UnregisterOutput(output_id) => {
+                pipeline.outputs().any(|(id, _)| id == output_id)
+            }
+            Operation::UnregisterRenderer(renderer_id, registry_type) => {
+                pipeline.is_renderer_registered(renderer_id, *registry_type)
+            }
+            _ => unreachable!("Registrations and updates are applied separately."),
+        };
+        if !exists {
+            return Err(invalid_operation(*index, &operation.conflict_message()));
+        }
+    }
+    Ok(())
+}
+
+fn update_outputs(
+    pipeline: &mut Pipeline,
+    registry: &Arc<Mutex<ApiRegistry>>,
+    updates: Vec<(OutputUpdate, (Option<VideoScene>, Option<AudioScene>))>,
+) -> Result<(), ApiError> {
+    if updates.is_empty() {
+        return Ok(());
+    }
+    let (updates, requests): (Vec<_>, Vec<_>) = updates
+        .into_iter()
+        .map(|(update, request)| {
+            let output_id = update.output_id.clone();
+            (update, (output_id, request))
+        })
+        .unzip();
+    pipeline.update_outputs(updates)?;
+
+    let mut registry = registry.lock().unwrap();
+    for (output_id, (video, audio)) in requests {
+        registry.update_output(&output_id, video, audio);
+    }
+    Ok(())
+}
+
+fn register(
+    pipeline: &mut Pipeline,
+    registry: &Arc<Mutex<ApiRegistry>>,
+    operation: Operation,
+) -> Result<Registered, ApiError> {
+    match operation {
+        Operation::RegisterPreparedInput {
+            input_id,
+            prepared,
+            request,
+        } => {
+            pipeline.register_prepared_input(prepared)?;
+            let mut registry = registry.lock().unwrap();
+            registry.inputs.insert(input_id.clone(), request);
+            Ok(Registered::Input(input_id))
+        }
+        Operation::RegisterPreparedOutput {
+            output_id,
+            prepared,
+            request,
+        } => {
+            pipeline.register_prepared_output(prepared)?;
+            let mut registry = registry.lock().unwrap();
+            let output = RegisteredOutput::new(request);
+            registry.outputs.insert(output_id.clone(), output);
+            Ok(Registered::Output(output_id))
+        }
+        Operation::RegisterPreparedRenderer { prepared, request } => {
+            let renderer_id = prepared.id().clone();
+            pipeline.register_prepared_renderer(prepared)?;
+            let registry_type = request.registry_type();
+            let mut registry = registry.lock().unwrap();
+            let id = renderer_id.clone();
+            match request {
+                RendererRequest::Shader(spec) => {
+                    registry.shaders.insert(id, spec);
+                }
+                RendererRequest::WebRenderer(spec) => {
+                    registry.web_renderers.insert(id, spec);
+                }
+                RendererRequest::Image(spec) => {
+                    registry.images.insert(id, spec);
+                }
+                RendererRequest::VideoClip(spec) => {
+                    registry.video_clips.insert(id, spec);
+                }
+                RendererRequest::Fragment(spec) => {
+                    registry.fragments.insert(id, spec);
+                }
+            }
+            Ok(Registered::Renderer(renderer_id, registry_type))
+        }
+        _ => unreachable!("Operation is not a registration or it is not prepared."),
+    }
+}
+
+fn revert_registrations(
+    pipeline: &mut Pipeline,
+    registry: &Arc<Mutex<ApiRegistry>>,
+    registered: Vec<Registered>,
+) {
+    for entity in registered.into_iter().rev() {
+        unregister(pipeline, registry, entity);
+    }
+}
+
+fn unregister(pipeline: &mut Pipeline, registry: &Arc<Mutex<ApiRegistry>>, entity: Registered) {
+    let mut registry = registry.lock().unwrap();
+    let result = match entity {
+        Registered::Input(input_id) => {
+            registry.inputs.remove(&input_id);
+            pipeline.unregister_input(&input_id).map_err(ApiError::from)
+        }
+        Registered::Output(output_id) => {
+            registry.outputs.remove(&output_id);
+            pipeline
+                .unregister_output(&output_id)
+                .map_err(ApiError::from)
+        }
+        Registered::Renderer(renderer_id, registry_type) => {
+            remove_renderer(&mut registry, &renderer_id, registry_type);
+            pipeline
+                .unregister_renderer(&renderer_id, registry_type)
+                .map_err(ApiError::from)
+        }
+    };
+    if let Err(err) = result {
+        warn!(
+            "Failed to revert registration from a failed batch: {}",
+            err.stack.join("\n")
+        );
+    }
+}
+
+/// Unregistrations are checked with [`check_unregistrations`] before any output is
+/// updated, so an error here is unexpected and only logged.
+fn apply_unregistration(
+    pipeline: &mut Pipeline,
+    registry: &Arc<Mutex<ApiRegistry>>,
+    operation: Operation,
+) {
+    let result = match operation {
+        Operation::UnregisterInput(input_id) => {
+            registry.lock().unwrap().inputs.remove(&input_id);
+            pipeline.unregister_input(&input_id).map_err(ApiError::from)
+        }
+        Operation::UnregisterOutput(output_id) => {
+            registry.lock().unwrap().outputs.remove(&output_id);
+            pipeline
+                .unregister_output(&output_id)
+                .map_err(ApiError::from)
+        }
+        Operation::UnregisterRenderer(renderer_id, registry_type) => {
+            remove_renderer(&mut registry.lock().unwrap(), &renderer_id, registry_type);
+            pipeline
+                .unregister_renderer(&renderer_id, registry_type)
+                .map_err(ApiError::from)
+        }
+        _ => unreachable!("Registrations and updates are applied separately."),
+    };
+    if let Err(err) = result {
+        error!(
+            "Failed to apply unregistration from a batch: {}",
+            err.stack.join("\n")
+        );
+    }
+}
+
+impl RendererRequest {
+    fn registry_type(&self) -> RegistryType {
+        match self {
+            RendererRequest::Shader(_) => RegistryType::Shader,
+            RendererRequest::WebRenderer(_) => RegistryType::WebRenderer,
+            RendererRequest::Image(_) => RegistryType::Image,
+            RendererRequest::VideoClip(_) => RegistryType::VideoClip,
+            RendererRequest::Fragment(_) => RegistryType::Fragment,
+        }
+    }
+}
+
+impl Operation {
+    fn is_registration(&self) -> bool {
+        matches!(
+            self,
+            Operation::RegisterInput { .. }
+                | Operation::RegisterOutput { .. }
+                | Operation::RegisterRenderer { .. }
+                | Operation::RegisterVideoClip { .. }
+                | Operation::RegisterPreparedInput { .. }
+                | Operation::RegisterPreparedOutput { .. }
+                | Operation::RegisterPreparedRenderer { .. }
+        )
+    }
+
+    fn conflict_message(&self) -> String {
+        match self {
+            Operation::RegisterInput { input_id, .. }
+            | Operation::RegisterPreparedInput { input_id, .. } => {
+                format!("Input \"{input_id}\" is already registered.")
+            }
+            Operation::UnregisterInput(input_id) => {
+                format!("Input \"{input_id}\" does not exist.")
+            }
+            Operation::RegisterOutput { output_id, .. }
+            | Operation::RegisterPreparedOutput { output_id, .. } => {
+                format!("Output \"{output_id}\" is already registered.")
+            }
+            Operation::UnregisterOutput(output_id) => {
+                format!("Output \"{output_id}\" does not exist.")
+            }
+            Operation::UpdateOutput { update, .. } => {
+                format!("Output \"{}\" does not exist.", update.output_id)
+            }
+            Operation::RegisterRenderer { renderer_id, .. }
+            | Operation::RegisterVideoClip { renderer_id, .. } => {
+                format!("Renderer \"{renderer_id}\" is already registered.")
+            }
+            Operation::RegisterPreparedRenderer { prepared, .. } => {
+                format!("Renderer \"{}\" is already registered.", prepared.id())
+            }
+            Operation::UnregisterRenderer(renderer_id, _) => {
+                format!("Renderer \"{renderer_id}\" does not exist.")
+            }
+        }
+    }
+}
+
+/// Ids of entities that exist at the validated point of the batch. Registrations are
+/// applied before other operations, so an id stays taken even if it is unregistered
+/// earlier in the batch.
+struct RegisteredIds<T> {
+    existing: HashSet<T>,
+    taken: HashSet<T>,
+}
+
+impl<T: Clone + Eq + Hash> RegisteredIds<T> {
+    fn new(ids: impl Iterator<Item = T>) -> Self {
+        let existing: HashSet<T> = ids.collect();
+        Self {
+            taken: existing.clone(),
+            existing,
+        }
+    }
+
+    fn register(&mut self, id: T) -> bool {
+        self.existing.insert(id.clone());
+        self.taken.insert(id)
+    }
+
+    fn unregister(&mut self, id: T) -> bool {
+        self.existing.remove(&id)
+    }
+
+    fn exists(&self, id: T) -> bool {
+        self.existing.contains(&id)
+    }
+}
+
+fn registered_renderers(
+    registry: &ApiRegistry,
+    registry_type: RegistryType,
+) -> Vec<smelter_render::RendererId> {
+    match registry_type {
+        RegistryType::Shader => registry.shaders.keys().cloned().collect(),
+        RegistryType::WebRenderer => registry.web_renderers.keys().cloned().collect(),
+        RegistryType::Image => registry.images.keys().cloned().collect(),
+        RegistryType::VideoClip => registry.video_clips.keys().cloned().collect(),
+        RegistryType::Fragment => registry.fragments.keys().cloned().collect(),
+    }
+}
+
+fn remove_renderer(
+    registry: &mut ApiRegistry,
+    renderer_id: &smelter_render::RendererId,
+    registry_type: RegistryType,
+) {
+    match registry_type {
+        RegistryType::Shader => {
+            registry.shaders.remove(renderer_id);
+        }
+        RegistryType::WebRenderer => {
+            registry.web_renderers.remove(renderer_id);
+        }
+        RegistryType::Image => {
+            registry.images.remove(renderer_id);
+        }
+        RegistryType::VideoClip => {
+            registry.video_clips.remove(renderer_id);
+        }
+        RegistryType::Fragment => {
+            registry.fragments.remove(renderer_id);
+        }
+    }
+}
+
+fn invalid_operation(index: usize, message: &dyn Display) -> ApiError {
+    ApiError::new(
+        "INVALID_BATCH_OPERATION",
+        format!("Invalid batch operation at index {index}: {message}"),
+        StatusCode::BAD_REQUEST,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn operations(value: serde_json::Value) -> Vec<BatchOperation> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn registry() -> ApiRegistry {
+        let mut registry = ApiRegistry::default();
+        registry.inputs.insert(
+            smelter_render::InputId::from(Arc::from("input_1")),
+            serde_json::from_value(json!({ "type": "mp4", "path": "input_1.mp4" })).unwrap(),
+        );
+        registry
+    }
+
+    #[test]
+    fn registered_ids_track_batch_state() {
+        let mut ids = RegisteredIds::new(["a"].into_iter());
+
+        assert!(!ids.register("a"));
+        assert!(ids.register("b"));
+        assert!(ids.exists("b"));
+
+        assert!(ids.unregister("a"));
+        assert!(!ids.exists("a"));
+        assert!(!ids.unregister("a"));
+        // registrations are applied first, so the id is still taken
+        assert!(!ids.register("a"));
+    }
+
+    #[test]
+    fn register_and_unregister_in_one_batch() {
+        let operations = operations(json!([
+            {
+                "type": "register_input",
+                "input_id": "input_2",
+                "input": { "type": "mp4", "path": "input_2.mp4" },
+            },
+            { "type": "unregister_input", "input_id": "input_1" },
+            { "type": "unregister_input", "input_id": "input_2" },
+        ]));
+
+        let operations = prepare_operations(&registry(), operations).unwrap();
+        assert_eq!(operations.len(), 3);
+    }
+
+    #[test]
+    fn reject_unregister_of_missing_entity() {
+        let operations = operations(json!([
+            { "type": "unregister_input", "input_id": "input_1" },
+            { "type": "unregister_input", "input_id": "input_1" },
+        ]));
+
+        let Err(err) = prepare_operations(&registry(), operations) else {
+            panic!("Expected batch to be rejected.");
+        };
+        assert_eq!(err.error_code, "INVALID_BATCH_OPERATION");
+        assert!(err.message.contains("index 1"), "{}", err.message);
+    }
+
+    #[test]
+    fn reject_duplicated_registration() {
+        let operations = operations(json!([
+            { "type": "unregister_input", "input_id": "input_1" },
+            {
+                "type": "register_input",
+                "input_id": "input_1",
+                "input": { "type": "mp4", "path": "input_1.mp4" },
+            },
+        ]));
+
+        let Err(err) = prepare_operations(&registry(), operations) else {
+            panic!("Expected batch to be rejected.");
+        };
+        assert!(err.message.contains("index 1"), "{}", err.message);
+    }
+
+    #[test]
+    fn reject_update_of_missing_output() {
+        let operations = operations(json!([
+            {
+                "type": "update_output",
+                "output_id": "output_1",
+                "video": { "root": { "type": "view" } },
+            },
+        ]));
+
+        let Err(err) = prepare_operations(&registry(), operations) else {
+            panic!("Expected batch to be rejected.");
+        };
+        assert!(err.message.contains("index 0"), "{}", err.message);
+    }
+
+    #[test]
+    fn reject_invalid_request() {
+        let operations = operations(json!([
+            { "type": "unregister_input", "input_id": "input_1" },
+            {
+                "type": "register_input",
+                "input_id": "input_2",
+                "input": { "type": "mp4" },
+            },
+        ]));
+
+        let Err(err) = prepare_operations(&registry(), operations) else {
+            panic!("Expected batch to be rejected.");
+        };
+        assert!(err.message.contains("index 1"), "{}", err.message);
+    }
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+
+use crate::{
+    error::ApiError,
+    state::{ApiState, Response},
+};
+
+use smelter_api::{InputFailover, InputId};
+
+use super::Json;
+
+pub(super) async fn handle_input_failover(
+    State(api): State<Arc<ApiState>>,
+    Path(input_id): Path<InputId>,
+    Json(request): Json<InputFailover>,
+) -> Result<Response, ApiError> {
+    let input_id = smelter_render::InputId::from(input_id);
+    api.pipeline()?
+        .lock()
+        .unwrap()
+        .set_input_failover(&input_id, request.clone().into())?;
+    api.registry
+        .lock()
+        .unwrap()
+        .update_input_failover(&input_id, request);
+    Ok(Response::Ok {})
+}
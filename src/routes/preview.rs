@@ -0,0 +1,427 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    body::Body,
+    extract::{
+        Path, Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt, stream};
+use image::{RgbaImage, imageops::FilterType};
+use serde::Deserialize;
+use smelter_api::OutputId;
+use smelter_core::Pipeline;
+use smelter_render::{SnapshotSource, error::SnapshotError};
+use tokio::{
+    sync::{
+        Notify,
+        mpsc::{self, error::TrySendError},
+    },
+    time::Instant,
+};
+use tracing::{debug, warn};
+
+use crate::error::ApiError;
+
+use super::{
+    ApiState,
+    snapshot::{encode_jpeg, encoding_error, jpeg_quality, scaled_size},
+};
+
+const DEFAULT_PREVIEW_FRAMERATE: f64 = 5.0;
+const MAX_PREVIEW_FRAMERATE: f64 = 30.0;
+const DEFAULT_PREVIEW_WIDTH: u32 = 640;
+const DEFAULT_PREVIEW_JPEG_QUALITY: u8 = 60;
+const MJPEG_BOUNDARY: &str = "preview_frame";
+
+/// Frames that were not yet sent to the client. If the client is too slow,
+/// new frames are dropped.
+const PREVIEW_FRAME_BUFFER: usize = 2;
+
+#[derive(Debug, Deserialize)]
+pub(super) struct PreviewQuery {
+    /// (**default=`5`**) Maximal number of frames per second sent to the client.
+    fps: Option<f64>,
+    /// (**default=`640`**) Width of the preview. If only one of `width` and `height` is
+    /// defined, the other one is calculated to preserve the aspect ratio.
+    width: Option<u32>,
+    /// Height of the preview.
+    height: Option<u32>,
+    /// (**default=`60`**) JPEG quality in the `[1, 100]` range.
+    quality: Option<u8>,
+}
+
+/// Streams JPEG frames of an output as `multipart/x-mixed-replace` response that can be
+/// used directly as a source of the `<img>` element.
+pub(super) async fn handle_mjpeg_preview(
+    State(api): State<Arc<ApiState>>,
+    Path(output_id): Path<OutputId>,
+    Query(query): Query<PreviewQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let frames = start_preview(api, output_id, query).await?;
+    let parts = stream::unfold(frames, |mut frames| async move {
+        let jpeg = frames.recv().await?;
+        let mut part = format!(
+            "--{MJPEG_BOUNDARY}\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n",
+            jpeg.len()
+        )
+        .into_bytes();
+        part.extend_from_slice(&jpeg);
+        part.extend_from_slice(b"\r\n");
+        Some((Ok::<_, Infallible>(Bytes::from(part)), frames))
+    });
+    let content_type = format!("multipart/x-mixed-replace; boundary={MJPEG_BOUNDARY}");
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "no-cache".to_string()),
+        ],
+        Body::from_stream(parts),
+    ))
+}
+
+/// Streams JPEG frames of an output as binary WebSocket messages.
+pub(super) async fn handle_ws_preview(
+    ws: WebSocketUpgrade,
+    State(api): State<Arc<ApiState>>,
+    Path(output_id): Path<OutputId>,
+    Query(query): Query<PreviewQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let frames = start_preview(api, output_id, query).await?;
+    Ok(ws.on_upgrade(move |socket| send_ws_preview(socket, frames)))
+}
+
+async fn send_ws_preview(socket: WebSocket, mut frames: mpsc::Receiver<Vec<u8>>) {
+    let (mut socket_sender, mut socket_receiver) = socket.split();
+    loop {
+        let message = tokio::select! {
+            frame = frames.recv() => match frame {
+                Some(jpeg) => Message::Binary(jpeg),
+                None => Message::Close(None),
+            },
+            message = socket_receiver.next() => match message {
+                Some(Ok(Message::Ping(data))) => Message::Pong(data),
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(message)) => {
+                    debug!(?message, "Received preview ws message.");
+                    continue;
+                }
+            },
+        };
+        let is_close = matches!(message, Message::Close(_));
+        if let Err(err) = socket_sender.send(message).await {
+            debug!(%err, "WebSocket send error.");
+            return;
+        }
+        if is_close {
+            return;
+        }
+    }
+}
+
+/// Previews of outputs that have at least one client. All clients of an output share
+/// a single capture, so the last rendered frame is copied from the GPU once per tick
+/// regardless of the number of clients. Clients that request the same size and quality
+/// also share the encoded frame.
+#[derive(Default)]
+pub struct OutputPreviews(Mutex<HashMap<smelter_render::OutputId, Arc<OutputPreview>>>);
+
+/// Capture of a single output, runs as long as the output has any clients.
+struct OutputPreview {
+    output_id: smelter_render::OutputId,
+    clients: Mutex<Vec<PreviewClient>>,
+    /// Wakes up the capture when a client is added, so its first frame is not delayed
+    /// by clients with lower framerate.
+    client_added: Notify,
+}
+
+struct PreviewClient {
+    options: PreviewOptions,
+    frame_interval: Duration,
+    next_frame: Instant,
+    /// Client is due, its frame is being captured.
+    pending: bool,
+    sender: mpsc::Sender<Vec<u8>>,
+}
+
+/// Options of a preview that affect the encoded frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct PreviewOptions {
+    size: Option<(u32, u32)>,
+    quality: u8,
+}
+
+async fn start_preview(
+    api: Arc<ApiState>,
+    output_id: OutputId,
+    query: PreviewQuery,
+) -> Result<mpsc::Receiver<Vec<u8>>, ApiError> {
+    let output_id = smelter_render::OutputId::from(output_id);
+    let (options, frame_interval) = preview_options(&api, &output_id, query)?;
+    let (sender, frames) = mpsc::channel(PREVIEW_FRAME_BUFFER);
+    let client = PreviewClient {
+        options,
+        frame_interval,
+        next_frame: Instant::now(),
+        pending: false,
+        sender,
+    };
+
+    let mut previews = api.previews.0.lock().unwrap();
+    match previews.get(&output_id) {
+        Some(preview) => {
+            preview.clients.lock().unwrap().push(client);
+            preview.client_added.notify_one();
+        }
+        None => {
+            let preview = Arc::new(OutputPreview {
+                output_id: output_id.clone(),
+                clients: Mutex::new(vec![client]),
+                client_added: Notify::new(),
+            });
+            previews.insert(output_id, preview.clone());
+            tokio::spawn(preview.run(api.clone()));
+        }
+    }
+    Ok(frames)
+}
+
+fn preview_options(
+    api: &ApiState,
+    output_id: &smelter_render::OutputId,
+    query: PreviewQuery,
+) -> Result<(PreviewOptions, Duration), ApiError> {
+    let fps = query.fps.unwrap_or(DEFAULT_PREVIEW_FRAMERATE);
+    if !(fps > 0.0 && fps <= MAX_PREVIEW_FRAMERATE) {
+        return Err(ApiError::malformed_request(&format!(
+            "Preview framerate has to be in the (0, {MAX_PREVIEW_FRAMERATE}] range."
+        )));
+    }
+    let quality = jpeg_quality(query.quality, DEFAULT_PREVIEW_JPEG_QUALITY)?;
+
+    let source_resolution = {
+        let registry = api.registry.lock().unwrap();
+        let Some(output) = registry.outputs.get(output_id) else {
+            return Err(ApiError::new(
+                "OUTPUT_STREAM_NOT_FOUND",
+                format!("Output \"{output_id}\" does not exist."),
+                StatusCode::NOT_FOUND,
+            ));
+        };
+        match (&output.video, output.options.video_resolution()) {
+            (Some(_), Some(resolution)) => resolution,
+            _ => {
+                return Err(ApiError::new(
+                    "OUTPUT_WITHOUT_VIDEO",
+                    format!("Output \"{output_id}\" does not produce video."),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        }
+    };
+
+    let size = preview_size(
+        source_resolution.width as u32,
+        source_resolution.height as u32,
+        query.width,
+        query.height,
+    )?;
+
+    Ok((
+        PreviewOptions { size, quality },
+        Duration::from_secs_f64(1.0 / fps),
+    ))
+}
+
+impl OutputPreview {
+    /// Sends frames until all clients disconnect or the output is unregistered.
+    async fn run(self: Arc<Self>, api: Arc<ApiState>) {
+        loop {
+            let Some(next_frame) = self.next_frame() else {
+                match self.close_if_unused(&api) {
+                    true => break,
+                    false => continue,
+                }
+            };
+            tokio::select! {
+                _ = tokio::time::sleep_until(next_frame) => (),
+                _ = self.client_added.notified() => continue,
+            }
+            let preview = self.clone();
+            let result = {
+                let api = api.clone();
+                tokio::task::spawn_blocking(move || preview.send_frames(&api))
+                    .await
+                    // `unwrap()` panics only when the task panicked
+                    .unwrap()
+            };
+            if let Err(err) = result {
+                debug!(%err, output_id=%self.output_id, "Stopping output preview.");
+                self.close(&api);
+                break;
+            }
+        }
+        debug!(output_id=%self.output_id, "Preview closed.");
+    }
+
+    /// Time of the earliest frame of all connected clients.
+    fn next_frame(&self) -> Option<Instant> {
+        let mut clients = self.clients.lock().unwrap();
+        clients.retain(|client| !client.sender.is_closed());
+        clients.iter().map(|client| client.next_frame).min()
+    }
+
+    /// Captures the frame once and sends it to all clients that are due. Errors end
+    /// the preview of all clients.
+    fn send_frames(&self, api: &ApiState) -> Result<(), ApiError> {
+        let now = Instant::now();
+        let options: HashSet<PreviewOptions> = {
+            let mut clients = self.clients.lock().unwrap();
+            clients
+                .iter_mut()
+                .filter(|client| client.next_frame <= now)
+                .map(|client| {
+                    client.pending = true;
+                    client.next_frame += client.frame_interval;
+                    if client.next_frame < now {
+                        client.next_frame = now + client.frame_interval;
+                    }
+                    client.options
+                })
+                .collect()
+        };
+        if options.is_empty() {
+            return Ok(());
+        }
+
+        // Renderer and clients are not locked while the frame is downloaded and encoded.
+        let frames: HashMap<PreviewOptions, Vec<u8>> = match self.capture_frame(api)? {
+            Some(image) => options
+                .into_iter()
+                .filter_map(|options| Some((options, encode_frame(&image, options)?)))
+                .collect(),
+            None => HashMap::new(),
+        };
+
+        let mut clients = self.clients.lock().unwrap();
+        for client in clients.iter_mut().filter(|client| client.pending) {
+            client.pending = false;
+            let Some(jpeg) = frames.get(&client.options) else {
+                continue;
+            };
+            // Frames are dropped for slow clients, disconnected clients are removed
+            // before the next frame.
+            let _ = client.sender.try_send(jpeg.clone());
+        }
+        Ok(())
+    }
+
+    /// Returns `None` if the frame should be skipped.
+    fn capture_frame(&self, api: &ApiState) -> Result<Option<RgbaImage>, ApiError> {
+        let snapshot = match Pipeline::snapshot(
+            &api.pipeline()?,
+            SnapshotSource::Output(self.output_id.clone()),
+        ) {
+            Ok(snapshot) => snapshot,
+            Err(SnapshotError::NoFrame) => return Ok(None),
+            Err(err @ (SnapshotError::DownloadFailed(_) | SnapshotError::DownloadCanceled)) => {
+                warn!(%err, "Failed to download preview frame.");
+                return Ok(None);
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let image = RgbaImage::from_raw(
+            snapshot.resolution.width as u32,
+            snapshot.resolution.height as u32,
+            snapshot.data.to_vec(),
+        )
+        .ok_or_else(|| encoding_error("Frame does not match its resolution."))?;
+        Ok(Some(image))
+    }
+
+    /// Removes the preview if all clients disconnected. Previews are locked first,
+    /// so a client can't be added to a preview that is being removed.
+    fn close_if_unused(&self, api: &ApiState) -> bool {
+        let mut previews = api.previews.0.lock().unwrap();
+        let mut clients = self.clients.lock().unwrap();
+        clients.retain(|client| !client.sender.is_closed());
+        if !clients.is_empty() {
+            return false;
+        }
+        previews.remove(&self.output_id);
+        true
+    }
+
+    /// Removes the preview and disconnects all its clients.
+    fn close(&self, api: &ApiState) {
+        let mut previews = api.previews.0.lock().unwrap();
+        previews.remove(&self.output_id);
+        self.clients.lock().unwrap().clear();
+    }
+}
+
+/// Returns `None` if the frame should be skipped.
+fn encode_frame(image: &RgbaImage, options: PreviewOptions) -> Option<Vec<u8>> {
+    let image = match options.size {
+        Some((width, height)) => {
+            image::imageops::resize(image, width, height, FilterType::Triangle)
+        }
+        None => image.clone(),
+    };
+    match encode_jpeg(image, options.quality) {
+        Ok(jpeg) => Some(jpeg),
+        Err(err) => {
+            warn!(%err, "Failed to encode preview frame.");
+            None
+        }
+    }
+}
+
+/// Size the frames are scaled to, `None` if the output resolution should be used.
+fn preview_size(
+    source_width: u32,
+    source_height: u32,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> Result<Option<(u32, u32)>, ApiError> {
+    let width = match (width, height) {
+        (None, None) => Some(u32::min(source_width, DEFAULT_PREVIEW_WIDTH)),
+        (width, _) => width,
+    };
+    scaled_size(source_width, source_height, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_size() {
+        assert_eq!(
+            preview_size(1920, 1080, None, None).unwrap(),
+            Some((640, 360))
+        );
+        assert_eq!(
+            preview_size(320, 240, None, None).unwrap(),
+            Some((320, 240))
+        );
+        assert_eq!(
+            preview_size(1920, 1080, None, Some(540)).unwrap(),
+            Some((960, 540))
+        );
+        assert_eq!(
+            preview_size(1920, 1080, Some(100), Some(100)).unwrap(),
+            Some((100, 100))
+        );
+        assert!(preview_size(1920, 1080, Some(0), None).is_err());
+    }
+}
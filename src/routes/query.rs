@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Serialize;
+use serde_json::json;
+use smelter_api::{AudioScene, OutputId, VideoScene};
+
+use crate::{
+    error::ApiError,
+    routes::{RegisterInput, RegisterOutput},
+};
+
+use super::ApiState;
+
+#[derive(Serialize)]
+struct InputInfo {
+    input_id: String,
+    /// Request that was used to register the input, without credentials.
+    options: Option<RegisterInput>,
+}
+
+#[derive(Serialize)]
+struct OutputInfo {
+    output_id: String,
+    /// Request that was used to register the output, without credentials.
+    options: Option<RegisterOutput>,
+}
+
+#[derive(Serialize)]
+struct RendererInfo<T> {
+    id: String,
+    spec: T,
+}
+
+#[derive(Serialize)]
+struct OutputSceneInfo {
+    video: Option<VideoScene>,
+    audio: Option<AudioScene>,
+    /// Positions of components with an id, calculated for the last rendered frame.
+    layouts: Vec<ComponentLayoutInfo>,
+}
+
+#[derive(Serialize)]
+struct ComponentLayoutInfo {
+    component_id: String,
+    top: f32,
+    left: f32,
+    width: f32,
+    height: f32,
+}
+
+pub(super) async fn handle_inputs(
+    State(state): State<Arc<ApiState>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let pipeline = state.pipeline()?;
+    let pipeline = pipeline.lock().unwrap();
+    let registry = state.registry.lock().unwrap();
+
+    let inputs: Vec<InputInfo> = pipeline
+        .inputs()
+        .map(|(id, _)| InputInfo {
+            input_id: id.to_string(),
+            options: registry.inputs.get(id).map(RegisterInput::redacted),
+        })
+        .collect();
+
+    Ok(axum::Json(json!({ "inputs": inputs })))
+}
+
+pub(super) async fn handle_outputs(
+    State(state): State<Arc<ApiState>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let pipeline = state.pipeline()?;
+    let pipeline = pipeline.lock().unwrap();
+    let registry = state.registry.lock().unwrap();
+
+    let outputs: Vec<OutputInfo> = pipeline
+        .outputs()
+        .map(|(id, _)| OutputInfo {
+            output_id: id.to_string(),
+            options: registry
+                .outputs
+                .get(id)
+                .map(|output| output.options.redacted()),
+        })
+        .collect();
+
+    Ok(axum::Json(json!({ "outputs": outputs })))
+}
+
+pub(super) async fn handle_output_scene(
+    State(state): State<Arc<ApiState>>,
+    Path(output_id): Path<OutputId>,
+) -> Result<impl IntoResponse, ApiError> {
+    let output_id = smelter_render::OutputId::from(output_id);
+    let pipeline = state.pipeline()?;
+    let pipeline = pipeline.lock().unwrap();
+    let registry = state.registry.lock().unwrap();
+
+    let Some(output) = registry.outputs.get(&output_id) else {
+        return Err(ApiError::new(
+            "OUTPUT_STREAM_NOT_FOUND",
+            format!("Output \"{output_id}\" does not exist."),
+            StatusCode::NOT_FOUND,
+        ));
+    };
+    let layouts = pipeline
+        .component_layouts(&output_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|layout| ComponentLayoutInfo {
+            component_id: layout.component_id.to_string(),
+            top: layout.top,
+            left: layout.left,
+            width: layout.width,
+            height: layout.height,
+        })
+        .collect();
+
+    Ok(axum::Json(OutputSceneInfo {
+        video: output.video.clone(),
+        audio: output.audio.clone(),
+        layouts,
+    }))
+}
+
+macro_rules! renderers_handler {
+    ($name:ident, $field:ident) => {
+        pub(super) async fn $name(
+            State(state): State<Arc<ApiState>>,
+        ) -> Result<impl IntoResponse, ApiError> {
+            let registry = state.registry.lock().unwrap();
+            let renderers: Vec<_> = registry
+                .$field
+                .iter()
+                .map(|(id, spec)| RendererInfo {
+                    id: id.to_string(),
+                    spec: spec.clone(),
+                })
+                .collect();
+            Ok(axum::Json(json!({ (stringify!($field)): renderers })))
+        }
+    };
+}
+
+renderers_handler!(handle_images, images);
+renderers_handler!(handle_shaders, shaders);
+renderers_handler!(handle_web_renderers, web_renderers);
+renderers_handler!(handle_video_clips, video_clips);
+renderers_handler!(handle_fragments, fragments);
@@ -1,20 +1,33 @@
-use std::sync::Arc;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use axum::extract::{Path, State};
 use glyphon::fontdb::Source;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-use smelter_core::{InputInitInfo, Pipeline, protocols::Port};
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{DeserializeOwned, Error as _},
+};
+use smelter_core::{
+    InputInitInfo, Pipeline, PreparedInput, PreparedOutput, RegisterInputOptions,
+    RegisterOutputOptions,
+    protocols::{Port, VideoClipOptions},
+};
+use smelter_render::{PreparedRenderer, RendererSpec};
+use tracing::error;
 
 use crate::{
     error::ApiError,
     routes::{Json, Multipart},
-    state::Response,
+    state::{RegisteredOutput, Response},
 };
 use smelter_api::{
-    DeckLink, HlsInput, HlsOutput, ImageSpec, InputId, Mp4Input, Mp4Output, OutputId, RendererId,
-    RtmpInput, RtmpOutput, RtpInput, RtpOutput, ShaderSpec, V4l2Input, WebRendererSpec, WhepInput,
-    WhepOutput, WhipInput, WhipOutput,
+    DeckLink, FragmentSpec, HlsInput, HlsOutput, ImageSpec, InputId, Mp4Input, Mp4Output, OutputId,
+    PlaylistInput, RendererId, ReplayInput, RtmpInput, RtmpOutput, RtpInput, RtpOutput, ShaderSpec,
+    TypeError, V4l2Input, VideoClipSpec, WebRendererSpec, WhepInput, WhepOutput, WhipInput,
+    WhipOutput,
 };
 
 use super::ApiState;
@@ -25,6 +38,8 @@ pub enum RegisterInput {
     RtpStream(RtpInput),
     RtmpServer(RtmpInput),
     Mp4(Mp4Input),
+    Playlist(PlaylistInput),
+    Replay(ReplayInput),
     WhipServer(WhipInput),
     WhepClient(WhepInput),
     Hls(HlsInput),
@@ -44,135 +59,358 @@ pub enum RegisterOutput {
     Hls(HlsOutput),
 }
 
+impl TryFrom<RegisterInput> for RegisterInputOptions {
+    type Error = TypeError;
+
+    fn try_from(value: RegisterInput) -> Result<Self, Self::Error> {
+        match value {
+            RegisterInput::RtpStream(rtp) => rtp.try_into(),
+            RegisterInput::RtmpServer(rtmp) => rtmp.try_into(),
+            RegisterInput::Mp4(mp4) => mp4.try_into(),
+            RegisterInput::Playlist(playlist) => playlist.try_into(),
+            RegisterInput::Replay(replay) => replay.try_into(),
+            RegisterInput::DeckLink(decklink) => decklink.try_into(),
+            RegisterInput::WhipServer(whip) => whip.try_into(),
+            RegisterInput::WhepClient(whep) => whep.try_into(),
+            RegisterInput::Hls(hls) => hls.try_into(),
+            RegisterInput::V4l2(v4l2) => v4l2.try_into(),
+        }
+    }
+}
+
+impl TryFrom<RegisterOutput> for RegisterOutputOptions {
+    type Error = TypeError;
+
+    fn try_from(value: RegisterOutput) -> Result<Self, Self::Error> {
+        match value {
+            RegisterOutput::RtpStream(rtp) => rtp.try_into(),
+            RegisterOutput::Mp4(mp4) => mp4.try_into(),
+            RegisterOutput::WhipClient(whip) => whip.try_into(),
+            RegisterOutput::WhepServer(whep) => whep.try_into(),
+            RegisterOutput::RtmpClient(rtmp) => rtmp.try_into(),
+            RegisterOutput::Hls(hls) => hls.try_into(),
+        }
+    }
+}
+
+/// Fields accepted by all register requests in addition to the registered entity.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct RegisterSchedule {
+    /// Time in milliseconds when this request should be applied. Value `0` represents
+    /// time of the start request.
+    ///
+    /// Registered entity is initialized right away and the response is sent before it
+    /// is added to the pipeline. It is used starting from a frame with exactly that time.
+    pub schedule_time_ms: Option<f64>,
+}
+
+/// Register request that can be applied at a specific time.
+#[derive(Debug)]
+pub(super) struct Scheduled<T> {
+    schedule: RegisterSchedule,
+    request: T,
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Scheduled<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // `schedule_time_ms` is removed before the request is deserialized, so request
+        // types with `deny_unknown_fields` still reject unknown fields. `#[serde(flatten)]`
+        // can't be used here because it silently ignores them.
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        let schedule_time_ms = value
+            .as_object_mut()
+            .and_then(|fields| fields.remove("schedule_time_ms"));
+        let schedule = RegisterSchedule {
+            schedule_time_ms: schedule_time_ms
+                .map(serde_json::from_value::<Option<f64>>)
+                .transpose()
+                .map_err(D::Error::custom)?
+                .flatten(),
+        };
+        let request = serde_json::from_value(value).map_err(D::Error::custom)?;
+        Ok(Self { schedule, request })
+    }
+}
+
 pub(super) async fn handle_input(
     State(api): State<Arc<ApiState>>,
     Path(input_id): Path<InputId>,
-    Json(request): Json<RegisterInput>,
+    Json(Scheduled { request, schedule }): Json<Scheduled<RegisterInput>>,
 ) -> Result<Response, ApiError> {
-    let api = api.clone();
-    tokio::task::spawn_blocking(move || {
-        let response = match request {
-            RegisterInput::RtpStream(rtp) => {
-                Pipeline::register_input(&api.pipeline()?, input_id.into(), rtp.try_into()?)?
-            }
-            RegisterInput::RtmpServer(rtmp) => {
-                Pipeline::register_input(&api.pipeline()?, input_id.into(), rtmp.try_into()?)?
-            }
-            RegisterInput::Mp4(mp4) => {
-                Pipeline::register_input(&api.pipeline()?, input_id.into(), mp4.try_into()?)?
-            }
-            RegisterInput::DeckLink(decklink) => {
-                Pipeline::register_input(&api.pipeline()?, input_id.into(), decklink.try_into()?)?
-            }
-            RegisterInput::WhipServer(whip) => {
-                Pipeline::register_input(&api.pipeline()?, input_id.into(), whip.try_into()?)?
-            }
-            RegisterInput::WhepClient(whep) => {
-                Pipeline::register_input(&api.pipeline()?, input_id.into(), whep.try_into()?)?
-            }
-            RegisterInput::Hls(hls) => {
-                Pipeline::register_input(&api.pipeline()?, input_id.into(), hls.try_into()?)?
-            }
-            RegisterInput::V4l2(v4l2) => {
-                Pipeline::register_input(&api.pipeline()?, input_id.into(), v4l2.try_into()?)?
-            }
-        };
-        match response {
-            InputInitInfo::Rtp { port } => Ok(Response::RegisteredPort {
+    let registered = (input_id.clone().into(), request.clone());
+    let options: RegisterInputOptions = request.try_into()?;
+    let registry = api.registry.clone();
+    let prepare = move |pipeline: &Arc<Mutex<Pipeline>>| {
+        let (prepared, init_info) = Pipeline::prepare_input(pipeline, input_id.into(), options)?;
+        let response = match init_info {
+            InputInitInfo::Rtp { port } => Response::RegisteredPort {
                 port: port.map(|p| p.0),
-            }),
+            },
             InputInitInfo::Mp4 {
                 video_duration,
                 audio_duration,
-            } => Ok(Response::RegisteredMp4 {
+            } => Response::RegisteredMp4 {
                 video_duration_ms: video_duration.map(|v| v.as_millis() as u64),
                 audio_duration_ms: audio_duration.map(|a| a.as_millis() as u64),
-            }),
-            InputInitInfo::Whip { bearer_token } => Ok(Response::BearerToken { bearer_token }),
-            InputInitInfo::Other => Ok(Response::Ok {}),
-        }
-    })
-    .await
-    // `unwrap()` panics only when the task panicked or `response.abort()` was called
-    .unwrap()
+            },
+            InputInitInfo::Whip { bearer_token } => Response::BearerToken { bearer_token },
+            InputInitInfo::Other => Response::Ok {},
+        };
+        Ok((prepared, response))
+    };
+    let register = move |pipeline: &mut Pipeline, prepared: PreparedInput| {
+        pipeline.register_prepared_input(prepared)?;
+        let (input_id, options) = registered;
+        registry.lock().unwrap().inputs.insert(input_id, options);
+        Ok(())
+    };
+    run_register(&api, schedule.schedule_time_ms, "input", prepare, register).await
 }
 
 pub(super) async fn handle_output(
     State(api): State<Arc<ApiState>>,
     Path(output_id): Path<OutputId>,
-    Json(request): Json<RegisterOutput>,
+    Json(Scheduled { request, schedule }): Json<Scheduled<RegisterOutput>>,
 ) -> Result<Response, ApiError> {
-    let api = api.clone();
-    tokio::task::spawn_blocking(move || {
-        let response = match request {
-            RegisterOutput::RtpStream(rtp) => {
-                Pipeline::register_output(&api.pipeline()?, output_id.into(), rtp.try_into()?)?
-            }
-            RegisterOutput::Mp4(mp4) => {
-                Pipeline::register_output(&api.pipeline()?, output_id.into(), mp4.try_into()?)?
-            }
-            RegisterOutput::WhipClient(whip) => {
-                Pipeline::register_output(&api.pipeline()?, output_id.into(), whip.try_into()?)?
-            }
-            RegisterOutput::WhepServer(whep) => {
-                Pipeline::register_output(&api.pipeline()?, output_id.into(), whep.try_into()?)?
-            }
-            RegisterOutput::RtmpClient(rtmp) => {
-                Pipeline::register_output(&api.pipeline()?, output_id.into(), rtmp.try_into()?)?
-            }
-            RegisterOutput::Hls(hls) => {
-                Pipeline::register_output(&api.pipeline()?, output_id.into(), hls.try_into()?)?
-            }
+    let registered = (output_id.clone().into(), request.clone());
+    let options: RegisterOutputOptions = request.try_into()?;
+    let registry = api.registry.clone();
+    let prepare = move |pipeline: &Arc<Mutex<Pipeline>>| {
+        let (prepared, port) = Pipeline::prepare_output(pipeline, output_id.into(), options)?;
+        let response = match port {
+            Some(Port(port)) => Response::RegisteredPort { port: Some(port) },
+            None => Response::Ok {},
         };
-        match response {
-            Some(Port(port)) => Ok(Response::RegisteredPort { port: Some(port) }),
-            None => Ok(Response::Ok {}),
-        }
-    })
-    .await
-    .unwrap()
+        Ok((prepared, response))
+    };
+    let register = move |pipeline: &mut Pipeline, prepared: PreparedOutput| {
+        pipeline.register_prepared_output(prepared)?;
+        let (output_id, options) = registered;
+        registry
+            .lock()
+            .unwrap()
+            .outputs
+            .insert(output_id, RegisteredOutput::new(options));
+        Ok(())
+    };
+    run_register(&api, schedule.schedule_time_ms, "output", prepare, register).await
 }
 
 pub(super) async fn handle_shader(
     State(api): State<Arc<ApiState>>,
     Path(shader_id): Path<RendererId>,
-    Json(request): Json<ShaderSpec>,
+    Json(Scheduled { request, schedule }): Json<Scheduled<ShaderSpec>>,
 ) -> Result<Response, ApiError> {
-    let api = api.clone();
-    tokio::task::spawn_blocking(move || {
-        Pipeline::register_renderer(&api.pipeline()?, shader_id.into(), request.try_into()?)?;
-        Ok(Response::Ok {})
-    })
+    let spec: RendererSpec = request.clone().try_into()?;
+    let registry = api.registry.clone();
+    let renderer_id = shader_id.clone().into();
+    let prepare = move |pipeline: &Arc<Mutex<Pipeline>>| {
+        Ok(Pipeline::prepare_renderer(pipeline, renderer_id, spec)?)
+    };
+    let on_registered = move || {
+        registry
+            .lock()
+            .unwrap()
+            .shaders
+            .insert(shader_id.into(), request);
+    };
+    run_register_renderer(
+        &api,
+        schedule.schedule_time_ms,
+        "shader",
+        prepare,
+        on_registered,
+    )
     .await
-    .unwrap()
 }
 
 pub(super) async fn handle_web_renderer(
     State(api): State<Arc<ApiState>>,
     Path(instance_id): Path<RendererId>,
-    Json(request): Json<WebRendererSpec>,
+    Json(Scheduled { request, schedule }): Json<Scheduled<WebRendererSpec>>,
 ) -> Result<Response, ApiError> {
-    let api = api.clone();
-    tokio::task::spawn_blocking(move || {
-        Pipeline::register_renderer(&api.pipeline()?, instance_id.into(), request.try_into()?)?;
-        Ok(Response::Ok {})
-    })
+    let spec: RendererSpec = request.clone().try_into()?;
+    let registry = api.registry.clone();
+    let renderer_id = instance_id.clone().into();
+    let prepare = move |pipeline: &Arc<Mutex<Pipeline>>| {
+        Ok(Pipeline::prepare_renderer(pipeline, renderer_id, spec)?)
+    };
+    let on_registered = move || {
+        registry
+            .lock()
+            .unwrap()
+            .web_renderers
+            .insert(instance_id.into(), request);
+    };
+    run_register_renderer(
+        &api,
+        schedule.schedule_time_ms,
+        "web renderer",
+        prepare,
+        on_registered,
+    )
     .await
-    .unwrap()
 }
 
 pub(super) async fn handle_image(
     State(api): State<Arc<ApiState>>,
     Path(image_id): Path<RendererId>,
-    Json(request): Json<ImageSpec>,
+    Json(Scheduled { request, schedule }): Json<Scheduled<ImageSpec>>,
 ) -> Result<Response, ApiError> {
-    let api = api.clone();
-    tokio::task::spawn_blocking(move || {
-        Pipeline::register_renderer(&api.pipeline()?, image_id.into(), request.try_into()?)?;
-        Ok(Response::Ok {})
-    })
+    let spec: RendererSpec = request.clone().try_into()?;
+    let registry = api.registry.clone();
+    let renderer_id = image_id.clone().into();
+    let prepare = move |pipeline: &Arc<Mutex<Pipeline>>| {
+        Ok(Pipeline::prepare_renderer(pipeline, renderer_id, spec)?)
+    };
+    let on_registered = move || {
+        registry
+            .lock()
+            .unwrap()
+            .images
+            .insert(image_id.into(), request);
+    };
+    run_register_renderer(
+        &api,
+        schedule.schedule_time_ms,
+        "image",
+        prepare,
+        on_registered,
+    )
     .await
-    .unwrap()
+}
+
+pub(super) async fn handle_video_clip(
+    State(api): State<Arc<ApiState>>,
+    Path(clip_id): Path<RendererId>,
+    Json(Scheduled { request, schedule }): Json<Scheduled<VideoClipSpec>>,
+) -> Result<Response, ApiError> {
+    let options: VideoClipOptions = request.clone().try_into()?;
+    let registry = api.registry.clone();
+    let renderer_id = clip_id.clone().into();
+    let prepare = move |pipeline: &Arc<Mutex<Pipeline>>| {
+        Ok(Pipeline::prepare_video_clip(
+            pipeline,
+            renderer_id,
+            options,
+        )?)
+    };
+    let on_registered = move || {
+        registry
+            .lock()
+            .unwrap()
+            .video_clips
+            .insert(clip_id.into(), request);
+    };
+    run_register_renderer(
+        &api,
+        schedule.schedule_time_ms,
+        "video clip",
+        prepare,
+        on_registered,
+    )
+    .await
+}
+
+pub(super) async fn handle_fragment(
+    State(api): State<Arc<ApiState>>,
+    Path(fragment_id): Path<RendererId>,
+    Json(Scheduled { request, schedule }): Json<Scheduled<FragmentSpec>>,
+) -> Result<Response, ApiError> {
+    let spec: RendererSpec = request.clone().try_into()?;
+    let registry = api.registry.clone();
+    let renderer_id = fragment_id.clone().into();
+    let prepare = move |pipeline: &Arc<Mutex<Pipeline>>| {
+        Ok(Pipeline::prepare_renderer(pipeline, renderer_id, spec)?)
+    };
+    let on_registered = move || {
+        registry
+            .lock()
+            .unwrap()
+            .fragments
+            .insert(fragment_id.into(), request);
+    };
+    run_register_renderer(
+        &api,
+        schedule.schedule_time_ms,
+        "fragment",
+        prepare,
+        on_registered,
+    )
+    .await
+}
+
+/// Converts `schedule_time_ms` to a pts. Rejects values that can't be represented
+/// as a [`Duration`].
+pub(super) fn schedule_time(schedule_time_ms: f64) -> Result<Duration, TypeError> {
+    Duration::try_from_secs_f64(schedule_time_ms / 1000.0).map_err(|_| {
+        TypeError::new(
+            "\"schedule_time_ms\" has to be a non-negative number that is not larger than the max supported time.",
+        )
+    })
+}
+
+/// Registers an entity right away or schedules the registration for `schedule_time_ms`.
+/// The entity is initialized (e.g. transport and decoders of an input, or a decoded
+/// video clip) before the response is sent, only adding it to the pipeline is scheduled.
+/// That way it is used exactly from the scheduled time and the queue is not blocked.
+/// Errors of a scheduled registration are only logged.
+async fn run_register<T, P, R>(
+    api: &ApiState,
+    schedule_time_ms: Option<f64>,
+    kind: &'static str,
+    prepare: P,
+    register: R,
+) -> Result<Response, ApiError>
+where
+    T: Send + 'static,
+    P: FnOnce(&Arc<Mutex<Pipeline>>) -> Result<(T, Response), ApiError> + Send + 'static,
+    R: FnOnce(&mut Pipeline, T) -> Result<(), ApiError> + Send + 'static,
+{
+    let pipeline = api.pipeline()?;
+    let schedule_time = schedule_time_ms.map(schedule_time).transpose()?;
+    let (prepared, response) = {
+        let pipeline = pipeline.clone();
+        tokio::task::spawn_blocking(move || prepare(&pipeline))
+            .await
+            // `unwrap()` panics only when the task panicked or `response.abort()` was called
+            .unwrap()?
+    };
+    match schedule_time {
+        Some(schedule_time) => {
+            Pipeline::schedule_event(&pipeline, schedule_time, move |pipeline| {
+                if let Err(err) = register(pipeline, prepared) {
+                    error!(
+                        "Error while running scheduled {kind} register for pts {}ms: {}",
+                        schedule_time.as_millis(),
+                        err.stack.join("\n")
+                    );
+                }
+            });
+        }
+        None => register(&mut pipeline.lock().unwrap(), prepared)?,
+    }
+    Ok(response)
+}
+
+async fn run_register_renderer<P, R>(
+    api: &ApiState,
+    schedule_time_ms: Option<f64>,
+    kind: &'static str,
+    prepare: P,
+    on_registered: R,
+) -> Result<Response, ApiError>
+where
+    P: FnOnce(&Arc<Mutex<Pipeline>>) -> Result<PreparedRenderer, ApiError> + Send + 'static,
+    R: FnOnce() + Send + 'static,
+{
+    let prepare = move |pipeline: &Arc<Mutex<Pipeline>>| Ok((prepare(pipeline)?, Response::Ok {}));
+    let register = move |pipeline: &mut Pipeline, prepared: PreparedRenderer| {
+        pipeline.register_prepared_renderer(prepared)?;
+        on_registered();
+        Ok(())
+    };
+    run_register(api, schedule_time_ms, kind, prepare, register).await
 }
 
 pub(super) async fn handle_font(
@@ -204,3 +442,111 @@ pub(super) async fn handle_font(
     .await
     .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::{
+        body::Body,
+        extract::{FromRequest, Request},
+        http::StatusCode,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn schedule_time_conversion() {
+        assert_eq!(schedule_time(0.0).unwrap(), Duration::ZERO);
+        assert_eq!(schedule_time(1500.0).unwrap(), Duration::from_millis(1500));
+        assert!(schedule_time(-1.0).is_err());
+        assert!(schedule_time(f64::NAN).is_err());
+        assert!(schedule_time(f64::INFINITY).is_err());
+        assert!(schedule_time(1e300).is_err());
+    }
+
+    #[test]
+    fn scheduled_request_parses_schedule_time() {
+        let request: Scheduled<RegisterInput> = serde_json::from_value(json!({
+            "type": "whip_server",
+            "bearer_token": "token",
+            "schedule_time_ms": 100.0,
+        }))
+        .unwrap();
+        assert_eq!(request.schedule.schedule_time_ms, Some(100.0));
+
+        let request: Scheduled<ShaderSpec> =
+            serde_json::from_value(json!({ "source": "" })).unwrap();
+        assert_eq!(request.schedule.schedule_time_ms, None);
+    }
+
+    async fn post<T: DeserializeOwned>(body: serde_json::Value) -> Result<T, StatusCode> {
+        let request = Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        Json::<T>::from_request(request, &())
+            .await
+            .map(|Json(value)| value)
+            .map_err(|(status, _)| status)
+    }
+
+    #[tokio::test]
+    async fn scheduled_request_rejects_unknown_fields() {
+        let shader = post::<Scheduled<ShaderSpec>>(json!({
+            "source": "",
+            "schedule_time_ms": 100.0,
+        }))
+        .await
+        .unwrap();
+        assert_eq!(shader.schedule.schedule_time_ms, Some(100.0));
+
+        let shader = post::<Scheduled<ShaderSpec>>(json!({
+            "source": "",
+            "schedule_time_ms": 100.0,
+            "unknown": 1,
+        }))
+        .await;
+        assert_eq!(shader.unwrap_err(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let web_renderer = post::<Scheduled<WebRendererSpec>>(json!({
+            "url": "https://example.com",
+            "resolution": { "width": 1280, "height": 720 },
+            "unknown": 1,
+        }))
+        .await;
+        assert_eq!(web_renderer.unwrap_err(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let image = post::<Scheduled<ImageSpec>>(json!({
+            "asset_type": "png",
+            "path": "image.png",
+            "unknown": 1,
+        }))
+        .await;
+        assert_eq!(image.unwrap_err(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let video_clip = post::<Scheduled<VideoClipSpec>>(json!({
+            "path": "clip.mp4",
+            "unknown": 1,
+        }))
+        .await;
+        assert_eq!(video_clip.unwrap_err(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let fragment = post::<Scheduled<FragmentSpec>>(json!({
+            "root": { "type": "view" },
+            "unknown": 1,
+        }))
+        .await;
+        assert_eq!(fragment.unwrap_err(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let input = post::<Scheduled<RegisterInput>>(json!({
+            "type": "rtp_stream",
+            "port": 8000,
+            "unknown": 1,
+        }))
+        .await;
+        assert_eq!(input.unwrap_err(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
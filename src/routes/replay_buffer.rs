@@ -0,0 +1,53 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::extract::{Path, State};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smelter_api::TypeError;
+use smelter_core::protocols::ReplayBufferOptions;
+
+use crate::{
+    error::ApiError,
+    state::{ApiState, Response},
+};
+
+use smelter_api::InputId;
+
+use super::Json;
+
+const DEFAULT_MAX_MEMORY_MB: f64 = 256.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayBufferRequest {
+    /// Duration in milliseconds of the most recent media kept in the buffer.
+    pub duration_ms: f64,
+    /// (**default=`256`**) Limit of memory in megabytes used by buffered encoded
+    /// chunks. When it is exceeded, the oldest media is dropped.
+    pub max_memory_mb: Option<f64>,
+}
+
+pub(super) async fn handle_replay_buffer(
+    State(api): State<Arc<ApiState>>,
+    Path(input_id): Path<InputId>,
+    Json(request): Json<ReplayBufferRequest>,
+) -> Result<Response, ApiError> {
+    let input_id = smelter_render::InputId::from(input_id);
+    let duration = Duration::try_from_secs_f64(request.duration_ms / 1000.0)
+        .ok()
+        .filter(|duration| !duration.is_zero())
+        .ok_or_else(|| TypeError::new("\"duration_ms\" has to be a positive number."))?;
+    let max_memory_mb = request.max_memory_mb.unwrap_or(DEFAULT_MAX_MEMORY_MB);
+    if !(max_memory_mb > 0.0 && max_memory_mb.is_finite()) {
+        return Err(TypeError::new("\"max_memory_mb\" has to be a positive number.").into());
+    }
+    let options = ReplayBufferOptions {
+        duration,
+        max_memory_bytes: (max_memory_mb * 1024.0 * 1024.0) as usize,
+    };
+    api.pipeline()?
+        .lock()
+        .unwrap()
+        .enable_replay_buffer(&input_id, options)?;
+    Ok(Response::Ok {})
+}
@@ -0,0 +1,198 @@
+use std::{io::Cursor, sync::Arc};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use image::{DynamicImage, ImageOutputFormat, RgbaImage, imageops::FilterType};
+use serde::Deserialize;
+use smelter_api::{InputId, OutputId};
+use smelter_core::Pipeline;
+use smelter_render::{RgbaSnapshot, SnapshotSource};
+
+use crate::error::ApiError;
+
+use super::ApiState;
+
+const DEFAULT_JPEG_QUALITY: u8 = 80;
+
+#[derive(Debug, Deserialize)]
+pub(super) struct SnapshotQuery {
+    /// (**default=`"png"`**) Format of the returned image.
+    format: Option<SnapshotFormat>,
+    /// Width of the returned image. If only one of `width` and `height` is defined, the other
+    /// one is calculated to preserve the aspect ratio. Values larger than the resolution
+    /// of the source are capped, snapshots are never upscaled.
+    width: Option<u32>,
+    /// Height of the returned image.
+    height: Option<u32>,
+    /// (**default=`80`**) JPEG quality in the `[1, 100]` range.
+    quality: Option<u8>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum SnapshotFormat {
+    Png,
+    Jpeg,
+}
+
+pub(super) async fn handle_output_snapshot(
+    State(api): State<Arc<ApiState>>,
+    Path(output_id): Path<OutputId>,
+    Query(query): Query<SnapshotQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    snapshot(api, SnapshotSource::Output(output_id.into()), query).await
+}
+
+pub(super) async fn handle_input_snapshot(
+    State(api): State<Arc<ApiState>>,
+    Path(input_id): Path<InputId>,
+    Query(query): Query<SnapshotQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    snapshot(api, SnapshotSource::Input(input_id.into()), query).await
+}
+
+async fn snapshot(
+    api: Arc<ApiState>,
+    source: SnapshotSource,
+    query: SnapshotQuery,
+) -> Result<impl IntoResponse, ApiError> {
+    tokio::task::spawn_blocking(move || {
+        let snapshot = Pipeline::snapshot(&api.pipeline()?, source)?;
+        let format = query.format.unwrap_or(SnapshotFormat::Png);
+        let content_type = match format {
+            SnapshotFormat::Png => "image/png",
+            SnapshotFormat::Jpeg => "image/jpeg",
+        };
+        let body = encode_snapshot(snapshot, format, &query)?;
+        Ok(([(header::CONTENT_TYPE, content_type)], body))
+    })
+    .await
+    // `unwrap()` panics only when the task panicked or `response.abort()` was called
+    .unwrap()
+}
+
+fn encode_snapshot(
+    snapshot: RgbaSnapshot,
+    format: SnapshotFormat,
+    query: &SnapshotQuery,
+) -> Result<Vec<u8>, ApiError> {
+    let (width, height) = (
+        snapshot.resolution.width as u32,
+        snapshot.resolution.height as u32,
+    );
+    let image = RgbaImage::from_raw(width, height, snapshot.data.to_vec())
+        .ok_or_else(|| encoding_error("Snapshot does not match its resolution."))?;
+
+    let image = match scaled_size(width, height, query.width, query.height)? {
+        Some((width, height)) => {
+            image::imageops::resize(&image, width, height, FilterType::Triangle)
+        }
+        None => image,
+    };
+
+    match format {
+        SnapshotFormat::Png => {
+            let mut bytes = Cursor::new(Vec::new());
+            image
+                .write_to(&mut bytes, ImageOutputFormat::Png)
+                .map_err(|err| encoding_error(&format!("Failed to encode snapshot: {err}")))?;
+            Ok(bytes.into_inner())
+        }
+        SnapshotFormat::Jpeg => {
+            let quality = jpeg_quality(query.quality, DEFAULT_JPEG_QUALITY)?;
+            encode_jpeg(image, quality)
+        }
+    }
+}
+
+pub(super) fn jpeg_quality(quality: Option<u8>, default: u8) -> Result<u8, ApiError> {
+    let quality = quality.unwrap_or(default);
+    if !(1..=100).contains(&quality) {
+        return Err(ApiError::malformed_request(
+            &"JPEG quality has to be in the [1, 100] range.",
+        ));
+    }
+    Ok(quality)
+}
+
+pub(super) fn encode_jpeg(image: RgbaImage, quality: u8) -> Result<Vec<u8>, ApiError> {
+    let mut bytes = Cursor::new(Vec::new());
+    DynamicImage::ImageRgba8(image)
+        .to_rgb8()
+        .write_to(&mut bytes, ImageOutputFormat::Jpeg(quality))
+        .map_err(|err| encoding_error(&format!("Failed to encode JPEG image: {err}")))?;
+    Ok(bytes.into_inner())
+}
+
+pub(super) fn encoding_error(message: &str) -> ApiError {
+    ApiError::new(
+        "SNAPSHOT_ENCODING_ERROR",
+        message.to_string(),
+        StatusCode::INTERNAL_SERVER_ERROR,
+    )
+}
+
+/// Size the image is scaled to, `None` if the source resolution should be used. Requested
+/// dimensions are capped at the source resolution.
+pub(super) fn scaled_size(
+    width: u32,
+    height: u32,
+    requested_width: Option<u32>,
+    requested_height: Option<u32>,
+) -> Result<Option<(u32, u32)>, ApiError> {
+    if requested_width == Some(0) || requested_height == Some(0) {
+        return Err(ApiError::malformed_request(
+            &"Snapshot width and height have to be positive.",
+        ));
+    }
+    let requested_width = requested_width.map(|w| w.min(width));
+    let requested_height = requested_height.map(|h| h.min(height));
+    let scale = |value: u32, target: u32, source: u32| {
+        u32::max(
+            (value as f64 * target as f64 / source as f64).round() as u32,
+            1,
+        )
+    };
+    let size = match (requested_width, requested_height) {
+        (None, None) => None,
+        (Some(w), Some(h)) => Some((w, h)),
+        (Some(w), None) => Some((w, scale(height, w, width))),
+        (None, Some(h)) => Some((scale(width, h, height), h)),
+    };
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled_size() {
+        assert_eq!(scaled_size(1920, 1080, None, None).unwrap(), None);
+        assert_eq!(
+            scaled_size(1920, 1080, Some(640), None).unwrap(),
+            Some((640, 360))
+        );
+        assert_eq!(
+            scaled_size(1920, 1080, None, Some(540)).unwrap(),
+            Some((960, 540))
+        );
+        assert_eq!(
+            scaled_size(1920, 1080, Some(100), Some(100)).unwrap(),
+            Some((100, 100))
+        );
+        // snapshots are not upscaled
+        assert_eq!(
+            scaled_size(1280, 720, Some(100_000), None).unwrap(),
+            Some((1280, 720))
+        );
+        assert_eq!(
+            scaled_size(1280, 720, Some(4000), Some(360)).unwrap(),
+            Some((1280, 360))
+        );
+        assert!(scaled_size(1920, 1080, None, Some(0)).is_err());
+    }
+}
@@ -58,6 +58,8 @@ pub(super) async fn status_handler(
                 InputProtocolKind::Rtp => "rtp",
                 InputProtocolKind::Rtmp => "rtmp",
                 InputProtocolKind::Mp4 => "mp4",
+                InputProtocolKind::Playlist => "playlist",
+                InputProtocolKind::Replay => "replay",
                 InputProtocolKind::Whip => "whip",
                 InputProtocolKind::Whep => "whep",
                 InputProtocolKind::Hls => "hls",
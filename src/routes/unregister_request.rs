@@ -1,4 +1,4 @@
-use std::{sync::Arc, time::Duration};
+use std::sync::Arc;
 
 use axum::extract::{Path, State};
 use serde::{Deserialize, Serialize};
@@ -13,7 +13,7 @@ use crate::{
 
 use smelter_api::{InputId, OutputId, RendererId};
 
-use super::Json;
+use super::{Json, register_request::schedule_time};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct UnregisterInput {
@@ -41,24 +41,29 @@ pub(super) async fn handle_input(
     Path(input_id): Path<InputId>,
     Json(request): Json<UnregisterInput>,
 ) -> Result<Response, ApiError> {
+    let input_id = smelter_render::InputId::from(input_id);
+    let registry = api.registry.clone();
     match request.schedule_time_ms {
         Some(schedule_time_ms) => {
-            let schedule_time = Duration::from_secs_f64(schedule_time_ms / 1000.0);
+            let schedule_time = schedule_time(schedule_time_ms)?;
             Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
-                if let Err(err) = pipeline.unregister_input(&input_id.into()) {
+                if let Err(err) = pipeline.unregister_input(&input_id) {
                     error!(
                         "Error while running scheduled input unregister for pts {}ms: {}",
                         schedule_time.as_millis(),
                         ErrorStack::new(&err).into_string()
-                    )
+                    );
+                    return;
                 }
+                registry.lock().unwrap().inputs.remove(&input_id);
             });
         }
         None => {
             api.pipeline()?
                 .lock()
                 .unwrap()
-                .unregister_input(&input_id.into())?;
+                .unregister_input(&input_id)?;
+            registry.lock().unwrap().inputs.remove(&input_id);
         }
     }
     Ok(Response::Ok {})
@@ -69,24 +74,29 @@ pub(super) async fn handle_output(
     Path(output_id): Path<OutputId>,
     Json(request): Json<UnregisterOutput>,
 ) -> Result<Response, ApiError> {
+    let output_id = smelter_render::OutputId::from(output_id);
+    let registry = api.registry.clone();
     match request.schedule_time_ms {
         Some(schedule_time_ms) => {
-            let schedule_time = Duration::from_secs_f64(schedule_time_ms / 1000.0);
+            let schedule_time = schedule_time(schedule_time_ms)?;
             Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
-                if let Err(err) = pipeline.unregister_output(&output_id.into()) {
+                if let Err(err) = pipeline.unregister_output(&output_id) {
                     error!(
                         "Error while running scheduled output unregister for pts {}ms: {}",
                         schedule_time.as_millis(),
                         ErrorStack::new(&err).into_string()
-                    )
+                    );
+                    return;
                 }
+                registry.lock().unwrap().outputs.remove(&output_id);
             });
         }
         None => {
             api.pipeline()?
                 .lock()
                 .unwrap()
-                .unregister_output(&output_id.into())?;
+                .unregister_output(&output_id)?;
+            registry.lock().unwrap().outputs.remove(&output_id);
         }
     }
     Ok(Response::Ok {})
@@ -97,26 +107,29 @@ pub(super) async fn handle_shader(
     Path(shader_id): Path<RendererId>,
     Json(request): Json<UnregisterRenderer>,
 ) -> Result<Response, ApiError> {
+    let shader_id = smelter_render::RendererId::from(shader_id);
+    let registry = api.registry.clone();
     match request.schedule_time_ms {
         Some(schedule_time_ms) => {
-            let schedule_time = Duration::from_secs_f64(schedule_time_ms / 1000.0);
+            let schedule_time = schedule_time(schedule_time_ms)?;
             Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
-                if let Err(err) =
-                    pipeline.unregister_renderer(&shader_id.into(), RegistryType::Shader)
-                {
+                if let Err(err) = pipeline.unregister_renderer(&shader_id, RegistryType::Shader) {
                     error!(
                         "Error while running scheduled shader unregister for pts {}ms: {}",
                         schedule_time.as_millis(),
                         ErrorStack::new(&err).into_string()
-                    )
+                    );
+                    return;
                 }
+                registry.lock().unwrap().shaders.remove(&shader_id);
             });
         }
         None => {
             api.pipeline()?
                 .lock()
                 .unwrap()
-                .unregister_renderer(&shader_id.into(), RegistryType::Shader)?;
+                .unregister_renderer(&shader_id, RegistryType::Shader)?;
+            registry.lock().unwrap().shaders.remove(&shader_id);
         }
     }
     Ok(Response::Ok {})
@@ -127,26 +140,31 @@ pub(super) async fn handle_web_renderer(
     Path(instance_id): Path<RendererId>,
     Json(request): Json<UnregisterRenderer>,
 ) -> Result<Response, ApiError> {
+    let instance_id = smelter_render::RendererId::from(instance_id);
+    let registry = api.registry.clone();
     match request.schedule_time_ms {
         Some(schedule_time_ms) => {
-            let schedule_time = Duration::from_secs_f64(schedule_time_ms / 1000.0);
+            let schedule_time = schedule_time(schedule_time_ms)?;
             Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
                 if let Err(err) =
-                    pipeline.unregister_renderer(&instance_id.into(), RegistryType::WebRenderer)
+                    pipeline.unregister_renderer(&instance_id, RegistryType::WebRenderer)
                 {
                     error!(
                         "Error while running scheduled web renderer unregister for pts {}ms: {}",
                         schedule_time.as_millis(),
                         ErrorStack::new(&err).into_string()
-                    )
+                    );
+                    return;
                 }
+                registry.lock().unwrap().web_renderers.remove(&instance_id);
             });
         }
         None => {
             api.pipeline()?
                 .lock()
                 .unwrap()
-                .unregister_renderer(&instance_id.into(), RegistryType::WebRenderer)?;
+                .unregister_renderer(&instance_id, RegistryType::WebRenderer)?;
+            registry.lock().unwrap().web_renderers.remove(&instance_id);
         }
     }
     Ok(Response::Ok {})
@@ -157,26 +175,96 @@ pub(super) async fn handle_image(
     Path(image_id): Path<RendererId>,
     Json(request): Json<UnregisterRenderer>,
 ) -> Result<Response, ApiError> {
+    let image_id = smelter_render::RendererId::from(image_id);
+    let registry = api.registry.clone();
     match request.schedule_time_ms {
         Some(schedule_time_ms) => {
-            let schedule_time = Duration::from_secs_f64(schedule_time_ms / 1000.0);
+            let schedule_time = schedule_time(schedule_time_ms)?;
             Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
-                if let Err(err) =
-                    pipeline.unregister_renderer(&image_id.into(), RegistryType::Image)
-                {
+                if let Err(err) = pipeline.unregister_renderer(&image_id, RegistryType::Image) {
                     error!(
                         "Error while running scheduled image unregister for pts {}ms: {}",
                         schedule_time.as_millis(),
                         ErrorStack::new(&err).into_string()
-                    )
+                    );
+                    return;
+                }
+                registry.lock().unwrap().images.remove(&image_id);
+            });
+        }
+        None => {
+            api.pipeline()?
+                .lock()
+                .unwrap()
+                .unregister_renderer(&image_id, RegistryType::Image)?;
+            registry.lock().unwrap().images.remove(&image_id);
+        }
+    }
+    Ok(Response::Ok {})
+}
+
+pub(super) async fn handle_video_clip(
+    State(api): State<Arc<ApiState>>,
+    Path(clip_id): Path<RendererId>,
+    Json(request): Json<UnregisterRenderer>,
+) -> Result<Response, ApiError> {
+    let clip_id = smelter_render::RendererId::from(clip_id);
+    let registry = api.registry.clone();
+    match request.schedule_time_ms {
+        Some(schedule_time_ms) => {
+            let schedule_time = schedule_time(schedule_time_ms)?;
+            Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
+                if let Err(err) = pipeline.unregister_renderer(&clip_id, RegistryType::VideoClip) {
+                    error!(
+                        "Error while running scheduled video clip unregister for pts {}ms: {}",
+                        schedule_time.as_millis(),
+                        ErrorStack::new(&err).into_string()
+                    );
+                    return;
+                }
+                registry.lock().unwrap().video_clips.remove(&clip_id);
+            });
+        }
+        None => {
+            api.pipeline()?
+                .lock()
+                .unwrap()
+                .unregister_renderer(&clip_id, RegistryType::VideoClip)?;
+            registry.lock().unwrap().video_clips.remove(&clip_id);
+        }
+    }
+    Ok(Response::Ok {})
+}
+
+pub(super) async fn handle_fragment(
+    State(api): State<Arc<ApiState>>,
+    Path(fragment_id): Path<RendererId>,
+    Json(request): Json<UnregisterRenderer>,
+) -> Result<Response, ApiError> {
+    let fragment_id = smelter_render::RendererId::from(fragment_id);
+    let registry = api.registry.clone();
+    match request.schedule_time_ms {
+        Some(schedule_time_ms) => {
+            let schedule_time = schedule_time(schedule_time_ms)?;
+            Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
+                if let Err(err) = pipeline.unregister_renderer(&fragment_id, RegistryType::Fragment)
+                {
+                    error!(
+                        "Error while running scheduled fragment unregister for pts {}ms: {}",
+                        schedule_time.as_millis(),
+                        ErrorStack::new(&err).into_string()
+                    );
+                    return;
                 }
+                registry.lock().unwrap().fragments.remove(&fragment_id);
             });
         }
         None => {
             api.pipeline()?
                 .lock()
                 .unwrap()
-                .unregister_renderer(&image_id.into(), RegistryType::Image)?;
+                .unregister_renderer(&fragment_id, RegistryType::Fragment)?;
+            registry.lock().unwrap().fragments.remove(&fragment_id);
         }
     }
     Ok(Response::Ok {})
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smelter_core::Pipeline;
+use smelter_render::error::ErrorStack;
+use tracing::error;
+
+use crate::{
+    error::ApiError,
+    state::{ApiState, Response},
+};
+
+use smelter_api::{Component, RendererId};
+
+use super::{Json, register_request::schedule_time};
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateFragmentRequest {
+    /// New component tree of the fragment. All outputs that use this fragment are
+    /// updated on the same frame.
+    pub root: Component,
+    pub schedule_time_ms: Option<f64>,
+}
+
+pub(super) async fn handle_fragment_update(
+    State(api): State<Arc<ApiState>>,
+    Path(fragment_id): Path<RendererId>,
+    Json(request): Json<UpdateFragmentRequest>,
+) -> Result<Response, ApiError> {
+    let fragment_id = smelter_render::RendererId::from(fragment_id);
+    let registered_root = request.root.clone();
+    let root = request.root.try_into()?;
+    let registry = api.registry.clone();
+
+    match request.schedule_time_ms {
+        Some(schedule_time_ms) => {
+            let schedule_time = schedule_time(schedule_time_ms)?;
+            Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
+                if let Err(err) = pipeline.update_fragment(fragment_id.clone(), root) {
+                    error!(
+                        "Error while running scheduled fragment update for pts {}ms: {}",
+                        schedule_time.as_millis(),
+                        ErrorStack::new(&err).into_string()
+                    );
+                    return;
+                }
+                registry
+                    .lock()
+                    .unwrap()
+                    .update_fragment(&fragment_id, registered_root);
+            });
+        }
+        None => {
+            api.pipeline()?
+                .lock()
+                .unwrap()
+                .update_fragment(fragment_id.clone(), root)?;
+            registry
+                .lock()
+                .unwrap()
+                .update_fragment(&fragment_id, registered_root);
+        }
+    };
+    Ok(Response::Ok {})
+}
@@ -1,20 +1,20 @@
-use std::{sync::Arc, time::Duration};
+use std::sync::Arc;
 
 use axum::extract::{Path, State};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smelter_core::Pipeline;
-use smelter_render::error::ErrorStack;
+use smelter_render::error::{ErrorStack, UpdateSceneError};
 use tracing::error;
 
 use crate::{
     error::ApiError,
-    state::{ApiState, Response},
+    state::{ApiRegistry, ApiState, Response},
 };
 
-use smelter_api::{AudioScene, OutputId, VideoScene};
+use smelter_api::{AudioScene, OutputId, ScenePatch, VideoScene};
 
-use super::Json;
+use super::{Json, register_request::schedule_time};
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -24,40 +24,128 @@ pub struct UpdateOutputRequest {
     pub schedule_time_ms: Option<f64>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PatchOutputRequest {
+    /// List of operations applied in order to the current video scene. If any of them
+    /// fails, the scene is not modified.
+    pub operations: Vec<ScenePatch>,
+    pub schedule_time_ms: Option<f64>,
+}
+
 pub(super) async fn handle_output_update(
     State(api): State<Arc<ApiState>>,
     Path(output_id): Path<OutputId>,
     Json(request): Json<UpdateOutputRequest>,
 ) -> Result<Response, ApiError> {
-    let output_id = output_id.into();
+    let output_id = smelter_render::OutputId::from(output_id);
+    let registered_scenes = (request.video.clone(), request.audio.clone());
     let scene = match request.video {
         Some(component) => Some(component.try_into()?),
         None => None,
     };
     let audio = request.audio.map(|a| a.try_into()).transpose()?;
+    let registry = api.registry.clone();
 
     match request.schedule_time_ms {
         Some(schedule_time_ms) => {
-            let schedule_time = Duration::from_secs_f64(schedule_time_ms / 1000.0);
+            let schedule_time = schedule_time(schedule_time_ms)?;
             Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
-                if let Err(err) = pipeline.update_output(output_id, scene, audio) {
+                if let Err(err) = pipeline.update_output(output_id.clone(), scene, audio) {
                     error!(
                         "Error while running scheduled output update for pts {}ms: {}",
                         schedule_time.as_millis(),
                         ErrorStack::new(&err).into_string()
-                    )
+                    );
+                    return;
+                }
+                let (video, audio) = registered_scenes;
+                registry
+                    .lock()
+                    .unwrap()
+                    .update_output(&output_id, video, audio);
+            });
+        }
+        None => {
+            api.pipeline()?
+                .lock()
+                .unwrap()
+                .update_output(output_id.clone(), scene, audio)?;
+            let (video, audio) = registered_scenes;
+            registry
+                .lock()
+                .unwrap()
+                .update_output(&output_id, video, audio);
+        }
+    };
+    Ok(Response::Ok {})
+}
+
+pub(super) async fn handle_output_patch(
+    State(api): State<Arc<ApiState>>,
+    Path(output_id): Path<OutputId>,
+    Json(request): Json<PatchOutputRequest>,
+) -> Result<Response, ApiError> {
+    let output_id = smelter_render::OutputId::from(output_id);
+    let registered_patches = request.operations.clone();
+    let patches = request
+        .operations
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect::<Result<Vec<_>, _>>()?;
+    let registry = api.registry.clone();
+
+    match request.schedule_time_ms {
+        Some(schedule_time_ms) => {
+            let schedule_time = schedule_time(schedule_time_ms)?;
+            Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
+                let mut registry = registry.lock().unwrap();
+                if let Err(err) = patch_output(
+                    pipeline,
+                    &mut registry,
+                    &output_id,
+                    patches,
+                    &registered_patches,
+                ) {
+                    error!(
+                        "Error while running scheduled output patch for pts {}ms: {}",
+                        schedule_time.as_millis(),
+                        ErrorStack::new(&err).into_string()
+                    );
                 }
             });
         }
-        None => api
-            .pipeline()?
-            .lock()
-            .unwrap()
-            .update_output(output_id, scene, audio)?,
+        None => {
+            let pipeline = api.pipeline()?;
+            let mut pipeline = pipeline.lock().unwrap();
+            let mut registry = registry.lock().unwrap();
+            patch_output(
+                &mut pipeline,
+                &mut registry,
+                &output_id,
+                patches,
+                &registered_patches,
+            )?;
+        }
     };
     Ok(Response::Ok {})
 }
 
+/// Patches the scene in the renderer and the scene stored in the registry. Patches are
+/// applied to the stored scene first, so an invalid patch does not modify either of them.
+fn patch_output(
+    pipeline: &mut Pipeline,
+    registry: &mut ApiRegistry,
+    output_id: &smelter_render::OutputId,
+    patches: Vec<smelter_render::scene::ScenePatch>,
+    registered_patches: &[ScenePatch],
+) -> Result<(), UpdateSceneError> {
+    let video = registry.patched_output_scene(output_id, registered_patches)?;
+    pipeline.patch_output(output_id.clone(), patches)?;
+    registry.update_output(output_id, video, None);
+    Ok(())
+}
+
 pub(super) async fn handle_keyframe_request(
     State(api): State<Arc<ApiState>>,
     Path(output_id): Path<OutputId>,
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smelter_core::protocols::PlaylistUpdate;
+
+use crate::{
+    error::ApiError,
+    state::{ApiState, Response},
+};
+
+use smelter_api::{InputId, PlaylistItem};
+
+use super::Json;
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpdatePlaylistRequest {
+    /// Ids of queued items that should be removed. Items that already started
+    /// playing can't be removed.
+    pub remove: Option<Vec<Arc<str>>>,
+    /// Items added at the end of the playlist. Removals are applied first.
+    pub append: Option<Vec<PlaylistItem>>,
+}
+
+pub(super) async fn handle_playlist_update(
+    State(api): State<Arc<ApiState>>,
+    Path(input_id): Path<InputId>,
+    Json(request): Json<UpdatePlaylistRequest>,
+) -> Result<Response, ApiError> {
+    let input_id = smelter_render::InputId::from(input_id);
+    let remove = request.remove.unwrap_or_default();
+    let append = request.append.unwrap_or_default();
+    let update = PlaylistUpdate {
+        remove: remove.clone(),
+        append: append
+            .iter()
+            .cloned()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?,
+    };
+    api.pipeline()?
+        .lock()
+        .unwrap()
+        .update_playlist(&input_id, update)?;
+    api.registry
+        .lock()
+        .unwrap()
+        .update_input_playlist(&input_id, &remove, append);
+    Ok(Response::Ok {})
+}
@@ -11,7 +11,11 @@ use reqwest::StatusCode;
 use serde::Serialize;
 use tokio::runtime::Runtime;
 
-use crate::{config::Config, error::ApiError};
+use crate::{config::Config, error::ApiError, routes::OutputPreviews};
+
+pub use registry::{ApiRegistry, RegisteredOutput};
+
+mod registry;
 
 #[derive(Serialize, Debug)]
 #[serde(untagged)]
@@ -46,6 +50,10 @@ pub enum ApiStateInitError {
 
 pub struct ApiState {
     pub pipeline: Mutex<Option<Arc<Mutex<Pipeline>>>>,
+    /// Registration requests and scenes of the current pipeline.
+    pub registry: Arc<Mutex<ApiRegistry>>,
+    /// Output previews that are currently streamed to clients.
+    pub previews: OutputPreviews,
     pub config: Config,
     pub chromium_context: Option<Arc<ChromiumContext>>,
     pub runtime: Arc<Runtime>,
@@ -64,6 +72,8 @@ impl ApiState {
         let pipeline = Pipeline::new(options)?;
         Ok(Arc::new(ApiState {
             pipeline: Mutex::new(Some(Arc::new(Mutex::new(pipeline)))),
+            registry: Arc::new(Mutex::new(ApiRegistry::default())),
+            previews: OutputPreviews::default(),
             config,
             runtime,
             chromium_context,
@@ -85,6 +95,7 @@ impl ApiState {
     pub fn reset(&self) -> Result<(), ApiError> {
         let mut guard = self.pipeline.lock().unwrap();
         guard.take();
+        *self.registry.lock().unwrap() = ApiRegistry::default();
 
         let options =
             pipeline_options_from_config(&self.config, &self.runtime, &self.chromium_context);
@@ -103,6 +114,7 @@ pub fn pipeline_options_from_config(
         stream_fallback_timeout: opt.stream_fallback_timeout,
         download_root: opt.download_root.clone(),
         default_buffer_duration: opt.default_buffer_duration,
+        media_alarm_threshold: opt.media_alarm_threshold,
 
         load_system_fonts: opt.load_system_fonts,
         ahead_of_time_processing: opt.ahead_of_time_processing,
@@ -0,0 +1,321 @@
+use std::{collections::HashMap, sync::Arc};
+
+use smelter_api::{
+    AudioScene, Component, FragmentSpec, ImageSpec, InputFailover, PlaylistItem, Resolution,
+    ScenePatch, ShaderSpec, VideoClipSpec, VideoScene, WebRendererSpec,
+};
+use smelter_render::{
+    InputId, OutputId, RendererId,
+    scene::{self, SceneError},
+};
+
+use crate::routes::{RegisterInput, RegisterOutput};
+
+/// Requests that were used to register inputs, outputs and renderers in the current
+/// pipeline, together with the last scene of each output. Entries are removed when
+/// the corresponding entity is unregistered.
+#[derive(Debug, Default)]
+pub struct ApiRegistry {
+    pub inputs: HashMap<InputId, RegisterInput>,
+    pub outputs: HashMap<OutputId, RegisteredOutput>,
+    pub images: HashMap<RendererId, ImageSpec>,
+    pub shaders: HashMap<RendererId, ShaderSpec>,
+    pub web_renderers: HashMap<RendererId, WebRendererSpec>,
+    pub video_clips: HashMap<RendererId, VideoClipSpec>,
+    pub fragments: HashMap<RendererId, FragmentSpec>,
+}
+
+impl ApiRegistry {
+    pub fn update_output(
+        &mut self,
+        output_id: &OutputId,
+        video: Option<VideoScene>,
+        audio: Option<AudioScene>,
+    ) {
+        let Some(output) = self.outputs.get_mut(output_id) else {
+            return;
+        };
+        if video.is_some() {
+            output.video = video;
+        }
+        if audio.is_some() {
+            output.audio = audio;
+        }
+    }
+
+    /// Video scene of an output with patches applied. Patches are applied the same way
+    /// as in the renderer, so the result matches the rendered scene. Returns `None` if
+    /// the output does not exist or does not have video.
+    pub fn patched_output_scene(
+        &self,
+        output_id: &OutputId,
+        patches: &[ScenePatch],
+    ) -> Result<Option<VideoScene>, SceneError> {
+        let Some(mut video) = self
+            .outputs
+            .get(output_id)
+            .and_then(|output| output.video.clone())
+        else {
+            return Ok(None);
+        };
+        for patch in patches {
+            scene::ScenePatch::<Component>::from(patch.clone()).apply(&mut video.root)?;
+        }
+        Ok(Some(video))
+    }
+
+    pub fn update_fragment(&mut self, fragment_id: &RendererId, root: Component) {
+        if let Some(fragment) = self.fragments.get_mut(fragment_id) {
+            fragment.root = root;
+        }
+    }
+
+    pub fn update_input_audio_delay(&mut self, input_id: &InputId, audio_delay_ms: f64) {
+        if let Some(field) = self
+            .inputs
+            .get_mut(input_id)
+            .and_then(RegisterInput::audio_delay_ms_mut)
+        {
+            *field = Some(audio_delay_ms);
+        }
+    }
+
+    pub fn update_input_failover(&mut self, input_id: &InputId, failover: InputFailover) {
+        if let Some(field) = self
+            .inputs
+            .get_mut(input_id)
+            .and_then(RegisterInput::failover_mut)
+        {
+            *field = Some(failover);
+        }
+    }
+
+    /// Items that were already played stay in the registry, only removed ones are
+    /// dropped.
+    pub fn update_input_playlist(
+        &mut self,
+        input_id: &InputId,
+        remove: &[Arc<str>],
+        append: Vec<PlaylistItem>,
+    ) {
+        if let Some(RegisterInput::Playlist(input)) = self.inputs.get_mut(input_id) {
+            input.items.retain(|item| !remove.contains(&item.item_id));
+            input.items.extend(append);
+        }
+    }
+}
+
+/// Value returned by query endpoints in place of credentials.
+const REDACTED: &str = "<redacted>";
+
+#[derive(Debug)]
+pub struct RegisteredOutput {
+    pub options: RegisterOutput,
+    pub video: Option<VideoScene>,
+    pub audio: Option<AudioScene>,
+}
+
+impl RegisteredOutput {
+    pub fn new(options: RegisterOutput) -> Self {
+        let (video, audio) = options.initial_scenes();
+        Self {
+            options,
+            video,
+            audio,
+        }
+    }
+}
+
+impl RegisterInput {
+    /// Copy of the request without credentials, so it can be returned by query endpoints.
+    pub fn redacted(&self) -> Self {
+        let mut input = self.clone();
+        match &mut input {
+            RegisterInput::WhipServer(input) => redact(&mut input.bearer_token),
+            RegisterInput::WhepClient(input) => redact(&mut input.bearer_token),
+            _ => {}
+        }
+        input
+    }
+
+    fn audio_delay_ms_mut(&mut self) -> Option<&mut Option<f64>> {
+        match self {
+            RegisterInput::RtpStream(input) => Some(&mut input.audio_delay_ms),
+            RegisterInput::RtmpServer(input) => Some(&mut input.audio_delay_ms),
+            RegisterInput::Mp4(input) => Some(&mut input.audio_delay_ms),
+            RegisterInput::Playlist(input) => Some(&mut input.audio_delay_ms),
+            RegisterInput::Replay(input) => Some(&mut input.audio_delay_ms),
+            RegisterInput::WhipServer(input) => Some(&mut input.audio_delay_ms),
+            RegisterInput::WhepClient(input) => Some(&mut input.audio_delay_ms),
+            RegisterInput::Hls(input) => Some(&mut input.audio_delay_ms),
+            RegisterInput::DeckLink(input) => Some(&mut input.audio_delay_ms),
+            RegisterInput::V4l2(_) => None,
+        }
+    }
+
+    fn failover_mut(&mut self) -> Option<&mut Option<InputFailover>> {
+        match self {
+            RegisterInput::RtpStream(input) => Some(&mut input.failover),
+            RegisterInput::RtmpServer(input) => Some(&mut input.failover),
+            RegisterInput::Mp4(input) => Some(&mut input.failover),
+            RegisterInput::Playlist(input) => Some(&mut input.failover),
+            RegisterInput::Replay(input) => Some(&mut input.failover),
+            RegisterInput::WhipServer(input) => Some(&mut input.failover),
+            RegisterInput::WhepClient(input) => Some(&mut input.failover),
+            RegisterInput::Hls(input) => Some(&mut input.failover),
+            RegisterInput::DeckLink(input) => Some(&mut input.failover),
+            RegisterInput::V4l2(_) => None,
+        }
+    }
+}
+
+impl RegisterOutput {
+    /// Copy of the request without credentials, so it can be returned by query endpoints.
+    /// Stream key of an RTMP output is usually the last segment of its URL.
+    pub fn redacted(&self) -> Self {
+        let mut output = self.clone();
+        match &mut output {
+            RegisterOutput::WhipClient(output) => redact(&mut output.bearer_token),
+            RegisterOutput::WhepServer(output) => redact(&mut output.bearer_token),
+            RegisterOutput::RtmpClient(output) => {
+                output.url = redact_last_path_segment(&output.url)
+            }
+            _ => {}
+        }
+        output
+    }
+
+    fn initial_scenes(&self) -> (Option<VideoScene>, Option<AudioScene>) {
+        macro_rules! initial_scenes {
+            ($output:expr) => {
+                (
+                    $output.video.as_ref().map(|video| video.initial.clone()),
+                    $output.audio.as_ref().map(|audio| audio.initial.clone()),
+                )
+            };
+        }
+        match self {
+            RegisterOutput::RtpStream(output) => initial_scenes!(output),
+            RegisterOutput::RtmpClient(output) => initial_scenes!(output),
+            RegisterOutput::Mp4(output) => initial_scenes!(output),
+            RegisterOutput::WhipClient(output) => initial_scenes!(output),
+            RegisterOutput::WhepServer(output) => initial_scenes!(output),
+            RegisterOutput::Hls(output) => initial_scenes!(output),
+        }
+    }
+
+    pub fn video_resolution(&self) -> Option<Resolution> {
+        match self {
+            RegisterOutput::RtpStream(output) => output.video.as_ref().map(|v| v.resolution),
+            RegisterOutput::RtmpClient(output) => output.video.as_ref().map(|v| v.resolution),
+            RegisterOutput::Mp4(output) => output.video.as_ref().map(|v| v.resolution),
+            RegisterOutput::WhipClient(output) => output.video.as_ref().map(|v| v.resolution),
+            RegisterOutput::WhepServer(output) => output.video.as_ref().map(|v| v.resolution),
+            RegisterOutput::Hls(output) => output.video.as_ref().map(|v| v.resolution),
+        }
+    }
+}
+
+fn redact(value: &mut Option<Arc<str>>) {
+    if value.is_some() {
+        *value = Some(REDACTED.into());
+    }
+}
+
+fn redact_last_path_segment(url: &str) -> Arc<str> {
+    let path_start = url.find("://").map_or(0, |index| index + 3);
+    match url[path_start..].rfind('/') {
+        Some(index) if path_start + index + 1 < url.len() => {
+            format!("{}{REDACTED}", &url[..path_start + index + 1]).into()
+        }
+        _ => url.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn redact_credentials() {
+        let input: RegisterInput =
+            serde_json::from_value(json!({ "type": "whip_server", "bearer_token": "secret" }))
+                .unwrap();
+        let RegisterInput::WhipServer(input) = input.redacted() else {
+            panic!("Expected WHIP input.");
+        };
+        assert_eq!(input.bearer_token.as_deref(), Some(REDACTED));
+
+        let input: RegisterInput =
+            serde_json::from_value(json!({ "type": "whip_server" })).unwrap();
+        let RegisterInput::WhipServer(input) = input.redacted() else {
+            panic!("Expected WHIP input.");
+        };
+        assert_eq!(input.bearer_token, None);
+    }
+
+    #[test]
+    fn redact_rtmp_stream_key() {
+        assert_eq!(
+            &*redact_last_path_segment("rtmp://example.com/live/stream_key"),
+            "rtmp://example.com/live/<redacted>"
+        );
+        assert_eq!(
+            &*redact_last_path_segment("rtmp://example.com"),
+            "rtmp://example.com"
+        );
+        assert_eq!(
+            &*redact_last_path_segment("rtmp://example.com/"),
+            "rtmp://example.com/"
+        );
+    }
+
+    #[test]
+    fn update_input_options() {
+        let input_id = InputId::from(Arc::from("input_1"));
+        let mut registry = ApiRegistry::default();
+        registry.inputs.insert(
+            input_id.clone(),
+            serde_json::from_value(json!({ "type": "mp4", "path": "input.mp4" })).unwrap(),
+        );
+
+        registry.update_input_audio_delay(&input_id, 120.0);
+        registry.update_input_failover(
+            &input_id,
+            serde_json::from_value(json!({ "backup_inputs": ["input_2"] })).unwrap(),
+        );
+
+        let Some(RegisterInput::Mp4(input)) = registry.inputs.get(&input_id) else {
+            panic!("Expected MP4 input.");
+        };
+        assert_eq!(input.audio_delay_ms, Some(120.0));
+        assert!(input.failover.is_some());
+
+        let playlist_id = InputId::from(Arc::from("playlist_1"));
+        registry.inputs.insert(
+            playlist_id.clone(),
+            serde_json::from_value(json!({
+                "type": "playlist",
+                "items": [
+                    { "item_id": "a", "path": "a.mp4" },
+                    { "item_id": "b", "path": "b.mp4" },
+                ],
+            }))
+            .unwrap(),
+        );
+
+        registry.update_input_playlist(
+            &playlist_id,
+            &[Arc::from("a")],
+            serde_json::from_value(json!([{ "item_id": "c", "path": "c.mp4" }])).unwrap(),
+        );
+
+        let Some(RegisterInput::Playlist(input)) = registry.inputs.get(&playlist_id) else {
+            panic!("Expected playlist input.");
+        };
+        let item_ids: Vec<_> = input.items.iter().map(|item| &*item.item_id).collect();
+        assert_eq!(item_ids, ["b", "c"]);
+    }
+}
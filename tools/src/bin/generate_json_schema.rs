@@ -27,7 +27,17 @@ enum ApiTypes {
     RegisterImage(smelter_api::ImageSpec),
     RegisterWebRenderer(smelter_api::WebRendererSpec),
     RegisterShader(smelter_api::ShaderSpec),
+    RegisterVideoClip(smelter_api::VideoClipSpec),
+    RegisterFragment(smelter_api::FragmentSpec),
     UpdateOutput(Box<routes::UpdateOutputRequest>),
+    PatchOutput(Box<routes::PatchOutputRequest>),
+    UpdateFragment(Box<routes::UpdateFragmentRequest>),
+    InputFailover(Box<smelter_api::InputFailover>),
+    UpdatePlaylist(Box<routes::UpdatePlaylistRequest>),
+    ReplayBuffer(Box<routes::ReplayBufferRequest>),
+    AudioDelay(Box<routes::AudioDelayRequest>),
+    Batch(Box<routes::BatchRequest>),
+    RegisterSchedule(routes::RegisterSchedule),
 }
 
 pub fn generate_json_schema(check_flag: bool) {
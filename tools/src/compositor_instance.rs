@@ -56,6 +56,8 @@ impl CompositorInstance {
         let pipeline = Arc::new(Mutex::new(Pipeline::new(options).unwrap()));
         let state = Arc::new(ApiState {
             pipeline: Mutex::new(Some(pipeline.clone())),
+            registry: Default::default(),
+            previews: Default::default(),
             config,
             chromium_context: None,
             runtime: runtime(),
@@ -5,7 +5,7 @@ export type ImageSpec = Required<Pick<Api.ImageSpec, 'asset_type' | 'url'>>;
 export type ShaderSpec = Api.ShaderSpec;
 export type Component = Extract<
   Api.Component,
-  { type: 'input_stream' | 'view' | 'rescaler' | 'image' | 'text' | 'tiles' }
+  { type: 'input_stream' | 'view' | 'rescaler' | 'image' | 'text' | 'tiles' | 'ticker' | 'mask' }
 >;
 export type RendererId = Api.RendererId;
 export type InputId = Api.InputId;
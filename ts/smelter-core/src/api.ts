@@ -44,6 +44,14 @@ export class ApiClient {
     });
   }
 
+  public async patchScene(outputId: string, request: Api.PatchOutputRequest): Promise<object> {
+    return this.serverManager.sendRequest({
+      method: 'POST',
+      route: `/api/output/${encodeURIComponent(outputId)}/patch`,
+      body: request,
+    });
+  }
+
   public async registerOutput(
     outputId: string,
     request: RegisterOutputRequest
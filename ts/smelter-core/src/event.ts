@@ -17,13 +17,67 @@ export function parseEvent(event: any, logger: Logger): SmelterEvent | null {
       SmelterEventType.AUDIO_INPUT_PLAYING,
       SmelterEventType.VIDEO_INPUT_EOS,
       SmelterEventType.AUDIO_INPUT_EOS,
+      SmelterEventType.INPUT_SILENCE,
+      SmelterEventType.INPUT_SILENCE_END,
+      SmelterEventType.INPUT_BLACK,
+      SmelterEventType.INPUT_BLACK_END,
+      SmelterEventType.INPUT_FROZEN,
+      SmelterEventType.INPUT_FROZEN_END,
     ].includes(event.type)
   ) {
     return { type: event.type, inputRef: parseInputRef(event.input_id) };
-  } else if (SmelterEventType.OUTPUT_DONE === event.type) {
+  } else if (
+    [SmelterEventType.VIDEO_INPUT_FAILOVER, SmelterEventType.AUDIO_INPUT_FAILOVER].includes(
+      event.type
+    )
+  ) {
+    return {
+      type: event.type,
+      inputRef: parseInputRef(event.input_id),
+      source: event.source,
+      backupInputRef: event.backup_input_id ? parseInputRef(event.backup_input_id) : undefined,
+    };
+  } else if (
+    [SmelterEventType.PLAYLIST_ITEM_START, SmelterEventType.PLAYLIST_ITEM_END].includes(event.type)
+  ) {
+    return { type: event.type, inputRef: parseInputRef(event.input_id), itemId: event.item_id };
+  } else if (SmelterEventType.PLAYLIST_ITEM_ERROR === event.type) {
+    return {
+      type: event.type,
+      inputRef: parseInputRef(event.input_id),
+      itemId: event.item_id,
+      error: event.error,
+    };
+  } else if (SmelterEventType.AUDIO_INPUT_LEVELS === event.type) {
+    return {
+      type: event.type,
+      inputRef: parseInputRef(event.input_id),
+      ...parseAudioLevels(event),
+    };
+  } else if (SmelterEventType.AUDIO_OUTPUT_LEVELS === event.type) {
+    return { type: event.type, outputId: event.output_id, ...parseAudioLevels(event) };
+  } else if (
+    [
+      SmelterEventType.OUTPUT_SILENCE,
+      SmelterEventType.OUTPUT_SILENCE_END,
+      SmelterEventType.OUTPUT_BLACK,
+      SmelterEventType.OUTPUT_BLACK_END,
+      SmelterEventType.OUTPUT_FROZEN,
+      SmelterEventType.OUTPUT_FROZEN_END,
+      SmelterEventType.OUTPUT_DONE,
+    ].includes(event.type)
+  ) {
     return { type: event.type, outputId: event.output_id };
   } else {
     logger.error(`Unknown event type: ${event.type}`);
     return null;
   }
 }
+
+function parseAudioLevels(event: any): _smelterInternals.AudioLevels {
+  return {
+    peakDbfs: Number(event.peak_dbfs),
+    rmsDbfs: Number(event.rms_dbfs),
+    shortTermLufs: Number(event.short_term_lufs),
+  };
+}
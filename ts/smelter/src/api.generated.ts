@@ -8,7 +8,7 @@
 /**
  * This enum is used to generate JSON schema for all API types. This prevents repeating types in generated schema.
  */
-export type ApiTypes = RegisterInput | RegisterOutput | ImageSpec | WebRendererSpec | ShaderSpec | UpdateOutputRequest;
+export type ApiTypes = RegisterInput | RegisterOutput | ImageSpec | WebRendererSpec | ShaderSpec | VideoClipSpec | FragmentSpec | UpdateOutputRequest | PatchOutputRequest | UpdateFragmentRequest | InputFailover | UpdatePlaylistRequest | ReplayBufferRequest | AudioDelayRequest | BatchRequest | RegisterSchedule;
 export type RegisterInput =
   | {
       type: "rtp_stream";
@@ -36,6 +36,14 @@ export type RegisterInput =
        * Offset in milliseconds relative to the pipeline start (start request). If the offset is not defined then the stream will be synchronized based on the delivery time of the initial frames.
        */
       offset_ms?: number | null;
+      /**
+       * Shift in milliseconds applied only to audio of this input to fix audio/video synchronization. Positive values delay audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range. It can be changed later with the `/api/input/:id/audio_delay` request.
+       */
+      audio_delay_ms?: number | null;
+      /**
+       * Backup inputs and slate image used when this input stops delivering data. It can be changed later with the `/api/input/:id/failover` request.
+       */
+      failover?: InputFailover | null;
     }
   | {
       type: "rtmp_server";
@@ -51,6 +59,14 @@ export type RegisterInput =
        * Offset in milliseconds relative to the pipeline start (start request). If the offset is not defined then the stream will be synchronized based on the delivery time of the initial frames.
        */
       offset_ms?: number | null;
+      /**
+       * Shift in milliseconds applied only to audio of this input to fix audio/video synchronization. Positive values delay audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range. It can be changed later with the `/api/input/:id/audio_delay` request.
+       */
+      audio_delay_ms?: number | null;
+      /**
+       * Backup inputs and slate image used when this input stops delivering data. It can be changed later with the `/api/input/:id/failover` request.
+       */
+      failover?: InputFailover | null;
       /**
        * Assigns which decoder should be used for media encoded with a specific codec.
        */
@@ -80,6 +96,14 @@ export type RegisterInput =
        * Offset in milliseconds relative to the pipeline start (start request). If offset is not defined then stream is synchronized based on the first frames delivery time.
        */
       offset_ms?: number | null;
+      /**
+       * Shift in milliseconds applied only to audio of this input to fix audio/video synchronization. Positive values delay audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range. It can be changed later with the `/api/input/:id/audio_delay` request.
+       */
+      audio_delay_ms?: number | null;
+      /**
+       * Backup inputs and slate image used when this input stops delivering data. It can be changed later with the `/api/input/:id/failover` request.
+       */
+      failover?: InputFailover | null;
       /**
        * Assigns which decoder should be used for media encoded with a specific codec.
        */
@@ -87,6 +111,70 @@ export type RegisterInput =
         [k: string]: Mp4VideoDecoderOptions;
       } | null;
     }
+  | {
+      type: "playlist";
+      /**
+       * Items played in order. When the playlist runs out of items, the input waits for new ones.
+       */
+      items: PlaylistItem[];
+      /**
+       * (**default=`false`**) If input is required and frames are not processed on time, then Smelter will delay producing output frames.
+       */
+      required?: boolean | null;
+      /**
+       * Offset in milliseconds relative to the pipeline start (start request). If offset is not defined then stream is synchronized based on the first frames delivery time.
+       */
+      offset_ms?: number | null;
+      /**
+       * Shift in milliseconds applied only to audio of this input to fix audio/video synchronization. Positive values delay audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range. It can be changed later with the `/api/input/:id/audio_delay` request.
+       */
+      audio_delay_ms?: number | null;
+      /**
+       * Backup inputs and slate image used when this input stops delivering data. It can be changed later with the `/api/input/:id/failover` request.
+       */
+      failover?: InputFailover | null;
+      /**
+       * Assigns which decoder should be used for media encoded with a specific codec.
+       */
+      decoder_map?: {
+        [k: string]: Mp4VideoDecoderOptions;
+      } | null;
+    }
+  | {
+      type: "replay";
+      /**
+       * Id of the input with a replay buffer enabled.
+       */
+      source_input_id: InputId;
+      /**
+       * Start of the replayed range in milliseconds, relative to the pipeline start.
+       */
+      start_ms: number;
+      /**
+       * End of the replayed range in milliseconds, relative to the pipeline start.
+       */
+      end_ms: number;
+      /**
+       * (**default=`1.0`**) Playback speed. Values below `1.0` produce slow motion. Slowed down audio is played at a lower pitch. Has to be in the `[0.1, 16]` range.
+       */
+      speed?: number | null;
+      /**
+       * (**default=`false`**) If input is required and frames are not processed on time, then Smelter will delay producing output frames.
+       */
+      required?: boolean | null;
+      /**
+       * Offset in milliseconds relative to the pipeline start (start request). If offset is not defined then the replay starts right after the input is registered.
+       */
+      offset_ms?: number | null;
+      /**
+       * Shift in milliseconds applied only to audio of this input to fix audio/video synchronization. Positive values delay audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range. It can be changed later with the `/api/input/:id/audio_delay` request.
+       */
+      audio_delay_ms?: number | null;
+      /**
+       * Backup inputs and slate image used when this input stops delivering data. It can be changed later with the `/api/input/:id/failover` request.
+       */
+      failover?: InputFailover | null;
+    }
   | {
       type: "whip_server";
       /**
@@ -109,6 +197,14 @@ export type RegisterInput =
        * Offset in milliseconds relative to the pipeline start (start request). If the offset is not defined then the stream will be synchronized based on the delivery time of the initial frames.
        */
       offset_ms?: number | null;
+      /**
+       * Shift in milliseconds applied only to audio of this input to fix audio/video synchronization. Positive values delay audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range. It can be changed later with the `/api/input/:id/audio_delay` request.
+       */
+      audio_delay_ms?: number | null;
+      /**
+       * Backup inputs and slate image used when this input stops delivering data. It can be changed later with the `/api/input/:id/failover` request.
+       */
+      failover?: InputFailover | null;
     }
   | {
       type: "whep_client";
@@ -132,6 +228,14 @@ export type RegisterInput =
        * Offset in milliseconds relative to the pipeline start (start request).
        */
       offset_ms?: number | null;
+      /**
+       * Shift in milliseconds applied only to audio of this input to fix audio/video synchronization. Positive values delay audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range. It can be changed later with the `/api/input/:id/audio_delay` request.
+       */
+      audio_delay_ms?: number | null;
+      /**
+       * Backup inputs and slate image used when this input stops delivering data. It can be changed later with the `/api/input/:id/failover` request.
+       */
+      failover?: InputFailover | null;
     }
   | {
       type: "hls";
@@ -147,6 +251,14 @@ export type RegisterInput =
        * Offset in milliseconds relative to the pipeline start (start request). If the offset is not defined then the stream will be synchronized based on the delivery time of the initial frames.
        */
       offset_ms?: number | null;
+      /**
+       * Shift in milliseconds applied only to audio of this input to fix audio/video synchronization. Positive values delay audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range. It can be changed later with the `/api/input/:id/audio_delay` request.
+       */
+      audio_delay_ms?: number | null;
+      /**
+       * Backup inputs and slate image used when this input stops delivering data. It can be changed later with the `/api/input/:id/failover` request.
+       */
+      failover?: InputFailover | null;
       /**
        * Assigns which decoder should be used for media encoded with a specific codec.
        */
@@ -211,6 +323,14 @@ export type RegisterInput =
        * (**default=`false`**) If input is required and frames are not processed on time, then Smelter will delay producing output frames.
        */
       required?: boolean | null;
+      /**
+       * Shift in milliseconds applied only to audio of this input to fix audio/video synchronization. Positive values delay audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range. It can be changed later with the `/api/input/:id/audio_delay` request.
+       */
+      audio_delay_ms?: number | null;
+      /**
+       * Backup inputs and slate image used when this input stops delivering data. It can be changed later with the `/api/input/:id/failover` request.
+       */
+      failover?: InputFailover | null;
     };
 export type PortOrPortRange = string | number;
 export type TransportProtocol = "udp" | "tcp_server";
@@ -370,6 +490,14 @@ export type RtpVideoEncoderOptions =
        * (**default=`"yuv420p"`**) Encoder pixel format.
        */
       pixel_format?: PixelFormat | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
       /**
        * Raw FFmpeg encoder options. Visit [docs](https://ffmpeg.org/ffmpeg-codecs.html) to learn more.
        */
@@ -400,6 +528,14 @@ export type RtpVideoEncoderOptions =
        * (**default=`"yuv420p"`**) Encoder pixel format.
        */
       pixel_format?: PixelFormat | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
       /**
        * Raw FFmpeg encoder options. Visit [docs](https://ffmpeg.org/ffmpeg-codecs.html) to learn more.
        */
@@ -413,6 +549,14 @@ export type RtpVideoEncoderOptions =
        * Encoding bitrate. If not provided, bitrate is calculated based on resolution and framerate. For example at 1080p 30 FPS the average bitrate is 5000 kbit/s and max bitrate is 6250 kbit/s.
        */
       bitrate?: VideoEncoderBitrate | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
     };
 export type H264EncoderPreset =
   | "ultrafast"
@@ -437,7 +581,16 @@ export type VideoEncoderBitrate =
        */
       max_bitrate: number;
     };
-export type PixelFormat = "yuv420p" | "yuv422p" | "yuv444p";
+export type PixelFormat =
+  | "yuv420p"
+  | "yuv422p"
+  | "yuv444p"
+  /**
+   * 10-bit YUV 4:2:0. Video is composed in 8-bit SDR, so it does not preserve more precision of 10-bit inputs than `yuv420p`, and HDR inputs are tone-mapped.
+   */
+  | "yuv420p10";
+export type ColorSpace = "bt601" | "bt709" | "bt2020";
+export type ColorRange = "limited" | "full";
 export type Component =
   | {
       type: "input_stream";
@@ -472,6 +625,30 @@ export type Component =
        * Direction defines how static children are positioned inside a View component.
        */
       direction?: ViewDirection | null;
+      /**
+       * (**default=`0.0`**) Space in pixels between static children. If `wrap` is enabled, it also defines space between rows (or columns).
+       */
+      gap?: number | null;
+      /**
+       * (**default=`"start"`**) Distribution of static children along the `direction`.
+       */
+      justify_content?: JustifyContent | null;
+      /**
+       * (**default=`"stretch"`**) Alignment of static children in the axis perpendicular to the `direction`. Unless children are stretched, children without an explicit size are sized to their content.
+       */
+      align_items?: AlignItems | null;
+      /**
+       * (**default=`false`**) Move static children that do not fit into the next row (or column if `direction` is `"column"`).
+       */
+      wrap?: boolean | null;
+      /**
+       * Share of the free space in the parent `View` that is added to the size of this component. By default, components without an explicit size grow with factor `1` and components with an explicit size do not grow. Other children of a `View`, e.g. `Image` or `Tiles`, always use the default.
+       */
+      flex_grow?: number | null;
+      /**
+       * (**default=`0.0`**) Share of the missing space in the parent `View` that is subtracted from the size of this component, weighted by its size. Components do not shrink below the size of their content. Other children of a `View`, e.g. `Image` or `Tiles`, always use the default.
+       */
+      flex_shrink?: number | null;
       /**
        * Distance in pixels between this component's top edge and its parent's top edge (including a border). If this field is defined, then the component will ignore a layout defined by its parent.
        */
@@ -496,6 +673,10 @@ export type Component =
        * Defines how this component will behave during a scene update. This will only have an effect if the previous scene already contained a `View` component with the same id.
        */
       transition?: Transition | null;
+      /**
+       * Keyframe animation of the component's size, position and opacity. Animated values override values defined directly on the component.
+       */
+      animation?: Animation | null;
       /**
        * (**default=`"hidden"`**) Controls what happens to content that is too big to fit into an area.
        */
@@ -548,6 +729,14 @@ export type Component =
        * (**default=`0.0`**) Padding on left side in pixels.
        */
       padding_left?: number | null;
+      /**
+       * (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+       */
+      opacity?: number | null;
+      /**
+       * (**default=`"normal"`**) Defines how the component is composed with the content rendered below it.
+       */
+      blend_mode?: BlendMode | null;
     }
   | {
       type: "web_view";
@@ -609,6 +798,49 @@ export type Component =
        * Height of the image in pixels. If `width` is not explicitly provided, the image will automatically adjust its width to maintain its original aspect ratio relative to the height.
        */
       height?: number | null;
+      /**
+       * Number of times an animated image (GIF, WebP, APNG) is played. After the last loop, the final frame stays visible. If not provided, the animation loops forever.
+       */
+      loop_count?: number | null;
+      /**
+       * (**default=`true`**) If `true`, an animated image starts from the first frame when the component is added to the scene. If `false`, the animation is synchronized with the output timeline, so it looks like it was playing all along.
+       */
+      restart_on_mount?: boolean | null;
+      /**
+       * (**default=`0.0`**) Offset in milliseconds from which an animated image starts playing.
+       */
+      start_at_ms?: number | null;
+    }
+  | {
+      type: "video_clip";
+      /**
+       * Id of a component.
+       */
+      id?: ComponentId | null;
+      /**
+       * Id of a video clip. It identifies a clip registered using a [`register video clip`](../routes.md#register-video-clip) request.
+       */
+      clip_id: RendererId;
+      /**
+       * Width of the clip in pixels. If `height` is not explicitly provided, the clip will automatically adjust its height to maintain its original aspect ratio relative to the width.
+       */
+      width?: number | null;
+      /**
+       * Height of the clip in pixels. If `width` is not explicitly provided, the clip will automatically adjust its width to maintain its original aspect ratio relative to the height.
+       */
+      height?: number | null;
+      /**
+       * Number of times a clip is played. After the last loop, the final frame stays visible. If not provided, the clip loops forever.
+       */
+      loop_count?: number | null;
+      /**
+       * (**default=`true`**) If `true`, a clip starts from the first frame when the component is added to the scene. If `false`, the clip is synchronized with the output timeline, so it looks like it was playing all along.
+       */
+      restart_on_mount?: boolean | null;
+      /**
+       * (**default=`0.0`**) Offset in milliseconds from which a clip starts playing.
+       */
+      start_at_ms?: number | null;
     }
   | {
       type: "text";
@@ -617,9 +849,13 @@ export type Component =
        */
       id?: ComponentId | null;
       /**
-       * Text that will be rendered.
+       * Text that will be rendered. Exactly one of `text` and `spans` has to be defined.
        */
-      text: string;
+      text?: string | null;
+      /**
+       * Text split into fragments with different styles. Properties that are not defined on a span are inherited from the component. Exactly one of `text` and `spans` has to be defined.
+       */
+      spans?: TextSpan[] | null;
       /**
        * Width of a texture that text will be rendered on. If not provided, the resulting texture will be sized based on the defined text but limited to `max_width` value.
        */
@@ -672,6 +908,49 @@ export type Component =
        * (**default=`"normal"`**) Font weight. The selected font needs to support the specified weight.
        */
       weight?: TextWeight | null;
+      /**
+       * (**default=`0.0`**) Additional space between letters in pixels.
+       */
+      letter_spacing?: number | null;
+      /**
+       * Outline drawn around the text.
+       */
+      outline?: TextOutline | null;
+      /**
+       * Shadow drawn below the text.
+       */
+      shadow?: TextShadow | null;
+    }
+  | {
+      type: "mask";
+      /**
+       * Id of a component.
+       */
+      id?: ComponentId | null;
+      /**
+       * Component that will be rendered. It is stretched to the size of the mask component.
+       */
+      content: Component;
+      /**
+       * Component that defines which parts of the content are visible. It is stretched to the size of the mask component.
+       */
+      mask: Component;
+      /**
+       * Width of a component in pixels.
+       */
+      width: number;
+      /**
+       * Height of a component in pixels.
+       */
+      height: number;
+      /**
+       * (**default=`"alpha"`**) Defines which property of the mask is used to calculate visibility of the content.
+       */
+      mode?: MaskMode | null;
+      /**
+       * (**default=`false`**) Render content only where the mask is not visible.
+       */
+      invert?: boolean | null;
     }
   | {
       type: "tiles";
@@ -719,6 +998,14 @@ export type Component =
        * Defines how this component will behave during a scene update. This will only have an effect if the previous scene already contained a `Tiles` component with the same id.
        */
       transition?: Transition | null;
+      /**
+       * (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+       */
+      opacity?: number | null;
+      /**
+       * (**default=`"normal"`**) Defines how the component is composed with the content rendered below it.
+       */
+      blend_mode?: BlendMode | null;
     }
   | {
       type: "rescaler";
@@ -742,6 +1029,14 @@ export type Component =
        * (**default=`"center"`**) Vertical alignment.
        */
       vertical_align?: VerticalAlign | null;
+      /**
+       * Share of the free space in the parent `View` that is added to the size of this component. By default, components without an explicit size grow with factor `1` and components with an explicit size do not grow. Other children of a `View`, e.g. `Image` or `Tiles`, always use the default.
+       */
+      flex_grow?: number | null;
+      /**
+       * (**default=`0.0`**) Share of the missing space in the parent `View` that is subtracted from the size of this component, weighted by its size. Components do not shrink below the size of their content. Other children of a `View`, e.g. `Image` or `Tiles`, always use the default.
+       */
+      flex_shrink?: number | null;
       /**
        * Width of a component in pixels (without a border). Exact behavior might be different based on the parent component: - If the parent component is a layout, check sections "Absolute positioning" and "Static positioning" of that component. - If the parent component is not a layout, then this field is required.
        */
@@ -774,6 +1069,10 @@ export type Component =
        * Defines how this component will behave during a scene update. This will only have an effect if the previous scene already contained a `Rescaler` component with the same id.
        */
       transition?: Transition | null;
+      /**
+       * Keyframe animation of the component's size, position and opacity. Animated values override values defined directly on the component.
+       */
+      animation?: Animation | null;
       /**
        * (**default=`0.0`**) Radius of a rounded corner.
        */
@@ -790,9 +1089,73 @@ export type Component =
        * List of box shadows.
        */
       box_shadow?: BoxShadow[] | null;
+      /**
+       * (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+       */
+      opacity?: number | null;
+      /**
+       * (**default=`"normal"`**) Defines how the component is composed with the content rendered below it.
+       */
+      blend_mode?: BlendMode | null;
+    }
+  | {
+      type: "ticker";
+      /**
+       * Id of a component. Scroll position is preserved across scene updates only if the previous scene already contained a `Ticker` component with the same id.
+       */
+      id?: ComponentId | null;
+      /**
+       * List of items to scroll. Items appended in a scene update are added at the end of the queue without restarting the scroll. Items removed from the beginning of the list do not affect positions of remaining items only if those items have an `id`.
+       */
+      children?: Component[] | null;
+      /**
+       * Width of a component in pixels. Exact behavior might be different based on the parent component: - If the parent component is a layout, check sections "Absolute positioning" and "Static positioning" of that component. - If the parent component is not a layout, then this field is required.
+       */
+      width?: number | null;
+      /**
+       * Height of a component in pixels. Exact behavior might be different based on the parent component: - If the parent component is a layout, check sections "Absolute positioning" and "Static positioning" of that component. - If the parent component is not a layout, then this field is required.
+       */
+      height?: number | null;
+      /**
+       * (**default=`"left"`**) Direction in which items are moving.
+       */
+      direction?: TickerDirection | null;
+      /**
+       * (**default=`100`**) Scroll speed in pixels per second.
+       */
+      speed?: number | null;
+      /**
+       * (**default=`0`**) Space between consecutive items in pixels.
+       */
+      gap?: number | null;
+      /**
+       * (**default=`true`**) Start scrolling items from the beginning after the last one leaves the component area.
+       */
+      loop?: boolean | null;
+      /**
+       * (**default=`"#00000000"`**) Background color in a `"#RRGGBBAA"` format.
+       */
+      background_color?: RGBAColor | null;
+      /**
+       * (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+       */
+      opacity?: number | null;
+      /**
+       * (**default=`"normal"`**) Defines how the component is composed with the content rendered below it.
+       */
+      blend_mode?: BlendMode | null;
+    }
+  | {
+      type: "fragment";
+      /**
+       * Id of a registered scene fragment.
+       */
+      fragment_id: RendererId;
     };
 export type ComponentId = string;
 export type ViewDirection = "row" | "column";
+export type JustifyContent = "start" | "end" | "center" | "space_between" | "space_around" | "space_evenly";
+export type AlignItems = "start" | "end" | "center" | "stretch";
 /**
  * Easing functions are used to interpolate between two values over time.
  *
@@ -813,8 +1176,10 @@ export type EasingFunction =
        */
       points: [number, number, number, number];
     };
+export type AnimationRepeat = "none" | "loop" | "alternate";
 export type Overflow = "visible" | "hidden" | "fit";
 export type RGBAColor = string;
+export type BlendMode = "normal" | "multiply" | "screen" | "add" | "overlay";
 export type RendererId = string;
 export type ShaderParam =
   | {
@@ -882,9 +1247,11 @@ export type TextWeight =
   | "bold"
   | "extra_bold"
   | "black";
+export type MaskMode = "alpha" | "luminance";
 export type AspectRatio = string;
 export type VerticalAlign = "top" | "center" | "bottom" | "justified";
 export type RescaleMode = "fit" | "fill";
+export type TickerDirection = "left" | "right" | "up" | "down";
 export type AudioMixingStrategy = "sum_clip" | "sum_scale";
 export type RtpAudioEncoderOptions = {
   type: "opus";
@@ -922,6 +1289,14 @@ export type RtmpClientVideoEncoderOptions =
        * (**default=`"yuv420p"`**) Encoder pixel format
        */
       pixel_format?: PixelFormat | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
       /**
        * Raw FFmpeg encoder options. See [docs](https://ffmpeg.org/ffmpeg-codecs.html) for more.
        */
@@ -935,6 +1310,14 @@ export type RtmpClientVideoEncoderOptions =
        * Encoding bitrate. If not provided, bitrate is calculated based on resolution and framerate. For example at 1080p 30 FPS the average bitrate is 5000 kbit/s and max bitrate is 6250 kbit/s.
        */
       bitrate?: VideoEncoderBitrate | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
     };
 export type RtmpClientAudioEncoderOptions = {
   type: "aac";
@@ -958,6 +1341,14 @@ export type Mp4VideoEncoderOptions =
        * (**default=`"yuv420p"`**) Encoder pixel format.
        */
       pixel_format?: PixelFormat | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
       /**
        * Raw FFmpeg encoder options. See [docs](https://ffmpeg.org/ffmpeg-codecs.html) for more.
        */
@@ -971,6 +1362,14 @@ export type Mp4VideoEncoderOptions =
        * Encoding bitrate. If not provided, bitrate is calculated based on resolution and framerate. For example at 1080p 30 FPS the average bitrate is 5000 kbit/s and max bitrate is 6250 kbit/s.
        */
       bitrate?: VideoEncoderBitrate | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
     };
 export type Mp4AudioEncoderOptions = {
   type: "aac";
@@ -994,6 +1393,14 @@ export type WhipVideoEncoderOptions =
        * (**default=`"yuv420p"`**) Encoder pixel format
        */
       pixel_format?: PixelFormat | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
       /**
        * Raw FFmpeg encoder options. See [docs](https://ffmpeg.org/ffmpeg-codecs.html) for more.
        */
@@ -1020,6 +1427,14 @@ export type WhipVideoEncoderOptions =
        * (**default=`"yuv420p"`**) Encoder pixel format
        */
       pixel_format?: PixelFormat | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
       /**
        * Encoding bitrate. If not provided, bitrate is calculated based on resolution and framerate. For example at 1080p 30 FPS the average bitrate is 5000 kbit/s and max bitrate is 6250 kbit/s.
        */
@@ -1037,6 +1452,14 @@ export type WhipVideoEncoderOptions =
        * Encoding bitrate. If not provided, bitrate is calculated based on resolution and framerate. For example at 1080p 30 FPS the average bitrate is 5000 kbit/s and max bitrate is 6250 kbit/s.
        */
       bitrate?: VideoEncoderBitrate | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
     }
   | {
       type: "any";
@@ -1075,6 +1498,14 @@ export type WhepVideoEncoderOptions =
        * (**default=`"yuv420p"`**) Encoder pixel format.
        */
       pixel_format?: PixelFormat | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
       /**
        * Raw FFmpeg encoder options. Visit [docs](https://ffmpeg.org/ffmpeg-codecs.html) to learn more.
        */
@@ -1105,6 +1536,14 @@ export type WhepVideoEncoderOptions =
        * (**default=`"yuv420p"`**) Encoder pixel format.
        */
       pixel_format?: PixelFormat | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
       /**
        * Raw FFmpeg encoder options. Visit [docs](https://ffmpeg.org/ffmpeg-codecs.html) to learn more.
        */
@@ -1118,6 +1557,14 @@ export type WhepVideoEncoderOptions =
        * Encoding bitrate. If not provided, bitrate is calculated based on resolution and framerate. For example at 1080p 30 FPS the average bitrate is 5000 kbit/s and max bitrate is 6250 kbit/s.
        */
       bitrate?: VideoEncoderBitrate | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
     };
 export type WhepAudioEncoderOptions = {
   type: "opus";
@@ -1153,6 +1600,14 @@ export type HlsVideoEncoderOptions =
        * (**default=`"yuv420p"`**) Encoder pixel format
        */
       pixel_format?: PixelFormat | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
       /**
        * Raw FFmpeg encoder options. See [docs](https://ffmpeg.org/ffmpeg-codecs.html) for more.
        */
@@ -1166,6 +1621,14 @@ export type HlsVideoEncoderOptions =
        * Encoding bitrate. If not provided, bitrate is calculated based on resolution and framerate. For example at 1080p 30 FPS the average bitrate is 5000 kbit/s and max bitrate is 6250 kbit/s.
        */
       bitrate?: VideoEncoderBitrate | null;
+      /**
+       * (**default=`"bt709"`**) Color space of the encoded video.
+       */
+      color_space?: ColorSpace | null;
+      /**
+       * (**default=`"limited"`**) Color range of the encoded video.
+       */
+      color_range?: ColorRange | null;
     };
 export type HlsAudioEncoderOptions = {
   type: "aac";
@@ -1196,6 +1659,11 @@ export type ImageSpec =
       url?: string | null;
       path?: string | null;
     }
+  | {
+      asset_type: "webp";
+      url?: string | null;
+      path?: string | null;
+    }
   | {
       asset_type: "auto";
       url?: string | null;
@@ -1205,10 +1673,60 @@ export type WebEmbeddingMethod =
   | "chromium_embedding"
   | "native_embedding_over_content"
   | "native_embedding_under_content";
+/**
+ * Operation that modifies a part of the current scene. Components are identified by the `id` field.
+ */
+export type ScenePatch =
+  | {
+      op: "replace";
+      /**
+       * Id of a component that should be replaced.
+       */
+      id: ComponentId;
+      /**
+       * New component.
+       */
+      component: Component;
+    }
+  | {
+      op: "insert";
+      /**
+       * Id of a parent component.
+       */
+      parent_id: ComponentId;
+      /**
+       * Position of the new component in the list of children. If not specified, the component is added after the existing children.
+       */
+      index?: number | null;
+      /**
+       * New component.
+       */
+      component: Component;
+    }
+  | {
+      op: "remove";
+      /**
+       * Id of a component that should be removed.
+       */
+      id: ComponentId;
+    };
 
 export interface InputRtpVideoOptions {
   decoder: RtpVideoDecoderOptions;
 }
+/**
+ * Failover configuration of an input.
+ */
+export interface InputFailover {
+  /**
+   * Inputs used in place of this input when it stops delivering data for longer than `SMELTER_STREAM_FALLBACK_TIMEOUT_MS`. The first available input from the list is used. Audio and video are switched independently.
+   */
+  backup_inputs?: InputId[] | null;
+  /**
+   * Id of a registered image that is rendered when neither the input nor any of its backups deliver frames.
+   */
+  slate_image_id?: RendererId | null;
+}
 export interface InputWhipVideoOptions {
   decoder_preferences?: WhipVideoDecoderOptions[] | null;
 }
@@ -1230,6 +1748,12 @@ export interface OutputRtpVideoOptions {
    * Output resolution in pixels.
    */
   resolution: Resolution;
+  /**
+   * Output framerate. Must be either an unsigned integer, or a string in the "NUM/DEN"
+   * format, where NUM and DEN are both unsigned integers. Defaults to the framerate
+   * configured for the whole Smelter instance.
+   */
+  framerate?: Framerate | null;
   /**
    * Condition for termination of the output stream based on the input streams states. If output includes both audio and video streams, then EOS needs to be sent for every type.
    */
@@ -1281,17 +1805,129 @@ export interface Transition {
    */
   should_interrupt?: boolean | null;
 }
+/**
+ * Keyframe animation of component properties. Supported by `View` and `Rescaler` components, other components can be animated by wrapping them in one of those. Animation starts when a component with this animation is added to the scene. To keep the animation running across scene updates, the component needs to have an `id` and the same animation definition.
+ */
+export interface Animation {
+  /**
+   * List of keyframes sorted by `time_ms`. Values of a property are interpolated between consecutive keyframes that define that property.
+   */
+  keyframes: Keyframe[];
+  /**
+   * (**default=`"none"`**) Controls what happens after the last keyframe.
+   */
+  repeat?: AnimationRepeat | null;
+}
+export interface Keyframe {
+  /**
+   * Time in milliseconds since the start of the animation.
+   */
+  time_ms: number;
+  /**
+   * (**default=`"linear"`**) Easing function used to interpolate from this keyframe to the next one.
+   */
+  easing_function?: EasingFunction | null;
+  /**
+   * Width of a component in pixels.
+   */
+  width?: number | null;
+  /**
+   * Height of a component in pixels.
+   */
+  height?: number | null;
+  /**
+   * Distance in pixels between the component's top edge and its parent's top edge. Only applies to absolutely positioned components.
+   */
+  top?: number | null;
+  /**
+   * Distance in pixels between the component's bottom edge and its parent's bottom edge. Only applies to absolutely positioned components.
+   */
+  bottom?: number | null;
+  /**
+   * Distance in pixels between the component's left edge and its parent's left edge. Only applies to absolutely positioned components.
+   */
+  left?: number | null;
+  /**
+   * Distance in pixels between the component's right edge and its parent's right edge. Only applies to absolutely positioned components.
+   */
+  right?: number | null;
+  /**
+   * Rotation of a component in degrees. Only applies to absolutely positioned components.
+   */
+  rotation?: number | null;
+  /**
+   * Opacity of a component in the range `[0, 1]`.
+   */
+  opacity?: number | null;
+}
 export interface BoxShadow {
   offset_x?: number | null;
   offset_y?: number | null;
   color?: RGBAColor | null;
   blur_radius?: number | null;
 }
+/**
+ * Fragment of the text with its own styling. Color emoji are rendered if a font that supports them (e.g. "Noto Color Emoji") is installed or registered.
+ */
+export interface TextSpan {
+  /**
+   * Text that will be rendered.
+   */
+  text: string;
+  /**
+   * Font color in `#RRGGBBAA` format. Defaults to the `color` of the Text component.
+   */
+  color?: RGBAColor | null;
+  /**
+   * Font family. Defaults to the `font_family` of the Text component.
+   */
+  font_family?: string | null;
+  /**
+   * Font style. Defaults to the `style` of the Text component.
+   */
+  style?: TextStyle | null;
+  /**
+   * Font weight. Defaults to the `weight` of the Text component.
+   */
+  weight?: TextWeight | null;
+}
+export interface TextOutline {
+  /**
+   * Width of the outline in pixels. Has to be in the `[0, 500]` range.
+   */
+  width: number;
+  /**
+   * (**default=`"#000000FF"`**) Outline color in `#RRGGBBAA` format.
+   */
+  color?: RGBAColor | null;
+}
+export interface TextShadow {
+  /**
+   * (**default=`0.0`**) Horizontal offset of the shadow in pixels. Has to be in the `[-500, 500]` range.
+   */
+  offset_x?: number | null;
+  /**
+   * (**default=`0.0`**) Vertical offset of the shadow in pixels. Has to be in the `[-500, 500]` range.
+   */
+  offset_y?: number | null;
+  /**
+   * (**default=`0.0`**) Blur radius of the shadow in pixels. Has to be in the `[0, 500]` range.
+   */
+  blur_radius?: number | null;
+  /**
+   * (**default=`"#000000FF"`**) Shadow color in `#RRGGBBAA` format.
+   */
+  color?: RGBAColor | null;
+}
 export interface OutputRtpAudioOptions {
   /**
    * (**default="sum_clip"**) Specifies how audio should be mixed.
    */
   mixing_strategy?: AudioMixingStrategy | null;
+  /**
+   * Normalizes loudness of the mixed audio to the target. Applied after the mixing strategy.
+   */
+  loudness_normalization?: LoudnessNormalization | null;
   /**
    * Condition for termination of output stream based on the input streams states. If output includes both audio and video streams, then EOS needs to be sent for every type.
    */
@@ -1309,6 +1945,16 @@ export interface OutputRtpAudioOptions {
    */
   initial: AudioScene;
 }
+export interface LoudnessNormalization {
+  /**
+   * Target loudness in LUFS measured as defined in EBU R128, e.g. `-23` for broadcast or `-14` for streaming platforms. Value has to be in `[-70, 0]` range.
+   */
+  target_lufs: number;
+  /**
+   * (**default=`-1.0`**) Maximum true peak of the normalized audio in dBTP. Value has to be in `[-20, 0]` range.
+   */
+  true_peak_limit_dbtp?: number | null;
+}
 export interface AudioScene {
   inputs: AudioSceneInput[];
 }
@@ -1324,6 +1970,12 @@ export interface OutputRtmpClientVideoOptions {
    * Output resolution in pixels.
    */
   resolution: Resolution;
+  /**
+   * Output framerate. Must be either an unsigned integer, or a string in the "NUM/DEN"
+   * format, where NUM and DEN are both unsigned integers. Defaults to the framerate
+   * configured for the whole Smelter instance.
+   */
+  framerate?: Framerate | null;
   /**
    * Condition for termination of the output stream based on the input streams states. If output includes both audio and video streams, then EOS needs to be sent for every type.
    */
@@ -1342,6 +1994,10 @@ export interface OutputRtmpClientAudioOptions {
    * (**default="sum_clip"**) Specifies how audio should be mixed.
    */
   mixing_strategy?: AudioMixingStrategy | null;
+  /**
+   * Normalizes loudness of the mixed audio to the target. Applied after the mixing strategy.
+   */
+  loudness_normalization?: LoudnessNormalization | null;
   /**
    * Condition for termination of the output stream based on the input streams states. If output includes both audio and video streams, then EOS needs to be sent for every type.
    */
@@ -1364,6 +2020,12 @@ export interface OutputMp4VideoOptions {
    * Output resolution in pixels.
    */
   resolution: Resolution;
+  /**
+   * Output framerate. Must be either an unsigned integer, or a string in the "NUM/DEN"
+   * format, where NUM and DEN are both unsigned integers. Defaults to the framerate
+   * configured for the whole Smelter instance.
+   */
+  framerate?: Framerate | null;
   /**
    * Condition for termination of the output stream based on the input streams states. If output includes both audio and video streams, then EOS needs to be sent for every type.
    */
@@ -1382,6 +2044,10 @@ export interface OutputMp4AudioOptions {
    * (**default="sum_clip"**) Specifies how audio should be mixed.
    */
   mixing_strategy?: AudioMixingStrategy | null;
+  /**
+   * Normalizes loudness of the mixed audio to the target. Applied after the mixing strategy.
+   */
+  loudness_normalization?: LoudnessNormalization | null;
   /**
    * Condition for termination of output stream based on the input streams states.
    */
@@ -1404,6 +2070,12 @@ export interface OutputWhipVideoOptions {
    * Output resolution in pixels.
    */
   resolution: Resolution;
+  /**
+   * Output framerate. Must be either an unsigned integer, or a string in the "NUM/DEN"
+   * format, where NUM and DEN are both unsigned integers. Defaults to the framerate
+   * configured for the whole Smelter instance.
+   */
+  framerate?: Framerate | null;
   /**
    * Defines when output stream should end if some of the input streams are finished. If output includes both audio and video streams, then EOS needs to be sent on both.
    */
@@ -1422,6 +2094,10 @@ export interface OutputWhipAudioOptions {
    * (**default="sum_clip"**) Specifies how audio should be mixed.
    */
   mixing_strategy?: AudioMixingStrategy | null;
+  /**
+   * Normalizes loudness of the mixed audio to the target. Applied after the mixing strategy.
+   */
+  loudness_normalization?: LoudnessNormalization | null;
   /**
    * Condition for termination of output stream based on the input streams states.
    */
@@ -1444,6 +2120,12 @@ export interface OutputWhepVideoOptions {
    * Output resolution in pixels.
    */
   resolution: Resolution;
+  /**
+   * Output framerate. Must be either an unsigned integer, or a string in the "NUM/DEN"
+   * format, where NUM and DEN are both unsigned integers. Defaults to the framerate
+   * configured for the whole Smelter instance.
+   */
+  framerate?: Framerate | null;
   /**
    * Condition for termination of the output stream based on the input streams states. If output includes both audio and video streams, then EOS needs to be sent for every type.
    */
@@ -1462,6 +2144,10 @@ export interface OutputWhepAudioOptions {
    * (**default="sum_clip"**) Specifies how audio should be mixed.
    */
   mixing_strategy?: AudioMixingStrategy | null;
+  /**
+   * Normalizes loudness of the mixed audio to the target. Applied after the mixing strategy.
+   */
+  loudness_normalization?: LoudnessNormalization | null;
   /**
    * Condition for termination of output stream based on the input streams states.
    */
@@ -1484,6 +2170,12 @@ export interface OutputHlsVideoOptions {
    * Output resolution in pixels.
    */
   resolution: Resolution;
+  /**
+   * Output framerate. Must be either an unsigned integer, or a string in the "NUM/DEN"
+   * format, where NUM and DEN are both unsigned integers. Defaults to the framerate
+   * configured for the whole Smelter instance.
+   */
+  framerate?: Framerate | null;
   /**
    * Condition for termination of the output stream based on the input streams states. If output includes both audio and video streams, then EOS needs to be sent for every type.
    */
@@ -1502,6 +2194,10 @@ export interface OutputHlsAudioOptions {
    * (**default="sum_clip"**) Specifies how audio should be mixed.
    */
   mixing_strategy?: AudioMixingStrategy | null;
+  /**
+   * Normalizes loudness of the mixed audio to the target. Applied after the mixing strategy.
+   */
+  loudness_normalization?: LoudnessNormalization | null;
   /**
    * Condition for termination of output stream based on the input streams states.
    */
@@ -1539,8 +2235,173 @@ export interface ShaderSpec {
    */
   source: string;
 }
+/**
+ * Short video clip loaded from an MP4 file. The entire clip is decoded when registered and kept in memory, so only short clips are supported. Exactly one of `url` and `path` has to be defined.
+ */
+export interface VideoClipSpec {
+  /**
+   * URL of the MP4 file.
+   */
+  url?: string | null;
+  /**
+   * Path to the MP4 file.
+   */
+  path?: string | null;
+}
+export interface FragmentSpec {
+  /**
+   * Component tree that can be used in output scenes with a `fragment` component.
+   */
+  root: Component;
+}
+/**
+ * MP4 file played as a part of a playlist. Exactly one of `url` and `path` has to be defined.
+ */
+export interface PlaylistItem {
+  /**
+   * Identifies the item in playlist events and updates. It has to be unique among queued items.
+   */
+  item_id: string;
+  /**
+   * URL of the MP4 file.
+   */
+  url?: string | null;
+  /**
+   * Path to the MP4 file.
+   */
+  path?: string | null;
+}
 export interface UpdateOutputRequest {
   video?: VideoScene | null;
   audio?: AudioScene | null;
   schedule_time_ms?: number | null;
 }
+export interface PatchOutputRequest {
+  /**
+   * List of operations applied in order to the current video scene. If any of them fails, the scene is not modified.
+   */
+  operations: ScenePatch[];
+  schedule_time_ms?: number | null;
+}
+export interface UpdateFragmentRequest {
+  /**
+   * New component tree of the fragment. All outputs that use this fragment are updated on the same frame.
+   */
+  root: Component;
+  schedule_time_ms?: number | null;
+}
+export interface UpdatePlaylistRequest {
+  /**
+   * Ids of queued items that should be removed. Items that already started playing can't be removed.
+   */
+  remove?: string[] | null;
+  /**
+   * Items added at the end of the playlist. Removals are applied first.
+   */
+  append?: PlaylistItem[] | null;
+}
+export interface ReplayBufferRequest {
+  /**
+   * Duration in milliseconds of the most recent media kept in the buffer.
+   */
+  duration_ms: number;
+  /**
+   * (**default=`256`**) Limit of memory in megabytes used by buffered encoded chunks. When it is exceeded, the oldest media is dropped.
+   */
+  max_memory_mb?: number | null;
+}
+export interface AudioDelayRequest {
+  /**
+   * Shift in milliseconds applied only to audio of the input. Positive values delay audio, negative values play it earlier. Has to be in the `[-10000, 10000]` range. Replaces the previous value.
+   */
+  audio_delay_ms: number;
+}
+export interface BatchRequest {
+  /**
+   * All operations are validated before any of them is applied, if one of them is invalid the whole batch is rejected. Registrations are applied first, then all output updates are applied at once, so new scenes are rendered starting from the same frame. If any registration or update fails, entities registered by the batch are unregistered and no output is updated. Unregistrations are applied last.
+   */
+  operations: BatchOperation[];
+  /**
+   * Time in milliseconds when the batch should be applied. Value `0` represents time of the start request.
+   */
+  schedule_time_ms?: number | null;
+}
+export type BatchOperation =
+  | {
+      type: "register_input";
+      input_id: InputId;
+      input: RegisterInput;
+    }
+  | {
+      type: "unregister_input";
+      input_id: InputId;
+    }
+  | {
+      type: "register_output";
+      output_id: OutputId;
+      output: RegisterOutput;
+    }
+  | {
+      type: "unregister_output";
+      output_id: OutputId;
+    }
+  | {
+      type: "update_output";
+      output_id: OutputId;
+      video?: VideoScene | null;
+      audio?: AudioScene | null;
+    }
+  | {
+      type: "register_shader";
+      shader_id: RendererId;
+      shader: ShaderSpec;
+    }
+  | {
+      type: "unregister_shader";
+      shader_id: RendererId;
+    }
+  | {
+      type: "register_web_renderer";
+      instance_id: RendererId;
+      web_renderer: WebRendererSpec;
+    }
+  | {
+      type: "unregister_web_renderer";
+      instance_id: RendererId;
+    }
+  | {
+      type: "register_image";
+      image_id: RendererId;
+      image: ImageSpec;
+    }
+  | {
+      type: "unregister_image";
+      image_id: RendererId;
+    }
+  | {
+      type: "register_video_clip";
+      clip_id: RendererId;
+      video_clip: VideoClipSpec;
+    }
+  | {
+      type: "unregister_video_clip";
+      clip_id: RendererId;
+    }
+  | {
+      type: "register_fragment";
+      fragment_id: RendererId;
+      fragment: FragmentSpec;
+    }
+  | {
+      type: "unregister_fragment";
+      fragment_id: RendererId;
+    };
+export type OutputId = string;
+export interface RegisterSchedule {
+  /**
+   * Time in milliseconds when this request should be applied. Value `0` represents time of the start request.
+   *
+   * Registered entity is initialized right away and the response is sent before it is added to the pipeline. It is used starting from a frame with exactly that time.
+   */
+  schedule_time_ms?: number | null;
+}
@@ -24,6 +24,18 @@ export type ImageProps = Omit<ComponentBaseProps, 'children'> &
      * Component styling properties.
      */
     style?: ImageStyleProps;
+    /**
+     * Number of times an animated image (GIF, WebP, APNG) is played. After the last loop, the final frame stays visible. If not provided, the animation loops forever.
+     */
+    loopCount?: number;
+    /**
+     * (**default=`true`**) If `true`, an animated image starts from the first frame when the component is added to the scene. If `false`, the animation is synchronized with the output timeline.
+     */
+    restartOnMount?: boolean;
+    /**
+     * (**default=`0`**) Offset in milliseconds from which an animated image starts playing.
+     */
+    startAtMs?: number;
   };
 
 type ImageStyleProps = {
@@ -123,6 +135,9 @@ function sceneBuilder(props: ImageSceneBuliderProps, _children: SceneComponent[]
     image_id: props.imageId,
     width: props.style?.width,
     height: props.style?.height,
+    loop_count: props.loopCount,
+    restart_on_mount: props.restartOnMount,
+    start_at_ms: props.startAtMs,
   };
 }
 
@@ -0,0 +1,60 @@
+import type React from 'react';
+import type * as Api from '../api.js';
+import type { ComponentBaseProps, SceneComponent } from '../component.js';
+import { createSmelterComponent, sceneComponentIntoApi } from '../component.js';
+
+type MaskChild = React.ReactElement | string | number;
+
+export type MaskProps = ComponentBaseProps & {
+  /**
+   * Exactly two children. The first one is the content that will be rendered and the second
+   * one is the mask that defines which parts of the content are visible. Both are stretched
+   * to the size of the component.
+   */
+  children: [MaskChild, MaskChild];
+  /**
+   * Width of a component in pixels.
+   */
+  width: number;
+  /**
+   * Height of a component in pixels.
+   */
+  height: number;
+  /**
+   * (**default=`"alpha"`**) Defines which property of the mask is used to calculate
+   * visibility of the content.
+   */
+  mode?: Api.MaskMode;
+  /**
+   * (**default=`false`**) Render content only where the mask is not visible.
+   */
+  invert?: boolean;
+};
+
+/**
+ * Renders its content only in areas where the mask is visible, e.g. to cut a video
+ * into a circle or to fill a text with a video.
+ */
+const Mask = createSmelterComponent<MaskProps>(sceneBuilder);
+
+function sceneBuilder(
+  { id, width, height, mode, invert }: MaskProps,
+  children: SceneComponent[]
+): Api.Component {
+  if (children?.length !== 2) {
+    throw new Error('Mask component requires exactly two children: content and mask.');
+  }
+
+  return {
+    type: 'mask',
+    id: id,
+    content: sceneComponentIntoApi(children[0]),
+    mask: sceneComponentIntoApi(children[1]),
+    width,
+    height,
+    mode,
+    invert,
+  };
+}
+
+export default Mask;
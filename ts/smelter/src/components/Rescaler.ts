@@ -1,7 +1,7 @@
 import type React from 'react';
 import type * as Api from '../api.js';
-import type { BoxShadow, Transition } from './common.js';
-import { intoApiBoxShadow, intoApiTransition } from './common.js';
+import type { Animation, BoxShadow, Transition } from './common.js';
+import { intoApiAnimation, intoApiBoxShadow, intoApiTransition } from './common.js';
 import type { ComponentBaseProps, SceneComponent } from '../component.js';
 import { createSmelterComponent, sceneComponentIntoApi } from '../component.js';
 
@@ -18,6 +18,20 @@ export type RescalerStyleProps = {
    * (**default=`"center"`**) Vertical alignment.
    */
   verticalAlign?: Api.VerticalAlign;
+  /**
+   * Share of the free space in the parent `View` that is added to the size of this
+   * component. By default, components without an explicit size grow with factor `1`
+   * and components with an explicit size do not grow. Other children of a `View`,
+   * e.g. `Image` or `Tiles`, always use the default.
+   */
+  flexGrow?: number;
+  /**
+   * (**default=`0.0`**) Share of the missing space in the parent `View` that is
+   * subtracted from the size of this component, weighted by its size. Components
+   * do not shrink below the size of their content. Other children of a `View`,
+   * e.g. `Image` or `Tiles`, always use the default.
+   */
+  flexShrink?: number;
   /**
    * Width of a component in pixels. Exact behavior might be different based on the parent
    * component:
@@ -78,6 +92,15 @@ export type RescalerStyleProps = {
    * Properties of the BoxShadow applied to the container.
    */
   boxShadow?: BoxShadow[];
+  /**
+   * (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+   */
+  opacity?: number;
+  /**
+   * (**default=`"normal"`**) Defines how the component is composed with the content
+   * rendered below it.
+   */
+  blendMode?: Api.BlendMode;
 };
 
 export type RescalerProps = ComponentBaseProps & {
@@ -94,12 +117,18 @@ export type RescalerProps = ComponentBaseProps & {
    * effect if the previous scene already contained a `Rescaler` component with the same id.
    */
   transition?: Transition;
+  /**
+   * Keyframe animation of the component's size, position and opacity. Animated values
+   * override values defined in `style`. Animation keeps running across scene updates
+   * only if the component has an `id`.
+   */
+  animation?: Animation;
 };
 
 const Rescaler = createSmelterComponent<RescalerProps>(sceneBuilder);
 
 function sceneBuilder(
-  { id, style, transition }: RescalerProps,
+  { id, style, transition, animation }: RescalerProps,
   children: SceneComponent[]
 ): Api.Component {
   if (children?.length !== 1) {
@@ -113,6 +142,8 @@ function sceneBuilder(
     mode: style?.rescaleMode,
     horizontal_align: style?.horizontalAlign,
     vertical_align: style?.verticalAlign,
+    flex_grow: style?.flexGrow,
+    flex_shrink: style?.flexShrink,
     width: style?.width,
     height: style?.height,
     top: style?.top,
@@ -121,10 +152,13 @@ function sceneBuilder(
     right: style?.right,
     rotation: style?.rotation,
     transition: transition && intoApiTransition(transition),
+    animation: animation && intoApiAnimation(animation),
     border_radius: style?.borderRadius,
     border_width: style?.borderWidth,
     border_color: style?.borderColor,
     box_shadow: style?.boxShadow && intoApiBoxShadow(style.boxShadow),
+    opacity: style?.opacity,
+    blend_mode: style?.blendMode,
   };
 }
 
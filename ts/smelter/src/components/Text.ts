@@ -61,6 +61,39 @@ export type TextStyleProps = {
    * (**default=`"normal"`**) Font weight. The selected font needs to support the specified weight.
    */
   fontWeight?: Api.TextWeight;
+  /**
+   * (**default=`0`**) Additional space between letters in pixels.
+   */
+  letterSpacing?: number;
+  /**
+   * Outline drawn around the text.
+   */
+  textOutline?: TextOutline;
+  /**
+   * Shadow drawn below the text.
+   */
+  textShadow?: TextShadow;
+};
+
+export type TextOutline = {
+  /**
+   * Width of the outline in pixels. Has to be in the `[0, 500]` range.
+   */
+  width: number;
+  /**
+   * (**default=`"#000000FF"`**) Outline color in `RGB` or `RGBA` format.
+   */
+  color?: string;
+};
+
+export type TextShadow = {
+  offsetX?: number;
+  offsetY?: number;
+  blurRadius?: number;
+  /**
+   * (**default=`"#000000FF"`**) Shadow color in `RGB` or `RGBA` format.
+   */
+  color?: string;
 };
 
 export type TextProps = ComponentBaseProps & {
@@ -96,6 +129,17 @@ function sceneBuilder(props: TextProps, children: SceneComponent[]): Api.Compone
     align: style?.align,
     wrap: style?.wrap,
     weight: style?.fontWeight,
+    letter_spacing: style?.letterSpacing,
+    outline: style?.textOutline && {
+      width: style.textOutline.width,
+      color: style.textOutline.color,
+    },
+    shadow: style?.textShadow && {
+      offset_x: style.textShadow.offsetX,
+      offset_y: style.textShadow.offsetY,
+      blur_radius: style.textShadow.blurRadius,
+      color: style.textShadow.color,
+    },
   };
 }
 
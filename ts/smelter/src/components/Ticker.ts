@@ -0,0 +1,88 @@
+import type * as Api from '../api.js';
+import type { ComponentBaseProps, SceneComponent } from '../component.js';
+import { createSmelterComponent, sceneComponentIntoApi } from '../component.js';
+
+export type TickerStyleProps = {
+  /**
+   * Width of a component in pixels. Exact behavior might be different based on the parent
+   * component:
+   * - If the parent component is a layout, check sections "Absolute positioning" and "Static
+   * positioning" of that component.
+   * - If the parent component is not a layout, then this field is required.
+   */
+  width?: number;
+  /**
+   * Height of a component in pixels. Exact behavior might be different based on the parent
+   * component:
+   * - If the parent component is a layout, check sections "Absolute positioning" and "Static
+   * positioning" of that component.
+   * - If the parent component is not a layout, then this field is required.
+   */
+  height?: number;
+  /**
+   * (**default=`"#00000000"`**) Background color in `RGB` or `RGBA` format.
+   */
+  backgroundColor?: string;
+  /**
+   * (**default=`0`**) Space between consecutive items in pixels.
+   */
+  gap?: number;
+  /**
+   * (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+   */
+  opacity?: number;
+  /**
+   * (**default=`"normal"`**) Defines how the component is composed with the content
+   * rendered below it.
+   */
+  blendMode?: Api.BlendMode;
+};
+
+export type TickerProps = ComponentBaseProps & {
+  /**
+   * Ticker styling properties
+   */
+  style?: TickerStyleProps;
+  /**
+   * (**default=`"left"`**) Direction in which items are moving.
+   */
+  direction?: Api.TickerDirection;
+  /**
+   * (**default=`100`**) Scroll speed in pixels per second.
+   */
+  speed?: number;
+  /**
+   * (**default=`true`**) Start scrolling items from the beginning after the last one
+   * leaves the component area.
+   */
+  loop?: boolean;
+};
+
+/**
+ * Scrolls its children one after another. Children added on a scene update are appended
+ * at the end of the queue without restarting the scroll, as long as the component keeps
+ * the same `id`.
+ */
+const Ticker = createSmelterComponent<TickerProps>(sceneBuilder);
+
+function sceneBuilder(
+  { id, style, direction, speed, loop }: TickerProps,
+  children: SceneComponent[]
+): Api.Component {
+  return {
+    type: 'ticker',
+    id: id,
+    children: children.map(sceneComponentIntoApi),
+    width: style?.width,
+    height: style?.height,
+    background_color: style?.backgroundColor,
+    gap: style?.gap,
+    opacity: style?.opacity,
+    blend_mode: style?.blendMode,
+    direction,
+    speed,
+    loop,
+  };
+}
+
+export default Ticker;
@@ -45,6 +45,15 @@ export type TilesStyleProps = {
    * (**default=`"center"`**) Vertical alignment of tiles.
    */
   verticalAlign?: Api.VerticalAlign;
+  /**
+   * (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+   */
+  opacity?: number;
+  /**
+   * (**default=`"normal"`**) Defines how the component is composed with the content
+   * rendered below it.
+   */
+  blendMode?: Api.BlendMode;
 };
 
 export type TilesProps = ComponentBaseProps & {
@@ -78,6 +87,8 @@ function sceneBuilder(
     horizontal_align: style?.horizontalAlign,
     vertical_align: style?.verticalAlign,
     transition: transition && intoApiTransition(transition),
+    opacity: style?.opacity,
+    blend_mode: style?.blendMode,
   };
 }
 
@@ -1,8 +1,8 @@
 import type * as Api from '../api.js';
 import type { ComponentBaseProps, SceneComponent } from '../component.js';
 import { createSmelterComponent, sceneComponentIntoApi } from '../component.js';
-import type { BoxShadow, Transition } from './common.js';
-import { intoApiBoxShadow, intoApiTransition } from './common.js';
+import type { Animation, BoxShadow, Transition } from './common.js';
+import { intoApiAnimation, intoApiBoxShadow, intoApiTransition } from './common.js';
 
 export type ViewStyleProps = {
   /**
@@ -25,6 +25,40 @@ export type ViewStyleProps = {
    * Direction defines how static children are positioned inside a View component.
    */
   direction?: Api.ViewDirection;
+  /**
+   * (**default=`0.0`**) Space in pixels between static children. If `wrap` is enabled,
+   * it also defines space between rows (or columns).
+   */
+  gap?: number;
+  /**
+   * (**default=`"start"`**) Distribution of static children along the `direction`.
+   */
+  justifyContent?: Api.JustifyContent;
+  /**
+   * (**default=`"stretch"`**) Alignment of static children in the axis perpendicular
+   * to the `direction`. Unless children are stretched, children without an explicit
+   * size are sized to their content.
+   */
+  alignItems?: Api.AlignItems;
+  /**
+   * (**default=`false`**) Move static children that do not fit into the next row (or
+   * column if `direction` is `"column"`).
+   */
+  wrap?: boolean;
+  /**
+   * Share of the free space in the parent `View` that is added to the size of this
+   * component. By default, components without an explicit size grow with factor `1`
+   * and components with an explicit size do not grow. Other children of a `View`,
+   * e.g. `Image` or `Tiles`, always use the default.
+   */
+  flexGrow?: number;
+  /**
+   * (**default=`0.0`**) Share of the missing space in the parent `View` that is
+   * subtracted from the size of this component, weighted by its size. Components
+   * do not shrink below the size of their content. Other children of a `View`,
+   * e.g. `Image` or `Tiles`, always use the default.
+   */
+  flexShrink?: number;
   /**
    * Distance in pixels between this component's top edge and its parent's top edge.
    * If this field is defined, then the component will ignore a layout defined by its parent.
@@ -105,6 +139,15 @@ export type ViewStyleProps = {
    * (**default=`0.0`**) Sets padding for the left of the component.
    */
   paddingLeft?: number;
+  /**
+   * (**default=`1.0`**) Opacity of the component and its children in range `[0, 1]`.
+   */
+  opacity?: number;
+  /**
+   * (**default=`"normal"`**) Defines how the component is composed with the content
+   * rendered below it.
+   */
+  blendMode?: Api.BlendMode;
 };
 
 export type ViewProps = ComponentBaseProps & {
@@ -117,12 +160,18 @@ export type ViewProps = ComponentBaseProps & {
    * effect if the previous scene already contained a `View` component with the same id.
    */
   transition?: Transition;
+  /**
+   * Keyframe animation of the component's size, position and opacity. Animated values
+   * override values defined in `style`. Animation keeps running across scene updates
+   * only if the component has an `id`.
+   */
+  animation?: Animation;
 };
 
 const View = createSmelterComponent<ViewProps>(sceneBuilder);
 
 function sceneBuilder(
-  { id, style = {}, transition }: ViewProps,
+  { id, style = {}, transition, animation }: ViewProps,
   children: SceneComponent[]
 ): Api.Component {
   return {
@@ -132,6 +181,12 @@ function sceneBuilder(
     width: style.width,
     height: style.height,
     direction: style.direction,
+    gap: style.gap,
+    justify_content: style.justifyContent,
+    align_items: style.alignItems,
+    wrap: style.wrap,
+    flex_grow: style.flexGrow,
+    flex_shrink: style.flexShrink,
 
     top: style.top,
     right: style.right,
@@ -142,6 +197,7 @@ function sceneBuilder(
     overflow: style.overflow,
     background_color: style.backgroundColor,
     transition: transition && intoApiTransition(transition),
+    animation: animation && intoApiAnimation(animation),
 
     border_radius: style.borderRadius,
     border_width: style.borderWidth,
@@ -156,6 +212,8 @@ function sceneBuilder(
     padding_bottom: style.paddingBottom,
     padding_right: style.paddingRight,
     padding_left: style.paddingLeft,
+    opacity: style.opacity,
+    blend_mode: style.blendMode,
   };
 }
 
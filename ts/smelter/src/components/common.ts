@@ -26,6 +26,73 @@ export function intoApiTransition(transition: Transition): Api.Transition {
   };
 }
 
+export interface Animation {
+  /**
+   * List of keyframes sorted by `timeMs`. Values of a property are interpolated
+   * between consecutive keyframes that define that property.
+   */
+  keyframes: Keyframe[];
+  /**
+   * (**default=`"none"`**) Controls what happens after the last keyframe.
+   */
+  repeat?: Api.AnimationRepeat;
+}
+
+export interface Keyframe {
+  /**
+   * Time in milliseconds since the start of the animation.
+   */
+  timeMs: number;
+  /**
+   * (**default=`"linear"`**) Easing function used to interpolate from this keyframe
+   * to the next one.
+   */
+  easingFunction?: EasingFunction | null;
+  width?: number;
+  height?: number;
+  /**
+   * Only applies to absolutely positioned components.
+   */
+  top?: number;
+  /**
+   * Only applies to absolutely positioned components.
+   */
+  bottom?: number;
+  /**
+   * Only applies to absolutely positioned components.
+   */
+  left?: number;
+  /**
+   * Only applies to absolutely positioned components.
+   */
+  right?: number;
+  /**
+   * Only applies to absolutely positioned components.
+   */
+  rotation?: number;
+  opacity?: number;
+}
+
+export function intoApiAnimation(animation: Animation): Api.Animation {
+  return {
+    keyframes: animation.keyframes.map(keyframe => ({
+      time_ms: keyframe.timeMs,
+      easing_function: keyframe.easingFunction
+        ? intoApiEasingFunction(keyframe.easingFunction)
+        : undefined,
+      width: keyframe.width,
+      height: keyframe.height,
+      top: keyframe.top,
+      bottom: keyframe.bottom,
+      left: keyframe.left,
+      right: keyframe.right,
+      rotation: keyframe.rotation,
+      opacity: keyframe.opacity,
+    })),
+    repeat: animation.repeat,
+  };
+}
+
 export type EasingFunction =
   | 'linear'
   | 'bounce'
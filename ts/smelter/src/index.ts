@@ -6,7 +6,9 @@ import Rescaler, { RescalerProps } from './components/Rescaler.js';
 import WebView, { WebViewProps } from './components/WebView.js';
 import Shader, { ShaderParam, ShaderParamStructField, ShaderProps } from './components/Shader.js';
 import Tiles, { TilesProps } from './components/Tiles.js';
-import { EasingFunction, Transition } from './components/common.js';
+import Ticker, { TickerProps } from './components/Ticker.js';
+import Mask, { MaskProps } from './components/Mask.js';
+import { Animation, EasingFunction, Keyframe, Transition } from './components/common.js';
 import {
   useAudioInput,
   useInputStreams,
@@ -59,6 +61,10 @@ export {
   ShaderProps,
   Tiles,
   TilesProps,
+  Ticker,
+  TickerProps,
+  Mask,
+  MaskProps,
   Show,
   ShowProps,
   Slide,
@@ -71,4 +77,4 @@ export {
 
 export { useInputStreams, useAudioInput, useBlockingTask, useAfterTimestamp, useCurrentTimestamp };
 
-export { ShaderParam, ShaderParamStructField, EasingFunction, Transition };
+export { ShaderParam, ShaderParamStructField, Animation, EasingFunction, Keyframe, Transition };
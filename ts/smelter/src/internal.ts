@@ -10,7 +10,7 @@ export {
   OfflineInputStreamStore,
 } from './context/inputStreamStore.js';
 export { SceneBuilder, SceneComponent } from './component.js';
-export { SmelterEvent, SmelterEventType } from './types/events.js';
+export { SmelterEvent, SmelterEventType, AudioLevels } from './types/events.js';
 export { InputRef, inputRefIntoRawId, parseInputRef } from './types/refs/inputRef.js';
 export { ImageRef, imageRefIntoRawId, parseImageRef } from './types/refs/imageRef.js';
 export {
@@ -7,6 +7,25 @@ export enum SmelterEventType {
   VIDEO_INPUT_PLAYING = 'VIDEO_INPUT_PLAYING',
   AUDIO_INPUT_EOS = 'AUDIO_INPUT_EOS',
   VIDEO_INPUT_EOS = 'VIDEO_INPUT_EOS',
+  AUDIO_INPUT_FAILOVER = 'AUDIO_INPUT_FAILOVER',
+  VIDEO_INPUT_FAILOVER = 'VIDEO_INPUT_FAILOVER',
+  PLAYLIST_ITEM_START = 'PLAYLIST_ITEM_START',
+  PLAYLIST_ITEM_END = 'PLAYLIST_ITEM_END',
+  PLAYLIST_ITEM_ERROR = 'PLAYLIST_ITEM_ERROR',
+  INPUT_SILENCE = 'INPUT_SILENCE',
+  INPUT_SILENCE_END = 'INPUT_SILENCE_END',
+  INPUT_BLACK = 'INPUT_BLACK',
+  INPUT_BLACK_END = 'INPUT_BLACK_END',
+  INPUT_FROZEN = 'INPUT_FROZEN',
+  INPUT_FROZEN_END = 'INPUT_FROZEN_END',
+  OUTPUT_SILENCE = 'OUTPUT_SILENCE',
+  OUTPUT_SILENCE_END = 'OUTPUT_SILENCE_END',
+  OUTPUT_BLACK = 'OUTPUT_BLACK',
+  OUTPUT_BLACK_END = 'OUTPUT_BLACK_END',
+  OUTPUT_FROZEN = 'OUTPUT_FROZEN',
+  OUTPUT_FROZEN_END = 'OUTPUT_FROZEN_END',
+  AUDIO_INPUT_LEVELS = 'AUDIO_INPUT_LEVELS',
+  AUDIO_OUTPUT_LEVELS = 'AUDIO_OUTPUT_LEVELS',
   OUTPUT_DONE = 'OUTPUT_DONE',
 }
 
@@ -17,4 +36,57 @@ export type SmelterEvent =
   | { type: SmelterEventType.VIDEO_INPUT_PLAYING; inputRef: InputRef }
   | { type: SmelterEventType.AUDIO_INPUT_EOS; inputRef: InputRef }
   | { type: SmelterEventType.VIDEO_INPUT_EOS; inputRef: InputRef }
-  | { type: SmelterEventType.OUTPUT_DONE; outputId: string };
+  | {
+      type: SmelterEventType.AUDIO_INPUT_FAILOVER | SmelterEventType.VIDEO_INPUT_FAILOVER;
+      inputRef: InputRef;
+      source: FailoverSource;
+      backupInputRef?: InputRef;
+    }
+  | {
+      type: SmelterEventType.PLAYLIST_ITEM_START | SmelterEventType.PLAYLIST_ITEM_END;
+      inputRef: InputRef;
+      itemId: string;
+    }
+  | {
+      type: SmelterEventType.PLAYLIST_ITEM_ERROR;
+      inputRef: InputRef;
+      itemId: string;
+      error: string;
+    }
+  | {
+      type:
+        | SmelterEventType.INPUT_SILENCE
+        | SmelterEventType.INPUT_SILENCE_END
+        | SmelterEventType.INPUT_BLACK
+        | SmelterEventType.INPUT_BLACK_END
+        | SmelterEventType.INPUT_FROZEN
+        | SmelterEventType.INPUT_FROZEN_END;
+      inputRef: InputRef;
+    }
+  | ({ type: SmelterEventType.AUDIO_INPUT_LEVELS; inputRef: InputRef } & AudioLevels)
+  | ({ type: SmelterEventType.AUDIO_OUTPUT_LEVELS; outputId: string } & AudioLevels)
+  | {
+      type:
+        | SmelterEventType.OUTPUT_SILENCE
+        | SmelterEventType.OUTPUT_SILENCE_END
+        | SmelterEventType.OUTPUT_BLACK
+        | SmelterEventType.OUTPUT_BLACK_END
+        | SmelterEventType.OUTPUT_FROZEN
+        | SmelterEventType.OUTPUT_FROZEN_END
+        | SmelterEventType.OUTPUT_DONE;
+      outputId: string;
+    };
+
+export type FailoverSource = 'primary' | 'backup' | 'missing';
+
+/**
+ * Audio levels reported periodically for every input and output. Silence is reported as -100.
+ */
+export type AudioLevels = {
+  peakDbfs: number;
+  rmsDbfs: number;
+  /**
+   * Loudness of the last 3 seconds (EBU R128 short-term loudness).
+   */
+  shortTermLufs: number;
+};
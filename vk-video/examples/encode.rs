@@ -67,6 +67,7 @@ fn main() {
             height: height.get(),
         },
         pts: None,
+        color_description: None,
     };
 
     while let Ok(()) = nv12.read_exact(&mut frame.data.frame) {
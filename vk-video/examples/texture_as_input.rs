@@ -73,6 +73,7 @@ fn main() {
                     Frame {
                         data: wgpu_state.texture.clone(),
                         pts: None,
+                        color_description: None,
                     },
                     false,
                 )
@@ -19,8 +19,9 @@ use crate::parser::{h264::H264Parser, reference_manager::ReferenceContext};
 use crate::vulkan_decoder::{FrameSorter, VulkanDecoder};
 use crate::vulkan_encoder::{FullEncoderParameters, VulkanEncoder};
 use crate::{
-    BytesDecoder, BytesEncoder, DecoderError, RawFrameData, VulkanDecoderError, VulkanEncoderError,
-    VulkanInitError, VulkanInstance, WgpuTexturesDecoder, WgpuTexturesEncoder, wrappers::*,
+    BytesDecoder, BytesEncoder, ColorDescription, DecoderError, RawFrameData, VulkanDecoderError,
+    VulkanEncoderError, VulkanInitError, VulkanInstance, WgpuTexturesDecoder, WgpuTexturesEncoder,
+    wrappers::*,
 };
 
 pub(crate) mod caps;
@@ -121,6 +122,12 @@ pub struct EncoderParameters {
     ///
     /// Multiple flags can be combined using the `|` operator to indicate multiple usages.
     pub content_flags: Option<EncoderContentFlags>,
+
+    /// Color description signaled in the SPS VUI. Encoders created with
+    /// [`VulkanDevice::create_wgpu_textures_encoder`] also use it to convert RGBA textures to YUV,
+    /// if [`None`], BT.709 full range is used. Only BT.601, BT.709 and BT.2020 matrix coefficients
+    /// are supported by that conversion.
+    pub color_description: Option<ColorDescription>,
 }
 
 /// Open connection to a coding-capable device. Also contains a [`wgpu::Device`], a [`wgpu::Queue`] and
@@ -475,6 +482,7 @@ impl VulkanDevice {
             quality_level: 0,
             usage_flags: Some(EncoderUsageFlags::DEFAULT),
             content_flags: Some(EncoderContentFlags::DEFAULT),
+            color_description: None,
             tuning_mode: Some(EncoderTuningMode::LOW_LATENCY),
         })
     }
@@ -502,6 +510,7 @@ impl VulkanDevice {
                 - 1,
             usage_flags: Some(EncoderUsageFlags::DEFAULT),
             content_flags: Some(EncoderContentFlags::DEFAULT),
+            color_description: None,
             tuning_mode: Some(EncoderTuningMode::HIGH_QUALITY),
         })
     }
@@ -662,6 +671,7 @@ impl VulkanDevice {
             usage_flags,
             tuning_mode,
             content_flags,
+            color_description: encoder_parameters.color_description,
         })
     }
 
@@ -28,27 +28,50 @@ fn vs_main(@builtin(vertex_index) idx: u32) -> VertexOutput {
 @group(0) @binding(0) var texture: texture_2d<f32>;
 @group(1) @binding(0) var sampler_: sampler;
 
-@fragment
-fn fs_main_y(input: VertexOutput) -> @location(0) f32 {
-    let color = textureSample(texture, sampler_, input.tex_coords).rgb;
+// `KR`, `KB`, `FULL_RANGE` and `BT2020_PRIMARIES` constants are prepended by the converter
+// based on the color description of the encoded stream.
+const KG: f32 = 1.0 - KR - KB;
 
-    let conversion_weights = vec3<f32>(0.2126, 0.7152, 0.0722);
+fn sample_color(tex_coords: vec2<f32>) -> vec3<f32> {
+    let color = textureSample(texture, sampler_, tex_coords).rgb;
+    if !BT2020_PRIMARIES {
+        return color;
+    }
 
-    return clamp(dot(color, conversion_weights), 0.0, 1.0);
+    // Input is in BT.709 primaries, convert them to BT.2020 (ITU-R BT.2087)
+    let linear = pow(clamp(color, vec3(0.0), vec3(1.0)), vec3(2.4));
+    let bt2020 = vec3<f32>(
+        0.6274 * linear.r + 0.3293 * linear.g + 0.0433 * linear.b,
+        0.0691 * linear.r + 0.9195 * linear.g + 0.0114 * linear.b,
+        0.0164 * linear.r + 0.0880 * linear.g + 0.8956 * linear.b,
+    );
+    return pow(clamp(bt2020, vec3(0.0), vec3(1.0)), vec3(1.0 / 2.4));
 }
 
 @fragment
-fn fs_main_uv(input: VertexOutput) -> @location(0) vec2<f32> {
-    let color = textureSample(texture, sampler_, input.tex_coords).rgb;
-
-    let conversion_weights = mat3x2<f32>(
-        -0.1146,  0.5,
-        -0.3854, -0.4542,
-         0.5,    -0.0458,
-    );
-    let conversion_bias = vec2<f32>(0.5, 0.5);
+fn fs_main_y(input: VertexOutput) -> @location(0) f32 {
+    let color = sample_color(input.tex_coords);
 
-    return clamp(conversion_weights * color + conversion_bias, vec2(0.0, 0.0), vec2(1.0, 1.0));
+    let y = dot(color, vec3<f32>(KR, KG, KB));
+    if FULL_RANGE {
+        return clamp(y, 0.0, 1.0);
+    }
+    // (235 - 16) / 255
+    return clamp(y * 0.85882352941 + 16.0 / 255.0, 0.0, 1.0);
 }
 
+@fragment
+fn fs_main_uv(input: VertexOutput) -> @location(0) vec2<f32> {
+    let color = sample_color(input.tex_coords);
 
+    let y = dot(color, vec3<f32>(KR, KG, KB));
+    let uv = vec2<f32>(
+        (color.b - y) / (2.0 * (1.0 - KB)),
+        (color.r - y) / (2.0 * (1.0 - KR)),
+    ) + vec2(0.5, 0.5);
+    if FULL_RANGE {
+        return clamp(uv, vec2(0.0, 0.0), vec2(1.0, 1.0));
+    }
+    // (240 - 16) / 255
+    return clamp(uv * 0.87843137254 + 16.0 / 255.0, vec2(0.0, 0.0), vec2(1.0, 1.0));
+}
@@ -7,7 +7,7 @@ use session_resources::VideoSessionResources;
 use tracing::error;
 
 use crate::{
-    RawFrameData,
+    ColorDescription, RawFrameData,
     device::DecodingDevice,
     parser::{
         decoder_instructions::DecoderInstruction,
@@ -67,6 +67,7 @@ struct DecodeSubmission {
     max_num_reorder_frames: u64,
     is_idr: bool,
     pts: Option<u64>,
+    color_description: Option<ColorDescription>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -139,6 +140,7 @@ pub(crate) struct DecodeResult<T> {
     pic_order_cnt: i32,
     max_num_reorder_frames: u64,
     is_idr: bool,
+    color_description: Option<ColorDescription>,
 }
 
 impl VulkanDecoder<'_> {
@@ -151,6 +153,7 @@ impl VulkanDecoder<'_> {
             if let Some(output) = self.decode(instruction)? {
                 result.push(DecodeResult {
                     pts: output.pts,
+                    color_description: output.color_description,
                     is_idr: output.is_idr,
                     max_num_reorder_frames: output.max_num_reorder_frames,
                     pic_order_cnt: output.picture_order_cnt,
@@ -175,6 +178,7 @@ impl VulkanDecoder<'_> {
             if let Some(output) = self.decode(instruction)? {
                 result.push(DecodeResult {
                     pts: output.pts,
+                    color_description: output.color_description,
                     is_idr: output.is_idr,
                     max_num_reorder_frames: output.max_num_reorder_frames,
                     pic_order_cnt: output.picture_order_cnt,
@@ -565,6 +569,7 @@ impl VulkanDecoder<'_> {
             .ok_or(VulkanDecoderError::NoSession)?;
 
         let dimensions = sps.size()?;
+        let color_description = color_description_from_sps(sps);
 
         Ok(DecodeSubmission {
             image: target_image,
@@ -574,6 +579,7 @@ impl VulkanDecoder<'_> {
             max_num_reorder_frames: video_session_resources.parameters.max_num_reorder_frames,
             is_idr,
             pts: decode_information.pts,
+            color_description,
         })
     }
 
@@ -945,3 +951,26 @@ impl VulkanDecoder<'_> {
         Ok(dst_buffer)
     }
 }
+
+fn color_description_from_sps(sps: &SeqParameterSet) -> Option<ColorDescription> {
+    let signal_type = sps.vui_parameters.as_ref()?.video_signal_type.as_ref()?;
+    // 2 - unspecified
+    let (colour_primaries, transfer_characteristics, matrix_coefficients) = signal_type
+        .colour_description
+        .as_ref()
+        .map(|d| {
+            (
+                d.colour_primaries,
+                d.transfer_characteristics,
+                d.matrix_coefficients,
+            )
+        })
+        .unwrap_or((2, 2, 2));
+
+    Some(ColorDescription {
+        full_range: signal_type.video_full_range_flag,
+        colour_primaries,
+        transfer_characteristics,
+        matrix_coefficients,
+    })
+}
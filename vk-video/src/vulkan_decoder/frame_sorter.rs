@@ -47,12 +47,14 @@ impl<T> FrameSorter<T> {
                 result.push(Frame {
                     data: frame.frame,
                     pts: frame.pts,
+                    color_description: frame.color_description,
                 });
             }
 
             result.push(Frame {
                 data: frame.frame,
                 pts: frame.pts,
+                color_description: frame.color_description,
             });
         } else {
             self.frames.push(frame);
@@ -63,6 +65,7 @@ impl<T> FrameSorter<T> {
                 result.push(Frame {
                     data: frame.frame,
                     pts: frame.pts,
+                    color_description: frame.color_description,
                 });
             }
         }
@@ -88,6 +91,7 @@ impl<T> FrameSorter<T> {
             result.push(Frame {
                 data: frame.frame,
                 pts: frame.pts,
+                color_description: frame.color_description,
             });
         }
 
@@ -5,11 +5,11 @@ use std::{
 };
 
 use ash::vk;
-use encode_parameter_sets::{pps, sps};
+use encode_parameter_sets::{pps, sps, vui};
 use yuv_converter::Converter;
 
 use crate::{
-    EncodedOutputChunk, Frame, RawFrameData, VulkanCommonError,
+    ColorDescription, EncodedOutputChunk, Frame, RawFrameData, VulkanCommonError,
     device::{EncodingDevice, Rational},
     parameters::H264Profile,
     wrappers::{
@@ -129,11 +129,13 @@ impl VideoSessionResources<'_> {
             vk::ImageLayout::VIDEO_ENCODE_DPB_KHR,
         )?;
 
+        let sps_vui = parameters.color_description.map(vui);
         let sps = sps(
             parameters.profile,
             extent.width,
             extent.height,
             max_references,
+            sps_vui.as_ref(),
         )?;
         let pps = pps();
 
@@ -332,6 +334,8 @@ pub struct FullEncoderParameters {
     pub(crate) usage_flags: vk::VideoEncodeUsageFlagsKHR,
     pub(crate) tuning_mode: vk::VideoEncodeTuningModeKHR,
     pub(crate) content_flags: vk::VideoEncodeContentFlagsKHR,
+    /// Signaled in the SPS VUI, if [`None`] decoders assume their defaults.
+    pub(crate) color_description: Option<ColorDescription>,
 }
 
 impl VulkanEncoder<'_> {
@@ -341,6 +345,22 @@ impl VulkanEncoder<'_> {
         device: Arc<EncodingDevice>,
         parameters: FullEncoderParameters,
     ) -> Result<Self, VulkanEncoderError> {
+        let color_description = parameters
+            .color_description
+            .unwrap_or(Converter::DEFAULT_COLOR_DESCRIPTION);
+        if Converter::luma_coefficients(color_description.matrix_coefficients).is_none() {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "color_description",
+                problem: format!(
+                    "Matrix coefficients {} are not supported by the RGBA to YUV conversion.",
+                    color_description.matrix_coefficients
+                ),
+            });
+        }
+        let parameters = FullEncoderParameters {
+            color_description: Some(color_description),
+            ..parameters
+        };
         let mut enc = Self::new(device.clone(), parameters)?;
 
         let conv = Converter::new(
@@ -348,6 +368,7 @@ impl VulkanEncoder<'_> {
             parameters.width.get(),
             parameters.height.get(),
             &enc.profile_info,
+            color_description,
             enc.tracker.image_layout_tracker.clone(),
         )
         .unwrap();
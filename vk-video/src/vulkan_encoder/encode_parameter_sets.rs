@@ -1,6 +1,6 @@
 use ash::vk;
 
-use crate::parameters::H264Profile;
+use crate::{ColorDescription, parameters::H264Profile};
 
 use super::VulkanEncoderError;
 
@@ -14,6 +14,7 @@ pub(crate) fn sps(
     width: u32,
     height: u32,
     max_references: u32,
+    vui: Option<&vk::native::StdVideoH264SequenceParameterSetVui>,
 ) -> Result<vk::native::StdVideoH264SequenceParameterSet, VulkanEncoderError> {
     // separate_colour_plane_flag is 0 so the crop units are based on SubWidthC and SubHeightC for YUV420
     // with enabled frame_mbs_only_flag
@@ -27,7 +28,7 @@ pub(crate) fn sps(
     let frame_crop_right_offset = width_offset / CropUnitX;
     let frame_crop_bottom_offset = height_offset / CropUnitY;
 
-    Ok(vk::native::StdVideoH264SequenceParameterSet {
+    let mut sps = vk::native::StdVideoH264SequenceParameterSet {
         flags: vk::native::StdVideoH264SpsFlags {
             _bitfield_align_1: [0; 0],
             __bindgen_padding_0: 0,
@@ -38,7 +39,7 @@ pub(crate) fn sps(
                 0, // only for pic_order_cnt_type 1
                 0, 0, 0, // ffmpeg
                 1, // use frame cropping
-                0, 0,
+                0, 0, // vui_parameters_present_flag is set below
             ),
         },
         profile_idc: profile.to_profile_idc(),
@@ -65,8 +66,49 @@ pub(crate) fn sps(
         reserved2: 0,
         pOffsetForRefFrame: std::ptr::null(),
         pScalingLists: std::ptr::null(),
-        pSequenceParameterSetVui: std::ptr::null(), // TODO: VUI
-    })
+        pSequenceParameterSetVui: vui.map_or(std::ptr::null(), std::ptr::from_ref),
+    };
+    sps.flags
+        .set_vui_parameters_present_flag(vui.is_some() as u32);
+
+    Ok(sps)
+}
+
+/// VUI that only signals the video signal type, so decoders know how to convert
+/// the encoded samples back to RGB.
+pub(crate) fn vui(
+    color_description: ColorDescription,
+) -> vk::native::StdVideoH264SequenceParameterSetVui {
+    let mut vui = vk::native::StdVideoH264SequenceParameterSetVui {
+        flags: vk::native::StdVideoH264SpsVuiFlags {
+            _bitfield_align_1: [0; 0],
+            __bindgen_padding_0: 0,
+            _bitfield_1: vk::native::StdVideoH264SpsVuiFlags::new_bitfield_1(
+                0, 0, 0, // no aspect ratio and overscan info
+                1, 0, 1, // signal type with color description, range is set below
+                0, 0, 0, 0, 0, 0, // no chroma location, timing, bitstream restriction and HRD
+            ),
+        },
+        aspect_ratio_idc:
+            vk::native::StdVideoH264AspectRatioIdc_STD_VIDEO_H264_ASPECT_RATIO_IDC_UNSPECIFIED,
+        sar_width: 0,
+        sar_height: 0,
+        video_format: 5, // unspecified
+        colour_primaries: color_description.colour_primaries,
+        transfer_characteristics: color_description.transfer_characteristics,
+        matrix_coefficients: color_description.matrix_coefficients,
+        num_units_in_tick: 0,
+        time_scale: 0,
+        max_num_reorder_frames: 0,
+        max_dec_frame_buffering: 0,
+        chroma_sample_loc_type_top_field: 0,
+        chroma_sample_loc_type_bottom_field: 0,
+        reserved1: 0,
+        pHrdParameters: std::ptr::null(),
+    };
+    vui.flags
+        .set_video_full_range_flag(color_description.full_range as u32);
+    vui
 }
 
 pub(crate) fn pps() -> vk::native::StdVideoH264PictureParameterSet {
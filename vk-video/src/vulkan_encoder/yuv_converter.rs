@@ -4,7 +4,7 @@ use ash::vk;
 use wgpu::hal::{CommandEncoder, Device, Queue, vulkan::Api as VkApi};
 
 use crate::{
-    VulkanCommonError, VulkanDevice,
+    ColorDescription, VulkanCommonError, VulkanDevice,
     device::EncodingDevice,
     vulkan_encoder::EncoderTracker,
     wrappers::{
@@ -33,11 +33,31 @@ pub(crate) struct Converter {
 }
 
 impl Converter {
+    /// Used when the encoder parameters don't specify a color description.
+    pub(crate) const DEFAULT_COLOR_DESCRIPTION: ColorDescription = ColorDescription {
+        full_range: true,
+        colour_primaries: 1,
+        transfer_characteristics: 1,
+        matrix_coefficients: 1,
+    };
+
+    /// Returns `Kr` and `Kb` coefficients for H.273 `matrix_coefficients` code points
+    /// supported by the converter.
+    pub(crate) fn luma_coefficients(matrix_coefficients: u8) -> Option<(f32, f32)> {
+        match matrix_coefficients {
+            1 => Some((0.2126, 0.0722)),
+            5 | 6 => Some((0.299, 0.114)),
+            9 => Some((0.2627, 0.0593)),
+            _ => None,
+        }
+    }
+
     pub(crate) fn new(
         device: Arc<EncodingDevice>,
         width: u32,
         height: u32,
         profile: &H264EncodeProfileInfo,
+        color_description: ColorDescription,
         image_tracker: Arc<Mutex<ImageLayoutTracker>>,
     ) -> Result<Self, YuvConverterError> {
         let mut fence = unsafe {
@@ -121,9 +141,7 @@ impl Converter {
 
         let image = Arc::new(image);
 
-        let module =
-            wgpu::naga::front::wgsl::parse_str(include_str!("../shaders/rgba_to_yuv.wgsl"))
-                .unwrap();
+        let module = wgpu::naga::front::wgsl::parse_str(&shader_source(color_description)).unwrap();
         let mut validator = wgpu::naga::valid::Validator::new(
             wgpu::naga::valid::ValidationFlags::all(),
             wgpu::naga::valid::Capabilities::all(),
@@ -794,3 +812,16 @@ impl CommonState {
         })
     }
 }
+
+fn shader_source(color_description: ColorDescription) -> String {
+    // Unsupported matrices are rejected before the converter is created.
+    let (kr, kb) = Converter::luma_coefficients(color_description.matrix_coefficients)
+        .unwrap_or((0.2126, 0.0722));
+    let bt2020_primaries = color_description.colour_primaries == 9;
+
+    format!(
+        "const KR: f32 = {kr:?};\nconst KB: f32 = {kb:?};\nconst FULL_RANGE: bool = {};\nconst BT2020_PRIMARIES: bool = {bt2020_primaries};\n{}",
+        color_description.full_range,
+        include_str!("../shaders/rgba_to_yuv.wgsl")
+    )
+}
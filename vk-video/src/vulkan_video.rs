@@ -156,6 +156,18 @@ pub struct EncodedOutputChunk<T> {
 pub struct Frame<T> {
     pub data: T,
     pub pts: Option<u64>,
+    /// Color description signaled in the VUI of the stream. Ignored by the encoder.
+    pub color_description: Option<ColorDescription>,
+}
+
+/// Video signal type from the H264 VUI. Code points are defined in ITU-T H.273,
+/// value `2` means unspecified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorDescription {
+    pub full_range: bool,
+    pub colour_primaries: u8,
+    pub transfer_characteristics: u8,
+    pub matrix_coefficients: u8,
 }
 
 pub struct RawFrameData {